@@ -1,6 +1,6 @@
 use crate::command_executor::{Command, CommandContext, CommandMetadata, CommandParams, CommandGroup, CommandGroupMetadata, DynamicCompletionType};
 use crate::commands::*;
-use crate::utils::table::print_list_table;
+use crate::utils::table::{print_list_table, print_table};
 use indy::ErrorCode;
 use crate::libindy::wallet::Wallet;
 
@@ -477,6 +477,54 @@ pub mod export_command {
     }
 }
 
+pub mod verify_command {
+    use super::*;
+
+    command!(CommandMetadata::build("verify", "Check integrity of opened wallet records")
+                .add_optional_param("repair", "Remove records that can't be decrypted and tags that don't belong to any record (false by default)")
+                .add_example("wallet verify")
+                .add_example("wallet verify repair=true")
+                .finalize()
+    );
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let (wallet_handle, wallet_name) = ensure_opened_wallet(&ctx)?;
+
+        let repair = get_opt_bool_param("repair", params).map_err(error_err!())?.unwrap_or(false);
+
+        trace!("Wallet::verify_wallet try: wallet_name {}, repair {}", wallet_name, repair);
+
+        let res = Wallet::verify_wallet(wallet_handle, repair);
+
+        trace!("Wallet::verify_wallet return: {:?}", res);
+
+        let res = match res {
+            Ok(report) => {
+                let report: JSONValue = serde_json::from_str(&report)
+                    .map_err(|_| println_err!("Wrong data has been received"))?;
+
+                println_succ!("Wallet \"{}\" has been verified", wallet_name);
+
+                print_table(&report,
+                            &[("recordsCount", "Records"),
+                                ("unreadableRecords", "Unreadable records"),
+                                ("orphanedTags", "Orphaned tags"),
+                                ("repaired", "Repaired")]);
+                Ok(())
+            }
+            Err(err) => {
+                handle_indy_error(err, None, None, Some(wallet_name.as_ref()));
+                Err(())
+            }
+        };
+
+        trace!("execute << {:?}", res);
+        res
+    }
+}
+
 pub mod import_command {
     use super::*;
 
@@ -1166,6 +1214,44 @@ pub mod tests {
         }
     }
 
+    mod verify {
+        use super::*;
+
+        #[test]
+        pub fn verify_works() {
+            let ctx = setup_with_wallet();
+            {
+                let cmd = verify_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn verify_works_for_repair() {
+            let ctx = setup_with_wallet();
+            {
+                let cmd = verify_command::new();
+                let mut params = CommandParams::new();
+                params.insert("repair", "true".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn verify_works_for_no_opened_wallet() {
+            let ctx = setup();
+            {
+                let cmd = verify_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down();
+        }
+    }
+
     mod import {
         use super::*;
         use super::did::tests::{new_did, use_did, SEED_MY1, DID_MY1};
@@ -26,6 +26,10 @@ impl Wallet {
         wallet::export_wallet(wallet_handle, export_config_json).wait()
     }
 
+    pub fn verify_wallet(wallet_handle: WalletHandle, repair: bool) -> Result<String, IndyError> {
+        wallet::verify_wallet(wallet_handle, repair).wait()
+    }
+
     pub fn import_wallet(config: &str, credentials: &str, import_config_json: &str) -> Result<(), IndyError> {
         wallet::import_wallet(config, credentials, import_config_json).wait()
     }
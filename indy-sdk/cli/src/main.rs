@@ -154,6 +154,7 @@ fn build_executor() -> CommandExecutor {
         .add_command(wallet::delete_command::new())
         .add_command(wallet::detach_command::new())
         .add_command(wallet::export_command::new())
+        .add_command(wallet::verify_command::new())
         .add_command(wallet::import_command::new())
         .finalize_group()
         .add_group(ledger::group::new())
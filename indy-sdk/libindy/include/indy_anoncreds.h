@@ -88,7 +88,44 @@ extern "C" {
                                                                            const char*   cred_revoc_id,
                                                                            const char*   revoc_reg_delta_json)
                                                       );
+
+    extern indy_error_t indy_issuer_create_credential_with_rollover(indy_handle_t command_handle,
+                                                                    indy_handle_t wallet_handle,
+                                                                    const char *  cred_offer_json,
+                                                                    const char *  cred_req_json,
+                                                                    const char *  cred_values_json,
+                                                                    const char *  rev_reg_id,
+                                                                    indy_handle_t blob_storage_reader_handle,
+
+                                                                    void           (*cb)(indy_handle_t command_handle_,
+                                                                                         indy_error_t  err,
+                                                                                         const char*   cred_json,
+                                                                                         const char*   cred_revoc_id,
+                                                                                         const char*   revoc_reg_delta_json,
+                                                                                         const char*   new_revoc_regs_json)
+                                                                    );
+
+    extern indy_error_t indy_issuer_create_credentials_batch(indy_handle_t command_handle,
+                                                             indy_handle_t wallet_handle,
+                                                             const char *  cred_requests_json,
+                                                             indy_handle_t blob_storage_reader_handle,
+
+                                                             void           (*cb)(indy_handle_t command_handle_,
+                                                                                  indy_error_t  err,
+                                                                                  const char*   creds_json,
+                                                                                  const char*   revoc_reg_deltas_json,
+                                                                                  const char*   new_revoc_regs_json)
+                                                             );
     
+    extern indy_error_t indy_issuer_get_next_revoc_reg(indy_handle_t command_handle,
+                                                       indy_handle_t wallet_handle,
+                                                       const char *  rev_reg_id,
+
+                                                       void           (*cb)(indy_handle_t command_handle_,
+                                                                            indy_error_t  err,
+                                                                            const char*   next_revoc_reg_json)
+                                                       );
+
     extern indy_error_t indy_issuer_revoke_credential(indy_handle_t command_handle,
                                                       indy_handle_t wallet_handle,
                                                       indy_handle_t blob_storage_reader_handle,
@@ -100,6 +137,18 @@ extern "C" {
                                                                            const char*   revoc_reg_delta_json)
                                                       );
 
+    extern indy_error_t indy_issuer_revoke_credentials(indy_handle_t command_handle,
+                                                       indy_handle_t wallet_handle,
+                                                       indy_handle_t blob_storage_reader_handle,
+                                                       const char *  rev_reg_id,
+                                                       const char *  revoked_cred_revoc_ids_json,
+                                                       const char *  recovered_cred_revoc_ids_json,
+
+                                                       void           (*cb)(indy_handle_t command_handle_,
+                                                                            indy_error_t  err,
+                                                                            const char*   revoc_reg_delta_json)
+                                                       );
+
 /*    extern indy_error_t indy_issuer_recover_credential(indy_handle_t command_handle,
                                                        indy_handle_t wallet_handle,
                                                        indy_handle_t blob_storage_reader_handle,
@@ -121,6 +170,41 @@ extern "C" {
                                                                                           const char*   merged_rev_reg_delta)
                                                                      );
 
+    extern indy_error_t indy_issuer_set_issued_credential_metadata(indy_handle_t command_handle,
+                                                                   indy_handle_t wallet_handle,
+                                                                   const char *  issued_cred_id,
+                                                                   const char *  metadata_json,
+
+                                                                   void           (*cb)(indy_handle_t command_handle_,
+                                                                                        indy_error_t  err)
+                                                                   );
+
+    extern indy_error_t indy_issuer_search_issued_credentials(indy_handle_t command_handle,
+                                                              indy_handle_t wallet_handle,
+                                                              const char *  query_json,
+
+                                                              void           (*cb)(indy_handle_t command_handle_,
+                                                                                   indy_error_t  err,
+                                                                                   indy_handle_t search_handle,
+                                                                                   indy_u32_t    total_count)
+                                                              );
+
+    extern indy_error_t indy_issuer_fetch_issued_credentials(indy_handle_t command_handle,
+                                                             indy_handle_t search_handle,
+                                                             indy_u32_t    count,
+
+                                                             void           (*cb)(indy_handle_t command_handle_,
+                                                                                  indy_error_t  err,
+                                                                                  const char*   issued_credentials_json)
+                                                             );
+
+    extern indy_error_t indy_issuer_close_issued_credentials_search(indy_handle_t command_handle,
+                                                                    indy_handle_t search_handle,
+
+                                                                    void           (*cb)(indy_handle_t command_handle_,
+                                                                                         indy_error_t  err)
+                                                                    );
+
     extern indy_error_t indy_prover_create_master_secret(indy_handle_t command_handle,
                                                          indy_handle_t wallet_handle,
                                                          const char *  master_secret_id,
@@ -274,6 +358,19 @@ extern "C" {
                                                                         indy_bool_t   valid )
                                                    );
 
+    extern indy_error_t indy_verifier_verify_proof_with_report(indy_handle_t command_handle,
+                                                               const char *  proof_request_json,
+                                                               const char *  proof_json,
+                                                               const char *  schemas_json,
+                                                               const char *  credential_defs_jsons,
+                                                               const char *  rev_reg_defs_json,
+                                                               const char *  rev_regs_json,
+
+                                                               void           (*cb)(indy_handle_t command_handle_,
+                                                                                    indy_error_t  err,
+                                                                                    const char*   report_json)
+                                                               );
+
 
     extern indy_error_t indy_create_revocation_state(indy_handle_t command_handle,
                                                      indy_handle_t blob_storage_reader_handle,
@@ -7,6 +7,74 @@ extern "C" {
 
 
 
+    /// Registers external key manager implementation.
+    ///
+    /// It allows library user to keep private keys out of the wallet (for example in HSM or KMS).
+    /// Keys created with "key_manager" field of indy_create_key key_json are stored in the wallet
+    /// without private part and all operations with them (indy_crypto_sign, indy_sign_request,
+    /// indy_crypto_auth_crypt, indy_crypto_anon_decrypt, indy_pack_message, indy_unpack_message and others)
+    /// are delegated to the key manager.
+    ///
+    /// Key manager interface doesn't provide X25519 key agreement, so key manager keys can't be used
+    /// as the sender key of "ecdh" profile of indy_pack_message_with_options (ECDH-1PU) and
+    /// can't unpack messages packed with "ecdh" profile. Such calls fail with UnknownCryptoTypeError.
+    /// Use "legacy" profile for these keys. Sessions (indy_crypto_session_start) only need signatures,
+    /// so they work with key manager keys.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// name: Key manager name.
+    /// create_key: KeyManager create key operation handler
+    /// sign: KeyManager sign operation handler
+    /// encrypt: KeyManager authenticated encryption operation handler
+    /// decrypt: KeyManager authenticated and anonymous decryption operation handler
+    /// free_buffer: KeyManager handler that allows to de-allocate buffers allocated in key manager code
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+
+    extern indy_error_t indy_register_key_manager(indy_handle_t  command_handle,
+                                                  const char*    name,
+                                                  indy_error_t (*createKeyFn)(const char* key_config,
+                                                                              const char** verkey_p,
+                                                                              indy_handle_t* buffer_handle_p),
+
+                                                  indy_error_t (*signFn)(const char* verkey,
+                                                                         const indy_u8_t* message_raw,
+                                                                         size_t message_len,
+                                                                         const indy_u8_t** signature_raw_p,
+                                                                         size_t* signature_len_p,
+                                                                         indy_handle_t* buffer_handle_p),
+
+                                                  indy_error_t (*encryptFn)(const char* verkey,
+                                                                            const char* their_vk,
+                                                                            const indy_u8_t* message_raw,
+                                                                            size_t message_len,
+                                                                            const indy_u8_t* nonce_raw,
+                                                                            size_t nonce_len,
+                                                                            const indy_u8_t** encrypted_raw_p,
+                                                                            size_t* encrypted_len_p,
+                                                                            indy_handle_t* buffer_handle_p),
+
+                                                  indy_error_t (*decryptFn)(const char* verkey,
+                                                                            const char* their_vk,
+                                                                            const indy_u8_t* message_raw,
+                                                                            size_t message_len,
+                                                                            const indy_u8_t* nonce_raw,
+                                                                            size_t nonce_len,
+                                                                            const indy_u8_t** decrypted_raw_p,
+                                                                            size_t* decrypted_len_p,
+                                                                            indy_handle_t* buffer_handle_p),
+
+                                                  indy_error_t (*freeBufferFn)(indy_handle_t buffer_handle),
+
+                                                  void         (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                  );
+
     /// Creates keys pair and stores in the wallet.
     ///
     /// #Params
@@ -16,7 +84,10 @@ extern "C" {
     /// {
     ///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
     ///                                Can be UTF-8, base64 or hex string.
-    ///     "crypto_type": string, // Optional (if not set then ed25519 curve is used); Currently only 'ed25519' value is supported for this field.
+    ///     "crypto_type": string, // Optional (if not set then ed25519 curve is used); Supported values: 'ed25519', 'secp256k1', 'secp256r1'.
+    ///                            // secp256k1 and secp256r1 keys can be used only for signing.
+    ///     "key_manager": string, // Optional name of key manager registered with indy_register_key_manager that
+    ///                            // creates and holds the private key (if not set then the key is kept in the wallet).
     /// }
     /// cb: Callback that takes command result as parameter.
     ///
@@ -165,6 +236,121 @@ extern "C" {
                                                                 indy_bool_t   valid )
                                           );
 
+    /// Verify a set of signatures of a message against M-of-N threshold policy.
+    ///
+    /// The message is valid if at least "threshold" of the policy "signers" have valid signatures.
+    /// Signatures made by keys that aren't in the policy aren't counted.
+    ///
+    /// Note to use DID keys with this function you can call indy_key_for_did to get key id (verkey)
+    /// for specific DID.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// message_raw: a pointer to first byte of message that has been signed
+    /// message_len: a message length
+    /// signatures_json: base58 encoded signatures by verkeys of the signers:
+    ///   {
+    ///       "<verkey>": string, // signature
+    ///   }
+    /// policy_json: threshold policy:
+    ///   {
+    ///       "threshold": int, // minimal number of valid signatures (M)
+    ///       "signers": [string], // verkeys of the allowed signers (N)
+    ///       "serialization": (optional) string, // how the message was signed:
+    ///           "raw" - signatures are made over the message bytes as is (default)
+    ///           "request" - message is a ledger request json. Signatures are made over its serialized form
+    ///                       as in indy_sign_request and indy_multi_sign_request
+    ///   }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// valid: true - if the policy is satisfied, false - otherwise
+    ///
+    /// #Errors
+    /// Common*
+    /// Crypto*
+    extern indy_error_t indy_crypto_verify_multi(indy_handle_t      command_handle,
+                                                 const indy_u8_t *  message_raw,
+                                                 indy_u32_t         message_len,
+                                                 const char *       signatures_json,
+                                                 const char *       policy_json,
+
+                                                 void           (*cb)(indy_handle_t command_handle_,
+                                                                      indy_error_t  err,
+                                                                      indy_bool_t   valid )
+                                                );
+
+    /// Signs a payload with a key and returns JSON Web Signature (RFC 7515).
+    ///
+    /// Signature algorithm is chosen by the key crypto type: EdDSA for ed25519, ES256K for secp256k1
+    /// and ES256 for secp256r1. Protected header contains "alg" and "kid" of the signer.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// signer_vk: id (verkey) of signer. The key must be created by calling indy_create_key or indy_create_and_store_my_did
+    /// payload_raw: a pointer to first byte of payload to be signed
+    /// payload_len: a payload length
+    /// options_json: (optional) signing options:
+    ///     {
+    ///         "serialization": (optional) "compact" (default) or "json" (General JWS JSON Serialization),
+    ///         "detached": (optional) if true payload is omitted from the result (RFC 7515 Appendix F). false by default,
+    ///         "kid": (optional) "verkey" (default) - signer verkey is used as kid,
+    ///                           "did:key" - did:key URL of signer key is used as kid,
+    ///         "protected": (optional) additional protected header parameters. "alg", "kid", "crit" and "b64" can't be set.
+    ///     }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// jws: JWS in requested serialization
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+
+    extern indy_error_t indy_crypto_sign_jws(indy_handle_t      command_handle,
+                                             indy_handle_t      wallet_handle,
+                                             const char *       signer_vk,
+                                             const indy_u8_t *  payload_raw,
+                                             indy_u32_t         payload_len,
+                                             const char *       options_json,
+
+                                             void           (*cb)(indy_handle_t command_handle_,
+                                                                  indy_error_t  err,
+                                                                  const char *  jws)
+                                            );
+
+    /// Verifies JSON Web Signature (RFC 7515) created with EdDSA, ES256K or ES256 algorithm.
+    ///
+    /// Compact, General JSON and Flattened JSON serializations are supported.
+    /// Signer key is resolved from "kid" header parameter that must contain verkey or did:key.
+    /// For JSON serialization with several signatures all of them must be valid.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// jws: JWS to be verified
+    /// payload_raw: (optional) a pointer to first byte of detached payload. Must be passed only if JWS doesn't contain payload.
+    /// payload_len: a detached payload length
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// valid: true - if signatures are valid, false - otherwise
+    ///
+    /// #Errors
+    /// Common*
+    /// Crypto*
+
+    extern indy_error_t indy_crypto_verify_jws(indy_handle_t      command_handle,
+                                               const char *       jws,
+                                               const indy_u8_t *  payload_raw,
+                                               indy_u32_t         payload_len,
+
+                                               void           (*cb)(indy_handle_t command_handle_,
+                                                                    indy_error_t  err,
+                                                                    indy_bool_t   valid )
+                                              );
+
     /// **** THIS FUNCTION WILL BE DEPRECATED USE indy_pack_message() INSTEAD ****
     /// Encrypt a message by authenticated-encryption scheme.
     ///
@@ -323,6 +509,114 @@ extern "C" {
                                                                       indy_u32_t        decrypted_msg_len)
                                                  );
 
+    /// Starts an authenticated session with forward secrecy between my key and their DID (Experimental).
+    ///
+    /// Session is established by the exchange of two handshake messages:
+    ///  1. initiator calls this function without their_handshake_json and sends the returned handshake to the responder
+    ///  2. responder calls this function with the handshake of the initiator and sends the returned handshake back
+    ///  3. initiator calls this function with the handshake of the responder to complete the session
+    /// Handshakes carry X25519 ephemeral keys signed by the keys of the parties. Session keys are derived from
+    /// the ephemeral keys only, and every message is encrypted with a new key ratcheted from the previous one,
+    /// so the compromise of the wallet keys or of the current session state doesn't reveal the previous messages.
+    /// Session state is stored in the wallet.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// my_vk: id (verkey) of my key. The key must be created by calling indy_create_key or indy_create_and_store_my_did
+    /// their_did: DID of the other party. Its verkey must be known (stored by indy_store_their_did) unless it is did:key or did:peer
+    /// their_handshake_json: (optional) handshake received from the other party:
+    ///   {
+    ///     "session_id": string,
+    ///     "sender_vk": string,
+    ///     "ephemeral_key": string,
+    ///     "signature": string,
+    ///   }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// session_id: id of the session to use with indy_crypto_session_encrypt
+    /// handshake_json: handshake to send to the other party. Null when the session is completed by the initiator.
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+
+    extern indy_error_t indy_crypto_session_start(indy_handle_t      command_handle,
+                                                  indy_handle_t      wallet_handle,
+                                                  const char *       my_vk,
+                                                  const char *       their_did,
+                                                  const char *       their_handshake_json,
+
+                                                  void           (*cb)(indy_handle_t     command_handle_,
+                                                                       indy_error_t      err,
+                                                                       const char *      session_id,
+                                                                       const char *      handshake_json)
+                                                  );
+
+    /// Encrypts a message by the next key of the session (Experimental).
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// session_id: id of the session established by indy_crypto_session_start
+    /// msg_data: a pointer to first byte of message that to be encrypted
+    /// msg_len: a message length
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// encrypted message as a pointer to array of bytes (json of the session message).
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+
+    extern indy_error_t indy_crypto_session_encrypt(indy_handle_t      command_handle,
+                                                    indy_handle_t      wallet_handle,
+                                                    const char *       session_id,
+                                                    const indy_u8_t *  msg_data,
+                                                    indy_u32_t         msg_len,
+
+                                                    void           (*cb)(indy_handle_t     command_handle_,
+                                                                         indy_error_t      err,
+                                                                         const indy_u8_t * encrypted_msg,
+                                                                         indy_u32_t        encrypted_len)
+                                                    );
+
+    /// Decrypts a message encrypted by indy_crypto_session_encrypt of the other party (Experimental).
+    ///
+    /// Messages can be decrypted out of order. Keys of up to 100 skipped messages are kept in the session.
+    /// Every message can be decrypted only once.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// encrypted_msg: a pointer to first byte of message that to be decrypted
+    /// encrypted_len: a message length
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// decrypted message as a pointer to an array of bytes
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+
+    extern indy_error_t indy_crypto_session_decrypt(indy_handle_t      command_handle,
+                                                    indy_handle_t      wallet_handle,
+                                                    const indy_u8_t*   encrypted_msg,
+                                                    indy_u32_t         encrypted_len,
+
+                                                    void           (*cb)(indy_handle_t     command_handle_,
+                                                                         indy_error_t      err,
+                                                                         const indy_u8_t*  decrypted_msg_raw,
+                                                                         indy_u32_t        decrypted_msg_len)
+                                                    );
+
+
 
     /// Packs a message by encrypting the message and serializes it in a JWE-like format (Experimental)
     ///
@@ -404,6 +698,81 @@ extern "C" {
                                           );
 
 
+    /// Packs a message the same way as indy_pack_message but allows to choose envelope profile (Experimental)
+    ///
+    /// Note to use DID keys with this function you can call indy_key_for_did to get key id (verkey)
+    /// for specific DID.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// message: a pointer to the first byte of the message to be packed
+    /// message_len: the length of the message
+    /// receivers: a string in the format of a json list which will contain the list of receiver's keys
+    ///                the message is being encrypted for.
+    ///                Example:
+    ///                "[<receiver edge_agent_1 verkey>, <receiver edge_agent_2 verkey>]"
+    /// sender: the sender's verkey as a string When null pointer is used in this parameter, anoncrypt is used
+    /// options_json: (optional) packing options:
+    ///     {
+    ///         "profile": string, (optional) envelope profile:
+    ///             "legacy" (default) - Authcrypt/Anoncrypt envelope produced by indy_pack_message,
+    ///             "ecdh" - JWE with X25519 key agreement (DIDComm v2 style):
+    ///                 ECDH-1PU+A256KW is used if sender is set and ECDH-ES+A256KW otherwise.
+    ///                 Note that sender verkey is passed in plain "skid" header.
+    ///         "enc": string, (optional) content encryption for "ecdh" profile: "A256GCM" (default) or "XC20P"
+    ///     }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// a JWE described in indy_pack_message for "legacy" profile.
+    /// a JWE using JSON serialization for "ecdh" profile:
+    /// {
+    ///     "protected": b64URLencode({
+    ///         "typ": "application/didcomm-encrypted+json",
+    ///         "alg": "ECDH-1PU+A256KW" or "ECDH-ES+A256KW",
+    ///         "enc": "A256GCM" or "XC20P",
+    ///         "epk": {"kty": "OKP", "crv": "X25519", "x": b64URLencode(ephemeral_public_key)},
+    ///         "skid": sender_verkey, (ECDH-1PU only)
+    ///         "apu": b64URLencode(sender_verkey), (ECDH-1PU only)
+    ///         "apv": b64URLencode(sha256(sorted recipient verkeys joined with ".")),
+    ///     }),
+    ///     "recipients": [
+    ///         {
+    ///             "encrypted_key": b64URLencode(A256KW(kek, cek)),
+    ///             "header": {
+    ///                 "kid": base58encode(recipient_verkey)
+    ///             }
+    ///         },
+    ///     ],
+    ///     "iv": b64URLencode(iv),
+    ///     "ciphertext": b64URLencode(encrypt_detached(message, protected_value_encoded, iv, cek)),
+    ///     "tag": b64URLencode(tag)
+    /// }
+    /// X25519 keys are derived from ed25519 keys of sender and recipients.
+    /// kek is derived with Concat KDF from ECDH(epk, recipient_key) for ECDH-ES and from
+    /// ECDH(epk, recipient_key) || ECDH(sender_key, recipient_key) and tag for ECDH-1PU.
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Ledger*
+    /// Crypto*
+    extern indy_error_t indy_pack_message_with_options(indy_handle_t      command_handle,
+                                                       indy_handle_t      wallet_handle,
+                                                       const indy_u8_t*   message,
+                                                       indy_u32_t         message_len,
+                                                       const char *       receiver_keys,
+                                                       const char *       sender,
+                                                       const char *       options_json,
+
+                                                       void           (*cb)(indy_handle_t     command_handle_,
+                                                                            indy_error_t      err,
+                                                                            const indy_u8_t*  jwe_msg_raw,
+                                                                            indy_u32_t        jwe_msg_len)
+                                                       );
+
+
     /// Unpacks a JWE-like formatted message outputted by indy_pack_message (Experimental)
     ///
     /// #Params
@@ -413,8 +782,10 @@ extern "C" {
     /// jwe_len: the length of the JWE message in bytes
     /// cb: Callback that takes command result as parameter.
     ///
+    /// Envelope profile (see indy_pack_message_with_options) is detected automatically.
+    ///
     /// #Returns
-    /// if authcrypt was used to pack the message returns this json structure:
+    /// if authcrypt (or ECDH-1PU) was used to pack the message returns this json structure:
     /// {
     ///     message: <decrypted message>,
     ///     sender_verkey: <sender_verkey>
@@ -423,7 +794,7 @@ extern "C" {
     ///
     /// OR
     ///
-    /// if anoncrypt was used to pack the message returns this json structure:
+    /// if anoncrypt (or ECDH-ES) was used to pack the message returns this json structure:
     /// {
     ///     message: <decrypted message>,
     ///     recipient_verkey: <recipient_verkey>
@@ -144,6 +144,44 @@ extern "C" {
                                                                        indy_error_t err)
                                                  );
 
+    /// Apply a list of record operations to the wallet.
+    /// Operations are applied in the order they are provided.
+    ///
+    /// For the default SQLite storage (and the built-in in-memory storage) the list is applied atomically:
+    /// either all operations are applied or none of them (wallet is left unchanged if any operation fails).
+    /// Storages registered with indy_register_wallet_storage get a best-effort rollback only: operations are applied
+    /// one by one and the records changed before a failed operation are restored, as the plugged storage API has no transactions.
+    /// If restoring fails too (or the process stops in the middle) the wallet can be left partially changed,
+    /// unless the plugin makes such changes transactional on its own.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context
+    /// wallet_handle: wallet handle (created by open_wallet)
+    /// operations_json: the list of operations as json:
+    ///   [
+    ///     {"op": "add", "type": <record type>, "id": <record id>, "value": <record value>, "tags": (optional) <tags json>},
+    ///     {"op": "update", "type": <record type>, "id": <record id>, "value": <new record value>},
+    ///     {"op": "add_tags", "type": <record type>, "id": <record id>, "tags": <tags json>},
+    ///     {"op": "update_tags", "type": <record type>, "id": <record id>, "tags": <tags json>},
+    ///     {"op": "delete_tags", "type": <record type>, "id": <record id>, "tag_names": [<tag name>, ...]},
+    ///     {"op": "delete", "type": <record type>, "id": <record id>},
+    ///   ]
+    ///   Format of tags json is the same as for indy_add_wallet_record.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_wallet_batch_records(indy_handle_t  command_handle,
+                                                  indy_handle_t  wallet_handle,
+                                                  const char*    operations_json,
+                                                  void           (*fn)(indy_handle_t command_handle_,
+                                                                       indy_error_t err)
+                                                 );
+
     /// Get an wallet record by id
     ///
     /// #Params
@@ -200,6 +238,13 @@ extern "C" {
     ///    retrieveType: (optional, false by default) Retrieve record type,
     ///    retrieveValue: (optional, true by default) Retrieve record value,
     ///    retrieveTags: (optional, false by default) Retrieve record tags,
+    ///    sortBy: (optional) Sort records. Supported by the default and inmemory storages, plugged ones return WalletQueryError:
+    ///      [{
+    ///        field: "insertion_order" (the order records were added in, record ids are stored encrypted and can't be sorted by)
+    ///               or unencrypted ("~" prefixed) tag name,
+    ///        direction: (optional, "asc" by default) "asc" or "desc"
+    ///      }]
+    ///      Records without the tag go first in ascending order.
     ///  }
     /// #Returns
     /// search_handle: Wallet search handle that can be used later
@@ -215,6 +260,38 @@ extern "C" {
                                                                      indy_handle_t search_handle)
                                                );
 
+    /// Count wallet records matching the query.
+    ///
+    /// Optionally records can be grouped by the value of an unencrypted tag.
+    /// Counting is done by the storage without fetching and decrypting records.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context
+    /// wallet_handle: wallet handle (created by open_wallet)
+    /// type_: allows to separate different record types collections
+    /// query_json: MongoDB style query to wallet record tags (see indy_open_wallet_search)
+    /// group_by_tag: (optional) name of unencrypted ("~" prefixed) tag to group records by.
+    ///   Records without the tag are not included in any group.
+    ///
+    /// #Returns
+    /// count json:
+    /// {
+    ///   count: <int>, // count of all records matching the query
+    ///   groups: { // present only if group_by_tag is set
+    ///     "<tag value>": <int>, // count of matching records with the tag value
+    ///   },
+    /// }
+
+    extern indy_error_t indy_count_wallet_records(indy_handle_t  command_handle,
+                                                  indy_handle_t  wallet_handle,
+                                                  const char*    type_,
+                                                  const char*    query_json,
+                                                  const char*    group_by_tag,
+                                                  void           (*fn)(indy_handle_t command_handle_,
+                                                                       indy_error_t  err,
+                                                                       const char*   count_json)
+                                                  );
+
     /// Get an wallet record by id
     ///
     /// #Params
@@ -257,6 +334,44 @@ extern "C" {
                                                                       indy_error_t  err)
                                                 );
 
+    /// Fetch a single page of wallet records.
+    ///
+    /// Unlike indy_open_wallet_search this call doesn't keep any state between calls:
+    /// it returns an opaque cursor that can be passed to the next call to continue the search.
+    ///
+    /// #Params
+    /// wallet_handle: wallet handle (created by open_wallet)
+    /// type_: allows to separate different record types collections
+    /// query_json: MongoDB style query to wallet record tags (see indy_open_wallet_search)
+    /// options_json: search options (see indy_open_wallet_search)
+    /// cursor: (optional) cursor returned by the previous call. The first page is returned if not set
+    /// limit: maximum count of records in the page. Must be greater than 0
+    ///
+    /// #Returns
+    /// wallet records page json:
+    /// {
+    ///   totalCount: <int>, // present only if retrieveTotalCount set to true
+    ///   records: [{ // present only if retrieveRecords set to true and the page isn't empty
+    ///       id: "Some id",
+    ///       type: "Some type", // present only if retrieveType set to true
+    ///       value: "Some value", // present only if retrieveValue set to true
+    ///       tags: <tags json>, // present only if retrieveTags set to true
+    ///   }],
+    ///   cursor: <str>, // cursor to fetch the next page, null if there are no more records
+    /// }
+
+    extern indy_error_t indy_search_wallet_records_page(indy_handle_t  command_handle,
+                                                        indy_handle_t  wallet_handle,
+                                                        const char*    type_,
+                                                        const char*    query_json,
+                                                        const char*    options_json,
+                                                        const char*    cursor,
+                                                        indy_u32_t     limit,
+                                                        void           (*fn)(indy_handle_t command_handle_,
+                                                                             indy_error_t  err,
+                                                                             const char*   page_json)
+                                                        );
+
 
 #ifdef __cplusplus
 }
@@ -135,6 +135,128 @@ extern "C" {
                                                   );
 
 
+    /// Replace tags of the pairwise record.
+    /// Tags make pairwise searchable by indy_open_pairwise_search (e.g. by label, relationship state or last contact time).
+    ///
+    /// #Params
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// command_handle: command handle to map callback to user context.
+    /// their_did: encoded Did
+    /// tags_json: the new tags of the pairwise as json:
+    ///   {
+    ///     "tagName1": <str>, // string tag (will be stored encrypted)
+    ///     "~tagName2": <str>, // string tag (will be stored un-encrypted)
+    ///   }
+    ///   Note that only un-encrypted tags support $gt, $gte, $lt and $lte queries.
+    ///   Values of such tags are compared as strings, so store timestamps zero padded.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_set_pairwise_tags(indy_handle_t command_handle,
+                                               indy_handle_t wallet_handle,
+                                               const char *  their_did,
+                                               const char *  tags_json,
+
+                                               void          (*cb)(indy_handle_t  command_handle_,
+                                                                   indy_error_t   err)
+                                              );
+
+    /// Search for pairwise stored in the wallet.
+    ///
+    /// Instead of immediately returning of fetched pairwise
+    /// this call returns search_handle that can be used later
+    /// to fetch records by small batches (with indy_fetch_pairwise_search_next_records).
+    ///
+    /// #Params
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// command_handle: command handle to map callback to user context.
+    /// query_json: (optional) Wql query filter for pairwise tags (see indy_set_pairwise_tags):
+    ///  {
+    ///    "label": "Alice",
+    ///    "~last_contact": { "$gte": "0001577836800" },
+    ///  }
+    ///  Returns all pairwise if not set.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// search_handle: Search handle that can be used later to fetch records by small batches (with indy_fetch_pairwise_search_next_records)
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_open_pairwise_search(indy_handle_t command_handle,
+                                                  indy_handle_t wallet_handle,
+                                                  const char *  query_json,
+
+                                                  void          (*cb)(indy_handle_t  command_handle_,
+                                                                      indy_error_t   err,
+                                                                      indy_handle_t  search_handle)
+                                                 );
+
+    /// Fetch next pairwise for pairwise search.
+    ///
+    /// #Params
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// command_handle: command handle to map callback to user context.
+    /// search_handle: pairwise search handle (created by indy_open_pairwise_search)
+    /// count: Count of pairwise to fetch
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// pairwise records json:
+    /// {
+    ///   totalCount: <int>, // count of all pairwise matching the query
+    ///   records: [{ // absent if no more pairwise left
+    ///       my_did: "my did",
+    ///       their_did: "their did",
+    ///       metadata: "Some metadata", // present only if set
+    ///       tags: <tags json>,
+    ///   }],
+    /// }
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_fetch_pairwise_search_next_records(indy_handle_t command_handle,
+                                                                indy_handle_t wallet_handle,
+                                                                indy_handle_t search_handle,
+                                                                indy_u32_t    count,
+
+                                                                void          (*cb)(indy_handle_t  command_handle_,
+                                                                                    indy_error_t   err,
+                                                                                    const char*    records_json)
+                                                               );
+
+    /// Close pairwise search (make search handle invalid)
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// search_handle: pairwise search handle (created by indy_open_pairwise_search)
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_close_pairwise_search(indy_handle_t command_handle,
+                                                   indy_handle_t search_handle,
+
+                                                   void          (*cb)(indy_handle_t  command_handle_,
+                                                                       indy_error_t   err)
+                                                  );
+
+
 #ifdef __cplusplus
 }
 #endif
@@ -1,6 +1,7 @@
 #ifndef __indy__types__included__
 #define __indy__types__included__
 
+#include <stddef.h>
 #include <stdint.h>
 #include <stdbool.h>
 
@@ -153,6 +153,7 @@ extern "C" {
     ///         Configured storage uses this identifier to lookup exact wallet data placement.
     ///   "storage_type": optional<string>, Type of the wallet storage. Defaults to 'default'.
     ///                  'Default' storage type allows to store wallet data in the local file.
+    ///                  'inmemory' storage type keeps wallet data in the process memory until the wallet is deleted.
     ///                  Custom storage types can be registered with indy_register_wallet_storage call.
     ///   "storage_config": optional<object>, Storage configuration json. Storage type defines set of supported keys.
     ///                     Can be optional if storage supports default configuration.
@@ -201,6 +202,7 @@ extern "C" {
     ///             Configured storage uses this identifier to lookup exact wallet data placement.
     ///       "storage_type": optional<string>, Type of the wallet storage. Defaults to 'default'.
     ///                       'Default' storage type allows to store wallet data in the local file.
+    ///                       'inmemory' storage type keeps wallet data in the process memory until the wallet is deleted.
     ///                       Custom storage types can be registered with indy_register_wallet_storage call.
     ///       "storage_config": optional<object>, Storage configuration json. Storage type defines set of supported keys.
     ///                         Can be optional if storage supports default configuration.
@@ -260,6 +262,15 @@ extern "C" {
     ///                              ARGON2I_INT - derive secured export key (less secured but faster)
     ///                              RAW - raw export key provided (skip derivation).
     ///                                RAW keys can be generated with indy_generate_wallet_key call
+    ///     "record_types": optional<array> Export only records of listed types (all wallet records are exported by default):
+    ///       [
+    ///         {
+    ///           "type": <string>, record type (for example "Indy::Credential"),
+    ///           "query": optional<object>, WQL query to select records of the type (see indy_open_wallet_search).
+    ///                    All records of the type are exported if not set.
+    ///         },
+    ///         ...
+    ///       ]
     ///   }
     ///
     /// #Returns
@@ -275,6 +286,104 @@ extern "C" {
                                            void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
                                            );
 
+    /// Exports opened wallet reporting progress of the operation.
+    /// Export is written to temporary file "<path>.tmp" that is moved to "path" only
+    /// after successful completion, so failed or cancelled export doesn't leave partial file.
+    /// Export fails if "<path>.tmp" already exists.
+    /// Existing "path" is never replaced, except on file systems without hard links (FAT, exFAT, some network shares)
+    /// where a file created at "path" by someone else while the export is finalized can be overwritten.
+    ///
+    /// #Params:
+    /// wallet_handle: wallet handle returned by indy_open_wallet
+    /// export_config_json: JSON containing settings for input operation (see indy_export_wallet).
+    /// progress_fn: (optional) callback called after every 100 exported records and on completion
+    ///   with the number of already exported records.
+    ///   Returning of any error code except Success cancels the export.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_export_wallet_with_progress(indy_handle_t  command_handle,
+                                                         indy_handle_t  wallet_handle,
+                                                         const char*    export_config_json,
+                                                         indy_error_t   (*progress_fn)(indy_handle_t command_handle_, indy_u32_t records_count),
+                                                         void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                         );
+
+    /// Generates new encryption keys for opened wallet and re-encrypts all wallet records with them.
+    /// Records are re-encrypted in place in a single storage transaction,
+    /// so the wallet stays usable and either all records or none of them are affected.
+    /// Wallet master key isn't changed (use "rekey" field of indy_open_wallet credentials for it).
+    ///
+    /// Note: wallet searches opened before this call become invalid and have to be reopened.
+    /// Note: only 'default' storage type supports keys rotation.
+    ///
+    /// #Params
+    /// wallet_handle: wallet handle returned by indy_open_wallet
+    /// credentials: Wallet credentials json used to open the wallet
+    ///   {
+    ///       "key": string, Key or passphrase used for wallet key derivation.
+    ///                      Look to key_derivation_method param for information about supported key derivation methods.
+    ///       "storage_credentials": optional<object> Credentials for wallet storage. Storage type defines set of supported keys.
+    ///                              Can be optional if storage supports default configuration.
+    ///                              For 'default' storage type should be empty.
+    ///       "key_derivation_method": optional<string> Algorithm to use for wallet key derivation:
+    ///                             ARGON2I_MOD - derive secured wallet master key (used by default)
+    ///                             ARGON2I_INT - derive secured wallet master key (less secured but faster)
+    ///                             RAW - raw wallet key master provided (skip derivation).
+    ///                                RAW keys can be generated with indy_generate_wallet_key call
+    ///   }
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_rotate_wallet_keys(indy_handle_t  command_handle,
+                                                indy_handle_t  wallet_handle,
+                                                const char*    credentials,
+                                                void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                );
+
+    /// Checks integrity of opened wallet: every record value and tags are decrypted with the current wallet keys
+    /// and storage is checked for tags that don't belong to any record.
+    /// In repair mode unreadable records and orphaned tags are removed from the wallet.
+    ///
+    /// Note: only 'default' storage type supports orphaned tags detection.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context
+    /// wallet_handle: wallet handle returned by indy_open_wallet
+    /// repair: remove unreadable records and orphaned tags
+    /// cb: Callback that takes command result as parameter
+    ///
+    /// #Returns
+    /// Verification report json
+    ///   {
+    ///       "recordsCount": int, total number of records in the wallet
+    ///       "unreadableRecords": int, number of records that can't be decrypted with the current wallet keys
+    ///       "orphanedTags": int, number of tags that don't belong to any record,
+    ///       null if the storage can't check for orphaned tags (plugged storage)
+    ///       "repaired": bool, true if unreadable records or orphaned tags were removed
+    ///   }
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_verify_wallet(indy_handle_t  command_handle,
+                                           indy_handle_t  wallet_handle,
+                                           indy_bool_t    repair,
+                                           void           (*fn)(indy_handle_t command_handle_, indy_error_t err, const char* report_json)
+                                           );
+
+
 
     /// Creates a new secure wallet and then imports its content
     /// according to fields provided in import_config
@@ -287,6 +396,7 @@ extern "C" {
     ///         Configured storage uses this identifier to lookup exact wallet data placement.
     ///   "storage_type": optional<string>, Type of the wallet storage. Defaults to 'default'.
     ///                  'Default' storage type allows to store wallet data in the local file.
+    ///                  'inmemory' storage type keeps wallet data in the process memory until the wallet is deleted.
     ///                  Custom storage types can be registered with indy_register_wallet_storage call.
     ///   "storage_config": optional<object>, Storage configuration json. Storage type defines set of supported keys.
     ///                     Can be optional if storage supports default configuration.
@@ -314,8 +424,14 @@ extern "C" {
     /// {
     ///   "path": <string>, path of the file that contains exported wallet content
     ///   "key": <string>, key used for export of the wallet
+    ///   "resume": optional<bool>, if true and the wallet already exists, continue interrupted import into it:
+    ///             records already present in the wallet are skipped. False by default.
     /// }
     ///
+    /// If import is cancelled or fails after some records were imported the created wallet is kept
+    /// with already imported records, so import can be resumed by calling this function again with "resume": true.
+    /// If import fails before any record is imported (for example, on wrong export key) the created wallet is deleted.
+    ///
     /// #Returns
     /// Error code
     ///
@@ -330,6 +446,33 @@ extern "C" {
                                            void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
                                            );
 
+    /// Creates a new secure wallet and then imports its content reporting progress of the operation.
+    /// If "resume" field of import_config_json is true and the wallet already exists
+    /// import continues into it skipping already imported records.
+    ///
+    /// #Params
+    /// config: Wallet configuration json (see indy_import_wallet).
+    /// credentials: Wallet credentials json (see indy_import_wallet).
+    /// import_config_json: Import settings json (see indy_import_wallet).
+    /// progress_fn: (optional) callback called after every 100 imported records and on completion
+    ///   with the number of already imported records.
+    ///   Returning of any error code except Success cancels the import.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_import_wallet_with_progress(indy_handle_t  command_handle,
+                                                         const char*    config,
+                                                         const char*    credentials,
+                                                         const char*    import_config_json,
+                                                         indy_error_t   (*progress_fn)(indy_handle_t command_handle_, indy_u32_t records_count),
+                                                         void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                         );
+
     /// Closes opened wallet and frees allocated resources.
     ///
     /// #Params
@@ -356,6 +499,7 @@ extern "C" {
     ///         Configured storage uses this identifier to lookup exact wallet data placement.
     ///   "storage_type": optional<string>, Type of the wallet storage. Defaults to 'default'.
     ///                  'Default' storage type allows to store wallet data in the local file.
+    ///                  'inmemory' storage type keeps wallet data in the process memory until the wallet is deleted.
     ///                  Custom storage types can be registered with indy_register_wallet_storage call.
     ///   "storage_config": optional<object>, Storage configuration json. Storage type defines set of supported keys.
     ///                     Can be optional if storage supports default configuration.
@@ -393,6 +537,34 @@ extern "C" {
                                            void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
                                           );
 
+    /// Lists schema migrations that will be applied to the wallet storage on the next opening.
+    /// Wallet storage isn't modified and the wallet isn't opened by this call.
+    ///
+    /// Note: only 'default' storage type has versioned schema. Other storage types return empty list.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context
+    /// config: Wallet configuration json (see indy_open_wallet for details).
+    /// credentials: Wallet credentials json (see indy_open_wallet for details).
+    /// cb: Callback that takes command result as parameter
+    ///
+    /// #Returns
+    /// Pending migrations json (empty if wallet schema is up to date)
+    ///   [{
+    ///       "version": int, schema version the migration brings storage to
+    ///       "description": string, migration description
+    ///   }]
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_check_wallet_migrations(indy_handle_t  command_handle,
+                                                     const char*    config,
+                                                     const char*    credentials,
+                                                     void           (*fn)(indy_handle_t command_handle_, indy_error_t err, const char* migrations_json)
+                                                    );
+
     /// Generate wallet master key.
     /// Returned key is compatible with "RAW" key derivation method.
     /// It allows to avoid expensive key derivation for use cases when wallet keys can be stored in a secure enclave.
@@ -38,7 +38,20 @@ pub struct ExportConfig {
     pub key: String,
     pub path: String,
     #[serde(default = "default_key_derivation_method")]
-    pub key_derivation_method: KeyDerivationMethod
+    pub key_derivation_method: KeyDerivationMethod,
+    // Used by import only: continue interrupted import into already existing wallet
+    #[serde(default)]
+    pub resume: bool,
+    // Used by export only: export only records of listed types (all records if not set)
+    pub record_types: Option<Vec<ExportRecordType>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExportRecordType {
+    #[serde(rename = "type")]
+    pub type_: String,
+    // WQL query to select records of the type (all records of the type if not set)
+    pub query: Option<Value>,
 }
 
 #[derive(Debug, Deserialize)]
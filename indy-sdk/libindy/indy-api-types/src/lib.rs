@@ -586,4 +586,87 @@ pub mod wallet {
     pub type WalletFreeSearch = extern fn(storage_handle: StorageHandle,
                                           search_handle: i32) -> ErrorCode;
 
-}
\ No newline at end of file
+}
+
+pub mod key_manager {
+    use super::*;
+    use libc::c_char;
+
+    /// Create a new key held by the key manager
+    ///
+    /// #Params
+    /// key_config: key config json
+    ///   {
+    ///     "seed": optional<string>, seed that allows deterministic key creation
+    ///     "crypto_type": optional<string>, crypto type of the key ("ed25519" if not set)
+    ///   }
+    /// verkey_p: pointer to store verkey of created key (base58 with ":<crypto_type>" suffix for not "ed25519" keys)
+    ///           Note that pointer lifetime is the same as buffer lifetime (until free_buffer called)
+    /// buffer_handle_p: pointer to store handle of allocated buffer
+    pub type KeyManagerCreateKey = extern fn(key_config: *const c_char,
+                                             verkey_p: *mut *const c_char,
+                                             buffer_handle_p: *mut IndyHandle) -> ErrorCode;
+
+    /// Sign message with the key held by the key manager
+    ///
+    /// #Params
+    /// verkey: verkey of the key (See create_key handler)
+    /// message_raw, message_len: message to sign
+    /// signature_raw_p, signature_len_p: pointers to store signature
+    ///           Note that pointer lifetime is the same as buffer lifetime (until free_buffer called)
+    /// buffer_handle_p: pointer to store handle of allocated buffer
+    pub type KeyManagerSign = extern fn(verkey: *const c_char,
+                                        message_raw: *const u8,
+                                        message_len: usize,
+                                        signature_raw_p: *mut *const u8,
+                                        signature_len_p: *mut usize,
+                                        buffer_handle_p: *mut IndyHandle) -> ErrorCode;
+
+    /// Encrypt message for their_vk with authenticated encryption (crypto_box)
+    /// using the key held by the key manager as sender key
+    ///
+    /// #Params
+    /// verkey: verkey of the sender key (See create_key handler)
+    /// their_vk: verkey of the recipient
+    /// message_raw, message_len: message to encrypt
+    /// nonce_raw, nonce_len: nonce to use for encryption
+    /// encrypted_raw_p, encrypted_len_p: pointers to store encrypted message
+    ///           Note that pointer lifetime is the same as buffer lifetime (until free_buffer called)
+    /// buffer_handle_p: pointer to store handle of allocated buffer
+    pub type KeyManagerEncrypt = extern fn(verkey: *const c_char,
+                                           their_vk: *const c_char,
+                                           message_raw: *const u8,
+                                           message_len: usize,
+                                           nonce_raw: *const u8,
+                                           nonce_len: usize,
+                                           encrypted_raw_p: *mut *const u8,
+                                           encrypted_len_p: *mut usize,
+                                           buffer_handle_p: *mut IndyHandle) -> ErrorCode;
+
+    /// Decrypt message addressed to the key held by the key manager
+    ///
+    /// #Params
+    /// verkey: verkey of the recipient key (See create_key handler)
+    /// their_vk: verkey of the sender for authenticated encryption (crypto_box),
+    ///           null for anonymous encryption (sealed box)
+    /// message_raw, message_len: message to decrypt
+    /// nonce_raw, nonce_len: nonce used for authenticated encryption, null for anonymous encryption
+    /// decrypted_raw_p, decrypted_len_p: pointers to store decrypted message
+    ///           Note that pointer lifetime is the same as buffer lifetime (until free_buffer called)
+    /// buffer_handle_p: pointer to store handle of allocated buffer
+    pub type KeyManagerDecrypt = extern fn(verkey: *const c_char,
+                                           their_vk: *const c_char,
+                                           message_raw: *const u8,
+                                           message_len: usize,
+                                           nonce_raw: *const u8,
+                                           nonce_len: usize,
+                                           decrypted_raw_p: *mut *const u8,
+                                           decrypted_len_p: *mut usize,
+                                           buffer_handle_p: *mut IndyHandle) -> ErrorCode;
+
+    /// Free buffer allocated by the key manager (make buffer handle invalid)
+    ///
+    /// #Params
+    /// buffer_handle: handle of allocated buffer
+    pub type KeyManagerFreeBuffer = extern fn(buffer_handle: IndyHandle) -> ErrorCode;
+}
@@ -0,0 +1,85 @@
+extern crate openssl;
+
+use indy_api_types::errors::prelude::*;
+use self::openssl::rand::rand_bytes;
+use self::openssl::symm::{Cipher, decrypt_aead, encrypt_aead};
+
+pub const KEYBYTES: usize = 32;
+pub const NONCEBYTES: usize = 12;
+pub const TAGBYTES: usize = 16;
+
+pub fn gen_key() -> IndyResult<Vec<u8>> {
+    _random(KEYBYTES)
+}
+
+pub fn gen_nonce() -> IndyResult<Vec<u8>> {
+    _random(NONCEBYTES)
+}
+
+pub fn encrypt_detached(data: &[u8], aad: &[u8], key: &[u8], nonce: &[u8]) -> IndyResult<(Vec<u8>, Vec<u8>)> {
+    _check_key_and_nonce(key, nonce)?;
+
+    let mut tag = vec![0u8; TAGBYTES];
+    let encrypted = encrypt_aead(Cipher::aes_256_gcm(), key, Some(nonce), aad, data, &mut tag)?;
+
+    Ok((encrypted, tag))
+}
+
+pub fn decrypt_detached(data: &[u8], aad: &[u8], key: &[u8], nonce: &[u8], tag: &[u8]) -> IndyResult<Vec<u8>> {
+    _check_key_and_nonce(key, nonce)?;
+
+    if tag.len() != TAGBYTES {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Invalid tag length: {}, expected: {}", tag.len(), TAGBYTES)));
+    }
+
+    decrypt_aead(Cipher::aes_256_gcm(), key, Some(nonce), aad, data, tag)
+        .to_indy(IndyErrorKind::InvalidStructure, "Unable to decrypt data")
+}
+
+fn _check_key_and_nonce(key: &[u8], nonce: &[u8]) -> IndyResult<()> {
+    if key.len() != KEYBYTES {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Invalid key length: {}, expected: {}", key.len(), KEYBYTES)));
+    }
+
+    if nonce.len() != NONCEBYTES {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Invalid nonce length: {}, expected: {}", nonce.len(), NONCEBYTES)));
+    }
+
+    Ok(())
+}
+
+fn _random(len: usize) -> IndyResult<Vec<u8>> {
+    let mut res = vec![0u8; len];
+    rand_bytes(&mut res)?;
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_decrypt_detached_works() {
+        let data = b"some data to encrypt";
+        let aad = b"some associated data";
+        let key = gen_key().unwrap();
+        let nonce = gen_nonce().unwrap();
+
+        let (encrypted, tag) = encrypt_detached(data, aad, &key, &nonce).unwrap();
+        assert_eq!(TAGBYTES, tag.len());
+
+        let decrypted = decrypt_detached(&encrypted, aad, &key, &nonce, &tag).unwrap();
+        assert_eq!(data.to_vec(), decrypted);
+    }
+
+    #[test]
+    fn decrypt_detached_fails_for_other_aad() {
+        let key = gen_key().unwrap();
+        let nonce = gen_nonce().unwrap();
+
+        let (encrypted, tag) = encrypt_detached(b"data", b"aad", &key, &nonce).unwrap();
+
+        let res = decrypt_detached(&encrypted, b"other aad", &key, &nonce, &tag);
+        assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
+    }
+}
@@ -0,0 +1,82 @@
+extern crate openssl;
+
+use indy_api_types::errors::prelude::*;
+use self::openssl::aes::{AesKey, unwrap_key, wrap_key};
+
+pub const KEYBYTES: usize = 32;
+// AES Key Wrap (RFC 3394) adds 8 bytes of integrity check value
+pub const WRAPPEDBYTES: usize = 8;
+
+pub fn wrap(kek: &[u8], key: &[u8]) -> IndyResult<Vec<u8>> {
+    _check_kek(kek)?;
+
+    if key.len() < 16 || key.len() % 8 != 0 {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Invalid length of key to wrap: {}", key.len())));
+    }
+
+    let kek = AesKey::new_encrypt(kek)
+        .map_err(|_| err_msg(IndyErrorKind::InvalidStructure, "Invalid key encryption key"))?;
+
+    let mut wrapped = vec![0u8; key.len() + WRAPPEDBYTES];
+    wrap_key(&kek, None, &mut wrapped, key)
+        .map_err(|_| err_msg(IndyErrorKind::InvalidState, "Unable to wrap key"))?;
+
+    Ok(wrapped)
+}
+
+pub fn unwrap(kek: &[u8], wrapped: &[u8]) -> IndyResult<Vec<u8>> {
+    _check_kek(kek)?;
+
+    if wrapped.len() < 16 + WRAPPEDBYTES || wrapped.len() % 8 != 0 {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Invalid length of wrapped key: {}", wrapped.len())));
+    }
+
+    let kek = AesKey::new_decrypt(kek)
+        .map_err(|_| err_msg(IndyErrorKind::InvalidStructure, "Invalid key encryption key"))?;
+
+    let mut key = vec![0u8; wrapped.len() - WRAPPEDBYTES];
+    unwrap_key(&kek, None, &mut key, wrapped)
+        .map_err(|_| err_msg(IndyErrorKind::InvalidStructure, "Unable to unwrap key"))?;
+
+    Ok(key)
+}
+
+fn _check_kek(kek: &[u8]) -> IndyResult<()> {
+    if kek.len() != KEYBYTES {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Invalid key encryption key length: {}, expected: {}", kek.len(), KEYBYTES)));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_works_for_test_vector() {
+        // Test vector from RFC 3394, section 4.6
+        let kek: Vec<u8> = (0u8..32).collect();
+        let key = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff,
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f];
+
+        let expected = vec![0x28, 0xc9, 0xf4, 0x04, 0xc4, 0xb8, 0x10, 0xf4, 0xcb, 0xcc, 0xb3, 0x5c, 0xfb, 0x87, 0xf8, 0x26,
+            0x3f, 0x57, 0x86, 0xe2, 0xd8, 0x0e, 0xd3, 0x26, 0xcb, 0xc7, 0xf0, 0xe7, 0x1a, 0x99, 0xf4, 0x3b,
+            0xfb, 0x98, 0x8b, 0x9b, 0x7a, 0x02, 0xdd, 0x21];
+
+        let wrapped = wrap(&kek, &key).unwrap();
+        assert_eq!(expected, wrapped);
+
+        let unwrapped = unwrap(&kek, &wrapped).unwrap();
+        assert_eq!(key.to_vec(), unwrapped);
+    }
+
+    #[test]
+    fn unwrap_fails_for_other_kek() {
+        let kek = [1u8; KEYBYTES];
+        let wrapped = wrap(&kek, &[2u8; 32]).unwrap();
+
+        let res = unwrap(&[3u8; KEYBYTES], &wrapped);
+        assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
+    }
+}
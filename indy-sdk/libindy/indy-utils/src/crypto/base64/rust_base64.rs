@@ -16,6 +16,11 @@ pub fn encode_urlsafe(doc: &[u8]) -> String {
     base64::encode_config(doc, base64::URL_SAFE) //TODO switch to URL_SAFE_NO_PAD
 }
 
+// JOSE (RFC 7515) requires base64url encoding without padding
+pub fn encode_urlsafe_no_pad(doc: &[u8]) -> String {
+    base64::encode_config(doc, base64::URL_SAFE_NO_PAD)
+}
+
 pub fn decode_urlsafe(doc: &str) -> Result<Vec<u8>, IndyError> {
     base64::decode_config(doc, base64::URL_SAFE_NO_PAD)
         .context("Invalid base64URL_SAFE sequence")
@@ -47,6 +52,12 @@ mod tests {
         assert_eq!("AQID", &result);
     }
 
+    #[test]
+    fn encode_urlsafe_no_pad_works() {
+        let result = encode_urlsafe_no_pad(&[1, 2]);
+        assert_eq!("AQI", &result);
+    }
+
     #[test]
     fn decode_urlsafe_works() {
         let result = decode_urlsafe("AQID");
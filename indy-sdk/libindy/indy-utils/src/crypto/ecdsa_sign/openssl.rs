@@ -0,0 +1,233 @@
+extern crate openssl;
+
+use std::cmp::Ordering;
+
+use indy_api_types::errors::prelude::*;
+use self::openssl::bn::{BigNum, BigNumContext, BigNumRef};
+use self::openssl::ec::{EcGroup, EcKey, EcPoint, EcPointRef, PointConversionForm};
+use self::openssl::ecdsa::EcdsaSig;
+use self::openssl::nid::Nid;
+use self::openssl::pkey::{Private, Public};
+use self::openssl::sha::sha256;
+
+pub const SEEDBYTES: usize = 32;
+pub const SECRETKEYBYTES: usize = 32;
+// Public keys are kept in compressed SEC1 form
+pub const PUBLICKEYBYTES: usize = 33;
+// Signatures are kept as r || s, each 32 bytes big-endian (the same as JWS uses)
+pub const SIGNATUREBYTES: usize = 64;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Curve {
+    Secp256k1,
+    Secp256r1,
+}
+
+impl Curve {
+    fn group(&self) -> IndyResult<EcGroup> {
+        let nid = match *self {
+            Curve::Secp256k1 => Nid::SECP256K1,
+            Curve::Secp256r1 => Nid::X9_62_PRIME256V1,
+        };
+
+        Ok(EcGroup::from_curve_name(nid)?)
+    }
+}
+
+pub fn create_key_pair_for_signature(curve: Curve, seed: Option<&[u8]>) -> IndyResult<(Vec<u8>, Vec<u8>)> {
+    let group = curve.group()?;
+
+    let secret_key = match seed {
+        Some(seed) => {
+            if seed.len() != SEEDBYTES {
+                return Err(err_msg(IndyErrorKind::InvalidStructure,
+                                   format!("Invalid seed length: {}, expected: {}", seed.len(), SEEDBYTES)));
+            }
+
+            // Seed is used as secret scalar directly, so it has to be in [1, n - 1]
+            let secret_key = BigNum::from_slice(seed)?;
+            _check_secret_scalar(&group, &secret_key)?;
+            secret_key
+        }
+        None => EcKey::generate(&group)?.private_key().to_owned()?
+    };
+
+    let key = _private_key(&group, &secret_key)?;
+    let public_key = _public_key_bytes(&group, key.public_key())?;
+
+    Ok((public_key, secret_key.to_vec_padded(SECRETKEYBYTES as i32)?))
+}
+
+pub fn sign(curve: Curve, secret_key: &[u8], doc: &[u8]) -> IndyResult<Vec<u8>> {
+    if secret_key.len() != SECRETKEYBYTES {
+        return Err(err_msg(IndyErrorKind::InvalidStructure,
+                           format!("Invalid secret key length: {}, expected: {}", secret_key.len(), SECRETKEYBYTES)));
+    }
+
+    let group = curve.group()?;
+
+    let secret_key = BigNum::from_slice(secret_key)?;
+    _check_secret_scalar(&group, &secret_key)?;
+
+    let key = _private_key(&group, &secret_key)?;
+    let signature = EcdsaSig::sign(&sha256(doc), &key)?;
+
+    let s = match curve {
+        // secp256k1 consumers (Ethereum, Bitcoin) accept only low-S signatures
+        Curve::Secp256k1 => _normalize_s(&group, signature.s())?,
+        Curve::Secp256r1 => signature.s().to_owned()?,
+    };
+
+    let mut res = signature.r().to_vec_padded((SIGNATUREBYTES / 2) as i32)?;
+    res.extend(s.to_vec_padded((SIGNATUREBYTES / 2) as i32)?);
+
+    Ok(res)
+}
+
+pub fn verify(curve: Curve, public_key: &[u8], doc: &[u8], signature: &[u8]) -> IndyResult<bool> {
+    if signature.len() != SIGNATUREBYTES {
+        return Err(err_msg(IndyErrorKind::InvalidStructure,
+                           format!("Invalid signature length: {}, expected: {}", signature.len(), SIGNATUREBYTES)));
+    }
+
+    let group = curve.group()?;
+    let key = _public_key(&group, public_key)?;
+
+    let (r, s) = signature.split_at(SIGNATUREBYTES / 2);
+    let signature = EcdsaSig::from_private_components(BigNum::from_slice(r)?, BigNum::from_slice(s)?)?;
+
+    Ok(signature.verify(&sha256(doc), &key)?)
+}
+
+pub fn validate_key(curve: Curve, public_key: &[u8]) -> IndyResult<()> {
+    let group = curve.group()?;
+    _public_key(&group, public_key)?;
+    Ok(())
+}
+
+fn _check_secret_scalar(group: &EcGroup, secret_key: &BigNumRef) -> IndyResult<()> {
+    let mut ctx = BigNumContext::new()?;
+    let mut order = BigNum::new()?;
+    group.order(&mut order, &mut ctx)?;
+
+    if secret_key.num_bits() == 0 || secret_key.ucmp(&order) != Ordering::Less {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Secret key is out of the curve order range"));
+    }
+
+    Ok(())
+}
+
+fn _private_key(group: &EcGroup, secret_key: &BigNumRef) -> IndyResult<EcKey<Private>> {
+    let ctx = BigNumContext::new()?;
+    let mut public_key = EcPoint::new(group)?;
+    public_key.mul_generator(group, secret_key, &ctx)?;
+
+    Ok(EcKey::from_private_components(group, secret_key, &public_key)?)
+}
+
+fn _public_key(group: &EcGroup, public_key: &[u8]) -> IndyResult<EcKey<Public>> {
+    let mut ctx = BigNumContext::new()?;
+
+    let point = EcPoint::from_bytes(group, public_key, &mut ctx)
+        .to_indy(IndyErrorKind::InvalidStructure, "Invalid public key: not a point of the curve")?;
+
+    let key = EcKey::from_public_key(group, &point)?;
+    key.check_key()
+        .to_indy(IndyErrorKind::InvalidStructure, "Invalid public key")?;
+
+    Ok(key)
+}
+
+fn _public_key_bytes(group: &EcGroup, public_key: &EcPointRef) -> IndyResult<Vec<u8>> {
+    let mut ctx = BigNumContext::new()?;
+    Ok(public_key.to_bytes(group, PointConversionForm::COMPRESSED, &mut ctx)?)
+}
+
+fn _normalize_s(group: &EcGroup, s: &BigNumRef) -> IndyResult<BigNum> {
+    let mut ctx = BigNumContext::new()?;
+    let mut order = BigNum::new()?;
+    group.order(&mut order, &mut ctx)?;
+
+    let mut half_order = BigNum::new()?;
+    half_order.rshift1(&order)?;
+
+    if s.ucmp(&half_order) == Ordering::Greater {
+        let mut low_s = BigNum::new()?;
+        low_s.checked_sub(&order, s)?;
+        Ok(low_s)
+    } else {
+        Ok(s.to_owned()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::randombytes::randombytes;
+
+    #[test]
+    fn sign_verify_works() {
+        for curve in &[Curve::Secp256k1, Curve::Secp256r1] {
+            let text = randombytes(16);
+
+            let (public_key, secret_key) = create_key_pair_for_signature(*curve, None).unwrap();
+            assert_eq!(PUBLICKEYBYTES, public_key.len());
+            assert_eq!(SECRETKEYBYTES, secret_key.len());
+
+            let signature = sign(*curve, &secret_key, &text).unwrap();
+            assert_eq!(SIGNATUREBYTES, signature.len());
+
+            assert!(verify(*curve, &public_key, &text, &signature).unwrap());
+            assert!(!verify(*curve, &public_key, b"other text", &signature).unwrap());
+        }
+    }
+
+    #[test]
+    fn create_key_pair_for_signature_works_for_seed() {
+        let seed = randombytes(SEEDBYTES);
+
+        let (public_key, secret_key) = create_key_pair_for_signature(Curve::Secp256k1, Some(&seed)).unwrap();
+        let (public_key_2, _) = create_key_pair_for_signature(Curve::Secp256k1, Some(&seed)).unwrap();
+        let (public_key_r1, _) = create_key_pair_for_signature(Curve::Secp256r1, Some(&seed)).unwrap();
+
+        assert_eq!(seed, secret_key);
+        assert_eq!(public_key, public_key_2);
+        assert_ne!(public_key, public_key_r1);
+    }
+
+    #[test]
+    fn create_key_pair_for_signature_works_for_invalid_seed() {
+        let res = create_key_pair_for_signature(Curve::Secp256k1, Some(&[0; SEEDBYTES]));
+        assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
+
+        let res = create_key_pair_for_signature(Curve::Secp256r1, Some(&[1; 16]));
+        assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn sign_works_for_low_s_on_secp256k1() {
+        let group = Curve::Secp256k1.group().unwrap();
+        let mut ctx = BigNumContext::new().unwrap();
+        let mut order = BigNum::new().unwrap();
+        group.order(&mut order, &mut ctx).unwrap();
+        let mut half_order = BigNum::new().unwrap();
+        half_order.rshift1(&order).unwrap();
+
+        let (_, secret_key) = create_key_pair_for_signature(Curve::Secp256k1, None).unwrap();
+
+        for i in 0..16u8 {
+            let signature = sign(Curve::Secp256k1, &secret_key, &[i]).unwrap();
+            let s = BigNum::from_slice(&signature[SIGNATUREBYTES / 2..]).unwrap();
+            assert_ne!(Ordering::Greater, s.ucmp(&half_order));
+        }
+    }
+
+    #[test]
+    fn validate_key_works() {
+        let (public_key, _) = create_key_pair_for_signature(Curve::Secp256r1, None).unwrap();
+        validate_key(Curve::Secp256r1, &public_key).unwrap();
+
+        let res = validate_key(Curve::Secp256r1, &[5; PUBLICKEYBYTES]);
+        assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
+    }
+}
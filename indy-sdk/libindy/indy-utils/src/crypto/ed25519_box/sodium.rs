@@ -1,6 +1,7 @@
 extern crate sodiumoxide;
 
 use indy_api_types::errors::prelude::*;
+use libc::c_int;
 use self::sodiumoxide::crypto::box_;
 
 
@@ -12,6 +13,15 @@ sodium_type!(Nonce, box_::Nonce, NONCEBYTES);
 sodium_type!(PublicKey, box_::PublicKey, PUBLICKEYBYTES);
 sodium_type!(SecretKey, box_::SecretKey, SECRETKEYBYTES);
 
+pub const SHAREDSECRETBYTES: usize = 32;
+
+extern {
+    // crypto_scalarmult wrapper of sodiumoxide differs between versions, so libsodium function is called directly
+    fn crypto_scalarmult_curve25519(q: *mut [u8; SHAREDSECRETBYTES],
+                                    n: *const [u8; SECRETKEYBYTES],
+                                    p: *const [u8; PUBLICKEYBYTES]) -> c_int;
+}
+
 pub fn encrypt(secret_key: &SecretKey, public_key: &PublicKey, doc: &[u8], nonce: &Nonce) -> Result<Vec<u8>, IndyError> {
     Ok(box_::seal(
         doc,
@@ -35,6 +45,27 @@ pub fn gen_nonce() -> Nonce {
     Nonce(box_::gen_nonce())
 }
 
+pub fn gen_keypair() -> (PublicKey, SecretKey) {
+    let (public_key, secret_key) = box_::gen_keypair();
+    (PublicKey(public_key), SecretKey(secret_key))
+}
+
+// X25519 key agreement (RFC 7748)
+pub fn ecdh(secret_key: &SecretKey, public_key: &PublicKey) -> Result<Vec<u8>, IndyError> {
+    let mut shared_secret = [0u8; SHAREDSECRETBYTES];
+
+    let res = unsafe {
+        crypto_scalarmult_curve25519(&mut shared_secret, &(secret_key.0).0, &(public_key.0).0)
+    };
+
+    // Public keys of small order give all zeros result (only newer libsodium versions check it)
+    if res != 0 || shared_secret.iter().all(|b| *b == 0) {
+        return Err(IndyError::from_msg(IndyErrorKind::InvalidStructure, "Unable to compute shared secret: invalid public key"));
+    }
+
+    Ok(shared_secret.to_vec())
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -42,6 +73,26 @@ mod tests {
     use crate::crypto::ed25519_sign;
     use crate::crypto::randombytes::randombytes;
 
+    #[test]
+    fn ecdh_works() {
+        let (alice_pk, alice_sk) = gen_keypair();
+        let (bob_pk, bob_sk) = gen_keypair();
+
+        let alice_secret = ecdh(&alice_sk, &bob_pk).unwrap();
+        let bob_secret = ecdh(&bob_sk, &alice_pk).unwrap();
+
+        assert_eq!(SHAREDSECRETBYTES, alice_secret.len());
+        assert_eq!(alice_secret, bob_secret);
+    }
+
+    #[test]
+    fn ecdh_fails_for_small_order_public_key() {
+        let (_, secret_key) = gen_keypair();
+
+        let res = ecdh(&secret_key, &PublicKey::new([0; PUBLICKEYBYTES]));
+        assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
+    }
+
     #[test]
     fn encrypt_decrypt_works() {
         let text = randombytes(16);
@@ -1,6 +1,14 @@
 #[macro_use]
 pub mod sodium_type;
 
+#[cfg(feature = "aes256gcm_openssl")]
+#[path = "aes256gcm/openssl.rs"]
+pub mod aes256gcm;
+
+#[cfg(feature = "aes256kw_openssl")]
+#[path = "aes256kw/openssl.rs"]
+pub mod aes256kw;
+
 #[cfg(feature = "base64_rust_base64")]
 #[path = "base64/rust_base64.rs"]
 pub mod base64;
@@ -34,10 +42,18 @@ pub mod sealedbox;
 #[path = "xsalsa20/sodium.rs"]
 pub mod xsalsa20;
 
+#[cfg(feature = "xchacha20poly1305_ietf_sodium")]
+#[path = "xchacha20poly1305_ietf/sodium.rs"]
+pub mod xchacha20poly1305_ietf;
+
 #[cfg(feature = "ed25519_sign_sodium")]
 #[path = "ed25519_sign/sodium.rs"]
 pub mod ed25519_sign;
 
+#[cfg(feature = "ecdsa_sign_openssl")]
+#[path = "ecdsa_sign/openssl.rs"]
+pub mod ecdsa_sign;
+
 #[cfg(feature = "ed25519_box_sodium")]
 #[path = "ed25519_box/sodium.rs"]
 // TODO: The name is misleading as the operations do not happen over ed25519 curve
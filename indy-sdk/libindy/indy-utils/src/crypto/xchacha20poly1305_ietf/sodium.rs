@@ -0,0 +1,144 @@
+extern crate sodiumoxide;
+
+use indy_api_types::errors::prelude::*;
+use libc::{c_int, c_ulonglong};
+use self::sodiumoxide::randombytes;
+
+pub const KEYBYTES: usize = 32; // crypto_aead_xchacha20poly1305_ietf_KEYBYTES
+pub const NONCEBYTES: usize = 24; // crypto_aead_xchacha20poly1305_ietf_NPUBBYTES
+pub const TAGBYTES: usize = 16; // crypto_aead_xchacha20poly1305_ietf_ABYTES
+
+pub fn gen_key() -> Vec<u8> {
+    randombytes::randombytes(KEYBYTES)
+}
+
+pub fn gen_nonce() -> Vec<u8> {
+    randombytes::randombytes(NONCEBYTES)
+}
+
+pub fn encrypt_detached(data: &[u8], aad: &[u8], key: &[u8], nonce: &[u8]) -> IndyResult<(Vec<u8>, Vec<u8>)> {
+    _check_key_and_nonce(key, nonce)?;
+
+    let mut encrypted = vec![0u8; data.len()];
+    let mut tag = vec![0u8; TAGBYTES];
+    let mut tag_len: c_ulonglong = 0;
+
+    let res = unsafe {
+        crypto_aead_xchacha20poly1305_ietf_encrypt_detached(encrypted.as_mut_ptr(),
+                                                            tag.as_mut_ptr(),
+                                                            &mut tag_len,
+                                                            data.as_ptr(),
+                                                            data.len() as c_ulonglong,
+                                                            aad.as_ptr(),
+                                                            aad.len() as c_ulonglong,
+                                                            std::ptr::null(),
+                                                            nonce.as_ptr(),
+                                                            key.as_ptr())
+    };
+
+    if res != 0 {
+        return Err(err_msg(IndyErrorKind::InvalidState, "Unable to encrypt data"));
+    }
+
+    tag.truncate(tag_len as usize);
+
+    Ok((encrypted, tag))
+}
+
+pub fn decrypt_detached(data: &[u8], aad: &[u8], key: &[u8], nonce: &[u8], tag: &[u8]) -> IndyResult<Vec<u8>> {
+    _check_key_and_nonce(key, nonce)?;
+
+    if tag.len() != TAGBYTES {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Invalid tag length: {}, expected: {}", tag.len(), TAGBYTES)));
+    }
+
+    let mut decrypted = vec![0u8; data.len()];
+
+    let res = unsafe {
+        crypto_aead_xchacha20poly1305_ietf_decrypt_detached(decrypted.as_mut_ptr(),
+                                                            std::ptr::null_mut(),
+                                                            data.as_ptr(),
+                                                            data.len() as c_ulonglong,
+                                                            tag.as_ptr(),
+                                                            aad.as_ptr(),
+                                                            aad.len() as c_ulonglong,
+                                                            nonce.as_ptr(),
+                                                            key.as_ptr())
+    };
+
+    if res != 0 {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Unable to decrypt data"));
+    }
+
+    Ok(decrypted)
+}
+
+fn _check_key_and_nonce(key: &[u8], nonce: &[u8]) -> IndyResult<()> {
+    if key.len() != KEYBYTES {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Invalid key length: {}, expected: {}", key.len(), KEYBYTES)));
+    }
+
+    if nonce.len() != NONCEBYTES {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Invalid nonce length: {}, expected: {}", nonce.len(), NONCEBYTES)));
+    }
+
+    Ok(())
+}
+
+extern {
+    fn crypto_aead_xchacha20poly1305_ietf_encrypt_detached(c: *mut u8,
+                                                           mac: *mut u8,
+                                                           maclen_p: *mut c_ulonglong,
+                                                           m: *const u8,
+                                                           mlen: c_ulonglong,
+                                                           ad: *const u8,
+                                                           adlen: c_ulonglong,
+                                                           nsec: *const u8,
+                                                           npub: *const u8,
+                                                           k: *const u8) -> c_int;
+
+    fn crypto_aead_xchacha20poly1305_ietf_decrypt_detached(m: *mut u8,
+                                                           nsec: *mut u8,
+                                                           c: *const u8,
+                                                           clen: c_ulonglong,
+                                                           mac: *const u8,
+                                                           ad: *const u8,
+                                                           adlen: c_ulonglong,
+                                                           npub: *const u8,
+                                                           k: *const u8) -> c_int;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::randombytes::randombytes;
+
+    #[test]
+    fn encrypt_detached_works_for_test_vector() {
+        // Test vector from draft-irtf-cfrg-xchacha-03, appendix A.3.1
+        let plaintext = b"Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.";
+        let aad = [0x50, 0x51, 0x52, 0x53, 0xc0, 0xc1, 0xc2, 0xc3, 0xc4, 0xc5, 0xc6, 0xc7];
+        let key: Vec<u8> = (0x80u8..0xa0).collect();
+        let nonce: Vec<u8> = (0x40u8..0x58).collect();
+
+        let (encrypted, tag) = encrypt_detached(plaintext, &aad, &key, &nonce).unwrap();
+
+        assert_eq!(vec![0xbd, 0x6d, 0x17, 0x9d, 0x3e, 0x83, 0xd4, 0x3b, 0x95, 0x76, 0x57, 0x94, 0x93, 0xc0, 0xe9, 0x39], encrypted[..16].to_vec());
+        assert_eq!(vec![0xc0, 0x87, 0x59, 0x24, 0xc1, 0xc7, 0x98, 0x79, 0x47, 0xde, 0xaf, 0xd8, 0x78, 0x0a, 0xcf, 0x49], tag);
+    }
+
+    #[test]
+    fn encrypt_decrypt_detached_works() {
+        let data = randombytes(100);
+        let aad = randombytes(20);
+        let key = gen_key();
+        let nonce = gen_nonce();
+
+        let (encrypted, tag) = encrypt_detached(&data, &aad, &key, &nonce).unwrap();
+        let decrypted = decrypt_detached(&encrypted, &aad, &key, &nonce, &tag).unwrap();
+        assert_eq!(data, decrypted);
+
+        let res = decrypt_detached(&encrypted, b"other aad", &key, &nonce, &tag);
+        assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
+    }
+}
@@ -218,6 +218,21 @@ macro_rules! check_useful_c_byte_array {
     }
 }
 
+#[macro_export]
+macro_rules! check_useful_opt_c_byte_array {
+    ($ptr:ident, $len:expr, $err:expr) => {
+        if !$ptr.is_null() && $len <= 0 {
+            return err_msg($err.into(), "Array length must be greater than 0").into();
+        }
+
+        let $ptr = if $ptr.is_null() {
+            None
+        } else {
+            Some(unsafe { ::std::slice::from_raw_parts($ptr, $len as usize) }.to_vec())
+        };
+    }
+}
+
 //Returnable pointer is valid only before first vector modification
 pub fn vec_to_pointer(v: &Vec<u8>) -> (*const u8, u32) {
     let len = v.len() as u32;
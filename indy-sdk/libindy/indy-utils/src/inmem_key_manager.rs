@@ -0,0 +1,244 @@
+extern crate libc;
+extern crate rust_base58;
+extern crate serde_json;
+extern crate sodiumoxide;
+
+use super::ErrorCode;
+use super::sequence;
+
+use self::libc::c_char;
+use self::rust_base58::ToBase58;
+use self::sodiumoxide::crypto::{box_, sealedbox, sign};
+
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
+use std::slice;
+use std::sync::Mutex;
+
+// Software stand-in for an external key manager (HSM, KMS) that keeps keys in the process memory.
+// Only ed25519 keys are supported.
+
+extern {
+    fn crypto_sign_ed25519_pk_to_curve25519(curve25519_pk: *mut [u8; box_::PUBLICKEYBYTES],
+                                            ed25519_pk: *const [u8; sign::PUBLICKEYBYTES]) -> libc::c_int;
+    fn crypto_sign_ed25519_sk_to_curve25519(curve25519_sk: *mut [u8; box_::SECRETKEYBYTES],
+                                            ed25519_sk: *const [u8; sign::SECRETKEYBYTES]) -> libc::c_int;
+}
+
+macro_rules! check_useful_c_str {
+    ($x:ident, $e:expr) => {
+        if $x.is_null() {
+            return $e;
+        }
+
+        let $x = match unsafe { CStr::from_ptr($x) }.to_str() {
+            Ok(val) if !val.is_empty() => val.to_string(),
+            _ => return $e,
+        };
+    }
+}
+
+lazy_static! {
+    static ref INMEM_KEYS: Mutex<HashMap<String, (sign::PublicKey, sign::SecretKey)>> = Default::default();
+}
+
+lazy_static! {
+    static ref ACTIVE_BUFFERS: Mutex<HashMap<i32, Vec<u8>>> = Default::default();
+}
+
+pub struct InmemKeyManager {}
+
+impl InmemKeyManager {
+    pub extern "C" fn create_key(key_config: *const c_char,
+                                 verkey_p: *mut *const c_char,
+                                 buffer_handle_p: *mut i32) -> ErrorCode {
+        check_useful_c_str!(key_config, ErrorCode::CommonInvalidStructure);
+
+        let key_config: serde_json::Value = match serde_json::from_str(&key_config) {
+            Ok(key_config) => key_config,
+            Err(_) => return ErrorCode::CommonInvalidStructure
+        };
+
+        match key_config["crypto_type"].as_str() {
+            None | Some("ed25519") => {}
+            Some(_) => return ErrorCode::UnknownCryptoTypeError
+        }
+
+        let (pk, sk) = match key_config["seed"].as_str() {
+            Some(seed) => match sign::Seed::from_slice(seed.as_bytes()) {
+                Some(seed) => sign::keypair_from_seed(&seed),
+                None => return ErrorCode::CommonInvalidStructure
+            },
+            None => sign::gen_keypair()
+        };
+
+        let verkey = pk.0.to_base58();
+
+        INMEM_KEYS.lock().unwrap().insert(verkey.clone(), (pk, sk));
+
+        let verkey = CString::new(verkey).unwrap();
+
+        unsafe {
+            *buffer_handle_p = InmemKeyManager::_store_buffer(verkey.into_bytes_with_nul());
+            *verkey_p = ACTIVE_BUFFERS.lock().unwrap()[&*buffer_handle_p].as_ptr() as *const c_char;
+        }
+
+        ErrorCode::Success
+    }
+
+    pub extern "C" fn sign(verkey: *const c_char,
+                           message_raw: *const u8,
+                           message_len: usize,
+                           signature_raw_p: *mut *const u8,
+                           signature_len_p: *mut usize,
+                           buffer_handle_p: *mut i32) -> ErrorCode {
+        check_useful_c_str!(verkey, ErrorCode::CommonInvalidStructure);
+
+        let (_, sk) = match INMEM_KEYS.lock().unwrap().get(&verkey) {
+            Some(key) => key.clone(),
+            None => return ErrorCode::WalletItemNotFound
+        };
+
+        let message = unsafe { slice::from_raw_parts(message_raw, message_len) };
+        let signature = sign::sign_detached(message, &sk).0.to_vec();
+
+        unsafe { InmemKeyManager::_return_buffer(signature, signature_raw_p, signature_len_p, buffer_handle_p) };
+
+        ErrorCode::Success
+    }
+
+    pub extern "C" fn encrypt(verkey: *const c_char,
+                              their_vk: *const c_char,
+                              message_raw: *const u8,
+                              message_len: usize,
+                              nonce_raw: *const u8,
+                              nonce_len: usize,
+                              encrypted_raw_p: *mut *const u8,
+                              encrypted_len_p: *mut usize,
+                              buffer_handle_p: *mut i32) -> ErrorCode {
+        check_useful_c_str!(verkey, ErrorCode::CommonInvalidStructure);
+        check_useful_c_str!(their_vk, ErrorCode::CommonInvalidStructure);
+
+        let (_, my_sk) = match InmemKeyManager::_curve25519_keys(&verkey) {
+            Some(keys) => keys,
+            None => return ErrorCode::WalletItemNotFound
+        };
+
+        let their_pk = match InmemKeyManager::_their_curve25519_pk(&their_vk) {
+            Some(pk) => pk,
+            None => return ErrorCode::CommonInvalidStructure
+        };
+
+        let nonce = match box_::Nonce::from_slice(unsafe { slice::from_raw_parts(nonce_raw, nonce_len) }) {
+            Some(nonce) => nonce,
+            None => return ErrorCode::CommonInvalidStructure
+        };
+
+        let message = unsafe { slice::from_raw_parts(message_raw, message_len) };
+        let encrypted = box_::seal(message, &nonce, &their_pk, &my_sk);
+
+        unsafe { InmemKeyManager::_return_buffer(encrypted, encrypted_raw_p, encrypted_len_p, buffer_handle_p) };
+
+        ErrorCode::Success
+    }
+
+    pub extern "C" fn decrypt(verkey: *const c_char,
+                              their_vk: *const c_char,
+                              message_raw: *const u8,
+                              message_len: usize,
+                              nonce_raw: *const u8,
+                              nonce_len: usize,
+                              decrypted_raw_p: *mut *const u8,
+                              decrypted_len_p: *mut usize,
+                              buffer_handle_p: *mut i32) -> ErrorCode {
+        check_useful_c_str!(verkey, ErrorCode::CommonInvalidStructure);
+
+        let (my_pk, my_sk) = match InmemKeyManager::_curve25519_keys(&verkey) {
+            Some(keys) => keys,
+            None => return ErrorCode::WalletItemNotFound
+        };
+
+        let message = unsafe { slice::from_raw_parts(message_raw, message_len) };
+
+        let decrypted = if their_vk.is_null() {
+            sealedbox::open(message, &my_pk, &my_sk)
+        } else {
+            check_useful_c_str!(their_vk, ErrorCode::CommonInvalidStructure);
+
+            let their_pk = match InmemKeyManager::_their_curve25519_pk(&their_vk) {
+                Some(pk) => pk,
+                None => return ErrorCode::CommonInvalidStructure
+            };
+
+            let nonce = match box_::Nonce::from_slice(unsafe { slice::from_raw_parts(nonce_raw, nonce_len) }) {
+                Some(nonce) => nonce,
+                None => return ErrorCode::CommonInvalidStructure
+            };
+
+            box_::open(message, &nonce, &their_pk, &my_sk)
+        };
+
+        let decrypted = match decrypted {
+            Ok(decrypted) => decrypted,
+            Err(_) => return ErrorCode::CommonInvalidStructure
+        };
+
+        unsafe { InmemKeyManager::_return_buffer(decrypted, decrypted_raw_p, decrypted_len_p, buffer_handle_p) };
+
+        ErrorCode::Success
+    }
+
+    pub extern "C" fn free_buffer(buffer_handle: i32) -> ErrorCode {
+        match ACTIVE_BUFFERS.lock().unwrap().remove(&buffer_handle) {
+            Some(_) => ErrorCode::Success,
+            None => ErrorCode::CommonInvalidState
+        }
+    }
+
+    pub fn cleanup() {
+        INMEM_KEYS.lock().unwrap().clear();
+        ACTIVE_BUFFERS.lock().unwrap().clear();
+    }
+
+    fn _store_buffer(buffer: Vec<u8>) -> i32 {
+        let buffer_handle = sequence::get_next_id();
+        ACTIVE_BUFFERS.lock().unwrap().insert(buffer_handle, buffer);
+        buffer_handle
+    }
+
+    unsafe fn _return_buffer(buffer: Vec<u8>, data_p: *mut *const u8, len_p: *mut usize, buffer_handle_p: *mut i32) {
+        *len_p = buffer.len();
+        *buffer_handle_p = InmemKeyManager::_store_buffer(buffer);
+        // Vec data isn't moved on insertion to the map, so the pointer stays valid until free_buffer call
+        *data_p = ACTIVE_BUFFERS.lock().unwrap()[&*buffer_handle_p].as_ptr();
+    }
+
+    fn _curve25519_keys(verkey: &str) -> Option<(box_::PublicKey, box_::SecretKey)> {
+        let (pk, sk) = INMEM_KEYS.lock().unwrap().get(verkey)?.clone();
+
+        let mut curve_pk = [0; box_::PUBLICKEYBYTES];
+        let mut curve_sk = [0; box_::SECRETKEYBYTES];
+
+        unsafe {
+            crypto_sign_ed25519_pk_to_curve25519(&mut curve_pk, &pk.0);
+            crypto_sign_ed25519_sk_to_curve25519(&mut curve_sk, &sk.0);
+        }
+
+        Some((box_::PublicKey(curve_pk), box_::SecretKey(curve_sk)))
+    }
+
+    fn _their_curve25519_pk(their_vk: &str) -> Option<box_::PublicKey> {
+        use self::rust_base58::FromBase58;
+
+        let their_vk = their_vk.from_base58().ok()?;
+        let their_vk = sign::PublicKey::from_slice(&their_vk)?;
+
+        let mut curve_pk = [0; box_::PUBLICKEYBYTES];
+
+        unsafe {
+            crypto_sign_ed25519_pk_to_curve25519(&mut curve_pk, &their_vk.0);
+        }
+
+        Some(box_::PublicKey(curve_pk))
+    }
+}
@@ -7,6 +7,7 @@ use indy_utils::crypto::{chacha20poly1305_ietf, hmacsha256, pwhash_argon2i13};
 
 use super::{Keys, WalletRecord, Metadata};
 use super::storage::{StorageRecord, Tag, TagName};
+use super::wallet::EncryptedValue;
 use rust_base58::FromBase58;
 
 #[cfg(test)]
@@ -206,6 +207,17 @@ pub(super) fn decrypt_storage_record(record: &StorageRecord, keys: &Keys) -> Ind
     Ok(WalletRecord::new(decrypted_name, decrypted_type, decrypted_value, decrypted_tags))
 }
 
+pub(super) fn reencrypt_storage_record(record: &StorageRecord, old_keys: &Keys, new_keys: &Keys) -> IndyResult<StorageRecord> {
+    let record = decrypt_storage_record(record, old_keys)?;
+
+    Ok(StorageRecord {
+        id: encrypt_as_searchable(record.get_id().as_bytes(), &new_keys.name_key, &new_keys.item_hmac_key),
+        value: record.get_value().map(|value| EncryptedValue::encrypt(value, &new_keys.value_key)),
+        type_: record.get_type().map(|type_| encrypt_as_searchable(type_.as_bytes(), &new_keys.type_key, &new_keys.item_hmac_key)),
+        tags: record.get_tags().map(|tags| encrypt_tags(tags, &new_keys.tag_name_key, &new_keys.tag_value_key, &new_keys.tags_hmac_key)),
+    })
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -427,4 +439,35 @@ mod tests {
 
         assert_kind!(IndyErrorKind::InvalidStructure, res);
     }
+
+    #[test]
+    fn test_reencrypt_storage_record_works() {
+        let keys = Keys::new();
+        let new_keys = Keys::new();
+        let name = "test_name";
+        let value = "test_value";
+        let type_ = "test_type";
+        let mut tags = HashMap::new();
+        tags.insert("tag_name_1".to_string(), "tag_value_1".to_string());
+        tags.insert("~tag_name_2".to_string(), "tag_value_2".to_string());
+
+        let storage_record = StorageRecord {
+            id: encrypt_as_searchable(name.as_bytes(), &keys.name_key, &keys.item_hmac_key),
+            value: Some(EncryptedValue::encrypt(value, &keys.value_key)),
+            type_: Some(encrypt_as_searchable(type_.as_bytes(), &keys.type_key, &keys.item_hmac_key)),
+            tags: Some(encrypt_tags(&tags, &keys.tag_name_key, &keys.tag_value_key, &keys.tags_hmac_key)),
+        };
+
+        let reencrypted_record = reencrypt_storage_record(&storage_record, &keys, &new_keys).unwrap();
+
+        assert_eq!(reencrypted_record.id, encrypt_as_searchable(name.as_bytes(), &new_keys.name_key, &new_keys.item_hmac_key));
+        assert_kind!(IndyErrorKind::InvalidStructure, decrypt_storage_record(&reencrypted_record, &keys));
+
+        let decrypted_wallet_record = decrypt_storage_record(&reencrypted_record, &new_keys).unwrap();
+
+        assert_eq!(&decrypted_wallet_record.id, name);
+        assert_eq!(&decrypted_wallet_record.value.unwrap(), value);
+        assert_eq!(&decrypted_wallet_record.type_.unwrap(), type_);
+        assert_eq!(&decrypted_wallet_record.tags.unwrap(), &tags);
+    }
 }
\ No newline at end of file
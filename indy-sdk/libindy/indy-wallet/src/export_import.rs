@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::io;
 use std::io::{BufReader, BufWriter, Read, Write};
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -5,7 +6,7 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use rmp_serde;
 
-use indy_api_types::domain::wallet::Record;
+use indy_api_types::domain::wallet::{ExportRecordType, Record};
 use indy_api_types::domain::wallet::KeyDerivationMethod;
 use indy_api_types::errors::prelude::*;
 use crate::encryption::KeyDerivationData;
@@ -13,8 +14,11 @@ use indy_utils::crypto::{chacha20poly1305_ietf, pwhash_argon2i13};
 use indy_utils::crypto::hash::{hash, HASHBYTES};
 
 use super::{Wallet, WalletRecord};
+use super::iterator::WalletIterator;
 
 const CHUNK_SIZE: usize = 1024;
+const PROGRESS_STEP: usize = 100;
+const EXPORT_SEARCH_OPTIONS: &str = r#"{"retrieveType": true, "retrieveValue": true, "retrieveTags": true}"#;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum EncryptionMethod {
@@ -69,7 +73,17 @@ pub struct Header {
 //   "version": ..,
 // }
 
-pub(super) fn export_continue(wallet: &Wallet, writer: &mut dyn Write, version: u32, key: chacha20poly1305_ietf::Key, key_data: &KeyDerivationData) -> IndyResult<()> {
+pub(super) fn export_continue(wallet: &Wallet,
+                              writer: &mut dyn Write,
+                              version: u32,
+                              key: chacha20poly1305_ietf::Key,
+                              key_data: &KeyDerivationData,
+                              record_types: Option<&[ExportRecordType]>,
+                              progress: Option<&dyn Fn(usize) -> IndyResult<()>>) -> IndyResult<()> {
+    if let Some(record_types) = record_types {
+        _check_record_types(record_types)?;
+    }
+
     let nonce = chacha20poly1305_ietf::gen_nonce();
     let chunk_size = CHUNK_SIZE;
 
@@ -112,8 +126,41 @@ pub(super) fn export_continue(wallet: &Wallet, writer: &mut dyn Write, version:
 
     writer.write_all(&hash(&header)?)?;
 
-    let mut records = wallet.get_all()?;
+    let mut records_count: usize = 0;
+
+    match record_types {
+        Some(record_types) => {
+            for record_type in record_types {
+                let query = record_type.query.as_ref().map(|query| query.to_string()).unwrap_or_else(|| "{}".to_string());
+                let mut records = wallet.search(&record_type.type_, &query, Some(EXPORT_SEARCH_OPTIONS))?;
+                _write_records(&mut writer, &mut records, &mut records_count, progress)?;
+            }
+        }
+        None => {
+            let mut records = wallet.get_all()?;
+            _write_records(&mut writer, &mut records, &mut records_count, progress)?;
+        }
+    }
+
+    writer.write_u32::<LittleEndian>(0)?; // END message
+    writer.flush()?;
+
+    _report_progress(progress, records_count, true)
+}
+
+fn _check_record_types(record_types: &[ExportRecordType]) -> IndyResult<()> {
+    let mut types = HashSet::new();
+
+    for record_type in record_types {
+        if !types.insert(record_type.type_.as_str()) {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Record type {} is listed twice in export config", record_type.type_)));
+        }
+    }
+
+    Ok(())
+}
 
+fn _write_records(writer: &mut dyn Write, records: &mut WalletIterator, records_count: &mut usize, progress: Option<&dyn Fn(usize) -> IndyResult<()>>) -> IndyResult<()> {
     while let Some(WalletRecord { type_, id, value, tags }) = records.next()? {
         let record = Record {
             type_: type_.ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No type fetched for exported record"))?,
@@ -127,10 +174,11 @@ pub(super) fn export_continue(wallet: &Wallet, writer: &mut dyn Write, version:
 
         writer.write_u32::<LittleEndian>(record.len() as u32)?;
         writer.write_all(&record)?;
+
+        *records_count += 1;
+        _report_progress(progress, *records_count, false)?;
     }
 
-    writer.write_u32::<LittleEndian>(0)?; // END message
-    writer.flush()?;
     Ok(())
 }
 
@@ -138,7 +186,7 @@ pub(super) fn export_continue(wallet: &Wallet, writer: &mut dyn Write, version:
 fn import<T>(wallet: &Wallet, reader: T, passphrase: &str) -> IndyResult<()> where T: Read {
     let (reader, import_key_derivation_data, nonce, chunk_size, header_bytes) = preparse_file_to_import(reader, passphrase)?;
     let import_key = import_key_derivation_data.calc_master_key()?;
-    finish_import(wallet, reader, import_key, nonce, chunk_size, header_bytes)
+    finish_import(wallet, reader, import_key, nonce, chunk_size, header_bytes, false, None)
 }
 
 pub(super) fn preparse_file_to_import<T>(reader: T, passphrase: &str) -> IndyResult<(BufReader<T>, KeyDerivationData, chacha20poly1305_ietf::Nonce, usize, Vec<u8>)> where T: Read {
@@ -200,17 +248,45 @@ pub(super) fn preparse_file_to_import<T>(reader: T, passphrase: &str) -> IndyRes
     Ok((reader, import_key_derivation_data, nonce, chunk_size, header_bytes))
 }
 
-pub(super) fn finish_import<T>(wallet: &Wallet, reader: BufReader<T>, key: chacha20poly1305_ietf::Key, nonce: chacha20poly1305_ietf::Nonce, chunk_size: usize, header_bytes: Vec<u8>) -> IndyResult<()> where T: Read {
+#[cfg(test)]
+fn finish_import<T>(wallet: &Wallet,
+                    reader: BufReader<T>,
+                    key: chacha20poly1305_ietf::Key,
+                    nonce: chacha20poly1305_ietf::Nonce,
+                    chunk_size: usize,
+                    header_bytes: Vec<u8>,
+                    skip_existing: bool,
+                    progress: Option<&dyn Fn(usize) -> IndyResult<()>>) -> IndyResult<()> where T: Read {
+    let mut reader = open_import_reader(reader, key, nonce, chunk_size, &header_bytes)?;
+    let mut records_count: usize = 0;
+    import_records(wallet, &mut reader, skip_existing, progress, &mut records_count)
+}
+
+// Checks that export key is valid and header isn't modified before any record is imported
+pub(super) fn open_import_reader<T>(reader: BufReader<T>,
+                                    key: chacha20poly1305_ietf::Key,
+                                    nonce: chacha20poly1305_ietf::Nonce,
+                                    chunk_size: usize,
+                                    header_bytes: &[u8]) -> IndyResult<chacha20poly1305_ietf::Reader<BufReader<T>>> where T: Read {
     // Reads encrypted
     let mut reader = chacha20poly1305_ietf::Reader::new(reader, key, nonce, chunk_size);
 
     let mut header_hash = vec![0u8; HASHBYTES];
     reader.read_exact(&mut header_hash).map_err(_map_io_err)?;
 
-    if hash(&header_bytes)? != header_hash {
+    if hash(header_bytes)? != header_hash {
         return Err(err_msg(IndyErrorKind::InvalidStructure, "Invalid header hash"));
     }
 
+    Ok(reader)
+}
+
+// records_count is updated after every imported record, so caller knows how far failed import went
+pub(super) fn import_records<T>(wallet: &Wallet,
+                                reader: &mut T,
+                                skip_existing: bool,
+                                progress: Option<&dyn Fn(usize) -> IndyResult<()>>,
+                                records_count: &mut usize) -> IndyResult<()> where T: Read {
     loop {
         let record_len = reader.read_u32::<LittleEndian>().map_err(_map_io_err)? as usize;
 
@@ -224,10 +300,27 @@ pub(super) fn finish_import<T>(wallet: &Wallet, reader: BufReader<T>, key: chach
         let record: Record = rmp_serde::from_slice(&record)
             .to_indy(IndyErrorKind::InvalidStructure, "Record is malformed msgpack")?;
 
-        wallet.add(&record.type_, &record.id, &record.value, &record.tags)?;
+        match wallet.add(&record.type_, &record.id, &record.value, &record.tags) {
+            // Record was added by previous interrupted import
+            Err(ref err) if skip_existing && err.kind() == IndyErrorKind::WalletItemAlreadyExists => (),
+            res => res?
+        }
+
+        *records_count += 1;
+        _report_progress(progress, *records_count, false)?;
     }
 
-    Ok(())
+    _report_progress(progress, *records_count, true)
+}
+
+// Reports every PROGRESS_STEP records and once at the end if the last step wasn't reported yet
+fn _report_progress(progress: Option<&dyn Fn(usize) -> IndyResult<()>>, records_count: usize, is_last: bool) -> IndyResult<()> {
+    let is_step = records_count > 0 && records_count % PROGRESS_STEP == 0;
+
+    match progress {
+        Some(progress) if is_step != is_last => progress(records_count),
+        _ => Ok(())
+    }
 }
 
 fn _map_io_err(e: io::Error) -> IndyError {
@@ -240,6 +333,7 @@ fn _map_io_err(e: io::Error) -> IndyError {
 
 #[cfg(test)]
 mod tests {
+    use std::cell::RefCell;
     use std::collections::HashMap;
     use std::rc::Rc;
 
@@ -263,7 +357,7 @@ mod tests {
         let key_data = KeyDerivationData::from_passphrase_with_new_salt(passphrase, key_derivation_method);
         let key = key_data.calc_master_key()?;
 
-        export_continue(wallet, writer, version, key, &key_data)
+        export_continue(wallet, writer, version, key, &key_data, None, None)
     }
 
     #[test]
@@ -347,6 +441,127 @@ mod tests {
         _cleanup("export_import_works_for_multiple_items2");
     }
 
+    #[test]
+    fn export_import_works_for_record_types() {
+        _cleanup("export_import_works_for_record_types1");
+        _cleanup("export_import_works_for_record_types2");
+        {
+            let wallet = _add_300_records(_wallet("export_import_works_for_record_types1"));
+            let key_data = KeyDerivationData::from_passphrase_with_new_salt(_passphrase(), &KeyDerivationMethod::ARGON2I_MOD);
+            let key = key_data.calc_master_key().unwrap();
+
+            let record_types: Vec<ExportRecordType> = serde_json::from_value(json!([
+                {"type": _type(1)},
+                {"type": _type(2), "query": {"tag_id_5_1": "tag_value_5_1"}},
+            ])).unwrap();
+
+            let mut output: Vec<u8> = Vec::new();
+            export_continue(&wallet, &mut output, _version1(), key, &key_data, Some(&record_types), None).unwrap();
+
+            let wallet = _wallet("export_import_works_for_record_types2");
+            import(&wallet, &mut output.as_slice(), _passphrase()).unwrap();
+
+            let mut records = wallet.get_all().unwrap();
+            let mut ids = Vec::new();
+            while let Some(record) = records.next().unwrap() {
+                ids.push(record.id);
+            }
+
+            assert_eq!(101, ids.len());
+            assert!(ids.contains(&_id(1)));
+            assert!(ids.contains(&_id(5)));
+            assert!(!ids.contains(&_id(0)));
+            assert!(!ids.contains(&_id(2)));
+
+            let record = wallet.get(&_type(2), &_id(5), _options()).unwrap();
+            assert_eq!(record.value.unwrap(), _value(5));
+            assert_eq!(record.tags.unwrap(), _tags(5));
+        }
+        _cleanup("export_import_works_for_record_types1");
+        _cleanup("export_import_works_for_record_types2");
+    }
+
+    #[test]
+    fn export_works_for_duplicated_record_types() {
+        _cleanup("export_works_for_duplicated_record_types");
+        {
+            let wallet = _wallet("export_works_for_duplicated_record_types");
+            let key_data = KeyDerivationData::from_passphrase_with_new_salt(_passphrase(), &KeyDerivationMethod::ARGON2I_MOD);
+            let key = key_data.calc_master_key().unwrap();
+
+            let record_types: Vec<ExportRecordType> = serde_json::from_value(json!([{"type": _type(1)}, {"type": _type(1)}])).unwrap();
+
+            let mut output: Vec<u8> = Vec::new();
+            let res = export_continue(&wallet, &mut output, _version1(), key, &key_data, Some(&record_types), None);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+        _cleanup("export_works_for_duplicated_record_types");
+    }
+
+    #[test]
+    fn export_import_works_for_progress() {
+        _cleanup("export_import_works_for_progress1");
+        _cleanup("export_import_works_for_progress2");
+        {
+            let wallet = _add_300_records(_wallet("export_import_works_for_progress1"));
+            let key_data = KeyDerivationData::from_passphrase_with_new_salt(_passphrase(), &KeyDerivationMethod::ARGON2I_MOD);
+            let key = key_data.calc_master_key().unwrap();
+
+            let reported = RefCell::new(Vec::new());
+            let progress = |count: usize| -> IndyResult<()> {
+                reported.borrow_mut().push(count);
+                Ok(())
+            };
+
+            let mut output: Vec<u8> = Vec::new();
+            export_continue(&wallet, &mut output, _version1(), key, &key_data, None, Some(&progress)).unwrap();
+            assert_eq!(vec![100, 200, 300], *reported.borrow());
+
+            reported.borrow_mut().clear();
+
+            let wallet = _wallet("export_import_works_for_progress2");
+            let (reader, import_key_data, nonce, chunk_size, header_bytes) = preparse_file_to_import(output.as_slice(), _passphrase()).unwrap();
+            let import_key = import_key_data.calc_master_key().unwrap();
+            finish_import(&wallet, reader, import_key, nonce, chunk_size, header_bytes, false, Some(&progress)).unwrap();
+            assert_eq!(vec![100, 200, 300], *reported.borrow());
+            _assert_has_300_records(&wallet);
+        }
+        _cleanup("export_import_works_for_progress1");
+        _cleanup("export_import_works_for_progress2");
+    }
+
+    #[test]
+    fn import_works_for_cancel_and_resume() {
+        _cleanup("import_works_for_cancel_and_resume1");
+        _cleanup("import_works_for_cancel_and_resume2");
+        {
+            let mut output: Vec<u8> = Vec::new();
+            export(&_add_300_records(_wallet("import_works_for_cancel_and_resume1")), &mut output, _passphrase(), _version1(), &KeyDerivationMethod::ARGON2I_MOD).unwrap();
+
+            let wallet = _wallet("import_works_for_cancel_and_resume2");
+
+            let cancel = |_: usize| -> IndyResult<()> {
+                Err(err_msg(IndyErrorKind::InvalidState, "Import cancelled"))
+            };
+
+            let (reader, import_key_data, nonce, chunk_size, header_bytes) = preparse_file_to_import(output.as_slice(), _passphrase()).unwrap();
+            let import_key = import_key_data.calc_master_key().unwrap();
+            let res = finish_import(&wallet, reader, import_key, nonce, chunk_size, header_bytes, false, Some(&cancel));
+            assert_kind!(IndyErrorKind::InvalidState, res);
+            assert!(wallet.get_all().unwrap().next().unwrap().is_some());
+
+            let res = import(&wallet, &mut output.as_slice(), _passphrase());
+            assert_kind!(IndyErrorKind::WalletItemAlreadyExists, res);
+
+            let (reader, import_key_data, nonce, chunk_size, header_bytes) = preparse_file_to_import(output.as_slice(), _passphrase()).unwrap();
+            let import_key = import_key_data.calc_master_key().unwrap();
+            finish_import(&wallet, reader, import_key, nonce, chunk_size, header_bytes, true, None).unwrap();
+            _assert_has_300_records(&wallet);
+        }
+        _cleanup("import_works_for_cancel_and_resume1");
+        _cleanup("import_works_for_cancel_and_resume2");
+    }
+
     #[test]
     fn import_works_for_empty() {
         _cleanup("import_works_for_empty");
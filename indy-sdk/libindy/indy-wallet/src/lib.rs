@@ -11,11 +11,12 @@ extern crate serde;
 #[macro_use]
 extern crate serde_derive;
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::io;
 use std::io::BufReader;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
 use serde_json::Value as SValue;
@@ -28,9 +29,11 @@ pub use crate::encryption::KeyDerivationData;
 use indy_utils::crypto::chacha20poly1305_ietf;
 use indy_utils::crypto::chacha20poly1305_ietf::Key as MasterKey;
 
-use self::export_import::{export_continue, finish_import, preparse_file_to_import};
+use self::export_import::{export_continue, import_records, open_import_reader, preparse_file_to_import};
+pub use self::storage::SchemaMigration;
 use self::storage::{WalletStorage, WalletStorageType};
 use self::storage::default::SQLiteStorageType;
+use self::storage::inmemory::InMemoryStorageType;
 use self::storage::plugged::PluggedStorageType;
 use self::wallet::{Keys, Wallet};
 use indy_api_types::{WalletHandle};
@@ -49,7 +52,7 @@ pub struct WalletService {
     wallets: RefCell<HashMap<WalletHandle, Box<Wallet>>>,
     wallet_ids: RefCell<HashSet<String>>,
     pending_for_open: RefCell<HashMap<WalletHandle, (String /* id */, Box<dyn WalletStorage>, Metadata, Option<KeyDerivationData>)>>,
-    pending_for_import: RefCell<HashMap<WalletHandle, (BufReader<::std::fs::File>, chacha20poly1305_ietf::Nonce, usize, Vec<u8>, KeyDerivationData)>>,
+    pending_for_import: RefCell<HashMap<WalletHandle, (BufReader<::std::fs::File>, chacha20poly1305_ietf::Nonce, usize, Vec<u8>, KeyDerivationData, Option<(Box<dyn WalletStorage>, Metadata)> /* resumed */)>>,
 }
 
 impl WalletService {
@@ -57,6 +60,7 @@ impl WalletService {
         let storage_types = {
             let mut map: HashMap<String, Box<dyn WalletStorageType>> = HashMap::new();
             map.insert("default".to_string(), Box::new(SQLiteStorageType::new()));
+            map.insert("inmemory".to_string(), Box::new(InMemoryStorageType::new()));
             RefCell::new(map)
         };
 
@@ -189,6 +193,26 @@ impl WalletService {
         Ok(())
     }
 
+    // Dry run of schema migrations that will be applied to wallet storage on the next opening
+    pub fn check_wallet_migrations(&self, config: &Config, credentials: &Credentials) -> IndyResult<Vec<SchemaMigration>> {
+        trace!("check_wallet_migrations >>> config: {:?}, credentials: {:?}", config, secret!(credentials));
+
+        let storage_types = self.storage_types.borrow();
+
+        let (storage_type, storage_config, storage_credentials) = WalletService::_get_config_and_cred_for_storage(config, credentials, &storage_types)?;
+
+        let res = storage_type.check_migrations(&config.id,
+                                                storage_config
+                                                    .as_ref()
+                                                    .map(String::as_str),
+                                                storage_credentials
+                                                    .as_ref()
+                                                    .map(String::as_str));
+
+        trace!("check_wallet_migrations <<< res: {:?}", res);
+        res
+    }
+
     pub fn open_wallet_prepare(&self, config: &Config, credentials: &Credentials) -> IndyResult<(WalletHandle, KeyDerivationData, Option<KeyDerivationData>)> {
         trace!("open_wallet >>> config: {:?}, credentials: {:?}", config, secret!(&credentials));
 
@@ -340,6 +364,13 @@ impl WalletService {
         }
     }
 
+    pub fn batch_records(&self, wallet_handle: WalletHandle, operations: &[WalletRecordOperation]) -> IndyResult<()> {
+        match self.wallets.borrow().get(&wallet_handle) {
+            Some(wallet) => wallet.batch(operations),
+            None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
+        }
+    }
+
     pub fn delete_indy_record<T>(&self, wallet_handle: WalletHandle, name: &str) -> IndyResult<()> where T: Sized {
         self.delete_record(wallet_handle, &self.add_prefix(short_type_name::<T>()), name)
     }
@@ -395,6 +426,49 @@ impl WalletService {
         }
     }
 
+    pub fn search_records_page(&self,
+                               wallet_handle: WalletHandle,
+                               type_: &str,
+                               query_json: &str,
+                               options_json: &str,
+                               cursor: Option<&str>,
+                               limit: usize) -> IndyResult<WalletSearchPage> {
+        if limit == 0 {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Page limit must be greater than 0"));
+        }
+
+        match self.wallets.borrow().get(&wallet_handle) {
+            Some(wallet) => wallet.search_page(type_, query_json, Some(options_json), cursor, limit),
+            None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
+        }
+    }
+
+    pub fn count_records(&self, wallet_handle: WalletHandle, type_: &str, query_json: &str) -> IndyResult<usize> {
+        match self.wallets.borrow().get(&wallet_handle) {
+            Some(wallet) => wallet.count(type_, query_json),
+            None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
+        }
+    }
+
+    pub fn group_count_by_tag(&self, wallet_handle: WalletHandle, type_: &str, query_json: &str, tag_name: &str) -> IndyResult<HashMap<String, usize>> {
+        match self.wallets.borrow().get(&wallet_handle) {
+            Some(wallet) => Ok(wallet.group_count_by_tag(type_, query_json, tag_name)?.into_iter().collect()),
+            None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
+        }
+    }
+
+    pub fn verify_wallet(&self, wallet_handle: WalletHandle, repair: bool) -> IndyResult<WalletVerificationReport> {
+        trace!("verify_wallet >>> wallet_handle: {:?}, repair: {:?}", wallet_handle, repair);
+
+        let report = match self.wallets.borrow().get(&wallet_handle) {
+            Some(wallet) => wallet.verify(repair),
+            None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
+        }?;
+
+        trace!("verify_wallet <<< report: {:?}", report);
+        Ok(report)
+    }
+
     pub fn search_indy_records<T>(&self, wallet_handle: WalletHandle, query_json: &str, options_json: &str) -> IndyResult<WalletSearch> where T: Sized {
         self.search_records(wallet_handle, &self.add_prefix(short_type_name::<T>()), query_json, options_json)
     }
@@ -417,6 +491,44 @@ impl WalletService {
         }
     }
 
+    // Operations on typed objects that can be applied together with batch_records
+    pub fn add_indy_object_operation<T>(&self, name: &str, object: &T, tags: &Tags) -> IndyResult<WalletRecordOperation>
+        where T: ::serde::Serialize + Sized {
+        Ok(WalletRecordOperation::Add {
+            type_: self.add_prefix(short_type_name::<T>()),
+            id: name.to_string(),
+            value: serde_json::to_string(object)
+                .to_indy(IndyErrorKind::InvalidState, format!("Cannot serialize {:?}", short_type_name::<T>()))?,
+            tags: if tags.is_empty() { None } else { Some(tags.clone()) },
+        })
+    }
+
+    pub fn update_indy_object_operation<T>(&self, name: &str, object: &T) -> IndyResult<WalletRecordOperation>
+        where T: ::serde::Serialize + Sized {
+        Ok(WalletRecordOperation::Update {
+            type_: self.add_prefix(short_type_name::<T>()),
+            id: name.to_string(),
+            value: serde_json::to_string(object)
+                .to_indy(IndyErrorKind::InvalidState, format!("Cannot serialize {:?}", short_type_name::<T>()))?,
+        })
+    }
+
+    pub fn upsert_indy_object_operation<T>(&self, wallet_handle: WalletHandle, name: &str, object: &T) -> IndyResult<WalletRecordOperation>
+        where T: ::serde::Serialize + Sized {
+        if self.record_exists::<T>(wallet_handle, name)? {
+            self.update_indy_object_operation::<T>(name, object)
+        } else {
+            self.add_indy_object_operation::<T>(name, object, &HashMap::new())
+        }
+    }
+
+    pub fn delete_indy_object_operation<T>(&self, name: &str) -> WalletRecordOperation where T: Sized {
+        WalletRecordOperation::Delete {
+            type_: self.add_prefix(short_type_name::<T>()),
+            id: name.to_string(),
+        }
+    }
+
     pub fn record_exists<T>(&self, wallet_handle: WalletHandle, name: &str) -> IndyResult<bool> where T: Sized {
         match self.wallets.borrow().get(&wallet_handle) {
             Some(wallet) =>
@@ -436,7 +548,12 @@ impl WalletService {
         }
     }
 
-    pub fn export_wallet(&self, wallet_handle: WalletHandle, export_config: &ExportConfig, version: u32, key: (&KeyDerivationData, &MasterKey)) -> IndyResult<()> {
+    pub fn export_wallet(&self,
+                         wallet_handle: WalletHandle,
+                         export_config: &ExportConfig,
+                         version: u32,
+                         key: (&KeyDerivationData, &MasterKey),
+                         progress: Option<&dyn Fn(usize) -> IndyResult<()>>) -> IndyResult<()> {
         trace!("export_wallet >>> wallet_handle: {:?}, export_config: {:?}, version: {:?}", wallet_handle, secret!(export_config), version);
 
         if version != 0 {
@@ -458,13 +575,33 @@ impl WalletService {
                 .create(parent_path)?;
         }
 
-        let mut export_file =
-            fs::OpenOptions::new()
-                .write(true)
-                .create_new(true)
-                .open(export_config.path.clone())?;
+        if path.exists() {
+            return Err(err_msg(IndyErrorKind::IOError, format!("Export file already exists: {:?}", path)));
+        }
+
+        // Export is written to temporary file first and moved to the requested path
+        // only on success, so failed or cancelled export never leaves partial file
+        let mut tmp_path = path.clone().into_os_string();
+        tmp_path.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_path);
+
+        // Temporary file is created exclusively, so concurrent export to the same path fails
+        // instead of overwriting the file of another export
+        let mut export_file = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&tmp_path)
+            .map_err(|err| match err.kind() {
+                io::ErrorKind::AlreadyExists => err_msg(IndyErrorKind::IOError, format!("Export temporary file already exists: {:?}", tmp_path)),
+                _ => IndyError::from(err)
+            })?;
+
+        let res = export_continue(wallet, &mut export_file, version, key.clone(), key_data, export_config.record_types.as_ref().map(Vec::as_slice), progress)
+            .and_then(|_| export_file.sync_all().map_err(IndyError::from))
+            .and_then(|_| _move_export_file(&tmp_path, &path));
 
-        let res = export_continue(wallet, &mut export_file, version, key.clone(), key_data);
+        drop(export_file);
+        fs::remove_file(&tmp_path).ok();
 
         trace!("export_wallet <<<");
 
@@ -483,47 +620,142 @@ impl WalletService {
                 .open(&export_config.path)?;
 
         let (reader, import_key_derivation_data, nonce, chunk_size, header_bytes) = preparse_file_to_import(exported_file_to_import, &export_config.key)?;
-        let key_data = KeyDerivationData::from_passphrase_with_new_salt(&credentials.key, &credentials.key_derivation_method);
+        let (key_data, resumed) = self._prepare_import_target(config, credentials, export_config)?;
 
         let wallet_handle = indy_utils::next_wallet_handle();
 
         let stashed_key_data = key_data.clone();
 
-        self.pending_for_import.borrow_mut().insert(wallet_handle, (reader, nonce, chunk_size, header_bytes, stashed_key_data));
+        self.pending_for_import.borrow_mut().insert(wallet_handle, (reader, nonce, chunk_size, header_bytes, stashed_key_data, resumed));
 
         Ok((wallet_handle, key_data, import_key_derivation_data))
     }
 
-    pub fn import_wallet_continue(&self, wallet_handle: WalletHandle, config: &Config, credentials: &Credentials, key: (MasterKey, MasterKey)) -> IndyResult<()> {
-        let (reader, nonce, chunk_size, header_bytes, key_data) = self.pending_for_import.borrow_mut().remove(&wallet_handle).unwrap();
+    pub fn import_wallet_continue(&self,
+                                  wallet_handle: WalletHandle,
+                                  config: &Config,
+                                  credentials: &Credentials,
+                                  key: (MasterKey, MasterKey),
+                                  progress: Option<&dyn Fn(usize) -> IndyResult<()>>) -> IndyResult<()> {
+        let (reader, nonce, chunk_size, header_bytes, key_data, resumed) = self.pending_for_import.borrow_mut().remove(&wallet_handle).unwrap();
 
         let (import_key, master_key) = key;
 
+        // Export key and header are checked before the wallet is created,
+        // so import with wrong key or of corrupted file doesn't leave wallet behind
+        let mut reader = open_import_reader(reader, import_key, nonce, chunk_size, &header_bytes)?;
+
+        if let Some((storage, metadata)) = resumed {
+            // Records already imported by interrupted import are skipped and wallet is kept on failure,
+            // so import can be resumed again
+            let keys = self._restore_keys(&metadata, &master_key)?;
+            let wallet = Wallet::new(WalletService::_get_wallet_id(&config), storage, Rc::new(keys));
+
+            let mut records_count: usize = 0;
+            let res = import_records(&wallet, &mut reader, true, progress, &mut records_count);
+
+            trace!("import_wallet <<<");
+            return res;
+        }
+
         let keys = self._create_wallet(config, credentials, (&key_data, &master_key))?;
 
         self._is_id_from_config_not_used(config)?;
         let storage = self._open_storage(config, credentials)?;
         let metadata = storage.get_storage_metadata()?;
 
+        let cancelled = Cell::new(false);
+        let report_progress = |records_count: usize| -> IndyResult<()> {
+            progress
+                .map(|progress| progress(records_count))
+                .unwrap_or(Ok(()))
+                .map_err(|err| {
+                    cancelled.set(true);
+                    err
+                })
+        };
+
+        let mut records_count: usize = 0;
+
         let res = {
             let wallet = Wallet::new(WalletService::_get_wallet_id(&config), storage, Rc::new(keys));
 
-            finish_import(&wallet, reader, import_key, nonce, chunk_size, header_bytes)
+            import_records(&wallet, &mut reader, false, Some(&report_progress), &mut records_count)
         };
 
-        if res.is_err() {
+        // Wallet is kept if import was cancelled or some records were imported, so import can be resumed.
+        // Otherwise it is deleted and plain import can be just retried.
+        if res.is_err() && !cancelled.get() && records_count == 0 {
             let metadata: Metadata = serde_json::from_slice(&metadata)
                 .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize metadata")?;
 
             self.delete_wallet_continue(config, credentials, &metadata, &master_key)?;
         }
 
-        //        self.close_wallet(wallet_handle)?;
-
         trace!("import_wallet <<<");
         res
     }
 
+    fn _prepare_import_target(&self,
+                              config: &Config,
+                              credentials: &Credentials,
+                              export_config: &ExportConfig) -> IndyResult<(KeyDerivationData, Option<(Box<dyn WalletStorage>, Metadata)>)> {
+        if export_config.resume {
+            self._is_id_from_config_not_used(config)?;
+
+            match self._open_storage_and_fetch_metadata(config, credentials) {
+                Ok((storage, metadata, key_data)) => return Ok((key_data, Some((storage, metadata)))),
+                Err(ref err) if err.kind() == IndyErrorKind::WalletNotFound => (),
+                Err(err) => return Err(err)
+            }
+        }
+
+        let key_data = KeyDerivationData::from_passphrase_with_new_salt(&credentials.key, &credentials.key_derivation_method);
+        Ok((key_data, None))
+    }
+
+    pub fn rotate_keys_prepare(&self, wallet_handle: WalletHandle, credentials: &Credentials) -> IndyResult<KeyDerivationData> {
+        trace!("rotate_keys_prepare >>> wallet_handle: {:?}, credentials: {:?}", wallet_handle, secret!(credentials));
+
+        let wallets = self.wallets.borrow();
+        let wallet = wallets
+            .get(&wallet_handle)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))?;
+
+        let metadata: Metadata = serde_json::from_slice(&wallet.get_storage_metadata()?)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize metadata")?;
+
+        let res = KeyDerivationData::from_passphrase_and_metadata(&credentials.key, &metadata, &credentials.key_derivation_method)?;
+
+        trace!("rotate_keys_prepare <<<");
+        Ok(res)
+    }
+
+    pub fn rotate_keys(&self, wallet_handle: WalletHandle, key: (&KeyDerivationData, &MasterKey)) -> IndyResult<()> {
+        trace!("rotate_keys >>> wallet_handle: {:?}", wallet_handle);
+
+        let (key_data, master_key) = key;
+
+        let mut wallets = self.wallets.borrow_mut();
+        let wallet = wallets
+            .get_mut(&wallet_handle)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))?;
+
+        // Master key must be checked before re-encryption as it is used to protect new keys
+        let metadata: Metadata = serde_json::from_slice(&wallet.get_storage_metadata()?)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize metadata")?;
+
+        self._restore_keys(&metadata, master_key)?;
+
+        let keys = Keys::new();
+        let metadata = self._prepare_metadata(master_key, key_data, &keys)?;
+
+        wallet.rotate_keys(keys, &metadata)?;
+
+        trace!("rotate_keys <<<");
+        Ok(())
+    }
+
     pub fn get_wallets_count(&self) -> usize {
         self.wallets.borrow().len()
     }
@@ -700,10 +932,107 @@ impl WalletRecord {
     }
 }
 
+// Hard link fails if the requested path was created meanwhile, unlike rename that silently replaces it.
+// File systems without hard links (FAT, exFAT, some network shares) fall back to rename after an existence check,
+// so there a file created at the requested path between the check and the rename is overwritten.
+fn _move_export_file(tmp_path: &Path, path: &Path) -> IndyResult<()> {
+    match fs::hard_link(tmp_path, path) {
+        Ok(()) => Ok(()),
+        Err(ref err) if err.kind() == io::ErrorKind::AlreadyExists => {
+            Err(err_msg(IndyErrorKind::IOError, format!("Export file already exists: {:?}", path)))
+        }
+        Err(ref err) if _is_hard_link_unsupported(err) => {
+            if path.exists() {
+                return Err(err_msg(IndyErrorKind::IOError, format!("Export file already exists: {:?}", path)));
+            }
+
+            fs::rename(tmp_path, path)?;
+            Ok(())
+        }
+        Err(err) => Err(err.into())
+    }
+}
+
+fn _is_hard_link_unsupported(err: &io::Error) -> bool {
+    match err.raw_os_error() {
+        #[cfg(unix)]
+        Some(code) => code == libc::EPERM || code == libc::EOPNOTSUPP || code == libc::ENOSYS,
+        // ERROR_INVALID_FUNCTION and ERROR_NOT_SUPPORTED
+        #[cfg(windows)]
+        Some(code) => code == 1 || code == 50,
+        _ => false
+    }
+}
+
 fn default_true() -> bool { true }
 
 fn default_false() -> bool { false }
 
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum WalletRecordOperation {
+    Add {
+        #[serde(rename = "type")]
+        type_: String,
+        id: String,
+        value: String,
+        tags: Option<Tags>,
+    },
+    Update {
+        #[serde(rename = "type")]
+        type_: String,
+        id: String,
+        value: String,
+    },
+    AddTags {
+        #[serde(rename = "type")]
+        type_: String,
+        id: String,
+        tags: Tags,
+    },
+    UpdateTags {
+        #[serde(rename = "type")]
+        type_: String,
+        id: String,
+        tags: Tags,
+    },
+    DeleteTags {
+        #[serde(rename = "type")]
+        type_: String,
+        id: String,
+        tag_names: Vec<String>,
+    },
+    Delete {
+        #[serde(rename = "type")]
+        type_: String,
+        id: String,
+    },
+}
+
+impl WalletRecordOperation {
+    pub fn get_type(&self) -> &str {
+        match *self {
+            WalletRecordOperation::Add { ref type_, .. } |
+            WalletRecordOperation::Update { ref type_, .. } |
+            WalletRecordOperation::AddTags { ref type_, .. } |
+            WalletRecordOperation::UpdateTags { ref type_, .. } |
+            WalletRecordOperation::DeleteTags { ref type_, .. } |
+            WalletRecordOperation::Delete { ref type_, .. } => type_.as_str(),
+        }
+    }
+
+    pub fn get_id(&self) -> &str {
+        match *self {
+            WalletRecordOperation::Add { ref id, .. } |
+            WalletRecordOperation::Update { ref id, .. } |
+            WalletRecordOperation::AddTags { ref id, .. } |
+            WalletRecordOperation::UpdateTags { ref id, .. } |
+            WalletRecordOperation::DeleteTags { ref id, .. } |
+            WalletRecordOperation::Delete { ref id, .. } => id.as_str(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct RecordOptions {
@@ -761,6 +1090,25 @@ impl WalletSearch {
     }
 }
 
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletSearchPage {
+    pub total_count: Option<usize>,
+    pub records: Option<Vec<WalletRecord>>,
+    // Cursor to fetch the next page, None if there are no more records
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletVerificationReport {
+    pub records_count: usize,
+    pub unreadable_records: usize,
+    // None if the storage doesn't support the check
+    pub orphaned_tags: Option<usize>,
+    pub repaired: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct SearchOptions {
@@ -774,6 +1122,34 @@ pub struct SearchOptions {
     retrieve_value: bool,
     #[serde(default = "default_false")]
     retrieve_tags: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sort_by: Option<Vec<SortBy>>,
+}
+
+///
+/// Sorting of search results.
+/// `field` is either "insertion_order" (the order records were added in; record ids are stored
+/// encrypted, so they can't be sorted by) or the name of unencrypted ("~" prefixed) tag.
+/// Records without the tag go first in ascending order. Plugged storages don't support sorting.
+///
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SortBy {
+    pub field: String,
+    #[serde(default)]
+    pub direction: SortDirection,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+impl Default for SortDirection {
+    fn default() -> SortDirection {
+        SortDirection::Asc
+    }
 }
 
 impl SearchOptions {
@@ -784,6 +1160,7 @@ impl SearchOptions {
             retrieve_type: true,
             retrieve_value: true,
             retrieve_tags: false,
+            sort_by: None,
         };
 
         serde_json::to_string(&options).unwrap()
@@ -798,6 +1175,7 @@ impl Default for SearchOptions {
             retrieve_type: false,
             retrieve_value: true,
             retrieve_tags: false,
+            sort_by: None,
         }
     }
 }
@@ -861,6 +1239,14 @@ mod tests {
                              config: &Config,
                              credentials: &Credentials,
                              export_config: &ExportConfig) -> IndyResult<()> {
+            self.import_wallet_with_progress(config, credentials, export_config, None)
+        }
+
+        pub fn import_wallet_with_progress(&self,
+                                           config: &Config,
+                                           credentials: &Credentials,
+                                           export_config: &ExportConfig,
+                                           progress: Option<&dyn Fn(usize) -> IndyResult<()>>) -> IndyResult<()> {
             trace!("import_wallet_prepare >>> config: {:?}, credentials: {:?}, export_config: {:?}", config, secret!(export_config), secret!(export_config));
 
             let exported_file_to_import =
@@ -869,16 +1255,16 @@ mod tests {
                     .open(&export_config.path)?;
 
             let (reader, import_key_derivation_data, nonce, chunk_size, header_bytes) = preparse_file_to_import(exported_file_to_import, &export_config.key)?;
-            let key_data = KeyDerivationData::from_passphrase_with_new_salt(&credentials.key, &credentials.key_derivation_method);
+            let (key_data, resumed) = self._prepare_import_target(config, credentials, export_config)?;
 
             let wallet_handle = next_wallet_handle();
 
             let import_key = import_key_derivation_data.calc_master_key()?;
             let master_key = key_data.calc_master_key()?;
 
-            self.pending_for_import.borrow_mut().insert(wallet_handle, (reader, nonce, chunk_size, header_bytes, key_data));
+            self.pending_for_import.borrow_mut().insert(wallet_handle, (reader, nonce, chunk_size, header_bytes, key_data, resumed));
 
-            self.import_wallet_continue(wallet_handle, config, credentials, (import_key, master_key))
+            self.import_wallet_continue(wallet_handle, config, credentials, (import_key, master_key), progress)
         }
 
         pub fn delete_wallet(&self, config: &Config, credentials: &Credentials) -> IndyResult<()> {
@@ -1111,6 +1497,43 @@ mod tests {
         assert_eq!(IndyErrorKind::WalletNotFound, res.unwrap_err().kind());
     }
 
+    #[test]
+    fn wallet_service_check_wallet_migrations_works() {
+        test::cleanup_wallet("wallet_service_check_wallet_migrations_works");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_check_wallet_migrations_works"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+
+            let migrations = wallet_service.check_wallet_migrations(&_config("wallet_service_check_wallet_migrations_works"), &RAW_CREDENTIAL).unwrap();
+            assert!(migrations.is_empty());
+        }
+        test::cleanup_wallet("wallet_service_check_wallet_migrations_works");
+    }
+
+    #[test]
+    fn wallet_service_check_wallet_migrations_works_for_plugged() {
+        _cleanup("wallet_service_check_wallet_migrations_works_for_plugged");
+
+        let wallet_service = WalletService::new();
+        _register_inmem_wallet(&wallet_service);
+
+        wallet_service.create_wallet(&_config_inmem(), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+
+        let migrations = wallet_service.check_wallet_migrations(&_config_inmem(), &RAW_CREDENTIAL).unwrap();
+        assert!(migrations.is_empty());
+
+        _cleanup("wallet_service_check_wallet_migrations_works_for_plugged");
+    }
+
+    #[test]
+    fn wallet_service_check_wallet_migrations_works_for_nonexistent_wallet() {
+        test::cleanup_wallet("wallet_service_check_wallet_migrations_works_for_nonexistent_wallet");
+
+        let wallet_service = WalletService::new();
+        let res = wallet_service.check_wallet_migrations(&_config("wallet_service_check_wallet_migrations_works_for_nonexistent_wallet"), &RAW_CREDENTIAL);
+        assert_kind!(IndyErrorKind::WalletNotFound, res);
+    }
+
     #[test]
     fn wallet_service_open_wallet_works() {
         test::cleanup_wallet("wallet_service_open_wallet_works");
@@ -1788,115 +2211,461 @@ mod tests {
         assert!(search.fetch_next_record().unwrap().is_none());
     }
 
-    /**
-        Key rotation test
-    */
     #[test]
-    fn wallet_service_key_rotation() {
-        test::cleanup_wallet("wallet_service_key_rotation");
+    fn wallet_service_search_records_page_works() {
+        test::cleanup_wallet("wallet_service_search_records_page_works");
         {
-            let config: &Config = &_config("wallet_service_key_rotation");
             let wallet_service = WalletService::new();
-            wallet_service.create_wallet(config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
-            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+            wallet_service.create_wallet(&_config("wallet_service_search_records_page_works"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_search_records_page_works"), &RAW_CREDENTIAL).unwrap();
 
-            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
-            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(true, true, true)).unwrap();
-            assert_eq!("type", record.get_type().unwrap());
-            assert_eq!("value1", record.get_value().unwrap());
+            _assert_search_records_page_works(&wallet_service, wallet_handle);
+        }
+        test::cleanup_wallet("wallet_service_search_records_page_works");
+    }
 
-            wallet_service.close_wallet(wallet_handle).unwrap();
+    #[test]
+    fn wallet_service_search_records_page_works_for_plugged_wallet() {
+        _cleanup("wallet_service_search_records_page_works_for_plugged_wallet");
 
-            let wallet_handle = wallet_service.open_wallet(config, &_rekey_credentials_moderate()).unwrap();
-            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(true, true, true)).unwrap();
-            assert_eq!("type", record.get_type().unwrap());
-            assert_eq!("value1", record.get_value().unwrap());
-            wallet_service.close_wallet(wallet_handle).unwrap();
+        let wallet_service = WalletService::new();
+        _register_inmem_wallet(&wallet_service);
 
-            // Access failed for old key
-            let res = wallet_service.open_wallet(config, &RAW_CREDENTIAL);
-            assert_kind!(IndyErrorKind::WalletAccessFailed, res);
+        wallet_service.create_wallet(&_config_inmem(), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+        let wallet_handle = wallet_service.open_wallet(&_config_inmem(), &RAW_CREDENTIAL).unwrap();
 
-            // Works ok with new key when reopening
-            let wallet_handle = wallet_service.open_wallet(config, &_credentials_for_new_key_moderate()).unwrap();
-            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(true, true, true)).unwrap();
-            assert_eq!("type", record.get_type().unwrap());
-            assert_eq!("value1", record.get_value().unwrap());
-        }
-        test::cleanup_wallet("wallet_service_key_rotation");
+        _assert_search_records_page_works(&wallet_service, wallet_handle);
+
+        _cleanup("wallet_service_search_records_page_works_for_plugged_wallet");
     }
 
     #[test]
-    fn wallet_service_key_rotation_for_rekey_interactive_method() {
-        test::cleanup_wallet("wallet_service_key_rotation_for_rekey_interactive_method");
+    fn wallet_service_search_records_page_works_for_invalid_cursor_and_limit() {
+        test::cleanup_wallet("wallet_service_search_records_page_works_for_invalid_cursor_and_limit");
         {
-            let config: &Config = &_config("wallet_service_key_rotation_for_rekey_interactive_method");
             let wallet_service = WalletService::new();
-            wallet_service.create_wallet(config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
-            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+            wallet_service.create_wallet(&_config("wallet_service_search_records_page_works_for_invalid_cursor_and_limit"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_search_records_page_works_for_invalid_cursor_and_limit"), &RAW_CREDENTIAL).unwrap();
 
             wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
-            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(true, true, true)).unwrap();
-            assert_eq!("type", record.get_type().unwrap());
-            assert_eq!("value1", record.get_value().unwrap());
 
-            wallet_service.close_wallet(wallet_handle).unwrap();
-
-            let wallet_handle = wallet_service.open_wallet(config, &_rekey_credentials_interactive()).unwrap();
-            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(true, true, true)).unwrap();
-            assert_eq!("type", record.get_type().unwrap());
-            assert_eq!("value1", record.get_value().unwrap());
-            wallet_service.close_wallet(wallet_handle).unwrap();
-
-            // Access failed for old key
-            let res = wallet_service.open_wallet(config, &RAW_CREDENTIAL);
-            assert_kind!(IndyErrorKind::WalletAccessFailed, res);
+            let res = wallet_service.search_records_page(wallet_handle, "type", "{}", "{}", Some("not a cursor"), 10);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
 
-            // Works ok with new key when reopening
-            let wallet_handle = wallet_service.open_wallet(config, &_credentials_for_new_key_interactive()).unwrap();
-            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(true, true, true)).unwrap();
-            assert_eq!("type", record.get_type().unwrap());
-            assert_eq!("value1", record.get_value().unwrap());
+            let res = wallet_service.search_records_page(wallet_handle, "type", "{}", "{}", None, 0);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
         }
-        test::cleanup_wallet("wallet_service_key_rotation_for_rekey_interactive_method");
+        test::cleanup_wallet("wallet_service_search_records_page_works_for_invalid_cursor_and_limit");
     }
 
     #[test]
-    fn wallet_service_key_rotation_for_rekey_raw_method() {
-        test::cleanup_wallet("wallet_service_key_rotation_for_rekey_raw_method");
+    fn wallet_service_search_records_works_for_sort_by() {
+        test::cleanup_wallet("wallet_service_search_records_works_for_sort_by");
         {
-            let config: &Config = &_config("wallet_service_key_rotation_for_rekey_raw_method");
             let wallet_service = WalletService::new();
-            wallet_service.create_wallet(config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
-            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+            wallet_service.create_wallet(&_config("wallet_service_search_records_works_for_sort_by"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_search_records_works_for_sort_by"), &RAW_CREDENTIAL).unwrap();
 
-            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
-            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(true, true, true)).unwrap();
-            assert_eq!("type", record.get_type().unwrap());
-            assert_eq!("value1", record.get_value().unwrap());
+            _add_records_with_dates(&wallet_service, wallet_handle);
 
-            wallet_service.close_wallet(wallet_handle).unwrap();
+            let options = json!({"sortBy": [{"field": "~date", "direction": "desc"}]}).to_string();
+            let mut search = wallet_service.search_records(wallet_handle, "type", "{}", &options).unwrap();
+            assert_eq!(vec!["key1", "key3", "key2"], _fetch_ids(&mut search));
 
-            let wallet_handle = wallet_service.open_wallet(config, &_rekey_credentials_raw()).unwrap();
-            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(true, true, true)).unwrap();
-            assert_eq!("type", record.get_type().unwrap());
-            assert_eq!("value1", record.get_value().unwrap());
-            wallet_service.close_wallet(wallet_handle).unwrap();
+            let options = json!({"sortBy": [{"field": "~date"}]}).to_string();
+            let mut search = wallet_service.search_records(wallet_handle, "type", "{}", &options).unwrap();
+            assert_eq!(vec!["key2", "key3", "key1"], _fetch_ids(&mut search));
 
-            // Access failed for old key
-            let res = wallet_service.open_wallet(config, &RAW_CREDENTIAL);
-            assert_kind!(IndyErrorKind::WalletAccessFailed, res);
+            let options = json!({"sortBy": [{"field": "insertion_order", "direction": "desc"}]}).to_string();
+            let mut search = wallet_service.search_records(wallet_handle, "type", "{}", &options).unwrap();
+            assert_eq!(vec!["key3", "key2", "key1"], _fetch_ids(&mut search));
+        }
+        test::cleanup_wallet("wallet_service_search_records_works_for_sort_by");
+    }
 
-            // Works ok with new key when reopening
-            let wallet_handle = wallet_service.open_wallet(config, &_credentials_for_new_key_raw()).unwrap();
-            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(true, true, true)).unwrap();
-            assert_eq!("type", record.get_type().unwrap());
-            assert_eq!("value1", record.get_value().unwrap());
+    #[test]
+    fn wallet_service_search_records_works_for_sort_by_encrypted_tag() {
+        test::cleanup_wallet("wallet_service_search_records_works_for_sort_by_encrypted_tag");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_search_records_works_for_sort_by_encrypted_tag"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_search_records_works_for_sort_by_encrypted_tag"), &RAW_CREDENTIAL).unwrap();
+
+            let options = json!({"sortBy": [{"field": "date"}]}).to_string();
+            let res = wallet_service.search_records(wallet_handle, "type", "{}", &options);
+            assert_kind!(IndyErrorKind::WalletQueryError, res);
         }
-        test::cleanup_wallet("wallet_service_key_rotation_for_rekey_raw_method");
+        test::cleanup_wallet("wallet_service_search_records_works_for_sort_by_encrypted_tag");
     }
 
-    fn remove_exported_wallet(export_config: &ExportConfig) -> &Path {
+    #[test]
+    fn wallet_service_search_records_page_works_for_sort_by() {
+        test::cleanup_wallet("wallet_service_search_records_page_works_for_sort_by");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_search_records_page_works_for_sort_by"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_search_records_page_works_for_sort_by"), &RAW_CREDENTIAL).unwrap();
+
+            _add_records_with_dates(&wallet_service, wallet_handle);
+
+            let options = json!({"sortBy": [{"field": "~date", "direction": "desc"}]}).to_string();
+
+            let page = wallet_service.search_records_page(wallet_handle, "type", "{}", &options, None, 2).unwrap();
+            let ids: Vec<&str> = page.records.as_ref().unwrap().iter().map(|record| record.get_id()).collect();
+            assert_eq!(vec!["key1", "key3"], ids);
+            assert!(page.cursor.is_some());
+
+            let page = wallet_service.search_records_page(wallet_handle, "type", "{}", &options, page.cursor.as_ref().map(String::as_str), 2).unwrap();
+            let ids: Vec<&str> = page.records.as_ref().unwrap().iter().map(|record| record.get_id()).collect();
+            assert_eq!(vec!["key2"], ids);
+            assert!(page.cursor.is_none());
+        }
+        test::cleanup_wallet("wallet_service_search_records_page_works_for_sort_by");
+    }
+
+    #[test]
+    fn wallet_service_count_records_works() {
+        test::cleanup_wallet("wallet_service_count_records_works");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_count_records_works"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_count_records_works"), &RAW_CREDENTIAL).unwrap();
+
+            _add_records_with_dates(&wallet_service, wallet_handle);
+            wallet_service.add_record(wallet_handle, "type2", "key4", "value4", &HashMap::new()).unwrap();
+
+            assert_eq!(3, wallet_service.count_records(wallet_handle, "type", "{}").unwrap());
+            assert_eq!(2, wallet_service.count_records(wallet_handle, "type", r#"{"~date": {"$gt": "2020-01-01"}}"#).unwrap());
+            assert_eq!(0, wallet_service.count_records(wallet_handle, "type3", "{}").unwrap());
+        }
+        test::cleanup_wallet("wallet_service_count_records_works");
+    }
+
+    #[test]
+    fn wallet_service_group_count_by_tag_works() {
+        test::cleanup_wallet("wallet_service_group_count_by_tag_works");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_group_count_by_tag_works"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_group_count_by_tag_works"), &RAW_CREDENTIAL).unwrap();
+
+            _add_records_for_grouping(&wallet_service, wallet_handle);
+
+            let groups = wallet_service.group_count_by_tag(wallet_handle, "type", "{}", "~cred_def_id").unwrap();
+            assert_eq!(2, groups.len());
+            assert_eq!(2, groups["cred_def_1"]);
+            assert_eq!(1, groups["cred_def_2"]);
+
+            let groups = wallet_service.group_count_by_tag(wallet_handle, "type", r#"{"~cred_def_id": "cred_def_2"}"#, "~cred_def_id").unwrap();
+            assert_eq!(1, groups.len());
+            assert_eq!(1, groups["cred_def_2"]);
+
+            let res = wallet_service.group_count_by_tag(wallet_handle, "type", "{}", "cred_def_id");
+            assert_kind!(IndyErrorKind::WalletQueryError, res);
+        }
+        test::cleanup_wallet("wallet_service_group_count_by_tag_works");
+    }
+
+    #[test]
+    fn wallet_service_count_records_works_for_plugged_wallet() {
+        _cleanup("wallet_service_count_records_works_for_plugged_wallet");
+
+        let wallet_service = WalletService::new();
+        _register_inmem_wallet(&wallet_service);
+
+        wallet_service.create_wallet(&_config_inmem(), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+        let wallet_handle = wallet_service.open_wallet(&_config_inmem(), &RAW_CREDENTIAL).unwrap();
+
+        _add_records_for_grouping(&wallet_service, wallet_handle);
+
+        assert_eq!(4, wallet_service.count_records(wallet_handle, "type", "{}").unwrap());
+
+        let groups = wallet_service.group_count_by_tag(wallet_handle, "type", "{}", "~cred_def_id").unwrap();
+        assert_eq!(2, groups.len());
+        assert_eq!(2, groups["cred_def_1"]);
+        assert_eq!(1, groups["cred_def_2"]);
+
+        _cleanup("wallet_service_count_records_works_for_plugged_wallet");
+    }
+
+    fn _add_records_for_grouping(wallet_service: &WalletService, wallet_handle: WalletHandle) {
+        let tags = |cred_def_id: &str| serde_json::from_str::<Tags>(&json!({"~cred_def_id": cred_def_id}).to_string()).unwrap();
+
+        wallet_service.add_record(wallet_handle, "type", "key1", "value1", &tags("cred_def_1")).unwrap();
+        wallet_service.add_record(wallet_handle, "type", "key2", "value2", &tags("cred_def_1")).unwrap();
+        wallet_service.add_record(wallet_handle, "type", "key3", "value3", &tags("cred_def_2")).unwrap();
+        wallet_service.add_record(wallet_handle, "type", "key4", "value4", &HashMap::new()).unwrap();
+    }
+
+    fn _add_records_with_dates(wallet_service: &WalletService, wallet_handle: WalletHandle) {
+        let tags = |date: &str| serde_json::from_str::<Tags>(&json!({"~date": date, "tag": "value"}).to_string()).unwrap();
+
+        wallet_service.add_record(wallet_handle, "type", "key1", "value1", &tags("2020-03-01")).unwrap();
+        wallet_service.add_record(wallet_handle, "type", "key2", "value2", &tags("2020-01-01")).unwrap();
+        wallet_service.add_record(wallet_handle, "type", "key3", "value3", &tags("2020-02-01")).unwrap();
+    }
+
+    fn _fetch_ids(search: &mut WalletSearch) -> Vec<String> {
+        let mut ids = Vec::new();
+        while let Some(record) = search.fetch_next_record().unwrap() {
+            ids.push(record.get_id().to_string());
+        }
+        ids
+    }
+
+    fn _assert_search_records_page_works(wallet_service: &WalletService, wallet_handle: WalletHandle) {
+        wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+        wallet_service.add_record(wallet_handle, "type", "key2", "value2", &HashMap::new()).unwrap();
+        wallet_service.add_record(wallet_handle, "type", "key3", "value3", &HashMap::new()).unwrap();
+        wallet_service.add_record(wallet_handle, "type2", "key4", "value4", &HashMap::new()).unwrap();
+
+        let options = json!({"retrieveTotalCount": true, "retrieveValue": true}).to_string();
+
+        let page = wallet_service.search_records_page(wallet_handle, "type", "{}", &options, None, 2).unwrap();
+        assert_eq!(Some(3), page.total_count);
+        assert_eq!(2, page.records.as_ref().unwrap().len());
+        assert!(page.cursor.is_some());
+
+        let mut ids: Vec<String> = page.records.unwrap().iter().map(|record| record.get_id().to_string()).collect();
+
+        let page = wallet_service.search_records_page(wallet_handle, "type", "{}", &options, page.cursor.as_ref().map(String::as_str), 2).unwrap();
+        assert_eq!(1, page.records.as_ref().unwrap().len());
+        assert!(page.cursor.is_none());
+
+        ids.extend(page.records.unwrap().iter().map(|record| record.get_id().to_string()));
+        ids.sort();
+        assert_eq!(vec!["key1", "key2", "key3"], ids);
+
+        let page = wallet_service.search_records_page(wallet_handle, "type", "{}", &options, None, 3).unwrap();
+        assert_eq!(3, page.records.unwrap().len());
+        assert!(page.cursor.is_none());
+    }
+
+    #[test]
+    fn wallet_service_works_for_inmemory_storage() {
+        let config = _config_inmemory("wallet_service_works_for_inmemory_storage");
+        let wallet_service = WalletService::new();
+        wallet_service.create_wallet(&config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+
+        let wallet_handle = wallet_service.open_wallet(&config, &RAW_CREDENTIAL).unwrap();
+        let tags = serde_json::from_str::<Tags>(r#"{"tag1": "value1", "~tag2": "value2"}"#).unwrap();
+        wallet_service.add_record(wallet_handle, "type", "key1", "value1", &tags).unwrap();
+        wallet_service.add_record(wallet_handle, "type", "key2", "value2", &HashMap::new()).unwrap();
+        wallet_service.close_wallet(wallet_handle).unwrap();
+
+        // records are kept between openings
+        let wallet_handle = wallet_service.open_wallet(&config, &RAW_CREDENTIAL).unwrap();
+        let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(false, true, true)).unwrap();
+        assert_eq!("value1", record.get_value().unwrap());
+        assert_eq!(&tags, record.get_tags().unwrap());
+
+        let mut search = wallet_service.search_records(wallet_handle, "type", r#"{"tag1": "value1", "~tag2": {"$like": "val%"}}"#, &_fetch_options(false, true, false)).unwrap();
+        assert_eq!(vec!["key1"], _fetch_ids(&mut search));
+
+        wallet_service.close_wallet(wallet_handle).unwrap();
+
+        wallet_service.delete_wallet(&config, &RAW_CREDENTIAL).unwrap();
+
+        let res = wallet_service.open_wallet(&config, &RAW_CREDENTIAL);
+        assert_kind!(IndyErrorKind::WalletNotFound, res);
+    }
+
+    #[test]
+    fn wallet_service_create_wallet_works_for_inmemory_storage_isolation() {
+        let wallet_service = WalletService::new();
+
+        let config1 = _config_inmemory("wallet_service_create_wallet_works_for_inmemory_storage_isolation_1");
+        let config2 = _config_inmemory("wallet_service_create_wallet_works_for_inmemory_storage_isolation_2");
+        wallet_service.create_wallet(&config1, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+        wallet_service.create_wallet(&config2, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+
+        let res = wallet_service.create_wallet(&config1, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY));
+        assert_kind!(IndyErrorKind::WalletAlreadyExists, res);
+
+        let wallet_handle1 = wallet_service.open_wallet(&config1, &RAW_CREDENTIAL).unwrap();
+        wallet_service.add_record(wallet_handle1, "type", "key1", "value1", &HashMap::new()).unwrap();
+
+        let wallet_handle2 = wallet_service.open_wallet(&config2, &RAW_CREDENTIAL).unwrap();
+        let res = wallet_service.get_record(wallet_handle2, "type", "key1", "{}");
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+    }
+
+    #[test]
+    fn wallet_service_verify_wallet_works() {
+        test::cleanup_wallet("wallet_service_verify_wallet_works");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_verify_wallet_works"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_verify_wallet_works"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+
+            let report = wallet_service.verify_wallet(wallet_handle, false).unwrap();
+            assert_eq!(WalletVerificationReport { records_count: 1, unreadable_records: 0, orphaned_tags: Some(0), repaired: false }, report);
+        }
+        test::cleanup_wallet("wallet_service_verify_wallet_works");
+    }
+
+    #[test]
+    fn wallet_service_verify_wallet_works_for_plugged() {
+        _cleanup("wallet_service_verify_wallet_works_for_plugged");
+
+        let wallet_service = WalletService::new();
+        _register_inmem_wallet(&wallet_service);
+
+        wallet_service.create_wallet(&_config_inmem(), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+        let wallet_handle = wallet_service.open_wallet(&_config_inmem(), &RAW_CREDENTIAL).unwrap();
+
+        wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+
+        let report = wallet_service.verify_wallet(wallet_handle, true).unwrap();
+        assert_eq!(WalletVerificationReport { records_count: 1, unreadable_records: 0, orphaned_tags: None, repaired: false }, report);
+    }
+
+    #[test]
+    fn wallet_service_verify_wallet_works_for_repair() {
+        test::cleanup_wallet("wallet_service_verify_wallet_works_for_repair");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_verify_wallet_works_for_repair"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_verify_wallet_works_for_repair"), &RAW_CREDENTIAL).unwrap();
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+            wallet_service.close_wallet(wallet_handle).unwrap();
+
+            // simulates partially written records
+            {
+                let conn = rusqlite::Connection::open(environment::wallet_home_path().join("wallet_service_verify_wallet_works_for_repair").join("sqlite.db")).unwrap();
+                conn.execute("INSERT INTO items (id, type, name, value, key) VALUES (100, ?1, ?2, ?3, ?4)",
+                             rusqlite::params![&vec![1u8, 2, 3], &vec![4u8, 5, 6], &vec![7u8, 8, 9], &vec![10u8, 11, 12]]).unwrap();
+                conn.execute("INSERT INTO tags_plaintext (item_id, name, value) VALUES (200, ?1, ?2)", rusqlite::params![&vec![1u8, 2, 3], &"value"]).unwrap();
+            }
+
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_verify_wallet_works_for_repair"), &RAW_CREDENTIAL).unwrap();
+
+            let report = wallet_service.verify_wallet(wallet_handle, false).unwrap();
+            assert_eq!(WalletVerificationReport { records_count: 2, unreadable_records: 1, orphaned_tags: Some(1), repaired: false }, report);
+
+            let report = wallet_service.verify_wallet(wallet_handle, true).unwrap();
+            assert_eq!(WalletVerificationReport { records_count: 2, unreadable_records: 1, orphaned_tags: Some(1), repaired: true }, report);
+
+            let report = wallet_service.verify_wallet(wallet_handle, false).unwrap();
+            assert_eq!(WalletVerificationReport { records_count: 1, unreadable_records: 0, orphaned_tags: Some(0), repaired: false }, report);
+
+            let record = wallet_service.get_record(wallet_handle, "type", "key1", "{}").unwrap();
+            assert_eq!("value1", record.get_value().unwrap());
+        }
+        test::cleanup_wallet("wallet_service_verify_wallet_works_for_repair");
+    }
+
+    /**
+        Key rotation test
+    */
+    #[test]
+    fn wallet_service_key_rotation() {
+        test::cleanup_wallet("wallet_service_key_rotation");
+        {
+            let config: &Config = &_config("wallet_service_key_rotation");
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(true, true, true)).unwrap();
+            assert_eq!("type", record.get_type().unwrap());
+            assert_eq!("value1", record.get_value().unwrap());
+
+            wallet_service.close_wallet(wallet_handle).unwrap();
+
+            let wallet_handle = wallet_service.open_wallet(config, &_rekey_credentials_moderate()).unwrap();
+            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(true, true, true)).unwrap();
+            assert_eq!("type", record.get_type().unwrap());
+            assert_eq!("value1", record.get_value().unwrap());
+            wallet_service.close_wallet(wallet_handle).unwrap();
+
+            // Access failed for old key
+            let res = wallet_service.open_wallet(config, &RAW_CREDENTIAL);
+            assert_kind!(IndyErrorKind::WalletAccessFailed, res);
+
+            // Works ok with new key when reopening
+            let wallet_handle = wallet_service.open_wallet(config, &_credentials_for_new_key_moderate()).unwrap();
+            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(true, true, true)).unwrap();
+            assert_eq!("type", record.get_type().unwrap());
+            assert_eq!("value1", record.get_value().unwrap());
+        }
+        test::cleanup_wallet("wallet_service_key_rotation");
+    }
+
+    #[test]
+    fn wallet_service_key_rotation_for_rekey_interactive_method() {
+        test::cleanup_wallet("wallet_service_key_rotation_for_rekey_interactive_method");
+        {
+            let config: &Config = &_config("wallet_service_key_rotation_for_rekey_interactive_method");
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(true, true, true)).unwrap();
+            assert_eq!("type", record.get_type().unwrap());
+            assert_eq!("value1", record.get_value().unwrap());
+
+            wallet_service.close_wallet(wallet_handle).unwrap();
+
+            let wallet_handle = wallet_service.open_wallet(config, &_rekey_credentials_interactive()).unwrap();
+            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(true, true, true)).unwrap();
+            assert_eq!("type", record.get_type().unwrap());
+            assert_eq!("value1", record.get_value().unwrap());
+            wallet_service.close_wallet(wallet_handle).unwrap();
+
+            // Access failed for old key
+            let res = wallet_service.open_wallet(config, &RAW_CREDENTIAL);
+            assert_kind!(IndyErrorKind::WalletAccessFailed, res);
+
+            // Works ok with new key when reopening
+            let wallet_handle = wallet_service.open_wallet(config, &_credentials_for_new_key_interactive()).unwrap();
+            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(true, true, true)).unwrap();
+            assert_eq!("type", record.get_type().unwrap());
+            assert_eq!("value1", record.get_value().unwrap());
+        }
+        test::cleanup_wallet("wallet_service_key_rotation_for_rekey_interactive_method");
+    }
+
+    #[test]
+    fn wallet_service_key_rotation_for_rekey_raw_method() {
+        test::cleanup_wallet("wallet_service_key_rotation_for_rekey_raw_method");
+        {
+            let config: &Config = &_config("wallet_service_key_rotation_for_rekey_raw_method");
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(true, true, true)).unwrap();
+            assert_eq!("type", record.get_type().unwrap());
+            assert_eq!("value1", record.get_value().unwrap());
+
+            wallet_service.close_wallet(wallet_handle).unwrap();
+
+            let wallet_handle = wallet_service.open_wallet(config, &_rekey_credentials_raw()).unwrap();
+            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(true, true, true)).unwrap();
+            assert_eq!("type", record.get_type().unwrap());
+            assert_eq!("value1", record.get_value().unwrap());
+            wallet_service.close_wallet(wallet_handle).unwrap();
+
+            // Access failed for old key
+            let res = wallet_service.open_wallet(config, &RAW_CREDENTIAL);
+            assert_kind!(IndyErrorKind::WalletAccessFailed, res);
+
+            // Works ok with new key when reopening
+            let wallet_handle = wallet_service.open_wallet(config, &_credentials_for_new_key_raw()).unwrap();
+            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(true, true, true)).unwrap();
+            assert_eq!("type", record.get_type().unwrap());
+            assert_eq!("value1", record.get_value().unwrap());
+        }
+        test::cleanup_wallet("wallet_service_key_rotation_for_rekey_raw_method");
+    }
+
+    fn remove_exported_wallet(export_config: &ExportConfig) -> &Path {
         let export_path = Path::new(&export_config.path);
         if export_path.exists() {
             fs::remove_file(export_path).unwrap();
@@ -1916,7 +2685,7 @@ mod tests {
 
             let export_path = remove_exported_wallet(&export_config);
             let (kdd, master_key) = _export_key_raw("key_wallet_service_export_wallet_when_empty");
-            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key)).unwrap();
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), None).unwrap();
 
             assert!(export_path.exists());
         }
@@ -1938,7 +2707,7 @@ mod tests {
 
             let export_path = remove_exported_wallet(&export_config);
             let (kdd, master_key) = _export_key_raw("key_wallet_service_export_wallet_1_item");
-            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key)).unwrap();
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), None).unwrap();
             assert!(export_path.exists());
         }
         let _export_path = remove_exported_wallet(&export_config);
@@ -1959,7 +2728,7 @@ mod tests {
 
             let export_path = remove_exported_wallet(&export_config);
             let (kdd, master_key) = _export_key_interactive("wallet_service_export_wallet_1_item_interactive_method");
-            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key)).unwrap();
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), None).unwrap();
             assert!(export_path.exists());
         }
         let _export_path = remove_exported_wallet(&export_config);
@@ -1980,7 +2749,7 @@ mod tests {
 
             let export_path = remove_exported_wallet(&export_config);
             let (kdd, master_key) = _export_key("wallet_service_export_wallet_1_item_raw_method");
-            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key)).unwrap();
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), None).unwrap();
             assert!(&export_path.exists());
         }
         let _export_path = remove_exported_wallet(&export_config);
@@ -2005,7 +2774,7 @@ mod tests {
             let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_export_wallet_returns_error_if_file_exists"), &RAW_CREDENTIAL).unwrap();
 
             let (kdd, master_key) = _export_key_raw("key_wallet_service_export_wallet_returns_error_if_file_exists");
-            let res = wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key));
+            let res = wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), None);
             assert_eq!(IndyErrorKind::IOError, res.unwrap_err().kind());
         }
         let _export_path = remove_exported_wallet(&export_config);
@@ -2023,7 +2792,7 @@ mod tests {
             let (kdd, master_key) = _export_key_raw("key_wallet_service_export_wallet_returns_error_if_wrong_handle");
             let export_config = _export_config_raw("wallet_service_export_wallet_returns_error_if_wrong_handle");
             let export_path = remove_exported_wallet(&export_config);
-            let res = wallet_service.export_wallet(INVALID_WALLET_HANDLE, &export_config, 0, (&kdd, &master_key));
+            let res = wallet_service.export_wallet(INVALID_WALLET_HANDLE, &export_config, 0, (&kdd, &master_key), None);
             assert_kind!(IndyErrorKind::InvalidWalletHandle, res);
             assert!(!export_path.exists());
         }
@@ -2044,7 +2813,7 @@ mod tests {
 
             let (kdd, master_key) = _export_key_raw("key_wallet_service_export_import_wallet_1_item");
             let export_path = remove_exported_wallet(&export_config);
-            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key)).unwrap();
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), None).unwrap();
             assert!(export_path.exists());
 
             wallet_service.close_wallet(wallet_handle).unwrap();
@@ -2073,7 +2842,7 @@ mod tests {
 
             let (kdd, master_key) = _export_key_interactive("wallet_service_export_import_wallet_1_item_for_interactive_method");
             let export_path = remove_exported_wallet(&export_config);
-            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key)).unwrap();
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), None).unwrap();
             assert!(export_path.exists());
 
             wallet_service.close_wallet(wallet_handle).unwrap();
@@ -2101,7 +2870,7 @@ mod tests {
 
             let (kdd, master_key) = _export_key_raw("key_wallet_service_export_import_wallet_1_item_for_moderate_method");
             let export_path = remove_exported_wallet(&export_config);
-            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key)).unwrap();
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), None).unwrap();
             assert!(export_path.exists());
 
             wallet_service.close_wallet(wallet_handle).unwrap();
@@ -2130,7 +2899,7 @@ mod tests {
 
             let (kdd, master_key) = _export_key_interactive("wallet_service_export_import_wallet_1_item_for_export_interactive_import_as_raw");
             let export_path = remove_exported_wallet(&export_config);
-            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key)).unwrap();
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), None).unwrap();
             assert!(export_path.exists());
 
             wallet_service.close_wallet(wallet_handle).unwrap();
@@ -2159,7 +2928,7 @@ mod tests {
 
             let (kdd, master_key) = _export_key_interactive("wallet_service_export_import_wallet_1_item_for_export_raw_import_as_interactive");
             let export_path = remove_exported_wallet(&export_config);
-            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key)).unwrap();
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), None).unwrap();
             assert!(export_path.exists());
 
             wallet_service.close_wallet(wallet_handle).unwrap();
@@ -2185,7 +2954,7 @@ mod tests {
 
             let (kdd, master_key) = _export_key("wallet_service_export_import_wallet_if_empty");
             let export_path = remove_exported_wallet(&export_config);
-            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key)).unwrap();
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), None).unwrap();
             assert!(export_path.exists());
 
             wallet_service.close_wallet(wallet_handle).unwrap();
@@ -2214,6 +2983,392 @@ mod tests {
         _cleanup("wallet_service_export_import_returns_error_if_path_missing");
     }
 
+    #[test]
+    fn wallet_service_export_wallet_works_for_progress() {
+        test::cleanup_wallet("wallet_service_export_wallet_works_for_progress");
+        let export_config = _export_config_raw("wallet_service_export_wallet_works_for_progress");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_export_wallet_works_for_progress"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_export_wallet_works_for_progress"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+            wallet_service.add_record(wallet_handle, "type", "key2", "value2", &HashMap::new()).unwrap();
+
+            let (kdd, master_key) = _export_key_raw("key_wallet_service_export_wallet_works_for_progress");
+            let export_path = remove_exported_wallet(&export_config);
+
+            let reported = RefCell::new(Vec::new());
+            let progress = |count: usize| -> IndyResult<()> {
+                reported.borrow_mut().push(count);
+                Ok(())
+            };
+
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), Some(&progress)).unwrap();
+            assert!(export_path.exists());
+            assert_eq!(vec![2], *reported.borrow());
+        }
+        let _export_path = remove_exported_wallet(&export_config);
+        test::cleanup_wallet("wallet_service_export_wallet_works_for_progress");
+    }
+
+    #[test]
+    fn wallet_service_export_wallet_works_for_cancel() {
+        test::cleanup_wallet("wallet_service_export_wallet_works_for_cancel");
+        let export_config = _export_config_raw("wallet_service_export_wallet_works_for_cancel");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_export_wallet_works_for_cancel"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_export_wallet_works_for_cancel"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+
+            let (kdd, master_key) = _export_key_raw("key_wallet_service_export_wallet_works_for_cancel");
+            let export_path = remove_exported_wallet(&export_config);
+
+            let progress = |_: usize| -> IndyResult<()> {
+                Err(err_msg(IndyErrorKind::InvalidState, "Export cancelled"))
+            };
+
+            let res = wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), Some(&progress));
+            assert_kind!(IndyErrorKind::InvalidState, res);
+            assert!(!export_path.exists());
+
+            let mut tmp_path = export_path.to_path_buf().into_os_string();
+            tmp_path.push(".tmp");
+            assert!(!PathBuf::from(tmp_path).exists());
+        }
+        test::cleanup_wallet("wallet_service_export_wallet_works_for_cancel");
+    }
+
+    #[test]
+    fn wallet_service_export_wallet_returns_error_if_tmp_file_exists() {
+        test::cleanup_wallet("wallet_service_export_wallet_returns_error_if_tmp_file_exists");
+        let export_config = _export_config_raw("wallet_service_export_wallet_returns_error_if_tmp_file_exists");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_export_wallet_returns_error_if_tmp_file_exists"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_export_wallet_returns_error_if_tmp_file_exists"), &RAW_CREDENTIAL).unwrap();
+
+            let export_path = remove_exported_wallet(&export_config);
+            let mut tmp_path = export_path.to_path_buf().into_os_string();
+            tmp_path.push(".tmp");
+            let tmp_path = PathBuf::from(tmp_path);
+
+            fs::create_dir_all(tmp_path.parent().unwrap()).unwrap();
+            fs::write(&tmp_path, b"another export").unwrap();
+
+            let (kdd, master_key) = _export_key_raw("key_wallet_service_export_wallet_returns_error_if_tmp_file_exists");
+            let res = wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), None);
+            assert_kind!(IndyErrorKind::IOError, res);
+            assert!(!export_path.exists());
+
+            // Temporary file of another export is left untouched
+            assert_eq!(b"another export".to_vec(), fs::read(&tmp_path).unwrap());
+            fs::remove_file(&tmp_path).unwrap();
+        }
+        test::cleanup_wallet("wallet_service_export_wallet_returns_error_if_tmp_file_exists");
+    }
+
+    #[test]
+    fn wallet_service_export_import_wallet_deletes_wallet_for_wrong_key() {
+        test::cleanup_wallet("wallet_service_export_import_wallet_deletes_wallet_for_wrong_key");
+        let export_config = _export_config_raw("wallet_service_export_import_wallet_deletes_wallet_for_wrong_key");
+        {
+            let wallet_service = WalletService::new();
+            let config: &Config = &_config("wallet_service_export_import_wallet_deletes_wallet_for_wrong_key");
+            wallet_service.create_wallet(config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+
+            let (kdd, master_key) = _export_key_raw("key_wallet_service_export_import_wallet_deletes_wallet_for_wrong_key");
+            let export_path = remove_exported_wallet(&export_config);
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), None).unwrap();
+            assert!(export_path.exists());
+
+            wallet_service.close_wallet(wallet_handle).unwrap();
+            wallet_service.delete_wallet(config, &RAW_CREDENTIAL).unwrap();
+
+            let wrong_export_config = ExportConfig { key: "7nxtSiXFvBd593Y2DCed2dYvRY1PGK9WMtxCBjLzKgbw".to_string(), ..export_config.clone() };
+            let res = wallet_service.import_wallet(config, &RAW_CREDENTIAL, &wrong_export_config);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+
+            // Wallet isn't left behind, so import can be just retried
+            let res = wallet_service.open_wallet(config, &RAW_CREDENTIAL);
+            assert_kind!(IndyErrorKind::WalletNotFound, res);
+
+            wallet_service.import_wallet(config, &RAW_CREDENTIAL, &export_config).unwrap();
+
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+            wallet_service.get_record(wallet_handle, "type", "key1", "{}").unwrap();
+        }
+        let _export_path = remove_exported_wallet(&export_config);
+        test::cleanup_wallet("wallet_service_export_import_wallet_deletes_wallet_for_wrong_key");
+    }
+
+    #[test]
+    fn wallet_service_export_import_wallet_works_for_resume() {
+        test::cleanup_wallet("wallet_service_export_import_wallet_works_for_resume");
+        let export_config = _export_config_raw("wallet_service_export_import_wallet_works_for_resume");
+        {
+            let wallet_service = WalletService::new();
+            let config: &Config = &_config("wallet_service_export_import_wallet_works_for_resume");
+            wallet_service.create_wallet(config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+            wallet_service.add_record(wallet_handle, "type", "key2", "value2", &HashMap::new()).unwrap();
+
+            let (kdd, master_key) = _export_key_raw("key_wallet_service_export_import_wallet_works_for_resume");
+            let export_path = remove_exported_wallet(&export_config);
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), None).unwrap();
+            assert!(export_path.exists());
+
+            wallet_service.close_wallet(wallet_handle).unwrap();
+            wallet_service.delete_wallet(config, &RAW_CREDENTIAL).unwrap();
+
+            // Emulate interrupted import: wallet contains only part of exported records
+            wallet_service.import_wallet(config, &RAW_CREDENTIAL, &export_config).unwrap();
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+            wallet_service.delete_record(wallet_handle, "type", "key2").unwrap();
+            wallet_service.close_wallet(wallet_handle).unwrap();
+
+            let res = wallet_service.import_wallet(config, &RAW_CREDENTIAL, &export_config);
+            assert_kind!(IndyErrorKind::WalletAlreadyExists, res);
+
+            let export_config = ExportConfig { resume: true, ..export_config.clone() };
+            wallet_service.import_wallet(config, &RAW_CREDENTIAL, &export_config).unwrap();
+
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(false, true, false)).unwrap();
+            assert_eq!("value1", record.get_value().unwrap());
+            let record = wallet_service.get_record(wallet_handle, "type", "key2", &_fetch_options(false, true, false)).unwrap();
+            assert_eq!("value2", record.get_value().unwrap());
+        }
+        let _export_path = remove_exported_wallet(&export_config);
+        test::cleanup_wallet("wallet_service_export_import_wallet_works_for_resume");
+    }
+
+    #[test]
+    fn wallet_service_export_import_wallet_works_for_resume_after_cancel() {
+        test::cleanup_wallet("wallet_service_export_import_wallet_works_for_resume_after_cancel");
+        let export_config = _export_config_raw("wallet_service_export_import_wallet_works_for_resume_after_cancel");
+        {
+            let wallet_service = WalletService::new();
+            let config: &Config = &_config("wallet_service_export_import_wallet_works_for_resume_after_cancel");
+            wallet_service.create_wallet(config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+
+            // Progress is reported every 100 records, so the last record is left for resumed import
+            for i in 0..101 {
+                wallet_service.add_record(wallet_handle, "type", &format!("key{}", i), &format!("value{}", i), &HashMap::new()).unwrap();
+            }
+
+            let (kdd, master_key) = _export_key_raw("key_wallet_service_export_import_wallet_works_for_resume_after_cancel");
+            let export_path = remove_exported_wallet(&export_config);
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), None).unwrap();
+            assert!(export_path.exists());
+
+            wallet_service.close_wallet(wallet_handle).unwrap();
+            wallet_service.delete_wallet(config, &RAW_CREDENTIAL).unwrap();
+
+            // Fresh import is cancelled after the first progress report
+            let progress = |_: usize| -> IndyResult<()> {
+                Err(err_msg(IndyErrorKind::InvalidState, "Import cancelled"))
+            };
+
+            let res = wallet_service.import_wallet_with_progress(config, &RAW_CREDENTIAL, &export_config, Some(&progress));
+            assert_kind!(IndyErrorKind::InvalidState, res);
+
+            // Wallet is kept with already imported records
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+            wallet_service.get_record(wallet_handle, "type", "key99", &_fetch_options(false, true, false)).unwrap();
+            let res = wallet_service.get_record(wallet_handle, "type", "key100", &_fetch_options(false, true, false));
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+            wallet_service.close_wallet(wallet_handle).unwrap();
+
+            let export_config = ExportConfig { resume: true, ..export_config.clone() };
+            wallet_service.import_wallet(config, &RAW_CREDENTIAL, &export_config).unwrap();
+
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+            for i in 0..101 {
+                let record = wallet_service.get_record(wallet_handle, "type", &format!("key{}", i), &_fetch_options(false, true, false)).unwrap();
+                assert_eq!(format!("value{}", i), record.get_value().unwrap());
+            }
+        }
+        let _export_path = remove_exported_wallet(&export_config);
+        test::cleanup_wallet("wallet_service_export_import_wallet_works_for_resume_after_cancel");
+    }
+
+    #[test]
+    fn wallet_service_rotate_keys_works() {
+        test::cleanup_wallet("wallet_service_rotate_keys_works");
+        {
+            let wallet_service = WalletService::new();
+            let config: &Config = &_config("wallet_service_rotate_keys_works");
+            wallet_service.create_wallet(config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+
+            let tags = serde_json::from_str(r#"{"tag1":"value1", "~tag2":"value2"}"#).unwrap();
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &tags).unwrap();
+            wallet_service.add_record(wallet_handle, "type", "key2", "value2", &HashMap::new()).unwrap();
+
+            let key_data = wallet_service.rotate_keys_prepare(wallet_handle, &RAW_CREDENTIAL).unwrap();
+            let master_key = key_data.calc_master_key().unwrap();
+            wallet_service.rotate_keys(wallet_handle, (&key_data, &master_key)).unwrap();
+
+            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(true, true, true)).unwrap();
+            assert_eq!("value1", record.get_value().unwrap());
+            assert_eq!(&tags, record.get_tags().unwrap());
+
+            let mut search = wallet_service.search_records(wallet_handle, "type", r#"{"tag1":"value1", "~tag2":"value2"}"#, &_fetch_options(true, true, true)).unwrap();
+            assert_eq!("key1", search.fetch_next_record().unwrap().unwrap().get_id());
+            assert!(search.fetch_next_record().unwrap().is_none());
+
+            wallet_service.close_wallet(wallet_handle).unwrap();
+
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+            let record = wallet_service.get_record(wallet_handle, "type", "key2", &_fetch_options(true, true, true)).unwrap();
+            assert_eq!("value2", record.get_value().unwrap());
+        }
+        test::cleanup_wallet("wallet_service_rotate_keys_works");
+    }
+
+    #[test]
+    fn wallet_service_rotate_keys_works_for_invalid_master_key() {
+        test::cleanup_wallet("wallet_service_rotate_keys_works_for_invalid_master_key");
+        {
+            let wallet_service = WalletService::new();
+            let config: &Config = &_config("wallet_service_rotate_keys_works_for_invalid_master_key");
+            wallet_service.create_wallet(config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+
+            let res = wallet_service.rotate_keys(wallet_handle, (&RAW_KDD, &chacha20poly1305_ietf::gen_key()));
+            assert_kind!(IndyErrorKind::WalletAccessFailed, res);
+
+            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(true, true, true)).unwrap();
+            assert_eq!("value1", record.get_value().unwrap());
+        }
+        test::cleanup_wallet("wallet_service_rotate_keys_works_for_invalid_master_key");
+    }
+
+    #[test]
+    fn wallet_service_rotate_keys_works_for_plugged_wallet() {
+        _cleanup("wallet_service_rotate_keys_works_for_plugged_wallet");
+
+        let wallet_service = WalletService::new();
+        _register_inmem_wallet(&wallet_service);
+
+        wallet_service.create_wallet(&_config_inmem(), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+        let wallet_handle = wallet_service.open_wallet(&_config_inmem(), &RAW_CREDENTIAL).unwrap();
+
+        wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+
+        let res = wallet_service.rotate_keys(wallet_handle, (&RAW_KDD, &RAW_MASTER_KEY));
+        assert_kind!(IndyErrorKind::InvalidState, res);
+
+        let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(true, true, true)).unwrap();
+        assert_eq!("value1", record.get_value().unwrap());
+
+        _cleanup("wallet_service_rotate_keys_works_for_plugged_wallet");
+    }
+
+    #[test]
+    fn wallet_service_batch_records_works() {
+        test::cleanup_wallet("wallet_service_batch_records_works");
+        {
+            let wallet_service = WalletService::new();
+            let config: &Config = &_config("wallet_service_batch_records_works");
+            wallet_service.create_wallet(config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+
+            let operations: Vec<WalletRecordOperation> = serde_json::from_value(json!([
+                {"op": "add", "type": "type", "id": "key2", "value": "value2", "tags": {"tag1": "value1"}},
+                {"op": "update", "type": "type", "id": "key2", "value": "value3"},
+                {"op": "add_tags", "type": "type", "id": "key2", "tags": {"~tag2": "value2"}},
+                {"op": "delete_tags", "type": "type", "id": "key2", "tag_names": ["tag1"]},
+                {"op": "delete", "type": "type", "id": "key1"},
+            ])).unwrap();
+
+            wallet_service.batch_records(wallet_handle, &operations).unwrap();
+
+            let record = wallet_service.get_record(wallet_handle, "type", "key2", &_fetch_options(true, true, true)).unwrap();
+            assert_eq!("value3", record.get_value().unwrap());
+            let expected_tags: Tags = serde_json::from_str(r#"{"~tag2":"value2"}"#).unwrap();
+            assert_eq!(&expected_tags, record.get_tags().unwrap());
+
+            let res = wallet_service.get_record(wallet_handle, "type", "key1", "{}");
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+        }
+        test::cleanup_wallet("wallet_service_batch_records_works");
+    }
+
+    #[test]
+    fn wallet_service_batch_records_works_for_failed_operation() {
+        test::cleanup_wallet("wallet_service_batch_records_works_for_failed_operation");
+        {
+            let wallet_service = WalletService::new();
+            let config: &Config = &_config("wallet_service_batch_records_works_for_failed_operation");
+            wallet_service.create_wallet(config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+
+            let operations: Vec<WalletRecordOperation> = serde_json::from_value(json!([
+                {"op": "update", "type": "type", "id": "key1", "value": "value2"},
+                {"op": "add", "type": "type", "id": "key2", "value": "value2"},
+                {"op": "delete", "type": "type", "id": "key3"},
+            ])).unwrap();
+
+            let res = wallet_service.batch_records(wallet_handle, &operations);
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+            let record = wallet_service.get_record(wallet_handle, "type", "key1", "{}").unwrap();
+            assert_eq!("value1", record.get_value().unwrap());
+
+            let res = wallet_service.get_record(wallet_handle, "type", "key2", "{}");
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+        }
+        test::cleanup_wallet("wallet_service_batch_records_works_for_failed_operation");
+    }
+
+    #[test]
+    fn wallet_service_batch_records_works_for_plugged_wallet_and_failed_operation() {
+        _cleanup("wallet_service_batch_records_works_for_plugged_wallet_and_failed_operation");
+
+        let wallet_service = WalletService::new();
+        _register_inmem_wallet(&wallet_service);
+
+        wallet_service.create_wallet(&_config_inmem(), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+        let wallet_handle = wallet_service.open_wallet(&_config_inmem(), &RAW_CREDENTIAL).unwrap();
+
+        let tags: Tags = serde_json::from_str(r#"{"tag1":"value1"}"#).unwrap();
+        wallet_service.add_record(wallet_handle, "type", "key1", "value1", &tags).unwrap();
+
+        let operations: Vec<WalletRecordOperation> = serde_json::from_value(json!([
+            {"op": "update_tags", "type": "type", "id": "key1", "tags": {"tag2": "value2"}},
+            {"op": "add", "type": "type", "id": "key2", "value": "value2"},
+            {"op": "add", "type": "type", "id": "key1", "value": "value3"},
+        ])).unwrap();
+
+        let res = wallet_service.batch_records(wallet_handle, &operations);
+        assert_kind!(IndyErrorKind::WalletItemAlreadyExists, res);
+
+        let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(true, true, true)).unwrap();
+        assert_eq!("value1", record.get_value().unwrap());
+        assert_eq!(&tags, record.get_tags().unwrap());
+
+        let res = wallet_service.get_record(wallet_handle, "type", "key2", "{}");
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+        _cleanup("wallet_service_batch_records_works_for_plugged_wallet_and_failed_operation");
+    }
+
     fn _fetch_options(type_: bool, value: bool, tags: bool) -> String {
         json!({
           "retrieveType": type_,
@@ -2246,6 +3401,14 @@ mod tests {
         }
     }
 
+    fn _config_inmemory(name: &str) -> Config {
+        Config {
+            id: name.to_string(),
+            storage_type: Some("inmemory".to_string()),
+            storage_config: None,
+        }
+    }
+
     fn _config_unknown(name: &str) -> Config {
         Config {
             id: name.to_string(),
@@ -2399,6 +3562,8 @@ mod tests {
             key: "export_key".to_string(),
             path: _export_file_path(name).to_str().unwrap().to_string(),
             key_derivation_method: KeyDerivationMethod::ARGON2I_MOD,
+            resume: false,
+            record_types: None,
         }
     }
 
@@ -2417,6 +3582,8 @@ mod tests {
             key: "export_key".to_string(),
             path: _export_file_path(name).to_str().unwrap().to_string(),
             key_derivation_method: KeyDerivationMethod::ARGON2I_INT,
+            resume: false,
+            record_types: None,
         }
     }
 
@@ -2429,6 +3596,8 @@ mod tests {
             key: "6nxtSiXFvBd593Y2DCed2dYvRY1PGK9WMtxCBjLzKgbw".to_string(),
             path: _export_file_path(name).to_str().unwrap().to_string(),
             key_derivation_method: KeyDerivationMethod::RAW,
+            resume: false,
+            record_types: None,
         }
     }
 
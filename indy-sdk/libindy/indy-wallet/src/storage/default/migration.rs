@@ -0,0 +1,124 @@
+use rusqlite;
+
+use indy_api_types::errors::prelude::*;
+
+use super::transaction;
+use super::super::SchemaMigration;
+
+// Schema version of the storage is kept in a dedicated table with a single row. Metadata table
+// stays with the only row of wallet keys, so libindy versions without schema versioning can still
+// read and update it. Wallets created before schema versioning don't have this table and have version 0.
+
+struct Migration {
+    version: u32,
+    description: &'static str,
+    sql: &'static str,
+}
+
+// Migrations have to be listed in ascending version order.
+// Released migrations must never be changed: any schema change requires a new migration.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "Add composite indexes on tag names and values",
+        sql: "
+            CREATE INDEX ix_tags_encrypted_name_value ON tags_encrypted(name, value);
+            CREATE INDEX ix_tags_plaintext_name_value ON tags_plaintext(name, value);
+        ",
+    },
+];
+
+pub(super) fn latest_version() -> u32 {
+    MIGRATIONS.last().map(|migration| migration.version).unwrap_or(0)
+}
+
+pub(super) fn schema_version(conn: &rusqlite::Connection) -> IndyResult<u32> {
+    let table_exists: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'schema_version')",
+        [],
+        |row| { row.get(0) },
+    )?;
+
+    if !table_exists {
+        return Ok(0);
+    }
+
+    let version: i64 = conn.query_row("SELECT version FROM schema_version", [], |row| { row.get(0) })?;
+    Ok(version as u32)
+}
+
+fn set_schema_version(conn: &rusqlite::Connection, version: u32) -> IndyResult<()> {
+    conn.execute_batch("
+        CREATE TABLE IF NOT EXISTS schema_version(version INTEGER NOT NULL);
+        DELETE FROM schema_version;
+    ")?;
+    conn.execute("INSERT INTO schema_version(version) VALUES(?1)", &[&(version as i64)])?;
+    Ok(())
+}
+
+fn pending_migrations(version: u32) -> IndyResult<Vec<&'static Migration>> {
+    let latest_version = latest_version();
+
+    if version > latest_version {
+        return Err(err_msg(IndyErrorKind::InvalidState,
+                           format!("Wallet schema version {} is newer than the latest supported version {}", version, latest_version)));
+    }
+
+    Ok(MIGRATIONS.iter().filter(|migration| migration.version > version).collect())
+}
+
+///
+/// Lists migrations newer than the current schema version of the storage.
+/// Only schema version is read, so storage isn't locked or modified.
+///
+pub(super) fn check(conn: &rusqlite::Connection) -> IndyResult<Vec<SchemaMigration>> {
+    let migrations = pending_migrations(schema_version(conn)?)?
+        .into_iter()
+        .map(_schema_migration)
+        .collect();
+
+    Ok(migrations)
+}
+
+///
+/// Applies all migrations newer than the current schema version of the storage in a single transaction
+/// and updates the schema version.
+/// Returns the list of applied migrations.
+///
+pub(super) fn migrate(conn: &rusqlite::Connection) -> IndyResult<Vec<SchemaMigration>> {
+    // Schema version is checked without lock first, so opening of up to date storage doesn't block other connections
+    if pending_migrations(schema_version(conn)?)?.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let tx: transaction::Transaction = transaction::Transaction::new(conn, rusqlite::TransactionBehavior::Exclusive)?;
+
+    // Storage could be migrated by other connection before the lock was taken
+    let migrations = pending_migrations(schema_version(&tx)?)?;
+
+    if migrations.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut applied = Vec::new();
+
+    for migration in migrations {
+        tx.execute_batch(migration.sql)
+            .to_indy(IndyErrorKind::InvalidState, format!("Wallet schema migration {} failed", migration.version))?;
+
+        applied.push(_schema_migration(migration));
+    }
+
+    set_schema_version(&tx, latest_version())?;
+
+    tx.commit()?;
+
+    Ok(applied)
+}
+
+fn _schema_migration(migration: &Migration) -> SchemaMigration {
+    SchemaMigration {
+        version: migration.version,
+        description: migration.description.to_string(),
+    }
+}
@@ -11,11 +11,12 @@ use indy_api_types::errors::prelude::*;
 use crate::language;
 use indy_utils::environment;
 
-use super::{EncryptedValue, StorageIterator, StorageRecord, Tag, TagName, WalletStorage, WalletStorageType};
+use super::{decode_cursor, encode_cursor, sort_orders, EncryptedValue, SchemaMigration, StorageIterator, StorageOperation, StoragePage, StorageRecord, Tag, TagName, WalletStorage, WalletStorageType};
 use super::super::{RecordOptions, SearchOptions};
 
 use self::owning_ref::OwningHandle;
 
+mod migration;
 mod query;
 mod transaction;
 
@@ -349,106 +350,32 @@ impl WalletStorage for SQLiteStorage {
     ///
     fn add(&self, type_: &[u8], id: &[u8], value: &EncryptedValue, tags: &[Tag]) -> IndyResult<()> {
         let tx: transaction::Transaction = transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Deferred)?;
-        let res = tx.prepare_cached("INSERT INTO items (type, name, value, key) VALUES (?1, ?2, ?3, ?4)")?
-            .insert(&[&type_.to_vec(), &id.to_vec(), &value.data, &value.key]);
-
-        let id = match res {
-            Ok(entity) => entity,
-            Err(err) => return Err(IndyError::from(err))
-        };
-
-        if !tags.is_empty() {
-            let mut stmt_e = tx.prepare_cached("INSERT INTO tags_encrypted (item_id, name, value) VALUES (?1, ?2, ?3)")?;
-            let mut stmt_p = tx.prepare_cached("INSERT INTO tags_plaintext (item_id, name, value) VALUES (?1, ?2, ?3)")?;
-
-            for tag in tags {
-                match *tag {
-                    Tag::Encrypted(ref tag_name, ref tag_data) => stmt_e.execute(rusqlite::params![&id, tag_name, tag_data])?,
-                    Tag::PlainText(ref tag_name, ref tag_data) => stmt_p.execute(rusqlite::params![&id, tag_name, tag_data])?
-                };
-            }
-        }
-
+        SQLiteStorage::_add(&tx, type_, id, value, tags)?;
         tx.commit()?;
         Ok(())
     }
 
     fn update(&self, type_: &[u8], id: &[u8], value: &EncryptedValue) -> IndyResult<()> {
-        let res = self.conn.prepare_cached("UPDATE items SET value = ?1, key = ?2 WHERE type = ?3 AND name = ?4")?
-            .execute(rusqlite::params![&value.data, &value.key, &type_.to_vec(), &id.to_vec()]);
-
-        match res {
-            Ok(1) => Ok(()),
-            Ok(0) => Err(err_msg(IndyErrorKind::WalletItemNotFound, "Item to update not found")),
-            Ok(_) => Err(err_msg(IndyErrorKind::InvalidState, "More than one row update. Seems wallet structure is inconsistent")),
-            Err(err) => Err(err.into()),
-        }
+        SQLiteStorage::_update(&self.conn, type_, id, value)
     }
 
     fn add_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
         let tx: transaction::Transaction = transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Deferred)?;
-
-        let item_id: i64 = tx.prepare_cached("SELECT id FROM items WHERE type = ?1 AND name = ?2")?
-            .query_row(&[&type_.to_vec(), &id.to_vec()], |row| row.get(0))?;
-
-        if !tags.is_empty() {
-            let mut enc_tag_insert_stmt = tx.prepare_cached("INSERT OR REPLACE INTO tags_encrypted (item_id, name, value) VALUES (?1, ?2, ?3)")?;
-            let mut plain_tag_insert_stmt = tx.prepare_cached("INSERT OR REPLACE INTO tags_plaintext (item_id, name, value) VALUES (?1, ?2, ?3)")?;
-
-            for tag in tags {
-                match *tag {
-                    Tag::Encrypted(ref tag_name, ref tag_data) => enc_tag_insert_stmt.execute(rusqlite::params![&item_id, tag_name, tag_data])?,
-                    Tag::PlainText(ref tag_name, ref tag_data) => plain_tag_insert_stmt.execute(rusqlite::params![&item_id, tag_name, tag_data])?
-                };
-            }
-        }
+        SQLiteStorage::_add_tags(&tx, type_, id, tags)?;
         tx.commit()?;
-
         Ok(())
     }
 
     fn update_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
         let tx: transaction::Transaction = transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Deferred)?;
-
-        let item_id: i64 = tx.prepare_cached("SELECT id FROM items WHERE type = ?1 AND name = ?2")?
-            .query_row(&[&type_.to_vec(), &id.to_vec()], |row| row.get(0))?;
-
-        tx.execute("DELETE FROM tags_encrypted WHERE item_id = ?1", &[&item_id])?;
-        tx.execute("DELETE FROM tags_plaintext WHERE item_id = ?1", &[&item_id])?;
-
-        if !tags.is_empty() {
-            let mut enc_tag_insert_stmt = tx.prepare_cached("INSERT INTO tags_encrypted (item_id, name, value) VALUES (?1, ?2, ?3)")?;
-            let mut plain_tag_insert_stmt = tx.prepare_cached("INSERT INTO tags_plaintext (item_id, name, value) VALUES (?1, ?2, ?3)")?;
-
-            for tag in tags {
-                match *tag {
-                    Tag::Encrypted(ref tag_name, ref tag_data) => enc_tag_insert_stmt.execute(rusqlite::params![&item_id, tag_name, tag_data])?,
-                    Tag::PlainText(ref tag_name, ref tag_data) => plain_tag_insert_stmt.execute(rusqlite::params![&item_id, tag_name, tag_data])?
-                };
-            }
-        }
+        SQLiteStorage::_update_tags(&tx, type_, id, tags)?;
         tx.commit()?;
-
         Ok(())
     }
 
     fn delete_tags(&self, type_: &[u8], id: &[u8], tag_names: &[TagName]) -> IndyResult<()> {
-        let item_id: i64 = self.conn.prepare_cached("SELECT id FROM items WHERE type =?1 AND name = ?2")?
-            .query_row(&[&type_.to_vec(), &id.to_vec()], |row| row.get(0))?;
-
         let tx: transaction::Transaction = transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Deferred)?;
-        {
-            let mut enc_tag_delete_stmt = tx.prepare_cached("DELETE FROM tags_encrypted WHERE item_id = ?1 AND name = ?2")?;
-            let mut plain_tag_delete_stmt = tx.prepare_cached("DELETE FROM tags_plaintext WHERE item_id = ?1 AND name = ?2")?;
-
-            for tag_name in tag_names {
-                match *tag_name {
-                    TagName::OfEncrypted(ref tag_name) => enc_tag_delete_stmt.execute(rusqlite::params![&item_id, tag_name])?,
-                    TagName::OfPlain(ref tag_name) => plain_tag_delete_stmt.execute(rusqlite::params![&item_id, tag_name])?,
-                };
-            }
-        }
-
+        SQLiteStorage::_delete_tags(&tx, type_, id, tag_names)?;
         tx.commit()?;
         Ok(())
     }
@@ -480,16 +407,51 @@ impl WalletStorage for SQLiteStorage {
     ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
     ///
     fn delete(&self, type_: &[u8], id: &[u8]) -> IndyResult<()> {
-        let row_count = self.conn.execute(
-            "DELETE FROM items where type = ?1 AND name = ?2",
-            &[&type_.to_vec(), &id.to_vec()],
-        )?;
+        SQLiteStorage::_delete(&self.conn, type_, id)
+    }
 
-        if row_count == 1 {
-            Ok(())
-        } else {
-            Err(err_msg(IndyErrorKind::WalletItemNotFound, "Item to delete not found"))
+    ///
+    /// applies all operations of the batch to storage in a single transaction.
+    /// Returns Result with () if all operations are applied or
+    /// Result with IndyError of the first failed operation. Storage is left unchanged in this case.
+    ///
+    ///
+    /// # Arguments
+    ///
+    ///  * `operations` - operations to apply in the order they are provided
+    ///
+    /// # Returns
+    ///
+    /// Result that can be either:
+    ///
+    ///  * `()`
+    ///  * `IndyError`
+    ///
+    /// # Errors
+    ///
+    /// Any of the following `IndyError` type_ of errors can be throw by this method:
+    ///
+    ///  * `IndyError::Closed` - Storage is closed
+    ///  * `IndyError::ItemNotFound` - Item to update or delete is not found in database
+    ///  * `IndyError::ItemAlreadyExists` - Item to add is already present in database
+    ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
+    ///
+    fn batch(&self, operations: &[StorageOperation]) -> IndyResult<()> {
+        let tx: transaction::Transaction = transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Deferred)?;
+
+        for operation in operations {
+            match *operation {
+                StorageOperation::Add(ref type_, ref id, ref value, ref tags) => SQLiteStorage::_add(&tx, type_, id, value, tags)?,
+                StorageOperation::Update(ref type_, ref id, ref value) => SQLiteStorage::_update(&tx, type_, id, value)?,
+                StorageOperation::AddTags(ref type_, ref id, ref tags) => SQLiteStorage::_add_tags(&tx, type_, id, tags)?,
+                StorageOperation::UpdateTags(ref type_, ref id, ref tags) => SQLiteStorage::_update_tags(&tx, type_, id, tags)?,
+                StorageOperation::DeleteTags(ref type_, ref id, ref tag_names) => SQLiteStorage::_delete_tags(&tx, type_, id, tag_names)?,
+                StorageOperation::Delete(ref type_, ref id) => SQLiteStorage::_delete(&tx, type_, id)?,
+            }
         }
+
+        tx.commit()?;
+        Ok(())
     }
 
     fn get_storage_metadata(&self) -> IndyResult<Vec<u8>> {
@@ -551,7 +513,8 @@ impl WalletStorage for SQLiteStorage {
                 retrieve_type: search_options.retrieve_type,
             };
 
-            let (query_string, query_arguments) = query::wql_to_sql(&type_, query, options)?;
+            let sort_orders = sort_orders(search_options.sort_by.as_ref().map(Vec::as_slice))?;
+            let (query_string, query_arguments) = query::wql_to_sql(&type_, query, &sort_orders)?;
 
             let statement = self._prepare_statement(&query_string)?;
             let tag_retriever = if fetch_options.retrieve_tags {
@@ -567,6 +530,273 @@ impl WalletStorage for SQLiteStorage {
         }
     }
 
+    ///
+    /// Fetches one page of items matching the query without keeping any search state in the storage.
+    /// Without sorting items are ordered by their position in the storage, so pages stay consistent
+    /// when items are added or deleted between requests. Sorted pages are selected by offset.
+    ///
+    /// # Arguments
+    ///
+    ///  * `type_` - type_ of the items in storage
+    ///  * `query` - query to select items
+    ///  * `options` - JSon containing what needs to be fetched (the same as for `search`)
+    ///  * `cursor` - cursor returned with the previous page, None for the first page
+    ///  * `limit` - max amount of items in the page
+    ///
+    /// # Returns
+    ///
+    /// Result that can be either:
+    ///
+    ///  * `StoragePage` - Contains items and cursor for the next page (None if there are no more items)
+    ///  * `IndyError`
+    ///
+    /// # Errors
+    ///
+    /// Any of the following `IndyError` type_ of errors can be throw by this method:
+    ///
+    ///  * `IndyError::Closed` - Storage is closed
+    ///  * `InvalidStructure("Invalid search cursor")` - Cursor is malformed
+    ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
+    ///
+    fn search_page(&self, type_: &[u8], query: &language::Operator, options: Option<&str>, cursor: Option<&str>, limit: usize) -> IndyResult<StoragePage> {
+        let type_ = type_.to_vec();
+
+        let search_options = match options {
+            None => SearchOptions::default(),
+            Some(option_str) => serde_json::from_str(option_str)
+                .to_indy(IndyErrorKind::InvalidStructure, "Search options is malformed json")?
+        };
+
+        let total_count: Option<usize> = if search_options.retrieve_total_count {
+            let (query_string, query_arguments) = query::wql_to_sql_count(&type_, query)?;
+
+            let res: i64 = self.conn.query_row(
+                &query_string,
+                &*query_arguments,
+                |row| { row.get(0) },
+            )?;
+            Some(res as usize)
+        } else { None };
+
+        let mut records = Vec::new();
+        let mut next_cursor = None;
+
+        if search_options.retrieve_records {
+            let sort_orders = sort_orders(search_options.sort_by.as_ref().map(Vec::as_slice))?;
+
+            // cursor keeps id of the last returned item for unsorted search
+            // and position of the next item in the result for sorted one
+            let position = match cursor {
+                Some(cursor) => decode_cursor(cursor)?,
+                None => 0
+            };
+            // one extra item is fetched to know if there is the next page
+            let fetch_limit = limit as i64 + 1;
+
+            let (query_string, query_arguments) = if sort_orders.is_empty() {
+                query::wql_to_sql_page(&type_, query, &position, &fetch_limit)?
+            } else {
+                query::wql_to_sql_sorted_page(&type_, query, &sort_orders, &position, &fetch_limit)?
+            };
+
+            let mut stmt = self.conn.prepare(&query_string)?;
+            let mut rows = stmt.query(&*query_arguments)?;
+            let mut last_item_id = None;
+
+            while let Some(row) = rows.next()? {
+                if records.len() == limit {
+                    next_cursor = if sort_orders.is_empty() {
+                        last_item_id.map(encode_cursor)
+                    } else {
+                        Some(encode_cursor(position + limit as i64))
+                    };
+                    break;
+                }
+
+                let item_id: i64 = row.get(0)?;
+
+                let value = if search_options.retrieve_value {
+                    Some(EncryptedValue::new(row.get(2)?, row.get(3)?))
+                } else {
+                    None
+                };
+
+                let tags = if search_options.retrieve_tags {
+                    Some(SQLiteStorage::_get_item_tags(&self.conn, item_id)?)
+                } else {
+                    None
+                };
+
+                let type_ = if search_options.retrieve_type {
+                    Some(row.get(4)?)
+                } else {
+                    None
+                };
+
+                records.push(StorageRecord::new(row.get(1)?, value, type_, tags));
+                last_item_id = Some(item_id);
+            }
+        }
+
+        Ok(StoragePage {
+            records,
+            cursor: next_cursor,
+            total_count,
+        })
+    }
+
+    ///
+    /// Counts items matching the query.
+    ///
+    fn count(&self, type_: &[u8], query: &language::Operator) -> IndyResult<usize> {
+        let type_ = type_.to_vec();
+
+        let (query_string, query_arguments) = query::wql_to_sql_count(&type_, query)?;
+
+        let res: i64 = self.conn.query_row(
+            &query_string,
+            &*query_arguments,
+            |row| { row.get(0) },
+        )?;
+
+        Ok(res as usize)
+    }
+
+    ///
+    /// Counts items matching the query grouped by value of the plain text tag.
+    /// Items without the tag are skipped. Groups are ordered by the tag value.
+    ///
+    fn group_count_by_tag(&self, type_: &[u8], query: &language::Operator, tag_name: &[u8]) -> IndyResult<Vec<(String, usize)>> {
+        let type_ = type_.to_vec();
+        let tag_name = tag_name.to_vec();
+
+        let (query_string, query_arguments) = query::wql_to_sql_group_count(&type_, query, &tag_name)?;
+
+        let mut stmt = self.conn.prepare(&query_string)?;
+        let mut rows = stmt.query(&*query_arguments)?;
+        let mut groups = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let count: i64 = row.get(1)?;
+            groups.push((row.get(0)?, count as usize));
+        }
+
+        Ok(groups)
+    }
+
+    ///
+    /// Re-encrypts all items of the storage and replaces metadata in a single transaction.
+    /// Items are loaded and rewritten by batches to limit memory usage.
+    /// Returns Result with () on success or
+    /// Result with IndyError in case of failure.
+    ///
+    ///
+    /// # Arguments
+    ///
+    ///  * `metadata` - new metadata that will be stored with re-encrypted items
+    ///  * `batch_size` - max amount of items loaded in memory at once
+    ///  * `reencrypt` - function that converts item encrypted with old keys to item encrypted with new ones
+    ///
+    /// # Returns
+    ///
+    /// Result that can be either:
+    ///
+    ///  * `()`
+    ///  * `IndyError`
+    ///
+    /// # Errors
+    ///
+    /// Any of the following `IndyError` type_ of errors can be throw by this method:
+    ///
+    ///  * `IndyError::Closed` - Storage is closed
+    ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
+    ///  * any error returned by `reencrypt`. Storage is left unchanged in this case.
+    ///
+    fn reencrypt_records(&self, metadata: &[u8], batch_size: usize, reencrypt: &dyn Fn(StorageRecord) -> IndyResult<StorageRecord>) -> IndyResult<()> {
+        let tx: transaction::Transaction = transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Immediate)?;
+
+        let mut last_item_id: i64 = 0;
+
+        loop {
+            let batch: Vec<(i64, StorageRecord)> = {
+                let mut stmt = tx.prepare_cached("SELECT id, name, value, key, type FROM items WHERE id > ?1 ORDER BY id LIMIT ?2")?;
+                let mut rows = stmt.query(rusqlite::params![&last_item_id, &(batch_size as i64)])?;
+
+                let mut batch = Vec::new();
+                while let Some(row) = rows.next()? {
+                    batch.push((row.get(0)?, StorageRecord::new(row.get(1)?, Some(EncryptedValue::new(row.get(2)?, row.get(3)?)), Some(row.get(4)?), None)));
+                }
+                batch
+            };
+
+            if batch.is_empty() {
+                break;
+            }
+
+            let mut item_update_stmt = tx.prepare_cached("UPDATE items SET type = ?1, name = ?2, value = ?3, key = ?4 WHERE id = ?5")?;
+            let mut enc_tag_insert_stmt = tx.prepare_cached("INSERT INTO tags_encrypted (item_id, name, value) VALUES (?1, ?2, ?3)")?;
+            let mut plain_tag_insert_stmt = tx.prepare_cached("INSERT INTO tags_plaintext (item_id, name, value) VALUES (?1, ?2, ?3)")?;
+
+            for (item_id, mut record) in batch {
+                record.tags = Some(SQLiteStorage::_get_item_tags(&tx, item_id)?);
+
+                let record = reencrypt(record)?;
+
+                let (type_, value) = match (record.type_, record.value) {
+                    (Some(type_), Some(value)) => (type_, value),
+                    _ => return Err(err_msg(IndyErrorKind::InvalidState, "Re-encrypted item must contain type and value"))
+                };
+
+                item_update_stmt.execute(rusqlite::params![&type_, &record.id, &value.data, &value.key, &item_id])?;
+
+                tx.execute("DELETE FROM tags_encrypted WHERE item_id = ?1", &[&item_id])?;
+                tx.execute("DELETE FROM tags_plaintext WHERE item_id = ?1", &[&item_id])?;
+
+                for tag in record.tags.unwrap_or_default() {
+                    match tag {
+                        Tag::Encrypted(ref tag_name, ref tag_data) => enc_tag_insert_stmt.execute(rusqlite::params![&item_id, tag_name, tag_data])?,
+                        Tag::PlainText(ref tag_name, ref tag_data) => plain_tag_insert_stmt.execute(rusqlite::params![&item_id, tag_name, tag_data])?
+                    };
+                }
+
+                last_item_id = item_id;
+            }
+        }
+
+        tx.execute("UPDATE metadata SET value = ?1", &[&metadata.to_vec()])?;
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    ///
+    /// Counts tags that refer to missing items. Such tags can be left by writes made
+    /// with disabled foreign keys or by partially written wallet.
+    /// If `delete` is set orphaned tags are deleted in the same transaction.
+    ///
+    fn orphaned_tags(&self, delete: bool) -> IndyResult<Option<usize>> {
+        let tx: transaction::Transaction = transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Deferred)?;
+
+        let mut orphaned = 0;
+
+        for table in &["tags_encrypted", "tags_plaintext"] {
+            let count: i64 = tx.query_row(
+                &format!("SELECT count(*) FROM {} WHERE item_id NOT IN (SELECT id FROM items)", table),
+                [],
+                |row| row.get(0),
+            )?;
+
+            if delete && count > 0 {
+                tx.execute(&format!("DELETE FROM {} WHERE item_id NOT IN (SELECT id FROM items)", table), [])?;
+            }
+
+            orphaned += count as usize;
+        }
+
+        tx.commit()?;
+        Ok(Some(orphaned))
+    }
+
     fn close(&mut self) -> IndyResult<()> {
         Ok(())
     }
@@ -578,6 +808,133 @@ impl SQLiteStorage {
             unsafe { (*conn).prepare(sql) }.map(Box::new).map_err(IndyError::from)
         })
     }
+
+    fn _add(conn: &rusqlite::Connection, type_: &[u8], id: &[u8], value: &EncryptedValue, tags: &[Tag]) -> IndyResult<()> {
+        let res = conn.prepare_cached("INSERT INTO items (type, name, value, key) VALUES (?1, ?2, ?3, ?4)")?
+            .insert(&[&type_.to_vec(), &id.to_vec(), &value.data, &value.key]);
+
+        let id = match res {
+            Ok(entity) => entity,
+            Err(err) => return Err(IndyError::from(err))
+        };
+
+        if !tags.is_empty() {
+            let mut stmt_e = conn.prepare_cached("INSERT INTO tags_encrypted (item_id, name, value) VALUES (?1, ?2, ?3)")?;
+            let mut stmt_p = conn.prepare_cached("INSERT INTO tags_plaintext (item_id, name, value) VALUES (?1, ?2, ?3)")?;
+
+            for tag in tags {
+                match *tag {
+                    Tag::Encrypted(ref tag_name, ref tag_data) => stmt_e.execute(rusqlite::params![&id, tag_name, tag_data])?,
+                    Tag::PlainText(ref tag_name, ref tag_data) => stmt_p.execute(rusqlite::params![&id, tag_name, tag_data])?
+                };
+            }
+        }
+
+        Ok(())
+    }
+
+    fn _update(conn: &rusqlite::Connection, type_: &[u8], id: &[u8], value: &EncryptedValue) -> IndyResult<()> {
+        let res = conn.prepare_cached("UPDATE items SET value = ?1, key = ?2 WHERE type = ?3 AND name = ?4")?
+            .execute(rusqlite::params![&value.data, &value.key, &type_.to_vec(), &id.to_vec()]);
+
+        match res {
+            Ok(1) => Ok(()),
+            Ok(0) => Err(err_msg(IndyErrorKind::WalletItemNotFound, "Item to update not found")),
+            Ok(_) => Err(err_msg(IndyErrorKind::InvalidState, "More than one row update. Seems wallet structure is inconsistent")),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn _add_tags(conn: &rusqlite::Connection, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
+        let item_id: i64 = conn.prepare_cached("SELECT id FROM items WHERE type = ?1 AND name = ?2")?
+            .query_row(&[&type_.to_vec(), &id.to_vec()], |row| row.get(0))?;
+
+        if !tags.is_empty() {
+            let mut enc_tag_insert_stmt = conn.prepare_cached("INSERT OR REPLACE INTO tags_encrypted (item_id, name, value) VALUES (?1, ?2, ?3)")?;
+            let mut plain_tag_insert_stmt = conn.prepare_cached("INSERT OR REPLACE INTO tags_plaintext (item_id, name, value) VALUES (?1, ?2, ?3)")?;
+
+            for tag in tags {
+                match *tag {
+                    Tag::Encrypted(ref tag_name, ref tag_data) => enc_tag_insert_stmt.execute(rusqlite::params![&item_id, tag_name, tag_data])?,
+                    Tag::PlainText(ref tag_name, ref tag_data) => plain_tag_insert_stmt.execute(rusqlite::params![&item_id, tag_name, tag_data])?
+                };
+            }
+        }
+
+        Ok(())
+    }
+
+    fn _update_tags(conn: &rusqlite::Connection, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
+        let item_id: i64 = conn.prepare_cached("SELECT id FROM items WHERE type = ?1 AND name = ?2")?
+            .query_row(&[&type_.to_vec(), &id.to_vec()], |row| row.get(0))?;
+
+        conn.execute("DELETE FROM tags_encrypted WHERE item_id = ?1", &[&item_id])?;
+        conn.execute("DELETE FROM tags_plaintext WHERE item_id = ?1", &[&item_id])?;
+
+        if !tags.is_empty() {
+            let mut enc_tag_insert_stmt = conn.prepare_cached("INSERT INTO tags_encrypted (item_id, name, value) VALUES (?1, ?2, ?3)")?;
+            let mut plain_tag_insert_stmt = conn.prepare_cached("INSERT INTO tags_plaintext (item_id, name, value) VALUES (?1, ?2, ?3)")?;
+
+            for tag in tags {
+                match *tag {
+                    Tag::Encrypted(ref tag_name, ref tag_data) => enc_tag_insert_stmt.execute(rusqlite::params![&item_id, tag_name, tag_data])?,
+                    Tag::PlainText(ref tag_name, ref tag_data) => plain_tag_insert_stmt.execute(rusqlite::params![&item_id, tag_name, tag_data])?
+                };
+            }
+        }
+
+        Ok(())
+    }
+
+    fn _delete_tags(conn: &rusqlite::Connection, type_: &[u8], id: &[u8], tag_names: &[TagName]) -> IndyResult<()> {
+        let item_id: i64 = conn.prepare_cached("SELECT id FROM items WHERE type =?1 AND name = ?2")?
+            .query_row(&[&type_.to_vec(), &id.to_vec()], |row| row.get(0))?;
+
+        let mut enc_tag_delete_stmt = conn.prepare_cached("DELETE FROM tags_encrypted WHERE item_id = ?1 AND name = ?2")?;
+        let mut plain_tag_delete_stmt = conn.prepare_cached("DELETE FROM tags_plaintext WHERE item_id = ?1 AND name = ?2")?;
+
+        for tag_name in tag_names {
+            match *tag_name {
+                TagName::OfEncrypted(ref tag_name) => enc_tag_delete_stmt.execute(rusqlite::params![&item_id, tag_name])?,
+                TagName::OfPlain(ref tag_name) => plain_tag_delete_stmt.execute(rusqlite::params![&item_id, tag_name])?,
+            };
+        }
+
+        Ok(())
+    }
+
+    fn _delete(conn: &rusqlite::Connection, type_: &[u8], id: &[u8]) -> IndyResult<()> {
+        let row_count = conn.execute(
+            "DELETE FROM items where type = ?1 AND name = ?2",
+            &[&type_.to_vec(), &id.to_vec()],
+        )?;
+
+        if row_count == 1 {
+            Ok(())
+        } else {
+            Err(err_msg(IndyErrorKind::WalletItemNotFound, "Item to delete not found"))
+        }
+    }
+
+    fn _get_item_tags(conn: &rusqlite::Connection, item_id: i64) -> IndyResult<Vec<Tag>> {
+        let mut tags = Vec::new();
+
+        let mut stmt = conn.prepare_cached(_ENCRYPTED_TAGS_QUERY)?;
+        let mut rows = stmt.query(&[&item_id])?;
+
+        while let Some(row) = rows.next()? {
+            tags.push(Tag::Encrypted(row.get(0)?, row.get(1)?));
+        }
+
+        let mut stmt = conn.prepare_cached(_PLAIN_TAGS_QUERY)?;
+        let mut rows = stmt.query(&[&item_id])?;
+
+        while let Some(row) = rows.next()? {
+            tags.push(Tag::PlainText(row.get(0)?, row.get(1)?));
+        }
+
+        Ok(tags)
+    }
 }
 
 
@@ -648,6 +1005,7 @@ impl WalletStorageType for SQLiteStorageType {
     ///  * `IOError("IO error during storage operation:...")` - Connection to the DB failed
     ///  * `IOError("Error occurred while creating wallet file:..)"` - Creation of schema failed
     ///  * `IOError("Error occurred while inserting the keys...")` - Insertion of keys failed
+    ///  * `InvalidState("Wallet schema migration...")` - Migration of created schema to the latest version failed
     ///  * `IOError(..)` - Deletion of the file form the file-system failed
     ///
     fn create_storage(&self, id: &str, config: Option<&str>, _credentials: Option<&str>, metadata: &[u8]) -> IndyResult<()> {
@@ -670,7 +1028,13 @@ impl WalletStorageType for SQLiteStorageType {
 
         match conn.execute_batch(_CREATE_SCHEMA) {
             Ok(_) => match conn.execute("INSERT OR REPLACE INTO metadata(value) VALUES(?1)", &[&metadata.to_vec()]) {
-                Ok(_) => Ok(()),
+                Ok(_) => match migration::migrate(&conn) {
+                    Ok(_) => Ok(()),
+                    Err(error) => {
+                        std::fs::remove_file(db_path)?;
+                        Err(error)
+                    }
+                },
                 Err(error) => {
                     std::fs::remove_file(db_path)?;
                     Err(error.into())
@@ -709,6 +1073,8 @@ impl WalletStorageType for SQLiteStorageType {
     ///
     ///  * `IndyError::NotFound` - File with the provided id not found
     ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
+    ///  * `InvalidState("Wallet schema version...")` - Storage schema is newer than supported one
+    ///  * `InvalidState("Wallet schema migration...")` - Migration of storage schema to the latest version failed
     ///
     fn open_storage(&self, id: &str, config: Option<&str>, _credentials: Option<&str>) -> IndyResult<Box<dyn WalletStorage>> {
         let config = config
@@ -737,8 +1103,53 @@ impl WalletStorageType for SQLiteStorageType {
             conn.execute("PRAGMA synchronous = FULL", [])?;
         }
 
+        migration::migrate(&conn)?;
+
         Ok(Box::new(SQLiteStorage { conn: Rc::new(conn) }))
     }
+
+    ///
+    /// Checks which schema migrations will be applied to the SQLite DB with the provided id
+    /// on the next opening. Only schema version of the DB is compared with the latest one,
+    /// so DB isn't locked or modified.
+    ///
+    /// # Arguments
+    ///
+    ///  * `id` - id of the SQLite DB file
+    ///  * `config` - config containing the location of SQLite DB files
+    ///  * `credentials` - DB credentials
+    ///
+    /// # Returns
+    ///
+    /// Result that can be either:
+    ///
+    ///  * `Vec<SchemaMigration>` - Pending migrations (empty if schema is up to date)
+    ///  * `IndyError`
+    ///
+    /// # Errors
+    ///
+    /// Any of the following `IndyError` type_ of errors can be throw by this method:
+    ///
+    ///  * `IndyError::NotFound` - File with the provided id not found
+    ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
+    ///  * `InvalidState("Wallet schema version...")` - Storage schema is newer than supported one
+    ///
+    fn check_migrations(&self, id: &str, config: Option<&str>, _credentials: Option<&str>) -> IndyResult<Vec<SchemaMigration>> {
+        let config = config
+            .map(serde_json::from_str::<Config>)
+            .map_or(Ok(None), |v| v.map(Some))
+            .to_indy(IndyErrorKind::InvalidStructure, "Malformed config json")?;
+
+        let db_file_path = SQLiteStorageType::_db_path(id, config.as_ref());
+
+        if !db_file_path.exists() {
+            return Err(err_msg(IndyErrorKind::WalletNotFound, "No wallet database exists"));
+        }
+
+        let conn = rusqlite::Connection::open(db_file_path.as_path())?;
+
+        migration::check(&conn)
+    }
 }
 
 #[cfg(test)]
@@ -866,6 +1277,124 @@ mod tests {
         assert_kind!(IndyErrorKind::WalletNotFound, res);
     }
 
+    #[test]
+    fn sqlite_storage_type_create_works_for_latest_schema_version() {
+        _cleanup("sqlite_storage_type_create_works_for_latest_schema_version");
+        {
+            let storage_type = SQLiteStorageType::new();
+            storage_type.create_storage("sqlite_storage_type_create_works_for_latest_schema_version", None, None, &_metadata()).unwrap();
+
+            let conn = _connection("sqlite_storage_type_create_works_for_latest_schema_version");
+            assert_eq!(migration::latest_version(), migration::schema_version(&conn).unwrap());
+
+            let migrations = storage_type.check_migrations("sqlite_storage_type_create_works_for_latest_schema_version", None, None).unwrap();
+            assert!(migrations.is_empty());
+        }
+        _cleanup("sqlite_storage_type_create_works_for_latest_schema_version");
+    }
+
+    #[test]
+    fn sqlite_storage_type_open_works_for_legacy_schema() {
+        _cleanup("sqlite_storage_type_open_works_for_legacy_schema");
+        {
+            _legacy_storage("sqlite_storage_type_open_works_for_legacy_schema");
+
+            let storage_type = SQLiteStorageType::new();
+
+            let migrations = storage_type.check_migrations("sqlite_storage_type_open_works_for_legacy_schema", None, None).unwrap();
+            assert_eq!(migration::latest_version() as usize, migrations.len());
+            assert_eq!(1, migrations[0].version);
+
+            // Check doesn't change schema version
+            let conn = _connection("sqlite_storage_type_open_works_for_legacy_schema");
+            assert_eq!(0, migration::schema_version(&conn).unwrap());
+            drop(conn);
+
+            let storage = storage_type.open_storage("sqlite_storage_type_open_works_for_legacy_schema", None, None).unwrap();
+            assert_eq!(_metadata(), storage.get_storage_metadata().unwrap());
+
+            storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+            let record = storage.get(&_type1(), &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
+            assert_eq!(record.value.unwrap(), _value1());
+
+            storage.set_storage_metadata(&[1, 2, 3]).unwrap();
+            assert_eq!(vec![1, 2, 3], storage.get_storage_metadata().unwrap());
+
+            let migrations = storage_type.check_migrations("sqlite_storage_type_open_works_for_legacy_schema", None, None).unwrap();
+            assert!(migrations.is_empty());
+        }
+        _cleanup("sqlite_storage_type_open_works_for_legacy_schema");
+    }
+
+    #[test]
+    fn sqlite_storage_type_open_works_for_baseline_metadata_queries_after_migration() {
+        _cleanup("sqlite_storage_type_open_works_for_baseline_metadata_queries_after_migration");
+        {
+            _legacy_storage("sqlite_storage_type_open_works_for_baseline_metadata_queries_after_migration");
+
+            let storage_type = SQLiteStorageType::new();
+            let storage = storage_type.open_storage("sqlite_storage_type_open_works_for_baseline_metadata_queries_after_migration", None, None).unwrap();
+            drop(storage);
+
+            // Queries of libindy versions without schema versioning
+            let conn = _connection("sqlite_storage_type_open_works_for_baseline_metadata_queries_after_migration");
+            assert_eq!(migration::latest_version(), migration::schema_version(&conn).unwrap());
+
+            let count: i64 = conn.query_row("SELECT COUNT(*) FROM metadata", [], |row| { row.get(0) }).unwrap();
+            assert_eq!(1, count);
+
+            let metadata: Vec<u8> = conn.query_row("SELECT value FROM metadata", [], |row| { row.get(0) }).unwrap();
+            assert_eq!(_metadata(), metadata);
+
+            conn.execute("UPDATE metadata SET value = ?1", &[&vec![1u8, 2, 3]]).unwrap();
+            assert_eq!(migration::latest_version(), migration::schema_version(&conn).unwrap());
+            drop(conn);
+
+            let storage = storage_type.open_storage("sqlite_storage_type_open_works_for_baseline_metadata_queries_after_migration", None, None).unwrap();
+            assert_eq!(vec![1, 2, 3], storage.get_storage_metadata().unwrap());
+        }
+        _cleanup("sqlite_storage_type_open_works_for_baseline_metadata_queries_after_migration");
+    }
+
+    #[test]
+    fn sqlite_storage_type_check_migrations_works_for_locked_storage() {
+        _cleanup("sqlite_storage_type_check_migrations_works_for_locked_storage");
+        {
+            _legacy_storage("sqlite_storage_type_check_migrations_works_for_locked_storage");
+
+            // Other connection holds write lock
+            let conn = _connection("sqlite_storage_type_check_migrations_works_for_locked_storage");
+            conn.execute_batch("BEGIN IMMEDIATE TRANSACTION").unwrap();
+
+            let storage_type = SQLiteStorageType::new();
+            let migrations = storage_type.check_migrations("sqlite_storage_type_check_migrations_works_for_locked_storage", None, None).unwrap();
+            assert_eq!(migration::latest_version() as usize, migrations.len());
+
+            conn.execute_batch("ROLLBACK").unwrap();
+        }
+        _cleanup("sqlite_storage_type_check_migrations_works_for_locked_storage");
+    }
+
+    #[test]
+    fn sqlite_storage_type_open_works_for_newer_schema_version() {
+        _cleanup("sqlite_storage_type_open_works_for_newer_schema_version");
+        {
+            let storage_type = SQLiteStorageType::new();
+            storage_type.create_storage("sqlite_storage_type_open_works_for_newer_schema_version", None, None, &_metadata()).unwrap();
+
+            let conn = _connection("sqlite_storage_type_open_works_for_newer_schema_version");
+            conn.execute("UPDATE schema_version SET version = ?1", &[&(migration::latest_version() as i64 + 1)]).unwrap();
+            drop(conn);
+
+            let res = storage_type.open_storage("sqlite_storage_type_open_works_for_newer_schema_version", None, None);
+            assert_kind!(IndyErrorKind::InvalidState, res);
+
+            let res = storage_type.check_migrations("sqlite_storage_type_open_works_for_newer_schema_version", None, None);
+            assert_kind!(IndyErrorKind::InvalidState, res);
+        }
+        _cleanup("sqlite_storage_type_open_works_for_newer_schema_version");
+    }
+
     #[test]
     fn sqlite_storage_add_works_for_is_802() {
         _cleanup("sqlite_storage_add_works_for_is_802");
@@ -1313,6 +1842,30 @@ mod tests {
         _cleanup("sqlite_storage_delete_tags_works_for_non_existing_id");
     }
 
+    #[test]
+    fn sqlite_storage_orphaned_tags_works() {
+        _cleanup("sqlite_storage_orphaned_tags_works");
+        {
+            let storage = _storage("sqlite_storage_orphaned_tags_works");
+            storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+            assert_eq!(Some(0), storage.orphaned_tags(false).unwrap());
+        }
+        {
+            let conn = _connection("sqlite_storage_orphaned_tags_works");
+            conn.execute("INSERT INTO tags_plaintext (item_id, name, value) VALUES (?1, ?2, ?3)", rusqlite::params![&100, &vec![1u8, 2, 3], &"value"]).unwrap();
+        }
+        {
+            let storage = SQLiteStorageType::new().open_storage("sqlite_storage_orphaned_tags_works", None, None).unwrap();
+            assert_eq!(Some(1), storage.orphaned_tags(false).unwrap());
+            assert_eq!(Some(1), storage.orphaned_tags(true).unwrap());
+            assert_eq!(Some(0), storage.orphaned_tags(false).unwrap());
+
+            let record = storage.get(&_type1(), &_id1(), r##"{"retrieveTags": true}"##).unwrap();
+            assert_eq!(_sort(record.tags.unwrap()), _sort(_tags()));
+        }
+        _cleanup("sqlite_storage_orphaned_tags_works");
+    }
+
     fn _cleanup(name: &str) {
         test::cleanup_storage(name)
     }
@@ -1323,6 +1876,20 @@ mod tests {
         storage_type.open_storage(name, None, None).unwrap()
     }
 
+    fn _connection(name: &str) -> rusqlite::Connection {
+        rusqlite::Connection::open(SQLiteStorageType::_db_path(name, None)).unwrap()
+    }
+
+    // Storage created before schema versioning was introduced
+    fn _legacy_storage(name: &str) {
+        let db_path = SQLiteStorageType::_db_path(name, None);
+        fs::create_dir_all(db_path.parent().unwrap()).unwrap();
+
+        let conn = rusqlite::Connection::open(db_path).unwrap();
+        conn.execute_batch(_CREATE_SCHEMA).unwrap();
+        conn.execute("INSERT OR REPLACE INTO metadata(value) VALUES(?1)", &[&_metadata()]).unwrap();
+    }
+
     fn _storage_custom(name: &str) -> Box<dyn WalletStorage> {
         let storage_type = SQLiteStorageType::new();
 
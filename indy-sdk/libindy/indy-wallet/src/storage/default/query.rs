@@ -1,26 +1,56 @@
 use indy_api_types::errors::prelude::*;
 use rusqlite::types::ToSql;
 use crate::language::{Operator, TagName, TargetValue};
+use crate::SortDirection;
+use super::super::{SortField, SortOrder};
 
 
 // Translates Wallet Query Language to SQL
 // WQL input is provided as a reference to a top level Operator
 // Result is a tuple of query string and query arguments
-pub fn wql_to_sql<'a>(class: &'a Vec<u8>, op: &'a Operator, _options: Option<&str>) -> Result<(String, Vec<&'a dyn ToSql>), IndyError> {
+// Records are returned in storage order if no sorting is provided
+pub fn wql_to_sql<'a>(class: &'a Vec<u8>, op: &'a Operator, sort: &'a [SortOrder]) -> Result<(String, Vec<&'a dyn ToSql>), IndyError> {
     let mut arguments: Vec<&dyn ToSql> = Vec::new();
     arguments.push(class);
     let clause_string = operator_to_sql(op, &mut arguments)?;
-    const BASE: &str = "SELECT i.id, i.name, i.value, i.key, i.type FROM items as i WHERE i.type = ?";
+    let mut query_string = "SELECT i.id, i.name, i.value, i.key, i.type FROM items as i WHERE i.type = ?".to_string();
     if !clause_string.is_empty() {
-        let mut query_string = String::with_capacity(BASE.len() + 5 + clause_string.len());
-        query_string.push_str(BASE);
         query_string.push_str(" AND ");
         query_string.push_str(&clause_string);
-        Ok((query_string, arguments))
-    } else {
-        Ok((BASE.to_string(), arguments))
     }
+    if !sort.is_empty() {
+        query_string.push_str(" ORDER BY ");
+        query_string.push_str(&sort_to_sql(sort, &mut arguments));
+    }
+    Ok((query_string, arguments))
+}
 
+
+// Same as wql_to_sql, but selects only one page of sorted records that starts at `offset` position
+pub fn wql_to_sql_sorted_page<'a>(class: &'a Vec<u8>, op: &'a Operator, sort: &'a [SortOrder], offset: &'a i64, limit: &'a i64) -> Result<(String, Vec<&'a dyn ToSql>), IndyError> {
+    let (mut query_string, mut arguments) = wql_to_sql(class, op, sort)?;
+    query_string.push_str(" LIMIT ? OFFSET ?");
+    arguments.push(limit);
+    arguments.push(offset);
+    Ok((query_string, arguments))
+}
+
+
+// Same as wql_to_sql, but selects only one page of records ordered by id
+// that starts after the record with id equal to `last_id`
+pub fn wql_to_sql_page<'a>(class: &'a Vec<u8>, op: &'a Operator, last_id: &'a i64, limit: &'a i64) -> Result<(String, Vec<&'a dyn ToSql>), IndyError> {
+    let mut arguments: Vec<&dyn ToSql> = Vec::new();
+    arguments.push(class);
+    let clause_string = operator_to_sql(op, &mut arguments)?;
+    let mut query_string = "SELECT i.id, i.name, i.value, i.key, i.type FROM items as i WHERE i.type = ?".to_string();
+    if !clause_string.is_empty() {
+        query_string.push_str(" AND ");
+        query_string.push_str(&clause_string);
+    }
+    query_string.push_str(" AND i.id > ? ORDER BY i.id LIMIT ?");
+    arguments.push(last_id);
+    arguments.push(limit);
+    Ok((query_string, arguments))
 }
 
 
@@ -37,6 +67,58 @@ pub fn wql_to_sql_count<'a>(class: &'a Vec<u8>, op: &'a Operator) -> Result<(Str
 }
 
 
+// Counts records matching the query grouped by value of the unencrypted tag
+// Records without the tag are not counted
+pub fn wql_to_sql_group_count<'a>(class: &'a Vec<u8>, op: &'a Operator, tag_name: &'a Vec<u8>) -> Result<(String, Vec<&'a dyn ToSql>), IndyError> {
+    let mut arguments: Vec<&dyn ToSql> = Vec::new();
+    arguments.push(tag_name);
+    arguments.push(class);
+    let clause_string = operator_to_sql(op, &mut arguments)?;
+    let mut query_string = "SELECT t.value, count(*) FROM items as i JOIN tags_plaintext as t ON t.item_id = i.id AND t.name = ? WHERE i.type = ?".to_string();
+    if !clause_string.is_empty() {
+        query_string.push_str(" AND ");
+        query_string.push_str(&clause_string);
+    }
+    query_string.push_str(" GROUP BY t.value ORDER BY t.value");
+    Ok((query_string, arguments))
+}
+
+
+// Row id (insertion order) is always added as the last sort key to keep the order stable for equal values
+fn sort_to_sql<'a>(sort: &'a [SortOrder], arguments: &mut Vec<&'a dyn ToSql>) -> String {
+    let mut orders = Vec::with_capacity(sort.len() + 1);
+    let mut id_sorted = false;
+
+    for sort_order in sort {
+        let direction = match sort_order.direction {
+            SortDirection::Asc => "ASC",
+            SortDirection::Desc => "DESC",
+        };
+
+        match sort_order.field {
+            SortField::InsertionOrder => {
+                id_sorted = true;
+                orders.push(format!("i.id {}", direction));
+            }
+            SortField::PlainTagName(ref name) => {
+                arguments.push(name);
+                orders.push(format!("(SELECT value FROM tags_plaintext WHERE item_id = i.id AND name = ?) {}", direction));
+            }
+        }
+
+        if id_sorted {
+            break;
+        }
+    }
+
+    if !id_sorted {
+        orders.push("i.id ASC".to_string());
+    }
+
+    orders.join(", ")
+}
+
+
 fn operator_to_sql<'a>(op: &'a Operator, arguments: &mut Vec<&'a dyn ToSql>) -> IndyResult<String> {
     match *op {
         Operator::Eq(ref tag_name, ref target_value) => eq_to_sql(tag_name, target_value, arguments),
@@ -241,6 +323,19 @@ mod tests {
         ]);
         let query = Operator::Or(vec![condition_1, condition_2]);
         let class = vec![100,100,100];
-        let (_query, _arguments) = wql_to_sql(&class, &query, None).unwrap();
+        let (_query, _arguments) = wql_to_sql(&class, &query, &[]).unwrap();
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn sorted_by_plain_tag_and_id() {
+        let query = Operator::Eq(TagName::PlainTagName(vec![1,2,3]), TargetValue::Unencrypted("spam".to_string()));
+        let class = vec![100,100,100];
+        let sort = vec![
+            SortOrder { field: SortField::PlainTagName(vec![4,5,6]), direction: SortDirection::Desc },
+            SortOrder { field: SortField::InsertionOrder, direction: SortDirection::Desc },
+        ];
+        let (query, arguments) = wql_to_sql(&class, &query, &sort).unwrap();
+        assert!(query.ends_with(" ORDER BY (SELECT value FROM tags_plaintext WHERE item_id = i.id AND name = ?) DESC, i.id DESC"));
+        assert_eq!(4, arguments.len());
+    }
+}
@@ -0,0 +1,675 @@
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::rc::Rc;
+
+use serde_json;
+
+use indy_api_types::errors::prelude::*;
+use crate::language;
+
+use super::{decode_cursor, encode_cursor, sort_orders, EncryptedValue, SchemaMigration, SortField, SortOrder, StorageIterator, StorageOperation, StoragePage, StorageRecord, Tag, TagName, WalletStorage, WalletStorageType};
+use super::super::{RecordOptions, SearchOptions, SortDirection};
+
+mod query;
+
+#[derive(Clone)]
+struct InMemoryItem {
+    type_: Vec<u8>,
+    id: Vec<u8>,
+    value: EncryptedValue,
+    tags: Vec<Tag>,
+}
+
+impl InMemoryItem {
+    fn to_record(&self, options: &RecordOptions) -> StorageRecord {
+        StorageRecord::new(
+            self.id.clone(),
+            if options.retrieve_value { Some(self.value.clone()) } else { None },
+            if options.retrieve_type { Some(self.type_.clone()) } else { None },
+            if options.retrieve_tags { Some(self.tags.clone()) } else { None },
+        )
+    }
+}
+
+// Items are keyed by the position they were added at, that gives the same stable order
+// for searches and pages as row ids of default storage.
+#[derive(Clone)]
+struct InMemoryData {
+    metadata: Vec<u8>,
+    items: BTreeMap<i64, InMemoryItem>,
+    positions: HashMap<(Vec<u8>, Vec<u8>), i64>,
+    last_position: i64,
+}
+
+impl InMemoryData {
+    fn new(metadata: &[u8]) -> InMemoryData {
+        InMemoryData {
+            metadata: metadata.to_vec(),
+            items: BTreeMap::new(),
+            positions: HashMap::new(),
+            last_position: 0,
+        }
+    }
+
+    fn item_mut(&mut self, type_: &[u8], id: &[u8]) -> IndyResult<&mut InMemoryItem> {
+        let position = self.positions.get(&(type_.to_vec(), id.to_vec()))
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletItemNotFound, "Item not found"))?;
+
+        self.items.get_mut(position)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "Item position is unknown. Seems wallet structure is inconsistent"))
+    }
+
+    fn add(&mut self, type_: &[u8], id: &[u8], value: &EncryptedValue, tags: &[Tag]) -> IndyResult<()> {
+        let key = (type_.to_vec(), id.to_vec());
+
+        if self.positions.contains_key(&key) {
+            return Err(err_msg(IndyErrorKind::WalletItemAlreadyExists, "Wallet item already exists"));
+        }
+
+        let mut item = InMemoryItem {
+            type_: type_.to_vec(),
+            id: id.to_vec(),
+            value: value.clone(),
+            tags: Vec::new(),
+        };
+        InMemoryData::_set_tags(&mut item, tags);
+
+        self.last_position += 1;
+        self.positions.insert(key, self.last_position);
+        self.items.insert(self.last_position, item);
+        Ok(())
+    }
+
+    fn update(&mut self, type_: &[u8], id: &[u8], value: &EncryptedValue) -> IndyResult<()> {
+        let item = self.item_mut(type_, id)?;
+        item.value = value.clone();
+        Ok(())
+    }
+
+    fn add_tags(&mut self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
+        let item = self.item_mut(type_, id)?;
+        InMemoryData::_set_tags(item, tags);
+        Ok(())
+    }
+
+    fn update_tags(&mut self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
+        let item = self.item_mut(type_, id)?;
+        item.tags.clear();
+        InMemoryData::_set_tags(item, tags);
+        Ok(())
+    }
+
+    fn delete_tags(&mut self, type_: &[u8], id: &[u8], tag_names: &[TagName]) -> IndyResult<()> {
+        let item = self.item_mut(type_, id)?;
+
+        for tag_name in tag_names {
+            item.tags.retain(|tag| match (tag, tag_name) {
+                (&Tag::Encrypted(ref name, _), &TagName::OfEncrypted(ref deleted)) => name != deleted,
+                (&Tag::PlainText(ref name, _), &TagName::OfPlain(ref deleted)) => name != deleted,
+                _ => true
+            });
+        }
+
+        Ok(())
+    }
+
+    fn delete(&mut self, type_: &[u8], id: &[u8]) -> IndyResult<()> {
+        let position = self.positions.remove(&(type_.to_vec(), id.to_vec()))
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletItemNotFound, "Item to delete not found"))?;
+
+        self.items.remove(&position);
+        Ok(())
+    }
+
+    fn apply(&mut self, operation: &StorageOperation) -> IndyResult<()> {
+        match *operation {
+            StorageOperation::Add(ref type_, ref id, ref value, ref tags) => self.add(type_, id, value, tags),
+            StorageOperation::Update(ref type_, ref id, ref value) => self.update(type_, id, value),
+            StorageOperation::AddTags(ref type_, ref id, ref tags) => self.add_tags(type_, id, tags),
+            StorageOperation::UpdateTags(ref type_, ref id, ref tags) => self.update_tags(type_, id, tags),
+            StorageOperation::DeleteTags(ref type_, ref id, ref tag_names) => self.delete_tags(type_, id, tag_names),
+            StorageOperation::Delete(ref type_, ref id) => self.delete(type_, id),
+        }
+    }
+
+    // Returns items of the type matching the query in the requested order
+    fn select(&self, type_: &[u8], query: &language::Operator, sort: &[SortOrder]) -> IndyResult<Vec<(i64, &InMemoryItem)>> {
+        let mut selected = Vec::new();
+
+        for (position, item) in self.items.iter() {
+            if item.type_.as_slice() == type_ && query::matches(query, &item.tags)? {
+                selected.push((*position, item));
+            }
+        }
+
+        if !sort.is_empty() {
+            selected.sort_by(|&(position_a, item_a), &(position_b, item_b)| {
+                for sort_order in sort {
+                    let ordering = match sort_order.field {
+                        SortField::InsertionOrder => position_a.cmp(&position_b),
+                        SortField::PlainTagName(ref name) =>
+                            query::plain_tag_value(name, &item_a.tags).cmp(&query::plain_tag_value(name, &item_b.tags)),
+                    };
+
+                    let ordering = match sort_order.direction {
+                        SortDirection::Asc => ordering,
+                        SortDirection::Desc => ordering.reverse(),
+                    };
+
+                    if ordering != Ordering::Equal {
+                        return ordering;
+                    }
+                }
+
+                position_a.cmp(&position_b)
+            });
+        }
+
+        Ok(selected)
+    }
+
+    // Tags are unique by kind and name, the new value replaces the existing one
+    fn _set_tags(item: &mut InMemoryItem, tags: &[Tag]) {
+        for tag in tags {
+            item.tags.retain(|existing| match (existing, tag) {
+                (&Tag::Encrypted(ref name, _), &Tag::Encrypted(ref new_name, _)) => name != new_name,
+                (&Tag::PlainText(ref name, _), &Tag::PlainText(ref new_name, _)) => name != new_name,
+                _ => true
+            });
+            item.tags.push(tag.clone());
+        }
+    }
+}
+
+struct InMemoryStorageIterator {
+    records: std::vec::IntoIter<StorageRecord>,
+    total_count: Option<usize>,
+}
+
+impl StorageIterator for InMemoryStorageIterator {
+    fn next(&mut self) -> IndyResult<Option<StorageRecord>> {
+        Ok(self.records.next())
+    }
+
+    fn get_total_count(&self) -> IndyResult<Option<usize>> {
+        Ok(self.total_count)
+    }
+}
+
+pub struct InMemoryStorage {
+    data: Rc<RefCell<InMemoryData>>,
+}
+
+pub struct InMemoryStorageType {
+    storages: RefCell<HashMap<String, Rc<RefCell<InMemoryData>>>>,
+}
+
+impl InMemoryStorageType {
+    pub fn new() -> InMemoryStorageType {
+        InMemoryStorageType {
+            storages: RefCell::new(HashMap::new()),
+        }
+    }
+}
+
+impl WalletStorage for InMemoryStorage {
+    fn get(&self, type_: &[u8], id: &[u8], options: &str) -> IndyResult<StorageRecord> {
+        let options: RecordOptions = if options == "{}" {
+            RecordOptions::default()
+        } else {
+            serde_json::from_str(options)
+                .to_indy(IndyErrorKind::InvalidStructure, "RecordOptions is malformed json")?
+        };
+
+        let data = self.data.borrow();
+
+        let item = data.positions.get(&(type_.to_vec(), id.to_vec()))
+            .and_then(|position| data.items.get(position))
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletItemNotFound, "Item not found"))?;
+
+        Ok(item.to_record(&options))
+    }
+
+    fn add(&self, type_: &[u8], id: &[u8], value: &EncryptedValue, tags: &[Tag]) -> IndyResult<()> {
+        self.data.borrow_mut().add(type_, id, value, tags)
+    }
+
+    fn update(&self, type_: &[u8], id: &[u8], value: &EncryptedValue) -> IndyResult<()> {
+        self.data.borrow_mut().update(type_, id, value)
+    }
+
+    fn add_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
+        self.data.borrow_mut().add_tags(type_, id, tags)
+    }
+
+    fn update_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
+        self.data.borrow_mut().update_tags(type_, id, tags)
+    }
+
+    fn delete_tags(&self, type_: &[u8], id: &[u8], tag_names: &[TagName]) -> IndyResult<()> {
+        self.data.borrow_mut().delete_tags(type_, id, tag_names)
+    }
+
+    fn delete(&self, type_: &[u8], id: &[u8]) -> IndyResult<()> {
+        self.data.borrow_mut().delete(type_, id)
+    }
+
+    ///
+    /// Applies all operations of the batch to a copy of the storage data.
+    /// The copy replaces the data only if all operations are applied, so storage is left unchanged on failure.
+    ///
+    fn batch(&self, operations: &[StorageOperation]) -> IndyResult<()> {
+        let mut data = self.data.borrow().clone();
+
+        for operation in operations {
+            data.apply(operation)?;
+        }
+
+        *self.data.borrow_mut() = data;
+        Ok(())
+    }
+
+    fn get_storage_metadata(&self) -> IndyResult<Vec<u8>> {
+        Ok(self.data.borrow().metadata.clone())
+    }
+
+    fn set_storage_metadata(&self, metadata: &[u8]) -> IndyResult<()> {
+        self.data.borrow_mut().metadata = metadata.to_vec();
+        Ok(())
+    }
+
+    fn get_all(&self) -> IndyResult<Box<dyn StorageIterator>> {
+        let fetch_options = RecordOptions {
+            retrieve_type: true,
+            retrieve_value: true,
+            retrieve_tags: true,
+        };
+
+        let records = self.data.borrow().items
+            .values()
+            .map(|item| item.to_record(&fetch_options))
+            .collect::<Vec<StorageRecord>>();
+
+        Ok(Box::new(InMemoryStorageIterator { records: records.into_iter(), total_count: None }))
+    }
+
+    fn search(&self, type_: &[u8], query: &language::Operator, options: Option<&str>) -> IndyResult<Box<dyn StorageIterator>> {
+        let search_options = match options {
+            None => SearchOptions::default(),
+            Some(option_str) => serde_json::from_str(option_str)
+                .to_indy(IndyErrorKind::InvalidStructure, "Search options is malformed json")?
+        };
+
+        let sort_orders = sort_orders(search_options.sort_by.as_ref().map(Vec::as_slice))?;
+
+        let data = self.data.borrow();
+        let selected = data.select(type_, query, &sort_orders)?;
+
+        let total_count = if search_options.retrieve_total_count { Some(selected.len()) } else { None };
+
+        let records = if search_options.retrieve_records {
+            let fetch_options = RecordOptions {
+                retrieve_value: search_options.retrieve_value,
+                retrieve_tags: search_options.retrieve_tags,
+                retrieve_type: search_options.retrieve_type,
+            };
+
+            selected.iter().map(|(_, item)| item.to_record(&fetch_options)).collect()
+        } else {
+            Vec::new()
+        };
+
+        Ok(Box::new(InMemoryStorageIterator { records: records.into_iter(), total_count }))
+    }
+
+    ///
+    /// Fetches one page of items matching the query.
+    /// Cursors have the same meaning as for default storage: the position of the last returned item
+    /// for unsorted search and offset of the next page for sorted one.
+    ///
+    fn search_page(&self, type_: &[u8], query: &language::Operator, options: Option<&str>, cursor: Option<&str>, limit: usize) -> IndyResult<StoragePage> {
+        let search_options = match options {
+            None => SearchOptions::default(),
+            Some(option_str) => serde_json::from_str(option_str)
+                .to_indy(IndyErrorKind::InvalidStructure, "Search options is malformed json")?
+        };
+
+        let sort_orders = sort_orders(search_options.sort_by.as_ref().map(Vec::as_slice))?;
+
+        let position = match cursor {
+            Some(cursor) => decode_cursor(cursor)?,
+            None => 0
+        };
+
+        let data = self.data.borrow();
+        let selected = data.select(type_, query, &sort_orders)?;
+
+        let total_count = if search_options.retrieve_total_count { Some(selected.len()) } else { None };
+
+        let mut records = Vec::new();
+        let mut next_cursor = None;
+
+        if search_options.retrieve_records {
+            let fetch_options = RecordOptions {
+                retrieve_value: search_options.retrieve_value,
+                retrieve_tags: search_options.retrieve_tags,
+                retrieve_type: search_options.retrieve_type,
+            };
+
+            let page: Vec<&(i64, &InMemoryItem)> = if sort_orders.is_empty() {
+                selected.iter().filter(|(item_position, _)| *item_position > position).take(limit + 1).collect()
+            } else {
+                selected.iter().skip(position.max(0) as usize).take(limit + 1).collect()
+            };
+
+            if limit > 0 && page.len() > limit {
+                next_cursor = Some(encode_cursor(if sort_orders.is_empty() { page[limit - 1].0 } else { position + limit as i64 }));
+            }
+
+            records = page.iter().take(limit).map(|(_, item)| item.to_record(&fetch_options)).collect();
+        }
+
+        Ok(StoragePage {
+            records,
+            cursor: next_cursor,
+            total_count,
+        })
+    }
+
+    fn count(&self, type_: &[u8], query: &language::Operator) -> IndyResult<usize> {
+        Ok(self.data.borrow().select(type_, query, &[])?.len())
+    }
+
+    fn group_count_by_tag(&self, type_: &[u8], query: &language::Operator, tag_name: &[u8]) -> IndyResult<Vec<(String, usize)>> {
+        let data = self.data.borrow();
+        let mut groups: BTreeMap<String, usize> = BTreeMap::new();
+
+        for (_, item) in data.select(type_, query, &[])? {
+            if let Some(value) = query::plain_tag_value(tag_name, &item.tags) {
+                *groups.entry(value.to_string()).or_insert(0) += 1;
+            }
+        }
+
+        Ok(groups.into_iter().collect())
+    }
+
+    ///
+    /// Re-encrypts all items of a copy of the storage data, that replaces the data
+    /// together with the new metadata only if all items are re-encrypted.
+    ///
+    fn reencrypt_records(&self, metadata: &[u8], _batch_size: usize, reencrypt: &dyn Fn(StorageRecord) -> IndyResult<StorageRecord>) -> IndyResult<()> {
+        let fetch_options = RecordOptions {
+            retrieve_type: true,
+            retrieve_value: true,
+            retrieve_tags: true,
+        };
+
+        let mut data = InMemoryData::new(metadata);
+
+        {
+            let current = self.data.borrow();
+
+            for (position, item) in current.items.iter() {
+                let record = reencrypt(item.to_record(&fetch_options))?;
+
+                let item = match record {
+                    StorageRecord { id, value: Some(value), type_: Some(type_), tags } =>
+                        InMemoryItem { type_, id, value, tags: tags.unwrap_or_default() },
+                    _ => return Err(err_msg(IndyErrorKind::InvalidState, "Re-encrypted item must contain type and value"))
+                };
+
+                data.positions.insert((item.type_.clone(), item.id.clone()), *position);
+                data.items.insert(*position, item);
+            }
+
+            data.last_position = current.last_position;
+        }
+
+        *self.data.borrow_mut() = data;
+        Ok(())
+    }
+
+    fn orphaned_tags(&self, _delete: bool) -> IndyResult<Option<usize>> {
+        // Tags are kept together with items, so they can't refer to missing items
+        Ok(Some(0))
+    }
+
+    fn close(&mut self) -> IndyResult<()> {
+        Ok(())
+    }
+}
+
+///
+/// Storage that keeps wallets in process memory.
+/// Wallets are isolated by id and live until they are deleted or the process exits.
+/// Storage config and credentials are ignored.
+///
+impl WalletStorageType for InMemoryStorageType {
+    fn create_storage(&self, id: &str, _config: Option<&str>, _credentials: Option<&str>, metadata: &[u8]) -> IndyResult<()> {
+        let mut storages = self.storages.borrow_mut();
+
+        if storages.contains_key(id) {
+            return Err(err_msg(IndyErrorKind::WalletAlreadyExists, format!("In-memory wallet storage already exists: {}", id)));
+        }
+
+        storages.insert(id.to_string(), Rc::new(RefCell::new(InMemoryData::new(metadata))));
+        Ok(())
+    }
+
+    fn open_storage(&self, id: &str, _config: Option<&str>, _credentials: Option<&str>) -> IndyResult<Box<dyn WalletStorage>> {
+        let data = self.storages.borrow().get(id).cloned()
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletNotFound, format!("In-memory wallet storage isn't found: {}", id)))?;
+
+        Ok(Box::new(InMemoryStorage { data }))
+    }
+
+    fn delete_storage(&self, id: &str, _config: Option<&str>, _credentials: Option<&str>) -> IndyResult<()> {
+        self.storages.borrow_mut().remove(id)
+            .map(|_| ())
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletNotFound, format!("In-memory wallet storage isn't found: {}", id)))
+    }
+
+    fn check_migrations(&self, id: &str, _config: Option<&str>, _credentials: Option<&str>) -> IndyResult<Vec<SchemaMigration>> {
+        if !self.storages.borrow().contains_key(id) {
+            return Err(err_msg(IndyErrorKind::WalletNotFound, format!("In-memory wallet storage isn't found: {}", id)));
+        }
+
+        // In-memory storage has no persisted schema
+        Ok(Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::{Operator, TargetValue};
+
+    #[test]
+    fn inmemory_storage_type_create_works_for_twice() {
+        let storage_type = InMemoryStorageType::new();
+        storage_type.create_storage("inmemory_storage_type_create_works_for_twice", None, None, &_metadata()).unwrap();
+
+        let res = storage_type.create_storage("inmemory_storage_type_create_works_for_twice", None, None, &_metadata());
+        assert_kind!(IndyErrorKind::WalletAlreadyExists, res);
+    }
+
+    #[test]
+    fn inmemory_storage_type_open_works_for_isolation() {
+        let storage_type = InMemoryStorageType::new();
+        storage_type.create_storage("wallet1", None, None, &_metadata()).unwrap();
+        storage_type.create_storage("wallet2", None, None, &_metadata()).unwrap();
+
+        let storage1 = storage_type.open_storage("wallet1", None, None).unwrap();
+        storage1.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+
+        let storage2 = storage_type.open_storage("wallet2", None, None).unwrap();
+        let res = storage2.get(&_type1(), &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##);
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+        // data is kept between openings
+        let storage1 = storage_type.open_storage("wallet1", None, None).unwrap();
+        let record = storage1.get(&_type1(), &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
+        assert_eq!(record.value.unwrap(), _value1());
+        assert_eq!(_sort(record.tags.unwrap()), _sort(_tags()));
+    }
+
+    #[test]
+    fn inmemory_storage_type_delete_works() {
+        let storage_type = InMemoryStorageType::new();
+        storage_type.create_storage("inmemory_storage_type_delete_works", None, None, &_metadata()).unwrap();
+        storage_type.delete_storage("inmemory_storage_type_delete_works", None, None).unwrap();
+
+        let res = storage_type.open_storage("inmemory_storage_type_delete_works", None, None);
+        assert_kind!(IndyErrorKind::WalletNotFound, res);
+
+        let res = storage_type.delete_storage("inmemory_storage_type_delete_works", None, None);
+        assert_kind!(IndyErrorKind::WalletNotFound, res);
+    }
+
+    #[test]
+    fn inmemory_storage_metadata_works() {
+        let storage = _storage();
+        assert_eq!(storage.get_storage_metadata().unwrap(), _metadata());
+
+        storage.set_storage_metadata(&[1, 2, 3]).unwrap();
+        assert_eq!(storage.get_storage_metadata().unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn inmemory_storage_add_works_for_twice() {
+        let storage = _storage();
+        storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+
+        let res = storage.add(&_type1(), &_id1(), &_value2(), &_tags());
+        assert_kind!(IndyErrorKind::WalletItemAlreadyExists, res);
+
+        storage.add(&_type2(), &_id1(), &_value2(), &_tags()).unwrap();
+    }
+
+    #[test]
+    fn inmemory_storage_tags_works() {
+        let storage = _storage();
+        storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+
+        storage.add_tags(&_type1(), &_id1(), &_new_tags()).unwrap();
+        let record = storage.get(&_type1(), &_id1(), r##"{"retrieveTags": true}"##).unwrap();
+        let mut expected_tags = _tags();
+        expected_tags.extend(_new_tags());
+        assert_eq!(_sort(record.tags.unwrap()), _sort(expected_tags));
+
+        storage.update_tags(&_type1(), &_id1(), &_new_tags()).unwrap();
+        let record = storage.get(&_type1(), &_id1(), r##"{"retrieveTags": true}"##).unwrap();
+        assert_eq!(_sort(record.tags.unwrap()), _sort(_new_tags()));
+
+        storage.delete_tags(&_type1(), &_id1(), &[TagName::OfPlain(vec![1, 1, 1])]).unwrap();
+        let record = storage.get(&_type1(), &_id1(), r##"{"retrieveTags": true}"##).unwrap();
+        assert_eq!(record.tags.unwrap(), vec![Tag::Encrypted(vec![1, 1, 1], vec![2, 2, 2])]);
+
+        let res = storage.add_tags(&_type2(), &_id1(), &_new_tags());
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+    }
+
+    #[test]
+    fn inmemory_storage_batch_works_for_failed_operation() {
+        let storage = _storage();
+        storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+
+        let res = storage.batch(&[
+            StorageOperation::Delete(_type1(), _id1()),
+            StorageOperation::Update(_type1(), _id2(), _value2()),
+        ]);
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+        storage.get(&_type1(), &_id1(), "{}").unwrap();
+    }
+
+    #[test]
+    fn inmemory_storage_search_works() {
+        let storage = _storage();
+        storage.add(&_type1(), &_id1(), &_value1(), &[Tag::PlainText(vec![1], "b".to_string())]).unwrap();
+        storage.add(&_type1(), &_id2(), &_value2(), &[Tag::PlainText(vec![1], "a".to_string())]).unwrap();
+        storage.add(&_type2(), &_id1(), &_value1(), &[Tag::PlainText(vec![1], "a".to_string())]).unwrap();
+
+        let query = Operator::Eq(crate::language::TagName::PlainTagName(vec![1]), TargetValue::Unencrypted("a".to_string()));
+        let mut iterator = storage.search(&_type1(), &query, Some(r##"{"retrieveTotalCount": true}"##)).unwrap();
+        assert_eq!(Some(1), iterator.get_total_count().unwrap());
+        assert_eq!(_id2(), iterator.next().unwrap().unwrap().id);
+        assert!(iterator.next().unwrap().is_none());
+
+        let sort_by = format!(r##"{{"sortBy": [{{"field": "~{}"}}]}}"##, indy_utils::crypto::base64::encode(&[1]));
+        let mut iterator = storage.search(&_type1(), &Operator::And(vec![]), Some(&sort_by)).unwrap();
+        assert_eq!(_id2(), iterator.next().unwrap().unwrap().id);
+        assert_eq!(_id1(), iterator.next().unwrap().unwrap().id);
+
+        let res = storage.search(&_type1(), &Operator::And(vec![]), Some(r##"{"sortBy": [{"field": "tag"}]}"##));
+        assert_kind!(IndyErrorKind::WalletQueryError, res);
+    }
+
+    #[test]
+    fn inmemory_storage_search_page_works() {
+        let storage = _storage();
+        storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+        storage.add(&_type1(), &_id2(), &_value2(), &_tags()).unwrap();
+
+        let page = storage.search_page(&_type1(), &Operator::And(vec![]), None, None, 1).unwrap();
+        assert_eq!(_id1(), page.records[0].id);
+
+        let page = storage.search_page(&_type1(), &Operator::And(vec![]), None, page.cursor.as_ref().map(String::as_str), 1).unwrap();
+        assert_eq!(_id2(), page.records[0].id);
+        assert!(page.cursor.is_none());
+    }
+
+    fn _storage() -> Box<dyn WalletStorage> {
+        let storage_type = InMemoryStorageType::new();
+        storage_type.create_storage("wallet", None, None, &_metadata()).unwrap();
+        storage_type.open_storage("wallet", None, None).unwrap()
+    }
+
+    fn _metadata() -> Vec<u8> {
+        vec![1, 2, 3, 4, 5, 6, 7, 8]
+    }
+
+    fn _type1() -> Vec<u8> {
+        vec![1, 2, 3]
+    }
+
+    fn _type2() -> Vec<u8> {
+        vec![2, 3, 4]
+    }
+
+    fn _id1() -> Vec<u8> {
+        vec![3, 4, 5]
+    }
+
+    fn _id2() -> Vec<u8> {
+        vec![4, 5, 6]
+    }
+
+    fn _value1() -> EncryptedValue {
+        EncryptedValue::new(vec![1, 1, 1], vec![2, 2, 2])
+    }
+
+    fn _value2() -> EncryptedValue {
+        EncryptedValue::new(vec![3, 3, 3], vec![4, 4, 4])
+    }
+
+    fn _tags() -> Vec<Tag> {
+        vec![
+            Tag::Encrypted(vec![1, 5, 8], vec![3, 5, 6]),
+            Tag::PlainText(vec![1, 5, 8, 1], "Plain value".to_string()),
+        ]
+    }
+
+    fn _new_tags() -> Vec<Tag> {
+        vec![
+            Tag::Encrypted(vec![1, 1, 1], vec![2, 2, 2]),
+            Tag::PlainText(vec![1, 1, 1], String::from("tag_value_3"))
+        ]
+    }
+
+    fn _sort(mut v: Vec<Tag>) -> Vec<Tag> {
+        v.sort();
+        v
+    }
+}
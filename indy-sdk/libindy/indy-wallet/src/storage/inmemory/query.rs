@@ -0,0 +1,180 @@
+use indy_api_types::errors::prelude::*;
+use crate::language::{Operator, TagName, TargetValue};
+use super::super::Tag;
+
+
+// Evaluates Wallet Query Language against tags of one item
+// Semantic is the same as for SQL translation of default storage:
+// comparison operators are true only if the item has the tag.
+// Sub-operators are always evaluated, so malformed query fails for any item
+pub fn matches(op: &Operator, tags: &[Tag]) -> IndyResult<bool> {
+    match *op {
+        Operator::Eq(ref tag_name, ref target_value) => eq_matches(tag_name, target_value, tags),
+        Operator::Neq(ref tag_name, ref target_value) => neq_matches(tag_name, target_value, tags),
+        Operator::Gt(ref tag_name, ref target_value) => plain_cmp_matches(tag_name, target_value, tags, "$gt", |value, target| value > target),
+        Operator::Gte(ref tag_name, ref target_value) => plain_cmp_matches(tag_name, target_value, tags, "$gte", |value, target| value >= target),
+        Operator::Lt(ref tag_name, ref target_value) => plain_cmp_matches(tag_name, target_value, tags, "$lt", |value, target| value < target),
+        Operator::Lte(ref tag_name, ref target_value) => plain_cmp_matches(tag_name, target_value, tags, "$lte", |value, target| value <= target),
+        Operator::Like(ref tag_name, ref target_value) => plain_cmp_matches(tag_name, target_value, tags, "$like", like),
+        Operator::In(ref tag_name, ref target_values) => in_matches(tag_name, target_values, tags),
+        Operator::And(ref suboperators) => Ok(suboperators_matches(suboperators, tags)?.iter().all(|res| *res)),
+        Operator::Or(ref suboperators) => {
+            // empty $or is skipped by default storage the same way as empty $and
+            let res = suboperators_matches(suboperators, tags)?;
+            Ok(res.is_empty() || res.iter().any(|res| *res))
+        }
+        Operator::Not(ref suboperator) => Ok(!matches(suboperator, tags)?),
+    }
+}
+
+
+fn suboperators_matches(suboperators: &[Operator], tags: &[Tag]) -> IndyResult<Vec<bool>> {
+    suboperators
+        .iter()
+        .map(|suboperator| matches(suboperator, tags))
+        .collect()
+}
+
+
+fn eq_matches(name: &TagName, value: &TargetValue, tags: &[Tag]) -> IndyResult<bool> {
+    match (name, value) {
+        (&TagName::PlainTagName(ref queried_name), &TargetValue::Unencrypted(ref queried_value)) =>
+            Ok(plain_tag_value(queried_name, tags).map_or(false, |value| value == queried_value.as_str())),
+        (&TagName::EncryptedTagName(ref queried_name), &TargetValue::Encrypted(ref queried_value)) =>
+            Ok(encrypted_tag_value(queried_name, tags).map_or(false, |value| value == queried_value.as_slice())),
+        _ => Err(err_msg(IndyErrorKind::WalletQueryError, "Invalid combination of tag name and value for equality operator"))
+    }
+}
+
+
+fn neq_matches(name: &TagName, value: &TargetValue, tags: &[Tag]) -> IndyResult<bool> {
+    match (name, value) {
+        (&TagName::PlainTagName(ref queried_name), &TargetValue::Unencrypted(ref queried_value)) =>
+            Ok(plain_tag_value(queried_name, tags).map_or(false, |value| value != queried_value.as_str())),
+        (&TagName::EncryptedTagName(ref queried_name), &TargetValue::Encrypted(ref queried_value)) =>
+            Ok(encrypted_tag_value(queried_name, tags).map_or(false, |value| value != queried_value.as_slice())),
+        _ => Err(err_msg(IndyErrorKind::WalletQueryError, "Invalid combination of tag name and value for inequality operator"))
+    }
+}
+
+
+fn plain_cmp_matches(name: &TagName, value: &TargetValue, tags: &[Tag], operator: &str, cmp: fn(&str, &str) -> bool) -> IndyResult<bool> {
+    match (name, value) {
+        (&TagName::PlainTagName(ref queried_name), &TargetValue::Unencrypted(ref queried_value)) =>
+            Ok(plain_tag_value(queried_name, tags).map_or(false, |value| cmp(value, queried_value))),
+        _ => Err(err_msg(IndyErrorKind::WalletQueryError, format!("Invalid combination of tag name and value for {} operator", operator)))
+    }
+}
+
+
+fn in_matches(name: &TagName, values: &[TargetValue], tags: &[Tag]) -> IndyResult<bool> {
+    match *name {
+        TagName::PlainTagName(ref queried_name) => {
+            let mut targets = Vec::with_capacity(values.len());
+
+            for value in values {
+                match *value {
+                    TargetValue::Unencrypted(ref target) => targets.push(target.as_str()),
+                    _ => return Err(err_msg(IndyErrorKind::WalletQueryError, "Encrypted tag value in $in for nonencrypted tag name"))
+                }
+            }
+
+            Ok(plain_tag_value(queried_name, tags).map_or(false, |value| targets.contains(&value)))
+        }
+        TagName::EncryptedTagName(ref queried_name) => {
+            let mut targets = Vec::with_capacity(values.len());
+
+            for value in values {
+                match *value {
+                    TargetValue::Encrypted(ref target) => targets.push(target.as_slice()),
+                    _ => return Err(err_msg(IndyErrorKind::WalletQueryError, "Unencrypted tag value in $in for encrypted tag name"))
+                }
+            }
+
+            Ok(encrypted_tag_value(queried_name, tags).map_or(false, |value| targets.contains(&value)))
+        }
+    }
+}
+
+
+pub fn plain_tag_value<'a>(name: &[u8], tags: &'a [Tag]) -> Option<&'a str> {
+    tags.iter().find_map(|tag| match *tag {
+        Tag::PlainText(ref tag_name, ref tag_value) if tag_name.as_slice() == name => Some(tag_value.as_str()),
+        _ => None
+    })
+}
+
+
+fn encrypted_tag_value<'a>(name: &[u8], tags: &'a [Tag]) -> Option<&'a [u8]> {
+    tags.iter().find_map(|tag| match *tag {
+        Tag::Encrypted(ref tag_name, ref tag_value) if tag_name.as_slice() == name => Some(tag_value.as_slice()),
+        _ => None
+    })
+}
+
+
+// SQL LIKE: '%' matches any sequence of characters, '_' matches any single character,
+// ASCII characters are compared case-insensitively
+fn like(value: &str, pattern: &str) -> bool {
+    let value: Vec<char> = value.chars().collect();
+    let pattern: Vec<char> = pattern.chars().collect();
+    like_chars(&value, &pattern)
+}
+
+
+fn like_chars(value: &[char], pattern: &[char]) -> bool {
+    match pattern.split_first() {
+        None => value.is_empty(),
+        Some((&'%', rest)) => (0..=value.len()).any(|skip| like_chars(&value[skip..], rest)),
+        Some((&'_', rest)) => !value.is_empty() && like_chars(&value[1..], rest),
+        Some((c, rest)) => value.first().map_or(false, |v| v.eq_ignore_ascii_case(c)) && like_chars(&value[1..], rest),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn _tags() -> Vec<Tag> {
+        vec![
+            Tag::Encrypted(vec![1, 2, 3], vec![4, 5, 6]),
+            Tag::PlainText(vec![7, 8, 9], "spam".to_string()),
+        ]
+    }
+
+    #[test]
+    fn matches_works_for_eq_and_not() {
+        let query = Operator::And(vec![
+            Operator::Eq(TagName::EncryptedTagName(vec![1, 2, 3]), TargetValue::Encrypted(vec![4, 5, 6])),
+            Operator::Not(Box::new(Operator::Eq(TagName::PlainTagName(vec![7, 8, 9]), TargetValue::Unencrypted("eggs".to_string())))),
+        ]);
+        assert!(matches(&query, &_tags()).unwrap());
+        assert!(!matches(&query, &[]).unwrap());
+    }
+
+    #[test]
+    fn matches_works_for_comparison() {
+        let query = Operator::Gt(TagName::PlainTagName(vec![7, 8, 9]), TargetValue::Unencrypted("eggs".to_string()));
+        assert!(matches(&query, &_tags()).unwrap());
+
+        let query = Operator::Lte(TagName::PlainTagName(vec![7, 8, 9]), TargetValue::Unencrypted("eggs".to_string()));
+        assert!(!matches(&query, &_tags()).unwrap());
+    }
+
+    #[test]
+    fn matches_works_for_like() {
+        assert!(matches(&Operator::Like(TagName::PlainTagName(vec![7, 8, 9]), TargetValue::Unencrypted("S%".to_string())), &_tags()).unwrap());
+        assert!(matches(&Operator::Like(TagName::PlainTagName(vec![7, 8, 9]), TargetValue::Unencrypted("_pa_".to_string())), &_tags()).unwrap());
+        assert!(!matches(&Operator::Like(TagName::PlainTagName(vec![7, 8, 9]), TargetValue::Unencrypted("%x%".to_string())), &_tags()).unwrap());
+    }
+
+    #[test]
+    fn matches_works_for_invalid_combination() {
+        let query = Operator::Or(vec![
+            Operator::Eq(TagName::PlainTagName(vec![7, 8, 9]), TargetValue::Unencrypted("spam".to_string())),
+            Operator::Gt(TagName::EncryptedTagName(vec![1, 2, 3]), TargetValue::Encrypted(vec![4, 5, 6])),
+        ]);
+        assert_kind!(IndyErrorKind::WalletQueryError, matches(&query, &_tags()));
+    }
+}
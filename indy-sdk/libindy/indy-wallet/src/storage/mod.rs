@@ -1,9 +1,12 @@
 pub mod default;
+pub mod inmemory;
 pub mod plugged;
 
 use indy_api_types::errors::prelude::*;
+use indy_utils::crypto::base64;
 use crate::language;
 use crate::wallet::EncryptedValue;
+use crate::{SortBy, SortDirection};
 
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq)]
 pub enum Tag {
@@ -36,6 +39,87 @@ impl StorageRecord {
     }
 }
 
+#[derive(Debug)]
+pub enum StorageOperation {
+    Add(Vec<u8> /* type_ */, Vec<u8> /* id */, EncryptedValue, Vec<Tag>),
+    Update(Vec<u8> /* type_ */, Vec<u8> /* id */, EncryptedValue),
+    AddTags(Vec<u8> /* type_ */, Vec<u8> /* id */, Vec<Tag>),
+    UpdateTags(Vec<u8> /* type_ */, Vec<u8> /* id */, Vec<Tag>),
+    DeleteTags(Vec<u8> /* type_ */, Vec<u8> /* id */, Vec<TagName>),
+    Delete(Vec<u8> /* type_ */, Vec<u8> /* id */),
+}
+
+impl StorageOperation {
+    pub fn get_type_and_id(&self) -> (&[u8], &[u8]) {
+        match *self {
+            StorageOperation::Add(ref type_, ref id, _, _) |
+            StorageOperation::Update(ref type_, ref id, _) |
+            StorageOperation::AddTags(ref type_, ref id, _) |
+            StorageOperation::UpdateTags(ref type_, ref id, _) |
+            StorageOperation::DeleteTags(ref type_, ref id, _) |
+            StorageOperation::Delete(ref type_, ref id) => (type_, id),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct StoragePage {
+    pub records: Vec<StorageRecord>,
+    pub cursor: Option<String>,
+    pub total_count: Option<usize>,
+}
+
+// Cursor is opaque for storage users, storages keep position of the last returned record in it
+fn encode_cursor(position: i64) -> String {
+    base64::encode_urlsafe(position.to_string().as_bytes())
+}
+
+fn decode_cursor(cursor: &str) -> Result<i64, IndyError> {
+    base64::decode_urlsafe(cursor).ok()
+        .and_then(|position| String::from_utf8(position).ok())
+        .and_then(|position| position.parse::<i64>().ok())
+        .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Invalid search cursor"))
+}
+
+pub enum SortField {
+    // Order records were added in. Record ids are stored encrypted, so they can't be sorted by.
+    InsertionOrder,
+    PlainTagName(Vec<u8>),
+}
+
+pub struct SortOrder {
+    pub field: SortField,
+    pub direction: SortDirection,
+}
+
+// Parses sorting from search options
+// Tag names are expected to be encrypted by wallet and encoded as "~" followed by base64 of the name
+fn sort_orders(sort_by: Option<&[SortBy]>) -> Result<Vec<SortOrder>, IndyError> {
+    sort_by
+        .unwrap_or(&[])
+        .iter()
+        .map(|sort| {
+            let field = if sort.field == "insertion_order" {
+                SortField::InsertionOrder
+            } else if sort.field.starts_with('~') {
+                let name = base64::decode(&sort.field[1..])
+                    .map_err(|_| err_msg(IndyErrorKind::WalletQueryError, format!("Invalid sort field: {}", sort.field)))?;
+                SortField::PlainTagName(name)
+            } else {
+                return Err(err_msg(IndyErrorKind::WalletQueryError, format!("Invalid sort field: {}", sort.field)));
+            };
+
+            Ok(SortOrder { field, direction: sort.direction })
+        })
+        .collect()
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct SchemaMigration {
+    pub version: u32,
+    pub description: String,
+}
+
 pub trait StorageIterator {
     fn next(&mut self) -> Result<Option<StorageRecord>, IndyError>;
     fn get_total_count(&self) -> Result<Option<usize>, IndyError>;
@@ -49,10 +133,16 @@ pub trait WalletStorage {
     fn update_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> Result<(), IndyError>;
     fn delete_tags(&self, type_: &[u8], id: &[u8], tag_names: &[TagName]) -> Result<(), IndyError>;
     fn delete(&self, type_: &[u8], id: &[u8]) -> Result<(), IndyError>;
+    fn batch(&self, operations: &[StorageOperation]) -> Result<(), IndyError>;
     fn get_storage_metadata(&self) -> Result<Vec<u8>, IndyError>;
     fn set_storage_metadata(&self, metadata: &[u8]) -> Result<(), IndyError>;
     fn get_all(&self) -> Result<Box<dyn StorageIterator>, IndyError>;
     fn search(&self, type_: &[u8], query: &language::Operator, options: Option<&str>) -> Result<Box<dyn StorageIterator>, IndyError>;
+    fn search_page(&self, type_: &[u8], query: &language::Operator, options: Option<&str>, cursor: Option<&str>, limit: usize) -> Result<StoragePage, IndyError>;
+    fn count(&self, type_: &[u8], query: &language::Operator) -> Result<usize, IndyError>;
+    fn group_count_by_tag(&self, type_: &[u8], query: &language::Operator, tag_name: &[u8]) -> Result<Vec<(String, usize)>, IndyError>;
+    fn reencrypt_records(&self, metadata: &[u8], batch_size: usize, reencrypt: &dyn Fn(StorageRecord) -> Result<StorageRecord, IndyError>) -> Result<(), IndyError>;
+    fn orphaned_tags(&self, delete: bool) -> Result<Option<usize>, IndyError>;
     fn close(&mut self) -> Result<(), IndyError>;
 }
 
@@ -60,4 +150,17 @@ pub trait WalletStorageType {
     fn create_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>, metadata: &[u8]) -> Result<(), IndyError>;
     fn open_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>) -> Result<Box<dyn WalletStorage>, IndyError>;
     fn delete_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>) -> Result<(), IndyError>;
-}
\ No newline at end of file
+    fn check_migrations(&self, id: &str, config: Option<&str>, credentials: Option<&str>) -> Result<Vec<SchemaMigration>, IndyError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sort_orders_works_for_encrypted_tag() {
+        let sort_by = vec![SortBy { field: "tag".to_string(), direction: SortDirection::Asc }];
+        let res = sort_orders(Some(sort_by.as_slice()));
+        assert_eq!(IndyErrorKind::WalletQueryError, res.err().unwrap().kind());
+    }
+}
@@ -1,5 +1,5 @@
 use std::{slice, str};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::ffi::{CStr, CString};
 use std::ptr;
 
@@ -12,7 +12,7 @@ use indy_api_types::errors::prelude::*;
 use crate::language;
 use indy_utils::crypto::base64;
 
-use super::{EncryptedValue, StorageIterator, StorageRecord, Tag, TagName, WalletStorage, WalletStorageType};
+use super::{decode_cursor, encode_cursor, EncryptedValue, SchemaMigration, StorageIterator, StorageOperation, StoragePage, StorageRecord, Tag, TagName, WalletStorage, WalletStorageType};
 use super::super::{RecordOptions, SearchOptions};
 
 #[derive(Debug, Deserialize)]
@@ -285,6 +285,53 @@ impl PluggedStorage {
             close_handler,
         }
     }
+
+    fn _apply(&self, operation: &StorageOperation) -> IndyResult<()> {
+        match *operation {
+            StorageOperation::Add(ref type_, ref id, ref value, ref tags) => self.add(type_, id, value, tags),
+            StorageOperation::Update(ref type_, ref id, ref value) => self.update(type_, id, value),
+            StorageOperation::AddTags(ref type_, ref id, ref tags) => self.add_tags(type_, id, tags),
+            StorageOperation::UpdateTags(ref type_, ref id, ref tags) => self.update_tags(type_, id, tags),
+            StorageOperation::DeleteTags(ref type_, ref id, ref tag_names) => self.delete_tags(type_, id, tag_names),
+            StorageOperation::Delete(ref type_, ref id) => self.delete(type_, id),
+        }
+    }
+
+    // Restores saved state of items in reverse order. If some item can't be restored the wallet stays
+    // partially modified, so InvalidState error naming such items is returned instead of the original one.
+    fn _restore(&self, saved: Vec<(&[u8], &[u8], Option<StorageRecord>)>, err: IndyError) -> IndyError {
+        let inconsistent: Vec<String> = saved
+            .into_iter()
+            .rev()
+            .filter(|&(type_, id, ref record)| self._restore_record(type_, id, record).is_err())
+            .map(|(type_, id, _)| format!("{}:{}", base64::encode(type_), base64::encode(id)))
+            .collect();
+
+        if inconsistent.is_empty() {
+            return err;
+        }
+
+        err_msg(IndyErrorKind::InvalidState,
+                format!("Batch failed with error \"{}\" and can't be rolled back, records (type:id) left inconsistent: {}",
+                        err, inconsistent.join(", ")))
+    }
+
+    fn _restore_record(&self, type_: &[u8], id: &[u8], record: &Option<StorageRecord>) -> IndyResult<()> {
+        match self.delete(type_, id) {
+            Ok(()) => (),
+            Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound => (),
+            Err(err) => return Err(err),
+        }
+
+        if let Some(record) = record {
+            let value = record.value.as_ref()
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "Saved item doesn't contain value"))?;
+
+            self.add(type_, id, value, record.tags.as_ref().map(Vec::as_slice).unwrap_or(&[]))?;
+        }
+
+        Ok(())
+    }
 }
 
 fn _tags_to_json(tags: &[Tag]) -> IndyResult<String> {
@@ -518,6 +565,33 @@ impl WalletStorage for PluggedStorage {
         Ok(())
     }
 
+    fn batch(&self, operations: &[StorageOperation]) -> IndyResult<()> {
+        // Plugged storage API doesn't provide transactions, so state of each affected item is saved
+        // before applying an operation and restored if any of the following operations fails.
+        let options = serde_json::to_string(&RecordOptions { retrieve_type: true, retrieve_value: true, retrieve_tags: true })
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RecordOptions")?;
+
+        let mut saved: Vec<(&[u8], &[u8], Option<StorageRecord>)> = Vec::with_capacity(operations.len());
+
+        for operation in operations {
+            let (type_, id) = operation.get_type_and_id();
+
+            let record = match self.get(type_, id, &options) {
+                Ok(record) => Some(record),
+                Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound => None,
+                Err(err) => return Err(self._restore(saved, err))
+            };
+
+            saved.push((type_, id, record));
+
+            if let Err(err) = self._apply(operation) {
+                return Err(self._restore(saved, err));
+            }
+        }
+
+        Ok(())
+    }
+
     fn get_storage_metadata(&self) -> IndyResult<Vec<u8>> {
         let mut metadata_ptr: *const c_char = ptr::null_mut();
         let mut metadata_handle = -1;
@@ -572,6 +646,7 @@ impl WalletStorage for PluggedStorage {
                     retrieve_type: true,
                     retrieve_value: true,
                     retrieve_tags: true,
+                    sort_by: None,
                 },
             )
         ))
@@ -585,6 +660,11 @@ impl WalletStorage for PluggedStorage {
         let options: SearchOptions = serde_json::from_str(options.unwrap_or("{}"))
             .to_indy(IndyErrorKind::InvalidStructure, "Search options is malformed json")?;
 
+        // Plugin API has no notion of sorting, so plugins can't be relied on to order records
+        if options.sort_by.is_some() {
+            return Err(err_msg(IndyErrorKind::WalletQueryError, "Sorting isn't supported by plugged wallet storage"));
+        }
+
         let mut search_handle: SearchHandle = INVALID_SEARCH_HANDLE;
 
         let err = (self.search_records_handler)(self.handle,
@@ -606,6 +686,104 @@ impl WalletStorage for PluggedStorage {
         ))
     }
 
+    fn search_page(&self, type_: &[u8], query: &language::Operator, options: Option<&str>, cursor: Option<&str>, limit: usize) -> IndyResult<StoragePage> {
+        // Plugged storage API provides only stateful searches, so the search is repeated
+        // for every page and records before the cursor position are skipped.
+        let offset = match cursor {
+            Some(cursor) => decode_cursor(cursor)?,
+            None => 0
+        };
+
+        if offset < 0 {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Invalid search cursor"));
+        }
+
+        let mut search = self.search(type_, query, options)?;
+
+        for _ in 0..offset {
+            if search.next()?.is_none() {
+                break;
+            }
+        }
+
+        let mut records = Vec::new();
+        let mut next_cursor = None;
+
+        while let Some(record) = search.next()? {
+            if records.len() == limit {
+                next_cursor = Some(encode_cursor(offset + limit as i64));
+                break;
+            }
+
+            records.push(record);
+        }
+
+        Ok(StoragePage {
+            records,
+            cursor: next_cursor,
+            total_count: search.get_total_count()?,
+        })
+    }
+
+    fn count(&self, type_: &[u8], query: &language::Operator) -> IndyResult<usize> {
+        let options = SearchOptions {
+            retrieve_records: false,
+            retrieve_total_count: true,
+            retrieve_type: false,
+            retrieve_value: false,
+            retrieve_tags: false,
+            sort_by: None,
+        };
+        let options = serde_json::to_string(&options)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize search options")?;
+
+        let mut search = self.search(type_, query, Some(&options))?;
+
+        search.get_total_count()?
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "Plugged storage returned no total count"))
+    }
+
+    fn group_count_by_tag(&self, type_: &[u8], query: &language::Operator, tag_name: &[u8]) -> IndyResult<Vec<(String, usize)>> {
+        // Plugged storage API has no aggregation, so all matching records are fetched with tags.
+        let options = SearchOptions {
+            retrieve_records: true,
+            retrieve_total_count: false,
+            retrieve_type: false,
+            retrieve_value: false,
+            retrieve_tags: true,
+            sort_by: None,
+        };
+        let options = serde_json::to_string(&options)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize search options")?;
+
+        let mut search = self.search(type_, query, Some(&options))?;
+        let mut groups: BTreeMap<String, usize> = BTreeMap::new();
+
+        while let Some(record) = search.next()? {
+            let value = record.tags.as_ref()
+                .and_then(|tags| tags.iter().find_map(|tag| match tag {
+                    Tag::PlainText(name, value) if name.as_slice() == tag_name => Some(value.clone()),
+                    _ => None
+                }));
+
+            if let Some(value) = value {
+                *groups.entry(value).or_insert(0) += 1;
+            }
+        }
+
+        Ok(groups.into_iter().collect())
+    }
+
+    fn reencrypt_records(&self, _metadata: &[u8], _batch_size: usize, _reencrypt: &dyn Fn(StorageRecord) -> IndyResult<StorageRecord>) -> IndyResult<()> {
+        // Plugged storage API doesn't provide transactions, so items can't be atomically re-encrypted.
+        Err(err_msg(IndyErrorKind::InvalidState, "Keys rotation isn't supported by plugged wallet storage"))
+    }
+
+    fn orphaned_tags(&self, _delete: bool) -> IndyResult<Option<usize>> {
+        // Plugged storage API doesn't expose tags separately from records, so the check isn't performed.
+        Ok(None)
+    }
+
     fn close(&mut self) -> IndyResult<()> {
         let err = (self.close_handler)(self.handle);
 
@@ -805,6 +983,11 @@ impl WalletStorageType for PluggedStorageType {
 
         Ok(())
     }
+
+    fn check_migrations(&self, _id: &str, _config: Option<&str>, _credentials: Option<&str>) -> IndyResult<Vec<SchemaMigration>> {
+        // Plugged storage manages its schema by itself
+        Ok(Vec::new())
+    }
 }
 
 #[cfg(test)]
@@ -2100,6 +2283,15 @@ mod tests {
         assert_eq!(&expected_free_search_call, debug.get(6).unwrap());
     }
 
+    #[test]
+    fn plugged_storage_search_works_for_sort_by() {
+        let storage = _open_storage();
+
+        let options = r#"{"sortBy": [{"field": "insertion_order"}]}"#;
+        let res = storage.search(&_random_vector(32), &language::Operator::And(vec![]), Some(options));
+        assert_eq!(IndyErrorKind::WalletQueryError, res.err().unwrap().kind());
+    }
+
     #[test]
     fn plugged_storage_get_all_works() {
         DEBUG_VEC.write().unwrap().clear();
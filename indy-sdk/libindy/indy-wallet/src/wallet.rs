@@ -1,18 +1,19 @@
 use std::collections::HashMap;
 use std::rc::Rc;
 
-use indy_utils::crypto::{hmacsha256, chacha20poly1305_ietf};
+use indy_utils::crypto::{base64, hmacsha256, chacha20poly1305_ietf};
 use indy_utils::wql::Query;
 
 use indy_api_types::errors::prelude::*;
 
 use zeroize::Zeroize;
 
+use super::language;
 use super::storage;
 use super::iterator::WalletIterator;
 use super::encryption::*;
 use super::query_encryption::encrypt_query;
-use super::WalletRecord;
+use super::{SearchOptions, WalletRecord, WalletRecordOperation, WalletSearchPage, WalletVerificationReport};
 
 #[derive(Serialize, Deserialize)]
 pub(super) struct Keys {
@@ -112,6 +113,8 @@ impl EncryptedValue {
     }
 }
 
+const REENCRYPT_BATCH_SIZE: usize = 100;
+
 pub(super) struct Wallet {
     id: String,
     storage: Box<dyn storage::WalletStorage>,
@@ -164,6 +167,16 @@ impl Wallet {
         Ok(())
     }
 
+    pub fn batch(&self, operations: &[WalletRecordOperation]) -> IndyResult<()> {
+        let encrypted_operations = operations
+            .iter()
+            .map(|operation| self._encrypt_operation(operation))
+            .collect::<Vec<storage::StorageOperation>>();
+
+        self.storage.batch(&encrypted_operations)?;
+        Ok(())
+    }
+
     pub fn get(&self, type_: &str, name: &str, options: &str) -> IndyResult<WalletRecord> {
         let etype = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
         let ename = encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
@@ -189,6 +202,31 @@ impl Wallet {
     }
 
     pub fn search<'a>(&'a self, type_: &str, query: &str, options: Option<&str>) -> IndyResult<WalletIterator> {
+        let (encrypted_type_, encrypted_query) = self._encrypt_search(type_, query)?;
+        let options = self._encrypt_search_options(options)?;
+        let storage_iterator = self.storage.search(&encrypted_type_, &encrypted_query, options.as_ref().map(String::as_str))?;
+        let wallet_iterator = WalletIterator::new(storage_iterator, Rc::clone(&self.keys));
+        Ok(wallet_iterator)
+    }
+
+    pub fn search_page(&self, type_: &str, query: &str, options: Option<&str>, cursor: Option<&str>, limit: usize) -> IndyResult<WalletSearchPage> {
+        let (encrypted_type_, encrypted_query) = self._encrypt_search(type_, query)?;
+        let options = self._encrypt_search_options(options)?;
+        let storage_page = self.storage.search_page(&encrypted_type_, &encrypted_query, options.as_ref().map(String::as_str), cursor, limit)?;
+
+        let records = storage_page.records
+            .iter()
+            .map(|record| decrypt_storage_record(record, &self.keys))
+            .collect::<IndyResult<Vec<WalletRecord>>>()?;
+
+        Ok(WalletSearchPage {
+            total_count: storage_page.total_count,
+            records: if records.is_empty() { None } else { Some(records) },
+            cursor: storage_page.cursor,
+        })
+    }
+
+    fn _encrypt_search(&self, type_: &str, query: &str) -> IndyResult<(Vec<u8>, language::Operator)> {
         let parsed_query: Query = ::serde_json::from_str::<Query>(query)
             .map_err(|err| IndyError::from_msg(IndyErrorKind::WalletQueryError, err))?
             .optimise()
@@ -196,9 +234,59 @@ impl Wallet {
 
         let encrypted_query = encrypt_query(parsed_query, &self.keys)?;
         let encrypted_type_ = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
-        let storage_iterator = self.storage.search(&encrypted_type_, &encrypted_query, options)?;
-        let wallet_iterator = WalletIterator::new(storage_iterator, Rc::clone(&self.keys));
-        Ok(wallet_iterator)
+        Ok((encrypted_type_, encrypted_query))
+    }
+
+    pub fn count(&self, type_: &str, query: &str) -> IndyResult<usize> {
+        let (encrypted_type_, encrypted_query) = self._encrypt_search(type_, query)?;
+        self.storage.count(&encrypted_type_, &encrypted_query)
+    }
+
+    pub fn group_count_by_tag(&self, type_: &str, query: &str, tag_name: &str) -> IndyResult<Vec<(String, usize)>> {
+        if !tag_name.starts_with('~') {
+            return Err(err_msg(IndyErrorKind::WalletQueryError,
+                               format!("Grouping is supported only by unencrypted tags, got: {}", tag_name)));
+        }
+
+        let (encrypted_type_, encrypted_query) = self._encrypt_search(type_, query)?;
+        let encrypted_tag_name = encrypt_as_searchable(&tag_name.as_bytes()[1..], &self.keys.tag_name_key, &self.keys.tags_hmac_key);
+        self.storage.group_count_by_tag(&encrypted_type_, &encrypted_query, &encrypted_tag_name)
+    }
+
+    // Storage can't see tag names, so names of tags used for sorting are replaced with
+    // "~" followed by base64 of the encrypted name.
+    fn _encrypt_search_options(&self, options: Option<&str>) -> IndyResult<Option<String>> {
+        let options_json = match options {
+            Some(options_json) => options_json,
+            None => return Ok(None)
+        };
+
+        let mut search_options: SearchOptions = ::serde_json::from_str(options_json)
+            .to_indy(IndyErrorKind::InvalidStructure, "Search options is malformed json")?;
+
+        let sort_by = match search_options.sort_by.as_mut() {
+            Some(sort_by) => sort_by,
+            None => return Ok(Some(options_json.to_string()))
+        };
+
+        for sort in sort_by.iter_mut() {
+            if sort.field == "insertion_order" {
+                continue;
+            }
+
+            if !sort.field.starts_with('~') {
+                return Err(err_msg(IndyErrorKind::WalletQueryError,
+                                   format!("Sorting is supported only by insertion order and unencrypted tags, got: {}", sort.field)));
+            }
+
+            let encrypted_name = encrypt_as_searchable(&sort.field.as_bytes()[1..], &self.keys.tag_name_key, &self.keys.tags_hmac_key);
+            sort.field = format!("~{}", base64::encode(&encrypted_name));
+        }
+
+        let options_json = ::serde_json::to_string(&search_options)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize search options")?;
+
+        Ok(Some(options_json))
     }
 
     pub fn close(&mut self) -> IndyResult<()> {
@@ -211,9 +299,90 @@ impl Wallet {
         Ok(WalletIterator::new(all_items, Rc::clone(&self.keys)))
     }
 
+    ///
+    /// Checks that every record can be decrypted with the current keys and storage has no orphaned tags.
+    /// In repair mode orphaned tags are deleted and then unreadable records are deleted in a single batch.
+    /// Orphaned tags are reported as None if the storage can't check them.
+    ///
+    pub fn verify(&self, repair: bool) -> IndyResult<WalletVerificationReport> {
+        let mut records_count = 0;
+        let mut unreadable_records = Vec::new();
+
+        {
+            let mut records = self.storage.get_all()?;
+
+            while let Some(record) = records.next()? {
+                records_count += 1;
+
+                if let Err(err) = decrypt_storage_record(&record, &self.keys) {
+                    warn!("Wallet {} contains unreadable record: {}", self.id, err);
+
+                    let type_ = record.type_
+                        .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "Record type isn't returned by storage"))?;
+                    unreadable_records.push(storage::StorageOperation::Delete(type_, record.id));
+                }
+            }
+        }
+
+        let orphaned_tags = self.storage.orphaned_tags(repair)?;
+
+        if repair && !unreadable_records.is_empty() {
+            self.storage.batch(&unreadable_records)?;
+        }
+
+        Ok(WalletVerificationReport {
+            records_count,
+            unreadable_records: unreadable_records.len(),
+            orphaned_tags,
+            repaired: repair && (!unreadable_records.is_empty() || orphaned_tags.unwrap_or(0) > 0),
+        })
+    }
+
     pub fn get_id<'a>(&'a self) -> &'a str {
         &self.id
     }
+
+    fn _encrypt_operation(&self, operation: &WalletRecordOperation) -> storage::StorageOperation {
+        let encrypted_type = encrypt_as_searchable(operation.get_type().as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
+        let encrypted_name = encrypt_as_searchable(operation.get_id().as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
+
+        match *operation {
+            WalletRecordOperation::Add { ref value, ref tags, .. } =>
+                storage::StorageOperation::Add(encrypted_type, encrypted_name,
+                                               EncryptedValue::encrypt(value, &self.keys.value_key),
+                                               encrypt_tags(tags.as_ref().unwrap_or(&HashMap::new()), &self.keys.tag_name_key, &self.keys.tag_value_key, &self.keys.tags_hmac_key)),
+            WalletRecordOperation::Update { ref value, .. } =>
+                storage::StorageOperation::Update(encrypted_type, encrypted_name,
+                                                  EncryptedValue::encrypt(value, &self.keys.value_key)),
+            WalletRecordOperation::AddTags { ref tags, .. } =>
+                storage::StorageOperation::AddTags(encrypted_type, encrypted_name,
+                                                   encrypt_tags(tags, &self.keys.tag_name_key, &self.keys.tag_value_key, &self.keys.tags_hmac_key)),
+            WalletRecordOperation::UpdateTags { ref tags, .. } =>
+                storage::StorageOperation::UpdateTags(encrypted_type, encrypted_name,
+                                                      encrypt_tags(tags, &self.keys.tag_name_key, &self.keys.tag_value_key, &self.keys.tags_hmac_key)),
+            WalletRecordOperation::DeleteTags { ref tag_names, .. } => {
+                let tag_names = tag_names.iter().map(String::as_str).collect::<Vec<&str>>();
+                storage::StorageOperation::DeleteTags(encrypted_type, encrypted_name,
+                                                      encrypt_tag_names(&tag_names, &self.keys.tag_name_key, &self.keys.tags_hmac_key))
+            }
+            WalletRecordOperation::Delete { .. } =>
+                storage::StorageOperation::Delete(encrypted_type, encrypted_name),
+        }
+    }
+
+    pub fn get_storage_metadata(&self) -> IndyResult<Vec<u8>> {
+        self.storage.get_storage_metadata()
+    }
+
+    pub fn rotate_keys(&mut self, new_keys: Keys, metadata: &[u8]) -> IndyResult<()> {
+        {
+            let old_keys = &self.keys;
+            self.storage.reencrypt_records(metadata, REENCRYPT_BATCH_SIZE, &|record| reencrypt_storage_record(&record, old_keys, &new_keys))?;
+        }
+
+        self.keys = Rc::new(new_keys);
+        Ok(())
+    }
 }
 
 #[cfg(test)]
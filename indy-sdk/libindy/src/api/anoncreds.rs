@@ -11,7 +11,8 @@ use crate::domain::anoncreds::credential_definition::{CredentialDefinition, Cred
 use crate::domain::anoncreds::credential_offer::CredentialOffer;
 use crate::domain::anoncreds::credential_request::{CredentialRequest, CredentialRequestMetadata};
 use crate::domain::anoncreds::credential_attr_tag_policy::CredentialAttrTagPolicy;
-use crate::domain::anoncreds::credential::{Credential, CredentialValues};
+use crate::domain::anoncreds::credential::{Credential, CredentialValues, CredentialIssuanceRequests};
+use crate::domain::anoncreds::issued_credential_record::IssuedCredentialMetadata;
 use crate::domain::anoncreds::revocation_registry_definition::{RevocationRegistryConfig, RevocationRegistryDefinition, RevocationRegistryId, RevocationRegistryDefinitions};
 use crate::domain::anoncreds::revocation_registry_delta::RevocationRegistryDelta;
 use crate::domain::anoncreds::proof::Proof;
@@ -145,6 +146,16 @@ pub extern fn indy_issuer_create_schema(command_handle: CommandHandle,
 /// - 'CL':
 ///     {
 ///         "support_revocation" - bool (optional, default false) whether to request non-revocation credential
+///         "revocation_registry_rollover" - (optional) enables revocation registry rollover: when a revocation registry
+///             of the credential definition is full `indy_issuer_create_credential_with_rollover` and `indy_issuer_create_credentials_batch`
+///             continue issuance from the next registry created in advance with the same parameters and incremented tag ("TAG_1" -> "TAG_2").
+///             `indy_issuer_create_credential` only continues issuance from the next registry if it is already created.
+///             Requires "support_revocation".
+///             {
+///                 "tails_writer_type": string - type of blob storage to store tails of created registries
+///                 "tails_writer_config": object - config of blob storage writer (see `indy_open_blob_storage_writer`)
+///             }
+///             Blob storage reader passed to credential issuance must be able to read tails written with this writer.
 ///     }
 /// cb: Callback that takes command result as parameter.
 ///
@@ -557,6 +568,10 @@ pub extern fn indy_issuer_create_credential_offer(command_handle: CommandHandle,
 ///     }
 ///   If you want to use empty value for some credential field, you should set "raw" to "" and "encoded" should not be empty
 /// rev_reg_id: id of revocation registry stored in the wallet
+///     If revocation registry rollover is enabled for the credential definition and this registry is full the credential
+///     is issued from the next registry already created by rollover, and "rev_reg_id" of the credential refers to it.
+///     This call never creates registries: if the next registry is not created yet it fails with `AnoncredsRevocationRegistryFullError`.
+///     Use `indy_issuer_create_credential_with_rollover` to issue with creation of the next registry.
 /// blob_storage_reader_handle: configuration of blob storage reader handle that will allow to read revocation tails (returned by `indy_open_blob_storage_reader`)
 /// cb: Callback that takes command result as parameter.
 ///
@@ -644,6 +659,264 @@ pub extern fn indy_issuer_create_credential(command_handle: CommandHandle,
     res
 }
 
+/// Issue Credential for the given Cred Request the same way as `indy_issuer_create_credential`
+/// and create the next revocation registry by rollover if needed.
+///
+/// Requires revocation registry rollover enabled for the credential definition (see `indy_issuer_create_and_store_credential_def`).
+/// When the registry is full the credential is issued from the next one of the same credential definition,
+/// and "rev_reg_id" of the credential refers to it.
+/// The registry the credential is issued from always has the next registry created in advance,
+/// so REVOC_REG_DEF and REVOC_REG_ENTRY transactions for it can be published before it is needed.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// cred_offer_json: a cred offer created by indy_issuer_create_credential_offer
+/// cred_req_json: a credential request created by indy_prover_create_credential_req
+/// cred_values_json: a credential containing attribute values for each of requested attribute names (see `indy_issuer_create_credential`)
+/// rev_reg_id: id of revocation registry stored in the wallet
+/// blob_storage_reader_handle: configuration of blob storage reader handle that will allow to read revocation tails (returned by `indy_open_blob_storage_reader`)
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// cred_json: Credential json containing signed credential values (see `indy_issuer_create_credential`)
+/// cred_revoc_id: local id for revocation info (Can be used for revocation of this credential)
+/// revoc_reg_delta_json: Revocation registry delta json with a newly issued credential
+/// new_revoc_regs_json: (Optional) Revocation registries created by rollover which must be published to the ledger
+///     {
+///         "<rev_reg_id>": {
+///             "revoc_reg_def": <revocation registry definition>, - see `indy_issuer_create_and_store_revoc_reg`
+///             "revoc_reg_entry": <revocation registry entry>, - initial state of the registry
+///         },
+///         ...
+///     }
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_create_credential_with_rollover(command_handle: CommandHandle,
+                                                          wallet_handle: WalletHandle,
+                                                          cred_offer_json: *const c_char,
+                                                          cred_req_json: *const c_char,
+                                                          cred_values_json: *const c_char,
+                                                          rev_reg_id: *const c_char,
+                                                          blob_storage_reader_handle: IndyHandle,
+                                                          cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                               cred_json: *const c_char,
+                                                                               cred_revoc_id: *const c_char,
+                                                                               revoc_reg_delta_json: *const c_char,
+                                                                               new_revoc_regs_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_create_credential_with_rollover: >>> wallet_handle: {:?}, cred_offer_json: {:?}, cred_req_json: {:?}, cred_values_json: {:?}, rev_reg_id: {:?}, \
+    blob_storage_reader_handle: {:?}", wallet_handle, cred_offer_json, cred_req_json, cred_values_json, rev_reg_id, blob_storage_reader_handle);
+
+    check_useful_validatable_json!(cred_offer_json, ErrorCode::CommonInvalidParam3, CredentialOffer);
+    check_useful_validatable_json!(cred_req_json, ErrorCode::CommonInvalidParam4, CredentialRequest);
+    check_useful_validatable_json!(cred_values_json, ErrorCode::CommonInvalidParam5, CredentialValues);
+    check_useful_validatable_string!(rev_reg_id, ErrorCode::CommonInvalidParam6, RevocationRegistryId);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam8);
+
+    let blob_storage_reader_handle = if blob_storage_reader_handle != -1 { Some(blob_storage_reader_handle) } else { None };
+
+    trace!("indy_issuer_create_credential_with_rollover: entities >>> wallet_handle: {:?}, cred_offer_json: {:?}, cred_req_json: {:?}, cred_values_json: {:?}, rev_reg_id: {:?}, \
+    blob_storage_reader_handle: {:?}", wallet_handle, cred_offer_json, secret!(&cred_req_json), secret!(&cred_values_json), rev_reg_id, blob_storage_reader_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::CreateCredentialWithRollover(
+                    wallet_handle,
+                    cred_offer_json,
+                    cred_req_json,
+                    cred_values_json,
+                    rev_reg_id,
+                    blob_storage_reader_handle,
+                    Box::new(move |result| {
+                        let (err, cred_json, revoc_id, revoc_reg_delta_json, new_revoc_regs_json) = prepare_result_4!(result, String::new(), None, None, None);
+                        trace!("indy_issuer_create_credential_with_rollover: cred_json: {:?}, revoc_id: {:?}, revoc_reg_delta_json: {:?}, new_revoc_regs_json: {:?}",
+                               secret!(cred_json.as_str()), secret!(&revoc_id), revoc_reg_delta_json, new_revoc_regs_json);
+                        let cred_json = ctypes::string_to_cstring(cred_json);
+                        let revoc_id = revoc_id.map(ctypes::string_to_cstring);
+                        let revoc_reg_delta_json = revoc_reg_delta_json.map(ctypes::string_to_cstring);
+                        let new_revoc_regs_json = new_revoc_regs_json.map(ctypes::string_to_cstring);
+                        cb(command_handle, err, cred_json.as_ptr(),
+                           revoc_id.as_ref().map(|id| id.as_ptr()).unwrap_or(ptr::null()),
+                           revoc_reg_delta_json.as_ref().map(|delta| delta.as_ptr()).unwrap_or(ptr::null()),
+                           new_revoc_regs_json.as_ref().map(|regs| regs.as_ptr()).unwrap_or(ptr::null()))
+                    })
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_create_credential_with_rollover: <<< res: {:?}", res);
+
+    res
+}
+
+/// Issue a batch of Credentials for the same credential definition.
+///
+/// Works like `indy_issuer_create_credential` called for each element of the batch, but the credential definition
+/// private key and used revocation registries are loaded from the wallet only once and revocation registries
+/// are stored back only once after all credentials of the batch are issued.
+/// If issuance of any credential fails nothing is stored into the wallet.
+///
+/// All issued revocable credentials contain the revocation registry accumulator value after the whole batch is issued.
+/// For each used revocation registry a single delta covering all issued credentials is returned.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// cred_requests_json: list of credentials to issue
+///     [
+///         {
+///             "cred_offer": <cred offer>, - a cred offer created by indy_issuer_create_credential_offer
+///             "cred_req": <cred request>, - a credential request created by indy_prover_create_credential_req
+///             "cred_values": <cred values>, - attribute values (see `indy_issuer_create_credential`)
+///             "rev_reg_id": Optional<string>, - id of revocation registry stored in the wallet
+///             "metadata": Optional<object>, - metadata of the issued credential record (see `indy_issuer_set_issued_credential_metadata`)
+///         },
+///         ...
+///     ]
+///     All cred offers must refer to the same credential definition.
+///     If revocation registry rollover is enabled for the credential definition (see `indy_issuer_create_and_store_credential_def`)
+///     credentials which do not fit into a full registry are issued from the next one of the same credential definition.
+///     In this case "rev_reg_id" of the credential refers to the next registry.
+///     The registry credentials are issued from always has the next registry created in advance,
+///     so REVOC_REG_DEF and REVOC_REG_ENTRY transactions for it can be published before it is needed.
+/// blob_storage_reader_handle: configuration of blob storage reader handle that will allow to read revocation tails (returned by `indy_open_blob_storage_reader`)
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// creds_json: issued credentials in the order of cred_requests_json
+///     [
+///         {
+///             "cred": <credential>, - credential json (see `indy_issuer_create_credential`)
+///             "cred_revoc_id": Optional<string>, - local id for revocation info
+///             "issued_cred_id": string, - id of issued credential record (see `indy_issuer_set_issued_credential_metadata`)
+///         },
+///         ...
+///     ]
+/// revoc_reg_deltas_json: (Optional) Revocation registry deltas with all newly issued credentials
+///     {
+///         "<rev_reg_id>": <revocation registry delta>,
+///         ...
+///     }
+/// new_revoc_regs_json: (Optional) Revocation registries created by rollover which must be published to the ledger
+///     {
+///         "<rev_reg_id>": {
+///             "revoc_reg_def": <revocation registry definition>, - see `indy_issuer_create_and_store_revoc_reg`
+///             "revoc_reg_entry": <revocation registry entry>, - initial state of the registry
+///         },
+///         ...
+///     }
+///     Registry deltas of credentials issued from new registries are returned in revoc_reg_deltas_json.
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_create_credentials_batch(command_handle: CommandHandle,
+                                                   wallet_handle: WalletHandle,
+                                                   cred_requests_json: *const c_char,
+                                                   blob_storage_reader_handle: IndyHandle,
+                                                   cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                        creds_json: *const c_char,
+                                                                        revoc_reg_deltas_json: *const c_char,
+                                                                        new_revoc_regs_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_create_credentials_batch: >>> wallet_handle: {:?}, cred_requests_json: {:?}, blob_storage_reader_handle: {:?}",
+           wallet_handle, cred_requests_json, blob_storage_reader_handle);
+
+    check_useful_validatable_json!(cred_requests_json, ErrorCode::CommonInvalidParam3, CredentialIssuanceRequests);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    let blob_storage_reader_handle = if blob_storage_reader_handle != -1 { Some(blob_storage_reader_handle) } else { None };
+
+    trace!("indy_issuer_create_credentials_batch: entities >>> wallet_handle: {:?}, cred_requests_json: {:?}, blob_storage_reader_handle: {:?}",
+           wallet_handle, secret!(&cred_requests_json), blob_storage_reader_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::CreateCredentialsBatch(
+                    wallet_handle,
+                    cred_requests_json,
+                    blob_storage_reader_handle,
+                    Box::new(move |result| {
+                        let (err, creds_json, revoc_reg_deltas_json, new_revoc_regs_json) = prepare_result_3!(result, String::new(), None, None);
+                        trace!("indy_issuer_create_credentials_batch: creds_json: {:?}, revoc_reg_deltas_json: {:?}, new_revoc_regs_json: {:?}",
+                               secret!(creds_json.as_str()), revoc_reg_deltas_json, new_revoc_regs_json);
+                        let creds_json = ctypes::string_to_cstring(creds_json);
+                        let revoc_reg_deltas_json = revoc_reg_deltas_json.map(ctypes::string_to_cstring);
+                        let new_revoc_regs_json = new_revoc_regs_json.map(ctypes::string_to_cstring);
+                        cb(command_handle, err, creds_json.as_ptr(),
+                           revoc_reg_deltas_json.as_ref().map(|deltas| deltas.as_ptr()).unwrap_or(ptr::null()),
+                           new_revoc_regs_json.as_ref().map(|regs| regs.as_ptr()).unwrap_or(ptr::null()))
+                    })
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_create_credentials_batch: <<< res: {:?}", res);
+
+    res
+}
+
+/// Get the next revocation registry created by rollover for the given one.
+///
+/// If revocation registry rollover is enabled for the credential definition (see `indy_issuer_create_and_store_credential_def`)
+/// the next registry is created when issuance from the given registry starts.
+/// Its REVOC_REG_DEF and REVOC_REG_ENTRY transactions must be published before it is used for issuance.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// rev_reg_id: id of revocation registry stored in the wallet
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// next_revoc_reg_json: the next revocation registry
+///     {
+///         "rev_reg_id": string, - id of the next revocation registry
+///         "revoc_reg_def": <revocation registry definition>, - see `indy_issuer_create_and_store_revoc_reg`
+///         "revoc_reg_entry": <revocation registry entry>, - initial state of the registry
+///     }
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+///     WalletItemNotFound - the registry has no next registry
+#[no_mangle]
+pub extern fn indy_issuer_get_next_revoc_reg(command_handle: CommandHandle,
+                                             wallet_handle: WalletHandle,
+                                             rev_reg_id: *const c_char,
+                                             cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                  next_revoc_reg_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_get_next_revoc_reg: >>> wallet_handle: {:?}, rev_reg_id: {:?}", wallet_handle, rev_reg_id);
+
+    check_useful_validatable_string!(rev_reg_id, ErrorCode::CommonInvalidParam3, RevocationRegistryId);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_issuer_get_next_revoc_reg: entities >>> wallet_handle: {:?}, rev_reg_id: {:?}", wallet_handle, rev_reg_id);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::GetNextRevocationRegistry(
+                    wallet_handle,
+                    rev_reg_id,
+                    boxed_callback_string!("indy_issuer_get_next_revoc_reg", cb, command_handle)
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_get_next_revoc_reg: <<< res: {:?}", res);
+
+    res
+}
+
 /// Revoke a credential identified by a cred_revoc_id (returned by indy_issuer_create_credential).
 ///
 /// The corresponding credential definition and revocation registry must be already
@@ -711,6 +984,83 @@ pub extern fn indy_issuer_revoke_credential(command_handle: CommandHandle,
     res
 }
 
+/// Revoke and/or recover a list of credentials identified by cred_revoc_ids (returned by indy_issuer_create_credential)
+/// in one update of the revocation registry.
+///
+/// The corresponding credential definition and revocation registry must be already
+/// created an stored into the wallet.
+///
+/// All indices are validated before the revocation registry is updated, so the call either applies
+/// all requested changes or none of them.
+///
+/// This call returns a single revoc registry delta as json file intended to be shared as REVOC_REG_ENTRY transaction.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// blob_storage_reader_cfg_handle: configuration of blob storage reader handle that will allow to read revocation tails (returned by `indy_open_blob_storage_reader`).
+/// rev_reg_id: id of revocation registry stored in wallet
+/// revoked_cred_revoc_ids_json: local ids of credentials to revoke
+///     [string, ...] - may be empty if only recovery is requested
+/// recovered_cred_revoc_ids_json: (Optional) local ids of previously revoked credentials to recover
+///     [string, ...]
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// revoc_reg_delta_json: Revocation registry delta json with all revoked and recovered credentials
+/// {
+///     value: {
+///         prevAccum: string - previous accumulator value.
+///         accum: string - current accumulator value.
+///         issued: array<number> an array of recovered indices.
+///         revoked: array<number> an array of revoked indices.
+///     },
+///     ver: string - version revocation registry delta json
+/// }
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_revoke_credentials(command_handle: CommandHandle,
+                                             wallet_handle: WalletHandle,
+                                             blob_storage_reader_cfg_handle: IndyHandle,
+                                             rev_reg_id: *const c_char,
+                                             revoked_cred_revoc_ids_json: *const c_char,
+                                             recovered_cred_revoc_ids_json: *const c_char,
+                                             cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                  revoc_reg_delta_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_revoke_credentials: >>> wallet_handle: {:?}, blob_storage_reader_cfg_handle: {:?}, rev_reg_id: {:?}, revoked_cred_revoc_ids_json: {:?}, recovered_cred_revoc_ids_json: {:?}",
+           wallet_handle, blob_storage_reader_cfg_handle, rev_reg_id, revoked_cred_revoc_ids_json, recovered_cred_revoc_ids_json);
+
+    check_useful_validatable_string!(rev_reg_id, ErrorCode::CommonInvalidParam4, RevocationRegistryId);
+    check_useful_json!(revoked_cred_revoc_ids_json, ErrorCode::CommonInvalidParam5, Vec<String>);
+    check_useful_opt_json!(recovered_cred_revoc_ids_json, ErrorCode::CommonInvalidParam6, Vec<String>);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+
+    trace!("indy_issuer_revoke_credentials: entities >>> wallet_handle: {:?}, blob_storage_reader_cfg_handle: {:?}, rev_reg_id: {:?}, revoked_cred_revoc_ids_json: {:?}, recovered_cred_revoc_ids_json: {:?}",
+           wallet_handle, blob_storage_reader_cfg_handle, rev_reg_id, secret!(&revoked_cred_revoc_ids_json), secret!(&recovered_cred_revoc_ids_json));
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::RevokeCredentials(
+                    wallet_handle,
+                    blob_storage_reader_cfg_handle,
+                    rev_reg_id,
+                    revoked_cred_revoc_ids_json,
+                    recovered_cred_revoc_ids_json,
+                    boxed_callback_string!("indy_issuer_revoke_credentials", cb, command_handle)
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_revoke_credentials: <<< res: {:?}", res);
+
+    res
+}
+
 /*/// Recover a credential identified by a cred_revoc_id (returned by indy_issuer_create_credential).
 ///
 /// The corresponding credential definition and revocation registry must be already
@@ -840,6 +1190,222 @@ pub extern fn indy_issuer_merge_revocation_registry_deltas(command_handle: Comma
     res
 }
 
+/// Set holder-facing metadata for a credential issued from this wallet.
+///
+/// For each credential created by indy_issuer_create_credential or indy_issuer_create_credentials_batch
+/// the issuer wallet keeps a record linking the credential offer nonce, prover DID and revocation id.
+/// The metadata allows to associate the record with the issuer's own data (connection id, for example)
+/// and to search records by it later (with indy_issuer_search_issued_credentials).
+///
+/// Metadata replaces the previously set one.
+/// Metadata can also be set on issuance with `indy_issuer_create_credentials_batch`, which returns ids of created records.
+/// The record of a credential issued by `indy_issuer_create_credential` can be found by its "cred_offer_nonce" tag.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// issued_cred_id: id of issued credential record (referent returned by indy_issuer_fetch_issued_credentials
+///     or "issued_cred_id" returned by indy_issuer_create_credentials_batch)
+/// metadata_json: metadata of issued credential as a flat json object
+///     {"name": "value", ...}
+///     The following names are reserved: "cred_def_id", "schema_id", "rev_reg_id", "cred_rev_id",
+///     "cred_offer_nonce", "prover_did", "revoked"
+/// cb: Callback that takes command result as parameter.
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_set_issued_credential_metadata(command_handle: CommandHandle,
+                                                         wallet_handle: WalletHandle,
+                                                         issued_cred_id: *const c_char,
+                                                         metadata_json: *const c_char,
+                                                         cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_issuer_set_issued_credential_metadata: >>> wallet_handle: {:?}, issued_cred_id: {:?}, metadata_json: {:?}",
+           wallet_handle, issued_cred_id, metadata_json);
+
+    check_useful_c_str!(issued_cred_id, ErrorCode::CommonInvalidParam3);
+    check_useful_validatable_json!(metadata_json, ErrorCode::CommonInvalidParam4, IssuedCredentialMetadata);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_issuer_set_issued_credential_metadata: entities >>> wallet_handle: {:?}, issued_cred_id: {:?}, metadata_json: {:?}",
+           wallet_handle, issued_cred_id, metadata_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::SetIssuedCredentialMetadata(
+                    wallet_handle,
+                    issued_cred_id,
+                    metadata_json,
+                    Box::new(move |result| {
+                        let err = prepare_result!(result);
+                        trace!("indy_issuer_set_issued_credential_metadata:");
+                        cb(command_handle, err)
+                    })
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_set_issued_credential_metadata: <<< res: {:?}", res);
+
+    res
+}
+
+/// Search for records of credentials issued from this wallet.
+/// Records can be filtered by tags:
+///     "cred_def_id", "schema_id", "cred_offer_nonce", "prover_did" - for all issued credentials
+///     "rev_reg_id", "cred_rev_id", "revoked" ("true" or "false") - for revocable credentials only
+///         "rev_reg_id" tag holds the unqualified form of the registry id
+///     metadata names - set by indy_issuer_set_issued_credential_metadata
+///
+/// Revocation status of records is updated by indy_issuer_revoke_credential and indy_issuer_revoke_credentials.
+///
+/// Instead of immediately returning of fetched records
+/// this call returns search_handle that can be used later
+/// to fetch records by small batches (with indy_issuer_fetch_issued_credentials).
+///
+/// #Params
+/// wallet_handle: wallet handle (created by open_wallet).
+/// query_json: Wql query filter for issued credentials searching based on tags.
+///     where query: indy-sdk/docs/design/011-wallet-query-language/README.md
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// search_handle: Search handle that can be used later to fetch records by small batches (with indy_issuer_fetch_issued_credentials)
+/// total_count: Total count of records
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_search_issued_credentials(command_handle: CommandHandle,
+                                                    wallet_handle: WalletHandle,
+                                                    query_json: *const c_char,
+                                                    cb: Option<extern fn(
+                                                        command_handle_: CommandHandle, err: ErrorCode,
+                                                        search_handle: SearchHandle,
+                                                        total_count: usize)>) -> ErrorCode {
+    trace!("indy_issuer_search_issued_credentials: >>> wallet_handle: {:?}, query_json: {:?}", wallet_handle, query_json);
+
+    check_useful_opt_c_str!(query_json, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_issuer_search_issued_credentials: entities >>> wallet_handle: {:?}, query_json: {:?}", wallet_handle, query_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::SearchIssuedCredentials(
+                    wallet_handle,
+                    query_json,
+                    Box::new(move |result| {
+                        let (err, handle, total_count) = prepare_result_2!(result, INVALID_SEARCH_HANDLE, 0);
+                        cb(command_handle, err, handle, total_count)
+                    })
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_search_issued_credentials: <<< res: {:?}", res);
+
+    res
+}
+
+/// Fetch next records of issued credentials for search.
+///
+/// #Params
+/// search_handle: Search handle (created by indy_issuer_search_issued_credentials)
+/// count: Count of records to fetch
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// issued_credentials_json: List of issued credential records:
+///     [{
+///         "referent": string, - id of issued credential record in the wallet
+///         "cred_def_id": string, - identifier of credential definition
+///         "schema_id": string, - identifier of schema
+///         "rev_reg_id": Optional<string>, - identifier of revocation registry definition
+///         "cred_rev_id": Optional<string>, - identifier of credential in the revocation registry definition
+///         "cred_offer_nonce": string, - nonce of credential offer
+///         "prover_did": string, - prover DID from credential request
+///         "revoked": bool, - whether credential is revoked
+///         "metadata": {"name": "value", ...} - metadata set by indy_issuer_set_issued_credential_metadata
+///     }]
+/// NOTE: The list of length less than the requested count means records search iterator is completed.
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_fetch_issued_credentials(command_handle: CommandHandle,
+                                                   search_handle: SearchHandle,
+                                                   count: usize,
+                                                   cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                        issued_credentials_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_fetch_issued_credentials: >>> search_handle: {:?}, count: {:?}", search_handle, count);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_issuer_fetch_issued_credentials: entities >>> search_handle: {:?}, count: {:?}", search_handle, count);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::FetchIssuedCredentials(
+                    search_handle,
+                    count,
+                    boxed_callback_string!("indy_issuer_fetch_issued_credentials", cb, command_handle)
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_fetch_issued_credentials: <<< res: {:?}", res);
+
+    res
+}
+
+/// Close issued credentials search (make search handle invalid)
+///
+/// #Params
+/// search_handle: Search handle (created by indy_issuer_search_issued_credentials)
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_close_issued_credentials_search(command_handle: CommandHandle,
+                                                          search_handle: SearchHandle,
+                                                          cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_issuer_close_issued_credentials_search: >>> search_handle: {:?}", search_handle);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_issuer_close_issued_credentials_search: entities >>> search_handle: {:?}", search_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::CloseIssuedCredentialsSearch(
+                    search_handle,
+                    Box::new(move |result| {
+                        let err = prepare_result!(result);
+                        trace!("indy_issuer_close_issued_credentials_search:");
+                        cb(command_handle, err)
+                    })
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_close_issued_credentials_search: <<< res: {:?}", res);
+
+    res
+}
+
 /// Creates a master secret with a given id and stores it in the wallet.
 /// The id must be unique.
 ///
@@ -2259,6 +2825,110 @@ pub extern fn indy_verifier_verify_proof(command_handle: CommandHandle,
     res
 }
 
+/// Verifies a proof (of multiple credential) and returns a report describing the result of
+/// every check for each requested attribute and predicate.
+///
+/// In contrast to `indy_verifier_verify_proof` the function doesn't stop at the first failed check
+/// and doesn't return an error if the proof doesn't correspond to the proof request.
+/// All found problems are listed in the report instead.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// proof_request_json: proof request json (see `indy_verifier_verify_proof`)
+/// proof_json: created for request proof json (see `indy_verifier_verify_proof`)
+/// schemas_json: all schemas participating in the proof (see `indy_verifier_verify_proof`)
+/// credential_defs_json: all credential definitions participating in the proof (see `indy_verifier_verify_proof`)
+/// rev_reg_defs_json: all revocation registry definitions participating in the proof (see `indy_verifier_verify_proof`)
+/// rev_regs_json: all revocation registries participating in the proof (see `indy_verifier_verify_proof`)
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// report_json: proof verification report
+///     {
+///         "valid": bool, // true - if all checks passed, false - otherwise
+///         "requested_attributes": {
+///             "<attr_referent>": {
+///                 "valid": bool,
+///                 "errors": [<failure>, ...]
+///             },
+///             ...
+///         },
+///         "requested_predicates": {
+///             "<predicate_referent>": {
+///                 "valid": bool,
+///                 "errors": [<failure>, ...]
+///             },
+///             ...
+///         },
+///         "errors": [<failure>, ...] // failures not related to a single referent
+///     }
+/// where
+/// failure: describes failed check
+///     {
+///         "check": string, // one of:
+///                          // "structure" - proof structure is inconsistent
+///                          // "missing_referent" - requested referent is not present in proof
+///                          // "unexpected_referent" - proof contains referent which was not requested
+///                          // "encoded_value" - revealed value doesn't match to the value in cryptographic proof
+///                          // "restrictions" - restrictions of proof request are not satisfied
+///                          // "non_revocation_timestamp" - non-revocation is requested but proof doesn't contain timestamp
+///                          // "ledger_data" - schema, credential definition or revocation registry is not provided
+///                          // "crypto_proof" - cryptographic proof verification failed
+///         "reason": string // human readable description of the failure
+///     }
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_verifier_verify_proof_with_report(command_handle: CommandHandle,
+                                                     proof_request_json: *const c_char,
+                                                     proof_json: *const c_char,
+                                                     schemas_json: *const c_char,
+                                                     credential_defs_json: *const c_char,
+                                                     rev_reg_defs_json: *const c_char,
+                                                     rev_regs_json: *const c_char,
+                                                     cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                          report_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_verifier_verify_proof_with_report: >>> proof_request_json: {:?}, proof_json: {:?}, schemas_json: {:?}, credential_defs_json: {:?}, \
+    rev_reg_defs_json: {:?}, rev_regs_json: {:?}", proof_request_json, proof_json, schemas_json, credential_defs_json, rev_reg_defs_json, rev_regs_json);
+
+    check_useful_validatable_json!(proof_request_json, ErrorCode::CommonInvalidParam2, ProofRequest);
+    check_useful_validatable_json!(proof_json, ErrorCode::CommonInvalidParam3, Proof);
+    check_useful_json!(schemas_json, ErrorCode::CommonInvalidParam4, Schemas);
+    check_useful_json!(credential_defs_json, ErrorCode::CommonInvalidParam5, CredentialDefinitions);
+    check_useful_json!(rev_reg_defs_json, ErrorCode::CommonInvalidParam6, RevocationRegistryDefinitions);
+    check_useful_json!(rev_regs_json, ErrorCode::CommonInvalidParam7, RevocationRegistries);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam8);
+
+    trace!("indy_verifier_verify_proof_with_report: entities >>> proof_request_json: {:?}, proof_json: {:?}, schemas_json: {:?}, credential_defs_json: {:?}, \
+    rev_reg_defs_json: {:?}, rev_regs_json: {:?}", proof_request_json, proof_json, schemas_json, credential_defs_json, rev_reg_defs_json, rev_regs_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::Verifier(VerifierCommand::VerifyProofWithReport(
+            proof_request_json,
+            proof_json,
+            schemas_json,
+            credential_defs_json,
+            rev_reg_defs_json,
+            rev_regs_json,
+            Box::new(move |result| {
+                let (err, report_json) = prepare_result_1!(result, String::new());
+                trace!("indy_verifier_verify_proof_with_report: report_json: {:?}", report_json);
+
+                let report_json = ctypes::string_to_cstring(report_json);
+                cb(command_handle, err, report_json.as_ptr())
+            })
+        ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_verifier_verify_proof_with_report: <<< res: {:?}", res);
+
+    res
+}
+
 /// Create revocation state for a credential that corresponds to a particular time.
 ///
 /// Note that revocation delta must cover the whole registry existence time.
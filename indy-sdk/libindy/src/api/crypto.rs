@@ -2,15 +2,95 @@
 use indy_api_types::{ErrorCode, CommandHandle, WalletHandle};
 use crate::commands::{Command, CommandExecutor};
 use crate::commands::crypto::CryptoCommand;
-use crate::domain::crypto::pack::JWE;
+use crate::domain::crypto::did::DidValue;
+use crate::domain::crypto::pack::{JWE, PackOptions};
+use crate::domain::crypto::session::SessionHandshake;
+use crate::domain::crypto::jws::JwsOptions;
 use crate::domain::crypto::key::KeyInfo;
+use crate::domain::crypto::multi_sig::{MultiSignatures, MultiSigPolicy};
 use indy_api_types::errors::prelude::*;
+use indy_api_types::key_manager::*;
+use indy_api_types::validation::Validatable;
 use indy_utils::ctypes;
 
 use serde_json;
 use libc::c_char;
+use std::ptr;
 
 
+/// Registers external key manager implementation.
+///
+/// It allows library user to keep private keys out of the wallet (for example in HSM or KMS).
+/// Keys created with "key_manager" field of indy_create_key key_json are stored in the wallet
+/// without private part and all operations with them (indy_crypto_sign, indy_sign_request,
+/// indy_crypto_auth_crypt, indy_crypto_anon_decrypt, indy_pack_message, indy_unpack_message and others)
+/// are delegated to the key manager.
+///
+/// Key manager interface doesn't provide X25519 key agreement, so key manager keys can't be used
+/// as the sender key of "ecdh" profile of indy_pack_message_with_options (ECDH-1PU) and
+/// can't unpack messages packed with "ecdh" profile. Such calls fail with UnknownCryptoTypeError.
+/// Use "legacy" profile for these keys. Sessions (indy_crypto_session_start) only need signatures,
+/// so they work with key manager keys.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// name: Key manager name.
+/// create_key: KeyManager create key operation handler
+/// sign: KeyManager sign operation handler
+/// encrypt: KeyManager authenticated encryption operation handler
+/// decrypt: KeyManager authenticated and anonymous decryption operation handler
+/// free_buffer: KeyManager handler that allows to de-allocate buffers allocated in key manager code
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_register_key_manager(command_handle: CommandHandle,
+                                        name: *const c_char,
+                                        create_key: Option<KeyManagerCreateKey>,
+                                        sign: Option<KeyManagerSign>,
+                                        encrypt: Option<KeyManagerEncrypt>,
+                                        decrypt: Option<KeyManagerDecrypt>,
+                                        free_buffer: Option<KeyManagerFreeBuffer>,
+                                        cb: Option<extern fn(command_handle_: CommandHandle,
+                                                             err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_register_key_manager: >>> command_handle: {:?}, name: {:?}", command_handle, name);
+
+    check_useful_c_str!(name, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(create_key, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(sign, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(encrypt, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(decrypt, ErrorCode::CommonInvalidParam6);
+    check_useful_c_callback!(free_buffer, ErrorCode::CommonInvalidParam7);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam8);
+
+    trace!("indy_register_key_manager: params name: {:?}", name);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::RegisterKeyManager(
+            name,
+            create_key,
+            sign,
+            encrypt,
+            decrypt,
+            free_buffer,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_register_key_manager: cb command_handle: {:?} err: {:?}", command_handle, err);
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_register_key_manager: <<< res: {:?}", res);
+
+    res
+}
+
 /// Creates keys pair and stores in the wallet.
 ///
 /// #Params
@@ -20,7 +100,10 @@ use libc::c_char;
 /// {
 ///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
 ///                                Can be UTF-8, base64 or hex string.
-///     "crypto_type": string, // Optional (if not set then ed25519 curve is used); Currently only 'ed25519' value is supported for this field.
+///     "crypto_type": string, // Optional (if not set then ed25519 curve is used); Supported values: 'ed25519', 'secp256k1', 'secp256r1'.
+///                            // secp256k1 and secp256r1 keys can be used only for signing.
+///     "key_manager": string, // Optional name of key manager registered with indy_register_key_manager that
+///                            // creates and holds the private key (if not set then the key is kept in the wallet).
 /// }
 /// cb: Callback that takes command result as parameter.
 ///
@@ -285,6 +368,198 @@ pub  extern fn indy_crypto_verify(command_handle: CommandHandle,
     res
 }
 
+/// Verify a set of signatures of a message against M-of-N threshold policy.
+///
+/// The message is valid if at least "threshold" of the policy "signers" have valid signatures.
+/// Signatures made by keys that aren't in the policy aren't counted.
+///
+/// Note to use DID keys with this function you can call indy_key_for_did to get key id (verkey)
+/// for specific DID.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// message_raw: a pointer to first byte of message that has been signed
+/// message_len: a message length
+/// signatures_json: base58 encoded signatures by verkeys of the signers:
+///   {
+///       "<verkey>": string, // signature
+///   }
+/// policy_json: threshold policy:
+///   {
+///       "threshold": int, // minimal number of valid signatures (M)
+///       "signers": [string], // verkeys of the allowed signers (N)
+///       "serialization": (optional) string, // how the message was signed:
+///           "raw" - signatures are made over the message bytes as is (default)
+///           "request" - message is a ledger request json. Signatures are made over its serialized form
+///                       as in indy_sign_request and indy_multi_sign_request
+///   }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// valid: true - if the policy is satisfied, false - otherwise
+///
+/// #Errors
+/// Common*
+/// Crypto*
+#[no_mangle]
+pub  extern fn indy_crypto_verify_multi(command_handle: CommandHandle,
+                                        message_raw: *const u8,
+                                        message_len: u32,
+                                        signatures_json: *const c_char,
+                                        policy_json: *const c_char,
+                                        cb: Option<extern fn(command_handle_: CommandHandle,
+                                                             err: ErrorCode,
+                                                             valid: bool)>) -> ErrorCode {
+    trace!("indy_crypto_verify_multi: >>> message_raw: {:?}, message_len: {:?}, signatures_json: {:?}, policy_json: {:?}",
+           message_raw, message_len, signatures_json, policy_json);
+
+    check_useful_c_byte_array!(message_raw, message_len, ErrorCode::CommonInvalidParam2, ErrorCode::CommonInvalidParam3);
+    check_useful_json!(signatures_json, ErrorCode::CommonInvalidParam4, MultiSignatures);
+    check_useful_validatable_json!(policy_json, ErrorCode::CommonInvalidParam5, MultiSigPolicy);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_crypto_verify_multi: entities >>> message_raw: {:?}, message_len: {:?}, signatures_json: {:?}, policy_json: {:?}",
+           message_raw, message_len, signatures_json, policy_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::VerifyMulti(
+            message_raw,
+            signatures_json,
+            policy_json,
+            Box::new(move |result| {
+                let (err, valid) = prepare_result_1!(result, false);
+                trace!("indy_crypto_verify_multi: valid: {:?}", valid);
+                cb(command_handle, err, valid)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_crypto_verify_multi: <<< res: {:?}", res);
+
+    res
+}
+
+/// Signs a payload with a key and returns JSON Web Signature (RFC 7515).
+///
+/// Signature algorithm is chosen by the key crypto type: EdDSA for ed25519, ES256K for secp256k1
+/// and ES256 for secp256r1. Protected header contains "alg" and "kid" of the signer.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handler (created by open_wallet).
+/// signer_vk: id (verkey) of signer. The key must be created by calling indy_create_key or indy_create_and_store_my_did
+/// payload_raw: a pointer to first byte of payload to be signed
+/// payload_len: a payload length
+/// options_json: (optional) signing options:
+///     {
+///         "serialization": (optional) "compact" (default) or "json" (General JWS JSON Serialization),
+///         "detached": (optional) if true payload is omitted from the result (RFC 7515 Appendix F). false by default,
+///         "kid": (optional) "verkey" (default) - signer verkey is used as kid,
+///                           "did:key" - did:key URL of signer key is used as kid,
+///         "protected": (optional) additional protected header parameters. "alg", "kid", "crit" and "b64" can't be set.
+///     }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// jws: JWS in requested serialization
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub  extern fn indy_crypto_sign_jws(command_handle: CommandHandle,
+                                    wallet_handle: WalletHandle,
+                                    signer_vk: *const c_char,
+                                    payload_raw: *const u8,
+                                    payload_len: u32,
+                                    options_json: *const c_char,
+                                    cb: Option<extern fn(command_handle_: CommandHandle,
+                                                         err: ErrorCode,
+                                                         jws: *const c_char)>) -> ErrorCode {
+    trace!("indy_crypto_sign_jws: >>> wallet_handle: {:?}, signer_vk: {:?}, payload_raw: {:?}, payload_len: {:?}, options_json: {:?}",
+           wallet_handle, signer_vk, payload_raw, payload_len, options_json);
+
+    check_useful_c_str!(signer_vk, ErrorCode::CommonInvalidParam3);
+    check_useful_c_byte_array!(payload_raw, payload_len, ErrorCode::CommonInvalidParam4, ErrorCode::CommonInvalidParam5);
+    check_useful_opt_json!(options_json, ErrorCode::CommonInvalidParam6, JwsOptions);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+
+    trace!("indy_crypto_sign_jws: entities >>> wallet_handle: {:?}, signer_vk: {:?}, payload_raw: {:?}, payload_len: {:?}, options_json: {:?}",
+           wallet_handle, signer_vk, payload_raw, payload_len, options_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::SignJws(
+            wallet_handle,
+            signer_vk,
+            payload_raw,
+            options_json.unwrap_or_default(),
+            boxed_callback_string!("indy_crypto_sign_jws", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_crypto_sign_jws: <<< res: {:?}", res);
+
+    res
+}
+
+/// Verifies JSON Web Signature (RFC 7515) created with EdDSA, ES256K or ES256 algorithm.
+///
+/// Compact, General JSON and Flattened JSON serializations are supported.
+/// Signer key is resolved from "kid" header parameter that must contain verkey or did:key.
+/// For JSON serialization with several signatures all of them must be valid.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// jws: JWS to be verified
+/// payload_raw: (optional) a pointer to first byte of detached payload. Must be passed only if JWS doesn't contain payload.
+/// payload_len: a detached payload length
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// valid: true - if signatures are valid, false - otherwise
+///
+/// #Errors
+/// Common*
+/// Crypto*
+#[no_mangle]
+pub  extern fn indy_crypto_verify_jws(command_handle: CommandHandle,
+                                      jws: *const c_char,
+                                      payload_raw: *const u8,
+                                      payload_len: u32,
+                                      cb: Option<extern fn(command_handle_: CommandHandle,
+                                                           err: ErrorCode,
+                                                           valid: bool)>) -> ErrorCode {
+    trace!("indy_crypto_verify_jws: >>> jws: {:?}, payload_raw: {:?}, payload_len: {:?}",
+           jws, payload_raw, payload_len);
+
+    check_useful_c_str!(jws, ErrorCode::CommonInvalidParam2);
+    check_useful_opt_c_byte_array!(payload_raw, payload_len, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_crypto_verify_jws: entities >>> jws: {:?}, payload_raw: {:?}, payload_len: {:?}",
+           jws, payload_raw, payload_len);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::VerifyJws(
+            jws,
+            payload_raw,
+            Box::new(move |result| {
+                let (err, valid) = prepare_result_1!(result, false);
+                trace!("indy_crypto_verify_jws: valid: {:?}", valid);
+                cb(command_handle, err, valid)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_crypto_verify_jws: <<< res: {:?}", res);
+
+    res
+}
+
 /// **** THIS FUNCTION WILL BE DEPRECATED USE indy_pack_message() INSTEAD ****
 /// Encrypt a message by authenticated-encryption scheme.
 ///
@@ -554,6 +829,196 @@ pub  extern fn indy_crypto_anon_decrypt(command_handle: CommandHandle,
     res
 }
 
+/// Starts an authenticated session with forward secrecy between my key and their DID (Experimental).
+///
+/// Session is established by the exchange of two handshake messages:
+///  1. initiator calls this function without their_handshake_json and sends the returned handshake to the responder
+///  2. responder calls this function with the handshake of the initiator and sends the returned handshake back
+///  3. initiator calls this function with the handshake of the responder to complete the session
+/// Handshakes carry X25519 ephemeral keys signed by the keys of the parties. Session keys are derived from
+/// the ephemeral keys only, and every message is encrypted with a new key ratcheted from the previous one,
+/// so the compromise of the wallet keys or of the current session state doesn't reveal the previous messages.
+/// Session state is stored in the wallet.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handler (created by open_wallet).
+/// my_vk: id (verkey) of my key. The key must be created by calling indy_create_key or indy_create_and_store_my_did
+/// their_did: DID of the other party. Its verkey must be known (stored by indy_store_their_did) unless it is did:key or did:peer
+/// their_handshake_json: (optional) handshake received from the other party:
+///   {
+///     "session_id": string,
+///     "sender_vk": string,
+///     "ephemeral_key": string,
+///     "signature": string,
+///   }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// session_id: id of the session to use with indy_crypto_session_encrypt
+/// handshake_json: handshake to send to the other party. Null when the session is completed by the initiator.
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub  extern fn indy_crypto_session_start(command_handle: CommandHandle,
+                                         wallet_handle: WalletHandle,
+                                         my_vk: *const c_char,
+                                         their_did: *const c_char,
+                                         their_handshake_json: *const c_char,
+                                         cb: Option<extern fn(command_handle_: CommandHandle,
+                                                              err: ErrorCode,
+                                                              session_id: *const c_char,
+                                                              handshake_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_crypto_session_start: >>> wallet_handle: {:?}, my_vk: {:?}, their_did: {:?}, their_handshake_json: {:?}",
+           wallet_handle, my_vk, their_did, their_handshake_json);
+
+    check_useful_c_str!(my_vk, ErrorCode::CommonInvalidParam3);
+    check_useful_validatable_string!(their_did, ErrorCode::CommonInvalidParam4, DidValue);
+    check_useful_opt_json!(their_handshake_json, ErrorCode::CommonInvalidParam5, SessionHandshake);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_crypto_session_start: entities >>> wallet_handle: {:?}, my_vk: {:?}, their_did: {:?}, their_handshake_json: {:?}",
+           wallet_handle, my_vk, their_did, their_handshake_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::SessionStart(
+            wallet_handle,
+            my_vk,
+            their_did,
+            their_handshake_json,
+            Box::new(move |result| {
+                let (err, session_id, handshake_json) = prepare_result_2!(result, String::new(), None);
+                trace!("indy_crypto_session_start: session_id: {:?}, handshake_json: {:?}", session_id, handshake_json);
+                let session_id = ctypes::string_to_cstring(session_id);
+                let handshake_json = handshake_json.map(ctypes::string_to_cstring);
+                cb(command_handle, err, session_id.as_ptr(),
+                   handshake_json.as_ref().map(|handshake| handshake.as_ptr()).unwrap_or(ptr::null()))
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_crypto_session_start: <<< res: {:?}", res);
+
+    res
+}
+
+/// Encrypts a message by the next key of the session (Experimental).
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handler (created by open_wallet).
+/// session_id: id of the session established by indy_crypto_session_start
+/// msg_data: a pointer to first byte of message that to be encrypted
+/// msg_len: a message length
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// encrypted message as a pointer to array of bytes (json of the session message).
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub  extern fn indy_crypto_session_encrypt(command_handle: CommandHandle,
+                                           wallet_handle: WalletHandle,
+                                           session_id: *const c_char,
+                                           msg_data: *const u8,
+                                           msg_len: u32,
+                                           cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                err: ErrorCode,
+                                                                encrypted_msg: *const u8,
+                                                                encrypted_len: u32)>) -> ErrorCode {
+    trace!("indy_crypto_session_encrypt: >>> wallet_handle: {:?}, session_id: {:?}, msg_data: {:?}, msg_len: {:?}",
+           wallet_handle, session_id, msg_data, msg_len);
+
+    check_useful_c_str!(session_id, ErrorCode::CommonInvalidParam3);
+    check_useful_c_byte_array!(msg_data, msg_len, ErrorCode::CommonInvalidParam4, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_crypto_session_encrypt: entities >>> wallet_handle: {:?}, session_id: {:?}, msg_data: {:?}, msg_len: {:?}",
+           wallet_handle, session_id, msg_data, msg_len);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::SessionEncrypt(
+            wallet_handle,
+            session_id,
+            msg_data,
+            Box::new(move |result| {
+                let (err, encrypted_msg) = prepare_result_1!(result, Vec::new());
+                trace!("indy_crypto_session_encrypt: encrypted_msg: {:?}", encrypted_msg);
+                let (encrypted_msg_raw, encrypted_msg_len) = ctypes::vec_to_pointer(&encrypted_msg);
+                cb(command_handle, err, encrypted_msg_raw, encrypted_msg_len)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_crypto_session_encrypt: <<< res: {:?}", res);
+
+    res
+}
+
+/// Decrypts a message encrypted by indy_crypto_session_encrypt of the other party (Experimental).
+///
+/// Messages can be decrypted out of order. Keys of up to 100 skipped messages are kept in the session.
+/// Every message can be decrypted only once.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handler (created by open_wallet).
+/// encrypted_msg: a pointer to first byte of message that to be decrypted
+/// encrypted_len: a message length
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// decrypted message as a pointer to an array of bytes
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub  extern fn indy_crypto_session_decrypt(command_handle: CommandHandle,
+                                           wallet_handle: WalletHandle,
+                                           encrypted_msg: *const u8,
+                                           encrypted_len: u32,
+                                           cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                err: ErrorCode,
+                                                                msg_data: *const u8,
+                                                                msg_len: u32)>) -> ErrorCode {
+    trace!("indy_crypto_session_decrypt: >>> wallet_handle: {:?}, encrypted_msg: {:?}, encrypted_len: {:?}",
+           wallet_handle, encrypted_msg, encrypted_len);
+
+    check_useful_c_byte_array!(encrypted_msg, encrypted_len, ErrorCode::CommonInvalidParam3, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_crypto_session_decrypt: entities >>> wallet_handle: {:?}, encrypted_msg: {:?}, encrypted_len: {:?}",
+           wallet_handle, encrypted_msg, encrypted_len);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::SessionDecrypt(
+            wallet_handle,
+            encrypted_msg,
+            Box::new(move |result| {
+                let (err, msg) = prepare_result_1!(result, Vec::new());
+                trace!("indy_crypto_session_decrypt: msg: {:?}", msg);
+                let (msg_data, msg_len) = ctypes::vec_to_pointer(&msg);
+                cb(command_handle, err, msg_data, msg_len)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_crypto_session_decrypt: <<< res: {:?}", res);
+
+    res
+}
+
 /// Packs a message by encrypting the message and serializes it in a JWE-like format (Experimental)
 ///
 /// Note to use DID keys with this function you can call indy_key_for_did to get key id (verkey)
@@ -658,6 +1123,7 @@ pub extern fn indy_pack_message(
         message,
         receiver_list,
         sender,
+        PackOptions::default(),
         wallet_handle,
         Box::new(move |result| {
             let (err, jwe) = prepare_result_1!(result, Vec::new());
@@ -675,6 +1141,124 @@ pub extern fn indy_pack_message(
 }
 
 
+/// Packs a message the same way as indy_pack_message but allows to choose envelope profile (Experimental)
+///
+/// Note to use DID keys with this function you can call indy_key_for_did to get key id (verkey)
+/// for specific DID.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// message: a pointer to the first byte of the message to be packed
+/// message_len: the length of the message
+/// receivers: a string in the format of a json list which will contain the list of receiver's keys
+///                the message is being encrypted for.
+///                Example:
+///                "[<receiver edge_agent_1 verkey>, <receiver edge_agent_2 verkey>]"
+/// sender: the sender's verkey as a string When null pointer is used in this parameter, anoncrypt is used
+/// options_json: (optional) packing options:
+///     {
+///         "profile": string, (optional) envelope profile:
+///             "legacy" (default) - Authcrypt/Anoncrypt envelope produced by indy_pack_message,
+///             "ecdh" - JWE with X25519 key agreement (DIDComm v2 style):
+///                 ECDH-1PU+A256KW is used if sender is set and ECDH-ES+A256KW otherwise.
+///                 Note that sender verkey is passed in plain "skid" header.
+///         "enc": string, (optional) content encryption for "ecdh" profile: "A256GCM" (default) or "XC20P"
+///     }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// a JWE described in indy_pack_message for "legacy" profile.
+/// a JWE using JSON serialization for "ecdh" profile:
+/// {
+///     "protected": b64URLencode({
+///         "typ": "application/didcomm-encrypted+json",
+///         "alg": "ECDH-1PU+A256KW" or "ECDH-ES+A256KW",
+///         "enc": "A256GCM" or "XC20P",
+///         "epk": {"kty": "OKP", "crv": "X25519", "x": b64URLencode(ephemeral_public_key)},
+///         "skid": sender_verkey, (ECDH-1PU only)
+///         "apu": b64URLencode(sender_verkey), (ECDH-1PU only)
+///         "apv": b64URLencode(sha256(sorted recipient verkeys joined with ".")),
+///     }),
+///     "recipients": [
+///         {
+///             "encrypted_key": b64URLencode(A256KW(kek, cek)),
+///             "header": {
+///                 "kid": base58encode(recipient_verkey)
+///             }
+///         },
+///     ],
+///     "iv": b64URLencode(iv),
+///     "ciphertext": b64URLencode(encrypt_detached(message, protected_value_encoded, iv, cek)),
+///     "tag": b64URLencode(tag)
+/// }
+/// X25519 keys are derived from ed25519 keys of sender and recipients.
+/// kek is derived with Concat KDF from ECDH(epk, recipient_key) for ECDH-ES and from
+/// ECDH(epk, recipient_key) || ECDH(sender_key, recipient_key) and tag for ECDH-1PU.
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Ledger*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_pack_message_with_options(
+    command_handle: CommandHandle,
+    wallet_handle: WalletHandle,
+    message: *const u8,
+    message_len: u32,
+    receiver_keys: *const c_char,
+    sender: *const c_char,
+    options_json: *const c_char,
+    cb: Option<extern fn(xcommand_handle: CommandHandle, err: ErrorCode, jwe_data: *const u8, jwe_len: u32)>,
+) -> ErrorCode {
+    trace!("indy_pack_message_with_options: >>> wallet_handle: {:?}, message: {:?}, message_len {:?},\
+            receiver_keys: {:?}, sender: {:?}, options_json: {:?}", wallet_handle, message, message_len, receiver_keys, sender, options_json);
+
+    check_useful_c_byte_array!(message, message_len, ErrorCode::CommonInvalidParam2, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(receiver_keys, ErrorCode::CommonInvalidParam4);
+    check_useful_opt_c_str!(sender, ErrorCode::CommonInvalidParam5);
+    check_useful_opt_json!(options_json, ErrorCode::CommonInvalidParam6, PackOptions);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+
+    trace!("indy_pack_message_with_options: entities >>> wallet_handle: {:?}, message: {:?}, message_len {:?},\
+            receiver_keys: {:?}, sender: {:?}, options_json: {:?}", wallet_handle, message, message_len, receiver_keys, sender, options_json);
+
+    //parse json array of keys
+    let receiver_list = match serde_json::from_str::<Vec<String>>(&receiver_keys) {
+        Ok(x) => x,
+        Err(_) => {
+            return IndyError::from_msg(IndyErrorKind::InvalidParam(4), "Invalid RecipientKeys has been passed").into();
+        },
+    };
+
+    //break early and error out if no receivers keys are provided
+    if receiver_list.is_empty() {
+        return IndyError::from_msg(IndyErrorKind::InvalidParam(4), "Empty RecipientKeys has been passed").into();
+    }
+
+    let result = CommandExecutor::instance().send(Command::Crypto(CryptoCommand::PackMessage(
+        message,
+        receiver_list,
+        sender,
+        options_json.unwrap_or_default(),
+        wallet_handle,
+        Box::new(move |result| {
+            let (err, jwe) = prepare_result_1!(result, Vec::new());
+            trace!("indy_pack_message_with_options: jwe: {:?}", jwe);
+            let (jwe_data, jwe_len) = ctypes::vec_to_pointer(&jwe);
+            cb(command_handle, err, jwe_data, jwe_len)
+        }),
+    )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_pack_message_with_options: <<< res: {:?}", res);
+
+    res
+}
+
+
 /// Unpacks a JWE-like formatted message outputted by indy_pack_message (Experimental)
 ///
 /// #Params
@@ -684,8 +1268,10 @@ pub extern fn indy_pack_message(
 /// jwe_len: the length of the JWE message in bytes
 /// cb: Callback that takes command result as parameter.
 ///
+/// Envelope profile (see indy_pack_message_with_options) is detected automatically.
+///
 /// #Returns
-/// if authcrypt was used to pack the message returns this json structure:
+/// if authcrypt (or ECDH-1PU) was used to pack the message returns this json structure:
 /// {
 ///     message: <decrypted message>,
 ///     sender_verkey: <sender_verkey>,
@@ -694,7 +1280,7 @@ pub extern fn indy_pack_message(
 ///
 /// OR
 ///
-/// if anoncrypt was used to pack the message returns this json structure:
+/// if anoncrypt (or ECDH-ES) was used to pack the message returns this json structure:
 /// {
 ///     message: <decrypted message>,
 ///     recipient_verkey: <recipient_verkey>
@@ -33,9 +33,14 @@ use crate::domain::ledger::attrib::Endpoint;
 ///     "seed": string, (optional) Seed that allows deterministic did creation (if not set random one will be created).
 ///                                Can be UTF-8, base64 or hex string.
 ///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
-///               currently only 'ed25519' value is supported for this field)
+///               supported values: 'ed25519', 'secp256k1', 'secp256r1';
+///               secp256k1 and secp256r1 keys can be used only for signing)
 ///     "cid": bool, (optional; if not set then false is used;)
 ///     "method_name": string, (optional) method name to create fully qualified did.
+///               "key" and "peer" methods create did:key and did:peer DIDs derived from the verkey.
+///     "key_manager": string, (optional) name of key manager registered with indy_register_key_manager
+///               that creates and holds the private key (if not set then the key is kept in the wallet).
+///     "peer_numalgo": int, (optional) did:peer numalgo (0 or 2) used if "method_name" is "peer". 0 by default.
 /// }
 /// cb: Callback that takes command result as parameter.
 ///
@@ -98,7 +103,8 @@ pub  extern fn indy_create_and_store_my_did(command_handle: CommandHandle,
 ///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
 ///                                Can be UTF-8, base64 or hex string.
 ///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
-///               currently only 'ed25519' value is supported for this field)
+///               supported values: 'ed25519', 'secp256k1', 'secp256r1';
+///               secp256k1 and secp256r1 keys can be used only for signing)
 /// }
 /// cb: Callback that takes command result as parameter.
 ///
@@ -194,6 +200,187 @@ pub  extern fn indy_replace_keys_apply(command_handle: CommandHandle,
     res
 }
 
+/// Restores the previous key of an existing DID (owned by the caller of the library).
+///
+/// Rolls back the last key change made by "indy_replace_keys_apply".
+/// The rollback itself can't be rolled back: a second rollback in a row fails with CommonInvalidState.
+/// The current key is archived to the DID key history, so signatures made by it can still be verified.
+/// History entries of the restored key are kept, so its old signatures are still matched by the signing time.
+/// Replacement started by "indy_replace_keys_start" and not applied yet is discarded.
+/// Note that only the wallet is changed. NYM transaction with the restored key must be sent to the ledger separately.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handler (created by open_wallet).
+/// did: DID stored in the wallet
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: Command handle to map callback to caller context.
+/// - err: Error code.
+/// - verkey: The restored verkey of the DID.
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub  extern fn indy_replace_keys_rollback(command_handle: CommandHandle,
+                                          wallet_handle: WalletHandle,
+                                          did: *const c_char,
+                                          cb: Option<extern fn(command_handle_: CommandHandle,
+                                                               err: ErrorCode,
+                                                               verkey: *const c_char)>) -> ErrorCode {
+    trace!("indy_replace_keys_rollback: >>> wallet_handle: {:?}, did: {:?}", wallet_handle, did);
+
+    check_useful_validatable_string!(did, ErrorCode::CommonInvalidParam3, DidValue);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_replace_keys_rollback: entities >>> wallet_handle: {:?}, did: {:?}", wallet_handle, did);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::ReplaceKeysRollback(
+            wallet_handle,
+            did,
+            boxed_callback_string!("indy_replace_keys_rollback", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_replace_keys_rollback: <<< res: {:?}", res);
+
+    res
+}
+
+/// Returns keys of an existing DID (owned by the caller of the library) replaced by
+/// "indy_replace_keys_apply" or "indy_replace_keys_rollback".
+///
+/// Current key of the DID can be got by "indy_key_for_local_did".
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handler (created by open_wallet).
+/// did: DID stored in the wallet
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: Command handle to map callback to caller context.
+/// - err: Error code.
+/// - key_history_json: list of replaced keys, oldest first:
+///   [{
+///       "verkey": string, // the replaced verkey
+///       "activated_at": int, // unix timestamp (in seconds) since which the key was active, 0 if unknown
+///       "replaced_at": int, // unix timestamp (in seconds) until which the key was active
+///   }]
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub  extern fn indy_get_did_key_history(command_handle: CommandHandle,
+                                        wallet_handle: WalletHandle,
+                                        did: *const c_char,
+                                        cb: Option<extern fn(command_handle_: CommandHandle,
+                                                             err: ErrorCode,
+                                                             key_history_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_get_did_key_history: >>> wallet_handle: {:?}, did: {:?}", wallet_handle, did);
+
+    check_useful_validatable_string!(did, ErrorCode::CommonInvalidParam3, DidValue);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_get_did_key_history: entities >>> wallet_handle: {:?}, did: {:?}", wallet_handle, did);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::GetDidKeyHistory(
+            wallet_handle,
+            did,
+            boxed_callback_string!("indy_get_did_key_history", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_get_did_key_history: <<< res: {:?}", res);
+
+    res
+}
+
+/// Verify a signature made by the current key or one of historical keys of an existing DID
+/// (owned by the caller of the library).
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handler (created by open_wallet).
+/// did: DID stored in the wallet
+/// message_raw: a pointer to first byte of message that has been signed
+/// message_len: a message length
+/// signature_raw: a pointer to first byte of signature to be verified
+/// signature_len: a signature length
+/// signed_at: unix timestamp (in seconds) of the signing. Only the key that was active at this time is used.
+///            Pass -1 to try all keys of the DID.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: Command handle to map callback to caller context.
+/// - err: Error code.
+/// - valid: true - if signature is valid, false - otherwise
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub  extern fn indy_verify_with_did_key_history(command_handle: CommandHandle,
+                                                wallet_handle: WalletHandle,
+                                                did: *const c_char,
+                                                message_raw: *const u8,
+                                                message_len: u32,
+                                                signature_raw: *const u8,
+                                                signature_len: u32,
+                                                signed_at: i64,
+                                                cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                     err: ErrorCode,
+                                                                     valid: bool)>) -> ErrorCode {
+    trace!("indy_verify_with_did_key_history: >>> wallet_handle: {:?}, did: {:?}, message_raw: {:?}, message_len: {:?}, signature_raw: {:?}, signature_len: {:?}, signed_at: {:?}",
+           wallet_handle, did, message_raw, message_len, signature_raw, signature_len, signed_at);
+
+    check_useful_validatable_string!(did, ErrorCode::CommonInvalidParam3, DidValue);
+    check_useful_c_byte_array!(message_raw, message_len, ErrorCode::CommonInvalidParam4, ErrorCode::CommonInvalidParam5);
+    check_useful_c_byte_array!(signature_raw, signature_len, ErrorCode::CommonInvalidParam6, ErrorCode::CommonInvalidParam7);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam9);
+
+    let signed_at = if signed_at != -1 { Some(signed_at as u64) } else { None };
+
+    trace!("indy_verify_with_did_key_history: entities >>> wallet_handle: {:?}, did: {:?}, message_raw: {:?}, message_len: {:?}, signature_raw: {:?}, signature_len: {:?}, signed_at: {:?}",
+           wallet_handle, did, message_raw, message_len, signature_raw, signature_len, signed_at);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::VerifyWithDidKeyHistory(
+            wallet_handle,
+            did,
+            message_raw,
+            signature_raw,
+            signed_at,
+            Box::new(move |result| {
+                let (err, valid) = prepare_result_1!(result, false);
+                trace!("indy_verify_with_did_key_history: valid: {:?}", valid);
+                cb(command_handle, err, valid)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_verify_with_did_key_history: <<< res: {:?}", res);
+
+    res
+}
+
 /// Saves their DID for a pairwise connection in a secured Wallet,
 /// so that it can be used to verify transaction.
 /// Updates DID associated verkey in case DID already exists in the Wallet.
@@ -207,6 +394,7 @@ pub  extern fn indy_replace_keys_apply(command_handle: CommandHandle,
 ///        "verkey": string
 ///             - optional is case of adding a new DID, and DID is cryptonym: did == verkey,
 ///             - mandatory in case of updating an existing DID
+///             - optional for did:key and did:peer, must match the key derived from DID if set
 ///     }
 /// cb: Callback that takes command result as parameter.
 ///
@@ -317,6 +505,9 @@ pub extern fn indy_key_for_did(command_handle: CommandHandle,
 /// "indy_key_for_local_did" call looks data stored in the local wallet only and skips freshness
 /// checking.
 ///
+/// Keys of did:key and did:peer (numalgo 0 and 2) DIDs are resolved from the DID itself
+/// if they aren't stored in the wallet.
+///
 /// Note if you want to get fresh data from the ledger you can use "indy_key_for_did" call
 /// instead.
 ///
@@ -485,6 +676,71 @@ pub extern fn indy_get_endpoint_for_did(command_handle: CommandHandle,
     res
 }
 
+/// Builds W3C DID Document (JSON-LD) for the given DID from the local wallet state only.
+///
+/// The document is assembled from:
+///  - the DID key: my DID, their DID or the key of did:key and did:peer DID (see "indy_key_for_local_did");
+///  - X25519 key agreement key derived from the ed25519 DID key;
+///  - endpoint stored by "indy_set_endpoint_for_did" or cached from the ledger ATTRIB by "indy_get_endpoint_for_did".
+///    The ledger isn't queried.
+///
+/// Unqualified DIDs are represented as "did:sov:<did>" in the document.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// wallet_handle: Wallet handle (created by open_wallet).
+/// did - The DID to build DID Document for.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: Command handle to map callback to caller context.
+/// - err: Error code.
+/// - did_doc_json - DID Document json:
+///   {
+///       "@context": ["https://www.w3.org/ns/did/v1", ...],
+///       "id": string,
+///       "verificationMethod": [{"id": "<id>#key-1", "type": "Ed25519VerificationKey2018", "controller": "<id>", "publicKeyBase58": string}],
+///       "authentication": ["<id>#key-1"],
+///       "assertionMethod": ["<id>#key-1"],
+///       "keyAgreement": [{"id": "<id>#key-agreement-1", "type": "X25519KeyAgreementKey2019", ...}], (optional)
+///       "service": [{"id": "<id>#did-communication", "type": "did-communication", "serviceEndpoint": string,
+///                    "priority": 0, "recipientKeys": [string], "routingKeys": [string]}] (optional)
+///   }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_build_did_doc(command_handle: CommandHandle,
+                                 wallet_handle: WalletHandle,
+                                 did: *const c_char,
+                                 cb: Option<extern fn(command_handle_: CommandHandle,
+                                                      err: ErrorCode,
+                                                      did_doc_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_build_did_doc: >>> wallet_handle: {:?}, did: {:?}", wallet_handle, did);
+
+    check_useful_validatable_string!(did, ErrorCode::CommonInvalidParam3, DidValue);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_build_did_doc: entities >>> wallet_handle: {:?}, did: {:?}", wallet_handle, did);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::BuildDidDoc(
+            wallet_handle,
+            did,
+            boxed_callback_string!("indy_build_did_doc", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_build_did_doc: <<< res: {:?}", res);
+
+    res
+}
+
 /// Saves/replaces the meta information for the giving DID in the wallet.
 ///
 /// #Params
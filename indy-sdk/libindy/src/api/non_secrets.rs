@@ -4,6 +4,7 @@ use crate::commands::{Command, CommandExecutor};
 use crate::commands::non_secrets::NonSecretsCommand;
 use indy_api_types::domain::wallet::Tags;
 use indy_api_types::errors::prelude::*;
+use indy_wallet::WalletRecordOperation;
 use indy_utils::ctypes;
 
 use serde_json;
@@ -310,6 +311,67 @@ pub extern fn indy_delete_wallet_record(command_handle: CommandHandle,
     res
 }
 
+/// Apply a list of record operations to the wallet.
+/// Operations are applied in the order they are provided.
+///
+/// For the default SQLite storage (and the built-in in-memory storage) the list is applied atomically:
+/// either all operations are applied or none of them (wallet is left unchanged if any operation fails).
+/// Storages registered with indy_register_wallet_storage get a best-effort rollback only: operations are applied
+/// one by one and the records changed before a failed operation are restored, as the plugged storage API has no transactions.
+/// If restoring fails too (or the process stops in the middle) the wallet can be left partially changed,
+/// unless the plugin makes such changes transactional on its own.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context
+/// wallet_handle: wallet handle (created by open_wallet)
+/// operations_json: the list of operations as json:
+///   [
+///     {"op": "add", "type": <record type>, "id": <record id>, "value": <record value>, "tags": (optional) <tags json>},
+///     {"op": "update", "type": <record type>, "id": <record id>, "value": <new record value>},
+///     {"op": "add_tags", "type": <record type>, "id": <record id>, "tags": <tags json>},
+///     {"op": "update_tags", "type": <record type>, "id": <record id>, "tags": <tags json>},
+///     {"op": "delete_tags", "type": <record type>, "id": <record id>, "tag_names": [<tag name>, ...]},
+///     {"op": "delete", "type": <record type>, "id": <record id>},
+///   ]
+///   Format of tags json is the same as for indy_add_wallet_record.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_wallet_batch_records(command_handle: CommandHandle,
+                                        wallet_handle: WalletHandle,
+                                        operations_json: *const c_char,
+                                        cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_wallet_batch_records: >>> wallet_handle: {:?}, operations_json: {:?}", wallet_handle, operations_json);
+
+    check_useful_json!(operations_json, ErrorCode::CommonInvalidParam3, Vec<WalletRecordOperation>);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_wallet_batch_records: entities >>> wallet_handle: {:?}, operations_json: {:?}", wallet_handle, operations_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::NonSecrets(
+            NonSecretsCommand::BatchRecords(
+                wallet_handle,
+                operations_json,
+                Box::new(move |result| {
+                    let err = prepare_result!(result);
+                    trace!("indy_wallet_batch_records:");
+                    cb(command_handle, err)
+                })
+            )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_wallet_batch_records: <<< res: {:?}", res);
+
+    res
+}
+
 /// Get an wallet record by id
 ///
 /// #Params
@@ -389,6 +451,13 @@ pub  extern fn indy_get_wallet_record(command_handle: CommandHandle,
 ///    retrieveType: (optional, false by default) Retrieve record type,
 ///    retrieveValue: (optional, true by default) Retrieve record value,
 ///    retrieveTags: (optional, false by default) Retrieve record tags,
+///    sortBy: (optional) Sort records. Supported by the default and inmemory storages, plugged ones return WalletQueryError:
+///      [{
+///        field: "insertion_order" (the order records were added in, record ids are stored encrypted and can't be sorted by)
+///               or unencrypted ("~" prefixed) tag name,
+///        direction: (optional, "asc" by default) "asc" or "desc"
+///      }]
+///      Records without the tag go first in ascending order.
 ///  }
 /// #Returns
 /// search_handle: Wallet search handle that can be used later
@@ -431,6 +500,60 @@ pub  extern fn indy_open_wallet_search(command_handle: CommandHandle,
     res
 }
 
+/// Count wallet records matching the query.
+///
+/// Optionally records can be grouped by the value of an unencrypted tag.
+/// Counting is done by the storage without fetching and decrypting records.
+///
+/// #Params
+/// wallet_handle: wallet handle (created by open_wallet)
+/// type_: allows to separate different record types collections
+/// query_json: MongoDB style query to wallet record tags (see indy_open_wallet_search)
+/// group_by_tag: (optional) name of unencrypted ("~" prefixed) tag to group records by.
+///   Records without the tag are not included in any group.
+///
+/// #Returns
+/// count json:
+/// {
+///   count: <int>, // count of all records matching the query
+///   groups: { // present only if group_by_tag is set
+///     "<tag value>": <int>, // count of matching records with the tag value
+///   },
+/// }
+#[no_mangle]
+pub  extern fn indy_count_wallet_records(command_handle: CommandHandle,
+                                         wallet_handle: WalletHandle,
+                                         type_: *const c_char,
+                                         query_json: *const c_char,
+                                         group_by_tag: *const c_char,
+                                         cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                              count_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_count_wallet_records: >>> wallet_handle: {:?}, type_: {:?}, query_json: {:?}, group_by_tag: {:?}", wallet_handle, type_, query_json, group_by_tag);
+
+    check_useful_c_str!(type_, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(query_json, ErrorCode::CommonInvalidParam4);
+    check_useful_opt_c_str!(group_by_tag, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_count_wallet_records: entities >>> wallet_handle: {:?}, type_: {:?}, query_json: {:?}, group_by_tag: {:?}", wallet_handle, type_, query_json, group_by_tag);
+
+    let result = CommandExecutor::instance()
+        .send(Command::NonSecrets(
+            NonSecretsCommand::CountRecords(
+                wallet_handle,
+                type_,
+                query_json,
+                group_by_tag,
+                boxed_callback_string!("indy_count_wallet_records", cb, command_handle)
+            )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_count_wallet_records: <<< res: {:?}", res);
+
+    res
+}
+
 /// Fetch next records for wallet search.
 ///
 /// Not if there are no records this call returns WalletNoRecords error.
@@ -511,3 +634,70 @@ pub  extern fn indy_close_wallet_search(command_handle: CommandHandle,
 
     res
 }
+
+/// Fetch a single page of wallet records.
+///
+/// Unlike indy_open_wallet_search this call doesn't keep any state between calls:
+/// it returns an opaque cursor that can be passed to the next call to continue the search.
+/// Records are returned in stable order, so pages don't overlap while the matching records are not changed.
+///
+/// #Params
+/// wallet_handle: wallet handle (created by open_wallet)
+/// type_: allows to separate different record types collections
+/// query_json: MongoDB style query to wallet record tags (see indy_open_wallet_search)
+/// options_json: search options (see indy_open_wallet_search)
+/// cursor: (optional) cursor returned by the previous call. The first page is returned if not set
+/// limit: maximum count of records in the page. Must be greater than 0
+///
+/// #Returns
+/// wallet records page json:
+/// {
+///   totalCount: <int>, // present only if retrieveTotalCount set to true
+///   records: [{ // present only if retrieveRecords set to true and the page isn't empty
+///       id: "Some id",
+///       type: "Some type", // present only if retrieveType set to true
+///       value: "Some value", // present only if retrieveValue set to true
+///       tags: <tags json>, // present only if retrieveTags set to true
+///   }],
+///   cursor: <str>, // cursor to fetch the next page, null if there are no more records
+/// }
+#[no_mangle]
+pub  extern fn indy_search_wallet_records_page(command_handle: CommandHandle,
+                                               wallet_handle: WalletHandle,
+                                               type_: *const c_char,
+                                               query_json: *const c_char,
+                                               options_json: *const c_char,
+                                               cursor: *const c_char,
+                                               limit: u32,
+                                               cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                    page_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_search_wallet_records_page: >>> wallet_handle: {:?}, type_: {:?}, query_json: {:?}, options_json: {:?}, cursor: {:?}, limit: {:?}",
+           wallet_handle, type_, query_json, options_json, cursor, limit);
+
+    check_useful_c_str!(type_, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(query_json, ErrorCode::CommonInvalidParam4);
+    check_useful_c_str!(options_json, ErrorCode::CommonInvalidParam5);
+    check_useful_opt_c_str!(cursor, ErrorCode::CommonInvalidParam6);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam8);
+
+    trace!("indy_search_wallet_records_page: entities >>> wallet_handle: {:?}, type_: {:?}, query_json: {:?}, options_json: {:?}, cursor: {:?}, limit: {:?}",
+           wallet_handle, type_, query_json, options_json, cursor, limit);
+
+    let result = CommandExecutor::instance()
+        .send(Command::NonSecrets(
+            NonSecretsCommand::SearchRecordsPage(
+                wallet_handle,
+                type_,
+                query_json,
+                options_json,
+                cursor,
+                limit as usize,
+                boxed_callback_string!("indy_search_wallet_records_page", cb, command_handle)
+            )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_search_wallet_records_page: <<< res: {:?}", res);
+
+    res
+}
@@ -1,6 +1,7 @@
-use indy_api_types::{ErrorCode, CommandHandle, WalletHandle};
+use indy_api_types::{ErrorCode, CommandHandle, WalletHandle, SearchHandle, INVALID_SEARCH_HANDLE};
 use crate::commands::{Command, CommandExecutor};
 use crate::commands::pairwise::PairwiseCommand;
+use indy_api_types::domain::wallet::Tags;
 use indy_api_types::errors::prelude::*;
 use indy_utils::ctypes;
 use indy_api_types::validation::Validatable;
@@ -236,3 +237,205 @@ pub  extern fn indy_set_pairwise_metadata(command_handle: CommandHandle,
 
     res
 }
+
+/// Replace tags of the pairwise record.
+/// Tags make pairwise searchable by indy_open_pairwise_search (e.g. by label, relationship state or last contact time).
+///
+/// #Params
+/// wallet_handle: wallet handler (created by open_wallet).
+/// command_handle: command handle to map callback to user context.
+/// their_did: encoded Did
+/// tags_json: the new tags of the pairwise as json:
+///   {
+///     "tagName1": <str>, // string tag (will be stored encrypted)
+///     "~tagName2": <str>, // string tag (will be stored un-encrypted)
+///   }
+///   Note that only un-encrypted tags support $gt, $gte, $lt and $lte queries.
+///   Values of such tags are compared as strings, so store timestamps zero padded.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub  extern fn indy_set_pairwise_tags(command_handle: CommandHandle,
+                                      wallet_handle: WalletHandle,
+                                      their_did: *const c_char,
+                                      tags_json: *const c_char,
+                                      cb: Option<extern fn(command_handle_: CommandHandle,
+                                                           err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_set_pairwise_tags: >>> wallet_handle: {:?}, their_did: {:?}, tags_json: {:?}", wallet_handle, their_did, tags_json);
+
+    check_useful_validatable_string!(their_did, ErrorCode::CommonInvalidParam3, DidValue);
+    check_useful_json!(tags_json, ErrorCode::CommonInvalidParam4, Tags);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_set_pairwise_tags: entities >>> wallet_handle: {:?}, their_did: {:?}, tags_json: {:?}", wallet_handle, their_did, tags_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Pairwise(PairwiseCommand::SetPairwiseTags(
+            wallet_handle,
+            their_did,
+            tags_json,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_set_pairwise_tags:");
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_set_pairwise_tags: <<< res: {:?}", res);
+
+    res
+}
+
+/// Search for pairwise stored in the wallet.
+///
+/// Instead of immediately returning of fetched pairwise
+/// this call returns search_handle that can be used later
+/// to fetch records by small batches (with indy_fetch_pairwise_search_next_records).
+///
+/// #Params
+/// wallet_handle: wallet handler (created by open_wallet).
+/// command_handle: command handle to map callback to user context.
+/// query_json: (optional) Wql query filter for pairwise tags (see indy_set_pairwise_tags):
+///  {
+///    "label": "Alice",
+///    "~last_contact": { "$gte": "0001577836800" },
+///  }
+///  Returns all pairwise if not set.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// search_handle: Search handle that can be used later to fetch records by small batches (with indy_fetch_pairwise_search_next_records)
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub  extern fn indy_open_pairwise_search(command_handle: CommandHandle,
+                                         wallet_handle: WalletHandle,
+                                         query_json: *const c_char,
+                                         cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                              search_handle: SearchHandle)>) -> ErrorCode {
+    trace!("indy_open_pairwise_search: >>> wallet_handle: {:?}, query_json: {:?}", wallet_handle, query_json);
+
+    check_useful_opt_c_str!(query_json, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_open_pairwise_search: entities >>> wallet_handle: {:?}, query_json: {:?}", wallet_handle, query_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Pairwise(PairwiseCommand::OpenPairwiseSearch(
+            wallet_handle,
+            query_json,
+            Box::new(move |result| {
+                let (err, handle) = prepare_result_1!(result, INVALID_SEARCH_HANDLE);
+                trace!("indy_open_pairwise_search: handle: {:?}", handle);
+                cb(command_handle, err, handle)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_open_pairwise_search: <<< res: {:?}", res);
+
+    res
+}
+
+/// Fetch next pairwise for pairwise search.
+///
+/// #Params
+/// wallet_handle: wallet handler (created by open_wallet).
+/// command_handle: command handle to map callback to user context.
+/// search_handle: pairwise search handle (created by indy_open_pairwise_search)
+/// count: Count of pairwise to fetch
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// pairwise records json:
+/// {
+///   totalCount: <int>, // count of all pairwise matching the query
+///   records: [{ // absent if no more pairwise left
+///       my_did: "my did",
+///       their_did: "their did",
+///       metadata: "Some metadata", // present only if set
+///       tags: <tags json>,
+///   }],
+/// }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub  extern fn indy_fetch_pairwise_search_next_records(command_handle: CommandHandle,
+                                                       wallet_handle: WalletHandle,
+                                                       search_handle: SearchHandle,
+                                                       count: usize,
+                                                       cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                            records_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_fetch_pairwise_search_next_records: >>> wallet_handle: {:?}, search_handle: {:?}, count: {:?}", wallet_handle, search_handle, count);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_fetch_pairwise_search_next_records: entities >>> wallet_handle: {:?}, search_handle: {:?}, count: {:?}", wallet_handle, search_handle, count);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Pairwise(PairwiseCommand::FetchPairwiseSearchNextRecords(
+            wallet_handle,
+            search_handle,
+            count,
+            boxed_callback_string!("indy_fetch_pairwise_search_next_records", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_fetch_pairwise_search_next_records: <<< res: {:?}", res);
+
+    res
+}
+
+/// Close pairwise search (make search handle invalid)
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// search_handle: pairwise search handle (created by indy_open_pairwise_search)
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub  extern fn indy_close_pairwise_search(command_handle: CommandHandle,
+                                          search_handle: SearchHandle,
+                                          cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_close_pairwise_search: >>> search_handle: {:?}", search_handle);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_close_pairwise_search: entities >>> search_handle: {:?}", search_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Pairwise(PairwiseCommand::ClosePairwiseSearch(
+            search_handle,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_close_pairwise_search:");
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_close_pairwise_search: <<< res: {:?}", res);
+
+    res
+}
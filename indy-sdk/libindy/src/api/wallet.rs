@@ -10,6 +10,7 @@ use indy_api_types::validation::Validatable;
 
 use serde_json;
 use libc::c_char;
+use std::convert::TryFrom;
 
 
 /// Register custom wallet storage implementation.
@@ -152,6 +153,7 @@ pub extern fn indy_register_wallet_storage(command_handle: CommandHandle,
 ///         Configured storage uses this identifier to lookup exact wallet data placement.
 ///   "storage_type": optional<string>, Type of the wallet storage. Defaults to 'default'.
 ///                  'Default' storage type allows to store wallet data in the local file.
+///                  'inmemory' storage type keeps wallet data in the process memory until the wallet is deleted.
 ///                  Custom storage types can be registered with indy_register_wallet_storage call.
 ///   "storage_config": optional<object>, Storage configuration json. Storage type defines set of supported keys.
 ///                     Can be optional if storage supports default configuration.
@@ -225,6 +227,7 @@ pub extern fn indy_create_wallet(command_handle: CommandHandle,
 ///             Configured storage uses this identifier to lookup exact wallet data placement.
 ///       "storage_type": optional<string>, Type of the wallet storage. Defaults to 'default'.
 ///                       'Default' storage type allows to store wallet data in the local file.
+///                       'inmemory' storage type keeps wallet data in the process memory until the wallet is deleted.
 ///                       Custom storage types can be registered with indy_register_wallet_storage call.
 ///       "storage_config": optional<object>, Storage configuration json. Storage type defines set of supported keys.
 ///                         Can be optional if storage supports default configuration.
@@ -311,6 +314,15 @@ pub extern fn indy_open_wallet(command_handle: CommandHandle,
 ///                              ARGON2I_INT - derive secured export key (less secured but faster)
 ///                              RAW - raw export key provided (skip derivation).
 ///                                RAW keys can be generated with indy_generate_wallet_key call
+///     "record_types": optional<array> Export only records of listed types (all wallet records are exported by default):
+///       [
+///         {
+///           "type": <string>, record type (for example "Indy::Credential"),
+///           "query": optional<object>, WQL query to select records of the type (see indy_open_wallet_search).
+///                    All records of the type are exported if not set.
+///         },
+///         ...
+///       ]
 ///   }
 ///
 /// #Returns
@@ -336,6 +348,7 @@ pub extern fn indy_export_wallet(command_handle: CommandHandle,
         .send(Command::Wallet(WalletCommand::Export(
             wallet_handle,
             export_config,
+            None,
             Box::new(move |result| {
                 let err = prepare_result!(result);
                 trace!("indy_export_wallet: cb command_handle: {:?} err: {:?}", command_handle, err);
@@ -348,6 +361,167 @@ pub extern fn indy_export_wallet(command_handle: CommandHandle,
     res
 }
 
+/// Exports opened wallet reporting progress of the operation.
+/// Export is written to temporary file "<path>.tmp" that is moved to "path" only
+/// after successful completion, so failed or cancelled export doesn't leave partial file.
+/// Export fails if "<path>.tmp" already exists.
+/// Existing "path" is never replaced, except on file systems without hard links (FAT, exFAT, some network shares)
+/// where a file created at "path" by someone else while the export is finalized can be overwritten.
+///
+/// #Params:
+/// wallet_handle: wallet handle returned by indy_open_wallet
+/// export_config: JSON containing settings for input operation (see indy_export_wallet).
+/// progress_cb: (optional) callback called after every 100 exported records and on completion
+///   with the number of already exported records.
+///   Returning of any error code except Success cancels the export.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_export_wallet_with_progress(command_handle: CommandHandle,
+                                               wallet_handle: WalletHandle,
+                                               export_config: *const c_char,
+                                               progress_cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                             records_count: u32) -> ErrorCode>,
+                                               cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                    err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_export_wallet_with_progress: >>> wallet_handle: {:?}, export_config: {:?}", wallet_handle, export_config);
+
+    check_useful_json!(export_config, ErrorCode::CommonInvalidParam3, ExportConfig);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_export_wallet_with_progress: params wallet_handle: {:?}, export_config: {:?}", wallet_handle, secret!(&export_config));
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::Export(
+            wallet_handle,
+            export_config,
+            _progress_callback(command_handle, progress_cb),
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_export_wallet_with_progress: cb command_handle: {:?} err: {:?}", command_handle, err);
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_export_wallet_with_progress: <<< res: {:?}", res);
+    res
+}
+
+/// Generates new encryption keys for opened wallet and re-encrypts all wallet records with them.
+/// Records are re-encrypted in place in a single storage transaction,
+/// so the wallet stays usable and either all records or none of them are affected.
+/// Wallet master key isn't changed (use "rekey" field of indy_open_wallet credentials for it).
+///
+/// Note: wallet searches opened before this call become invalid and have to be reopened.
+/// Note: only 'default' storage type supports keys rotation.
+///
+/// #Params
+/// wallet_handle: wallet handle returned by indy_open_wallet
+/// credentials: Wallet credentials json used to open the wallet
+///   {
+///       "key": string, Key or passphrase used for wallet key derivation.
+///                      Look to key_derivation_method param for information about supported key derivation methods.
+///       "storage_credentials": optional<object> Credentials for wallet storage. Storage type defines set of supported keys.
+///                              Can be optional if storage supports default configuration.
+///                              For 'default' storage type should be empty.
+///       "key_derivation_method": optional<string> Algorithm to use for wallet key derivation:
+///                             ARGON2I_MOD - derive secured wallet master key (used by default)
+///                             ARGON2I_INT - derive secured wallet master key (less secured but faster)
+///                             RAW - raw wallet key master provided (skip derivation).
+///                                RAW keys can be generated with indy_generate_wallet_key call
+///   }
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_rotate_wallet_keys(command_handle: CommandHandle,
+                                      wallet_handle: WalletHandle,
+                                      credentials: *const c_char,
+                                      cb: Option<extern fn(command_handle_: CommandHandle,
+                                                           err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_rotate_wallet_keys: >>> wallet_handle: {:?}, credentials: {:?}", wallet_handle, credentials);
+
+    check_useful_json!(credentials, ErrorCode::CommonInvalidParam3, Credentials);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_rotate_wallet_keys: params wallet_handle: {:?}, credentials: {:?}", wallet_handle, secret!(&credentials));
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::RotateKeys(
+            wallet_handle,
+            credentials,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_rotate_wallet_keys: cb command_handle: {:?} err: {:?}", command_handle, err);
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_rotate_wallet_keys: <<< res: {:?}", res);
+    res
+}
+
+/// Checks integrity of opened wallet: every record value and tags are decrypted with the current wallet keys
+/// and storage is checked for tags that don't belong to any record.
+/// In repair mode unreadable records and orphaned tags are removed from the wallet.
+///
+/// Note: only 'default' storage type supports orphaned tags detection.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context
+/// wallet_handle: wallet handle returned by indy_open_wallet
+/// repair: remove unreadable records and orphaned tags
+/// cb: Callback that takes command result as parameter
+///
+/// #Returns
+/// Verification report json
+///   {
+///       "recordsCount": int, total number of records in the wallet
+///       "unreadableRecords": int, number of records that can't be decrypted with the current wallet keys
+///       "orphanedTags": int, number of tags that don't belong to any record,
+///       null if the storage can't check for orphaned tags (plugged storage)
+///       "repaired": bool, true if unreadable records or orphaned tags were removed
+///   }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_verify_wallet(command_handle: CommandHandle,
+                                 wallet_handle: WalletHandle,
+                                 repair: bool,
+                                 cb: Option<extern fn(command_handle_: CommandHandle,
+                                                      err: ErrorCode,
+                                                      report_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_verify_wallet: >>> wallet_handle: {:?}, repair: {:?}", wallet_handle, repair);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_verify_wallet: params wallet_handle: {:?}, repair: {:?}", wallet_handle, repair);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::Verify(
+            wallet_handle,
+            repair,
+            boxed_callback_string!("indy_verify_wallet", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_verify_wallet: <<< res: {:?}", res);
+    res
+}
+
 
 /// Creates a new secure wallet and then imports its content
 /// according to fields provided in import_config
@@ -360,6 +534,7 @@ pub extern fn indy_export_wallet(command_handle: CommandHandle,
 ///         Configured storage uses this identifier to lookup exact wallet data placement.
 ///   "storage_type": optional<string>, Type of the wallet storage. Defaults to 'default'.
 ///                  'Default' storage type allows to store wallet data in the local file.
+///                  'inmemory' storage type keeps wallet data in the process memory until the wallet is deleted.
 ///                  Custom storage types can be registered with indy_register_wallet_storage call.
 ///   "storage_config": optional<object>, Storage configuration json. Storage type defines set of supported keys.
 ///                     Can be optional if storage supports default configuration.
@@ -387,8 +562,14 @@ pub extern fn indy_export_wallet(command_handle: CommandHandle,
 /// {
 ///   "path": <string>, path of the file that contains exported wallet content
 ///   "key": <string>, key used for export of the wallet
+///   "resume": optional<bool>, if true and the wallet already exists, continue interrupted import into it:
+///             records already present in the wallet are skipped. False by default.
 /// }
 ///
+/// If import is cancelled or fails after some records were imported the created wallet is kept
+/// with already imported records, so import can be resumed by calling this function again with "resume": true.
+/// If import fails before any record is imported (for example, on wrong export key) the created wallet is deleted.
+///
 /// #Returns
 /// Error code
 ///
@@ -418,6 +599,7 @@ pub extern fn indy_import_wallet(command_handle: CommandHandle,
             config,
             credentials,
             import_config,
+            None,
             Box::new(move |result| {
                 let err = prepare_result!(result);
                 trace!("indy_import_wallet: cb command_handle: {:?}, err: {:?}", command_handle, err);
@@ -430,6 +612,80 @@ pub extern fn indy_import_wallet(command_handle: CommandHandle,
     res
 }
 
+/// Creates a new secure wallet and then imports its content reporting progress of the operation.
+/// If "resume" field of import_config is true and the wallet already exists
+/// import continues into it skipping already imported records.
+///
+/// #Params
+/// config: Wallet configuration json (see indy_import_wallet).
+/// credentials: Wallet credentials json (see indy_import_wallet).
+/// import_config: Import settings json (see indy_import_wallet).
+/// progress_cb: (optional) callback called after every 100 imported records and on completion
+///   with the number of already imported records.
+///   Returning of any error code except Success cancels the import.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_import_wallet_with_progress(command_handle: CommandHandle,
+                                               config: *const c_char,
+                                               credentials: *const c_char,
+                                               import_config: *const c_char,
+                                               progress_cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                             records_count: u32) -> ErrorCode>,
+                                               cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                    err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_import_wallet_with_progress: >>> command_handle: {:?}, config: {:?}, credentials: {:?}, import_config: {:?}, cb: {:?}",
+           command_handle, config, credentials, import_config, cb);
+
+    check_useful_validatable_json!(config, ErrorCode::CommonInvalidParam2, Config);
+    check_useful_json!(credentials, ErrorCode::CommonInvalidParam3, Credentials);
+    check_useful_json!(import_config, ErrorCode::CommonInvalidParam4, ExportConfig);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_import_wallet_with_progress: params config: {:?}, credentials: {:?}, import_config: {:?}",
+           config, secret!(&credentials), secret!(&import_config));
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::Import(
+            config,
+            credentials,
+            import_config,
+            _progress_callback(command_handle, progress_cb),
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_import_wallet_with_progress: cb command_handle: {:?}, err: {:?}", command_handle, err);
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_import_wallet_with_progress: <<< res: {:?}", res);
+    res
+}
+
+fn _progress_callback(command_handle: CommandHandle,
+                      progress_cb: Option<extern fn(command_handle_: CommandHandle,
+                                                    records_count: u32) -> ErrorCode>) -> Option<Box<dyn Fn(usize) -> IndyResult<()> + Send>> {
+    progress_cb.map(|progress_cb| {
+        Box::new(move |records_count: usize| {
+            trace!("_progress_callback: command_handle: {:?}, records_count: {:?}", command_handle, records_count);
+
+            let records_count = u32::try_from(records_count)
+                .to_indy(IndyErrorKind::InvalidState, "Records count doesn't fit into u32")?;
+
+            match progress_cb(command_handle, records_count) {
+                ErrorCode::Success => Ok(()),
+                err => Err(err_msg(IndyErrorKind::from(err), "Operation cancelled by progress callback"))
+            }
+        }) as Box<dyn Fn(usize) -> IndyResult<()> + Send>
+    })
+}
+
 
 /// Closes opened wallet and frees allocated resources.
 ///
@@ -478,6 +734,7 @@ pub extern fn indy_close_wallet(command_handle: CommandHandle,
 ///         Configured storage uses this identifier to lookup exact wallet data placement.
 ///   "storage_type": optional<string>, Type of the wallet storage. Defaults to 'default'.
 ///                  'Default' storage type allows to store wallet data in the local file.
+///                  'inmemory' storage type keeps wallet data in the process memory until the wallet is deleted.
 ///                  Custom storage types can be registered with indy_register_wallet_storage call.
 ///   "storage_config": optional<object>, Storage configuration json. Storage type defines set of supported keys.
 ///                     Can be optional if storage supports default configuration.
@@ -539,6 +796,55 @@ pub extern fn indy_delete_wallet(command_handle: CommandHandle,
     res
 }
 
+/// Lists schema migrations that will be applied to the wallet storage on the next opening.
+/// Wallet storage isn't modified and the wallet isn't opened by this call.
+///
+/// Note: only 'default' storage type has versioned schema. Other storage types return empty list.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context
+/// config: Wallet configuration json (see indy_open_wallet for details).
+/// credentials: Wallet credentials json (see indy_open_wallet for details).
+/// cb: Callback that takes command result as parameter
+///
+/// #Returns
+/// Pending migrations json (empty if wallet schema is up to date)
+///   [{
+///       "version": int, schema version the migration brings storage to
+///       "description": string, migration description
+///   }]
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_check_wallet_migrations(command_handle: CommandHandle,
+                                           config: *const c_char,
+                                           credentials: *const c_char,
+                                           cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                err: ErrorCode,
+                                                                migrations_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_check_wallet_migrations: >>> command_handle: {:?}, config: {:?}, credentials: {:?}, cb: {:?}",
+           command_handle, config, credentials, cb);
+
+    check_useful_validatable_json!(config, ErrorCode::CommonInvalidParam2, Config);
+    check_useful_json!(credentials, ErrorCode::CommonInvalidParam3, Credentials);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_check_wallet_migrations: params config: {:?}, credentials: {:?}", config, secret!(&credentials));
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::CheckMigrations(
+            config,
+            credentials,
+            boxed_callback_string!("indy_check_wallet_migrations", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_check_wallet_migrations: <<< res: {:?}", res);
+    res
+}
+
 /// Generate wallet master key.
 /// Returned key is compatible with "RAW" key derivation method.
 /// It allows to avoid expensive key derivation for use cases when wallet keys can be stored in a secure enclave.
@@ -1,5 +1,5 @@
 use std::cell::RefCell;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::rc::Rc;
 
 use ursa::cl::{
@@ -11,7 +11,7 @@ use ursa::cl::{CredentialKeyCorrectnessProof, CredentialPrivateKey};
 
 use crate::commands::{Command, CommandExecutor, BoxedCallbackStringStringSend};
 use crate::commands::anoncreds::AnoncredsCommand;
-use crate::domain::anoncreds::credential::{CredentialValues, Credential};
+use crate::domain::anoncreds::credential::{CredentialValues, Credential, CredentialIssuanceRequest, CredentialIssuanceRequests, IssuedCredential};
 use crate::domain::anoncreds::credential_definition::{
     CredentialDefinition,
     CredentialDefinitionConfig,
@@ -19,12 +19,14 @@ use crate::domain::anoncreds::credential_definition::{
     CredentialDefinitionData,
     CredentialDefinitionPrivateKey,
     CredentialDefinitionV1,
+    RevocationRegistryRolloverConfig,
     SignatureType,
     TemporaryCredentialDefinition,
     CredentialDefinitionId
 };
 use crate::domain::anoncreds::credential_offer::CredentialOffer;
 use crate::domain::anoncreds::credential_request::CredentialRequest;
+use crate::domain::anoncreds::issued_credential_record::{IssuedCredentialInfo, IssuedCredentialMetadata, IssuedCredentialRecord};
 use crate::domain::anoncreds::revocation_registry::{
     RevocationRegistry,
     RevocationRegistryV1,
@@ -38,7 +40,9 @@ use crate::domain::anoncreds::revocation_registry_definition::{
     RevocationRegistryDefinitionV1,
     RevocationRegistryDefinitionValue,
     RevocationRegistryInfo,
-    RevocationRegistryId
+    RevocationRegistryId,
+    RevocationRegistryRollover,
+    next_rev_reg_tag,
 };
 use crate::domain::anoncreds::revocation_registry_delta::{
     RevocationRegistryDelta,
@@ -53,11 +57,11 @@ use crate::services::anoncreds::helpers::parse_cred_rev_id;
 use crate::services::blob_storage::BlobStorageService;
 use crate::services::crypto::CryptoService;
 use crate::services::pool::PoolService;
-use indy_wallet::{RecordOptions, WalletService};
+use indy_wallet::{RecordOptions, SearchOptions, WalletRecordOperation, WalletSearch, WalletService};
 
 use super::tails::{SDKTailsAccessor, store_tails_from_generator};
-use indy_api_types::{WalletHandle, CommandHandle};
-use indy_utils::next_command_handle;
+use indy_api_types::{WalletHandle, CommandHandle, SearchHandle};
+use indy_utils::{next_command_handle, next_search_handle};
 
 pub enum IssuerCommand {
     CreateSchema(
@@ -81,6 +85,7 @@ pub enum IssuerCommand {
         CredentialDefinitionId, // cred_def_id
         String, // tag
         SignatureType, // signature_type
+        CredentialDefinitionConfig, // config
         IndyResult<(CredentialDefinitionData,
                     CredentialPrivateKey,
                     CredentialKeyCorrectnessProof)>,
@@ -125,12 +130,36 @@ pub enum IssuerCommand {
         Option<RevocationRegistryId>, // revocation registry id
         Option<i32>, // blob storage reader config handle
         Box<dyn Fn(IndyResult<(String, Option<String>, Option<String>)>) + Send>),
+    CreateCredentialWithRollover(
+        WalletHandle,
+        CredentialOffer, // credential offer
+        CredentialRequest, // credential request
+        CredentialValues, // credential values
+        RevocationRegistryId, // revocation registry id
+        Option<i32>, // blob storage reader config handle
+        Box<dyn Fn(IndyResult<(String, Option<String>, Option<String>, Option<String>)>) + Send>),
+    CreateCredentialsBatch(
+        WalletHandle,
+        CredentialIssuanceRequests, // credential issuance requests
+        Option<i32>, // blob storage reader config handle
+        Box<dyn Fn(IndyResult<(String, Option<String>, Option<String>)>) + Send>),
+    GetNextRevocationRegistry(
+        WalletHandle,
+        RevocationRegistryId, // revocation registry id
+        Box<dyn Fn(IndyResult<String>) + Send>),
     RevokeCredential(
         WalletHandle,
         i32, // blob storage reader config handle
         RevocationRegistryId, //revocation registry id
         String, //credential revoc id
         Box<dyn Fn(IndyResult<String>) + Send>),
+    RevokeCredentials(
+        WalletHandle,
+        i32, // blob storage reader config handle
+        RevocationRegistryId, //revocation registry id
+        Vec<String>, //credential revoc ids to revoke
+        Option<Vec<String>>, //credential revoc ids to recover
+        Box<dyn Fn(IndyResult<String>) + Send>),
     /*    RecoverCredential(
             WalletHandle,
             i32, // blob storage reader config handle
@@ -141,6 +170,32 @@ pub enum IssuerCommand {
         RevocationRegistryDelta, //revocation registry delta
         RevocationRegistryDelta, //other revocation registry delta
         Box<dyn Fn(IndyResult<String>) + Send>),
+    SetIssuedCredentialMetadata(
+        WalletHandle,
+        String, // issued credential id
+        IssuedCredentialMetadata, // metadata
+        Box<dyn Fn(IndyResult<()>) + Send>),
+    SearchIssuedCredentials(
+        WalletHandle,
+        Option<String>, // query json
+        Box<dyn Fn(IndyResult<(SearchHandle, usize)>) + Send>),
+    FetchIssuedCredentials(
+        SearchHandle, // search handle
+        usize, // count
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    CloseIssuedCredentialsSearch(
+        SearchHandle, // search handle
+        Box<dyn Fn(IndyResult<()>) + Send>),
+}
+
+// Revocation registry state loaded once and updated in memory during batch issuance
+struct BatchRevocationRegistry {
+    rev_reg_def: RevocationRegistryDefinitionV1,
+    rev_reg: RevocationRegistryV1,
+    rev_key_priv: RevocationRegistryDefinitionPrivate,
+    rev_reg_info: RevocationRegistryInfo,
+    sdk_tails_accessor: SDKTailsAccessor,
+    rev_reg_delta: Option<CryptoRevocationRegistryDelta>,
 }
 
 pub struct IssuerCommandExecutor {
@@ -151,6 +206,7 @@ pub struct IssuerCommandExecutor {
     pub crypto_service: Rc<CryptoService>,
     pending_str_str_callbacks: RefCell<HashMap<CommandHandle, BoxedCallbackStringStringSend>>,
     pending_str_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<String>) + Send>>>,
+    issued_credentials_searches: RefCell<HashMap<SearchHandle, Box<WalletSearch>>>,
 }
 
 impl IssuerCommandExecutor {
@@ -167,6 +223,7 @@ impl IssuerCommandExecutor {
             crypto_service,
             pending_str_str_callbacks: RefCell::new(HashMap::new()),
             pending_str_callbacks: RefCell::new(HashMap::new()),
+            issued_credentials_searches: RefCell::new(HashMap::new()),
         }
     }
 
@@ -181,9 +238,9 @@ impl IssuerCommandExecutor {
                 self.create_and_store_credential_definition(wallet_handle, &issuer_did, &SchemaV1::from(schema), &tag,
                                                             type_.as_ref().map(String::as_str), config.as_ref(), cb);
             }
-            IssuerCommand::CreateAndStoreCredentialDefinitionContinue(wallet_handle, schema, schema_id, cred_def_id, tag, signature_type, result, cb_id) => {
+            IssuerCommand::CreateAndStoreCredentialDefinitionContinue(wallet_handle, schema, schema_id, cred_def_id, tag, signature_type, config, result, cb_id) => {
                 debug!(target: "wallet_command_executor", "CreateAndStoreCredentialDefinitionContinue command received");
-                self._create_and_store_credential_definition_continue(cb_id, wallet_handle, &schema, &schema_id, &cred_def_id, &tag, &signature_type, result)
+                self._create_and_store_credential_definition_continue(cb_id, wallet_handle, &schema, &schema_id, &cred_def_id, &tag, &signature_type, &config, result)
             }
             IssuerCommand::RotateCredentialDefinitionStart(wallet_handle, cred_def_id, cred_def_config, cb) => {
                 debug!(target: "wallet_command_executor", "RotateCredentialDefinitionStart command received");
@@ -214,12 +271,29 @@ impl IssuerCommandExecutor {
             }
             IssuerCommand::CreateCredential(wallet_handle, cred_offer, cred_req, cred_values, rev_reg_id, blob_storage_reader_handle, cb) => {
                 debug!(target: "issuer_command_executor", "CreateCredential command received");
-                cb(self.new_credential(wallet_handle, &cred_offer, &cred_req, &cred_values, rev_reg_id.as_ref(), blob_storage_reader_handle));
+                cb(self.new_credential(wallet_handle, cred_offer, cred_req, cred_values, rev_reg_id, blob_storage_reader_handle));
+            }
+            IssuerCommand::CreateCredentialWithRollover(wallet_handle, cred_offer, cred_req, cred_values, rev_reg_id, blob_storage_reader_handle, cb) => {
+                debug!(target: "issuer_command_executor", "CreateCredentialWithRollover command received");
+                cb(self.new_credential_with_rollover(wallet_handle, cred_offer, cred_req, cred_values, rev_reg_id, blob_storage_reader_handle));
+            }
+            IssuerCommand::CreateCredentialsBatch(wallet_handle, requests, blob_storage_reader_handle, cb) => {
+                debug!(target: "issuer_command_executor", "CreateCredentialsBatch command received");
+                cb(self.new_credentials_batch(wallet_handle, &requests, blob_storage_reader_handle));
+            }
+            IssuerCommand::GetNextRevocationRegistry(wallet_handle, rev_reg_id, cb) => {
+                debug!(target: "issuer_command_executor", "GetNextRevocationRegistry command received");
+                cb(self.get_next_revocation_registry(wallet_handle, &rev_reg_id));
             }
             IssuerCommand::RevokeCredential(wallet_handle, blob_storage_reader_handle, rev_reg_id, cred_revoc_id, cb) => {
                 debug!(target: "issuer_command_executor", "RevokeCredential command received");
                 cb(self.revoke_credential(wallet_handle, blob_storage_reader_handle, &rev_reg_id, &cred_revoc_id));
             }
+            IssuerCommand::RevokeCredentials(wallet_handle, blob_storage_reader_handle, rev_reg_id, revoked_cred_revoc_ids, recovered_cred_revoc_ids, cb) => {
+                debug!(target: "issuer_command_executor", "RevokeCredentials command received");
+                cb(self.revoke_credentials(wallet_handle, blob_storage_reader_handle, &rev_reg_id,
+                                           &revoked_cred_revoc_ids, recovered_cred_revoc_ids.as_ref().map(Vec::as_slice).unwrap_or(&[])));
+            }
             /*            IssuerCommand::RecoverCredential(wallet_handle, blob_storage_reader_handle, rev_reg_id, cred_revoc_id, cb) => {
                             debug!(target: "issuer_command_executor", "RecoverCredential command received");
                             cb(self.recovery_credential(wallet_handle, blob_storage_reader_handle, &rev_reg_id, &cred_revoc_id));
@@ -229,6 +303,22 @@ impl IssuerCommandExecutor {
                 cb(self.merge_revocation_registry_deltas(&mut RevocationRegistryDeltaV1::from(rev_reg_delta),
                                                          &RevocationRegistryDeltaV1::from(other_rev_reg_delta)));
             }
+            IssuerCommand::SetIssuedCredentialMetadata(wallet_handle, issued_cred_id, metadata, cb) => {
+                debug!(target: "issuer_command_executor", "SetIssuedCredentialMetadata command received");
+                cb(self.set_issued_credential_metadata(wallet_handle, &issued_cred_id, metadata));
+            }
+            IssuerCommand::SearchIssuedCredentials(wallet_handle, query_json, cb) => {
+                debug!(target: "issuer_command_executor", "SearchIssuedCredentials command received");
+                cb(self.search_issued_credentials(wallet_handle, query_json.as_ref().map(String::as_str)));
+            }
+            IssuerCommand::FetchIssuedCredentials(search_handle, count, cb) => {
+                debug!(target: "issuer_command_executor", "FetchIssuedCredentials command received");
+                cb(self.fetch_issued_credentials(search_handle, count));
+            }
+            IssuerCommand::CloseIssuedCredentialsSearch(search_handle, cb) => {
+                debug!(target: "issuer_command_executor", "CloseIssuedCredentialsSearch command received");
+                cb(self.close_issued_credentials_search(search_handle));
+            }
         };
     }
 
@@ -296,6 +386,7 @@ impl IssuerCommandExecutor {
                             cred_def_id.clone(),
                             tag.clone(),
                             signature_type.clone(),
+                            cred_def_config.clone(),
                             res,
                             cb_id,
                         ))
@@ -321,13 +412,14 @@ impl IssuerCommandExecutor {
                                                         cred_def_id: &CredentialDefinitionId,
                                                         tag: &str,
                                                         signature_type: &SignatureType,
+                                                        config: &CredentialDefinitionConfig,
                                                         result: IndyResult<(CredentialDefinitionData,
                                                                             CredentialPrivateKey,
                                                                             CredentialKeyCorrectnessProof)>) {
         let cb = self.pending_str_str_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
         cb(result
             .and_then(|result| {
-                self._complete_create_and_store_credential_definition(wallet_handle, schema, schema_id, cred_def_id, tag, signature_type.clone(), result)
+                self._complete_create_and_store_credential_definition(wallet_handle, schema, schema_id, cred_def_id, tag, signature_type.clone(), config, result)
             }))
     }
 
@@ -371,6 +463,7 @@ impl IssuerCommandExecutor {
                                                         cred_def_id: &CredentialDefinitionId,
                                                         tag: &str,
                                                         signature_type: SignatureType,
+                                                        config: &CredentialDefinitionConfig,
                                                         res: (crate::domain::anoncreds::credential_definition::CredentialDefinitionData,
                                                               ursa::cl::CredentialPrivateKey,
                                                               ursa::cl::CredentialKeyCorrectnessProof)) -> IndyResult<(String, String)> {
@@ -401,6 +494,11 @@ impl IssuerCommandExecutor {
         self.wallet_service.add_indy_object(wallet_handle, &cred_def_id.0, &cred_def_correctness_proof, &HashMap::new())?;
         let _ = self.wallet_service.add_indy_object(wallet_handle, &schema_id.0, &schema_, &HashMap::new()).ok();
 
+        // Issuer options used during issuance are kept along with the credential definition
+        if config.revocation_registry_rollover.is_some() {
+            self.wallet_service.add_indy_object(wallet_handle, &cred_def_id.0, config, &HashMap::new())?;
+        }
+
         let schema_id = schema.id.clone();
 
         self._wallet_set_schema_id(wallet_handle, &cred_def_id.0, &schema_id)?; // TODO: FIXME delete temporary storing of schema id
@@ -581,49 +679,24 @@ impl IssuerCommandExecutor {
 
         let cred_def: CredentialDefinition = self.wallet_service.get_indy_object(wallet_handle, &cred_def_id.0, &RecordOptions::id_value())?;
 
-        let (revoc_public_keys, revoc_key_private, revoc_registry, mut revoc_tails_generator) =
-            self.anoncreds_service.issuer.new_revocation_registry(&CredentialDefinitionV1::from(cred_def),
-                                                                  max_cred_num,
-                                                                  issuance_type.to_bool(),
-                                                                  &issuer_did)?;
-
-        let (tails_location, tails_hash) =
-            store_tails_from_generator(self.blob_storage_service.clone(), tails_writer_handle, &mut revoc_tails_generator)?;
-
-        let revoc_reg_def_value = RevocationRegistryDefinitionValue {
-            max_cred_num,
-            issuance_type,
-            public_keys: revoc_public_keys,
-            tails_location,
-            tails_hash,
-        };
-
-        let revoc_reg_def =
-            RevocationRegistryDefinition::RevocationRegistryDefinitionV1(
-                RevocationRegistryDefinitionV1 {
-                    id: rev_reg_id.clone(),
-                    revoc_def_type: rev_reg_type,
-                    tag: tag.to_string(),
-                    cred_def_id: cred_def_id.clone(),
-                    value: revoc_reg_def_value,
-                });
-
-        let revoc_reg =
-            RevocationRegistry::RevocationRegistryV1(
-                RevocationRegistryV1 {
-                    value: revoc_registry
-                }
-            );
+        let (revoc_reg_def, revoc_reg, revoc_reg_def_priv) =
+            self._new_revocation_registry(issuer_did,
+                                          rev_reg_type,
+                                          tag,
+                                          cred_def_id,
+                                          &CredentialDefinitionV1::from(cred_def),
+                                          issuance_type,
+                                          max_cred_num,
+                                          tails_writer_handle)?;
 
-        let revoc_reg_def_priv = RevocationRegistryDefinitionPrivate {
-            value: revoc_key_private
-        };
+        let revoc_reg_def = RevocationRegistryDefinition::RevocationRegistryDefinitionV1(revoc_reg_def);
+        let revoc_reg = RevocationRegistry::RevocationRegistryV1(revoc_reg);
 
-        let revoc_reg_def_json = self.wallet_service.add_indy_object(wallet_handle, &rev_reg_id.0, &revoc_reg_def, &HashMap::new())?;
+        let revoc_reg_def_json = serde_json::to_string(&revoc_reg_def)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistryDefinition")?;
 
-        let revoc_reg_json = self.wallet_service.add_indy_object(wallet_handle, &rev_reg_id.0, &revoc_reg, &HashMap::new())?;
-
-        self.wallet_service.add_indy_object(wallet_handle, &rev_reg_id.0, &revoc_reg_def_priv, &HashMap::new())?;
+        let revoc_reg_json = serde_json::to_string(&revoc_reg)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistry")?;
 
         let rev_reg_info = RevocationRegistryInfo {
             id: rev_reg_id.clone(),
@@ -631,7 +704,14 @@ impl IssuerCommandExecutor {
             used_ids: HashSet::new(),
         };
 
-        self.wallet_service.add_indy_object(wallet_handle, &rev_reg_id.0, &rev_reg_info, &HashMap::new())?;
+        let operations: Vec<WalletRecordOperation> = vec![
+            self.wallet_service.add_indy_object_operation(&rev_reg_id.0, &revoc_reg_def, &HashMap::new())?,
+            self.wallet_service.add_indy_object_operation(&rev_reg_id.0, &revoc_reg, &HashMap::new())?,
+            self.wallet_service.add_indy_object_operation(&rev_reg_id.0, &revoc_reg_def_priv, &HashMap::new())?,
+            self.wallet_service.add_indy_object_operation(&rev_reg_id.0, &rev_reg_info, &HashMap::new())?,
+        ];
+
+        self.wallet_service.batch_records(wallet_handle, &operations)?;
 
         debug!("create_and_store_revocation_registry <<< rev_reg_id: {:?}, revoc_reg_def_json: {:?}, revoc_reg_json: {:?}",
                rev_reg_id, revoc_reg_def_json, revoc_reg_json);
@@ -639,6 +719,50 @@ impl IssuerCommandExecutor {
         Ok((rev_reg_id.0, revoc_reg_def_json, revoc_reg_json))
     }
 
+    // Generates keys and tails of a new revocation registry, storing into the wallet is up to the caller
+    fn _new_revocation_registry(&self,
+                                issuer_did: &DidValue,
+                                rev_reg_type: RegistryType,
+                                tag: &str,
+                                cred_def_id: &CredentialDefinitionId,
+                                cred_def: &CredentialDefinitionV1,
+                                issuance_type: IssuanceType,
+                                max_cred_num: u32,
+                                tails_writer_handle: i32) -> IndyResult<(RevocationRegistryDefinitionV1, RevocationRegistryV1, RevocationRegistryDefinitionPrivate)> {
+        let (revoc_public_keys, revoc_key_private, revoc_registry, mut revoc_tails_generator) =
+            self.anoncreds_service.issuer.new_revocation_registry(cred_def,
+                                                                  max_cred_num,
+                                                                  issuance_type.to_bool(),
+                                                                  issuer_did)?;
+
+        let (tails_location, tails_hash) =
+            store_tails_from_generator(self.blob_storage_service.clone(), tails_writer_handle, &mut revoc_tails_generator)?;
+
+        let revoc_reg_def = RevocationRegistryDefinitionV1 {
+            id: RevocationRegistryId::new(issuer_did, cred_def_id, rev_reg_type.to_str(), tag),
+            revoc_def_type: rev_reg_type,
+            tag: tag.to_string(),
+            cred_def_id: cred_def_id.clone(),
+            value: RevocationRegistryDefinitionValue {
+                max_cred_num,
+                issuance_type,
+                public_keys: revoc_public_keys,
+                tails_location,
+                tails_hash,
+            },
+        };
+
+        let revoc_reg = RevocationRegistryV1 {
+            value: revoc_registry
+        };
+
+        let revoc_reg_def_priv = RevocationRegistryDefinitionPrivate {
+            value: revoc_key_private
+        };
+
+        Ok((revoc_reg_def, revoc_reg, revoc_reg_def_priv))
+    }
+
     fn create_credential_offer(&self,
                                wallet_handle: WalletHandle,
                                cred_def_id: &CredentialDefinitionId) -> IndyResult<String> {
@@ -669,10 +793,10 @@ impl IssuerCommandExecutor {
 
     fn new_credential(&self,
                       wallet_handle: WalletHandle,
-                      cred_offer: &CredentialOffer,
-                      cred_request: &CredentialRequest,
-                      cred_values: &CredentialValues,
-                      rev_reg_id: Option<&RevocationRegistryId>,
+                      cred_offer: CredentialOffer,
+                      cred_request: CredentialRequest,
+                      cred_values: CredentialValues,
+                      rev_reg_id: Option<RevocationRegistryId>,
                       blob_storage_reader_handle: Option<i32>) -> IndyResult<(String, Option<String>, Option<String>)> {
         debug!("new_credential >>> wallet_handle: {:?}, cred_offer: {:?}, cred_req: {:?}, cred_values_json: {:?}, rev_reg_id: {:?}, blob_storage_reader_handle: {:?}",
                wallet_handle, secret!(&cred_offer), secret!(&cred_request), secret!(&cred_values), rev_reg_id, blob_storage_reader_handle);
@@ -682,6 +806,19 @@ impl IssuerCommandExecutor {
             None => cred_offer.cred_def_id.clone()
         };
 
+        // With rollover enabled issuance may continue from the next registry if it is already created.
+        // New registries are created only by calls which return them, so here the result has none.
+        if rev_reg_id.is_some() && self._wallet_get_rev_reg_rollover_config(wallet_handle, &cred_def_id)?.is_some() {
+            let (cred_json, cred_rev_id, rev_reg_delta_json, _) =
+                self._new_credential_from_batch(wallet_handle, cred_offer, cred_request, cred_values, rev_reg_id, blob_storage_reader_handle, false)?;
+
+            debug!("new_credential <<< cred_json: {:?}, cred_rev_id: {:?}, rev_reg_delta_json: {:?}", secret!(&cred_json), secret!(&cred_rev_id), rev_reg_delta_json);
+
+            return Ok((cred_json, cred_rev_id, rev_reg_delta_json));
+        }
+
+        let rev_reg_id = rev_reg_id.as_ref();
+
         let cred_def: CredentialDefinitionV1 =
             CredentialDefinitionV1::from(
                 self.wallet_service.get_indy_object::<CredentialDefinition>(wallet_handle, &cred_def_id.0, &RecordOptions::id_value())?);
@@ -782,20 +919,496 @@ impl IssuerCommandExecutor {
             .map_or(Ok(None), |v| v.map(Some))
             .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistryDelta")?;
 
-        if let (Some(r_reg), Some(r_reg_id), Some(r_reg_info)) = (credential.rev_reg, rev_reg_id, rev_reg_info.clone()) {
+        // Registry, its info and the issued credential record are stored together to not lose or reuse an index
+        let mut operations: Vec<WalletRecordOperation> = Vec::new();
+
+        if let (Some(r_reg), Some(r_reg_id), Some(r_reg_info)) = (credential.rev_reg, rev_reg_id, rev_reg_info.as_ref()) {
             let revoc_reg = RevocationRegistry::RevocationRegistryV1(RevocationRegistryV1 { value: r_reg });
 
-            self.wallet_service.update_indy_object(wallet_handle, &r_reg_id.0, &revoc_reg)?;
-            self.wallet_service.update_indy_object(wallet_handle, &r_reg_id.0, &r_reg_info)?;
+            operations.push(self.wallet_service.update_indy_object_operation(&r_reg_id.0, &revoc_reg)?);
+            operations.push(self.wallet_service.update_indy_object_operation(&r_reg_id.0, r_reg_info)?);
         };
 
         let cred_rev_id = rev_reg_info.map(|r_reg_info| r_reg_info.curr_id.to_string());
 
+        operations.push(self._add_issued_credential_record_operation(&uuid::Uuid::new_v4().to_string(), &cred_def_id, &cred_offer, &cred_request,
+                                                                     rev_reg_id, cred_rev_id.clone(), Tags::new())?);
+
+        self.wallet_service.batch_records(wallet_handle, &operations)?;
+
         debug!("new_credential <<< cred_json: {:?}, cred_rev_id: {:?}, rev_reg_delta_json: {:?}", secret!(&cred_json), secret!(&cred_rev_id), rev_reg_delta_json);
 
         Ok((cred_json, cred_rev_id, rev_reg_delta_json))
     }
 
+    fn new_credential_with_rollover(&self,
+                                    wallet_handle: WalletHandle,
+                                    cred_offer: CredentialOffer,
+                                    cred_request: CredentialRequest,
+                                    cred_values: CredentialValues,
+                                    rev_reg_id: RevocationRegistryId,
+                                    blob_storage_reader_handle: Option<i32>) -> IndyResult<(String, Option<String>, Option<String>, Option<String>)> {
+        debug!("new_credential_with_rollover >>> wallet_handle: {:?}, cred_offer: {:?}, cred_req: {:?}, cred_values_json: {:?}, rev_reg_id: {:?}, blob_storage_reader_handle: {:?}",
+               wallet_handle, secret!(&cred_offer), secret!(&cred_request), secret!(&cred_values), rev_reg_id, blob_storage_reader_handle);
+
+        let res = self._new_credential_from_batch(wallet_handle, cred_offer, cred_request, cred_values, Some(rev_reg_id), blob_storage_reader_handle, true)?;
+
+        debug!("new_credential_with_rollover <<< res: {:?}", secret!(&res));
+
+        Ok(res)
+    }
+
+    // Issues a single credential as a one-element batch, so rollover links are followed the same way
+    fn _new_credential_from_batch(&self,
+                                  wallet_handle: WalletHandle,
+                                  cred_offer: CredentialOffer,
+                                  cred_request: CredentialRequest,
+                                  cred_values: CredentialValues,
+                                  rev_reg_id: Option<RevocationRegistryId>,
+                                  blob_storage_reader_handle: Option<i32>,
+                                  create_rollovers: bool) -> IndyResult<(String, Option<String>, Option<String>, Option<String>)> {
+        let requests = CredentialIssuanceRequests(vec![CredentialIssuanceRequest {
+            cred_offer,
+            cred_req: cred_request,
+            cred_values,
+            rev_reg_id,
+            metadata: None,
+        }]);
+
+        let (mut issued_credentials, rev_reg_deltas, new_rev_regs) =
+            self._issue_credentials_batch(wallet_handle, &requests, blob_storage_reader_handle, create_rollovers)?;
+
+        let issued_credential = issued_credentials.pop()
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "Issued credential not found"))?;
+
+        let cred_json = serde_json::to_string(&issued_credential.cred)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize Credential")?;
+
+        // Only the registry the credential is issued from has a delta
+        let rev_reg_delta_json = rev_reg_deltas
+            .values()
+            .next()
+            .map(serde_json::to_string)
+            .map_or(Ok(None), |v| v.map(Some))
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistryDelta")?;
+
+        let new_rev_regs_json = if new_rev_regs.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(&new_rev_regs)
+                .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistryDefinition")?)
+        };
+
+        Ok((cred_json, issued_credential.cred_revoc_id, rev_reg_delta_json, new_rev_regs_json))
+    }
+
+    fn new_credentials_batch(&self,
+                             wallet_handle: WalletHandle,
+                             requests: &CredentialIssuanceRequests,
+                             blob_storage_reader_handle: Option<i32>) -> IndyResult<(String, Option<String>, Option<String>)> {
+        debug!("new_credentials_batch >>> wallet_handle: {:?}, requests: {:?}, blob_storage_reader_handle: {:?}",
+               wallet_handle, secret!(&requests), blob_storage_reader_handle);
+
+        let (issued_credentials, rev_reg_deltas, new_rev_regs) = self._issue_credentials_batch(wallet_handle, requests, blob_storage_reader_handle, true)?;
+
+        let credentials_json = serde_json::to_string(&issued_credentials)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize Credentials")?;
+
+        let rev_reg_deltas_json = if rev_reg_deltas.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(&rev_reg_deltas)
+                .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistryDelta")?)
+        };
+
+        let new_rev_regs_json = if new_rev_regs.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(&new_rev_regs)
+                .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistryDefinition")?)
+        };
+
+        debug!("new_credentials_batch <<< credentials_json: {:?}, rev_reg_deltas_json: {:?}, new_rev_regs_json: {:?}",
+               secret!(&credentials_json), rev_reg_deltas_json, new_rev_regs_json);
+
+        Ok((credentials_json, rev_reg_deltas_json, new_rev_regs_json))
+    }
+
+    fn get_next_revocation_registry(&self,
+                                    wallet_handle: WalletHandle,
+                                    rev_reg_id: &RevocationRegistryId) -> IndyResult<String> {
+        debug!("get_next_revocation_registry >>> wallet_handle: {:?}, rev_reg_id: {:?}", wallet_handle, rev_reg_id);
+
+        let rollover: RevocationRegistryRollover =
+            self.wallet_service.get_indy_object(wallet_handle, &rev_reg_id.0, &RecordOptions::id_value())?;
+
+        let next_rev_reg_json = json!({
+            "rev_reg_id": rollover.next_rev_reg_id.0,
+            "revoc_reg_def": rollover.next_rev_reg["revoc_reg_def"],
+            "revoc_reg_entry": rollover.next_rev_reg["revoc_reg_entry"],
+        }).to_string();
+
+        debug!("get_next_revocation_registry <<< next_rev_reg_json: {:?}", next_rev_reg_json);
+
+        Ok(next_rev_reg_json)
+    }
+
+    // Issues and stores the whole batch, returns issued credentials, registry deltas and public parts of registries created by rollover.
+    // Without `create_rollovers` only existing rollover links are followed.
+    fn _issue_credentials_batch(&self,
+                                wallet_handle: WalletHandle,
+                                requests: &CredentialIssuanceRequests,
+                                blob_storage_reader_handle: Option<i32>,
+                                create_rollovers: bool) -> IndyResult<(Vec<IssuedCredential>,
+                                                                       HashMap<String, RevocationRegistryDelta>,
+                                                                       HashMap<String, serde_json::Value>)> {
+        let cred_def_ids = requests.0
+            .iter()
+            .map(|request| match request.cred_offer.method_name {
+                Some(ref method_name) => request.cred_offer.cred_def_id.qualify(method_name),
+                None => request.cred_offer.cred_def_id.clone()
+            })
+            .collect::<HashSet<CredentialDefinitionId>>();
+
+        if cred_def_ids.len() != 1 {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "All credentials in batch must be issued for the same Credential Definition"));
+        }
+
+        let cred_def_id = cred_def_ids.into_iter().next().unwrap();
+
+        let cred_def: CredentialDefinitionV1 =
+            CredentialDefinitionV1::from(
+                self.wallet_service.get_indy_object::<CredentialDefinition>(wallet_handle, &cred_def_id.0, &RecordOptions::id_value())?);
+
+        let cred_def_priv_key: CredentialDefinitionPrivateKey =
+            self.wallet_service.get_indy_object(wallet_handle, &cred_def_id.0, &RecordOptions::id_value())?;
+
+        let rollover_config = self._wallet_get_rev_reg_rollover_config(wallet_handle, &cred_def_id)?;
+
+        let mut registries: HashMap<RevocationRegistryId, BatchRevocationRegistry> = HashMap::new();
+        let mut credentials: Vec<(Credential, Option<(RevocationRegistryId, u32)>)> = Vec::new();
+
+        // Registries created by rollover in this batch: links from their predecessors and public parts to return
+        let mut new_rollovers: HashMap<RevocationRegistryId, RevocationRegistryId> = HashMap::new();
+        let mut new_rev_regs: HashMap<String, serde_json::Value> = HashMap::new();
+
+        for request in requests.0.iter() {
+            let (credential_signature, signature_correctness_proof, cred_rev_idx) = match request.rev_reg_id {
+                Some(ref requested_rev_reg_id) => {
+                    // Follow rollover links while the registry is full, taking into account credentials issued earlier in the batch.
+                    // With rollover enabled and `create_rollovers` the registry in use always gets a successor, so it can be published before it is needed.
+                    let mut r_reg_id = requested_rev_reg_id.clone();
+
+                    loop {
+                        if !registries.contains_key(&r_reg_id) {
+                            let registry = self._load_batch_revocation_registry(wallet_handle, &r_reg_id, blob_storage_reader_handle)?;
+                            registries.insert(r_reg_id.clone(), registry);
+                        }
+
+                        let is_full = registries[&r_reg_id].rev_reg_info.curr_id >= registries[&r_reg_id].rev_reg_def.value.max_cred_num;
+
+                        let next_rev_reg_id = match new_rollovers.get(&r_reg_id) {
+                            Some(next_rev_reg_id) => Some(next_rev_reg_id.clone()),
+                            None => self._wallet_get_rev_reg_rollover(wallet_handle, &r_reg_id)?
+                        };
+
+                        let next_rev_reg_id = match (next_rev_reg_id, rollover_config.as_ref()) {
+                            (Some(next_rev_reg_id), _) => Some(next_rev_reg_id),
+                            (None, Some(rollover_config)) if create_rollovers => {
+                                let (next_rev_reg_id, registry, next_rev_reg) =
+                                    self._rollover_revocation_registry(wallet_handle, &registries[&r_reg_id].rev_reg_def, &cred_def,
+                                                                       rollover_config, blob_storage_reader_handle, &registries)?;
+
+                                registries.insert(next_rev_reg_id.clone(), registry);
+                                new_rollovers.insert(r_reg_id.clone(), next_rev_reg_id.clone());
+                                new_rev_regs.insert(next_rev_reg_id.0.clone(), next_rev_reg);
+
+                                Some(next_rev_reg_id)
+                            }
+                            (None, Some(_)) if is_full => {
+                                return Err(err_msg(IndyErrorKind::RevocationRegistryFull,
+                                                   format!("Revocation registry {} is full and the next registry is not created yet. \
+                                                            Use indy_issuer_create_credential_with_rollover or indy_issuer_create_credentials_batch \
+                                                            to create it", r_reg_id.0)));
+                            }
+                            _ => None
+                        };
+
+                        match next_rev_reg_id {
+                            Some(next_rev_reg_id) if is_full => r_reg_id = next_rev_reg_id,
+                            _ => break
+                        }
+                    }
+
+                    let registry = registries.get_mut(&r_reg_id).unwrap();
+
+                    registry.rev_reg_info.curr_id += 1;
+
+                    if registry.rev_reg_info.curr_id > registry.rev_reg_def.value.max_cred_num {
+                        return Err(err_msg(IndyErrorKind::RevocationRegistryFull, "RevocationRegistryAccumulator is full"));
+                    }
+
+                    if registry.rev_reg_def.value.issuance_type == IssuanceType::ISSUANCE_ON_DEMAND {
+                        registry.rev_reg_info.used_ids.insert(registry.rev_reg_info.curr_id);
+                    }
+
+                    let (credential_signature, signature_correctness_proof, rev_reg_delta) =
+                        self.anoncreds_service.issuer.new_credential(&cred_def,
+                                                                     &cred_def_priv_key.value,
+                                                                     &request.cred_offer.nonce,
+                                                                     &request.cred_req,
+                                                                     &request.cred_values,
+                                                                     Some(registry.rev_reg_info.curr_id),
+                                                                     Some(&registry.rev_reg_def),
+                                                                     Some(&mut registry.rev_reg.value),
+                                                                     Some(&registry.rev_key_priv.value),
+                                                                     Some(&registry.sdk_tails_accessor))?;
+
+                    if let Some(rev_reg_delta) = rev_reg_delta {
+                        match registry.rev_reg_delta {
+                            Some(ref mut batch_rev_reg_delta) => batch_rev_reg_delta.merge(&rev_reg_delta)?,
+                            None => registry.rev_reg_delta = Some(rev_reg_delta)
+                        }
+                    }
+
+                    (credential_signature, signature_correctness_proof, Some((r_reg_id, registry.rev_reg_info.curr_id)))
+                }
+                None => {
+                    let (credential_signature, signature_correctness_proof, _) =
+                        self.anoncreds_service.issuer.new_credential::<SDKTailsAccessor>(&cred_def,
+                                                                                         &cred_def_priv_key.value,
+                                                                                         &request.cred_offer.nonce,
+                                                                                         &request.cred_req,
+                                                                                         &request.cred_values,
+                                                                                         None,
+                                                                                         None,
+                                                                                         None,
+                                                                                         None,
+                                                                                         None)?;
+
+                    (credential_signature, signature_correctness_proof, None)
+                }
+            };
+
+            let cred_rev_reg_id = match (cred_rev_idx.as_ref().map(|(r_reg_id, _)| r_reg_id), request.cred_offer.method_name.as_ref()) {
+                (Some(rev_reg_id), Some(ref _method_name)) => Some(rev_reg_id.to_unqualified()),
+                (rev_reg_id, _) => rev_reg_id.cloned()
+            };
+
+            let credential = Credential {
+                schema_id: request.cred_offer.schema_id.clone(),
+                cred_def_id: request.cred_offer.cred_def_id.clone(),
+                rev_reg_id: cred_rev_reg_id,
+                values: request.cred_values.clone(),
+                signature: credential_signature,
+                signature_correctness_proof,
+                rev_reg: None,
+                witness: None,
+            };
+
+            credentials.push((credential, cred_rev_idx));
+        }
+
+        // All credentials of the batch refer to the registry state after the whole batch is issued,
+        // so they are valid against the accumulator published with the combined delta.
+        let mut full_deltas: HashMap<RevocationRegistryId, CryptoRevocationRegistryDelta> = HashMap::new();
+
+        for (r_reg_id, registry) in registries.iter() {
+            let (issued, revoked) = match registry.rev_reg_def.value.issuance_type {
+                IssuanceType::ISSUANCE_ON_DEMAND => (registry.rev_reg_info.used_ids.clone(), HashSet::new()),
+                IssuanceType::ISSUANCE_BY_DEFAULT => (HashSet::new(), registry.rev_reg_info.used_ids.clone())
+            };
+
+            full_deltas.insert(r_reg_id.clone(), CryptoRevocationRegistryDelta::from_parts(None, &registry.rev_reg.value, &issued, &revoked));
+        }
+
+        let mut issued_credentials: Vec<IssuedCredential> = Vec::new();
+        let mut issued_rev_reg_ids: Vec<Option<RevocationRegistryId>> = Vec::new();
+
+        for (mut credential, cred_rev_idx) in credentials {
+            let cred_revoc_id = match cred_rev_idx {
+                Some((r_reg_id, cred_rev_idx)) => {
+                    let registry = &registries[&r_reg_id];
+
+                    credential.witness = Some(Witness::new(cred_rev_idx, registry.rev_reg_def.value.max_cred_num,
+                                                           registry.rev_reg_def.value.issuance_type.to_bool(), &full_deltas[&r_reg_id],
+                                                           &registry.sdk_tails_accessor)?);
+                    credential.rev_reg = Some(registry.rev_reg.value.clone());
+
+                    issued_rev_reg_ids.push(Some(r_reg_id));
+
+                    Some(cred_rev_idx.to_string())
+                }
+                None => {
+                    issued_rev_reg_ids.push(None);
+
+                    None
+                }
+            };
+
+            issued_credentials.push(IssuedCredential { cred: credential, cred_revoc_id, issued_cred_id: uuid::Uuid::new_v4().to_string() });
+        }
+
+        let rev_reg_deltas: HashMap<String, RevocationRegistryDelta> = registries
+            .iter_mut()
+            .filter_map(|(r_reg_id, registry)|
+                registry.rev_reg_delta.take()
+                    .map(|r_reg_delta| (r_reg_id.0.clone(), RevocationRegistryDelta::RevocationRegistryDeltaV1(RevocationRegistryDeltaV1 { value: r_reg_delta }))))
+            .collect();
+
+        // The whole batch is stored at once: either all credentials are registered as issued or none
+        let mut operations: Vec<WalletRecordOperation> = Vec::new();
+
+        for (r_reg_id, registry) in registries {
+            let revoc_reg = RevocationRegistry::RevocationRegistryV1(registry.rev_reg);
+
+            if new_rev_regs.contains_key(&r_reg_id.0) {
+                let revoc_reg_def = RevocationRegistryDefinition::RevocationRegistryDefinitionV1(registry.rev_reg_def);
+
+                operations.push(self.wallet_service.add_indy_object_operation(&r_reg_id.0, &revoc_reg_def, &HashMap::new())?);
+                operations.push(self.wallet_service.add_indy_object_operation(&r_reg_id.0, &revoc_reg, &HashMap::new())?);
+                operations.push(self.wallet_service.add_indy_object_operation(&r_reg_id.0, &registry.rev_key_priv, &HashMap::new())?);
+                operations.push(self.wallet_service.add_indy_object_operation(&r_reg_id.0, &registry.rev_reg_info, &HashMap::new())?);
+            } else {
+                operations.push(self.wallet_service.update_indy_object_operation(&r_reg_id.0, &revoc_reg)?);
+                operations.push(self.wallet_service.update_indy_object_operation(&r_reg_id.0, &registry.rev_reg_info)?);
+            }
+        }
+
+        // Public parts of the next registry are kept with the link, so they can be got for publishing later
+        for (r_reg_id, next_rev_reg_id) in new_rollovers {
+            let next_rev_reg = new_rev_regs[&next_rev_reg_id.0].clone();
+            let rollover = RevocationRegistryRollover { next_rev_reg_id, next_rev_reg };
+
+            operations.push(self.wallet_service.add_indy_object_operation(&r_reg_id.0, &rollover, &HashMap::new())?);
+        }
+
+        for ((request, issued_credential), r_reg_id) in requests.0.iter().zip(issued_credentials.iter()).zip(issued_rev_reg_ids.into_iter()) {
+            let metadata = request.metadata.as_ref().map(|metadata| metadata.0.clone()).unwrap_or_default();
+
+            operations.push(self._add_issued_credential_record_operation(&issued_credential.issued_cred_id, &cred_def_id, &request.cred_offer, &request.cred_req,
+                                                                         r_reg_id.as_ref(), issued_credential.cred_revoc_id.clone(), metadata)?);
+        }
+
+        self.wallet_service.batch_records(wallet_handle, &operations)?;
+
+        Ok((issued_credentials, rev_reg_deltas, new_rev_regs))
+    }
+
+    // Creates the next registry of the rollover chain with the same parameters and incremented tag.
+    // Tails are stored with the writer from the Credential Definition config, so the reader used for issuance must be able to read them.
+    fn _rollover_revocation_registry(&self,
+                                     wallet_handle: WalletHandle,
+                                     rev_reg_def: &RevocationRegistryDefinitionV1,
+                                     cred_def: &CredentialDefinitionV1,
+                                     rollover_config: &RevocationRegistryRolloverConfig,
+                                     blob_storage_reader_handle: Option<i32>,
+                                     registries: &HashMap<RevocationRegistryId, BatchRevocationRegistry>) -> IndyResult<(RevocationRegistryId, BatchRevocationRegistry, serde_json::Value)> {
+        let (issuer_did, _, _, _) = rev_reg_def.id.parts()
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Invalid RevocationRegistryId: {:?}", rev_reg_def.id)))?;
+
+        // Skip tags which are already used by registries created for the same Credential Definition
+        let mut tag = next_rev_reg_tag(&rev_reg_def.tag);
+
+        loop {
+            let rev_reg_id = RevocationRegistryId::new(&issuer_did, &rev_reg_def.cred_def_id, rev_reg_def.revoc_def_type.to_str(), &tag);
+
+            if !registries.contains_key(&rev_reg_id) &&
+                !self.wallet_service.record_exists::<RevocationRegistryDefinition>(wallet_handle, &rev_reg_id.0)? {
+                break;
+            }
+
+            tag = next_rev_reg_tag(&tag);
+        }
+
+        let tails_writer_handle = self.blob_storage_service.open_writer(&rollover_config.tails_writer_type,
+                                                                        &rollover_config.tails_writer_config.to_string())?;
+
+        let res = self._new_revocation_registry(&issuer_did,
+                                                RegistryType::CL_ACCUM,
+                                                &tag,
+                                                &rev_reg_def.cred_def_id,
+                                                cred_def,
+                                                rev_reg_def.value.issuance_type.clone(),
+                                                rev_reg_def.value.max_cred_num,
+                                                tails_writer_handle);
+
+        // The writer is opened per rollover, so release it even if the registry creation failed
+        self.blob_storage_service.close_writer(tails_writer_handle)?;
+
+        let (revoc_reg_def, revoc_reg, revoc_reg_def_priv) = res?;
+
+        let rev_reg_id = revoc_reg_def.id.clone();
+
+        let revoc_reg_def = RevocationRegistryDefinition::RevocationRegistryDefinitionV1(revoc_reg_def);
+        let revoc_reg = RevocationRegistry::RevocationRegistryV1(revoc_reg);
+
+        let new_rev_reg = json!({
+            "revoc_reg_def": serde_json::to_value(&revoc_reg_def)
+                .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistryDefinition")?,
+            "revoc_reg_entry": serde_json::to_value(&revoc_reg)
+                .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistry")?,
+        });
+
+        let rev_reg_info = RevocationRegistryInfo {
+            id: rev_reg_id.clone(),
+            curr_id: 0,
+            used_ids: HashSet::new(),
+        };
+
+        let registry = self._batch_revocation_registry(RevocationRegistryDefinitionV1::from(revoc_reg_def),
+                                                       RevocationRegistryV1::from(revoc_reg),
+                                                       revoc_reg_def_priv,
+                                                       rev_reg_info,
+                                                       blob_storage_reader_handle)?;
+
+        Ok((rev_reg_id, registry, new_rev_reg))
+    }
+
+    fn _load_batch_revocation_registry(&self,
+                                       wallet_handle: WalletHandle,
+                                       rev_reg_id: &RevocationRegistryId,
+                                       blob_storage_reader_handle: Option<i32>) -> IndyResult<BatchRevocationRegistry> {
+        let rev_reg_def: RevocationRegistryDefinitionV1 =
+            RevocationRegistryDefinitionV1::from(
+                self._wallet_get_rev_reg_def(wallet_handle, &rev_reg_id)?);
+
+        let rev_reg: RevocationRegistryV1 =
+            RevocationRegistryV1::from(
+                self._wallet_get_rev_reg(wallet_handle, &rev_reg_id)?);
+
+        let rev_key_priv: RevocationRegistryDefinitionPrivate =
+            self.wallet_service.get_indy_object(wallet_handle, &rev_reg_id.0, &RecordOptions::id_value())?;
+
+        let rev_reg_info = self._wallet_get_rev_reg_info(wallet_handle, &rev_reg_id)?;
+
+        self._batch_revocation_registry(rev_reg_def, rev_reg, rev_key_priv, rev_reg_info, blob_storage_reader_handle)
+    }
+
+    fn _batch_revocation_registry(&self,
+                                  rev_reg_def: RevocationRegistryDefinitionV1,
+                                  rev_reg: RevocationRegistryV1,
+                                  rev_key_priv: RevocationRegistryDefinitionPrivate,
+                                  rev_reg_info: RevocationRegistryInfo,
+                                  blob_storage_reader_handle: Option<i32>) -> IndyResult<BatchRevocationRegistry> {
+        // TODO: FIXME: Review error kind!
+        let blob_storage_reader_handle = blob_storage_reader_handle
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "TailsReaderHandle not found"))?;
+
+        let sdk_tails_accessor = SDKTailsAccessor::new(self.blob_storage_service.clone(),
+                                                       blob_storage_reader_handle,
+                                                       &rev_reg_def)?;
+
+        Ok(BatchRevocationRegistry {
+            rev_reg_def,
+            rev_reg,
+            rev_key_priv,
+            rev_reg_info,
+            sdk_tails_accessor,
+            rev_reg_delta: None,
+        })
+    }
+
     fn revoke_credential(&self,
                          wallet_handle: WalletHandle,
                          blob_storage_reader_handle: i32,
@@ -847,14 +1460,296 @@ impl IssuerCommandExecutor {
 
         let rev_reg = RevocationRegistry::RevocationRegistryV1(rev_reg);
 
-        self.wallet_service.update_indy_object(wallet_handle, &rev_reg_id.0, &rev_reg)?;
-        self.wallet_service.update_indy_object(wallet_handle, &rev_reg_id.0, &rev_reg_info)?;
+        let mut operations: Vec<WalletRecordOperation> = vec![
+            self.wallet_service.update_indy_object_operation(&rev_reg_id.0, &rev_reg)?,
+            self.wallet_service.update_indy_object_operation(&rev_reg_id.0, &rev_reg_info)?,
+        ];
+
+        operations.extend(self._update_issued_credentials_revocation_status_operations(wallet_handle, rev_reg_id, &[cred_revoc_id], true)?);
+
+        self.wallet_service.batch_records(wallet_handle, &operations)?;
 
         debug!("revoke_credential <<< rev_reg_delta_json: {:?}", rev_reg_delta_json);
 
         Ok(rev_reg_delta_json)
     }
 
+    fn revoke_credentials(&self,
+                          wallet_handle: WalletHandle,
+                          blob_storage_reader_handle: i32,
+                          rev_reg_id: &RevocationRegistryId,
+                          revoked_cred_revoc_ids: &[String],
+                          recovered_cred_revoc_ids: &[String]) -> IndyResult<String> {
+        debug!("revoke_credentials >>> wallet_handle: {:?}, blob_storage_reader_handle: {:?}, rev_reg_id: {:?}, revoked_cred_revoc_ids: {:?}, recovered_cred_revoc_ids: {:?}",
+               wallet_handle, blob_storage_reader_handle, rev_reg_id, secret!(revoked_cred_revoc_ids), secret!(recovered_cred_revoc_ids));
+
+        if revoked_cred_revoc_ids.is_empty() && recovered_cred_revoc_ids.is_empty() {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Neither revoked nor recovered revocation ids are provided"));
+        }
+
+        let revoked = Self::_parse_cred_rev_ids(revoked_cred_revoc_ids)?;
+        let recovered = Self::_parse_cred_rev_ids(recovered_cred_revoc_ids)?;
+
+        if let Some(cred_revoc_id) = revoked.intersection(&recovered).next() {
+            return Err(err_msg(IndyErrorKind::InvalidUserRevocId, format!("Revocation id: {:?} is both revoked and recovered", cred_revoc_id)));
+        }
+
+        let revocation_registry_definition: RevocationRegistryDefinitionV1 =
+            RevocationRegistryDefinitionV1::from(
+                self._wallet_get_rev_reg_def(wallet_handle, &rev_reg_id)?);
+
+        let mut rev_reg: RevocationRegistryV1 =
+            RevocationRegistryV1::from(
+                self._wallet_get_rev_reg(wallet_handle, &rev_reg_id)?);
+
+        let mut rev_reg_info = self._wallet_get_rev_reg_info(wallet_handle, &rev_reg_id)?;
+
+        let max_cred_num = revocation_registry_definition.value.max_cred_num;
+
+        if let Some(cred_revoc_id) = revoked.iter().chain(recovered.iter()).find(|id| **id == 0 || **id > max_cred_num) {
+            return Err(err_msg(IndyErrorKind::InvalidUserRevocId, format!("Revocation id: {:?} not found in RevocationRegistry", cred_revoc_id)));
+        }
+
+        // `used_ids` keeps issued indices for ISSUANCE_ON_DEMAND and revoked indices for ISSUANCE_BY_DEFAULT
+        for cred_revoc_id in revoked.iter() {
+            let updated = match revocation_registry_definition.value.issuance_type {
+                IssuanceType::ISSUANCE_ON_DEMAND => rev_reg_info.used_ids.remove(cred_revoc_id),
+                IssuanceType::ISSUANCE_BY_DEFAULT => rev_reg_info.used_ids.insert(*cred_revoc_id),
+            };
+
+            if !updated {
+                return Err(err_msg(IndyErrorKind::InvalidUserRevocId, format!("Revocation id: {:?} is not issued or already revoked", cred_revoc_id)));
+            }
+        }
+
+        for cred_revoc_id in recovered.iter() {
+            let updated = match revocation_registry_definition.value.issuance_type {
+                IssuanceType::ISSUANCE_ON_DEMAND => *cred_revoc_id <= rev_reg_info.curr_id && rev_reg_info.used_ids.insert(*cred_revoc_id),
+                IssuanceType::ISSUANCE_BY_DEFAULT => rev_reg_info.used_ids.remove(cred_revoc_id),
+            };
+
+            if !updated {
+                return Err(err_msg(IndyErrorKind::InvalidUserRevocId, format!("Revocation id: {:?} is not revoked", cred_revoc_id)));
+            }
+        }
+
+        let sdk_tails_accessor = SDKTailsAccessor::new(self.blob_storage_service.clone(),
+                                                       blob_storage_reader_handle,
+                                                       &revocation_registry_definition)?;
+
+        let revoked_ids: Vec<u32> = revoked.iter().cloned().collect();
+        let recovered_ids: Vec<u32> = recovered.iter().cloned().collect();
+
+        let rev_reg_delta =
+            self.anoncreds_service.issuer.update_revocation_registry(&mut rev_reg.value, max_cred_num, recovered, revoked, &sdk_tails_accessor)?;
+
+        let rev_reg_delta = RevocationRegistryDelta::RevocationRegistryDeltaV1(RevocationRegistryDeltaV1 { value: rev_reg_delta });
+
+        let rev_reg_delta_json = serde_json::to_string(&rev_reg_delta)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistryDelta")?;
+
+        let rev_reg = RevocationRegistry::RevocationRegistryV1(rev_reg);
+
+        // Accumulator, issued indices and credential statuses must never diverge, so they are stored together
+        let mut operations: Vec<WalletRecordOperation> = vec![
+            self.wallet_service.update_indy_object_operation(&rev_reg_id.0, &rev_reg)?,
+            self.wallet_service.update_indy_object_operation(&rev_reg_id.0, &rev_reg_info)?,
+        ];
+
+        operations.extend(self._update_issued_credentials_revocation_status_operations(wallet_handle, rev_reg_id, &revoked_ids, true)?);
+        operations.extend(self._update_issued_credentials_revocation_status_operations(wallet_handle, rev_reg_id, &recovered_ids, false)?);
+
+        self.wallet_service.batch_records(wallet_handle, &operations)?;
+
+        debug!("revoke_credentials <<< rev_reg_delta_json: {:?}", rev_reg_delta_json);
+
+        Ok(rev_reg_delta_json)
+    }
+
+    fn _parse_cred_rev_ids(cred_revoc_ids: &[String]) -> IndyResult<BTreeSet<u32>> {
+        let mut res = BTreeSet::new();
+
+        for cred_revoc_id in cred_revoc_ids {
+            if !res.insert(parse_cred_rev_id(cred_revoc_id)?) {
+                return Err(err_msg(IndyErrorKind::InvalidUserRevocId, format!("Revocation id: {:?} is duplicated", cred_revoc_id)));
+            }
+        }
+
+        Ok(res)
+    }
+
+    fn set_issued_credential_metadata(&self,
+                                      wallet_handle: WalletHandle,
+                                      issued_cred_id: &str,
+                                      metadata: IssuedCredentialMetadata) -> IndyResult<()> {
+        debug!("set_issued_credential_metadata >>> wallet_handle: {:?}, issued_cred_id: {:?}, metadata: {:?}", wallet_handle, issued_cred_id, metadata);
+
+        let mut record: IssuedCredentialRecord =
+            self.wallet_service.get_indy_object(wallet_handle, issued_cred_id, &RecordOptions::id_value())?;
+
+        record.metadata = metadata.0;
+
+        self.wallet_service.batch_records(wallet_handle, &self._update_issued_credential_record_operations(issued_cred_id, &record)?)?;
+
+        debug!("set_issued_credential_metadata <<<");
+
+        Ok(())
+    }
+
+    fn search_issued_credentials(&self,
+                                 wallet_handle: WalletHandle,
+                                 query_json: Option<&str>) -> IndyResult<(SearchHandle, usize)> {
+        debug!("search_issued_credentials >>> wallet_handle: {:?}, query_json: {:?}", wallet_handle, query_json);
+
+        let issued_credentials_search =
+            self.wallet_service.search_indy_records::<IssuedCredentialRecord>(wallet_handle, query_json.unwrap_or("{}"), &SearchOptions::id_value())?;
+
+        let total_count = issued_credentials_search.get_total_count()?.unwrap_or(0);
+
+        let handle: SearchHandle = next_search_handle();
+
+        self.issued_credentials_searches.borrow_mut().insert(handle, Box::new(issued_credentials_search));
+
+        let res = (handle, total_count);
+
+        debug!("search_issued_credentials <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn fetch_issued_credentials(&self,
+                                search_handle: SearchHandle,
+                                count: usize) -> IndyResult<String> {
+        debug!("fetch_issued_credentials >>> search_handle: {:?}, count: {:?}", search_handle, count);
+
+        let mut searches = self.issued_credentials_searches.borrow_mut();
+        let search = searches.get_mut(&search_handle)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidWalletHandle, format!("Unknown IssuedCredentialsSearch handle: {:?}", search_handle)))?;
+
+        let mut issued_credentials_info: Vec<IssuedCredentialInfo> = Vec::new();
+
+        for _ in 0..count {
+            match search.fetch_next_record()? {
+                Some(record) => {
+                    let record_value = record.get_value()
+                        .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "Issued credential record value not found"))?;
+
+                    let issued_credential_record: IssuedCredentialRecord = serde_json::from_str(record_value)
+                        .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize IssuedCredentialRecord")?;
+
+                    issued_credentials_info.push(IssuedCredentialInfo {
+                        referent: record.get_id().to_string(),
+                        record: issued_credential_record,
+                    })
+                }
+                None => break
+            }
+        }
+
+        let issued_credentials_info_json = serde_json::to_string(&issued_credentials_info)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize list of IssuedCredentialInfo")?;
+
+        debug!("fetch_issued_credentials <<< issued_credentials_info_json: {:?}", issued_credentials_info_json);
+
+        Ok(issued_credentials_info_json)
+    }
+
+    fn close_issued_credentials_search(&self, search_handle: SearchHandle) -> IndyResult<()> {
+        debug!("close_issued_credentials_search >>> search_handle: {:?}", search_handle);
+
+        match self.issued_credentials_searches.borrow_mut().remove(&search_handle) {
+            Some(_) => Ok(()),
+            None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, format!("Unknown IssuedCredentialsSearch handle: {:?}", search_handle)))
+        }?;
+
+        debug!("close_issued_credentials_search <<<");
+
+        Ok(())
+    }
+
+    fn _add_issued_credential_record_operation(&self,
+                                               issued_cred_id: &str,
+                                               cred_def_id: &CredentialDefinitionId,
+                                               cred_offer: &CredentialOffer,
+                                               cred_request: &CredentialRequest,
+                                               rev_reg_id: Option<&RevocationRegistryId>,
+                                               cred_rev_id: Option<String>,
+                                               metadata: Tags) -> IndyResult<WalletRecordOperation> {
+        let schema_id = match cred_offer.method_name {
+            Some(ref method_name) => cred_offer.schema_id.qualify(method_name),
+            None => cred_offer.schema_id.clone()
+        };
+
+        let record = IssuedCredentialRecord {
+            cred_def_id: cred_def_id.clone(),
+            schema_id,
+            rev_reg_id: rev_reg_id.cloned(),
+            cred_rev_id,
+            cred_offer_nonce: cred_offer.nonce.to_dec()
+                .to_indy(IndyErrorKind::InvalidState, "Cannot serialize Nonce")?,
+            prover_did: cred_request.prover_did.clone(),
+            revoked: false,
+            metadata,
+        };
+
+        self.wallet_service.add_indy_object_operation(issued_cred_id, &record, &record.tags())
+    }
+
+    fn _update_issued_credential_record_operations(&self,
+                                                   issued_cred_id: &str,
+                                                   record: &IssuedCredentialRecord) -> IndyResult<Vec<WalletRecordOperation>> {
+        Ok(vec![
+            self.wallet_service.update_indy_object_operation(issued_cred_id, record)?,
+            WalletRecordOperation::UpdateTags {
+                type_: self.wallet_service.add_prefix("IssuedCredentialRecord"),
+                id: issued_cred_id.to_string(),
+                tags: record.tags(),
+            },
+        ])
+    }
+
+    fn _update_issued_credentials_revocation_status_operations(&self,
+                                                               wallet_handle: WalletHandle,
+                                                               rev_reg_id: &RevocationRegistryId,
+                                                               cred_rev_ids: &[u32],
+                                                               revoked: bool) -> IndyResult<Vec<WalletRecordOperation>> {
+        let mut operations: Vec<WalletRecordOperation> = Vec::new();
+
+        if cred_rev_ids.is_empty() {
+            return Ok(operations);
+        }
+
+        // Records are tagged with unqualified registry id, the original form also matches records stored before that
+        let query_json = json!({
+            "rev_reg_id": {"$in": [rev_reg_id.to_unqualified().0, rev_reg_id.0.clone()]},
+            "cred_rev_id": {"$in": cred_rev_ids.iter().map(u32::to_string).collect::<Vec<String>>()}
+        }).to_string();
+
+        // Records are collected before building updates to not modify wallet while search is in progress.
+        // Credentials issued before records were introduced have no record, so they are skipped.
+        let mut records: Vec<(String, IssuedCredentialRecord)> = Vec::new();
+        {
+            let mut search = self.wallet_service.search_indy_records::<IssuedCredentialRecord>(wallet_handle, &query_json, &SearchOptions::id_value())?;
+
+            while let Some(record) = search.fetch_next_record()? {
+                let record_value = record.get_value()
+                    .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "Issued credential record value not found"))?;
+
+                let issued_credential_record: IssuedCredentialRecord = serde_json::from_str(record_value)
+                    .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize IssuedCredentialRecord")?;
+
+                records.push((record.get_id().to_string(), issued_credential_record));
+            }
+        }
+
+        for (issued_cred_id, mut record) in records {
+            record.revoked = revoked;
+            operations.extend(self._update_issued_credential_record_operations(&issued_cred_id, &record)?);
+        }
+
+        Ok(operations)
+    }
+
     fn _recovery_credential(&self,
                             wallet_handle: WalletHandle,
                             blob_storage_reader_handle: i32,
@@ -906,8 +1801,14 @@ impl IssuerCommandExecutor {
 
         let rev_reg = RevocationRegistry::RevocationRegistryV1(rev_reg);
 
-        self.wallet_service.update_indy_object(wallet_handle, &rev_reg_id.0, &rev_reg)?;
-        self.wallet_service.update_indy_object(wallet_handle, &rev_reg_id.0, &rev_reg_info)?;
+        let mut operations: Vec<WalletRecordOperation> = vec![
+            self.wallet_service.update_indy_object_operation(&rev_reg_id.0, &rev_reg)?,
+            self.wallet_service.update_indy_object_operation(&rev_reg_id.0, &rev_reg_info)?,
+        ];
+
+        operations.extend(self._update_issued_credentials_revocation_status_operations(wallet_handle, rev_reg_id, &[cred_revoc_id], false)?);
+
+        self.wallet_service.batch_records(wallet_handle, &operations)?;
 
         debug!("recovery_credential <<< rev_reg_delta_json: {:?}", rev_reg_delta_json);
 
@@ -956,4 +1857,22 @@ impl IssuerCommandExecutor {
     fn _wallet_get_rev_reg_info(&self, wallet_handle: WalletHandle, key: &RevocationRegistryId) -> IndyResult<RevocationRegistryInfo> {
         self.wallet_service.get_indy_object(wallet_handle, &key.0, &RecordOptions::id_value())
     }
+
+    fn _wallet_get_rev_reg_rollover(&self, wallet_handle: WalletHandle, key: &RevocationRegistryId) -> IndyResult<Option<RevocationRegistryId>> {
+        if !self.wallet_service.record_exists::<RevocationRegistryRollover>(wallet_handle, &key.0)? {
+            return Ok(None);
+        }
+
+        let rollover: RevocationRegistryRollover =
+            self.wallet_service.get_indy_object(wallet_handle, &key.0, &RecordOptions::id_value())?;
+
+        Ok(Some(rollover.next_rev_reg_id))
+    }
+
+    fn _wallet_get_rev_reg_rollover_config(&self, wallet_handle: WalletHandle, key: &CredentialDefinitionId) -> IndyResult<Option<RevocationRegistryRolloverConfig>> {
+        let config: Option<CredentialDefinitionConfig> =
+            self.wallet_service.get_indy_opt_object(wallet_handle, &key.0, &RecordOptions::id_value())?;
+
+        Ok(config.and_then(|config| config.revocation_registry_rollover))
+    }
 }
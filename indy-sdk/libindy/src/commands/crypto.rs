@@ -1,23 +1,45 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
+use crate::domain::crypto::did::{DidValue, TheirDid};
+use crate::domain::crypto::jws::*;
 use crate::domain::crypto::key::{Key, KeyInfo, KeyMetadata};
+use crate::domain::crypto::multi_sig::*;
 use crate::domain::crypto::pack::*;
+use crate::domain::crypto::session::*;
 use indy_api_types::errors::prelude::*;
-use crate::services::crypto::CryptoService;
+use crate::services::crypto::{CryptoService, PluggedKeyManager};
+use crate::utils::crypto::signature_serializer::serialize_signature;
+use crate::utils::crypto::verkey_builder::{did_key_to_verkey, split_verkey, verkey_to_did_key, DID_KEY_PREFIX};
 use indy_wallet::{RecordOptions, WalletService};
 
+use rust_base58::{FromBase58, ToBase58};
+use serde_json::{Map, Value};
 use std::rc::Rc;
 use std::str;
+use indy_utils::crypto::aes256kw;
 use indy_utils::crypto::base64;
 use indy_utils::crypto::chacha20poly1305_ietf;
+use indy_utils::crypto::ed25519_box;
+use indy_utils::crypto::hash;
+use indy_utils::crypto::randombytes;
 use crate::domain::crypto::combo_box::ComboBox;
 use indy_api_types::WalletHandle;
+use indy_api_types::key_manager::*;
 
 pub const PROTECTED_HEADER_ENC: &str = "xchacha20poly1305_ietf";
 pub const PROTECTED_HEADER_TYP: &str = "JWM/1.0";
 pub const PROTECTED_HEADER_ALG_AUTH: &str = "Authcrypt";
 pub const PROTECTED_HEADER_ALG_ANON: &str = "Anoncrypt";
 
+pub const ECDH_PROTECTED_HEADER_TYP: &str = "application/didcomm-encrypted+json";
+pub const ECDH_PROTECTED_HEADER_ALG_ES: &str = "ECDH-ES+A256KW";
+pub const ECDH_PROTECTED_HEADER_ALG_1PU: &str = "ECDH-1PU+A256KW";
+pub const ECDH_EPK_KTY: &str = "OKP";
+pub const ECDH_EPK_CRV: &str = "X25519";
+
+// Protected header parameters set by libindy or not supported in signed JWS
+const JWS_RESERVED_HEADERS: [&str; 4] = ["alg", "kid", "crit", "b64"];
+
 pub enum CryptoCommand {
     CreateKey(
         WalletHandle,
@@ -47,6 +69,24 @@ pub enum CryptoCommand {
         Vec<u8>, // signature
         Box<dyn Fn(IndyResult<bool>) + Send>,
     ),
+    VerifyMulti(
+        Vec<u8>, // msg
+        MultiSignatures,
+        MultiSigPolicy,
+        Box<dyn Fn(IndyResult<bool>) + Send>,
+    ),
+    SignJws(
+        WalletHandle,
+        String,  // my vk
+        Vec<u8>, // payload
+        JwsOptions,
+        Box<dyn Fn(IndyResult<String>) + Send>,
+    ),
+    VerifyJws(
+        String,  // jws
+        Option<Vec<u8>>, // detached payload
+        Box<dyn Fn(IndyResult<bool>) + Send>,
+    ),
     AuthenticatedEncrypt(
         WalletHandle,
         String,  // my vk
@@ -75,6 +115,7 @@ pub enum CryptoCommand {
         Vec<u8>, // plaintext message
         Vec<String>,  // list of receiver's keys
         Option<String>,  // senders verkey
+        PackOptions,
         WalletHandle,
         Box<dyn Fn(IndyResult<Vec<u8>>) + Send>,
     ),
@@ -83,6 +124,33 @@ pub enum CryptoCommand {
         WalletHandle,
         Box<dyn Fn(IndyResult<Vec<u8>>) + Send>,
     ),
+    SessionStart(
+        WalletHandle,
+        String, // my vk
+        DidValue, // their did
+        Option<SessionHandshake>, // their handshake
+        Box<dyn Fn(IndyResult<(String, Option<String>)>) + Send>,
+    ),
+    SessionEncrypt(
+        WalletHandle,
+        String, // session id
+        Vec<u8>, // msg
+        Box<dyn Fn(IndyResult<Vec<u8>>) + Send>,
+    ),
+    SessionDecrypt(
+        WalletHandle,
+        Vec<u8>, // encrypted msg
+        Box<dyn Fn(IndyResult<Vec<u8>>) + Send>,
+    ),
+    RegisterKeyManager(
+        String, // name
+        KeyManagerCreateKey,
+        KeyManagerSign,
+        KeyManagerEncrypt,
+        KeyManagerDecrypt,
+        KeyManagerFreeBuffer,
+        Box<dyn Fn(IndyResult<()>) + Send>,
+    ),
 }
 
 pub struct CryptoCommandExecutor {
@@ -123,6 +191,18 @@ impl CryptoCommandExecutor {
                 debug!("CryptoVerify command received");
                 cb(self.crypto_verify(&their_vk, &msg, &signature));
             }
+            CryptoCommand::VerifyMulti(msg, signatures, policy, cb) => {
+                debug!("VerifyMulti command received");
+                cb(self.verify_multi(&msg, &signatures, &policy));
+            }
+            CryptoCommand::SignJws(wallet_handle, my_vk, payload, options, cb) => {
+                debug!("SignJws command received");
+                cb(self.sign_jws(wallet_handle, &my_vk, &payload, &options));
+            }
+            CryptoCommand::VerifyJws(jws, payload, cb) => {
+                debug!("VerifyJws command received");
+                cb(self.verify_jws(&jws, payload.as_ref().map(Vec::as_slice)));
+            }
             CryptoCommand::AuthenticatedEncrypt(wallet_handle, my_vk, their_vk, msg, cb) => {
                 debug!("AuthenticatedEncrypt command received");
                 cb(self.authenticated_encrypt(wallet_handle, &my_vk, &their_vk, &msg));
@@ -139,17 +219,49 @@ impl CryptoCommandExecutor {
                 debug!("AnonymousDecrypt command received");
                 cb(self.anonymous_decrypt(wallet_handle, &my_vk, &encrypted_msg));
             }
-            CryptoCommand::PackMessage(message, receivers, sender_vk, wallet_handle, cb) => {
+            CryptoCommand::PackMessage(message, receivers, sender_vk, options, wallet_handle, cb) => {
                 debug!("PackMessage command received");
-                cb(self.pack_msg(message, receivers, sender_vk, wallet_handle));
+                cb(self.pack_msg(message, receivers, sender_vk, options, wallet_handle));
             }
             CryptoCommand::UnpackMessage(jwe_json, wallet_handle, cb) => {
                 debug!("UnpackMessage command received");
                 cb(self.unpack_msg(jwe_json, wallet_handle));
             }
+            CryptoCommand::SessionStart(wallet_handle, my_vk, their_did, their_handshake, cb) => {
+                debug!("SessionStart command received");
+                cb(self.session_start(wallet_handle, &my_vk, &their_did, their_handshake));
+            }
+            CryptoCommand::SessionEncrypt(wallet_handle, session_id, msg, cb) => {
+                debug!("SessionEncrypt command received");
+                cb(self.session_encrypt(wallet_handle, &session_id, &msg));
+            }
+            CryptoCommand::SessionDecrypt(wallet_handle, encrypted_msg, cb) => {
+                debug!("SessionDecrypt command received");
+                cb(self.session_decrypt(wallet_handle, &encrypted_msg));
+            }
+            CryptoCommand::RegisterKeyManager(name, create_key, sign, encrypt, decrypt, free_buffer, cb) => {
+                debug!("RegisterKeyManager command received");
+                cb(self.register_key_manager(&name, create_key, sign, encrypt, decrypt, free_buffer));
+            }
         };
     }
 
+    fn register_key_manager(&self,
+                            name: &str,
+                            create_key: KeyManagerCreateKey,
+                            sign: KeyManagerSign,
+                            encrypt: KeyManagerEncrypt,
+                            decrypt: KeyManagerDecrypt,
+                            free_buffer: KeyManagerFreeBuffer) -> IndyResult<()> {
+        debug!("register_key_manager >>> name: {:?}", name);
+
+        let key_manager = PluggedKeyManager::new(create_key, sign, encrypt, decrypt, free_buffer);
+        let res = self.crypto_service.register_key_manager(name, key_manager);
+
+        debug!("register_key_manager <<< res: {:?}", res);
+        res
+    }
+
     fn create_key(&self, wallet_handle: WalletHandle, key_info: &KeyInfo) -> IndyResult<String> {
         debug!(
             "create_key >>> wallet_handle: {:?}, key_info: {:?}",
@@ -205,6 +317,215 @@ impl CryptoCommandExecutor {
         Ok(res)
     }
 
+    fn verify_multi(&self,
+                    msg: &[u8],
+                    signatures: &MultiSignatures,
+                    policy: &MultiSigPolicy) -> IndyResult<bool> {
+        trace!(
+            "verify_multi >>> msg: {:?}, signatures: {:?}, policy: {:?}",
+            msg, signatures, policy
+        );
+
+        let msg = match policy.serialization {
+            MultiSigSerialization::Raw => msg.to_vec(),
+            MultiSigSerialization::Request => {
+                let request: Value = serde_json::from_slice(msg)
+                    .to_indy(IndyErrorKind::InvalidStructure, "Message is invalid json")?;
+
+                if !request.is_object() {
+                    return Err(err_msg(IndyErrorKind::InvalidStructure, "Message isn't json object"));
+                }
+
+                serialize_signature(request)?.into_bytes()
+            }
+        };
+
+        let mut signed_keys = HashSet::new();
+
+        // Signatures of keys that aren't in the policy aren't counted
+        for signer in policy.signers.iter() {
+            self.crypto_service.validate_key(signer)?;
+
+            if signer.starts_with('~') {
+                return Err(err_msg(IndyErrorKind::InvalidStructure, "Abbreviated verkey can't be used as multi-signature signer"));
+            }
+
+            // Each key is counted once whatever spelling of it is used
+            if signed_keys.contains(&split_verkey(signer)) {
+                continue;
+            }
+
+            if let Some(signature) = signatures.get(signer) {
+                let signature = signature.from_base58()?;
+
+                if self.crypto_service.verify(signer, &msg, &signature)? {
+                    signed_keys.insert(split_verkey(signer));
+                }
+            }
+        }
+
+        let res = signed_keys.len() >= policy.threshold;
+
+        trace!("verify_multi <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn sign_jws(&self,
+                wallet_handle: WalletHandle,
+                my_vk: &str,
+                payload: &[u8],
+                options: &JwsOptions) -> IndyResult<String> {
+        trace!(
+            "sign_jws >>> wallet_handle: {:?}, my_vk: {:?}, payload: {:?}, options: {:?}",
+            wallet_handle, my_vk, payload, options
+        );
+
+        self.crypto_service.validate_key(my_vk)?;
+
+        let key: Key = self.wallet_service.get_indy_object(
+            wallet_handle,
+            &my_vk,
+            &RecordOptions::id_value(),
+        )?;
+
+        let mut protected = options.protected.clone().unwrap_or_default();
+
+        if let Some(header) = JWS_RESERVED_HEADERS.iter().find(|header| protected.contains_key(**header)) {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Protected header parameter \"{}\" can't be set", header)));
+        }
+
+        let kid = match options.kid {
+            JwsKidFormat::Verkey => key.verkey.clone(),
+            JwsKidFormat::DidKey => {
+                let did_key = verkey_to_did_key(&key.verkey)?;
+                format!("{}#{}", did_key, &did_key[DID_KEY_PREFIX.len()..])
+            }
+        };
+
+        protected.insert("alg".to_string(), Value::from(self.crypto_service.jws_alg(&key.verkey)?));
+        protected.insert("kid".to_string(), Value::from(kid));
+
+        let protected = serde_json::to_string(&protected)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize JWS protected header")?;
+        let protected = base64::encode_urlsafe_no_pad(protected.as_bytes());
+        let payload = base64::encode_urlsafe_no_pad(payload);
+
+        let signature = self.crypto_service.sign(&key, format!("{}.{}", protected, payload).as_bytes())?;
+        let signature = base64::encode_urlsafe_no_pad(&signature);
+
+        let payload = if options.detached { None } else { Some(payload) };
+
+        let res = match options.serialization {
+            JwsSerialization::Compact =>
+                format!("{}.{}.{}", protected, payload.unwrap_or_default(), signature),
+            JwsSerialization::Json => {
+                let jws = JwsJson {
+                    payload,
+                    signatures: vec![JwsSignature { protected, header: None, signature }],
+                };
+
+                serde_json::to_string(&jws)
+                    .to_indy(IndyErrorKind::InvalidState, "Can't serialize JWS")?
+            }
+        };
+
+        trace!("sign_jws <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn verify_jws(&self, jws: &str, detached_payload: Option<&[u8]>) -> IndyResult<bool> {
+        trace!("verify_jws >>> jws: {:?}, detached_payload: {:?}", jws, detached_payload);
+
+        let jws = self._parse_jws(jws)?;
+
+        let payload = match (jws.payload.filter(|payload| !payload.is_empty()), detached_payload) {
+            (Some(payload), None) => payload,
+            (None, Some(payload)) => base64::encode_urlsafe_no_pad(payload),
+            (Some(_), Some(_)) =>
+                return Err(err_msg(IndyErrorKind::InvalidStructure, "JWS contains payload, but detached payload is passed")),
+            (None, None) =>
+                return Err(err_msg(IndyErrorKind::InvalidStructure, "JWS payload is detached, but it isn't passed")),
+        };
+
+        if jws.signatures.is_empty() {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "JWS doesn't contain signatures"));
+        }
+
+        let mut res = true;
+
+        for signature in jws.signatures {
+            let protected = base64::decode_urlsafe(&signature.protected)?;
+            let protected: Map<String, Value> = serde_json::from_slice(&protected)
+                .to_indy(IndyErrorKind::InvalidStructure, "Invalid JWS protected header")?;
+
+            if protected.contains_key("crit") {
+                return Err(err_msg(IndyErrorKind::InvalidStructure, "JWS critical header parameters aren't supported"));
+            }
+
+            let alg = protected.get("alg")
+                .and_then(Value::as_str)
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "JWS protected header doesn't contain alg"))?;
+
+            let kid = protected.get("kid")
+                .or_else(|| signature.header.as_ref().and_then(|header| header.get("kid")))
+                .and_then(Value::as_str)
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "JWS header doesn't contain kid"))?;
+
+            let their_vk = if kid.starts_with(DID_KEY_PREFIX) {
+                did_key_to_verkey(kid)?
+            } else {
+                kid.to_string()
+            };
+
+            self.crypto_service.validate_key(&their_vk)?;
+
+            if alg != self.crypto_service.jws_alg(&their_vk)? {
+                return Err(err_msg(IndyErrorKind::InvalidStructure, format!("JWS alg {} doesn't match the key {}", alg, kid)));
+            }
+
+            let signing_input = format!("{}.{}", signature.protected, payload);
+            let signature = base64::decode_urlsafe(&signature.signature)?;
+
+            res = res && self.crypto_service.verify(&their_vk, signing_input.as_bytes(), &signature)?;
+        }
+
+        trace!("verify_jws <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn _parse_jws(&self, jws: &str) -> IndyResult<JwsJson> {
+        let jws = jws.trim();
+
+        if jws.starts_with('{') {
+            let jws: Value = serde_json::from_str(jws)
+                .to_indy(IndyErrorKind::InvalidStructure, "Invalid JWS JSON serialization")?;
+
+            return if jws.get("signatures").is_some() {
+                serde_json::from_value::<JwsJson>(jws)
+            } else {
+                serde_json::from_value::<FlattenedJwsJson>(jws).map(JwsJson::from)
+            }.to_indy(IndyErrorKind::InvalidStructure, "Invalid JWS JSON serialization");
+        }
+
+        let parts: Vec<&str> = jws.split('.').collect();
+
+        if parts.len() != 3 {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Invalid JWS compact serialization"));
+        }
+
+        Ok(JwsJson {
+            payload: Some(parts[1].to_string()),
+            signatures: vec![JwsSignature {
+                protected: parts[0].to_string(),
+                header: None,
+                signature: parts[2].to_string(),
+            }],
+        })
+    }
+
     //TODO begin deprecation process this function. It will be replaced by pack
     fn authenticated_encrypt(
         &self,
@@ -316,6 +637,300 @@ impl CryptoCommandExecutor {
         Ok(res)
     }
 
+    fn session_start(&self,
+                     wallet_handle: WalletHandle,
+                     my_vk: &str,
+                     their_did: &DidValue,
+                     their_handshake: Option<SessionHandshake>) -> IndyResult<(String, Option<String>)> {
+        trace!("session_start >>> wallet_handle: {:?}, my_vk: {:?}, their_did: {:?}, their_handshake: {:?}", wallet_handle, my_vk, their_did, their_handshake);
+
+        self.crypto_service.validate_key(my_vk)?;
+
+        let my_key: Key = self.wallet_service.get_indy_object(wallet_handle, my_vk, &RecordOptions::id_value())?;
+
+        let their_vk = self._their_did_verkey(wallet_handle, their_did)?;
+
+        let (session_id, handshake) = match their_handshake {
+            None => {
+                let (session_id, handshake) = self._session_initiate(wallet_handle, &my_key, their_did, &their_vk)?;
+                (session_id, Some(handshake))
+            }
+            Some(their_handshake) => {
+                if their_handshake.sender_vk != their_vk {
+                    return Err(err_msg(IndyErrorKind::InvalidStructure, "Session handshake isn't sent by the key of their DID"));
+                }
+
+                match self.wallet_service.get_indy_object::<CryptoSession>(wallet_handle, &their_handshake.session_id, &RecordOptions::id_value()) {
+                    Ok(session) => {
+                        self._session_complete(wallet_handle, &my_key, their_did, session, &their_handshake)?;
+                        (their_handshake.session_id, None)
+                    }
+                    Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound => {
+                        let handshake = self._session_respond(wallet_handle, &my_key, their_did, &their_vk, &their_handshake)?;
+                        (their_handshake.session_id, Some(handshake))
+                    }
+                    Err(err) => return Err(err)
+                }
+            }
+        };
+
+        let handshake = handshake
+            .map(|handshake| serde_json::to_string(&handshake))
+            .transpose()
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize SessionHandshake")?;
+
+        let res = (session_id, handshake);
+
+        trace!("session_start <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn _session_initiate(&self,
+                         wallet_handle: WalletHandle,
+                         my_key: &Key,
+                         their_did: &DidValue,
+                         their_vk: &str) -> IndyResult<(String, SessionHandshake)> {
+        let session_id = randombytes::randombytes(16).to_base58();
+
+        let (ephemeral_pk, ephemeral_sk) = ed25519_box::gen_keypair();
+        let ephemeral_pk = ephemeral_pk[..].to_base58();
+
+        let handshake = self._session_handshake(my_key, &session_id, &ephemeral_pk, their_vk, None)?;
+
+        let session = CryptoSession {
+            my_vk: my_key.verkey.clone(),
+            their_did: their_did.clone(),
+            their_vk: their_vk.to_string(),
+            initiator: true,
+            state: SessionState::Pending {
+                ephemeral_sk: ephemeral_sk[..].to_base58(),
+                ephemeral_pk,
+            },
+        };
+
+        self.wallet_service.add_indy_object(wallet_handle, &session_id, &session, &HashMap::new())?;
+
+        Ok((session_id, handshake))
+    }
+
+    fn _session_respond(&self,
+                        wallet_handle: WalletHandle,
+                        my_key: &Key,
+                        their_did: &DidValue,
+                        their_vk: &str,
+                        their_handshake: &SessionHandshake) -> IndyResult<SessionHandshake> {
+        self._verify_session_handshake(their_handshake, &my_key.verkey, None)?;
+
+        let (ephemeral_pk, ephemeral_sk) = ed25519_box::gen_keypair();
+        let their_ephemeral_pk = ed25519_box::PublicKey::from_slice(&their_handshake.ephemeral_key.from_base58()?)?;
+
+        let root_key = self.crypto_service.session_root_key(&ephemeral_sk, &their_ephemeral_pk, &their_handshake.session_id)?;
+        let (initiator_chain_key, responder_chain_key) = self.crypto_service.session_chain_keys(&root_key)?;
+
+        let handshake = self._session_handshake(my_key,
+                                                &their_handshake.session_id,
+                                                &ephemeral_pk[..].to_base58(),
+                                                their_vk,
+                                                Some(&their_handshake.ephemeral_key))?;
+
+        let session = CryptoSession {
+            my_vk: my_key.verkey.clone(),
+            their_did: their_did.clone(),
+            their_vk: their_vk.to_string(),
+            initiator: false,
+            state: SessionState::Established {
+                send_chain_key: responder_chain_key.to_base58(),
+                send_counter: 0,
+                recv_chain_key: initiator_chain_key.to_base58(),
+                recv_counter: 0,
+                skipped_keys: BTreeMap::new(),
+            },
+        };
+
+        self.wallet_service.add_indy_object(wallet_handle, &their_handshake.session_id, &session, &HashMap::new())?;
+
+        Ok(handshake)
+    }
+
+    fn _session_complete(&self,
+                         wallet_handle: WalletHandle,
+                         my_key: &Key,
+                         their_did: &DidValue,
+                         mut session: CryptoSession,
+                         their_handshake: &SessionHandshake) -> IndyResult<()> {
+        if session.my_vk != my_key.verkey || session.their_did != *their_did {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Session handshake doesn't match the session"));
+        }
+
+        let (initiator_chain_key, responder_chain_key) = match session.state {
+            SessionState::Pending { ref ephemeral_sk, ref ephemeral_pk } if session.initiator => {
+                self._verify_session_handshake(their_handshake, &my_key.verkey, Some(ephemeral_pk.as_str()))?;
+
+                let ephemeral_sk = ed25519_box::SecretKey::from_slice(&ephemeral_sk.from_base58()?)?;
+                let their_ephemeral_pk = ed25519_box::PublicKey::from_slice(&their_handshake.ephemeral_key.from_base58()?)?;
+
+                let root_key = self.crypto_service.session_root_key(&ephemeral_sk, &their_ephemeral_pk, &their_handshake.session_id)?;
+                self.crypto_service.session_chain_keys(&root_key)?
+            }
+            _ => return Err(err_msg(IndyErrorKind::InvalidState, "Session is already established"))
+        };
+
+        // Ephemeral secret key is dropped with the pending state
+        session.state = SessionState::Established {
+            send_chain_key: initiator_chain_key.to_base58(),
+            send_counter: 0,
+            recv_chain_key: responder_chain_key.to_base58(),
+            recv_counter: 0,
+            skipped_keys: BTreeMap::new(),
+        };
+
+        self.wallet_service.update_indy_object(wallet_handle, &their_handshake.session_id, &session)?;
+
+        Ok(())
+    }
+
+    fn _session_handshake(&self,
+                          my_key: &Key,
+                          session_id: &str,
+                          ephemeral_key: &str,
+                          their_vk: &str,
+                          their_ephemeral_key: Option<&str>) -> IndyResult<SessionHandshake> {
+        let signed_data = SessionHandshake::signed_data(session_id, ephemeral_key, their_vk, their_ephemeral_key);
+        let signature = self.crypto_service.sign(my_key, &signed_data)?;
+
+        Ok(SessionHandshake {
+            session_id: session_id.to_string(),
+            sender_vk: my_key.verkey.clone(),
+            ephemeral_key: ephemeral_key.to_string(),
+            signature: signature.to_base58(),
+        })
+    }
+
+    fn _verify_session_handshake(&self, handshake: &SessionHandshake, my_vk: &str, my_ephemeral_key: Option<&str>) -> IndyResult<()> {
+        let signed_data = SessionHandshake::signed_data(&handshake.session_id, &handshake.ephemeral_key, my_vk, my_ephemeral_key);
+
+        if !self.crypto_service.verify(&handshake.sender_vk, &signed_data, &handshake.signature.from_base58()?)? {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Session handshake signature is invalid"));
+        }
+
+        Ok(())
+    }
+
+    fn _their_did_verkey(&self, wallet_handle: WalletHandle, their_did: &DidValue) -> IndyResult<String> {
+        match self.wallet_service.get_indy_object::<TheirDid>(wallet_handle, &their_did.0, &RecordOptions::id_value()) {
+            Ok(their_did) => Ok(their_did.verkey),
+            Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound && their_did.is_self_certifying() =>
+                self.crypto_service.verkey_for_self_certifying_did(their_did),
+            Err(err) => Err(err)
+        }
+    }
+
+    fn session_encrypt(&self,
+                       wallet_handle: WalletHandle,
+                       session_id: &str,
+                       msg: &[u8]) -> IndyResult<Vec<u8>> {
+        trace!("session_encrypt >>> wallet_handle: {:?}, session_id: {:?}, msg: {:?}", wallet_handle, session_id, msg);
+
+        let mut session: CryptoSession = self.wallet_service.get_indy_object(wallet_handle, session_id, &RecordOptions::id_value())?;
+
+        let (message_key, counter) = match session.state {
+            SessionState::Established { ref mut send_chain_key, ref mut send_counter, .. } => {
+                let (message_key, next_chain_key) = self.crypto_service.session_ratchet(&send_chain_key.from_base58()?)?;
+
+                let counter = *send_counter;
+
+                *send_chain_key = next_chain_key.to_base58();
+                *send_counter += 1;
+
+                (message_key, counter)
+            }
+            SessionState::Pending { .. } => return Err(err_msg(IndyErrorKind::InvalidState, "Session isn't established yet"))
+        };
+
+        let (ciphertext, iv, tag) =
+            self.crypto_service.encrypt_detached(ContentEncryption::XC20P, msg, &SessionMessage::aad(session_id, counter), &message_key)?;
+
+        // Ratcheted state is stored before the message is returned, so message keys are never reused
+        self.wallet_service.update_indy_object(wallet_handle, session_id, &session)?;
+
+        let message = SessionMessage {
+            session_id: session_id.to_string(),
+            counter,
+            iv: base64::encode_urlsafe(&iv),
+            ciphertext: base64::encode_urlsafe(&ciphertext),
+            tag: base64::encode_urlsafe(&tag),
+        };
+
+        let res = serde_json::to_vec(&message)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize SessionMessage")?;
+
+        trace!("session_encrypt <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn session_decrypt(&self,
+                       wallet_handle: WalletHandle,
+                       encrypted_msg: &[u8]) -> IndyResult<Vec<u8>> {
+        trace!("session_decrypt >>> wallet_handle: {:?}, encrypted_msg: {:?}", wallet_handle, encrypted_msg);
+
+        let message: SessionMessage = serde_json::from_slice(encrypted_msg)
+            .to_indy(IndyErrorKind::InvalidStructure, "Invalid session message")?;
+
+        let mut session: CryptoSession = self.wallet_service.get_indy_object(wallet_handle, &message.session_id, &RecordOptions::id_value())?;
+
+        let message_key = match session.state {
+            SessionState::Established { ref mut recv_chain_key, ref mut recv_counter, ref mut skipped_keys, .. } => {
+                if message.counter < *recv_counter {
+                    skipped_keys.remove(&message.counter)
+                        .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Session message is already received or too old"))?
+                        .from_base58()?
+                } else {
+                    if message.counter - *recv_counter > MAX_SKIPPED_MESSAGE_KEYS {
+                        return Err(err_msg(IndyErrorKind::InvalidStructure, "Too many session messages are skipped"));
+                    }
+
+                    let mut chain_key = recv_chain_key.from_base58()?;
+
+                    while *recv_counter < message.counter {
+                        let (skipped_key, next_chain_key) = self.crypto_service.session_ratchet(&chain_key)?;
+                        skipped_keys.insert(*recv_counter, skipped_key.to_base58());
+                        chain_key = next_chain_key;
+                        *recv_counter += 1;
+                    }
+
+                    let (message_key, next_chain_key) = self.crypto_service.session_ratchet(&chain_key)?;
+
+                    *recv_chain_key = next_chain_key.to_base58();
+                    *recv_counter += 1;
+
+                    while skipped_keys.len() as u64 > MAX_SKIPPED_MESSAGE_KEYS {
+                        let oldest = *skipped_keys.keys().next().unwrap();
+                        skipped_keys.remove(&oldest);
+                    }
+
+                    message_key
+                }
+            }
+            SessionState::Pending { .. } => return Err(err_msg(IndyErrorKind::InvalidState, "Session isn't established yet"))
+        };
+
+        let res = self.crypto_service.decrypt_detached(ContentEncryption::XC20P,
+                                                       &base64::decode_urlsafe(&message.ciphertext)?,
+                                                       &SessionMessage::aad(&message.session_id, message.counter),
+                                                       &message_key,
+                                                       &base64::decode_urlsafe(&message.iv)?,
+                                                       &base64::decode_urlsafe(&message.tag)?)?;
+
+        // Session state is changed only by authentic messages
+        self.wallet_service.update_indy_object(wallet_handle, &message.session_id, &session)?;
+
+        trace!("session_decrypt <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
     fn set_key_metadata(&self, wallet_handle: WalletHandle, verkey: &str, metadata: &str) -> IndyResult<()> {
         debug!(
             "set_key_metadata >>> wallet_handle: {:?}, verkey: {:?}, metadata: {:?}",
@@ -364,6 +979,7 @@ impl CryptoCommandExecutor {
         message: Vec<u8>,
         receiver_list: Vec<String>,
         sender_vk: Option<String>,
+        options: PackOptions,
         wallet_handle: WalletHandle,
     ) -> IndyResult<Vec<u8>> {
 
@@ -372,6 +988,10 @@ impl CryptoCommandExecutor {
             return Err(err_msg(IndyErrorKind::InvalidStructure, "No receiver keys found".to_string()));
         }
 
+        if options.profile == PackProfile::Ecdh {
+            return self._pack_msg_ecdh(message, receiver_list, sender_vk, options.enc, wallet_handle);
+        }
+
         //generate content encryption key that will encrypt `message`
         let cek = chacha20poly1305_ietf::gen_key();
 
@@ -390,7 +1010,112 @@ impl CryptoCommandExecutor {
             self.crypto_service
                 .encrypt_plaintext(message, &base64_protected, &cek);
 
-        self._format_pack_message(&base64_protected, &ciphertext, &iv, &tag)
+        self._format_pack_message(&base64_protected, None, &ciphertext, &iv, &tag)
+    }
+
+    // JWE with X25519 key agreement: ECDH-1PU+A256KW if sender is set and ECDH-ES+A256KW otherwise.
+    // Sender verkey is passed in "skid" header, so recipients and intermediaries can see it.
+    fn _pack_msg_ecdh(
+        &self,
+        message: Vec<u8>,
+        receiver_list: Vec<String>,
+        sender_vk: Option<String>,
+        enc: ContentEncryption,
+        wallet_handle: WalletHandle,
+    ) -> IndyResult<Vec<u8>> {
+        let receiver_pks = receiver_list
+            .iter()
+            .map(|their_vk| self.crypto_service.x25519_public_key(their_vk))
+            .collect::<IndyResult<Vec<ed25519_box::PublicKey>>>()?;
+
+        let sender_key: Option<Key> = match sender_vk {
+            Some(ref sender_vk) => {
+                self.crypto_service.validate_key(sender_vk)?;
+                Some(self.wallet_service.get_indy_object(wallet_handle, sender_vk, &RecordOptions::id_value())?)
+            }
+            None => None
+        };
+
+        let alg = if sender_key.is_some() { ECDH_PROTECTED_HEADER_ALG_1PU } else { ECDH_PROTECTED_HEADER_ALG_ES };
+
+        let (epk, esk) = ed25519_box::gen_keypair();
+
+        let protected = EcdhProtected {
+            typ: ECDH_PROTECTED_HEADER_TYP.to_string(),
+            alg: alg.to_string(),
+            enc,
+            epk: EphemeralPublicKey {
+                kty: ECDH_EPK_KTY.to_string(),
+                crv: ECDH_EPK_CRV.to_string(),
+                x: base64::encode_urlsafe_no_pad(&epk[..]),
+            },
+            apu: sender_vk.as_ref().map(|sender_vk| base64::encode_urlsafe_no_pad(sender_vk.as_bytes())),
+            skid: sender_vk,
+            apv: base64::encode_urlsafe_no_pad(&self._ecdh_apv(&receiver_list)?),
+        };
+
+        let protected_encoded = serde_json::to_string(&protected).map_err(|err| {
+            err_msg(IndyErrorKind::InvalidStructure, format!(
+                "Failed to serialize protected field {}",
+                err
+            ))
+        })?;
+        let base64_protected = base64::encode_urlsafe_no_pad(protected_encoded.as_bytes());
+
+        // ECDH-1PU key wrapping depends on the tag, so content is encrypted first
+        let cek = self.crypto_service.gen_content_encryption_key(enc)?;
+        let (ciphertext, iv, tag) =
+            self.crypto_service
+                .encrypt_detached(enc, &message, base64_protected.as_bytes(), &cek)?;
+
+        let mut recipients: Vec<Recipient> = Vec::with_capacity(receiver_list.len());
+
+        for (their_vk, their_pk) in receiver_list.into_iter().zip(receiver_pks) {
+            let mut z = ed25519_box::ecdh(&esk, &their_pk)?;
+
+            let cc_tag = match sender_key {
+                Some(ref sender_key) => {
+                    z.extend(self.crypto_service.x25519_key_agreement(sender_key, &their_pk)?);
+                    Some(tag.as_slice())
+                }
+                None => None
+            };
+
+            let kek = self._ecdh_kek(&protected, &z, cc_tag)?;
+            let encrypted_key = aes256kw::wrap(&kek, &cek)?;
+
+            recipients.push(Recipient {
+                encrypted_key: base64::encode_urlsafe_no_pad(&encrypted_key),
+                header: Header {
+                    kid: their_vk,
+                    sender: None,
+                    iv: None
+                },
+            });
+        }
+
+        self._format_pack_message(&base64_protected,
+                                  Some(recipients),
+                                  &base64::encode_urlsafe_no_pad(&ciphertext),
+                                  &base64::encode_urlsafe_no_pad(&iv),
+                                  &base64::encode_urlsafe_no_pad(&tag))
+    }
+
+    // The same as DIDComm v2 uses: SHA-256 of sorted recipient key ids joined with "."
+    fn _ecdh_apv(&self, receiver_list: &[String]) -> IndyResult<Vec<u8>> {
+        let mut kids = receiver_list.to_vec();
+        kids.sort();
+        hash::hash(kids.join(".").as_bytes())
+    }
+
+    fn _ecdh_kek(&self, protected: &EcdhProtected, z: &[u8], cc_tag: Option<&[u8]>) -> IndyResult<Vec<u8>> {
+        let apu = match protected.apu {
+            Some(ref apu) => base64::decode_urlsafe(apu)?,
+            None => Vec::new()
+        };
+        let apv = base64::decode_urlsafe(&protected.apv)?;
+
+        self.crypto_service.concat_kdf(z, &protected.alg, &apu, &apv, cc_tag)
     }
 
     fn _prepare_protected_anoncrypt(&self,
@@ -473,6 +1198,7 @@ impl CryptoCommandExecutor {
     fn _format_pack_message(
         &self,
         base64_protected: &str,
+        recipients: Option<Vec<Recipient>>,
         ciphertext: &str,
         iv: &str,
         tag: &str
@@ -481,6 +1207,7 @@ impl CryptoCommandExecutor {
         //serialize pack message and return as vector of bytes
         let jwe_struct = JWE {
             protected: base64_protected.to_string(),
+            recipients,
             iv: iv.to_string(),
             ciphertext: ciphertext.to_string(),
             tag: tag.to_string()
@@ -503,6 +1230,19 @@ impl CryptoCommandExecutor {
                 err
             ))
         })?;
+
+        //JWE with ECDH key agreement keeps per-recipient data outside of protected header
+        if jwe_struct.recipients.is_some() {
+            let protected_struct: EcdhProtected = serde_json::from_str(&protected_decoded_str).map_err(|err| {
+                err_msg(IndyErrorKind::InvalidStructure, format!(
+                    "Failed to deserialize protected data {}",
+                    err
+                ))
+            })?;
+
+            return self._unpack_msg_ecdh(jwe_struct, protected_struct, wallet_handle);
+        }
+
         //convert protected_data_str to struct
         let protected_struct: Protected = serde_json::from_str(&protected_decoded_str).map_err(|err| {
             err_msg(IndyErrorKind::InvalidStructure, format!(
@@ -512,7 +1252,7 @@ impl CryptoCommandExecutor {
         })?;
 
         //extract recipient that matches a key in the wallet
-        let (recipient, is_auth_recipient) = self._find_correct_recipient(protected_struct, wallet_handle)?;
+        let (recipient, is_auth_recipient) = self._find_correct_recipient(protected_struct.recipients, wallet_handle)?;
 
         //get cek and sender data
         let (sender_verkey_option, cek) = if is_auth_recipient {
@@ -545,8 +1285,82 @@ impl CryptoCommandExecutor {
         })
     }
 
-    fn _find_correct_recipient(&self, protected_struct: Protected, wallet_handle: WalletHandle) -> IndyResult<(Recipient, bool)>{
-        for recipient in protected_struct.recipients {
+    fn _unpack_msg_ecdh(&self, jwe_struct: JWE, protected_struct: EcdhProtected, wallet_handle: WalletHandle) -> IndyResult<Vec<u8>> {
+        if protected_struct.epk.kty != ECDH_EPK_KTY || protected_struct.epk.crv != ECDH_EPK_CRV {
+            return Err(err_msg(IndyErrorKind::UnknownCrypto, format!(
+                "Unsupported ephemeral key type: {} {}",
+                protected_struct.epk.kty,
+                protected_struct.epk.crv
+            )));
+        }
+
+        let recipients = jwe_struct.recipients.clone().unwrap_or_default();
+
+        //extract recipient that matches a key in the wallet
+        let (recipient, _) = self._find_correct_recipient(recipients, wallet_handle)?;
+
+        //get my private key
+        let my_key: Key = self.wallet_service.get_indy_object(
+            wallet_handle,
+            &recipient.header.kid,
+            &RecordOptions::id_value(),
+        )?;
+
+        let epk = ed25519_box::PublicKey::from_slice(&base64::decode_urlsafe(&protected_struct.epk.x)?)?;
+        let tag = base64::decode_urlsafe(&jwe_struct.tag)?;
+
+        let mut z = self.crypto_service.x25519_key_agreement(&my_key, &epk)?;
+
+        let (sender_verkey, cc_tag) = match protected_struct.alg.as_str() {
+            ECDH_PROTECTED_HEADER_ALG_ES => (None, None),
+            ECDH_PROTECTED_HEADER_ALG_1PU => {
+                let sender_vk = protected_struct.skid.clone()
+                    .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Sender key id isn't set for ECDH-1PU"))?;
+
+                let sender_pk = self.crypto_service.x25519_public_key(&sender_vk)?;
+                z.extend(self.crypto_service.x25519_key_agreement(&my_key, &sender_pk)?);
+
+                (Some(sender_vk), Some(tag.as_slice()))
+            }
+            alg => return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("Unsupported key management algorithm: {}", alg)))
+        };
+
+        //decrypt cek
+        let kek = self._ecdh_kek(&protected_struct, &z, cc_tag)?;
+        let cek = aes256kw::unwrap(&kek, &base64::decode_urlsafe(&recipient.encrypted_key)?)
+            .map_err(|err| err.extend("Failed to decrypt cek"))?;
+
+        //decrypt message
+        let message = self.crypto_service.decrypt_detached(
+            protected_struct.enc,
+            &base64::decode_urlsafe(&jwe_struct.ciphertext)?,
+            jwe_struct.protected.as_bytes(),
+            &cek,
+            &base64::decode_urlsafe(&jwe_struct.iv)?,
+            &tag,
+        ).map_err(|err| err.extend("Failed to decrypt ciphertext"))?;
+
+        let message = String::from_utf8(message).map_err(|err| {
+            err_msg(IndyErrorKind::InvalidStructure, format!("Failed to convert message to UTF-8 {}", err))
+        })?;
+
+        //serialize and return decrypted message
+        let res = UnpackMessage {
+            message,
+            sender_verkey,
+            recipient_verkey: recipient.header.kid
+        };
+
+        serde_json::to_vec(&res).map_err(|err| {
+            err_msg(IndyErrorKind::InvalidStructure, format!(
+                "Failed to serialize message {}",
+                err
+            ))
+        })
+    }
+
+    fn _find_correct_recipient(&self, recipients: Vec<Recipient>, wallet_handle: WalletHandle) -> IndyResult<(Recipient, bool)>{
+        for recipient in recipients {
             let my_key_res = self.wallet_service.get_indy_object::<Key>(
                 wallet_handle,
                 &recipient.header.kid,
@@ -1,21 +1,23 @@
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use serde_json;
 
 use crate::commands::{Command, CommandExecutor, BoxedCallbackStringStringSend};
 use crate::commands::ledger::LedgerCommand;
-use crate::domain::crypto::did::{Did, DidValue, DidMetadata, DidWithMeta, MyDidInfo, TemporaryDid, TheirDid, TheirDidInfo, DidMethod};
-use crate::domain::crypto::key::KeyInfo;
+use crate::domain::crypto::did::{Did, DidValue, DidMetadata, DidWithMeta, MyDidInfo, TemporaryDid, TheirDid, TheirDidInfo, DidMethod, DidKeyHistory, DidKeyHistoryEntry};
+use crate::domain::crypto::key::{Key, KeyInfo};
 use crate::domain::ledger::attrib::{AttribData, Endpoint, GetAttrReplyResult};
 use crate::domain::ledger::nym::{GetNymReplyResult, GetNymResultDataV0};
 use crate::domain::ledger::response::Reply;
 use crate::domain::pairwise::Pairwise;
 use indy_api_types::errors::prelude::*;
 use crate::services::crypto::CryptoService;
+use crate::services::did_doc::DidDocService;
 use crate::services::ledger::LedgerService;
-use indy_wallet::{RecordOptions, SearchOptions, WalletService};
+use indy_wallet::{RecordOptions, SearchOptions, WalletRecordOperation, WalletService};
 use indy_api_types::{WalletHandle, PoolHandle, CommandHandle};
 use indy_utils::next_command_handle;
 use rust_base58::{FromBase58, ToBase58};
@@ -34,6 +36,21 @@ pub enum DidCommand {
         WalletHandle,
         DidValue, // my did
         Box<dyn Fn(IndyResult<()>) + Send>),
+    ReplaceKeysRollback(
+        WalletHandle,
+        DidValue, // my did
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    GetDidKeyHistory(
+        WalletHandle,
+        DidValue, // my did
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    VerifyWithDidKeyHistory(
+        WalletHandle,
+        DidValue, // my did
+        Vec<u8>, // msg
+        Vec<u8>, // signature
+        Option<u64>, // signed at
+        Box<dyn Fn(IndyResult<bool>) + Send>),
     StoreTheirDid(
         WalletHandle,
         TheirDidInfo, // their did info json
@@ -77,6 +94,10 @@ pub enum DidCommand {
         DidValue, // did
         String, // verkey
         Box<dyn Fn(IndyResult<String>) + Send>),
+    BuildDidDoc(
+        WalletHandle,
+        DidValue, // did
+        Box<dyn Fn(IndyResult<String>) + Send>),
     // Internal commands
     GetNymAck(
         WalletHandle,
@@ -113,17 +134,20 @@ pub struct DidCommandExecutor {
     wallet_service: Rc<WalletService>,
     crypto_service: Rc<CryptoService>,
     ledger_service: Rc<LedgerService>,
+    did_doc_service: Rc<DidDocService>,
     deferred_commands: RefCell<HashMap<CommandHandle, DidCommand>>,
 }
 
 impl DidCommandExecutor {
     pub fn new(wallet_service: Rc<WalletService>,
                crypto_service: Rc<CryptoService>,
-               ledger_service: Rc<LedgerService>) -> DidCommandExecutor {
+               ledger_service: Rc<LedgerService>,
+               did_doc_service: Rc<DidDocService>) -> DidCommandExecutor {
         DidCommandExecutor {
             wallet_service,
             crypto_service,
             ledger_service,
+            did_doc_service,
             deferred_commands: RefCell::new(HashMap::new()),
         }
     }
@@ -142,6 +166,18 @@ impl DidCommandExecutor {
                 debug!("ReplaceKeysApply command received");
                 cb(self.replace_keys_apply(wallet_handle, &did));
             }
+            DidCommand::ReplaceKeysRollback(wallet_handle, did, cb) => {
+                debug!("ReplaceKeysRollback command received");
+                cb(self.replace_keys_rollback(wallet_handle, &did));
+            }
+            DidCommand::GetDidKeyHistory(wallet_handle, did, cb) => {
+                debug!("GetDidKeyHistory command received");
+                cb(self.get_did_key_history(wallet_handle, &did));
+            }
+            DidCommand::VerifyWithDidKeyHistory(wallet_handle, did, msg, signature, signed_at, cb) => {
+                debug!("VerifyWithDidKeyHistory command received");
+                cb(self.verify_with_did_key_history(wallet_handle, &did, &msg, &signature, signed_at));
+            }
             DidCommand::StoreTheirDid(wallet_handle, their_did_info, cb) => {
                 debug!("StoreTheirDid command received");
                 cb(self.store_their_did(wallet_handle, &their_did_info));
@@ -182,6 +218,10 @@ impl DidCommandExecutor {
                 debug!("AbbreviateVerkey command received");
                 cb(self.abbreviate_verkey(&did, verkey));
             }
+            DidCommand::BuildDidDoc(wallet_handle, did, cb) => {
+                debug!("BuildDidDoc command received");
+                cb(self.build_did_doc(wallet_handle, &did));
+            }
             DidCommand::GetNymAck(wallet_handle, did, result, deferred_cmd_id) => {
                 debug!("GetNymAck command received");
                 self.get_nym_ack(wallet_handle, did, result, deferred_cmd_id);
@@ -231,6 +271,11 @@ impl DidCommandExecutor {
 
         self.crypto_service.validate_did(my_did)?;
 
+        // did:key and did:peer are derived from the key, so the key can't be replaced
+        if my_did.is_self_certifying() {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Keys of self-certifying DID \"{}\" can't be replaced", my_did.0)));
+        }
+
         let my_did = self._wallet_get_my_did(wallet_handle, my_did)?;
 
         let temporary_key = self.crypto_service.create_key(&key_info)?;
@@ -253,20 +298,151 @@ impl DidCommandExecutor {
 
         self.crypto_service.validate_did(my_did)?;
 
+        // did:key and did:peer are derived from the key, so the key can't be replaced
+        if my_did.is_self_certifying() {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Keys of self-certifying DID \"{}\" can't be replaced", my_did.0)));
+        }
+
         let my_did = self._wallet_get_my_did(wallet_handle, my_did)?;
         let my_temporary_did: TemporaryDid =
             self.wallet_service.get_indy_object(wallet_handle, &my_did.did.0, &RecordOptions::id_value())?;
 
+        let mut key_history = self._wallet_get_did_key_history(wallet_handle, &my_did.did)?;
+        key_history.rolled_back = false;
+
+        let archive_operation = self._archive_did_key_operation(wallet_handle, &my_did, key_history)?;
+
         let my_did = Did::from(my_temporary_did);
 
-        self.wallet_service.update_indy_object(wallet_handle, &my_did.did.0, &my_did)?;
-        self.wallet_service.delete_indy_record::<TemporaryDid>(wallet_handle, &my_did.did.0)?;
+        self.wallet_service.batch_records(wallet_handle, &[
+            archive_operation,
+            self.wallet_service.update_indy_object_operation(&my_did.did.0, &my_did)?,
+            self.wallet_service.delete_indy_object_operation::<TemporaryDid>(&my_did.did.0),
+        ])?;
 
         debug!("replace_keys_apply <<<");
 
         Ok(())
     }
 
+    fn replace_keys_rollback(&self,
+                             wallet_handle: WalletHandle,
+                             my_did: &DidValue) -> IndyResult<String> {
+        debug!("replace_keys_rollback >>> wallet_handle: {:?}, my_did: {:?}", wallet_handle, my_did);
+
+        self.crypto_service.validate_did(my_did)?;
+
+        let my_did = self._wallet_get_my_did(wallet_handle, my_did)?;
+
+        let mut key_history = self._wallet_get_did_key_history(wallet_handle, &my_did.did)?;
+
+        // The last entry is the key replaced by the last apply, after a rollback it is the key just rolled back from
+        if key_history.rolled_back {
+            return Err(err_msg(IndyErrorKind::InvalidState, format!("The last key change of DID \"{}\" is already rolled back", my_did.did.0)));
+        }
+
+        // History entries are kept, so signatures made by the restored key in the past are still matched by their time
+        let previous_verkey = key_history
+            .keys
+            .last()
+            .map(|entry| entry.verkey.clone())
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, format!("DID \"{}\" doesn't have previous keys", my_did.did.0)))?;
+
+        // Signing key must still be in the wallet to use it again
+        self.wallet_service.get_indy_object::<Key>(wallet_handle, &previous_verkey, &RecordOptions::id_value())?;
+
+        key_history.rolled_back = true;
+
+        let mut operations = vec![self._archive_did_key_operation(wallet_handle, &my_did, key_history)?];
+
+        // Replacement started for the current key can't be applied after the rollback
+        if self.wallet_service.record_exists::<TemporaryDid>(wallet_handle, &my_did.did.0)? {
+            operations.push(self.wallet_service.delete_indy_object_operation::<TemporaryDid>(&my_did.did.0));
+        }
+
+        let my_did = Did::new(my_did.did, previous_verkey);
+
+        operations.push(self.wallet_service.update_indy_object_operation(&my_did.did.0, &my_did)?);
+
+        self.wallet_service.batch_records(wallet_handle, &operations)?;
+
+        let res = my_did.verkey;
+
+        debug!("replace_keys_rollback <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn get_did_key_history(&self,
+                           wallet_handle: WalletHandle,
+                           my_did: &DidValue) -> IndyResult<String> {
+        debug!("get_did_key_history >>> wallet_handle: {:?}, my_did: {:?}", wallet_handle, my_did);
+
+        self.crypto_service.validate_did(my_did)?;
+
+        let my_did = self._wallet_get_my_did(wallet_handle, my_did)?;
+
+        let key_history = self._wallet_get_did_key_history(wallet_handle, &my_did.did)?;
+
+        let res = serde_json::to_string(&key_history.keys)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize DID key history")?;
+
+        debug!("get_did_key_history <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn verify_with_did_key_history(&self,
+                                   wallet_handle: WalletHandle,
+                                   my_did: &DidValue,
+                                   msg: &[u8],
+                                   signature: &[u8],
+                                   signed_at: Option<u64>) -> IndyResult<bool> {
+        debug!("verify_with_did_key_history >>> wallet_handle: {:?}, my_did: {:?}, msg: {:?}, signature: {:?}, signed_at: {:?}",
+               wallet_handle, my_did, msg, signature, signed_at);
+
+        self.crypto_service.validate_did(my_did)?;
+
+        let my_did = self._wallet_get_my_did(wallet_handle, my_did)?;
+
+        let key_history = self._wallet_get_did_key_history(wallet_handle, &my_did.did)?;
+
+        // Only the key that was active at the signing time is accepted if it is known
+        let verkeys: Vec<&str> = match signed_at {
+            Some(signed_at) => vec![key_history.key_active_at(signed_at).unwrap_or(&my_did.verkey)],
+            None => key_history.keys.iter().rev()
+                .map(|entry| entry.verkey.as_str())
+                .chain(::std::iter::once(my_did.verkey.as_str()))
+                .collect()
+        };
+
+        let mut res = false;
+
+        for verkey in verkeys {
+            if self.crypto_service.verify(verkey, msg, signature)? {
+                res = true;
+                break;
+            }
+        }
+
+        debug!("verify_with_did_key_history <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    // Appends the current key of the DID to the history, returns the operation storing the history
+    fn _archive_did_key_operation(&self, wallet_handle: WalletHandle, my_did: &Did, mut key_history: DidKeyHistory) -> IndyResult<WalletRecordOperation> {
+        let replaced_at = SystemTime::now().duration_since(UNIX_EPOCH)
+            .to_indy(IndyErrorKind::InvalidState, "Time has gone backwards")?
+            .as_secs();
+
+        let activated_at = key_history.current_key_activated_at();
+
+        key_history.keys.push(DidKeyHistoryEntry { verkey: my_did.verkey.clone(), activated_at, replaced_at });
+
+        self.wallet_service.upsert_indy_object_operation(wallet_handle, &my_did.did.0, &key_history)
+    }
+
     fn store_their_did(&self,
                        wallet_handle: WalletHandle,
                        their_did_info: &TheirDidInfo) -> IndyResult<()> {
@@ -383,6 +559,17 @@ impl DidCommandExecutor {
             Err(err) => return cb(Err(err))
         };
 
+        // did:key and did:peer contain the key, so there is nothing to fetch from the ledger
+        if did.is_self_certifying() {
+            match self._wallet_get_their_did(wallet_handle, &did) {
+                Ok(their_did) => return cb(Ok(their_did.verkey)),
+                Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound => {}
+                Err(err) => return cb(Err(err))
+            };
+
+            return cb(self.crypto_service.verkey_for_self_certifying_did(&did));
+        }
+
         // look to their did
         let their_did = ensure_their_did!(self,
                                           wallet_handle,
@@ -419,9 +606,12 @@ impl DidCommandExecutor {
         };
 
         // look to their did
-        let their_did = self._wallet_get_their_did(wallet_handle, did)?;
-
-        let res = their_did.verkey;
+        let res = match self._wallet_get_their_did(wallet_handle, did) {
+            Ok(their_did) => their_did.verkey,
+            Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound && did.is_self_certifying() =>
+                self.crypto_service.verkey_for_self_certifying_did(did)?,
+            Err(err) => return Err(err)
+        };
 
         debug!("key_for_local_did <<< res: {:?}", res);
 
@@ -534,6 +724,35 @@ impl DidCommandExecutor {
         Ok(res)
     }
 
+    fn build_did_doc(&self,
+                     wallet_handle: WalletHandle,
+                     did: &DidValue) -> IndyResult<String> {
+        debug!("build_did_doc >>> wallet_handle: {:?}, did: {:?}", wallet_handle, did);
+
+        let verkey = self.key_for_local_did(wallet_handle, did)?;
+
+        // Either set by indy_set_endpoint_for_did or cached from the ledger ATTRIB by indy_get_endpoint_for_did
+        let endpoint = match self.wallet_service.get_indy_object::<Endpoint>(wallet_handle, &did.0, &RecordOptions::id_value()) {
+            Ok(endpoint) => Some(endpoint),
+            Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound => None,
+            Err(err) => return Err(err)
+        };
+
+        let key_agreement_pk = self.crypto_service.x25519_public_key(&verkey).ok();
+
+        let did_doc = self.did_doc_service.build_did_doc(did,
+                                                         &verkey,
+                                                         key_agreement_pk.as_ref().map(|pk| &pk[..]),
+                                                         endpoint.as_ref())?;
+
+        let res = serde_json::to_string(&did_doc)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize DID Document")?;
+
+        debug!("build_did_doc <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
     fn qualify_did(&self,
                    wallet_handle: WalletHandle,
                    did: &DidValue,
@@ -542,6 +761,10 @@ impl DidCommandExecutor {
 
         self.crypto_service.validate_did(did)?;
 
+        if did.is_self_certifying() {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "did:key and did:peer DIDs are derived from the keys and can't be qualified"));
+        }
+
         let mut curr_did: Did = self.wallet_service.get_indy_object::<Did>(wallet_handle, &did.0, &RecordOptions::id_value())?;
 
         curr_did.did = DidValue::new(&did.to_short().0, Some(&method.0));
@@ -562,6 +785,9 @@ impl DidCommandExecutor {
         // move endpoint
         self.update_dependent_entity_reference::<Endpoint>(wallet_handle, &did.0, &curr_did.did.0)?;
 
+        // move key history
+        self.update_dependent_entity_reference::<DidKeyHistory>(wallet_handle, &did.0, &curr_did.did.0)?;
+
         // move all pairwise
         let mut pairwise_search =
             self.wallet_service.search_indy_records::<Pairwise>(wallet_handle, "{}", &RecordOptions::id_value())?;
@@ -656,9 +882,7 @@ impl DidCommandExecutor {
         let attrib_data: AttribData = serde_json::from_str(&raw)
             .to_indy(IndyErrorKind::InvalidState, "Invalid GetAttReply json")?;
 
-        let endpoint = Endpoint::new(attrib_data.endpoint.ha, attrib_data.endpoint.verkey);
-
-        self.wallet_service.add_indy_object(wallet_handle, &did.0, &endpoint, &HashMap::new())?;
+        self.wallet_service.add_indy_object(wallet_handle, &did.0, &attrib_data.endpoint, &HashMap::new())?;
 
         trace!("_get_attrib_ack <<<");
 
@@ -766,4 +990,9 @@ impl DidCommandExecutor {
     fn _wallet_get_their_did(&self, wallet_handle: WalletHandle, their_did: &DidValue) -> IndyResult<TheirDid> {
         self.wallet_service.get_indy_object(wallet_handle, &their_did.0, &RecordOptions::id_value())
     }
+
+    fn _wallet_get_did_key_history(&self, wallet_handle: WalletHandle, my_did: &DidValue) -> IndyResult<DidKeyHistory> {
+        self.wallet_service.get_indy_opt_object(wallet_handle, &my_did.0, &RecordOptions::id_value())
+            .map(Option::unwrap_or_default)
+    }
 }
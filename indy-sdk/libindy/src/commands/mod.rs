@@ -24,6 +24,7 @@ use indy_api_types::errors::prelude::*;
 use crate::services::anoncreds::AnoncredsService;
 use crate::services::blob_storage::BlobStorageService;
 use crate::services::crypto::CryptoService;
+use crate::services::did_doc::DidDocService;
 use crate::services::ledger::LedgerService;
 use crate::services::payments::PaymentsService;
 use crate::services::pool::{PoolService, set_freshness_threshold};
@@ -129,6 +130,7 @@ impl CommandExecutor {
                 let anoncreds_service = Rc::new(AnoncredsService::new());
                 let blob_storage_service = Rc::new(BlobStorageService::new());
                 let crypto_service = Rc::new(CryptoService::new());
+                let did_doc_service = Rc::new(DidDocService::new());
                 let ledger_service = Rc::new(LedgerService::new());
                 let payments_service = Rc::new(PaymentsService::new());
                 let pool_service = Rc::new(PoolService::new());
@@ -139,9 +141,9 @@ impl CommandExecutor {
                 let crypto_command_executor = CryptoCommandExecutor::new(wallet_service.clone(), crypto_service.clone());
                 let ledger_command_executor = LedgerCommandExecutor::new(pool_service.clone(), crypto_service.clone(), wallet_service.clone(), ledger_service.clone());
                 let pool_command_executor = PoolCommandExecutor::new(pool_service.clone());
-                let did_command_executor = DidCommandExecutor::new(wallet_service.clone(), crypto_service.clone(), ledger_service.clone());
+                let did_command_executor = DidCommandExecutor::new(wallet_service.clone(), crypto_service.clone(), ledger_service.clone(), did_doc_service.clone());
                 let wallet_command_executor = WalletCommandExecutor::new(wallet_service.clone(), crypto_service.clone());
-                let pairwise_command_executor = PairwiseCommandExecutor::new(wallet_service.clone());
+                let pairwise_command_executor = PairwiseCommandExecutor::new(wallet_service.clone(), crypto_service.clone());
                 let blob_storage_command_executor = BlobStorageCommandExecutor::new(blob_storage_service.clone());
                 let non_secret_command_executor = NonSecretsCommandExecutor::new(wallet_service.clone());
                 let payments_command_executor = PaymentsCommandExecutor::new(payments_service.clone(), wallet_service.clone(), crypto_service.clone(), ledger_service.clone());
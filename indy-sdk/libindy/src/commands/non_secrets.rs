@@ -4,7 +4,7 @@ use std::rc::Rc;
 
 use indy_api_types::domain::wallet::Tags;
 use indy_api_types::errors::prelude::*;
-use indy_wallet::{RecordOptions, SearchOptions, WalletRecord, WalletSearch, WalletService};
+use indy_wallet::{RecordOptions, SearchOptions, WalletRecord, WalletRecordOperation, WalletSearch, WalletService};
 use indy_utils::next_search_handle;
 use indy_api_types::{WalletHandle, SearchHandle};
 
@@ -40,6 +40,9 @@ pub enum NonSecretsCommand {
                  String, // type
                  String, // id
                  Box<dyn Fn(IndyResult<()>) + Send>),
+    BatchRecords(WalletHandle,
+                 Vec<WalletRecordOperation>, // operations
+                 Box<dyn Fn(IndyResult<()>) + Send>),
     GetRecord(WalletHandle,
               String, // type
               String, // id
@@ -56,6 +59,18 @@ pub enum NonSecretsCommand {
                            Box<dyn Fn(IndyResult<String>) + Send>),
     CloseSearch(SearchHandle, // wallet search handle
                 Box<dyn Fn(IndyResult<()>) + Send>),
+    SearchRecordsPage(WalletHandle,
+                      String, // type
+                      String, // query json
+                      String, // options json
+                      Option<String>, // cursor
+                      usize, // limit
+                      Box<dyn Fn(IndyResult<String>) + Send>),
+    CountRecords(WalletHandle,
+                 String, // type
+                 String, // query json
+                 Option<String>, // group by tag name
+                 Box<dyn Fn(IndyResult<String>) + Send>),
 }
 
 pub struct NonSecretsCommandExecutor {
@@ -97,6 +112,10 @@ impl NonSecretsCommandExecutor {
                 debug!(target: "non_secrets_command_executor", "DeleteRecord command received");
                 cb(self.delete_record(handle, &type_, &id));
             }
+            NonSecretsCommand::BatchRecords(handle, operations, cb) => {
+                debug!(target: "non_secrets_command_executor", "BatchRecords command received");
+                cb(self.batch_records(handle, &operations));
+            }
             NonSecretsCommand::GetRecord(handle, type_, id, options_json, cb) => {
                 debug!(target: "non_secrets_command_executor", "GetRecord command received");
                 cb(self.get_record(handle, &type_, &id, &options_json));
@@ -113,6 +132,14 @@ impl NonSecretsCommandExecutor {
                 debug!(target: "non_secrets_command_executor", "CloseSearch command received");
                 cb(self.close_search(wallet_search_handle));
             }
+            NonSecretsCommand::SearchRecordsPage(handle, type_, query_json, options_json, cursor, limit, cb) => {
+                debug!(target: "non_secrets_command_executor", "SearchRecordsPage command received");
+                cb(self.search_records_page(handle, &type_, &query_json, &options_json, cursor.as_ref().map(String::as_str), limit));
+            }
+            NonSecretsCommand::CountRecords(handle, type_, query_json, group_by_tag, cb) => {
+                debug!(target: "non_secrets_command_executor", "CountRecords command received");
+                cb(self.count_records(handle, &type_, &query_json, group_by_tag.as_ref().map(String::as_str)));
+            }
         };
     }
 
@@ -215,6 +242,22 @@ impl NonSecretsCommandExecutor {
         Ok(())
     }
 
+    fn batch_records(&self,
+                     wallet_handle: WalletHandle,
+                     operations: &[WalletRecordOperation]) -> IndyResult<()> {
+        trace!("batch_records >>> wallet_handle: {:?}, operations: {:?}", wallet_handle, operations);
+
+        for operation in operations {
+            self._check_type(operation.get_type())?;
+        }
+
+        self.wallet_service.batch_records(wallet_handle, operations)?;
+
+        trace!("batch_records <<< res: ()");
+
+        Ok(())
+    }
+
     fn get_record(&self,
                   wallet_handle: WalletHandle,
                   type_: &str,
@@ -304,6 +347,55 @@ impl NonSecretsCommandExecutor {
         Ok(())
     }
 
+    fn search_records_page(&self,
+                           wallet_handle: WalletHandle,
+                           type_: &str,
+                           query_json: &str,
+                           options_json: &str,
+                           cursor: Option<&str>,
+                           limit: usize) -> IndyResult<String> {
+        trace!("search_records_page >>> wallet_handle: {:?}, type_: {:?}, query_json: {:?}, options_json: {:?}, cursor: {:?}, limit: {:?}",
+               wallet_handle, type_, query_json, options_json, cursor, limit);
+
+        self._check_type(type_)?;
+
+        serde_json::from_str::<SearchOptions>(options_json)
+            .to_indy(IndyErrorKind::InvalidStructure, "Cannot deserialize options")?;
+
+        let page = self.wallet_service.search_records_page(wallet_handle, type_, query_json, options_json, cursor, limit)?;
+
+        let res = serde_json::to_string(&page)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize WalletSearchPage")?;
+
+        trace!("search_records_page <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn count_records(&self,
+                     wallet_handle: WalletHandle,
+                     type_: &str,
+                     query_json: &str,
+                     group_by_tag: Option<&str>) -> IndyResult<String> {
+        trace!("count_records >>> wallet_handle: {:?}, type_: {:?}, query_json: {:?}, group_by_tag: {:?}", wallet_handle, type_, query_json, group_by_tag);
+
+        self._check_type(type_)?;
+
+        let count = self.wallet_service.count_records(wallet_handle, type_, query_json)?;
+
+        let groups = match group_by_tag {
+            Some(tag_name) => Some(self.wallet_service.group_count_by_tag(wallet_handle, type_, query_json, tag_name)?),
+            None => None
+        };
+
+        let res = serde_json::to_string(&CountRecords { count, groups })
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize CountRecords")?;
+
+        trace!("count_records <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
     fn _check_type(&self, type_: &str) -> IndyResult<()> {
         if type_.starts_with(WalletService::PREFIX) {
             return Err(err_msg(IndyErrorKind::WalletAccessFailed, format!("Record of type \"{}\" is not available for fetching", type_)));
@@ -318,3 +410,10 @@ pub struct SearchRecords {
     pub total_count: Option<usize>,
     pub records: Option<Vec<WalletRecord>>
 }
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CountRecords {
+    pub count: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub groups: Option<HashMap<String, usize>>
+}
@@ -1,11 +1,15 @@
-use crate::domain::crypto::did::{Did, TheirDid};
-use crate::domain::pairwise::{Pairwise, PairwiseInfo};
+use crate::domain::crypto::did::{Did, TheirDid, TheirDidInfo};
+use crate::domain::pairwise::{Pairwise, PairwiseInfo, PairwiseRecord, PairwiseSearchRecords};
+use indy_api_types::domain::wallet::Tags;
 use indy_api_types::errors::prelude::*;
-use indy_wallet::{RecordOptions, WalletService};
+use crate::services::crypto::CryptoService;
+use indy_wallet::{RecordOptions, SearchOptions, WalletSearch, WalletService};
+use indy_utils::next_search_handle;
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
 use std::str;
-use indy_api_types::WalletHandle;
+use indy_api_types::{WalletHandle, SearchHandle};
 use crate::domain::crypto::did::DidValue;
 
 
@@ -31,17 +35,38 @@ pub enum PairwiseCommand {
         WalletHandle,
         DidValue, // their_did
         Option<String>, // metadata
+        Box<dyn Fn(IndyResult<()>) + Send>),
+    SetPairwiseTags(
+        WalletHandle,
+        DidValue, // their_did
+        Tags, // tags
+        Box<dyn Fn(IndyResult<()>) + Send>),
+    OpenPairwiseSearch(
+        WalletHandle,
+        Option<String>, // query json
+        Box<dyn Fn(IndyResult<SearchHandle>) + Send>),
+    FetchPairwiseSearchNextRecords(
+        WalletHandle,
+        SearchHandle, // pairwise search handle
+        usize, // count
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    ClosePairwiseSearch(
+        SearchHandle, // pairwise search handle
         Box<dyn Fn(IndyResult<()>) + Send>)
 }
 
 pub struct PairwiseCommandExecutor {
-    wallet_service: Rc<WalletService>
+    wallet_service: Rc<WalletService>,
+    crypto_service: Rc<CryptoService>,
+    searches: RefCell<HashMap<SearchHandle, Box<WalletSearch>>>,
 }
 
 impl PairwiseCommandExecutor {
-    pub fn new(wallet_service: Rc<WalletService>) -> PairwiseCommandExecutor {
+    pub fn new(wallet_service: Rc<WalletService>, crypto_service: Rc<CryptoService>) -> PairwiseCommandExecutor {
         PairwiseCommandExecutor {
-            wallet_service
+            wallet_service,
+            crypto_service,
+            searches: RefCell::new(HashMap::new()),
         }
     }
 
@@ -67,6 +92,22 @@ impl PairwiseCommandExecutor {
                 debug!(target: "pairwise_command_executor", "SetPairwiseMetadata command received");
                 cb(self.set_pairwise_metadata(wallet_handle, &their_did, metadata.as_ref().map(String::as_str)));
             }
+            PairwiseCommand::SetPairwiseTags(wallet_handle, their_did, tags, cb) => {
+                debug!(target: "pairwise_command_executor", "SetPairwiseTags command received");
+                cb(self.set_pairwise_tags(wallet_handle, &their_did, &tags));
+            }
+            PairwiseCommand::OpenPairwiseSearch(wallet_handle, query_json, cb) => {
+                debug!(target: "pairwise_command_executor", "OpenPairwiseSearch command received");
+                cb(self.open_pairwise_search(wallet_handle, query_json.as_ref().map(String::as_str)));
+            }
+            PairwiseCommand::FetchPairwiseSearchNextRecords(wallet_handle, search_handle, count, cb) => {
+                debug!(target: "pairwise_command_executor", "FetchPairwiseSearchNextRecords command received");
+                cb(self.fetch_pairwise_search_next_records(wallet_handle, search_handle, count));
+            }
+            PairwiseCommand::ClosePairwiseSearch(search_handle, cb) => {
+                debug!(target: "pairwise_command_executor", "ClosePairwiseSearch command received");
+                cb(self.close_pairwise_search(search_handle));
+            }
         };
     }
 
@@ -90,7 +131,16 @@ impl PairwiseCommandExecutor {
         debug!("create_pairwise >>> wallet_handle: {:?}, their_did: {:?}, my_did: {:?}, metadata: {:?}", wallet_handle, their_did, my_did, metadata);
 
         self.wallet_service.get_indy_record::<Did>(wallet_handle, &my_did.0, &RecordOptions::id())?;
-        self.wallet_service.get_indy_record::<TheirDid>(wallet_handle, &their_did.0, &RecordOptions::id())?;
+
+        match self.wallet_service.get_indy_record::<TheirDid>(wallet_handle, &their_did.0, &RecordOptions::id()) {
+            Ok(_) => {}
+            // did:key and did:peer are stored on the fly as their keys are known from the DIDs
+            Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound && their_did.is_self_certifying() => {
+                let their_did = self.crypto_service.create_their_did(&TheirDidInfo::new(their_did.clone(), None))?;
+                self.wallet_service.add_indy_object(wallet_handle, &their_did.did.0, &their_did, &HashMap::new())?;
+            }
+            Err(err) => return Err(err)
+        };
 
         let pairwise = Pairwise {
             my_did: my_did.clone(),
@@ -166,4 +216,99 @@ impl PairwiseCommandExecutor {
 
         Ok(())
     }
+
+    fn set_pairwise_tags(&self,
+                         wallet_handle: WalletHandle,
+                         their_did: &DidValue,
+                         tags: &Tags) -> IndyResult<()> {
+        debug!("set_pairwise_tags >>> wallet_handle: {:?}, their_did: {:?}, tags: {:?}", wallet_handle, their_did, tags);
+
+        let type_ = self.wallet_service.add_prefix("Pairwise");
+
+        self.wallet_service.update_record_tags(wallet_handle, &type_, &their_did.0, tags)?;
+
+        debug!("set_pairwise_tags <<<");
+
+        Ok(())
+    }
+
+    fn open_pairwise_search(&self,
+                            wallet_handle: WalletHandle,
+                            query_json: Option<&str>) -> IndyResult<SearchHandle> {
+        debug!("open_pairwise_search >>> wallet_handle: {:?}, query_json: {:?}", wallet_handle, query_json);
+
+        let options = json!({
+            "retrieveRecords": true,
+            "retrieveTotalCount": true,
+            "retrieveType": false,
+            "retrieveValue": true,
+            "retrieveTags": true,
+        }).to_string();
+
+        let search = self.wallet_service.search_indy_records::<Pairwise>(wallet_handle, query_json.unwrap_or("{}"), &options)?;
+
+        let search_handle = next_search_handle();
+
+        self.searches.borrow_mut().insert(search_handle, Box::new(search));
+
+        debug!("open_pairwise_search <<< res: {:?}", search_handle);
+
+        Ok(search_handle)
+    }
+
+    fn fetch_pairwise_search_next_records(&self,
+                                          wallet_handle: WalletHandle,
+                                          search_handle: SearchHandle,
+                                          count: usize) -> IndyResult<String> {
+        debug!("fetch_pairwise_search_next_records >>> wallet_handle: {:?}, search_handle: {:?}, count: {:?}", wallet_handle, search_handle, count);
+
+        let mut searches = self.searches.borrow_mut();
+        let search = searches.get_mut(&search_handle)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidWalletHandle, format!("Unknown pairwise search handle: {:?}", search_handle)))?;
+
+        let mut records: Vec<PairwiseRecord> = Vec::new();
+        for _ in 0..count {
+            match search.fetch_next_record()? {
+                Some(record) => {
+                    let pairwise_value = record.get_value()
+                        .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Pairwise not found for id: {}", record.get_id())))?;
+
+                    let pairwise: Pairwise = serde_json::from_str(pairwise_value)
+                        .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize Pairwise")?;
+
+                    records.push(PairwiseRecord {
+                        pairwise,
+                        tags: record.get_tags().cloned().unwrap_or_default(),
+                    })
+                }
+                None => break
+            }
+        }
+
+        let search_result = PairwiseSearchRecords {
+            total_count: search.get_total_count()?,
+            records: if records.is_empty() { None } else { Some(records) },
+        };
+
+        let res = serde_json::to_string(&search_result)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize PairwiseSearchRecords")?;
+
+        debug!("fetch_pairwise_search_next_records <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn close_pairwise_search(&self,
+                             search_handle: SearchHandle) -> IndyResult<()> {
+        debug!("close_pairwise_search >>> search_handle: {:?}", search_handle);
+
+        match self.searches.borrow_mut().remove(&search_handle) {
+            Some(_) => Ok(()),
+            None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, format!("Pairwise search handle is invalid: {:?}", search_handle)))
+        }?;
+
+        debug!("close_pairwise_search <<<");
+
+        Ok(())
+    }
 }
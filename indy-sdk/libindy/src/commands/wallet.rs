@@ -68,6 +68,7 @@ pub enum WalletCommand {
                    CallbackHandle),
     Export(WalletHandle,
            ExportConfig, // export config
+           Option<Box<dyn Fn(usize) -> IndyResult<()> + Send>>, // progress
            Box<dyn Fn(IndyResult<()>) + Send>),
     ExportContinue(WalletHandle,
                    ExportConfig, // export config
@@ -77,6 +78,7 @@ pub enum WalletCommand {
     Import(Config, // config
            Credentials, // credentials
            ExportConfig, // import config
+           Option<Box<dyn Fn(usize) -> IndyResult<()> + Send>>, // progress
            Box<dyn Fn(IndyResult<()>) + Send>),
     ImportContinue(Config, // config
                    Credentials, // credentials
@@ -84,6 +86,19 @@ pub enum WalletCommand {
                    WalletHandle,
                    CallbackHandle
     ),
+    RotateKeys(WalletHandle,
+               Credentials, // credentials
+               Box<dyn Fn(IndyResult<()>) + Send>),
+    RotateKeysContinue(WalletHandle,
+                       KeyDerivationData,
+                       DeriveKeyResult<MasterKey>,
+                       CallbackHandle),
+    Verify(WalletHandle,
+           bool, // repair
+           Box<dyn Fn(IndyResult<String>) + Send>),
+    CheckMigrations(Config, // config
+                    Credentials, // credentials
+                    Box<dyn Fn(IndyResult<String>) + Send>),
     GenerateKey(Option<KeyConfig>, // config
                 Box<dyn Fn(IndyResult<String>) + Send>),
     DeriveKey(KeyDerivationData,
@@ -101,7 +116,8 @@ pub struct WalletCommandExecutor {
     wallet_service: Rc<WalletService>,
     crypto_service: Rc<CryptoService>,
     open_callbacks: RefCell<HashMap<WalletHandle, Box<dyn Fn(IndyResult<WalletHandle>) + Send>>>,
-    pending_callbacks: RefCell<HashMap<CallbackHandle, Box<dyn Fn(IndyResult<()>) + Send>>>
+    pending_callbacks: RefCell<HashMap<CallbackHandle, Box<dyn Fn(IndyResult<()>) + Send>>>,
+    pending_progress_callbacks: RefCell<HashMap<CallbackHandle, Box<dyn Fn(usize) -> IndyResult<()> + Send>>>
 }
 
 impl WalletCommandExecutor {
@@ -110,7 +126,8 @@ impl WalletCommandExecutor {
             wallet_service,
             crypto_service,
             open_callbacks: RefCell::new(HashMap::new()),
-            pending_callbacks: RefCell::new(HashMap::new())
+            pending_callbacks: RefCell::new(HashMap::new()),
+            pending_progress_callbacks: RefCell::new(HashMap::new())
         }
     }
 
@@ -158,22 +175,38 @@ impl WalletCommandExecutor {
                 debug!(target: "wallet_command_executor", "DeleteContinue command received");
                 self._delete_continue(cb_id, &config, &credentials, &metadata, key_result)
             }
-            WalletCommand::Export(wallet_handle, export_config, cb) => {
+            WalletCommand::Export(wallet_handle, export_config, progress, cb) => {
                 debug!(target: "wallet_command_executor", "Export command received");
-                self._export(wallet_handle, &export_config, cb)
+                self._export(wallet_handle, &export_config, progress, cb)
             }
             WalletCommand::ExportContinue(wallet_handle, export_config, key_data, key_result, cb_id) => {
                 debug!(target: "wallet_command_executor", "ExportContinue command received");
                 self._export_continue(cb_id, wallet_handle, &export_config, key_data, key_result)
             }
-            WalletCommand::Import(config, credentials, import_config, cb) => {
+            WalletCommand::Import(config, credentials, import_config, progress, cb) => {
                 debug!(target: "wallet_command_executor", "Import command received");
-                self._import(&config, &credentials, &import_config, cb);
+                self._import(&config, &credentials, &import_config, progress, cb);
             }
             WalletCommand::ImportContinue(config, credential, key_result, wallet_handle, cb_id) => {
                 debug!(target: "wallet_command_executor", "ImportContinue command received");
                 self._import_continue(cb_id, wallet_handle, &config, &credential, key_result);
             }
+            WalletCommand::RotateKeys(wallet_handle, credentials, cb) => {
+                debug!(target: "wallet_command_executor", "RotateKeys command received");
+                self._rotate_keys(wallet_handle, &credentials, cb);
+            }
+            WalletCommand::RotateKeysContinue(wallet_handle, key_data, key_result, cb_id) => {
+                debug!(target: "wallet_command_executor", "RotateKeysContinue command received");
+                self._rotate_keys_continue(cb_id, wallet_handle, key_data, key_result);
+            }
+            WalletCommand::Verify(wallet_handle, repair, cb) => {
+                debug!(target: "wallet_command_executor", "Verify command received");
+                cb(self._verify(wallet_handle, repair));
+            }
+            WalletCommand::CheckMigrations(config, credentials, cb) => {
+                debug!(target: "wallet_command_executor", "CheckMigrations command received");
+                cb(self._check_migrations(&config, &credentials));
+            }
             WalletCommand::GenerateKey(config, cb) => {
                 debug!(target: "wallet_command_executor", "DeriveKey command received");
                 cb(self._generate_key(config.as_ref()));
@@ -390,6 +423,7 @@ impl WalletCommandExecutor {
     fn _export(&self,
                wallet_handle: WalletHandle,
                export_config: &ExportConfig,
+               progress: Option<Box<dyn Fn(usize) -> IndyResult<()> + Send>>,
                cb: Box<dyn Fn(IndyResult<()>) + Send>) {
         trace!("_export >>> handle: {:?}, export_config: {:?}", wallet_handle, secret!(export_config));
 
@@ -397,6 +431,7 @@ impl WalletCommandExecutor {
 
         let cb_id = indy_utils::sequence::get_next_id();
         self.pending_callbacks.borrow_mut().insert(cb_id, cb);
+        self._stash_progress(cb_id, progress);
 
         let export_config = export_config.clone();
 
@@ -425,14 +460,16 @@ impl WalletCommandExecutor {
                         key_data: KeyDerivationData,
                         key_result: DeriveKeyResult<MasterKey>) {
         let cb = get_cb!(self, cb_id);
+        let progress = self.pending_progress_callbacks.borrow_mut().remove(&cb_id);
         cb(key_result
-            .and_then(|key| self.wallet_service.export_wallet(wallet_handle, export_config, 0, (&key_data,& key)))) // TODO - later add proper versioning
+            .and_then(|key| self.wallet_service.export_wallet(wallet_handle, export_config, 0, (&key_data,& key), WalletCommandExecutor::_as_progress(&progress)))) // TODO - later add proper versioning
     }
 
     fn _import(&self,
                config: &Config,
                credentials: &Credentials,
                import_config: &ExportConfig,
+               progress: Option<Box<dyn Fn(usize) -> IndyResult<()> + Send>>,
                cb: Box<dyn Fn(IndyResult<()>) + Send>) {
         trace!("_import >>> config: {:?}, credentials: {:?}, import_config: {:?}",
                config, secret!(credentials), secret!(import_config));
@@ -441,6 +478,7 @@ impl WalletCommandExecutor {
 
         let cb_id : CallbackHandle = indy_utils::sequence::get_next_id();
         self.pending_callbacks.borrow_mut().insert(cb_id, cb);
+        self._stash_progress(cb_id, progress);
 
         let config = config.clone();
         let credentials = credentials.clone();
@@ -481,8 +519,85 @@ impl WalletCommandExecutor {
                         credential: &Credentials,
                         key_result: DeriveKeyResult<(MasterKey, MasterKey)>) {
         let cb = get_cb!(self, cb_id);
+        let progress = self.pending_progress_callbacks.borrow_mut().remove(&cb_id);
+        cb(key_result
+            .and_then(|key| self.wallet_service.import_wallet_continue(wallet_handle, &config, &credential, key, WalletCommandExecutor::_as_progress(&progress))))
+    }
+
+    fn _stash_progress(&self, cb_id: CallbackHandle, progress: Option<Box<dyn Fn(usize) -> IndyResult<()> + Send>>) {
+        if let Some(progress) = progress {
+            self.pending_progress_callbacks.borrow_mut().insert(cb_id, progress);
+        }
+    }
+
+    fn _as_progress(progress: &Option<Box<dyn Fn(usize) -> IndyResult<()> + Send>>) -> Option<&dyn Fn(usize) -> IndyResult<()>> {
+        progress.as_ref().map(|progress| &**progress as &dyn Fn(usize) -> IndyResult<()>)
+    }
+
+    fn _rotate_keys(&self,
+                    wallet_handle: WalletHandle,
+                    credentials: &Credentials,
+                    cb: Box<dyn Fn(IndyResult<()>) + Send>) {
+        trace!("_rotate_keys >>> handle: {:?}, credentials: {:?}", wallet_handle, secret!(credentials));
+
+        let key_data = try_cb!(self.wallet_service.rotate_keys_prepare(wallet_handle, credentials), cb);
+
+        let cb_id: CallbackHandle = indy_utils::sequence::get_next_id();
+        self.pending_callbacks.borrow_mut().insert(cb_id, cb);
+
+        CommandExecutor::instance().send(
+            Command::Wallet(WalletCommand::DeriveKey(
+                key_data.clone(),
+                Box::new(move |master_key_res| {
+                    CommandExecutor::instance().send(Command::Wallet(WalletCommand::RotateKeysContinue(
+                        wallet_handle,
+                        key_data.clone(),
+                        master_key_res,
+                        cb_id,
+                    ))).unwrap();
+                })
+            ))
+        ).unwrap();
+
+        trace!("_rotate_keys <<<");
+    }
+
+    fn _rotate_keys_continue(&self,
+                             cb_id: CallbackHandle,
+                             wallet_handle: WalletHandle,
+                             key_data: KeyDerivationData,
+                             key_result: DeriveKeyResult<MasterKey>) {
+        let cb = get_cb!(self, cb_id);
         cb(key_result
-            .and_then(|key| self.wallet_service.import_wallet_continue(wallet_handle, &config, &credential, key)))
+            .and_then(|key| self.wallet_service.rotate_keys(wallet_handle, (&key_data, &key))))
+    }
+
+    fn _verify(&self,
+               wallet_handle: WalletHandle,
+               repair: bool) -> IndyResult<String> {
+        trace!("_verify >>> wallet_handle: {:?}, repair: {:?}", wallet_handle, repair);
+
+        let report = self.wallet_service.verify_wallet(wallet_handle, repair)?;
+
+        let res = serde_json::to_string(&report)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize WalletVerificationReport")?;
+
+        trace!("_verify <<< res: {:?}", res);
+        Ok(res)
+    }
+
+    fn _check_migrations(&self,
+                         config: &Config,
+                         credentials: &Credentials) -> IndyResult<String> {
+        trace!("_check_migrations >>> config: {:?}, credentials: {:?}", config, secret!(credentials));
+
+        let migrations = self.wallet_service.check_wallet_migrations(config, credentials)?;
+
+        let res = serde_json::to_string(&migrations)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize SchemaMigration list")?;
+
+        trace!("_check_migrations <<< res: {:?}", res);
+        Ok(res)
     }
 
     fn _generate_key(&self,
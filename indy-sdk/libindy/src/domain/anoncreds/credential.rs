@@ -10,6 +10,9 @@ use ursa::cl::{
 use indy_api_types::validation::Validatable;
 
 use super::credential_definition::CredentialDefinitionId;
+use super::credential_offer::CredentialOffer;
+use super::credential_request::CredentialRequest;
+use super::issued_credential_record::IssuedCredentialMetadata;
 use super::revocation_registry_definition::RevocationRegistryId;
 use super::schema::SchemaId;
 
@@ -81,4 +84,55 @@ impl Validatable for Credential {
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CredentialIssuanceRequest {
+    pub cred_offer: CredentialOffer,
+    pub cred_req: CredentialRequest,
+    pub cred_values: CredentialValues,
+    pub rev_reg_id: Option<RevocationRegistryId>,
+    pub metadata: Option<IssuedCredentialMetadata>,
+}
+
+impl Validatable for CredentialIssuanceRequest {
+    fn validate(&self) -> Result<(), String> {
+        self.cred_offer.validate()?;
+        self.cred_req.validate()?;
+        self.cred_values.validate()?;
+
+        if let Some(ref rev_reg_id) = self.rev_reg_id {
+            rev_reg_id.validate()?;
+        }
+
+        if let Some(ref metadata) = self.metadata {
+            metadata.validate()?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CredentialIssuanceRequests(pub Vec<CredentialIssuanceRequest>);
+
+impl Validatable for CredentialIssuanceRequests {
+    fn validate(&self) -> Result<(), String> {
+        if self.0.is_empty() {
+            return Err(String::from("CredentialIssuanceRequests validation failed: empty list has been passed"));
+        }
+
+        for request in self.0.iter() {
+            request.validate()?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct IssuedCredential {
+    pub cred: Credential,
+    pub cred_revoc_id: Option<String>,
+    pub issued_cred_id: String,
+}
@@ -33,17 +33,28 @@ impl SignatureType {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CredentialDefinitionConfig {
     #[serde(default)]
-    pub support_revocation: bool
+    pub support_revocation: bool,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revocation_registry_rollover: Option<RevocationRegistryRolloverConfig>
 }
 
 impl Default for CredentialDefinitionConfig {
     fn default() -> Self {
         CredentialDefinitionConfig {
-            support_revocation: false
+            support_revocation: false,
+            revocation_registry_rollover: None
         }
     }
 }
 
+// Blob storage writer used to store tails of revocation registries created by rollover during issuance
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RevocationRegistryRolloverConfig {
+    pub tails_writer_type: String,
+    pub tails_writer_config: serde_json::Value
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CredentialDefinitionData {
     pub primary: CredentialPrimaryPublicKey,
@@ -240,7 +251,20 @@ impl Validatable for CredentialDefinitionId {
     }
 }
 
-impl Validatable for CredentialDefinitionConfig {}
+impl Validatable for CredentialDefinitionConfig {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(ref rollover) = self.revocation_registry_rollover {
+            if !self.support_revocation {
+                return Err(String::from("CredentialDefinitionConfig validation failed: `revocation_registry_rollover` requires `support_revocation`"));
+            }
+
+            if !rollover.tails_writer_config.is_object() {
+                return Err(String::from("CredentialDefinitionConfig validation failed: `tails_writer_config` must be an object"));
+            }
+        }
+        Ok(())
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -419,4 +443,34 @@ mod tests {
             _cred_def_id_qualified_with_schema_as_seq_no().validate().unwrap();
         }
     }
-}
\ No newline at end of file
+    mod validate_config {
+        use super::*;
+
+        fn _config(config: serde_json::Value) -> CredentialDefinitionConfig {
+            serde_json::from_value(config).unwrap()
+        }
+
+        #[test]
+        fn test_validate_config_with_revocation_registry_rollover() {
+            _config(json!({
+                "support_revocation": true,
+                "revocation_registry_rollover": {"tails_writer_type": "default", "tails_writer_config": {"base_dir": "/tmp", "uri_pattern": ""}}
+            })).validate().unwrap();
+        }
+
+        #[test]
+        fn test_validate_config_with_revocation_registry_rollover_without_revocation_support() {
+            _config(json!({
+                "revocation_registry_rollover": {"tails_writer_type": "default", "tails_writer_config": {"base_dir": "/tmp", "uri_pattern": ""}}
+            })).validate().unwrap_err();
+        }
+
+        #[test]
+        fn test_validate_config_with_revocation_registry_rollover_for_invalid_tails_writer_config() {
+            _config(json!({
+                "support_revocation": true,
+                "revocation_registry_rollover": {"tails_writer_type": "default", "tails_writer_config": "/tmp"}
+            })).validate().unwrap_err();
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use super::credential_definition::CredentialDefinitionId;
+use super::revocation_registry_definition::RevocationRegistryId;
+use super::schema::SchemaId;
+use crate::domain::crypto::did::DidValue;
+use indy_api_types::domain::wallet::Tags;
+use indy_api_types::validation::Validatable;
+
+// Tag names filled by libindy for each issued credential record
+pub const RESERVED_TAG_NAMES: [&str; 7] = [
+    "cred_def_id",
+    "schema_id",
+    "rev_reg_id",
+    "cred_rev_id",
+    "cred_offer_nonce",
+    "prover_did",
+    "revoked",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssuedCredentialRecord {
+    pub cred_def_id: CredentialDefinitionId,
+    pub schema_id: SchemaId,
+    pub rev_reg_id: Option<RevocationRegistryId>,
+    pub cred_rev_id: Option<String>,
+    pub cred_offer_nonce: String,
+    pub prover_did: DidValue,
+    pub revoked: bool,
+    #[serde(default)]
+    pub metadata: Tags,
+}
+
+impl IssuedCredentialRecord {
+    pub fn tags(&self) -> Tags {
+        let mut tags = self.metadata.clone();
+
+        tags.insert("cred_def_id".to_string(), self.cred_def_id.0.clone());
+        tags.insert("schema_id".to_string(), self.schema_id.0.clone());
+        tags.insert("cred_offer_nonce".to_string(), self.cred_offer_nonce.clone());
+        tags.insert("prover_did".to_string(), self.prover_did.0.clone());
+
+        // Registry id is tagged in unqualified form to match records regardless of the form used in requests
+        if let (Some(rev_reg_id), Some(cred_rev_id)) = (self.rev_reg_id.as_ref(), self.cred_rev_id.as_ref()) {
+            tags.insert("rev_reg_id".to_string(), rev_reg_id.to_unqualified().0);
+            tags.insert("cred_rev_id".to_string(), cred_rev_id.clone());
+            tags.insert("revoked".to_string(), self.revoked.to_string());
+        }
+
+        tags
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IssuedCredentialInfo {
+    pub referent: String,
+    #[serde(flatten)]
+    pub record: IssuedCredentialRecord,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct IssuedCredentialMetadata(pub Tags);
+
+impl Validatable for IssuedCredentialMetadata {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(name) = self.0.keys().find(|name| RESERVED_TAG_NAMES.contains(&name.as_str())) {
+            return Err(format!("Metadata name \"{}\" is reserved for issued credential record", name));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn _record(rev_reg_id: Option<&str>, cred_rev_id: Option<&str>) -> IssuedCredentialRecord {
+        IssuedCredentialRecord {
+            cred_def_id: CredentialDefinitionId("NcYxiDXkpYi6ov5FcYDi1e:3:CL:1:tag".to_string()),
+            schema_id: SchemaId("NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0".to_string()),
+            rev_reg_id: rev_reg_id.map(|id| RevocationRegistryId(id.to_string())),
+            cred_rev_id: cred_rev_id.map(String::from),
+            cred_offer_nonce: "123456789".to_string(),
+            prover_did: DidValue("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW".to_string()),
+            revoked: false,
+            metadata: vec![("connection_id".to_string(), "connection_1".to_string())].into_iter().collect(),
+        }
+    }
+
+    #[test]
+    fn tags_works_for_revocable_credential() {
+        let tags = _record(Some("NcYxiDXkpYi6ov5FcYDi1e:4:NcYxiDXkpYi6ov5FcYDi1e:3:CL:1:tag:CL_ACCUM:TAG_1"), Some("1")).tags();
+
+        assert_eq!("1", tags["cred_rev_id"]);
+        assert_eq!("false", tags["revoked"]);
+        assert_eq!("connection_1", tags["connection_id"]);
+    }
+
+    #[test]
+    fn tags_works_for_qualified_rev_reg_id() {
+        let tags = _record(Some("revreg:sov:did:sov:NcYxiDXkpYi6ov5FcYDi1e:4:creddef:sov:did:sov:NcYxiDXkpYi6ov5FcYDi1e:3:CL:schema:sov:did:sov:NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0:tag:CL_ACCUM:TAG_1"), Some("1")).tags();
+
+        assert_eq!("NcYxiDXkpYi6ov5FcYDi1e:4:NcYxiDXkpYi6ov5FcYDi1e:3:CL:NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0:tag:CL_ACCUM:TAG_1", tags["rev_reg_id"]);
+    }
+
+    #[test]
+    fn tags_works_for_not_revocable_credential() {
+        let tags = _record(None, None).tags();
+
+        assert!(!tags.contains_key("rev_reg_id"));
+        assert!(!tags.contains_key("revoked"));
+        assert_eq!("123456789", tags["cred_offer_nonce"]);
+    }
+
+    #[test]
+    fn validate_metadata_works_for_reserved_name() {
+        let metadata: IssuedCredentialMetadata = serde_json::from_str(r#"{"connection_id": "connection_1", "revoked": "false"}"#).unwrap();
+        metadata.validate().unwrap_err();
+
+        let metadata: IssuedCredentialMetadata = serde_json::from_str(r#"{"connection_id": "connection_1"}"#).unwrap();
+        metadata.validate().unwrap();
+    }
+}
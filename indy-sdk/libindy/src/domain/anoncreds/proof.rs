@@ -74,6 +74,48 @@ pub struct Identifier {
 
 impl Validatable for Proof {}
 
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ProofVerificationReport {
+    pub valid: bool,
+    pub requested_attributes: HashMap<String, ReferentVerificationResult>,
+    pub requested_predicates: HashMap<String, ReferentVerificationResult>,
+    // Failures which are not related to a single referent of the proof request
+    pub errors: Vec<VerificationFailure>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ReferentVerificationResult {
+    pub valid: bool,
+    pub errors: Vec<VerificationFailure>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VerificationFailure {
+    pub check: VerificationCheck,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationCheck {
+    // Proof structure is inconsistent (identifiers, attribute groups)
+    Structure,
+    // Referent requested in proof request is not present in proof
+    MissingReferent,
+    // Proof contains referent which was not requested
+    UnexpectedReferent,
+    // Revealed value does not match the value in the cryptographic proof
+    EncodedValue,
+    // Restrictions of proof request are not satisfied
+    Restrictions,
+    // Non-revocation interval is requested but proof does not contain timestamp
+    NonRevocationTimestamp,
+    // Ledger entities required for verification are not provided
+    LedgerData,
+    // Cryptographic proof verification failed
+    CryptoProof,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -129,6 +129,26 @@ pub struct RevocationRegistryInfo {
     pub used_ids: HashSet<u32>
 }
 
+// Link to the registry which continues issuance when the current one is full.
+// Keeps public parts of the next registry as created: {"revoc_reg_def": .., "revoc_reg_entry": ..}
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RevocationRegistryRollover {
+    pub next_rev_reg_id: RevocationRegistryId,
+    pub next_rev_reg: serde_json::Value,
+}
+
+// Tag of the next registry in a rollover chain: trailing number is incremented ("TAG_1" -> "TAG_2"),
+// otherwise "_1" suffix is appended ("tag" -> "tag_1")
+pub fn next_rev_reg_tag(tag: &str) -> String {
+    let prefix = tag.trim_end_matches(|c: char| c.is_ascii_digit());
+    let digits = &tag[prefix.len()..];
+
+    match digits.parse::<u64>().ok().and_then(|num| num.checked_add(1)) {
+        Some(num) => format!("{}{:0width$}", prefix, num, width = digits.len()),
+        None => format!("{}_1", tag)
+    }
+}
+
 qualifiable_type!(RevocationRegistryId);
 
 impl RevocationRegistryId {
@@ -269,4 +289,20 @@ mod tests {
             _rev_reg_id_qualified().validate().unwrap();
         }
     }
+
+    mod next_rev_reg_tag {
+        use super::*;
+
+        #[test]
+        fn test_next_rev_reg_tag_for_tag_with_number() {
+            assert_eq!("TAG_2", next_rev_reg_tag(&_tag()));
+            assert_eq!("TAG_10", next_rev_reg_tag("TAG_9"));
+            assert_eq!("tag010", next_rev_reg_tag("tag009"));
+        }
+
+        #[test]
+        fn test_next_rev_reg_tag_for_tag_without_number() {
+            assert_eq!("tag_1", next_rev_reg_tag("tag"));
+        }
+    }
 }
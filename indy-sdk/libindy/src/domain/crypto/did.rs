@@ -26,6 +26,8 @@ pub struct MyDidInfo {
     pub crypto_type: Option<String>,
     pub cid: Option<bool>,
     pub method_name: Option<DidMethod>,
+    pub key_manager: Option<String>,
+    pub peer_numalgo: Option<u8>,
 }
 
 impl Validatable for MyDidInfo {
@@ -36,6 +38,14 @@ impl Validatable for MyDidInfo {
         if let Some(ref name) = self.method_name {
             name.validate()?
         }
+        if let Some(numalgo) = self.peer_numalgo {
+            if self.method_name.as_ref().map(|method| method.0.as_str()) != Some(qualifier::DID_PEER_METHOD) {
+                return Err("peer_numalgo can be set only for peer DID method".to_string());
+            }
+            if numalgo != 0 && numalgo != 2 {
+                return Err(format!("Unsupported did:peer numalgo: {}. Only 0 and 2 are supported.", numalgo));
+            }
+        }
         Ok(())
     }
 }
@@ -99,6 +109,10 @@ impl DidValue {
         DidValue(qualifier::to_unqualified(&self.0))
     }
 
+    pub fn is_self_certifying(&self) -> bool {
+        qualifier::is_self_certifying(&self.0)
+    }
+
     pub fn is_abbreviatable(&self) -> bool {
         match self.get_method() {
             Some(ref method) if method.starts_with("sov") => true,
@@ -177,6 +191,41 @@ pub struct TemporaryDid {
     pub verkey: String,
 }
 
+// Keys replaced by indy_replace_keys_apply (or indy_replace_keys_rollback), oldest first.
+// Each entry keeps the interval [activated_at, replaced_at) (unix timestamps in seconds) the key was active in.
+// Intervals follow each other, so the current key is active since "replaced_at" of the last entry.
+// The same key can have several entries if it was restored by indy_replace_keys_rollback.
+// "rolled_back" is set if the last key change was a rollback, as only a key change made by indy_replace_keys_apply can be rolled back.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct DidKeyHistory {
+    pub keys: Vec<DidKeyHistoryEntry>,
+    #[serde(default)]
+    pub rolled_back: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct DidKeyHistoryEntry {
+    pub verkey: String,
+    pub activated_at: u64,
+    pub replaced_at: u64,
+}
+
+impl DidKeyHistory {
+    // Time since which the current key is active, 0 if the key was never replaced
+    pub fn current_key_activated_at(&self) -> u64 {
+        self.keys.last()
+            .map(|entry| entry.replaced_at)
+            .unwrap_or(0)
+    }
+
+    // Returns the key that was active at the given time or None if it is the current one
+    pub fn key_active_at(&self, timestamp: u64) -> Option<&str> {
+        self.keys.iter()
+            .find(|entry| entry.activated_at <= timestamp && timestamp < entry.replaced_at)
+            .map(|entry| entry.verkey.as_str())
+    }
+}
+
 impl From<TemporaryDid> for Did {
     fn from(temp_did: TemporaryDid) -> Self {
         Did {
@@ -185,3 +234,38 @@ impl From<TemporaryDid> for Did {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn _entry(verkey: &str, activated_at: u64, replaced_at: u64) -> DidKeyHistoryEntry {
+        DidKeyHistoryEntry { verkey: verkey.to_string(), activated_at, replaced_at }
+    }
+
+    #[test]
+    fn key_active_at_works() {
+        let key_history = DidKeyHistory { keys: vec![_entry("A", 0, 100), _entry("B", 100, 200)], rolled_back: false };
+
+        assert_eq!(Some("A"), key_history.key_active_at(50));
+        assert_eq!(Some("B"), key_history.key_active_at(100));
+        assert_eq!(None, key_history.key_active_at(200));
+        assert_eq!(200, key_history.current_key_activated_at());
+    }
+
+    #[test]
+    fn key_active_at_works_for_restored_key() {
+        // "A" replaced by "B" at 100, restored at 200 and replaced by "C" at 300
+        let key_history = DidKeyHistory { keys: vec![_entry("A", 0, 100), _entry("B", 100, 200), _entry("A", 200, 300)], rolled_back: false };
+
+        assert_eq!(Some("A"), key_history.key_active_at(50));
+        assert_eq!(Some("B"), key_history.key_active_at(150));
+        assert_eq!(Some("A"), key_history.key_active_at(250));
+        assert_eq!(None, key_history.key_active_at(300));
+    }
+
+    #[test]
+    fn current_key_activated_at_works_for_empty_history() {
+        assert_eq!(0, DidKeyHistory::default().current_key_activated_at());
+    }
+}
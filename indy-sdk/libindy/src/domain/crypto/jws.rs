@@ -0,0 +1,84 @@
+use serde_json::{Map, Value};
+
+#[derive(Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum JwsSerialization {
+    #[serde(rename = "compact")]
+    Compact,
+    #[serde(rename = "json")]
+    Json,
+}
+
+impl Default for JwsSerialization {
+    fn default() -> Self {
+        JwsSerialization::Compact
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum JwsKidFormat {
+    #[serde(rename = "verkey")]
+    Verkey,
+    #[serde(rename = "did:key")]
+    DidKey,
+}
+
+impl Default for JwsKidFormat {
+    fn default() -> Self {
+        JwsKidFormat::Verkey
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Default, Eq, PartialEq)]
+pub struct JwsOptions {
+    #[serde(default)]
+    pub serialization: JwsSerialization,
+    #[serde(default)]
+    pub detached: bool,
+    #[serde(default)]
+    pub kid: JwsKidFormat,
+    // Additional protected header parameters. "alg" and "kid" are set by libindy.
+    #[serde(default)]
+    pub protected: Option<Map<String, Value>>,
+}
+
+// General JWS JSON Serialization
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct JwsJson {
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload: Option<String>,
+    pub signatures: Vec<JwsSignature>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct JwsSignature {
+    pub protected: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header: Option<Map<String, Value>>,
+    pub signature: String,
+}
+
+// Flattened JWS JSON Serialization
+#[derive(Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct FlattenedJwsJson {
+    #[serde(default)]
+    pub payload: Option<String>,
+    pub protected: String,
+    #[serde(default)]
+    pub header: Option<Map<String, Value>>,
+    pub signature: String,
+}
+
+impl From<FlattenedJwsJson> for JwsJson {
+    fn from(jws: FlattenedJwsJson) -> Self {
+        JwsJson {
+            payload: jws.payload,
+            signatures: vec![JwsSignature {
+                protected: jws.protected,
+                header: jws.header,
+                signature: jws.signature,
+            }],
+        }
+    }
+}
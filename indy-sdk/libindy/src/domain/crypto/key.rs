@@ -12,6 +12,9 @@ pub struct Key {
     pub signkey: String,
     #[cfg(test)]
     pub signkey: String,
+    // Name of the key manager that holds signkey (signkey is empty in this case)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_manager: Option<String>,
 }
 
 impl Key {
@@ -19,6 +22,15 @@ impl Key {
         Key {
             verkey,
             signkey,
+            key_manager: None,
+        }
+    }
+
+    pub fn new_external(verkey: String, key_manager: String) -> Key {
+        Key {
+            verkey,
+            signkey: String::new(),
+            key_manager: Some(key_manager),
         }
     }
 }
@@ -39,6 +51,7 @@ impl Drop for Key {
 pub struct KeyInfo {
     pub seed: Option<String>,
     pub crypto_type: Option<String>,
+    pub key_manager: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -2,3 +2,6 @@ pub mod key;
 pub mod did;
 pub mod combo_box;
 pub mod pack;
+pub mod jws;
+pub mod multi_sig;
+pub mod session;
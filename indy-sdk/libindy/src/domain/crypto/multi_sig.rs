@@ -0,0 +1,105 @@
+use std::collections::{HashMap, HashSet};
+
+use indy_api_types::validation::Validatable;
+
+use crate::utils::crypto::verkey_builder::split_verkey;
+
+#[derive(Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum MultiSigSerialization {
+    // Signatures are made over the message bytes as is
+    #[serde(rename = "raw")]
+    Raw,
+    // Message is a ledger request json. Signatures are made over its serialized form as in indy_multi_sign_request
+    #[serde(rename = "request")]
+    Request,
+}
+
+impl Default for MultiSigSerialization {
+    fn default() -> Self {
+        MultiSigSerialization::Raw
+    }
+}
+
+// M-of-N policy: at least "threshold" of "signers" must sign the message
+#[derive(Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct MultiSigPolicy {
+    pub threshold: usize,
+    pub signers: Vec<String>,
+    #[serde(default)]
+    pub serialization: MultiSigSerialization,
+}
+
+impl Validatable for MultiSigPolicy {
+    fn validate(&self) -> Result<(), String> {
+        if self.signers.is_empty() {
+            return Err("Multi-signature policy must contain at least one signer".to_string());
+        }
+
+        if let Some(signer) = self.signers.iter().find(|signer| signer.starts_with('~')) {
+            return Err(format!("Multi-signature policy contains abbreviated signer verkey: {}", signer));
+        }
+
+        // The same key can be spelled with or without its crypto type suffix
+        if self.signers.iter().map(|signer| split_verkey(signer)).collect::<HashSet<_>>().len() != self.signers.len() {
+            return Err("Multi-signature policy contains duplicated signers".to_string());
+        }
+
+        if self.threshold == 0 || self.threshold > self.signers.len() {
+            return Err(format!("Invalid multi-signature threshold: {}. It must be between 1 and the number of signers {}",
+                               self.threshold, self.signers.len()));
+        }
+
+        Ok(())
+    }
+}
+
+// Base58 encoded signatures by verkeys of the signers
+pub type MultiSignatures = HashMap<String, String>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn _policy(threshold: usize, signers: &[&str]) -> MultiSigPolicy {
+        MultiSigPolicy {
+            threshold,
+            signers: signers.iter().map(|signer| signer.to_string()).collect(),
+            serialization: MultiSigSerialization::Raw,
+        }
+    }
+
+    #[test]
+    fn multi_sig_policy_validate_works() {
+        _policy(1, &["a"]).validate().unwrap();
+        _policy(2, &["a", "b", "c"]).validate().unwrap();
+        _policy(3, &["a", "b", "c"]).validate().unwrap();
+    }
+
+    #[test]
+    fn multi_sig_policy_validate_not_works_for_invalid_threshold() {
+        _policy(0, &["a", "b"]).validate().unwrap_err();
+        _policy(3, &["a", "b"]).validate().unwrap_err();
+    }
+
+    #[test]
+    fn multi_sig_policy_validate_not_works_for_invalid_signers() {
+        _policy(1, &[]).validate().unwrap_err();
+        _policy(1, &["a", "a"]).validate().unwrap_err();
+        _policy(1, &["~a"]).validate().unwrap_err();
+    }
+
+    #[test]
+    fn multi_sig_policy_validate_not_works_for_same_signer_with_crypto_type() {
+        _policy(2, &["a", "a:ed25519"]).validate().unwrap_err();
+        _policy(1, &["a:ed25519", "b", "a"]).validate().unwrap_err();
+    }
+
+    #[test]
+    fn multi_sig_policy_deserialize_works_for_default_serialization() {
+        let policy: MultiSigPolicy = serde_json::from_str(r#"{"threshold": 1, "signers": ["a"]}"#).unwrap();
+        assert_eq!(MultiSigSerialization::Raw, policy.serialization);
+
+        let policy: MultiSigPolicy = serde_json::from_str(r#"{"threshold": 1, "signers": ["a"], "serialization": "request"}"#).unwrap();
+        assert_eq!(MultiSigSerialization::Request, policy.serialization);
+    }
+}
@@ -1,6 +1,10 @@
 #[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
 pub struct JWE {
     pub protected: String,
+    // Per-recipient data of JWE JSON serialization. Authcrypt/Anoncrypt envelopes keep it in "protected".
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recipients: Option<Vec<Recipient>>,
     pub iv: String,
     pub ciphertext: String,
     pub tag: String
@@ -38,4 +42,60 @@ pub struct UnpackMessage {
     pub recipient_verkey: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sender_verkey: Option<String>
-}
\ No newline at end of file
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct EcdhProtected {
+    pub typ: String,
+    pub alg: String,
+    pub enc: ContentEncryption,
+    pub epk: EphemeralPublicKey,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skid: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub apu: Option<String>,
+    pub apv: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct EphemeralPublicKey {
+    pub kty: String,
+    pub crv: String,
+    pub x: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ContentEncryption {
+    A256GCM,
+    XC20P,
+}
+
+impl Default for ContentEncryption {
+    fn default() -> Self {
+        ContentEncryption::A256GCM
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PackProfile {
+    #[serde(rename = "legacy")]
+    Legacy,
+    #[serde(rename = "ecdh")]
+    Ecdh,
+}
+
+impl Default for PackProfile {
+    fn default() -> Self {
+        PackProfile::Legacy
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Default, Eq, PartialEq)]
+pub struct PackOptions {
+    #[serde(default)]
+    pub profile: PackProfile,
+    #[serde(default)]
+    pub enc: ContentEncryption,
+}
@@ -0,0 +1,70 @@
+use std::collections::BTreeMap;
+
+use super::did::DidValue;
+
+// Max count of message keys kept for messages delivered out of order
+pub const MAX_SKIPPED_MESSAGE_KEYS: u64 = 100;
+
+// Handshake message that is exchanged by the parties to establish a session
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct SessionHandshake {
+    pub session_id: String,
+    pub sender_vk: String,
+    // Base58 encoded X25519 ephemeral public key of the sender
+    pub ephemeral_key: String,
+    // Base58 encoded signature of the handshake data by the sender key
+    pub signature: String,
+}
+
+impl SessionHandshake {
+    // Data signed by the sender. Binds the handshake to the recipient and,
+    // for the response, to the ephemeral key of the initiator.
+    pub fn signed_data(session_id: &str, ephemeral_key: &str, recipient_vk: &str, peer_ephemeral_key: Option<&str>) -> Vec<u8> {
+        [session_id, ephemeral_key, recipient_vk, peer_ephemeral_key.unwrap_or("")].join(".").into_bytes()
+    }
+}
+
+// Message encrypted by a session key
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SessionMessage {
+    pub session_id: String,
+    pub counter: u64,
+    pub iv: String,
+    pub ciphertext: String,
+    pub tag: String,
+}
+
+impl SessionMessage {
+    pub fn aad(session_id: &str, counter: u64) -> Vec<u8> {
+        format!("{}.{}", session_id, counter).into_bytes()
+    }
+}
+
+// Session state stored in the wallet
+#[derive(Serialize, Deserialize)]
+pub struct CryptoSession {
+    pub my_vk: String,
+    pub their_did: DidValue,
+    pub their_vk: String,
+    pub initiator: bool,
+    pub state: SessionState,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SessionState {
+    // Initiator waits for the handshake of the responder
+    Pending {
+        ephemeral_sk: String,
+        ephemeral_pk: String,
+    },
+    Established {
+        send_chain_key: String,
+        send_counter: u64,
+        recv_chain_key: String,
+        recv_counter: u64,
+        // Keys of messages that were skipped by counter, but not received yet
+        #[serde(default)]
+        skipped_keys: BTreeMap<u64, String>,
+    },
+}
@@ -84,15 +84,27 @@ pub struct AttribData {
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Endpoint {
-    pub ha: String, // indy-node and indy-plenum restrict this to ip-address:port
-    pub verkey: Option<String>
+    // indy-node and indy-plenum restrict this to ip-address:port.
+    // ATTRIBs written by Aries agents use "endpoint" with a full URL instead.
+    #[serde(alias = "endpoint")]
+    pub ha: String,
+    pub verkey: Option<String>,
+    #[serde(default)]
+    #[serde(rename = "routingKeys")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub routing_keys: Option<Vec<String>>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub types: Option<Vec<String>>,
 }
 
 impl Endpoint {
     pub fn new(ha: String, verkey: Option<String>) -> Endpoint {
         Endpoint {
             ha,
-            verkey
+            verkey,
+            routing_keys: None,
+            types: None,
         }
     }
 }
@@ -1,5 +1,6 @@
 pub mod anoncreds;
 pub mod crypto;
+pub mod did_doc;
 pub mod ledger;
 pub mod pairwise;
 pub mod pool;
@@ -1,3 +1,5 @@
+use indy_api_types::domain::wallet::Tags;
+
 use super::crypto::did::DidValue;
 
 #[derive(Serialize, Deserialize)]
@@ -22,4 +24,19 @@ impl From<Pairwise> for PairwiseInfo {
             metadata: pairwise.metadata
         }
     }
-}
\ No newline at end of file
+}
+
+// Pairwise returned by pairwise search together with its tags
+#[derive(Serialize, Deserialize)]
+pub struct PairwiseRecord {
+    #[serde(flatten)]
+    pub pairwise: Pairwise,
+    pub tags: Tags,
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize)]
+pub struct PairwiseSearchRecords {
+    pub total_count: Option<usize>,
+    pub records: Option<Vec<PairwiseRecord>>,
+}
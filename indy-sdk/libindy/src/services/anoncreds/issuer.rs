@@ -1,3 +1,5 @@
+use std::collections::BTreeSet;
+
 use ursa::cl::{
     CredentialKeyCorrectnessProof,
     CredentialPrivateKey,
@@ -178,4 +180,20 @@ impl Issuer {
 
         Ok(rev_reg_delta)
     }
+
+    pub fn update_revocation_registry<RTA>(&self,
+                                           rev_reg: &mut RevocationRegistry,
+                                           max_cred_num: u32,
+                                           issued: BTreeSet<u32>,
+                                           revoked: BTreeSet<u32>,
+                                           rev_tails_accessor: &RTA) -> IndyResult<RevocationRegistryDelta> where RTA: RevocationTailsAccessor {
+        trace!("update_revocation_registry >>> rev_reg: {:?}, max_cred_num: {:?}, issued: {:?}, revoked: {:?}",
+               rev_reg, max_cred_num, secret!(&issued), secret!(&revoked));
+
+        let rev_reg_delta = CryptoIssuer::update_revocation_registry(rev_reg, max_cred_num, issued, revoked, rev_tails_accessor)?;
+
+        trace!("update_revocation_registry <<< rev_reg_delta {:?}", rev_reg_delta);
+
+        Ok(rev_reg_delta)
+    }
 }
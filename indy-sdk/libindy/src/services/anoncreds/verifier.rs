@@ -1,7 +1,8 @@
 use std::collections::{HashMap, HashSet};
 
 use crate::domain::anoncreds::credential_definition::{CredentialDefinitionV1, CredentialDefinitionId};
-use crate::domain::anoncreds::proof::{Proof, RequestedProof, Identifier, RevealedAttributeInfo};
+use crate::domain::anoncreds::proof::{Proof, RequestedProof, Identifier, RevealedAttributeInfo, RevealedAttributeGroupInfo,
+                                      ProofVerificationReport, ReferentVerificationResult, VerificationFailure, VerificationCheck};
 use crate::domain::anoncreds::proof_request::{AttributeInfo, PredicateInfo, ProofRequestPayload, NonRevocedInterval};
 use crate::domain::anoncreds::revocation_registry::RevocationRegistryV1;
 use crate::domain::anoncreds::revocation_registry_definition::{RevocationRegistryDefinitionV1, RevocationRegistryId};
@@ -9,6 +10,7 @@ use crate::domain::anoncreds::schema::{SchemaV1, SchemaId};
 use indy_api_types::errors::prelude::*;
 use crate::services::anoncreds::helpers::*;
 
+use failure::Fail;
 use ursa::bn::BigNumber;
 use ursa::cl::{CredentialPublicKey, new_nonce, Nonce};
 use ursa::cl::verifier::Verifier as CryptoVerifier;
@@ -73,38 +75,147 @@ impl Verifier {
                                                              &received_self_attested_attrs,
                                                              &received_predicates)?;
 
-        let mut proof_verifier = CryptoVerifier::new_proof_verifier()?;
-        let non_credential_schema = build_non_credential_schema()?;
+        let valid = Verifier::_verify_crypto_proof(full_proof, proof_req, schemas, cred_defs, rev_reg_defs, rev_regs)?;
 
-        for sub_proof_index in 0..full_proof.identifiers.len() {
-            let identifier = full_proof.identifiers[sub_proof_index].clone();
+        trace!("verify <<< valid: {:?}", valid);
+
+        Ok(valid)
+    }
+
+    pub fn verify_with_report(&self,
+                              full_proof: &Proof,
+                              proof_req: &ProofRequestPayload,
+                              schemas: &HashMap<SchemaId, SchemaV1>,
+                              cred_defs: &HashMap<CredentialDefinitionId, CredentialDefinitionV1>,
+                              rev_reg_defs: &HashMap<RevocationRegistryId, RevocationRegistryDefinitionV1>,
+                              rev_regs: &HashMap<RevocationRegistryId, HashMap<u64, RevocationRegistryV1>>) -> IndyResult<ProofVerificationReport> {
+        trace!("verify_with_report >>> full_proof: {:?}, proof_req: {:?}, schemas: {:?}, cred_defs: {:?}, rev_reg_defs: {:?} rev_regs: {:?}",
+               full_proof, proof_req, schemas, cred_defs, rev_reg_defs, rev_regs);
+
+        let mut report = ProofVerificationReport::default();
+
+        for referent in proof_req.requested_attributes.keys() {
+            report.requested_attributes.insert(referent.to_string(), ReferentVerificationResult::default());
+        }
+
+        for referent in proof_req.requested_predicates.keys() {
+            report.requested_predicates.insert(referent.to_string(), ReferentVerificationResult::default());
+        }
+
+        let received = Verifier::_received_revealed_attrs(&full_proof)
+            .and_then(|revealed_attrs| Ok((revealed_attrs,
+                                           Verifier::_received_unrevealed_attrs(&full_proof)?,
+                                           Verifier::_received_predicates(&full_proof)?)));
+
+        let (received_revealed_attrs, received_unrevealed_attrs, received_predicates) = match received {
+            Ok(received) => received,
+            Err(err) => {
+                report.errors.push(VerificationFailure { check: VerificationCheck::Structure, reason: Verifier::_failure_reason(&err) });
+                return Ok(Verifier::_complete_report(report));
+            }
+        };
+        let received_self_attested_attrs: HashSet<String> = Verifier::_received_self_attested_attrs(&full_proof);
+
+        // All following checks rely on correspondence of proof and proof request referents
+        if !Verifier::_compare_referents_for_report(&mut report,
+                                                    proof_req,
+                                                    &received_revealed_attrs,
+                                                    &received_unrevealed_attrs,
+                                                    &received_self_attested_attrs,
+                                                    &received_predicates) {
+            return Ok(Verifier::_complete_report(report));
+        }
+
+        for (referent, attr_info) in full_proof.requested_proof.revealed_attrs.iter() {
+            if let Err(err) = Verifier::_verify_revealed_attr(proof_req, full_proof, referent, attr_info) {
+                Verifier::_add_referent_failure(&mut report.requested_attributes, referent, VerificationCheck::EncodedValue, &err);
+            }
+        }
+
+        for (referent, attr_infos) in full_proof.requested_proof.revealed_attr_groups.iter() {
+            if let Err(err) = Verifier::_verify_revealed_attr_group(proof_req, full_proof, referent, attr_infos) {
+                let check = if err.kind() == IndyErrorKind::InvalidStructure { VerificationCheck::Structure } else { VerificationCheck::EncodedValue };
+                Verifier::_add_referent_failure(&mut report.requested_attributes, referent, check, &err);
+            }
+        }
+
+        let proof_attr_identifiers: HashMap<String, Identifier> = received_revealed_attrs
+            .iter()
+            .chain(&received_unrevealed_attrs)
+            .map(|(r, id)| (r.to_string(), id.clone()))
+            .collect();
+
+        let requested_attrs = Verifier::_requested_non_self_attested_attrs(proof_req, &received_self_attested_attrs);
+
+        for (referent, info) in requested_attrs.iter() {
+            if let Err(err) = Verifier::_verify_attr_restrictions(proof_req, &full_proof.requested_proof, referent, info, &proof_attr_identifiers) {
+                Verifier::_add_referent_failure(&mut report.requested_attributes, referent, VerificationCheck::Restrictions, &err);
+            }
+        }
 
-            let schema: &SchemaV1 = schemas.get(&identifier.schema_id)
-                .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Schema not found for id: {:?}", identifier.schema_id)))?;
+        for (referent, info) in proof_req.requested_predicates.iter() {
+            if let Err(err) = Verifier::_verify_predicate_restrictions(&full_proof.requested_proof, referent, info, &requested_attrs, &received_predicates) {
+                Verifier::_add_referent_failure(&mut report.requested_predicates, referent, VerificationCheck::Restrictions, &err);
+            }
+        }
+
+        for (referent, info) in proof_req.requested_attributes.iter() {
+            if received_self_attested_attrs.contains(referent) {
+                continue;
+            }
+
+            let received_ = if received_revealed_attrs.contains_key(referent) { &received_revealed_attrs } else { &received_unrevealed_attrs };
+
+            if Verifier::_validate_timestamp(received_, referent, &proof_req.non_revoked, &info.non_revoked).is_err() {
+                let err = err_msg(IndyErrorKind::InvalidStructure, "Non-revocation interval is requested but proof does not contain timestamp");
+                Verifier::_add_referent_failure(&mut report.requested_attributes, referent, VerificationCheck::NonRevocationTimestamp, &err);
+            }
+        }
 
-            let cred_def: &CredentialDefinitionV1 = cred_defs.get(&identifier.cred_def_id)
-                .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("CredentialDefinition not found for id: {:?}", identifier.cred_def_id)))?;
+        for (referent, info) in proof_req.requested_predicates.iter() {
+            if Verifier::_validate_timestamp(&received_predicates, referent, &proof_req.non_revoked, &info.non_revoked).is_err() {
+                let err = err_msg(IndyErrorKind::InvalidStructure, "Non-revocation interval is requested but proof does not contain timestamp");
+                Verifier::_add_referent_failure(&mut report.requested_predicates, referent, VerificationCheck::NonRevocationTimestamp, &err);
+            }
+        }
 
-            let (rev_reg_def, rev_reg) =
-                if let Some(timestamp) = identifier.timestamp {
-                    let rev_reg_id = identifier.rev_reg_id
-                        .clone()
-                        .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Revocation Registry Id not found"))?;
+        let ledger_data_errors: Vec<VerificationFailure> = full_proof.identifiers
+            .iter()
+            .filter_map(|identifier| Verifier::_get_ledger_data(identifier, schemas, cred_defs, rev_reg_defs, rev_regs).err())
+            .map(|err| VerificationFailure { check: VerificationCheck::LedgerData, reason: Verifier::_failure_reason(&err) })
+            .collect();
 
-                    let rev_reg_def = Some(rev_reg_defs
-                        .get(&rev_reg_id)
-                        .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("RevocationRegistryDefinition not found for id: {:?}", identifier.rev_reg_id)))?);
+        if ledger_data_errors.is_empty() {
+            match Verifier::_verify_crypto_proof(full_proof, proof_req, schemas, cred_defs, rev_reg_defs, rev_regs) {
+                Ok(true) => {}
+                Ok(false) => report.errors.push(VerificationFailure { check: VerificationCheck::CryptoProof, reason: "Cryptographic proof is not valid".to_string() }),
+                Err(err) => report.errors.push(VerificationFailure { check: VerificationCheck::CryptoProof, reason: Verifier::_failure_reason(&err) }),
+            }
+        } else {
+            report.errors.extend(ledger_data_errors);
+        }
 
-                    let rev_regs_for_cred = rev_regs
-                        .get(&rev_reg_id)
-                        .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("RevocationRegistry not found for id: {:?}", rev_reg_id)))?;
+        let report = Verifier::_complete_report(report);
 
-                    let rev_reg = Some(rev_regs_for_cred
-                        .get(&timestamp)
-                        .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("RevocationRegistry not found for timestamp: {:?}", timestamp)))?);
+        trace!("verify_with_report <<< report: {:?}", report);
 
-                    (rev_reg_def, rev_reg)
-                } else { (None, None) };
+        Ok(report)
+    }
+
+    fn _verify_crypto_proof(full_proof: &Proof,
+                            proof_req: &ProofRequestPayload,
+                            schemas: &HashMap<SchemaId, SchemaV1>,
+                            cred_defs: &HashMap<CredentialDefinitionId, CredentialDefinitionV1>,
+                            rev_reg_defs: &HashMap<RevocationRegistryId, RevocationRegistryDefinitionV1>,
+                            rev_regs: &HashMap<RevocationRegistryId, HashMap<u64, RevocationRegistryV1>>) -> IndyResult<bool> {
+        let mut proof_verifier = CryptoVerifier::new_proof_verifier()?;
+        let non_credential_schema = build_non_credential_schema()?;
+
+        for sub_proof_index in 0..full_proof.identifiers.len() {
+            let identifier = &full_proof.identifiers[sub_proof_index];
+
+            let (schema, cred_def, rev_reg_def, rev_reg) =
+                Verifier::_get_ledger_data(identifier, schemas, cred_defs, rev_reg_defs, rev_regs)?;
 
             let attrs_for_credential = Verifier::_get_revealed_attributes_for_credential(sub_proof_index, &full_proof.requested_proof, proof_req)?;
             let predicates_for_credential = Verifier::_get_predicates_for_credential(sub_proof_index, &full_proof.requested_proof, proof_req)?;
@@ -118,15 +229,123 @@ impl Verifier {
                                                  &credential_schema,
                                                  &non_credential_schema,
                                                  &credential_pub_key,
-                                                 rev_reg_def.as_ref().map(|r_reg_def| &r_reg_def.value.public_keys.accum_key),
-                                                 rev_reg.as_ref().map(|r_reg| &r_reg.value))?;
+                                                 rev_reg_def.map(|r_reg_def| &r_reg_def.value.public_keys.accum_key),
+                                                 rev_reg.map(|r_reg| &r_reg.value))?;
         }
 
-        let valid = proof_verifier.verify(&full_proof.proof, &proof_req.nonce)?;
+        Ok(proof_verifier.verify(&full_proof.proof, &proof_req.nonce)?)
+    }
 
-        trace!("verify <<< valid: {:?}", valid);
+    fn _get_ledger_data<'a>(identifier: &Identifier,
+                            schemas: &'a HashMap<SchemaId, SchemaV1>,
+                            cred_defs: &'a HashMap<CredentialDefinitionId, CredentialDefinitionV1>,
+                            rev_reg_defs: &'a HashMap<RevocationRegistryId, RevocationRegistryDefinitionV1>,
+                            rev_regs: &'a HashMap<RevocationRegistryId, HashMap<u64, RevocationRegistryV1>>)
+                            -> IndyResult<(&'a SchemaV1, &'a CredentialDefinitionV1, Option<&'a RevocationRegistryDefinitionV1>, Option<&'a RevocationRegistryV1>)> {
+        let schema: &SchemaV1 = schemas.get(&identifier.schema_id)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Schema not found for id: {:?}", identifier.schema_id)))?;
 
-        Ok(valid)
+        let cred_def: &CredentialDefinitionV1 = cred_defs.get(&identifier.cred_def_id)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("CredentialDefinition not found for id: {:?}", identifier.cred_def_id)))?;
+
+        let (rev_reg_def, rev_reg) =
+            if let Some(timestamp) = identifier.timestamp {
+                let rev_reg_id = identifier.rev_reg_id
+                    .clone()
+                    .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Revocation Registry Id not found"))?;
+
+                let rev_reg_def = Some(rev_reg_defs
+                    .get(&rev_reg_id)
+                    .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("RevocationRegistryDefinition not found for id: {:?}", identifier.rev_reg_id)))?);
+
+                let rev_regs_for_cred = rev_regs
+                    .get(&rev_reg_id)
+                    .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("RevocationRegistry not found for id: {:?}", rev_reg_id)))?;
+
+                let rev_reg = Some(rev_regs_for_cred
+                    .get(&timestamp)
+                    .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("RevocationRegistry not found for timestamp: {:?}", timestamp)))?);
+
+                (rev_reg_def, rev_reg)
+            } else { (None, None) };
+
+        Ok((schema, cred_def, rev_reg_def, rev_reg))
+    }
+
+    fn _compare_referents_for_report(report: &mut ProofVerificationReport,
+                                     proof_req: &ProofRequestPayload,
+                                     received_revealed_attrs: &HashMap<String, Identifier>,
+                                     received_unrevealed_attrs: &HashMap<String, Identifier>,
+                                     received_self_attested_attrs: &HashSet<String>,
+                                     received_predicates: &HashMap<String, Identifier>) -> bool {
+        let received_attrs: HashSet<&String> = received_revealed_attrs.keys()
+            .chain(received_unrevealed_attrs.keys())
+            .chain(received_self_attested_attrs.iter())
+            .collect();
+
+        let mut consistent = true;
+
+        for referent in proof_req.requested_attributes.keys() {
+            if !received_attrs.contains(referent) {
+                let err = err_msg(IndyErrorKind::InvalidStructure, format!("Requested attribute \"{}\" is not present in proof", referent));
+                Verifier::_add_referent_failure(&mut report.requested_attributes, referent, VerificationCheck::MissingReferent, &err);
+                consistent = false;
+            }
+        }
+
+        for referent in proof_req.requested_predicates.keys() {
+            if !received_predicates.contains_key(referent) {
+                let err = err_msg(IndyErrorKind::InvalidStructure, format!("Requested predicate \"{}\" is not present in proof", referent));
+                Verifier::_add_referent_failure(&mut report.requested_predicates, referent, VerificationCheck::MissingReferent, &err);
+                consistent = false;
+            }
+        }
+
+        for referent in received_attrs.iter().filter(|referent| !proof_req.requested_attributes.contains_key(referent.as_str())) {
+            report.errors.push(VerificationFailure {
+                check: VerificationCheck::UnexpectedReferent,
+                reason: format!("Attribute \"{}\" is not requested in proof request", referent),
+            });
+            consistent = false;
+        }
+
+        for referent in received_predicates.keys().filter(|referent| !proof_req.requested_predicates.contains_key(referent.as_str())) {
+            report.errors.push(VerificationFailure {
+                check: VerificationCheck::UnexpectedReferent,
+                reason: format!("Predicate \"{}\" is not requested in proof request", referent),
+            });
+            consistent = false;
+        }
+
+        consistent
+    }
+
+    fn _add_referent_failure(results: &mut HashMap<String, ReferentVerificationResult>,
+                             referent: &str,
+                             check: VerificationCheck,
+                             err: &IndyError) {
+        if let Some(result) = results.get_mut(referent) {
+            result.errors.push(VerificationFailure { check, reason: Verifier::_failure_reason(err) });
+        }
+    }
+
+    fn _failure_reason(err: &IndyError) -> String {
+        let causes: Vec<String> = <dyn Fail>::iter_causes(err)
+            .map(|cause| cause.to_string())
+            .collect();
+
+        if causes.is_empty() { err.kind().to_string() } else { causes.join(": ") }
+    }
+
+    fn _complete_report(mut report: ProofVerificationReport) -> ProofVerificationReport {
+        for result in report.requested_attributes.values_mut().chain(report.requested_predicates.values_mut()) {
+            result.valid = result.errors.is_empty();
+        }
+
+        report.valid = report.errors.is_empty() &&
+            report.requested_attributes.values().chain(report.requested_predicates.values()).all(|result| result.valid);
+
+        report
     }
 
     pub fn generate_nonce(&self) -> IndyResult<Nonce> {
@@ -325,34 +544,48 @@ impl Verifier {
     fn _verify_revealed_attribute_values(proof_req: &ProofRequestPayload,
                                          proof: &Proof) -> IndyResult<()> {
         for (attr_referent, attr_info) in proof.requested_proof.revealed_attrs.iter() {
-
-            let attr_name = proof_req.requested_attributes.get(attr_referent)
-                .as_ref()
-                .ok_or(IndyError::from_msg(IndyErrorKind::ProofRejected, format!("Attribute with referent \"{}\" not found in ProofRequests", attr_referent)))?
-                .name.as_ref()
-                .ok_or(IndyError::from_msg(IndyErrorKind::ProofRejected, format!("Attribute with referent \"{}\" not found in ProofRequests", attr_referent)))?;
-            Verifier::_verify_revealed_attribute_value(attr_name.as_str(), proof, &attr_info)?;
+            Verifier::_verify_revealed_attr(proof_req, proof, attr_referent, attr_info)?;
         }
 
         for (attr_referent, attr_infos) in proof.requested_proof.revealed_attr_groups.iter() {
-            let attr_names = proof_req.requested_attributes.get(attr_referent)
-                .as_ref()
-                .ok_or(IndyError::from_msg(IndyErrorKind::ProofRejected, format!("Attribute with referent \"{}\" not found in ProofRequests", attr_referent)))?
-                .names.as_ref()
-                .ok_or(IndyError::from_msg(IndyErrorKind::ProofRejected, format!("Attribute with referent \"{}\" not found in ProofRequests", attr_referent)))?;
-            if attr_infos.values.len() != attr_names.len() {
-                error!("Proof Revealed Attr Group does not match Proof Request Attribute Group, proof request attrs: {:?}, referent: {:?}, attr_infos: {:?}", proof_req.requested_attributes, attr_referent, attr_infos);
-                return Err(IndyError::from_msg(IndyErrorKind::InvalidStructure, "Proof Revealed Attr Group does not match Proof Request Attribute Group"))
-            }
-            for attr_name in attr_names {
-                let attr_info = &attr_infos.values.get(attr_name)
-                    .ok_or(IndyError::from_msg(IndyErrorKind::InvalidStructure, "Proof Revealed Attr Group does not match Proof Request Attribute Group"))?;
-                Verifier::_verify_revealed_attribute_value(attr_name, proof, &RevealedAttributeInfo {
-                    sub_proof_index: attr_infos.sub_proof_index,
-                    raw: attr_info.raw.clone(),
-                    encoded: attr_info.encoded.clone()
-                })?;
-            }
+            Verifier::_verify_revealed_attr_group(proof_req, proof, attr_referent, attr_infos)?;
+        }
+        Ok(())
+    }
+
+    fn _verify_revealed_attr(proof_req: &ProofRequestPayload,
+                             proof: &Proof,
+                             attr_referent: &str,
+                             attr_info: &RevealedAttributeInfo) -> IndyResult<()> {
+        let attr_name = proof_req.requested_attributes.get(attr_referent)
+            .as_ref()
+            .ok_or(IndyError::from_msg(IndyErrorKind::ProofRejected, format!("Attribute with referent \"{}\" not found in ProofRequests", attr_referent)))?
+            .name.as_ref()
+            .ok_or(IndyError::from_msg(IndyErrorKind::ProofRejected, format!("Attribute with referent \"{}\" not found in ProofRequests", attr_referent)))?;
+        Verifier::_verify_revealed_attribute_value(attr_name.as_str(), proof, &attr_info)
+    }
+
+    fn _verify_revealed_attr_group(proof_req: &ProofRequestPayload,
+                                   proof: &Proof,
+                                   attr_referent: &str,
+                                   attr_infos: &RevealedAttributeGroupInfo) -> IndyResult<()> {
+        let attr_names = proof_req.requested_attributes.get(attr_referent)
+            .as_ref()
+            .ok_or(IndyError::from_msg(IndyErrorKind::ProofRejected, format!("Attribute with referent \"{}\" not found in ProofRequests", attr_referent)))?
+            .names.as_ref()
+            .ok_or(IndyError::from_msg(IndyErrorKind::ProofRejected, format!("Attribute with referent \"{}\" not found in ProofRequests", attr_referent)))?;
+        if attr_infos.values.len() != attr_names.len() {
+            error!("Proof Revealed Attr Group does not match Proof Request Attribute Group, proof request attrs: {:?}, referent: {:?}, attr_infos: {:?}", proof_req.requested_attributes, attr_referent, attr_infos);
+            return Err(IndyError::from_msg(IndyErrorKind::InvalidStructure, "Proof Revealed Attr Group does not match Proof Request Attribute Group"))
+        }
+        for attr_name in attr_names {
+            let attr_info = &attr_infos.values.get(attr_name)
+                .ok_or(IndyError::from_msg(IndyErrorKind::InvalidStructure, "Proof Revealed Attr Group does not match Proof Request Attribute Group"))?;
+            Verifier::_verify_revealed_attribute_value(attr_name, proof, &RevealedAttributeInfo {
+                sub_proof_index: attr_infos.sub_proof_index,
+                raw: attr_info.raw.clone(),
+                encoded: attr_info.encoded.clone()
+            })?;
         }
         Ok(())
     }
@@ -392,77 +625,102 @@ impl Verifier {
             .map(|(r, id)| (r.to_string(), id.clone()))
             .collect();
 
-        let requested_attrs: HashMap<String, AttributeInfo> = proof_req.requested_attributes
+        let requested_attrs = Verifier::_requested_non_self_attested_attrs(proof_req, self_attested_attrs);
+
+        for (referent, info) in requested_attrs.iter() {
+            Verifier::_verify_attr_restrictions(proof_req, requested_proof, referent, info, &proof_attr_identifiers)?;
+        }
+
+        for (referent, info) in proof_req.requested_predicates.iter() {
+            Verifier::_verify_predicate_restrictions(requested_proof, referent, info, &requested_attrs, received_predicates)?;
+        }
+
+        Ok(())
+    }
+
+    fn _requested_non_self_attested_attrs(proof_req: &ProofRequestPayload,
+                                          self_attested_attrs: &HashSet<String>) -> HashMap<String, AttributeInfo> {
+        proof_req.requested_attributes
             .iter()
             .filter(|&(referent, info)| !Verifier::_is_self_attested(&referent, &info, self_attested_attrs))
             .map(|(referent, info)| (referent.to_string(), info.clone()))
-            .collect();
-
-        for (referent, info) in requested_attrs.clone() {
-            if let Some(ref query) = info.restrictions {
-                let filter = Verifier::_gather_filter_info(&referent, &proof_attr_identifiers)?;
-
-                let name_value_map: HashMap<String, Option<&str>> = if let Some(name) = info.name {
-                    let mut map = HashMap::new();
-                    map.insert(name.clone(), requested_proof.revealed_attrs.get(&referent).map(|attr| attr.raw.as_str()));
-                    map
-                } else if let Some(names) = info.names {
-                    let mut map = HashMap::new();
-                    let attrs = requested_proof.revealed_attr_groups.get(&referent)
-                        .ok_or(IndyError::from_msg(IndyErrorKind::InvalidStructure, "Proof does not have referent from proof request"))?;
-                    for name in names {
-                        let val = attrs.values.get(&name).map(|attr| attr.raw.as_str());
-                        map.insert(name, val);
-                    }
-                    map
-                } else {
-                    error!(r#"Proof Request attribute restriction should contain "name" or "names" param. Current proof request: {:?}"#, proof_req);
-                    return Err(IndyError::from_msg(IndyErrorKind::InvalidStructure, r#"Proof Request attribute restriction should contain "name" or "names" param"#));
-                };
+            .collect()
+    }
 
-                Verifier::_do_process_operator(&name_value_map, &query, &filter)
-                    .map_err(|err| err.extend(format!("Requested restriction validation failed for \"{:?}\" attributes", &name_value_map)))?;
-            }
+    fn _verify_attr_restrictions(proof_req: &ProofRequestPayload,
+                                 requested_proof: &RequestedProof,
+                                 referent: &str,
+                                 info: &AttributeInfo,
+                                 proof_attr_identifiers: &HashMap<String, Identifier>) -> IndyResult<()> {
+        if let Some(ref query) = info.restrictions {
+            let filter = Verifier::_gather_filter_info(&referent, proof_attr_identifiers)?;
+
+            let name_value_map: HashMap<String, Option<&str>> = if let Some(ref name) = info.name {
+                let mut map = HashMap::new();
+                map.insert(name.clone(), requested_proof.revealed_attrs.get(referent).map(|attr| attr.raw.as_str()));
+                map
+            } else if let Some(ref names) = info.names {
+                let mut map = HashMap::new();
+                let attrs = requested_proof.revealed_attr_groups.get(referent)
+                    .ok_or(IndyError::from_msg(IndyErrorKind::InvalidStructure, "Proof does not have referent from proof request"))?;
+                for name in names {
+                    let val = attrs.values.get(name).map(|attr| attr.raw.as_str());
+                    map.insert(name.clone(), val);
+                }
+                map
+            } else {
+                error!(r#"Proof Request attribute restriction should contain "name" or "names" param. Current proof request: {:?}"#, proof_req);
+                return Err(IndyError::from_msg(IndyErrorKind::InvalidStructure, r#"Proof Request attribute restriction should contain "name" or "names" param"#));
+            };
+
+            Verifier::_do_process_operator(&name_value_map, &query, &filter)
+                .map_err(|err| err.extend(format!("Requested restriction validation failed for \"{:?}\" attributes", &name_value_map)))?;
         }
 
-        for (referent, info) in proof_req.requested_predicates.iter() {
-            if let Some(ref query) = info.restrictions {
-                let filter = Verifier::_gather_filter_info(&referent, received_predicates)?;
-
-                // start with the predicate requested attribute, which is un-revealed
-                let mut attr_value_map = HashMap::new();
-                attr_value_map.insert(info.name.to_string(), None);
-
-                // include any revealed attributes for the same credential (based on sub_proof_index)
-                let pred_sub_proof_index = requested_proof.predicates.get(referent).unwrap().sub_proof_index;
-                for attr_referent in requested_proof.revealed_attrs.keys() {
-                    let attr_info = requested_proof.revealed_attrs.get(attr_referent).unwrap();
-                    let attr_sub_proof_index = attr_info.sub_proof_index;
-                    if pred_sub_proof_index == attr_sub_proof_index {
-                        let attr_name = requested_attrs.get(attr_referent).unwrap().name.clone();
-                        if let Some(name) = attr_name {
-                            attr_value_map.insert(name, Some(attr_info.raw.as_str()));
-                        }
+        Ok(())
+    }
+
+    fn _verify_predicate_restrictions(requested_proof: &RequestedProof,
+                                      referent: &str,
+                                      info: &PredicateInfo,
+                                      requested_attrs: &HashMap<String, AttributeInfo>,
+                                      received_predicates: &HashMap<String, Identifier>) -> IndyResult<()> {
+        if let Some(ref query) = info.restrictions {
+            let filter = Verifier::_gather_filter_info(&referent, received_predicates)?;
+
+            // start with the predicate requested attribute, which is un-revealed
+            let mut attr_value_map = HashMap::new();
+            attr_value_map.insert(info.name.to_string(), None);
+
+            // include any revealed attributes for the same credential (based on sub_proof_index)
+            let pred_sub_proof_index = requested_proof.predicates.get(referent).unwrap().sub_proof_index;
+            for attr_referent in requested_proof.revealed_attrs.keys() {
+                let attr_info = requested_proof.revealed_attrs.get(attr_referent).unwrap();
+                let attr_sub_proof_index = attr_info.sub_proof_index;
+                if pred_sub_proof_index == attr_sub_proof_index {
+                    let attr_name = requested_attrs.get(attr_referent).unwrap().name.clone();
+                    if let Some(name) = attr_name {
+                        attr_value_map.insert(name, Some(attr_info.raw.as_str()));
                     }
                 }
-                for attr_referent in requested_proof.revealed_attr_groups.keys() {
-                    let attr_info = requested_proof.revealed_attr_groups.get(attr_referent).unwrap();
-                    let attr_sub_proof_index = attr_info.sub_proof_index;
-                    if pred_sub_proof_index == attr_sub_proof_index {
-                        for name in attr_info.values.keys() {
-                            let raw_val = attr_info.values.get(name).unwrap().raw.as_str();
-                            attr_value_map.insert(name.clone(), Some(raw_val.clone()));
-                        }
+            }
+            for attr_referent in requested_proof.revealed_attr_groups.keys() {
+                let attr_info = requested_proof.revealed_attr_groups.get(attr_referent).unwrap();
+                let attr_sub_proof_index = attr_info.sub_proof_index;
+                if pred_sub_proof_index == attr_sub_proof_index {
+                    for name in attr_info.values.keys() {
+                        let raw_val = attr_info.values.get(name).unwrap().raw.as_str();
+                        attr_value_map.insert(name.clone(), Some(raw_val.clone()));
                     }
                 }
+            }
 
-                Verifier::_do_process_operator(&attr_value_map, &query, &filter)
-                    .map_err(|err| err.extend(format!("Requested restriction validation failed for \"{}\" predicate", &info.name)))?;
+            Verifier::_do_process_operator(&attr_value_map, &query, &filter)
+                .map_err(|err| err.extend(format!("Requested restriction validation failed for \"{}\" predicate", &info.name)))?;
 
-                // old style :-/ which fails for attribute restrictions on predicates
-                //Verifier::_process_operator(&info.name, &query, &filter, None)
-                //    .map_err(|err| err.extend(format!("Requested restriction validation failed for \"{}\" predicate", &info.name)))?;
-            }
+            // old style :-/ which fails for attribute restrictions on predicates
+            //Verifier::_process_operator(&info.name, &query, &filter, None)
+            //    .map_err(|err| err.extend(format!("Requested restriction validation failed for \"{}\" predicate", &info.name)))?;
         }
 
         Ok(())
@@ -969,4 +1227,44 @@ mod tests {
         Verifier::_validate_timestamp(&_received(), "referent_2", &None, &Some(_interval())).unwrap_err();
         Verifier::_validate_timestamp(&_received(), "referent_3", &None, &Some(_interval())).unwrap_err();
     }
+
+    #[test]
+    fn complete_report_works() {
+        let mut report = ProofVerificationReport::default();
+        report.requested_attributes.insert("referent_1".to_string(), ReferentVerificationResult::default());
+        report.requested_predicates.insert("referent_2".to_string(), ReferentVerificationResult::default());
+
+        let report = Verifier::_complete_report(report);
+        assert!(report.valid);
+        assert!(report.requested_attributes["referent_1"].valid);
+        assert!(report.requested_predicates["referent_2"].valid);
+    }
+
+    #[test]
+    fn complete_report_works_for_failed_referent() {
+        let mut report = ProofVerificationReport::default();
+        report.requested_attributes.insert("referent_1".to_string(), ReferentVerificationResult::default());
+        report.requested_predicates.insert("referent_2".to_string(), ReferentVerificationResult::default());
+
+        let err = err_msg(IndyErrorKind::ProofRejected, "Restriction is not satisfied");
+        Verifier::_add_referent_failure(&mut report.requested_predicates, "referent_2", VerificationCheck::Restrictions, &err);
+
+        let report = Verifier::_complete_report(report);
+        assert!(!report.valid);
+        assert!(report.requested_attributes["referent_1"].valid);
+        assert!(!report.requested_predicates["referent_2"].valid);
+        assert_eq!(VerificationCheck::Restrictions, report.requested_predicates["referent_2"].errors[0].check);
+        assert_eq!("Restriction is not satisfied", report.requested_predicates["referent_2"].errors[0].reason);
+    }
+
+    #[test]
+    fn complete_report_works_for_proof_level_failure() {
+        let mut report = ProofVerificationReport::default();
+        report.requested_attributes.insert("referent_1".to_string(), ReferentVerificationResult::default());
+        report.errors.push(VerificationFailure { check: VerificationCheck::CryptoProof, reason: String::new() });
+
+        let report = Verifier::_complete_report(report);
+        assert!(!report.valid);
+        assert!(report.requested_attributes["referent_1"].valid);
+    }
 }
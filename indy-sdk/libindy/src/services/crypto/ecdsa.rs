@@ -0,0 +1,63 @@
+use indy_api_types::errors::prelude::*;
+use super::CryptoType;
+use indy_utils::crypto::ecdsa_sign;
+use indy_utils::crypto::ecdsa_sign::Curve;
+use indy_utils::crypto::ed25519_box;
+use indy_utils::crypto::ed25519_sign;
+
+
+// ECDSA keys are signing only: there is no standard way to derive box keys from them,
+// so authenticated and anonymous encryption aren't supported for these crypto types.
+pub struct ECDSACryptoType {
+    curve: Curve,
+    name: &'static str,
+}
+
+impl ECDSACryptoType {
+    pub fn new(curve: Curve, name: &'static str) -> ECDSACryptoType {
+        ECDSACryptoType { curve, name }
+    }
+
+    fn box_unsupported<T>(&self) -> IndyResult<T> {
+        Err(err_msg(IndyErrorKind::UnknownCrypto,
+                    format!("Encryption isn't supported for {} keys", self.name)))
+    }
+}
+
+impl CryptoType for ECDSACryptoType {
+    fn crypto_box(&self, _sk: &[u8], _vk: &[u8], _doc: &[u8], _nonce: &ed25519_box::Nonce) -> IndyResult<Vec<u8>> {
+        self.box_unsupported()
+    }
+
+    fn crypto_box_open(&self, _sk: &[u8], _vk: &[u8], _doc: &[u8], _nonce: &ed25519_box::Nonce) -> IndyResult<Vec<u8>> {
+        self.box_unsupported()
+    }
+
+    fn gen_nonce(&self) -> ed25519_box::Nonce {
+        ed25519_box::gen_nonce()
+    }
+
+    fn create_key(&self, seed: Option<&ed25519_sign::Seed>) -> IndyResult<(Vec<u8>, Vec<u8>)> {
+        ecdsa_sign::create_key_pair_for_signature(self.curve, seed.map(|seed| &seed[..]))
+    }
+
+    fn validate_key(&self, vk: &[u8]) -> IndyResult<()> {
+        ecdsa_sign::validate_key(self.curve, vk)
+    }
+
+    fn sign(&self, sk: &[u8], doc: &[u8]) -> IndyResult<Vec<u8>> {
+        ecdsa_sign::sign(self.curve, sk, doc)
+    }
+
+    fn verify(&self, vk: &[u8], doc: &[u8], signature: &[u8]) -> IndyResult<bool> {
+        ecdsa_sign::verify(self.curve, vk, doc, signature)
+    }
+
+    fn crypto_box_seal(&self, _vk: &[u8], _doc: &[u8]) -> IndyResult<Vec<u8>> {
+        self.box_unsupported()
+    }
+
+    fn crypto_box_seal_open(&self, _vk: &[u8], _sk: &[u8], _doc: &[u8]) -> IndyResult<Vec<u8>> {
+        self.box_unsupported()
+    }
+}
@@ -0,0 +1,172 @@
+use std::ffi::{CStr, CString};
+use std::{ptr, slice};
+
+use libc::c_char;
+
+use indy_api_types::{ErrorCode, IndyHandle};
+use indy_api_types::key_manager::*;
+use indy_api_types::errors::prelude::*;
+
+use crate::domain::crypto::key::KeyInfo;
+
+// This struct is used as a helper to free the buffer even in case of error.
+struct BufferGuard {
+    buffer_handle: IndyHandle,
+    free_buffer_handler: KeyManagerFreeBuffer,
+}
+
+impl BufferGuard {
+    fn new(buffer_handle: IndyHandle, free_buffer_handler: KeyManagerFreeBuffer) -> Self {
+        Self {
+            buffer_handle,
+            free_buffer_handler,
+        }
+    }
+}
+
+impl Drop for BufferGuard {
+    fn drop(&mut self) {
+        (self.free_buffer_handler)(self.buffer_handle);
+    }
+}
+
+// Key manager registered by application. Private keys never leave the key manager:
+// wallet keeps only verkey and the name of the key manager.
+#[derive(Clone, Copy)]
+pub struct PluggedKeyManager {
+    create_key_handler: KeyManagerCreateKey,
+    sign_handler: KeyManagerSign,
+    encrypt_handler: KeyManagerEncrypt,
+    decrypt_handler: KeyManagerDecrypt,
+    free_buffer_handler: KeyManagerFreeBuffer,
+}
+
+impl PluggedKeyManager {
+    pub fn new(create_key_handler: KeyManagerCreateKey,
+               sign_handler: KeyManagerSign,
+               encrypt_handler: KeyManagerEncrypt,
+               decrypt_handler: KeyManagerDecrypt,
+               free_buffer_handler: KeyManagerFreeBuffer) -> PluggedKeyManager {
+        PluggedKeyManager {
+            create_key_handler,
+            sign_handler,
+            encrypt_handler,
+            decrypt_handler,
+            free_buffer_handler,
+        }
+    }
+
+    pub fn create_key(&self, key_info: &KeyInfo) -> IndyResult<String> {
+        let key_config = json!({
+            "seed": key_info.seed,
+            "crypto_type": key_info.crypto_type,
+        }).to_string();
+
+        let key_config = CString::new(key_config)?;
+
+        let mut verkey_ptr: *const c_char = ptr::null();
+        let mut buffer_handle: IndyHandle = -1;
+
+        let err = (self.create_key_handler)(key_config.as_ptr(),
+                                            &mut verkey_ptr,
+                                            &mut buffer_handle);
+
+        if err != ErrorCode::Success {
+            return Err(err.into());
+        }
+
+        let _buffer_guard = BufferGuard::new(buffer_handle, self.free_buffer_handler);
+
+        if verkey_ptr.is_null() {
+            return Err(err_msg(IndyErrorKind::InvalidState, "Key manager returned empty verkey"));
+        }
+
+        let verkey = unsafe { CStr::from_ptr(verkey_ptr) }
+            .to_str()
+            .to_indy(IndyErrorKind::InvalidState, "Key manager returned invalid verkey")?
+            .to_string();
+
+        Ok(verkey)
+    }
+
+    pub fn sign(&self, verkey: &str, doc: &[u8]) -> IndyResult<Vec<u8>> {
+        let verkey = CString::new(verkey)?;
+
+        let mut signature_ptr: *const u8 = ptr::null();
+        let mut signature_len: usize = 0;
+        let mut buffer_handle: IndyHandle = -1;
+
+        let err = (self.sign_handler)(verkey.as_ptr(),
+                                      doc.as_ptr(),
+                                      doc.len(),
+                                      &mut signature_ptr,
+                                      &mut signature_len,
+                                      &mut buffer_handle);
+
+        self._buffer(err, buffer_handle, signature_ptr, signature_len)
+    }
+
+    pub fn crypto_box(&self, verkey: &str, their_vk: &str, doc: &[u8], nonce: &[u8]) -> IndyResult<Vec<u8>> {
+        let verkey = CString::new(verkey)?;
+        let their_vk = CString::new(their_vk)?;
+
+        let mut encrypted_ptr: *const u8 = ptr::null();
+        let mut encrypted_len: usize = 0;
+        let mut buffer_handle: IndyHandle = -1;
+
+        let err = (self.encrypt_handler)(verkey.as_ptr(),
+                                         their_vk.as_ptr(),
+                                         doc.as_ptr(),
+                                         doc.len(),
+                                         nonce.as_ptr(),
+                                         nonce.len(),
+                                         &mut encrypted_ptr,
+                                         &mut encrypted_len,
+                                         &mut buffer_handle);
+
+        self._buffer(err, buffer_handle, encrypted_ptr, encrypted_len)
+    }
+
+    pub fn crypto_box_open(&self, verkey: &str, their_vk: &str, doc: &[u8], nonce: &[u8]) -> IndyResult<Vec<u8>> {
+        let their_vk = CString::new(their_vk)?;
+        self._decrypt(verkey, their_vk.as_ptr(), doc, nonce.as_ptr(), nonce.len())
+    }
+
+    pub fn crypto_box_seal_open(&self, verkey: &str, doc: &[u8]) -> IndyResult<Vec<u8>> {
+        self._decrypt(verkey, ptr::null(), doc, ptr::null(), 0)
+    }
+
+    fn _decrypt(&self, verkey: &str, their_vk: *const c_char, doc: &[u8], nonce: *const u8, nonce_len: usize) -> IndyResult<Vec<u8>> {
+        let verkey = CString::new(verkey)?;
+
+        let mut decrypted_ptr: *const u8 = ptr::null();
+        let mut decrypted_len: usize = 0;
+        let mut buffer_handle: IndyHandle = -1;
+
+        let err = (self.decrypt_handler)(verkey.as_ptr(),
+                                         their_vk,
+                                         doc.as_ptr(),
+                                         doc.len(),
+                                         nonce,
+                                         nonce_len,
+                                         &mut decrypted_ptr,
+                                         &mut decrypted_len,
+                                         &mut buffer_handle);
+
+        self._buffer(err, buffer_handle, decrypted_ptr, decrypted_len)
+    }
+
+    fn _buffer(&self, err: ErrorCode, buffer_handle: IndyHandle, data: *const u8, len: usize) -> IndyResult<Vec<u8>> {
+        if err != ErrorCode::Success {
+            return Err(err.into());
+        }
+
+        let _buffer_guard = BufferGuard::new(buffer_handle, self.free_buffer_handler);
+
+        if data.is_null() {
+            return Err(err_msg(IndyErrorKind::InvalidState, "Key manager returned empty buffer"));
+        }
+
+        Ok(unsafe { slice::from_raw_parts(data, len) }.to_vec())
+    }
+}
@@ -1,58 +1,115 @@
 extern crate hex;
 
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::str;
 
 use crate::domain::crypto::combo_box::ComboBox;
 use crate::domain::crypto::did::{Did, DidValue, MyDidInfo, TheirDid, TheirDidInfo};
 use crate::domain::crypto::key::{Key, KeyInfo};
+use crate::domain::crypto::pack::ContentEncryption;
 use indy_api_types::errors::prelude::*;
+use indy_utils::crypto::aes256gcm;
+use indy_utils::crypto::aes256kw;
 use indy_utils::crypto::base64;
 use indy_utils::crypto::ed25519_box;
 use indy_utils::crypto::chacha20poly1305_ietf;
 use indy_utils::crypto::chacha20poly1305_ietf::gen_nonce_and_encrypt_detached;
+use indy_utils::crypto::ecdsa_sign::Curve;
 use indy_utils::crypto::ed25519_sign;
-use crate::utils::crypto::verkey_builder::{build_full_verkey, split_verkey, verkey_get_cryptoname};
-
+use indy_utils::crypto::hash;
+use indy_utils::crypto::hmacsha256;
+use indy_utils::crypto::xchacha20poly1305_ietf;
+use crate::utils::crypto::verkey_builder::{build_full_verkey, did_key_to_verkey, did_peer_to_verkey, split_verkey,
+                                           verkey_get_cryptoname, verkey_to_did_key, verkey_to_did_peer};
+use crate::utils::qualifier;
+
+use self::ecdsa::ECDSACryptoType;
 use self::ed25519::ED25519CryptoType;
 use self::hex::FromHex;
 use rust_base58::{FromBase58, ToBase58};
 
+mod ecdsa;
 mod ed25519;
+mod key_manager;
+
+pub use self::key_manager::PluggedKeyManager;
 
 pub const DEFAULT_CRYPTO_TYPE: &str = "ed25519";
+pub const SECP256K1_CRYPTO_TYPE: &str = "secp256k1";
+pub const SECP256R1_CRYPTO_TYPE: &str = "secp256r1";
 
 //TODO fix this crypto trait so it matches the functions below
 //TODO create a second crypto trait for additional functions
+// Keys and signatures are passed as raw bytes as their sizes depend on crypto type
 trait CryptoType {
-    fn crypto_box(&self, sk: &ed25519_sign::SecretKey, vk: &ed25519_sign::PublicKey, doc: &[u8], nonce: &ed25519_box::Nonce) -> IndyResult<Vec<u8>>;
-    fn crypto_box_open(&self, sk: &ed25519_sign::SecretKey, vk: &ed25519_sign::PublicKey, doc: &[u8], nonce: &ed25519_box::Nonce) -> IndyResult<Vec<u8>>;
+    fn crypto_box(&self, sk: &[u8], vk: &[u8], doc: &[u8], nonce: &ed25519_box::Nonce) -> IndyResult<Vec<u8>>;
+    fn crypto_box_open(&self, sk: &[u8], vk: &[u8], doc: &[u8], nonce: &ed25519_box::Nonce) -> IndyResult<Vec<u8>>;
     fn gen_nonce(&self) -> ed25519_box::Nonce;
-    fn create_key(&self, seed: Option<&ed25519_sign::Seed>) -> IndyResult<(ed25519_sign::PublicKey, ed25519_sign::SecretKey)>;
-    fn validate_key(&self, _vk: &ed25519_sign::PublicKey) -> IndyResult<()>;
-    fn sign(&self, sk: &ed25519_sign::SecretKey, doc: &[u8]) -> IndyResult<ed25519_sign::Signature>;
-    fn verify(&self, vk: &ed25519_sign::PublicKey, doc: &[u8], signature: &ed25519_sign::Signature) -> IndyResult<bool>;
-    fn crypto_box_seal(&self, vk: &ed25519_sign::PublicKey, doc: &[u8]) -> IndyResult<Vec<u8>>;
-    fn crypto_box_seal_open(&self, vk: &ed25519_sign::PublicKey, sk: &ed25519_sign::SecretKey, doc: &[u8]) -> IndyResult<Vec<u8>>;
+    fn create_key(&self, seed: Option<&ed25519_sign::Seed>) -> IndyResult<(Vec<u8>, Vec<u8>)>;
+    fn validate_key(&self, vk: &[u8]) -> IndyResult<()>;
+    fn sign(&self, sk: &[u8], doc: &[u8]) -> IndyResult<Vec<u8>>;
+    fn verify(&self, vk: &[u8], doc: &[u8], signature: &[u8]) -> IndyResult<bool>;
+    fn crypto_box_seal(&self, vk: &[u8], doc: &[u8]) -> IndyResult<Vec<u8>>;
+    fn crypto_box_seal_open(&self, vk: &[u8], sk: &[u8], doc: &[u8]) -> IndyResult<Vec<u8>>;
 }
 
 pub struct CryptoService {
-    crypto_types: HashMap<&'static str, Box<dyn CryptoType>>
+    crypto_types: HashMap<&'static str, Box<dyn CryptoType>>,
+    key_managers: RefCell<HashMap<String, PluggedKeyManager>>,
 }
 
 impl CryptoService {
     pub fn new() -> CryptoService {
         let mut crypto_types: HashMap<&str, Box<dyn CryptoType>> = HashMap::new();
         crypto_types.insert(DEFAULT_CRYPTO_TYPE, Box::new(ED25519CryptoType::new()));
+        crypto_types.insert(SECP256K1_CRYPTO_TYPE, Box::new(ECDSACryptoType::new(Curve::Secp256k1, SECP256K1_CRYPTO_TYPE)));
+        crypto_types.insert(SECP256R1_CRYPTO_TYPE, Box::new(ECDSACryptoType::new(Curve::Secp256r1, SECP256R1_CRYPTO_TYPE)));
 
         CryptoService {
-            crypto_types
+            crypto_types,
+            key_managers: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn register_key_manager(&self, name: &str, key_manager: PluggedKeyManager) -> IndyResult<()> {
+        trace!("register_key_manager >>> name: {:?}", name);
+
+        let mut key_managers = self.key_managers.borrow_mut();
+
+        if key_managers.contains_key(name) {
+            return Err(err_msg(IndyErrorKind::InvalidState, format!("Key manager is already registered: {}", name)));
         }
+
+        key_managers.insert(name.to_string(), key_manager);
+
+        trace!("register_key_manager <<<");
+
+        Ok(())
+    }
+
+    fn key_manager(&self, name: &str) -> IndyResult<PluggedKeyManager> {
+        self.key_managers
+            .borrow()
+            .get(name)
+            .cloned()
+            .ok_or_else(|| err_msg(IndyErrorKind::UnknownCrypto, format!("Key manager isn't registered: {}", name)))
     }
 
     pub fn create_key(&self, key_info: &KeyInfo) -> IndyResult<Key> {
         trace!("create_key >>> key_info: {:?}", secret!(key_info));
 
+        if let Some(ref key_manager) = key_info.key_manager {
+            let verkey = self.key_manager(key_manager)?.create_key(key_info)?;
+            self.validate_key(&verkey)?;
+
+            let key = Key::new_external(verkey, key_manager.to_string());
+
+            trace!("create_key <<< key: {:?}", key);
+
+            return Ok(key);
+        }
+
         let crypto_type_name = key_info.crypto_type
             .as_ref()
             .map(String::as_str)
@@ -83,6 +140,26 @@ impl CryptoService {
     pub fn create_my_did(&self, my_did_info: &MyDidInfo) -> IndyResult<(Did, Key)> {
         trace!("create_my_did >>> my_did_info: {:?}", secret!(my_did_info));
 
+        if my_did_info.key_manager.is_some() {
+            let key_info = KeyInfo {
+                seed: my_did_info.seed.clone(),
+                crypto_type: my_did_info.crypto_type.clone(),
+                key_manager: my_did_info.key_manager.clone(),
+            };
+
+            let key = self.create_key(&key_info)?;
+
+            let vk = split_verkey(&key.verkey).0
+                .from_base58()
+                .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, format!("Invalid verkey returned by key manager: {:?}", err)))?;
+
+            let did = (Did::new(self._build_did_value(my_did_info, &vk, &key.verkey)?, key.verkey.clone()), key);
+
+            trace!("create_my_did <<< did: {:?}", did);
+
+            return Ok(did);
+        }
+
         let crypto_type_name = my_did_info.crypto_type
             .as_ref()
             .map(String::as_str)
@@ -95,16 +172,9 @@ impl CryptoService {
         let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
 
         let seed = self.convert_seed(my_did_info.seed.as_ref().map(String::as_ref))?;
-        let (vk, sk) = crypto_type.create_key(seed.as_ref())?;
-        let did = match my_did_info.did {
-            Some(ref did) => did.clone(),
-            _ if my_did_info.cid == Some(true) =>
-                DidValue::new(&vk[..].to_vec().to_base58(), my_did_info.method_name.as_ref().map(|method| method.0.as_str())),
-            _ =>
-                DidValue::new(&vk[0..16].to_vec().to_base58(), my_did_info.method_name.as_ref().map(|method| method.0.as_str()))
-        };
+        let (vk_raw, sk) = crypto_type.create_key(seed.as_ref())?;
 
-        let mut vk = vk[..].to_base58();
+        let mut vk = vk_raw[..].to_base58();
         let sk = sk[..].to_base58();
 
         if !crypto_type_name.eq(DEFAULT_CRYPTO_TYPE) {
@@ -112,6 +182,8 @@ impl CryptoService {
             vk = format!("{}:{}", vk, crypto_type_name);
         }
 
+        let did = self._build_did_value(my_did_info, &vk_raw, &vk)?;
+
         let did = (Did::new(did, vk.clone()), Key::new(vk, sk));
 
         trace!("create_my_did <<< did: {:?}", did);
@@ -119,14 +191,63 @@ impl CryptoService {
         Ok(did)
     }
 
+    fn _build_did_value(&self, my_did_info: &MyDidInfo, vk: &[u8], verkey: &str) -> IndyResult<DidValue> {
+        let method = my_did_info.method_name.as_ref().map(|method| method.0.as_str());
+
+        let did = match (method, &my_did_info.did) {
+            (Some(qualifier::DID_KEY_METHOD), &None) =>
+                DidValue(verkey_to_did_key(verkey)?),
+            (Some(qualifier::DID_PEER_METHOD), &None) => {
+                let numalgo = my_did_info.peer_numalgo.unwrap_or(0);
+                let x25519_pk = self.x25519_public_key(verkey).ok();
+                DidValue(verkey_to_did_peer(verkey, numalgo, x25519_pk.as_ref().map(|pk| &pk[..]))?)
+            }
+            (Some(qualifier::DID_KEY_METHOD), &Some(_)) | (Some(qualifier::DID_PEER_METHOD), &Some(_)) =>
+                return Err(err_msg(IndyErrorKind::InvalidStructure, "DID of key and peer methods is derived from the key and can't be passed")),
+            (_, &Some(ref did)) => did.clone(),
+            _ if my_did_info.cid == Some(true) =>
+                DidValue::new(&vk[..].to_vec().to_base58(), method),
+            _ =>
+                DidValue::new(&vk[0..16].to_vec().to_base58(), method)
+        };
+
+        Ok(did)
+    }
+
+    pub fn verkey_for_self_certifying_did(&self, did: &DidValue) -> IndyResult<String> {
+        trace!("verkey_for_self_certifying_did >>> did: {:?}", did);
+
+        let verkey = match did.get_method() {
+            Some(ref method) if method == qualifier::DID_KEY_METHOD => did_key_to_verkey(&did.0)?,
+            Some(ref method) if method == qualifier::DID_PEER_METHOD => did_peer_to_verkey(&did.0)?,
+            _ => return Err(err_msg(IndyErrorKind::InvalidStructure, format!("DID {} isn't of key or peer method", did.0)))
+        };
+
+        self.validate_key(&verkey)?;
+
+        trace!("verkey_for_self_certifying_did <<< verkey: {:?}", verkey);
+
+        Ok(verkey)
+    }
+
     pub fn create_their_did(&self, their_did_info: &TheirDidInfo) -> IndyResult<TheirDid> {
         trace!("create_their_did >>> their_did_info: {:?}", their_did_info);
 
         // Check did is correct Base58
         let _ = self.validate_did(&their_did_info.did)?;
 
-        let verkey = build_full_verkey(&their_did_info.did.to_unqualified().0,
-                                       their_did_info.verkey.as_ref().map(String::as_str))?;
+        let verkey = if their_did_info.did.is_self_certifying() {
+            let verkey = self.verkey_for_self_certifying_did(&their_did_info.did)?;
+
+            match their_did_info.verkey {
+                Some(ref their_verkey) if *their_verkey != verkey =>
+                    return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Verkey {} doesn't match DID {}", their_verkey, their_did_info.did.0))),
+                _ => verkey
+            }
+        } else {
+            build_full_verkey(&their_did_info.did.to_unqualified().0,
+                              their_did_info.verkey.as_ref().map(String::as_str))?
+        };
 
         self.validate_key(&verkey)?;
 
@@ -140,6 +261,14 @@ impl CryptoService {
     pub fn sign(&self, my_key: &Key, doc: &[u8]) -> IndyResult<Vec<u8>> {
         trace!("sign >>> my_key: {:?}, doc: {:?}", my_key, doc);
 
+        if let Some(ref key_manager) = my_key.key_manager {
+            let signature = self.key_manager(key_manager)?.sign(&my_key.verkey, doc)?;
+
+            trace!("sign <<< signature: {:?}", signature);
+
+            return Ok(signature);
+        }
+
         let crypto_type_name = verkey_get_cryptoname(&my_key.verkey);
 
         if !self.crypto_types.contains_key(crypto_type_name) {
@@ -148,8 +277,8 @@ impl CryptoService {
 
         let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
 
-        let my_sk = ed25519_sign::SecretKey::from_slice(&my_key.signkey.as_str().from_base58()?.as_slice())?;
-        let signature = crypto_type.sign(&my_sk, doc)?[..].to_vec();
+        let my_sk = my_key.signkey.as_str().from_base58()?;
+        let signature = crypto_type.sign(&my_sk, doc)?;
 
         trace!("sign <<< signature: {:?}", signature);
 
@@ -167,16 +296,24 @@ impl CryptoService {
 
         let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
 
-        let their_vk = ed25519_sign::PublicKey::from_slice(&their_vk.from_base58()?)?;
-        let signature = ed25519_sign::Signature::from_slice(&signature)?;
+        let their_vk = their_vk.from_base58()?;
 
-        let valid = crypto_type.verify(&their_vk, msg, &signature)?;
+        let valid = crypto_type.verify(&their_vk, msg, signature)?;
 
         trace!("verify <<< valid: {:?}", valid);
 
         Ok(valid)
     }
 
+    pub fn jws_alg(&self, vk: &str) -> IndyResult<&'static str> {
+        match verkey_get_cryptoname(vk) {
+            DEFAULT_CRYPTO_TYPE => Ok("EdDSA"),
+            SECP256K1_CRYPTO_TYPE => Ok("ES256K"),
+            SECP256R1_CRYPTO_TYPE => Ok("ES256"),
+            crypto_type_name => Err(err_msg(IndyErrorKind::UnknownCrypto, format!("JWS isn't supported for crypto: {}", crypto_type_name)))
+        }
+    }
+
     pub fn create_combo_box(&self, my_key: &Key, their_vk: &str, doc: &[u8]) -> IndyResult<ComboBox> {
         trace!("create_combo_box >>> my_key: {:?}, their_vk: {:?}, doc: {:?}", my_key, their_vk, doc);
 
@@ -214,11 +351,17 @@ impl CryptoService {
 
         let crypto_type = self.crypto_types.get(&crypto_type_name).unwrap();
 
-        let my_sk = ed25519_sign::SecretKey::from_slice(my_key.signkey.as_str().from_base58()?.as_slice())?;
-        let their_vk = ed25519_sign::PublicKey::from_slice(their_vk.from_base58()?.as_slice())?;
         let nonce = crypto_type.gen_nonce();
 
-        let encrypted_doc = crypto_type.crypto_box(&my_sk, &their_vk, doc, &nonce)?;
+        let encrypted_doc = match my_key.key_manager {
+            Some(ref key_manager) =>
+                self.key_manager(key_manager)?.crypto_box(&my_key.verkey, their_vk, doc, &nonce[..])?,
+            None => {
+                let my_sk = my_key.signkey.as_str().from_base58()?;
+                let their_vk = their_vk.from_base58()?;
+                crypto_type.crypto_box(&my_sk, &their_vk, doc, &nonce)?
+            }
+        };
         let nonce = nonce[..].to_vec();
 
         trace!("crypto_box <<< encrypted_doc: {:?}, nonce: {:?}", encrypted_doc, nonce);
@@ -248,11 +391,17 @@ impl CryptoService {
 
         let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
 
-        let my_sk = ed25519_sign::SecretKey::from_slice(&my_key.signkey.from_base58()?.as_slice())?;
-        let their_vk = ed25519_sign::PublicKey::from_slice(their_vk.from_base58()?.as_slice())?;
         let nonce = ed25519_box::Nonce::from_slice(&nonce)?;
 
-        let decrypted_doc = crypto_type.crypto_box_open(&my_sk, &their_vk, &doc, &nonce)?;
+        let decrypted_doc = match my_key.key_manager {
+            Some(ref key_manager) =>
+                self.key_manager(key_manager)?.crypto_box_open(&my_key.verkey, their_vk, &doc, &nonce[..])?,
+            None => {
+                let my_sk = my_key.signkey.from_base58()?;
+                let their_vk = their_vk.from_base58()?;
+                crypto_type.crypto_box_open(&my_sk, &their_vk, &doc, &nonce)?
+            }
+        };
 
         trace!("crypto_box_open <<< decrypted_doc: {:?}", decrypted_doc);
 
@@ -270,7 +419,7 @@ impl CryptoService {
 
         let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
 
-        let their_vk = ed25519_sign::PublicKey::from_slice(their_vk.from_base58()?.as_slice())?;
+        let their_vk = their_vk.from_base58()?;
 
         let encrypted_doc = crypto_type.crypto_box_seal(&their_vk, doc)?;
 
@@ -291,10 +440,15 @@ impl CryptoService {
 
         let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
 
-        let my_vk = ed25519_sign::PublicKey::from_slice(my_vk.from_base58()?.as_slice())?;
-        let my_sk = ed25519_sign::SecretKey::from_slice(my_key.signkey.as_str().from_base58()?.as_slice())?;
-
-        let decrypted_doc = crypto_type.crypto_box_seal_open(&my_vk, &my_sk, doc)?;
+        let decrypted_doc = match my_key.key_manager {
+            Some(ref key_manager) =>
+                self.key_manager(key_manager)?.crypto_box_seal_open(&my_key.verkey, doc)?,
+            None => {
+                let my_vk = my_vk.from_base58()?;
+                let my_sk = my_key.signkey.as_str().from_base58()?;
+                crypto_type.crypto_box_seal_open(&my_vk, &my_sk, doc)?
+            }
+        };
 
         trace!("crypto_box_seal_open <<< decrypted_doc: {:?}", decrypted_doc);
 
@@ -356,8 +510,7 @@ impl CryptoService {
         if vk.starts_with('~') {
             let _ = vk[1..].from_base58()?; // TODO: proper validate abbreviated verkey
         } else {
-            let vk = ed25519_sign::PublicKey::from_slice(vk.from_base58()?.as_slice())?;
-            crypto_type.validate_key(&vk)?;
+            crypto_type.validate_key(&vk.from_base58()?)?;
         };
 
         trace!("validate_key <<<");
@@ -440,12 +593,126 @@ impl CryptoService {
             err_msg(IndyErrorKind::InvalidStructure, format!("Failed to convert message to UTF-8 {}", err))
         })
     }
+
+    /* ECDH-ES and ECDH-1PU helper functions. X25519 keys are derived from ed25519 ones. */
+    pub fn x25519_public_key(&self, vk: &str) -> IndyResult<ed25519_box::PublicKey> {
+        let (vk, crypto_type_name) = split_verkey(vk);
+
+        if crypto_type_name != DEFAULT_CRYPTO_TYPE {
+            return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("X25519 key agreement isn't supported for {} keys", crypto_type_name)));
+        }
+
+        let vk = ed25519_sign::PublicKey::from_slice(&vk.from_base58()?)?;
+        ed25519_sign::vk_to_curve25519(&vk)
+    }
+
+    pub fn x25519_key_agreement(&self, my_key: &Key, their_pk: &ed25519_box::PublicKey) -> IndyResult<Vec<u8>> {
+        trace!("x25519_key_agreement >>> my_key: {:?}, their_pk: {:?}", my_key, their_pk);
+
+        let crypto_type_name = verkey_get_cryptoname(&my_key.verkey);
+
+        if crypto_type_name != DEFAULT_CRYPTO_TYPE {
+            return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("X25519 key agreement isn't supported for {} keys", crypto_type_name)));
+        }
+
+        if my_key.key_manager.is_some() {
+            return Err(err_msg(IndyErrorKind::UnknownCrypto, "X25519 key agreement isn't supported for keys of key manager"));
+        }
+
+        let my_sk = ed25519_sign::SecretKey::from_slice(&my_key.signkey.from_base58()?)?;
+        let res = ed25519_box::ecdh(&ed25519_sign::sk_to_curve25519(&my_sk)?, their_pk)?;
+
+        trace!("x25519_key_agreement <<<");
+
+        Ok(res)
+    }
+
+    // Concat KDF (NIST SP 800-56A) as it is used by JWA ECDH-ES (RFC 7518, section 4.6.2).
+    // ECDH-1PU in Key Wrapping mode appends the tag of the content encryption to SuppPubInfo.
+    pub fn concat_kdf(&self, z: &[u8], alg: &str, apu: &[u8], apv: &[u8], cc_tag: Option<&[u8]>) -> IndyResult<Vec<u8>> {
+        const KEY_DATA_LEN_BITS: u32 = (aes256kw::KEYBYTES * 8) as u32;
+
+        fn _with_len(data: &[u8]) -> Vec<u8> {
+            let mut res = (data.len() as u32).to_be_bytes().to_vec();
+            res.extend_from_slice(data);
+            res
+        }
+
+        let mut input = 1u32.to_be_bytes().to_vec();
+        input.extend_from_slice(z);
+        input.extend(_with_len(alg.as_bytes()));
+        input.extend(_with_len(apu));
+        input.extend(_with_len(apv));
+        input.extend_from_slice(&KEY_DATA_LEN_BITS.to_be_bytes());
+
+        if let Some(cc_tag) = cc_tag {
+            input.extend(_with_len(cc_tag));
+        }
+
+        // SHA-256 output length is the same as key length so one round is enough
+        hash::hash(&input)
+    }
+
+    pub fn gen_content_encryption_key(&self, enc: ContentEncryption) -> IndyResult<Vec<u8>> {
+        match enc {
+            ContentEncryption::A256GCM => aes256gcm::gen_key(),
+            ContentEncryption::XC20P => Ok(xchacha20poly1305_ietf::gen_key()),
+        }
+    }
+
+    pub fn encrypt_detached(&self, enc: ContentEncryption, plaintext: &[u8], aad: &[u8], cek: &[u8]) -> IndyResult<(Vec<u8>, Vec<u8>, Vec<u8>)> {
+        let (ciphertext, iv, tag) = match enc {
+            ContentEncryption::A256GCM => {
+                let iv = aes256gcm::gen_nonce()?;
+                let (ciphertext, tag) = aes256gcm::encrypt_detached(plaintext, aad, cek, &iv)?;
+                (ciphertext, iv, tag)
+            }
+            ContentEncryption::XC20P => {
+                let iv = xchacha20poly1305_ietf::gen_nonce();
+                let (ciphertext, tag) = xchacha20poly1305_ietf::encrypt_detached(plaintext, aad, cek, &iv)?;
+                (ciphertext, iv, tag)
+            }
+        };
+
+        Ok((ciphertext, iv, tag))
+    }
+
+    pub fn decrypt_detached(&self, enc: ContentEncryption, ciphertext: &[u8], aad: &[u8], cek: &[u8], iv: &[u8], tag: &[u8]) -> IndyResult<Vec<u8>> {
+        match enc {
+            ContentEncryption::A256GCM => aes256gcm::decrypt_detached(ciphertext, aad, cek, iv, tag),
+            ContentEncryption::XC20P => xchacha20poly1305_ietf::decrypt_detached(ciphertext, aad, cek, iv, tag),
+        }
+    }
+
+    /* Session helpers. Chain keys are ratcheted by HMAC-SHA256 as in the Signal symmetric-key ratchet. */
+    pub fn session_root_key(&self, my_ephemeral_sk: &ed25519_box::SecretKey, their_ephemeral_pk: &ed25519_box::PublicKey, session_id: &str) -> IndyResult<Vec<u8>> {
+        let mut input = ed25519_box::ecdh(my_ephemeral_sk, their_ephemeral_pk)?;
+        input.extend_from_slice(session_id.as_bytes());
+
+        hash::hash(&input)
+    }
+
+    // Returns chain keys of the messages sent by the initiator and by the responder
+    pub fn session_chain_keys(&self, root_key: &[u8]) -> IndyResult<(Vec<u8>, Vec<u8>)> {
+        let root_key = hmacsha256::Key::from_slice(root_key)?;
+
+        Ok((hmacsha256::authenticate(b"initiator", &root_key)[..].to_vec(),
+            hmacsha256::authenticate(b"responder", &root_key)[..].to_vec()))
+    }
+
+    // Returns the message key and the next chain key. The previous chain key must be forgotten.
+    pub fn session_ratchet(&self, chain_key: &[u8]) -> IndyResult<(Vec<u8>, Vec<u8>)> {
+        let chain_key = hmacsha256::Key::from_slice(chain_key)?;
+
+        Ok((hmacsha256::authenticate(&[1], &chain_key)[..].to_vec(),
+            hmacsha256::authenticate(&[2], &chain_key)[..].to_vec()))
+    }
 }
 
 
 #[cfg(test)]
 mod tests {
-    use crate::domain::crypto::did::MyDidInfo;
+    use crate::domain::crypto::did::{DidMethod, MyDidInfo};
     use indy_utils::crypto::chacha20poly1305_ietf::gen_key;
 
     use super::*;
@@ -453,7 +720,7 @@ mod tests {
     #[test]
     fn create_my_did_with_works_for_empty_info() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, key_manager: None, peer_numalgo: None };
         let my_did = service.create_my_did(&did_info);
         assert!(my_did.is_ok());
     }
@@ -463,7 +730,7 @@ mod tests {
         let service = CryptoService::new();
 
         let did = DidValue("NcYxiDXkpYi6ov5FcYDi1e".to_string());
-        let did_info = MyDidInfo { did: Some(did.clone()), cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: Some(did.clone()), cid: None, seed: None, crypto_type: None, method_name: None, key_manager: None, peer_numalgo: None };
 
         let (my_did, _) = service.create_my_did(&did_info).unwrap();
         assert_eq!(did, my_did.did);
@@ -476,7 +743,7 @@ mod tests {
         let did = DidValue("NcYxiDXkpYi6ov5FcYDi1e".to_string());
         let crypto_type = Some("type".to_string());
 
-        let did_info = MyDidInfo { did: Some(did), cid: None, seed: None, crypto_type, method_name: None };
+        let did_info = MyDidInfo { did: Some(did), cid: None, seed: None, crypto_type, method_name: None, key_manager: None, peer_numalgo: None };
 
         assert!(service.create_my_did(&did_info).is_err());
     }
@@ -488,8 +755,8 @@ mod tests {
         let did = DidValue("NcYxiDXkpYi6ov5FcYDi1e".to_string());
         let seed = Some("00000000000000000000000000000My1".to_string());
 
-        let did_info_with_seed = MyDidInfo { did: Some(did.clone()), cid: None, seed, crypto_type: None, method_name: None };
-        let did_info_without_seed = MyDidInfo { did: Some(did.clone()), cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info_with_seed = MyDidInfo { did: Some(did.clone()), cid: None, seed, crypto_type: None, method_name: None, key_manager: None, peer_numalgo: None };
+        let did_info_without_seed = MyDidInfo { did: Some(did.clone()), cid: None, seed: None, crypto_type: None, method_name: None, key_manager: None, peer_numalgo: None };
 
         let (did_with_seed, _) = service.create_my_did(&did_info_with_seed).unwrap();
         let (did_without_seed, _) = service.create_my_did(&did_info_without_seed).unwrap();
@@ -536,7 +803,7 @@ mod tests {
     #[test]
     fn sign_works() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, key_manager: None, peer_numalgo: None };
 
         let message = r#"message"#;
         let (_, my_key) = service.create_my_did(&did_info).unwrap();
@@ -555,7 +822,7 @@ mod tests {
     #[test]
     fn sign_verify_works() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, key_manager: None, peer_numalgo: None };
         let message = r#"message"#;
         let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
         let signature = service.sign(&my_key, message.as_bytes()).unwrap();
@@ -566,7 +833,7 @@ mod tests {
     #[test]
     fn sign_verify_works_for_verkey_contained_crypto_type() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, key_manager: None, peer_numalgo: None };
         let message = r#"message"#;
         let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
         let signature = service.sign(&my_key, message.as_bytes()).unwrap();
@@ -579,7 +846,7 @@ mod tests {
     #[test]
     fn sign_verify_works_for_verkey_contained_invalid_crypto_type() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, key_manager: None, peer_numalgo: None };
         let message = r#"message"#;
         let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
         let signature = service.sign(&my_key, message.as_bytes()).unwrap();
@@ -590,7 +857,7 @@ mod tests {
     #[test]
     fn verify_not_works_for_invalid_verkey() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, key_manager: None, peer_numalgo: None };
         let message = r#"message"#;
         let (_, my_key) = service.create_my_did(&did_info).unwrap();
         let signature = service.sign(&my_key, message.as_bytes()).unwrap();
@@ -599,11 +866,61 @@ mod tests {
         assert_eq!(false, valid);
     }
 
+    #[test]
+    fn sign_verify_works_for_ecdsa_crypto_types() {
+        let service = CryptoService::new();
+        let message = r#"message"#;
+
+        for crypto_type in &[SECP256K1_CRYPTO_TYPE, SECP256R1_CRYPTO_TYPE] {
+            let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: Some(crypto_type.to_string()), method_name: None, key_manager: None, peer_numalgo: None };
+            let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
+            assert_eq!(*crypto_type, verkey_get_cryptoname(&my_did.verkey));
+
+            let signature = service.sign(&my_key, message.as_bytes()).unwrap();
+            assert!(service.verify(&my_did.verkey, message.as_bytes(), &signature).unwrap());
+            assert!(!service.verify(&my_did.verkey, "other message".as_bytes(), &signature).unwrap());
+        }
+    }
+
+    #[test]
+    fn create_key_works_for_ecdsa_crypto_type_and_seed() {
+        let service = CryptoService::new();
+        let seed = Some("00000000000000000000000000000My1".to_string());
+
+        let key_info = KeyInfo { seed: seed.clone(), crypto_type: Some(SECP256K1_CRYPTO_TYPE.to_string()), key_manager: None };
+        let key_1 = service.create_key(&key_info).unwrap();
+        let key_2 = service.create_key(&key_info).unwrap();
+        assert_eq!(key_1.verkey, key_2.verkey);
+        assert!(key_1.verkey.ends_with(":secp256k1"));
+
+        let key_info = KeyInfo { seed, crypto_type: Some(SECP256R1_CRYPTO_TYPE.to_string()), key_manager: None };
+        let key_3 = service.create_key(&key_info).unwrap();
+        assert_ne!(split_verkey(&key_1.verkey).0, split_verkey(&key_3.verkey).0);
+
+        service.validate_key(&key_1.verkey).unwrap();
+        service.validate_key(&key_3.verkey).unwrap();
+    }
+
+    #[test]
+    fn crypto_box_not_works_for_ecdsa_crypto_type() {
+        let service = CryptoService::new();
+        let msg = "some message";
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: Some(SECP256R1_CRYPTO_TYPE.to_string()), method_name: None, key_manager: None, peer_numalgo: None };
+        let (_, my_key) = service.create_my_did(&did_info).unwrap();
+        let (their_did, _) = service.create_my_did(&did_info.clone()).unwrap();
+
+        let res = service.crypto_box(&my_key, &their_did.verkey, msg.as_bytes());
+        assert_kind!(IndyErrorKind::UnknownCrypto, res);
+
+        let res = service.crypto_box_seal(&their_did.verkey, msg.as_bytes());
+        assert_kind!(IndyErrorKind::UnknownCrypto, res);
+    }
+
     #[test]
     fn crypto_box_works() {
         let service = CryptoService::new();
         let msg = "some message";
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, key_manager: None, peer_numalgo: None };
         let (_, my_key) = service.create_my_did(&did_info).unwrap();
         let (their_did, _) = service.create_my_did(&did_info.clone()).unwrap();
         let their_did = Did::new(their_did.did, their_did.verkey);
@@ -617,7 +934,7 @@ mod tests {
 
         let msg = "some message";
 
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, key_manager: None, peer_numalgo: None };
 
         let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
 
@@ -645,7 +962,7 @@ mod tests {
 
         let msg = "some message";
 
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, key_manager: None, peer_numalgo: None };
 
         let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
 
@@ -671,7 +988,7 @@ mod tests {
     fn crypto_box_seal_works() {
         let service = CryptoService::new();
         let msg = "some message";
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, key_manager: None, peer_numalgo: None };
         let (did, _) = service.create_my_did(&did_info.clone()).unwrap();
         let did = Did::new(did.did, did.verkey);
         let encrypted_message = service.crypto_box_seal(&did.verkey, msg.as_bytes());
@@ -682,7 +999,7 @@ mod tests {
     fn crypto_box_seal_and_crypto_box_seal_open_works() {
         let service = CryptoService::new();
         let msg = "some message".as_bytes();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, key_manager: None, peer_numalgo: None };
         let (did, key) = service.create_my_did(&did_info.clone()).unwrap();
         let encrypt_did = Did::new(did.did.clone(), did.verkey.clone());
         let encrypted_message = service.crypto_box_seal(&encrypt_did.verkey, msg).unwrap();
@@ -824,4 +1141,133 @@ mod tests {
             .decrypt_ciphertext(&expected_ciphertext, &iv_encoded, &tag, bad_aad, &cek);
         assert!(expected_error.is_err());
     }
-}
\ No newline at end of file
+
+    #[test]
+    pub fn x25519_key_agreement_works() {
+        let service = CryptoService::new();
+        let key_info = KeyInfo { seed: None, crypto_type: None, key_manager: None };
+
+        let key1 = service.create_key(&key_info).unwrap();
+        let key2 = service.create_key(&key_info).unwrap();
+
+        let secret1 = service.x25519_key_agreement(&key1, &service.x25519_public_key(&key2.verkey).unwrap()).unwrap();
+        let secret2 = service.x25519_key_agreement(&key2, &service.x25519_public_key(&key1.verkey).unwrap()).unwrap();
+
+        assert_eq!(secret1, secret2);
+    }
+
+    #[test]
+    pub fn x25519_public_key_fails_for_ecdsa_key() {
+        let service = CryptoService::new();
+
+        let key = service.create_key(&KeyInfo { seed: None, crypto_type: Some(SECP256K1_CRYPTO_TYPE.to_string()), key_manager: None }).unwrap();
+
+        let res = service.x25519_public_key(&key.verkey);
+        assert_kind!(IndyErrorKind::UnknownCrypto, res);
+    }
+
+    #[test]
+    pub fn encrypt_detached_decrypt_detached_works() {
+        let service = CryptoService::new();
+        let plaintext = b"Hello World";
+        let aad = b"protected";
+
+        for enc in &[ContentEncryption::A256GCM, ContentEncryption::XC20P] {
+            let cek = service.gen_content_encryption_key(*enc).unwrap();
+            let (ciphertext, iv, tag) = service.encrypt_detached(*enc, plaintext, aad, &cek).unwrap();
+
+            let decrypted = service.decrypt_detached(*enc, &ciphertext, aad, &cek, &iv, &tag).unwrap();
+            assert_eq!(plaintext.to_vec(), decrypted);
+
+            let res = service.decrypt_detached(*enc, &ciphertext, b"other protected", &cek, &iv, &tag);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+    }
+
+    #[test]
+    fn jws_alg_works() {
+        let service = CryptoService::new();
+
+        assert_eq!("EdDSA", service.jws_alg("GjZWsBLgZCR18aL468JAT7w9CZRiBnpxUPPgyQxh4voa").unwrap());
+        assert_eq!("ES256K", service.jws_alg("verkey:secp256k1").unwrap());
+        assert_eq!("ES256", service.jws_alg("verkey:secp256r1").unwrap());
+        assert_kind!(IndyErrorKind::UnknownCrypto, service.jws_alg("verkey:unknown"));
+    }
+
+    #[test]
+    fn create_my_did_works_for_key_method() {
+        let service = CryptoService::new();
+
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: Some(DidMethod("key".to_string())), key_manager: None, peer_numalgo: None };
+        let (my_did, key) = service.create_my_did(&did_info).unwrap();
+
+        assert!(my_did.did.0.starts_with("did:key:z6Mk"));
+        assert_eq!(key.verkey, service.verkey_for_self_certifying_did(&my_did.did).unwrap());
+    }
+
+    #[test]
+    fn create_my_did_works_for_peer_method() {
+        let service = CryptoService::new();
+
+        for numalgo in &[0, 2] {
+            let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: Some(DidMethod("peer".to_string())), key_manager: None, peer_numalgo: Some(*numalgo) };
+            let (my_did, key) = service.create_my_did(&did_info).unwrap();
+
+            assert!(my_did.did.0.starts_with(&format!("did:peer:{}", numalgo)));
+            assert_eq!(key.verkey, service.verkey_for_self_certifying_did(&my_did.did).unwrap());
+        }
+    }
+
+    #[test]
+    fn create_my_did_not_works_for_key_method_and_passed_did() {
+        let service = CryptoService::new();
+
+        let did = DidValue("NcYxiDXkpYi6ov5FcYDi1e".to_string());
+        let did_info = MyDidInfo { did: Some(did), cid: None, seed: None, crypto_type: None, method_name: Some(DidMethod("key".to_string())), key_manager: None, peer_numalgo: None };
+
+        assert_kind!(IndyErrorKind::InvalidStructure, service.create_my_did(&did_info));
+    }
+
+    #[test]
+    fn create_their_did_works_for_did_key() {
+        let service = CryptoService::new();
+        let did = DidValue("did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp".to_string());
+
+        let their_did = service.create_their_did(&TheirDidInfo::new(did.clone(), None)).unwrap();
+        assert_eq!("4zvwRjXUKGfvwnParsHAS3HuSVzV5cA4McphgmoCtajS", their_did.verkey);
+
+        let res = service.create_their_did(&TheirDidInfo::new(did, Some("GjZWsBLgZCR18aL468JAT7w9CZRiBnpxUPPgyQxh4voa".to_string())));
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    #[test]
+    pub fn session_root_key_works() {
+        let service = CryptoService::new();
+
+        let (pk1, sk1) = ed25519_box::gen_keypair();
+        let (pk2, sk2) = ed25519_box::gen_keypair();
+
+        let root1 = service.session_root_key(&sk1, &pk2, "session").unwrap();
+        let root2 = service.session_root_key(&sk2, &pk1, "session").unwrap();
+        assert_eq!(root1, root2);
+
+        let other_root = service.session_root_key(&sk1, &pk2, "other session").unwrap();
+        assert_ne!(root1, other_root);
+    }
+
+    #[test]
+    pub fn session_ratchet_works() {
+        let service = CryptoService::new();
+
+        let (initiator_chain_key, responder_chain_key) = service.session_chain_keys(&[1u8; 32]).unwrap();
+        assert_ne!(initiator_chain_key, responder_chain_key);
+
+        let (message_key1, chain_key1) = service.session_ratchet(&initiator_chain_key).unwrap();
+        let (message_key2, chain_key2) = service.session_ratchet(&chain_key1).unwrap();
+
+        assert_eq!(32, message_key1.len());
+        assert_ne!(message_key1, message_key2);
+        assert_ne!(chain_key1, chain_key2);
+        assert_eq!((message_key1, chain_key1), service.session_ratchet(&initiator_chain_key).unwrap());
+    }
+}
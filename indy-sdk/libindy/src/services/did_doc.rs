@@ -0,0 +1,294 @@
+use rust_base58::ToBase58;
+
+use indy_api_types::errors::prelude::*;
+
+use crate::domain::crypto::did::DidValue;
+use crate::domain::did_doc::*;
+use crate::domain::ledger::attrib::Endpoint;
+use crate::services::crypto::{DEFAULT_CRYPTO_TYPE, SECP256K1_CRYPTO_TYPE, SECP256R1_CRYPTO_TYPE};
+use crate::utils::crypto::verkey_builder::{build_full_verkey, split_verkey, verkey_to_did_key};
+
+// Method used for the document id of legacy unqualified DIDs
+const DEFAULT_DID_METHOD: &str = "sov";
+
+const VERIFICATION_KEY_FRAGMENT: &str = "key-1";
+const KEY_AGREEMENT_FRAGMENT: &str = "key-agreement-1";
+
+pub struct DidDocService {}
+
+impl DidDocService {
+    pub fn new() -> DidDocService {
+        DidDocService {}
+    }
+
+    pub fn build_did_doc(&self,
+                         did: &DidValue,
+                         verkey: &str,
+                         key_agreement_pk: Option<&[u8]>,
+                         endpoint: Option<&Endpoint>) -> IndyResult<DidDocument> {
+        trace!("build_did_doc >>> did: {:?}, verkey: {:?}, key_agreement_pk: {:?}, endpoint: {:?}", did, verkey, key_agreement_pk, endpoint);
+
+        let id = if did.is_fully_qualified() { did.0.clone() } else { did.qualify(DEFAULT_DID_METHOD).0 };
+
+        let (vk, crypto_type_name) = split_verkey(verkey);
+
+        let (key_type, key_context) = match crypto_type_name {
+            DEFAULT_CRYPTO_TYPE => (ED25519_VERIFICATION_KEY_2018, Some(ED25519_2018_CONTEXT)),
+            SECP256K1_CRYPTO_TYPE => (ECDSA_SECP256K1_VERIFICATION_KEY_2019, Some(SECP256K1_2019_CONTEXT)),
+            SECP256R1_CRYPTO_TYPE => (ECDSA_SECP256R1_VERIFICATION_KEY_2019, None),
+            _ => return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("DID Document can't be built for {} keys", crypto_type_name)))
+        };
+
+        let mut context = vec![DID_CONTEXT.to_string()];
+        context.extend(key_context.map(String::from));
+
+        let key_id = format!("{}#{}", id, VERIFICATION_KEY_FRAGMENT);
+
+        let verification_method = VerificationMethod {
+            id: key_id.clone(),
+            type_: key_type.to_string(),
+            controller: id.clone(),
+            public_key_base58: vk.to_string(),
+        };
+
+        let key_agreement = match key_agreement_pk {
+            Some(pk) => {
+                context.push(X25519_2019_CONTEXT.to_string());
+                vec![VerificationMethod {
+                    id: format!("{}#{}", id, KEY_AGREEMENT_FRAGMENT),
+                    type_: X25519_KEY_AGREEMENT_KEY_2019.to_string(),
+                    controller: id.clone(),
+                    public_key_base58: pk.to_base58(),
+                }]
+            }
+            None => Vec::new()
+        };
+
+        let service = match endpoint {
+            Some(endpoint) => self._build_services(did, &id, &key_id, verkey, endpoint)?,
+            None => Vec::new()
+        };
+
+        let res = DidDocument {
+            context,
+            id,
+            verification_method: vec![verification_method],
+            authentication: vec![key_id.clone()],
+            assertion_method: vec![key_id],
+            key_agreement,
+            service,
+        };
+
+        trace!("build_did_doc <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn _build_services(&self, did: &DidValue, id: &str, key_id: &str, verkey: &str, endpoint: &Endpoint) -> IndyResult<Vec<Service>> {
+        // Transport key can be abbreviated against the DID the same way as the DID key.
+        // DIDs of other methods (did:key, did:peer) aren't made of the key bytes, so there is nothing to expand against.
+        let transport_key = match endpoint.verkey {
+            Some(ref transport_key) if transport_key.starts_with('~') && !did.is_abbreviatable() => {
+                return Err(err_msg(IndyErrorKind::InvalidStructure,
+                                   format!("Abbreviated transport key can't be used with DID \"{}\", full key is required", did.0)));
+            }
+            Some(ref transport_key) => Some(build_full_verkey(&did.to_unqualified().0, Some(transport_key))?),
+            None => None
+        };
+
+        // Transport key equal to the DID key is referenced by the verification method id
+        let recipient_key = match transport_key {
+            Some(ref transport_key) if transport_key != verkey => verkey_to_did_key(transport_key)?,
+            _ => key_id.to_string()
+        };
+
+        let types = match endpoint.types {
+            Some(ref types) if !types.is_empty() => types.clone(),
+            _ => vec![DID_COMMUNICATION_SERVICE.to_string()]
+        };
+
+        let services = types
+            .into_iter()
+            .map(|type_| {
+                let is_endpoint = type_ == ENDPOINT_SERVICE;
+
+                Service {
+                    id: format!("{}#{}", id, type_),
+                    service_endpoint: endpoint.ha.clone(),
+                    priority: if is_endpoint { None } else { Some(0) },
+                    recipient_keys: if is_endpoint { None } else { Some(vec![recipient_key.clone()]) },
+                    routing_keys: if is_endpoint { None } else { Some(endpoint.routing_keys.clone().unwrap_or_default()) },
+                    type_,
+                }
+            })
+            .collect();
+
+        Ok(services)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_base58::FromBase58;
+
+    use super::*;
+
+    const DID: &str = "VsKV7grR1BUE29mG2Fm2kX";
+    const VERKEY: &str = "GjZWsBLgZCR18aL468JAT7w9CZRiBnpxUPPgyQxh4voa";
+    const TRANSPORT_KEY: &str = "CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW";
+    const ENDPOINT: &str = "127.0.0.1:9700";
+
+    #[test]
+    fn build_did_doc_works_for_unqualified_did() {
+        let service = DidDocService::new();
+
+        let did_doc = service.build_did_doc(&DidValue(DID.to_string()), VERKEY, None, None).unwrap();
+
+        assert_eq!(format!("did:sov:{}", DID), did_doc.id);
+        assert_eq!(vec![DID_CONTEXT.to_string(), ED25519_2018_CONTEXT.to_string()], did_doc.context);
+        assert_eq!(1, did_doc.verification_method.len());
+        assert_eq!(format!("did:sov:{}#key-1", DID), did_doc.verification_method[0].id);
+        assert_eq!(ED25519_VERIFICATION_KEY_2018, did_doc.verification_method[0].type_);
+        assert_eq!(VERKEY, did_doc.verification_method[0].public_key_base58);
+        assert_eq!(vec![did_doc.verification_method[0].id.clone()], did_doc.authentication);
+        assert!(did_doc.key_agreement.is_empty());
+        assert!(did_doc.service.is_empty());
+    }
+
+    #[test]
+    fn build_did_doc_works_for_qualified_did() {
+        let service = DidDocService::new();
+
+        let did = DidValue(format!("did:peer:{}", DID));
+        let did_doc = service.build_did_doc(&did, VERKEY, None, None).unwrap();
+
+        assert_eq!(did.0, did_doc.id);
+        assert_eq!(did.0, did_doc.verification_method[0].controller);
+    }
+
+    #[test]
+    fn build_did_doc_works_for_key_agreement() {
+        let service = DidDocService::new();
+
+        let did_doc = service.build_did_doc(&DidValue(DID.to_string()), VERKEY, Some(&[1u8; 32][..]), None).unwrap();
+
+        assert_eq!(1, did_doc.key_agreement.len());
+        assert_eq!(X25519_KEY_AGREEMENT_KEY_2019, did_doc.key_agreement[0].type_);
+        assert_eq!([1u8; 32].to_base58(), did_doc.key_agreement[0].public_key_base58);
+        assert!(did_doc.context.contains(&X25519_2019_CONTEXT.to_string()));
+    }
+
+    #[test]
+    fn build_did_doc_works_for_secp256k1_key() {
+        let service = DidDocService::new();
+
+        let verkey = format!("{}:{}", VERKEY, SECP256K1_CRYPTO_TYPE);
+        let did_doc = service.build_did_doc(&DidValue(DID.to_string()), &verkey, None, None).unwrap();
+
+        assert_eq!(ECDSA_SECP256K1_VERIFICATION_KEY_2019, did_doc.verification_method[0].type_);
+        assert_eq!(VERKEY, did_doc.verification_method[0].public_key_base58);
+    }
+
+    #[test]
+    fn build_did_doc_not_works_for_unknown_crypto_type() {
+        let service = DidDocService::new();
+
+        let verkey = format!("{}:unknown", VERKEY);
+        let res = service.build_did_doc(&DidValue(DID.to_string()), &verkey, None, None);
+
+        assert_kind!(IndyErrorKind::UnknownCrypto, res);
+    }
+
+    #[test]
+    fn build_did_doc_works_for_endpoint() {
+        let service = DidDocService::new();
+
+        let endpoint = Endpoint::new(ENDPOINT.to_string(), Some(VERKEY.to_string()));
+        let did_doc = service.build_did_doc(&DidValue(DID.to_string()), VERKEY, None, Some(&endpoint)).unwrap();
+
+        assert_eq!(1, did_doc.service.len());
+        assert_eq!(format!("did:sov:{}#did-communication", DID), did_doc.service[0].id);
+        assert_eq!(DID_COMMUNICATION_SERVICE, did_doc.service[0].type_);
+        assert_eq!(ENDPOINT, did_doc.service[0].service_endpoint);
+        assert_eq!(Some(vec![format!("did:sov:{}#key-1", DID)]), did_doc.service[0].recipient_keys);
+        assert_eq!(Some(vec![]), did_doc.service[0].routing_keys);
+    }
+
+    #[test]
+    fn build_did_doc_works_for_endpoint_with_other_transport_key() {
+        let service = DidDocService::new();
+
+        let endpoint = Endpoint::new(ENDPOINT.to_string(), Some(TRANSPORT_KEY.to_string()));
+        let did_doc = service.build_did_doc(&DidValue(DID.to_string()), VERKEY, None, Some(&endpoint)).unwrap();
+
+        assert_eq!(Some(vec![verkey_to_did_key(TRANSPORT_KEY).unwrap()]), did_doc.service[0].recipient_keys);
+    }
+
+    #[test]
+    fn build_did_doc_works_for_endpoint_with_abbreviated_transport_key() {
+        let service = DidDocService::new();
+
+        let key_end = TRANSPORT_KEY.from_base58().unwrap()[16..].to_vec();
+
+        let endpoint = Endpoint::new(ENDPOINT.to_string(), Some(format!("~{}", key_end.to_base58())));
+        let did_doc = service.build_did_doc(&DidValue(DID.to_string()), VERKEY, None, Some(&endpoint)).unwrap();
+
+        let mut full_key = DID.from_base58().unwrap();
+        full_key.extend(key_end);
+
+        assert_eq!(Some(vec![verkey_to_did_key(&full_key.to_base58()).unwrap()]), did_doc.service[0].recipient_keys);
+    }
+
+    #[test]
+    fn build_did_doc_works_for_endpoint_with_abbreviated_transport_key_for_self_certifying_did() {
+        let service = DidDocService::new();
+
+        let key_end = TRANSPORT_KEY.from_base58().unwrap()[16..].to_vec();
+        let endpoint = Endpoint::new(ENDPOINT.to_string(), Some(format!("~{}", key_end.to_base58())));
+
+        let did_key = verkey_to_did_key(VERKEY).unwrap();
+        let did_peer = format!("did:peer:0{}", &did_key["did:key:".len()..]);
+
+        for did in vec![did_key, did_peer] {
+            let err = service.build_did_doc(&DidValue(did), VERKEY, None, Some(&endpoint)).unwrap_err();
+            assert_eq!(IndyErrorKind::InvalidStructure, err.kind());
+        }
+    }
+
+    #[test]
+    fn build_did_doc_works_for_endpoint_with_types_and_routing_keys() {
+        let service = DidDocService::new();
+
+        let endpoint = Endpoint {
+            ha: "https://agent.example.com".to_string(),
+            verkey: None,
+            routing_keys: Some(vec![TRANSPORT_KEY.to_string()]),
+            types: Some(vec![ENDPOINT_SERVICE.to_string(), DID_COMMUNICATION_SERVICE.to_string()]),
+        };
+        let did_doc = service.build_did_doc(&DidValue(DID.to_string()), VERKEY, None, Some(&endpoint)).unwrap();
+
+        assert_eq!(2, did_doc.service.len());
+
+        assert_eq!(format!("did:sov:{}#endpoint", DID), did_doc.service[0].id);
+        assert_eq!(ENDPOINT_SERVICE, did_doc.service[0].type_);
+        assert_eq!(None, did_doc.service[0].recipient_keys);
+
+        assert_eq!(format!("did:sov:{}#did-communication", DID), did_doc.service[1].id);
+        assert_eq!(Some(vec![TRANSPORT_KEY.to_string()]), did_doc.service[1].routing_keys);
+    }
+
+    #[test]
+    fn build_did_doc_serializes_to_json_ld() {
+        let service = DidDocService::new();
+
+        let endpoint = Endpoint::new(ENDPOINT.to_string(), None);
+        let did_doc = service.build_did_doc(&DidValue(DID.to_string()), VERKEY, None, Some(&endpoint)).unwrap();
+
+        let did_doc_json = serde_json::to_value(&did_doc).unwrap();
+
+        assert_eq!(json!(DID_CONTEXT), did_doc_json["@context"][0]);
+        assert_eq!(json!(VERKEY), did_doc_json["verificationMethod"][0]["publicKeyBase58"]);
+        assert_eq!(json!(ENDPOINT), did_doc_json["service"][0]["serviceEndpoint"]);
+        assert!(did_doc_json.get("keyAgreement").is_none());
+    }
+}
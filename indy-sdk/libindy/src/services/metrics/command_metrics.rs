@@ -43,7 +43,7 @@ impl From<&IssuerCommand> for CommandMetric {
             IssuerCommand::CreateAndStoreCredentialDefinition(_, _, _, _, _, _, _) => {
                 CommandMetric::IssuerCommandCreateAndStoreCredentialDefinition
             }
-            IssuerCommand::CreateAndStoreCredentialDefinitionContinue(_, _, _, _, _, _, _, _) => {
+            IssuerCommand::CreateAndStoreCredentialDefinitionContinue(_, _, _, _, _, _, _, _, _) => {
                 CommandMetric::IssuerCommandCreateAndStoreCredentialDefinitionContinue
             }
             IssuerCommand::RotateCredentialDefinitionStart(_, _, _, _) => {
@@ -64,12 +64,36 @@ impl From<&IssuerCommand> for CommandMetric {
             IssuerCommand::CreateCredential(_, _, _, _, _, _, _) => {
                 CommandMetric::IssuerCommandCreateCredential
             }
+            IssuerCommand::CreateCredentialWithRollover(_, _, _, _, _, _, _) => {
+                CommandMetric::IssuerCommandCreateCredentialWithRollover
+            }
+            IssuerCommand::CreateCredentialsBatch(_, _, _, _) => {
+                CommandMetric::IssuerCommandCreateCredentialsBatch
+            }
+            IssuerCommand::GetNextRevocationRegistry(_, _, _) => {
+                CommandMetric::IssuerCommandGetNextRevocationRegistry
+            }
             IssuerCommand::RevokeCredential(_, _, _, _, _) => {
                 CommandMetric::IssuerCommandRevokeCredential
             }
+            IssuerCommand::RevokeCredentials(_, _, _, _, _, _) => {
+                CommandMetric::IssuerCommandRevokeCredentials
+            }
             IssuerCommand::MergeRevocationRegistryDeltas(_, _, _) => {
                 CommandMetric::IssuerCommandMergeRevocationRegistryDeltas
             }
+            IssuerCommand::SetIssuedCredentialMetadata(_, _, _, _) => {
+                CommandMetric::IssuerCommandSetIssuedCredentialMetadata
+            }
+            IssuerCommand::SearchIssuedCredentials(_, _, _) => {
+                CommandMetric::IssuerCommandSearchIssuedCredentials
+            }
+            IssuerCommand::FetchIssuedCredentials(_, _, _) => {
+                CommandMetric::IssuerCommandFetchIssuedCredentials
+            }
+            IssuerCommand::CloseIssuedCredentialsSearch(_, _) => {
+                CommandMetric::IssuerCommandCloseIssuedCredentialsSearch
+            }
         }
     }
 }
@@ -103,6 +127,7 @@ impl From<&VerifierCommand> for CommandMetric {
     fn from(cmd: &VerifierCommand) -> Self {
         match cmd {
             VerifierCommand::VerifyProof(_, _, _, _, _, _, _) => { CommandMetric::VerifierCommandVerifyProof }
+            VerifierCommand::VerifyProofWithReport(_, _, _, _, _, _, _) => { CommandMetric::VerifierCommandVerifyProofWithReport }
             VerifierCommand::GenerateNonce(_) => { CommandMetric::VerifierCommandGenerateNonce }
         }
     }
@@ -133,12 +158,19 @@ impl From<&Command> for CommandMetric {
                     CryptoCommand::GetKeyMetadata(_, _, _) => { CommandMetric::CryptoCommandGetKeyMetadata }
                     CryptoCommand::CryptoSign(_, _, _, _) => { CommandMetric::CryptoCommandCryptoSign }
                     CryptoCommand::CryptoVerify(_, _, _, _) => { CommandMetric::CryptoCommandCryptoVerify }
+                    CryptoCommand::SignJws(_, _, _, _, _) => { CommandMetric::CryptoCommandSignJws }
+                    CryptoCommand::VerifyJws(_, _, _) => { CommandMetric::CryptoCommandVerifyJws }
+                    CryptoCommand::VerifyMulti(_, _, _, _) => { CommandMetric::CryptoCommandVerifyMulti }
                     CryptoCommand::AuthenticatedEncrypt(_, _, _, _, _) => { CommandMetric::CryptoCommandAuthenticatedEncrypt }
                     CryptoCommand::AuthenticatedDecrypt(_, _, _, _) => { CommandMetric::CryptoCommandAuthenticatedDecrypt }
                     CryptoCommand::AnonymousEncrypt(_, _, _) => { CommandMetric::CryptoCommandAnonymousEncrypt }
                     CryptoCommand::AnonymousDecrypt(_, _, _, _) => { CommandMetric::CryptoCommandAnonymousDecrypt }
-                    CryptoCommand::PackMessage(_, _, _, _, _) => { CommandMetric::CryptoCommandPackMessage }
+                    CryptoCommand::PackMessage(_, _, _, _, _, _) => { CommandMetric::CryptoCommandPackMessage }
                     CryptoCommand::UnpackMessage(_, _, _) => { CommandMetric::CryptoCommandUnpackMessage }
+                    CryptoCommand::SessionStart(_, _, _, _, _) => { CommandMetric::CryptoCommandSessionStart }
+                    CryptoCommand::SessionEncrypt(_, _, _, _) => { CommandMetric::CryptoCommandSessionEncrypt }
+                    CryptoCommand::SessionDecrypt(_, _, _) => { CommandMetric::CryptoCommandSessionDecrypt }
+                    CryptoCommand::RegisterKeyManager(_, _, _, _, _, _, _) => { CommandMetric::CryptoCommandRegisterKeyManager }
                 }
             }
             Command::Ledger(cmd) => {
@@ -214,6 +246,9 @@ impl From<&Command> for CommandMetric {
                     DidCommand::CreateAndStoreMyDid(_, _, _) => { CommandMetric::DidCommandCreateAndStoreMyDid }
                     DidCommand::ReplaceKeysStart(_, _, _, _) => { CommandMetric::DidCommandReplaceKeysStart }
                     DidCommand::ReplaceKeysApply(_, _, _) => { CommandMetric::DidCommandReplaceKeysApply }
+                    DidCommand::ReplaceKeysRollback(_, _, _) => { CommandMetric::DidCommandReplaceKeysRollback }
+                    DidCommand::GetDidKeyHistory(_, _, _) => { CommandMetric::DidCommandGetDidKeyHistory }
+                    DidCommand::VerifyWithDidKeyHistory(_, _, _, _, _, _) => { CommandMetric::DidCommandVerifyWithDidKeyHistory }
                     DidCommand::StoreTheirDid(_, _, _) => { CommandMetric::DidCommandStoreTheirDid }
                     DidCommand::GetMyDidWithMeta(_, _, _) => { CommandMetric::DidCommandGetMyDidWithMeta }
                     DidCommand::ListMyDidsWithMeta(_, _) => { CommandMetric::DidCommandListMyDidsWithMeta }
@@ -224,6 +259,7 @@ impl From<&Command> for CommandMetric {
                     DidCommand::SetDidMetadata(_, _, _, _) => { CommandMetric::DidCommandSetDidMetadata }
                     DidCommand::GetDidMetadata(_, _, _) => { CommandMetric::DidCommandGetDidMetadata }
                     DidCommand::AbbreviateVerkey(_, _, _) => { CommandMetric::DidCommandAbbreviateVerkey }
+                    DidCommand::BuildDidDoc(_, _, _) => { CommandMetric::DidCommandBuildDidDoc }
                     DidCommand::GetNymAck(_, _, _, _) => { CommandMetric::DidCommandGetNymAck }
                     DidCommand::GetAttribAck(_, _, _) => { CommandMetric::DidCommandGetAttribAck }
                     DidCommand::QualifyDid(_, _, _, _) => { CommandMetric::DidCommandQualifyDid }
@@ -239,10 +275,14 @@ impl From<&Command> for CommandMetric {
                     WalletCommand::Close(_, _) => { CommandMetric::WalletCommandClose }
                     WalletCommand::Delete(_, _, _) => { CommandMetric::WalletCommandDelete }
                     WalletCommand::DeleteContinue(_, _, _, _, _) => { CommandMetric::WalletCommandDeleteContinue }
-                    WalletCommand::Export(_, _, _) => { CommandMetric::WalletCommandExport }
+                    WalletCommand::Export(_, _, _, _) => { CommandMetric::WalletCommandExport }
                     WalletCommand::ExportContinue(_, _, _, _, _) => { CommandMetric::WalletCommandExportContinue }
-                    WalletCommand::Import(_, _, _, _) => { CommandMetric::WalletCommandImport }
+                    WalletCommand::Import(_, _, _, _, _) => { CommandMetric::WalletCommandImport }
                     WalletCommand::ImportContinue(_, _, _, _, _) => { CommandMetric::WalletCommandImportContinue }
+                    WalletCommand::RotateKeys(_, _, _) => { CommandMetric::WalletCommandRotateKeys }
+                    WalletCommand::RotateKeysContinue(_, _, _, _) => { CommandMetric::WalletCommandRotateKeysContinue }
+                    WalletCommand::Verify(_, _, _) => { CommandMetric::WalletCommandVerify }
+                    WalletCommand::CheckMigrations(_, _, _) => { CommandMetric::WalletCommandCheckMigrations }
                     WalletCommand::GenerateKey(_, _) => { CommandMetric::WalletCommandGenerateKey }
                     WalletCommand::DeriveKey(_, _) => { CommandMetric::WalletCommandDeriveKey }
                 }
@@ -254,6 +294,10 @@ impl From<&Command> for CommandMetric {
                     PairwiseCommand::ListPairwise(_, _) => { CommandMetric::PairwiseCommandListPairwise }
                     PairwiseCommand::GetPairwise(_, _, _) => { CommandMetric::PairwiseCommandGetPairwise }
                     PairwiseCommand::SetPairwiseMetadata(_, _, _, _) => { CommandMetric::PairwiseCommandSetPairwiseMetadata }
+                    PairwiseCommand::SetPairwiseTags(_, _, _, _) => { CommandMetric::PairwiseCommandSetPairwiseTags }
+                    PairwiseCommand::OpenPairwiseSearch(_, _, _) => { CommandMetric::PairwiseCommandOpenPairwiseSearch }
+                    PairwiseCommand::FetchPairwiseSearchNextRecords(_, _, _, _) => { CommandMetric::PairwiseCommandFetchPairwiseSearchNextRecords }
+                    PairwiseCommand::ClosePairwiseSearch(_, _) => { CommandMetric::PairwiseCommandClosePairwiseSearch }
                 }
             }
             Command::NonSecrets(cmd) => {
@@ -264,10 +308,13 @@ impl From<&Command> for CommandMetric {
                     NonSecretsCommand::AddRecordTags(_, _, _, _, _) => { CommandMetric::NonSecretsCommandAddRecordTags }
                     NonSecretsCommand::DeleteRecordTags(_, _, _, _, _) => { CommandMetric::NonSecretsCommandDeleteRecordTags }
                     NonSecretsCommand::DeleteRecord(_, _, _, _) => { CommandMetric::NonSecretsCommandDeleteRecord }
+                    NonSecretsCommand::BatchRecords(_, _, _) => { CommandMetric::NonSecretsCommandBatchRecords }
                     NonSecretsCommand::GetRecord(_, _, _, _, _) => { CommandMetric::NonSecretsCommandGetRecord }
                     NonSecretsCommand::OpenSearch(_, _, _, _, _) => { CommandMetric::NonSecretsCommandOpenSearch }
                     NonSecretsCommand::FetchSearchNextRecords(_, _, _, _) => { CommandMetric::NonSecretsCommandFetchSearchNextRecords }
                     NonSecretsCommand::CloseSearch(_, _) => { CommandMetric::NonSecretsCommandCloseSearch }
+                    NonSecretsCommand::SearchRecordsPage(_, _, _, _, _, _, _) => { CommandMetric::NonSecretsCommandSearchRecordsPage }
+                    NonSecretsCommand::CountRecords(_, _, _, _, _) => { CommandMetric::NonSecretsCommandCountRecords }
                 }
             }
             Command::Payments(cmd) => {
@@ -339,8 +386,16 @@ pub enum CommandMetric {
     IssuerCommandCreateAndStoreRevocationRegistry,
     IssuerCommandCreateCredentialOffer,
     IssuerCommandCreateCredential,
+    IssuerCommandCreateCredentialWithRollover,
+    IssuerCommandCreateCredentialsBatch,
+    IssuerCommandGetNextRevocationRegistry,
     IssuerCommandRevokeCredential,
+    IssuerCommandRevokeCredentials,
     IssuerCommandMergeRevocationRegistryDeltas,
+    IssuerCommandSetIssuedCredentialMetadata,
+    IssuerCommandSearchIssuedCredentials,
+    IssuerCommandFetchIssuedCredentials,
+    IssuerCommandCloseIssuedCredentialsSearch,
     // ProverCommand
     ProverCommandCreateMasterSecret,
     ProverCommandCreateCredentialRequest,
@@ -362,6 +417,7 @@ pub enum CommandMetric {
     ProverCommandUpdateRevocationState,
     // VerifierCommand
     VerifierCommandVerifyProof,
+    VerifierCommandVerifyProofWithReport,
     VerifierCommandGenerateNonce,
     // AnoncredsCommand
     AnoncredsCommandToUnqualified,
@@ -374,12 +430,19 @@ pub enum CommandMetric {
     CryptoCommandGetKeyMetadata,
     CryptoCommandCryptoSign,
     CryptoCommandCryptoVerify,
+    CryptoCommandSignJws,
+    CryptoCommandVerifyJws,
+    CryptoCommandVerifyMulti,
     CryptoCommandAuthenticatedEncrypt,
     CryptoCommandAuthenticatedDecrypt,
     CryptoCommandAnonymousEncrypt,
     CryptoCommandAnonymousDecrypt,
     CryptoCommandPackMessage,
     CryptoCommandUnpackMessage,
+    CryptoCommandSessionStart,
+    CryptoCommandSessionEncrypt,
+    CryptoCommandSessionDecrypt,
+    CryptoCommandRegisterKeyManager,
     LedgerCommandSignAndSubmitRequest,
     // LedgerCommand
     LedgerCommandSubmitRequest,
@@ -446,6 +509,9 @@ pub enum CommandMetric {
     DidCommandCreateAndStoreMyDid,
     DidCommandReplaceKeysStart,
     DidCommandReplaceKeysApply,
+    DidCommandReplaceKeysRollback,
+    DidCommandGetDidKeyHistory,
+    DidCommandVerifyWithDidKeyHistory,
     DidCommandStoreTheirDid,
     DidCommandGetMyDidWithMeta,
     DidCommandListMyDidsWithMeta,
@@ -456,6 +522,7 @@ pub enum CommandMetric {
     DidCommandSetDidMetadata,
     DidCommandGetDidMetadata,
     DidCommandAbbreviateVerkey,
+    DidCommandBuildDidDoc,
     DidCommandGetNymAck,
     DidCommandGetAttribAck,
     DidCommandQualifyDid,
@@ -472,6 +539,10 @@ pub enum CommandMetric {
     WalletCommandExportContinue,
     WalletCommandImport,
     WalletCommandImportContinue,
+    WalletCommandRotateKeys,
+    WalletCommandRotateKeysContinue,
+    WalletCommandVerify,
+    WalletCommandCheckMigrations,
     WalletCommandGenerateKey,
     WalletCommandDeriveKey,
     // PairwiseCommand
@@ -480,6 +551,10 @@ pub enum CommandMetric {
     PairwiseCommandListPairwise,
     PairwiseCommandGetPairwise,
     PairwiseCommandSetPairwiseMetadata,
+    PairwiseCommandSetPairwiseTags,
+    PairwiseCommandOpenPairwiseSearch,
+    PairwiseCommandFetchPairwiseSearchNextRecords,
+    PairwiseCommandClosePairwiseSearch,
     // NonSecretsCommand
     NonSecretsCommandAddRecord,
     NonSecretsCommandUpdateRecordValue,
@@ -487,10 +562,13 @@ pub enum CommandMetric {
     NonSecretsCommandAddRecordTags,
     NonSecretsCommandDeleteRecordTags,
     NonSecretsCommandDeleteRecord,
+    NonSecretsCommandBatchRecords,
     NonSecretsCommandGetRecord,
     NonSecretsCommandOpenSearch,
     NonSecretsCommandFetchSearchNextRecords,
     NonSecretsCommandCloseSearch,
+    NonSecretsCommandSearchRecordsPage,
+    NonSecretsCommandCountRecords,
     // PaymentsCommand
     PaymentsCommandRegisterMethod,
     PaymentsCommandCreateAddress,
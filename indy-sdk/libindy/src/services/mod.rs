@@ -1,6 +1,7 @@
 pub mod anoncreds;
 pub mod blob_storage;
 pub mod crypto;
+pub mod did_doc;
 pub mod ledger;
 pub mod payments;
 pub mod pool;
@@ -1,6 +1,21 @@
 use indy_api_types::errors::prelude::*;
 use rust_base58::{FromBase58, ToBase58};
-use crate::services::crypto::DEFAULT_CRYPTO_TYPE;
+use crate::services::crypto::{DEFAULT_CRYPTO_TYPE, SECP256K1_CRYPTO_TYPE, SECP256R1_CRYPTO_TYPE};
+
+pub const DID_KEY_PREFIX: &str = "did:key:";
+pub const DID_PEER_PREFIX: &str = "did:peer:";
+const DID_PEER_PURPOSE_AUTHENTICATION: char = 'V';
+const DID_PEER_PURPOSE_KEY_AGREEMENT: char = 'E';
+// Multibase prefix of base58btc encoding
+const MULTIBASE_BASE58_BTC: char = 'z';
+
+// Multicodec prefixes (unsigned varint encoded) of public key types did:key and did:peer support
+const MULTICODECS: [(&str, &[u8]); 3] = [
+    (DEFAULT_CRYPTO_TYPE, &[0xed, 0x01]),
+    (SECP256K1_CRYPTO_TYPE, &[0xe7, 0x01]),
+    (SECP256R1_CRYPTO_TYPE, &[0x80, 0x24]),
+];
+const X25519_MULTICODEC: &[u8] = &[0xec, 0x01];
 
 
 pub fn build_full_verkey(dest: &str, verkey: Option<&str>) -> Result<String, IndyError> {
@@ -68,53 +83,212 @@ pub fn verkey_get_cryptoname(verkey: &str) -> &str {
     }
 }
 
+pub fn verkey_to_did_key(verkey: &str) -> IndyResult<String> {
+    Ok(format!("{}{}", DID_KEY_PREFIX, verkey_to_multibase(verkey)?))
+}
+
+// Accepts did:key both as DID and as DID URL with verification method fragment
+pub fn did_key_to_verkey(did_key: &str) -> IndyResult<String> {
+    let did_key = did_key.split('#').next().unwrap_or_default();
+
+    if !did_key.starts_with(DID_KEY_PREFIX) {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Invalid did:key: {}", did_key)));
+    }
+
+    multibase_to_verkey(&did_key[DID_KEY_PREFIX.len()..])
+}
+
+// did:peer numalgo 2 also contains X25519 key for key agreement if it can be derived from the verkey
+pub fn verkey_to_did_peer(verkey: &str, numalgo: u8, x25519_pk: Option<&[u8]>) -> IndyResult<String> {
+    match numalgo {
+        0 => Ok(format!("{}0{}", DID_PEER_PREFIX, verkey_to_multibase(verkey)?)),
+        2 => {
+            let key_agreement = x25519_pk
+                .map(|pk| format!(".{}{}", DID_PEER_PURPOSE_KEY_AGREEMENT, _multibase(X25519_MULTICODEC, pk)))
+                .unwrap_or_default();
+
+            Ok(format!("{}2{}.{}{}", DID_PEER_PREFIX, key_agreement, DID_PEER_PURPOSE_AUTHENTICATION, verkey_to_multibase(verkey)?))
+        }
+        _ => Err(err_msg(IndyErrorKind::InvalidStructure, format!("Unsupported did:peer numalgo: {}", numalgo)))
+    }
+}
+
+// Resolves verkey of did:peer numalgo 0 or the first authentication key of numalgo 2
+pub fn did_peer_to_verkey(did_peer: &str) -> IndyResult<String> {
+    let did_peer = did_peer.split('#').next().unwrap_or_default();
+
+    if !did_peer.starts_with(DID_PEER_PREFIX) {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Invalid did:peer: {}", did_peer)));
+    }
+
+    let id = &did_peer[DID_PEER_PREFIX.len()..];
+
+    if id.starts_with('0') {
+        multibase_to_verkey(&id[1..])
+    } else if id.starts_with('2') {
+        id[1..].split('.')
+            .find(|element| element.starts_with(DID_PEER_PURPOSE_AUTHENTICATION))
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("did:peer doesn't contain authentication key: {}", did_peer)))
+            .and_then(|element| multibase_to_verkey(&element[1..]))
+    } else {
+        Err(err_msg(IndyErrorKind::InvalidStructure, format!("Unsupported did:peer numalgo: {}", did_peer)))
+    }
+}
+
+pub fn verkey_to_multibase(verkey: &str) -> IndyResult<String> {
+    let (vk, crypto_type) = split_verkey(verkey);
+
+    let codec = MULTICODECS.iter()
+        .find(|(name, _)| *name == crypto_type)
+        .map(|(_, codec)| codec)
+        .ok_or_else(|| err_msg(IndyErrorKind::UnknownCrypto, format!("Multicodec key isn't supported for crypto type: {}", crypto_type)))?;
+
+    if vk.starts_with('~') {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Multicodec key can't be built from abbreviated verkey"));
+    }
+
+    Ok(_multibase(codec, &vk.from_base58()?))
+}
+
+pub fn multibase_to_verkey(multibase: &str) -> IndyResult<String> {
+    if !multibase.starts_with(MULTIBASE_BASE58_BTC) {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Unsupported multibase encoding: {}", multibase)));
+    }
+
+    let key = multibase[1..].from_base58()
+        .map_err(|_| err_msg(IndyErrorKind::InvalidStructure, format!("Invalid multibase key: {}", multibase)))?;
+
+    let (crypto_type, codec) = MULTICODECS.iter()
+        .find(|(_, codec)| key.starts_with(codec))
+        .ok_or_else(|| err_msg(IndyErrorKind::UnknownCrypto, format!("Unsupported multicodec key type: {}", multibase)))?;
+
+    let vk = key[codec.len()..].to_base58();
+
+    if *crypto_type == DEFAULT_CRYPTO_TYPE {
+        Ok(vk)
+    } else {
+        Ok(format!("{}:{}", vk, crypto_type))
+    }
+}
+
+fn _multibase(codec: &[u8], key: &[u8]) -> String {
+    let mut res = codec.to_vec();
+    res.extend_from_slice(key);
+    format!("{}{}", MULTIBASE_BASE58_BTC, res.to_base58())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    # [test]
+    #[test]
     fn split_verkey_empty() {
         assert_eq!(split_verkey(""), ("", DEFAULT_CRYPTO_TYPE))
     }
 
-    # [test]
+    #[test]
     fn split_verkey_single_colon() {
         assert_eq!(split_verkey(":"), ("", DEFAULT_CRYPTO_TYPE))
     }
 
-    # [test]
+    #[test]
     fn split_verkey_ends_with_colon() {
         assert_eq!(split_verkey("foo:"), ("foo", DEFAULT_CRYPTO_TYPE))
     }
 
-    # [test]
+    #[test]
     fn split_verkey_starts_with_colon() {
         assert_eq!(split_verkey(":bar"), ("", "bar"))
     }
 
-    # [test]
+    #[test]
     fn split_verkey_works() {
         assert_eq!(split_verkey("foo:bar:baz"), ("foo", "bar:baz"))
     }
 
-    # [test]
+    #[test]
     fn verkey_get_cryptoname_empty() {
         assert_eq!(verkey_get_cryptoname(""), DEFAULT_CRYPTO_TYPE)
     }
 
-    # [test]
+    #[test]
     fn verkey_get_cryptoname_single_colon() {
         assert_eq!(verkey_get_cryptoname(":"), DEFAULT_CRYPTO_TYPE)
     }
 
-    # [test]
+    #[test]
     fn verkey_get_cryptoname_ends_with_colon() {
         assert_eq!(verkey_get_cryptoname("foo:"), DEFAULT_CRYPTO_TYPE)
     }
 
-    # [test]
+    #[test]
     fn verkey_get_cryptoname_works() {
         assert_eq!(verkey_get_cryptoname("foo:bar"), "bar")
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn did_key_works_for_ed25519() {
+        let did_key = "did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp";
+        let verkey = "4zvwRjXUKGfvwnParsHAS3HuSVzV5cA4McphgmoCtajS";
+
+        assert_eq!(did_key, verkey_to_did_key(verkey).unwrap());
+        assert_eq!(verkey, did_key_to_verkey(did_key).unwrap());
+    }
+
+    #[test]
+    fn did_key_to_verkey_works_for_did_url() {
+        let did_key = "did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp#z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp";
+        assert_eq!("4zvwRjXUKGfvwnParsHAS3HuSVzV5cA4McphgmoCtajS", did_key_to_verkey(did_key).unwrap());
+    }
+
+    #[test]
+    fn did_key_works_for_secp256k1() {
+        let did_key = "did:key:zQ3shokFTS3brHcDQrn82RUDfCZESWL1ZdCEJwekUDPQiYBme";
+        let verkey = did_key_to_verkey(did_key).unwrap();
+
+        assert_eq!(SECP256K1_CRYPTO_TYPE, verkey_get_cryptoname(&verkey));
+        assert_eq!(did_key, verkey_to_did_key(&verkey).unwrap());
+    }
+
+    #[test]
+    fn did_key_to_verkey_fails_for_other_did() {
+        assert!(did_key_to_verkey("did:sov:NcYxiDXkpYi6ov5FcYDi1e").is_err());
+        assert!(did_key_to_verkey("did:key:6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp").is_err());
+    }
+
+    #[test]
+    fn did_peer_works_for_numalgo_0() {
+        let verkey = "4zvwRjXUKGfvwnParsHAS3HuSVzV5cA4McphgmoCtajS";
+        let did_peer = verkey_to_did_peer(verkey, 0, None).unwrap();
+
+        assert_eq!("did:peer:0z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp", did_peer);
+        assert_eq!(verkey, did_peer_to_verkey(&did_peer).unwrap());
+    }
+
+    #[test]
+    fn did_peer_works_for_numalgo_2() {
+        let verkey = "4zvwRjXUKGfvwnParsHAS3HuSVzV5cA4McphgmoCtajS";
+        let did_peer = verkey_to_did_peer(verkey, 2, Some(&[1u8; 32][..])).unwrap();
+
+        assert!(did_peer.starts_with("did:peer:2.Ez6LS"));
+        assert!(did_peer.ends_with(".Vz6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp"));
+        assert_eq!(verkey, did_peer_to_verkey(&did_peer).unwrap());
+    }
+
+    #[test]
+    fn did_peer_to_verkey_works_for_numalgo_2_with_service() {
+        let did_peer = "did:peer:2.Ez6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc\
+                        .Vz6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V\
+                        .SeyJ0IjoiZG0iLCJzIjoiaHR0cHM6Ly9leGFtcGxlLmNvbS9lbmRwb2ludCJ9";
+
+        let verkey = did_peer_to_verkey(did_peer).unwrap();
+        assert_eq!("did:key:z6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V", verkey_to_did_key(&verkey).unwrap());
+    }
+
+    #[test]
+    fn did_peer_to_verkey_fails_for_unsupported_numalgo() {
+        assert!(did_peer_to_verkey("did:peer:1zQmZMygzYqNwU6Uhmewx5Xepf2VLp5S4HLSwwgf2aiKZuwa").is_err());
+        assert!(verkey_to_did_peer("4zvwRjXUKGfvwnParsHAS3HuSVzV5cA4McphgmoCtajS", 1, None).is_err());
+    }
+
+}
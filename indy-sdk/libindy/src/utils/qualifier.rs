@@ -4,11 +4,20 @@ lazy_static! {
     pub static ref REGEX: Regex = Regex::new("^[a-z0-9]+:([a-z0-9]+):(.*)$").unwrap();
 }
 
+// DIDs of these methods are derived from the keys and are resolved without the ledger
+pub const DID_KEY_METHOD: &str = "key";
+pub const DID_PEER_METHOD: &str = "peer";
+
 pub fn qualify(entity: &str, prefix: &str, method: &str) -> String {
     format!("{}:{}:{}", prefix, method, entity)
 }
 
 pub fn to_unqualified(entity: &str) -> String {
+    // did:key and did:peer have no unqualified form
+    if is_self_certifying(entity) {
+        return entity.to_string();
+    }
+
     match REGEX.captures(entity) {
         None => entity.to_string(),
         Some(caps) => {
@@ -30,6 +39,14 @@ pub fn is_fully_qualified(entity: &str) -> bool {
     REGEX.is_match(&entity)
 }
 
+// did:key and did:peer numalgo 0/2 with base58btc multibase keys.
+// Legacy DIDs qualified with "key" or "peer" method are not matched.
+pub fn is_self_certifying(entity: &str) -> bool {
+    entity.starts_with(&format!("did:{}:z", DID_KEY_METHOD)) ||
+        entity.starts_with(&format!("did:{}:0z", DID_PEER_METHOD)) ||
+        entity.starts_with(&format!("did:{}:2.", DID_PEER_METHOD))
+}
+
 macro_rules! qualifiable_type (($newtype:ident) => (
 
     #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
@@ -92,6 +92,159 @@ mod high_cases {
         }
     }
 
+    mod issuer_create_credentials_batch {
+        use super::*;
+
+        #[test]
+        fn issuer_create_credentials_batch_works() {
+            let (_, credential_offer, credential_req, _) = anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let cred_requests_json = json!([
+                {
+                    "cred_offer": serde_json::from_str::<serde_json::Value>(credential_offer).unwrap(),
+                    "cred_req": serde_json::from_str::<serde_json::Value>(credential_req).unwrap(),
+                    "cred_values": serde_json::from_str::<serde_json::Value>(&anoncreds::gvt_credential_values_json()).unwrap(),
+                },
+                {
+                    "cred_offer": serde_json::from_str::<serde_json::Value>(credential_offer).unwrap(),
+                    "cred_req": serde_json::from_str::<serde_json::Value>(credential_req).unwrap(),
+                    "cred_values": serde_json::from_str::<serde_json::Value>(&anoncreds::gvt2_credential_values_json()).unwrap(),
+                }
+            ]).to_string();
+
+            let (creds_json, revoc_reg_deltas_json, new_revoc_regs_json) = anoncreds::issuer_create_credentials_batch(wallet_handle, &cred_requests_json, None).unwrap();
+            assert!(revoc_reg_deltas_json.is_none());
+            assert!(new_revoc_regs_json.is_none());
+
+            let creds: Vec<serde_json::Value> = serde_json::from_str(&creds_json).unwrap();
+            assert_eq!(2, creds.len());
+            assert_eq!(anoncreds::issuer_1_gvt_cred_def_id(), creds[0]["cred"]["cred_def_id"].as_str().unwrap());
+            assert_eq!("Alex", creds[0]["cred"]["values"]["name"]["raw"].as_str().unwrap());
+            assert_eq!("Alexander", creds[1]["cred"]["values"]["name"]["raw"].as_str().unwrap());
+            assert!(creds[0]["cred_revoc_id"].is_null());
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn issuer_create_credentials_batch_works_for_metadata() {
+            let (_, credential_offer, credential_req, _) = anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let connection_id = format!("connection_{}", anoncreds::generate_nonce().unwrap());
+
+            let cred_requests_json = json!([
+                {
+                    "cred_offer": serde_json::from_str::<serde_json::Value>(credential_offer).unwrap(),
+                    "cred_req": serde_json::from_str::<serde_json::Value>(credential_req).unwrap(),
+                    "cred_values": serde_json::from_str::<serde_json::Value>(&anoncreds::gvt_credential_values_json()).unwrap(),
+                    "metadata": {"connection_id": connection_id},
+                },
+                {
+                    "cred_offer": serde_json::from_str::<serde_json::Value>(credential_offer).unwrap(),
+                    "cred_req": serde_json::from_str::<serde_json::Value>(credential_req).unwrap(),
+                    "cred_values": serde_json::from_str::<serde_json::Value>(&anoncreds::gvt2_credential_values_json()).unwrap(),
+                }
+            ]).to_string();
+
+            let (creds_json, _, _) = anoncreds::issuer_create_credentials_batch(wallet_handle, &cred_requests_json, None).unwrap();
+
+            let creds: Vec<serde_json::Value> = serde_json::from_str(&creds_json).unwrap();
+            assert_eq!(2, creds.len());
+            assert_ne!(creds[0]["issued_cred_id"], creds[1]["issued_cred_id"]);
+
+            let issued_credentials = anoncreds::issuer_get_issued_credentials(wallet_handle, &json!({"connection_id": connection_id}).to_string());
+            assert_eq!(1, issued_credentials.len());
+            assert_eq!(creds[0]["issued_cred_id"], issued_credentials[0]["referent"]);
+
+            let issued_cred_id = creds[1]["issued_cred_id"].as_str().unwrap();
+            anoncreds::issuer_set_issued_credential_metadata(wallet_handle, issued_cred_id, &json!({"connection_id": connection_id}).to_string()).unwrap();
+
+            let issued_credentials = anoncreds::issuer_get_issued_credentials(wallet_handle, &json!({"connection_id": connection_id}).to_string());
+            assert_eq!(2, issued_credentials.len());
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+    }
+
+    mod issuer_issued_credentials_search {
+        use super::*;
+
+        #[test]
+        fn issuer_search_issued_credentials_works() {
+            let (_, credential_offer, _, _) = anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let query_json = json!({"cred_def_id": anoncreds::issuer_1_gvt_cred_def_id()}).to_string();
+            let issued_credentials = anoncreds::issuer_get_issued_credentials(wallet_handle, &query_json);
+            assert!(!issued_credentials.is_empty());
+
+            let credential_offer: serde_json::Value = serde_json::from_str(credential_offer).unwrap();
+            assert!(issued_credentials.iter().any(|issued_credential| issued_credential["cred_offer_nonce"] == credential_offer["nonce"]));
+
+            for issued_credential in issued_credentials {
+                assert_eq!(anoncreds::issuer_1_gvt_cred_def_id(), issued_credential["cred_def_id"].as_str().unwrap());
+                assert_eq!(anoncreds::gvt_schema_id(), issued_credential["schema_id"].as_str().unwrap());
+                assert!(issued_credential["cred_rev_id"].is_null());
+                assert!(!issued_credential["revoked"].as_bool().unwrap());
+            }
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn issuer_search_issued_credentials_works_for_empty_result() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let query_json = json!({"cred_def_id": "unknown_cred_def_id"}).to_string();
+            let (search_handle, total_count) = anoncreds::issuer_search_issued_credentials(wallet_handle, &query_json).unwrap();
+            assert_eq!(0, total_count);
+
+            let issued_credentials_json = anoncreds::issuer_fetch_issued_credentials(search_handle, 10).unwrap();
+            assert_eq!("[]", issued_credentials_json);
+
+            anoncreds::issuer_close_issued_credentials_search(search_handle).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn issuer_set_issued_credential_metadata_works() {
+            let (_, _, credential_req, _) = anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let credential_offer = anoncreds::issuer_create_credential_offer(wallet_handle, &anoncreds::issuer_1_gvt_cred_def_id()).unwrap();
+            let nonce = serde_json::from_str::<serde_json::Value>(&credential_offer).unwrap()["nonce"].as_str().unwrap().to_string();
+
+            anoncreds::issuer_create_credential(wallet_handle,
+                                                &credential_offer,
+                                                credential_req,
+                                                &anoncreds::gvt_credential_values_json(),
+                                                None,
+                                                None).unwrap();
+
+            let issued_credentials = anoncreds::issuer_get_issued_credentials(wallet_handle, &json!({"cred_offer_nonce": nonce}).to_string());
+            assert_eq!(1, issued_credentials.len());
+            let issued_cred_id = issued_credentials[0]["referent"].as_str().unwrap();
+
+            let connection_id = format!("connection_{}", nonce);
+            anoncreds::issuer_set_issued_credential_metadata(wallet_handle, issued_cred_id, &json!({"connection_id": connection_id}).to_string()).unwrap();
+
+            let issued_credentials = anoncreds::issuer_get_issued_credentials(wallet_handle, &json!({"connection_id": connection_id}).to_string());
+            assert_eq!(1, issued_credentials.len());
+            assert_eq!(issued_cred_id, issued_credentials[0]["referent"].as_str().unwrap());
+            assert_eq!(connection_id, issued_credentials[0]["metadata"]["connection_id"].as_str().unwrap());
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+    }
+
     mod prover_store_credential {
         use super::*;
 
@@ -3344,6 +3497,130 @@ mod high_cases {
         assert!(!valid);
     }
 
+    mod verifier_verify_proof_with_report {
+        use super::*;
+
+        fn _failed_checks(result: &serde_json::Value) -> Vec<String> {
+            result["errors"].as_array().unwrap()
+                .iter()
+                .map(|failure| failure["check"].as_str().unwrap().to_string())
+                .collect()
+        }
+
+        #[test]
+        fn verifier_verify_proof_with_report_works_for_correct_proof() {
+            let report_json = anoncreds::verifier_verify_proof_with_report(&anoncreds::proof_request_attr(),
+                                                                           &anoncreds::proof_json(),
+                                                                           &anoncreds::schemas_for_proof(),
+                                                                           &anoncreds::cred_defs_for_proof(),
+                                                                           "{}",
+                                                                           "{}").unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report_json).unwrap();
+
+            assert_eq!(true, report["valid"]);
+            assert_eq!(true, report["requested_attributes"]["attr1_referent"]["valid"]);
+            assert!(_failed_checks(&report["requested_attributes"]["attr1_referent"]).is_empty());
+            assert!(_failed_checks(&report).is_empty());
+        }
+
+        #[test]
+        fn verifier_verify_proof_with_report_works_for_missing_referent() {
+            let other_proof_req_json = json!({
+               "nonce":"123432421212",
+               "name":"proof_req_1",
+               "version":"0.1",
+               "requested_attributes": {
+                   "attr2_referent": { "name":"name" }
+               },
+               "requested_predicates": {},
+            }).to_string();
+
+            let report_json = anoncreds::verifier_verify_proof_with_report(&other_proof_req_json,
+                                                                           &anoncreds::proof_json(),
+                                                                           &anoncreds::schemas_for_proof(),
+                                                                           &anoncreds::cred_defs_for_proof(),
+                                                                           "{}",
+                                                                           "{}").unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report_json).unwrap();
+
+            assert_eq!(false, report["valid"]);
+            assert_eq!(false, report["requested_attributes"]["attr2_referent"]["valid"]);
+            assert_eq!(vec!["missing_referent"], _failed_checks(&report["requested_attributes"]["attr2_referent"]));
+            assert_eq!(vec!["unexpected_referent"], _failed_checks(&report));
+        }
+
+        #[test]
+        fn verifier_verify_proof_with_report_works_for_wrong_encoded() {
+            let proof_json = anoncreds::proof_json().replace(r#"encoded":"1139481716457488690172217916278103335"#, r#"encoded":"1111111111111111111111111111111111111"#);
+
+            let report_json = anoncreds::verifier_verify_proof_with_report(&anoncreds::proof_request_attr(),
+                                                                           &proof_json,
+                                                                           &anoncreds::schemas_for_proof(),
+                                                                           &anoncreds::cred_defs_for_proof(),
+                                                                           "{}",
+                                                                           "{}").unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report_json).unwrap();
+
+            assert_eq!(false, report["valid"]);
+            assert_eq!(vec!["encoded_value"], _failed_checks(&report["requested_attributes"]["attr1_referent"]));
+        }
+
+        #[test]
+        fn verifier_verify_proof_with_report_works_for_unsatisfied_restrictions() {
+            let proof_req = json!({
+                   "nonce":"123432421212",
+                   "name":"proof_req_1",
+                   "version":"0.1",
+                   "requested_attributes": {
+                       "attr1_referent": {
+                           "name":"name",
+                           "restrictions": { "issuer_did": { "$in": ["NO DID"] } }
+                       }
+                   },
+                   "requested_predicates": {},
+                }).to_string();
+
+            let report_json = anoncreds::verifier_verify_proof_with_report(&proof_req,
+                                                                           &anoncreds::proof_json(),
+                                                                           &anoncreds::schemas_for_proof(),
+                                                                           &anoncreds::cred_defs_for_proof(),
+                                                                           "{}",
+                                                                           "{}").unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report_json).unwrap();
+
+            assert_eq!(false, report["valid"]);
+            assert_eq!(vec!["restrictions"], _failed_checks(&report["requested_attributes"]["attr1_referent"]));
+            assert!(_failed_checks(&report).is_empty());
+        }
+
+        #[test]
+        fn verifier_verify_proof_with_report_works_for_missing_timestamp() {
+            let proof_req = json!({
+                   "nonce":"123432421212",
+                   "name":"proof_req_1",
+                   "version":"0.1",
+                   "requested_attributes": {
+                       "attr1_referent": {
+                           "name":"name",
+                           "non_revoked": { "from": 0, "to": 100 }
+                       }
+                   },
+                   "requested_predicates": {},
+                }).to_string();
+
+            let report_json = anoncreds::verifier_verify_proof_with_report(&proof_req,
+                                                                           &anoncreds::proof_json(),
+                                                                           &anoncreds::schemas_for_proof(),
+                                                                           &anoncreds::cred_defs_for_proof(),
+                                                                           "{}",
+                                                                           "{}").unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report_json).unwrap();
+
+            assert_eq!(false, report["valid"]);
+            assert_eq!(vec!["non_revocation_timestamp"], _failed_checks(&report["requested_attributes"]["attr1_referent"]));
+        }
+    }
+
     mod verifier_verify_proof_with_proof_req_restrictions {
         use super::*;
 
@@ -4237,6 +4514,314 @@ mod medium_cases {
         }
     }
 
+    mod issuer_create_credentials_batch {
+        use super::*;
+
+        fn _cred_request(cred_offer: &str, cred_req: &str, cred_values: &str) -> serde_json::Value {
+            json!({
+                "cred_offer": serde_json::from_str::<serde_json::Value>(cred_offer).unwrap(),
+                "cred_req": serde_json::from_str::<serde_json::Value>(cred_req).unwrap(),
+                "cred_values": serde_json::from_str::<serde_json::Value>(cred_values).unwrap(),
+            })
+        }
+
+        #[test]
+        fn issuer_create_credentials_batch_works_for_empty_list() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let res = anoncreds::issuer_create_credentials_batch(wallet_handle, "[]", None);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn issuer_create_credentials_batch_works_for_different_cred_defs() {
+            let (_, credential_offer, credential_req, _) = anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let other_credential_offer = anoncreds::issuer_create_credential_offer(wallet_handle, &anoncreds::issuer_1_xyz_cred_def_id()).unwrap();
+
+            let cred_requests_json = json!([
+                _cred_request(credential_offer, credential_req, &anoncreds::gvt_credential_values_json()),
+                _cred_request(&other_credential_offer, credential_req, &anoncreds::xyz_credential_values_json()),
+            ]).to_string();
+
+            let res = anoncreds::issuer_create_credentials_batch(wallet_handle, &cred_requests_json, None);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn issuer_create_credentials_batch_works_for_reserved_metadata_name() {
+            let (_, credential_offer, credential_req, _) = anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let mut cred_request = _cred_request(credential_offer, credential_req, &anoncreds::gvt_credential_values_json());
+            cred_request["metadata"] = json!({"revoked": "true"});
+
+            let cred_requests_json = json!([cred_request]).to_string();
+
+            let res = anoncreds::issuer_create_credentials_batch(wallet_handle, &cred_requests_json, None);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn issuer_create_credentials_batch_works_for_credential_does_not_correspond_to_credential_values() {
+            let (_, credential_offer, credential_req, _) = anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let cred_requests_json = json!([
+                _cred_request(credential_offer, credential_req, &anoncreds::gvt_credential_values_json()),
+                _cred_request(credential_offer, credential_req, &anoncreds::xyz_credential_values_json()),
+            ]).to_string();
+
+            let res = anoncreds::issuer_create_credentials_batch(wallet_handle, &cred_requests_json, None);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn issuer_create_credentials_batch_works_for_invalid_wallet_handle() {
+            let (_, credential_offer, credential_req, _) = anoncreds::init_common_wallet();
+
+            let cred_requests_json = json!([
+                _cred_request(credential_offer, credential_req, &anoncreds::gvt_credential_values_json()),
+            ]).to_string();
+
+            let res = anoncreds::issuer_create_credentials_batch(INVALID_WALLET_HANDLE, &cred_requests_json, None);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+    }
+
+    mod issuer_create_credential_with_rollover {
+        use super::*;
+
+        #[test]
+        fn issuer_create_credential_with_rollover_works_for_invalid_rev_reg_id() {
+            let (_, credential_offer, credential_req, _) = anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let res = anoncreds::issuer_create_credential_with_rollover(wallet_handle,
+                                                                        &credential_offer,
+                                                                        &credential_req,
+                                                                        &anoncreds::gvt_credential_values_json(),
+                                                                        "invalid_rev_reg_id",
+                                                                        None);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn issuer_create_credential_with_rollover_works_for_unknown_registry() {
+            let (_, credential_offer, credential_req, _) = anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let res = anoncreds::issuer_create_credential_with_rollover(wallet_handle,
+                                                                        &credential_offer,
+                                                                        &credential_req,
+                                                                        &anoncreds::gvt_credential_values_json(),
+                                                                        &anoncreds::gvt_rev_reg_id(),
+                                                                        None);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+    }
+
+    mod issuer_get_next_revoc_reg {
+        use super::*;
+
+        #[test]
+        fn issuer_get_next_revoc_reg_works_for_unknown_registry() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let res = anoncreds::issuer_get_next_revoc_reg(wallet_handle, &anoncreds::gvt_rev_reg_id());
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn issuer_get_next_revoc_reg_works_for_invalid_rev_reg_id() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let res = anoncreds::issuer_get_next_revoc_reg(wallet_handle, "invalid_rev_reg_id");
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn issuer_get_next_revoc_reg_works_for_invalid_wallet_handle() {
+            anoncreds::init_common_wallet();
+
+            let res = anoncreds::issuer_get_next_revoc_reg(INVALID_WALLET_HANDLE, &anoncreds::gvt_rev_reg_id());
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+    }
+
+    #[cfg(feature = "revocation_tests")]
+    mod issuer_revoke_credentials {
+        use super::*;
+
+        // Registry with ISSUANCE_BY_DEFAULT, so all 5 credentials are issued and not revoked
+        fn _setup() -> (Setup, String, i32) {
+            let setup = Setup::wallet();
+
+            let (_, _, _, _, rev_reg_id, _, _, blob_storage_reader_handle) =
+                anoncreds::multi_steps_issuer_revocation_preparation(setup.wallet_handle,
+                                                                     ISSUER_DID,
+                                                                     GVT_SCHEMA_NAME,
+                                                                     GVT_SCHEMA_ATTRIBUTES,
+                                                                     r#"{"max_cred_num":5, "issuance_type":"ISSUANCE_BY_DEFAULT"}"#);
+
+            (setup, rev_reg_id, blob_storage_reader_handle)
+        }
+
+        #[test]
+        fn issuer_revoke_credentials_works_for_duplicated_id() {
+            let (setup, rev_reg_id, blob_storage_reader_handle) = _setup();
+
+            let res = anoncreds::issuer_revoke_credentials(setup.wallet_handle, blob_storage_reader_handle, &rev_reg_id, r#"["1", "1"]"#, None);
+            assert_code!(ErrorCode::AnoncredsInvalidUserRevocId, res);
+        }
+
+        #[test]
+        fn issuer_revoke_credentials_works_for_revoked_and_recovered_id() {
+            let (setup, rev_reg_id, blob_storage_reader_handle) = _setup();
+
+            anoncreds::issuer_revoke_credentials(setup.wallet_handle, blob_storage_reader_handle, &rev_reg_id, r#"["2"]"#, None).unwrap();
+
+            let res = anoncreds::issuer_revoke_credentials(setup.wallet_handle, blob_storage_reader_handle, &rev_reg_id, r#"["2"]"#, Some(r#"["2"]"#));
+            assert_code!(ErrorCode::AnoncredsInvalidUserRevocId, res);
+        }
+
+        #[test]
+        fn issuer_revoke_credentials_works_for_out_of_range_id() {
+            let (setup, rev_reg_id, blob_storage_reader_handle) = _setup();
+
+            let res = anoncreds::issuer_revoke_credentials(setup.wallet_handle, blob_storage_reader_handle, &rev_reg_id, r#"["6"]"#, None);
+            assert_code!(ErrorCode::AnoncredsInvalidUserRevocId, res);
+
+            let res = anoncreds::issuer_revoke_credentials(setup.wallet_handle, blob_storage_reader_handle, &rev_reg_id, r#"["0"]"#, None);
+            assert_code!(ErrorCode::AnoncredsInvalidUserRevocId, res);
+        }
+
+        #[test]
+        fn issuer_revoke_credentials_works_for_already_revoked_id() {
+            let (setup, rev_reg_id, blob_storage_reader_handle) = _setup();
+
+            anoncreds::issuer_revoke_credentials(setup.wallet_handle, blob_storage_reader_handle, &rev_reg_id, r#"["3"]"#, None).unwrap();
+
+            let res = anoncreds::issuer_revoke_credentials(setup.wallet_handle, blob_storage_reader_handle, &rev_reg_id, r#"["1", "3"]"#, None);
+            assert_code!(ErrorCode::AnoncredsInvalidUserRevocId, res);
+
+            // Failed request doesn't revoke other ids
+            anoncreds::issuer_revoke_credentials(setup.wallet_handle, blob_storage_reader_handle, &rev_reg_id, r#"["1"]"#, None).unwrap();
+        }
+
+        #[test]
+        fn issuer_revoke_credentials_works_for_empty_lists() {
+            let (setup, rev_reg_id, blob_storage_reader_handle) = _setup();
+
+            let res = anoncreds::issuer_revoke_credentials(setup.wallet_handle, blob_storage_reader_handle, &rev_reg_id, "[]", None);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            let res = anoncreds::issuer_revoke_credentials(setup.wallet_handle, blob_storage_reader_handle, &rev_reg_id, "[]", Some("[]"));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
+    mod issuer_issued_credentials_search {
+        use super::*;
+
+        #[test]
+        fn issuer_search_issued_credentials_works_for_invalid_wallet_handle() {
+            anoncreds::init_common_wallet();
+
+            let res = anoncreds::issuer_search_issued_credentials(INVALID_WALLET_HANDLE, "{}");
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+
+        #[test]
+        fn issuer_search_issued_credentials_works_for_invalid_query() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let res = anoncreds::issuer_search_issued_credentials(wallet_handle, r#"{"cred_def_id": {"$unknown": "value"}}"#);
+            assert_code!(ErrorCode::WalletQueryError, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn issuer_fetch_issued_credentials_works_for_closed_search() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let (search_handle, _) = anoncreds::issuer_search_issued_credentials(wallet_handle, "{}").unwrap();
+            anoncreds::issuer_close_issued_credentials_search(search_handle).unwrap();
+
+            let res = anoncreds::issuer_fetch_issued_credentials(search_handle, 1);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+
+            let res = anoncreds::issuer_close_issued_credentials_search(search_handle);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn issuer_set_issued_credential_metadata_works_for_unknown_record() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let res = anoncreds::issuer_set_issued_credential_metadata(wallet_handle, "unknown_issued_cred_id", r#"{"connection_id": "connection_1"}"#);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn issuer_set_issued_credential_metadata_works_for_reserved_name() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let query_json = json!({"cred_def_id": anoncreds::issuer_1_gvt_cred_def_id()}).to_string();
+            let issued_credentials = anoncreds::issuer_get_issued_credentials(wallet_handle, &query_json);
+            let issued_cred_id = issued_credentials[0]["referent"].as_str().unwrap();
+
+            let res = anoncreds::issuer_set_issued_credential_metadata(wallet_handle, issued_cred_id, r#"{"revoked": "true"}"#);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            let res = anoncreds::issuer_set_issued_credential_metadata(wallet_handle, issued_cred_id, r#"{"connection_id": 1}"#);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+    }
+
     mod prover_store_credential {
         use super::*;
 
@@ -4672,6 +5257,52 @@ mod medium_cases {
         }
     }
 
+    mod verifier_verify_proof_with_report {
+        use super::*;
+
+        #[test]
+        fn verifier_verify_proof_with_report_works_for_wrong_proof() {
+            let proof_json = anoncreds::proof_json().replace("1139481716457488690172217916278103335", "1111111111111111111111111111111111111");
+
+            let report_json = anoncreds::verifier_verify_proof_with_report(&anoncreds::proof_request_attr(),
+                                                                           &proof_json,
+                                                                           &anoncreds::schemas_for_proof(),
+                                                                           &anoncreds::cred_defs_for_proof(),
+                                                                           "{}",
+                                                                           "{}").unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report_json).unwrap();
+
+            assert_eq!(false, report["valid"]);
+            assert_eq!(true, report["requested_attributes"]["attr1_referent"]["valid"]);
+            assert_eq!("crypto_proof", report["errors"][0]["check"]);
+        }
+
+        #[test]
+        fn verifier_verify_proof_with_report_works_for_missing_schemas() {
+            let report_json = anoncreds::verifier_verify_proof_with_report(&anoncreds::proof_request_attr(),
+                                                                           &anoncreds::proof_json(),
+                                                                           "{}",
+                                                                           &anoncreds::cred_defs_for_proof(),
+                                                                           "{}",
+                                                                           "{}").unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report_json).unwrap();
+
+            assert_eq!(false, report["valid"]);
+            assert_eq!("ledger_data", report["errors"][0]["check"]);
+        }
+
+        #[test]
+        fn verifier_verify_proof_with_report_works_for_invalid_proof_json() {
+            let res = anoncreds::verifier_verify_proof_with_report(&anoncreds::proof_request_attr(),
+                                                                   r#"{"proof": {}}"#,
+                                                                   &anoncreds::schemas_for_proof(),
+                                                                   &anoncreds::cred_defs_for_proof(),
+                                                                   "{}",
+                                                                   "{}");
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
     mod verifier_verify_proof_with_proof_req_restrictions {
         use super::*;
 
@@ -28,6 +28,8 @@ mod demos {
     use crate::utils::domain::anoncreds::revocation_registry::RevocationRegistry;
     use crate::utils::domain::anoncreds::credential_offer::CredentialOffer;
 
+    use std::collections::{HashSet, HashMap};
+
     #[test]
     fn anoncreds_works_for_single_issuer_single_prover() {
         Setup::empty();
@@ -864,6 +866,580 @@ mod demos {
         wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
     }
 
+    #[test]
+    fn anoncreds_works_for_revocation_proof_for_credentials_batch() {
+        Setup::empty();
+
+        //1. Issuer creates wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_revocation_proof_for_credentials_batch").unwrap();
+
+        //2. Provers create wallets, get wallet handles
+        let (prover_1_wallet_handle, prover_1_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_revocation_proof_for_credentials_batch").unwrap();
+        let (prover_2_wallet_handle, prover_2_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_revocation_proof_for_credentials_batch").unwrap();
+
+        //3 Issuer creates Schema, Credential Definition and Revocation Registry
+        let (schema_id, schema_json,
+            cred_def_id, cred_def_json,
+            rev_reg_id, revoc_reg_def_json, _,
+            blob_storage_reader_handle) = anoncreds::multi_steps_issuer_revocation_preparation(issuer_wallet_handle,
+                                                                                               ISSUER_DID,
+                                                                                               GVT_SCHEMA_NAME,
+                                                                                               GVT_SCHEMA_ATTRIBUTES,
+                                                                                               r#"{"max_cred_num":5, "issuance_type":"ISSUANCE_ON_DEMAND"}"#);
+
+        //4. Provers create Master Secrets
+        let prover_1_master_secret = "prover1_master_secret";
+        anoncreds::prover_create_master_secret(prover_1_wallet_handle, prover_1_master_secret).unwrap();
+
+        let prover_2_master_secret = "prover2_master_secret";
+        anoncreds::prover_create_master_secret(prover_2_wallet_handle, prover_2_master_secret).unwrap();
+
+        //5. Issuer creates Credential Offers, Provers create Credential Requests
+        let cred_offer_1_json = anoncreds::issuer_create_credential_offer(issuer_wallet_handle, &cred_def_id).unwrap();
+        let (cred_req_1_json, cred_req_1_metadata_json) = anoncreds::prover_create_credential_req(prover_1_wallet_handle,
+                                                                                                  DID_MY1,
+                                                                                                  &cred_offer_1_json,
+                                                                                                  &cred_def_json,
+                                                                                                  prover_1_master_secret).unwrap();
+
+        let cred_offer_2_json = anoncreds::issuer_create_credential_offer(issuer_wallet_handle, &cred_def_id).unwrap();
+        let (cred_req_2_json, cred_req_2_metadata_json) = anoncreds::prover_create_credential_req(prover_2_wallet_handle,
+                                                                                                  DID_MY2,
+                                                                                                  &cred_offer_2_json,
+                                                                                                  &cred_def_json,
+                                                                                                  prover_2_master_secret).unwrap();
+
+        //6. Issuer issues both Credentials in one batch
+        let cred_requests_json = json!([
+            {
+                "cred_offer": serde_json::from_str::<serde_json::Value>(&cred_offer_1_json).unwrap(),
+                "cred_req": serde_json::from_str::<serde_json::Value>(&cred_req_1_json).unwrap(),
+                "cred_values": serde_json::from_str::<serde_json::Value>(&anoncreds::gvt_credential_values_json()).unwrap(),
+                "rev_reg_id": rev_reg_id,
+            },
+            {
+                "cred_offer": serde_json::from_str::<serde_json::Value>(&cred_offer_2_json).unwrap(),
+                "cred_req": serde_json::from_str::<serde_json::Value>(&cred_req_2_json).unwrap(),
+                "cred_values": serde_json::from_str::<serde_json::Value>(&anoncreds::gvt2_credential_values_json()).unwrap(),
+                "rev_reg_id": rev_reg_id,
+            }
+        ]).to_string();
+
+        let (creds_json, revoc_reg_deltas_json, _) = anoncreds::issuer_create_credentials_batch(issuer_wallet_handle,
+                                                                                               &cred_requests_json,
+                                                                                               Some(blob_storage_reader_handle)).unwrap();
+        let creds: Vec<serde_json::Value> = serde_json::from_str(&creds_json).unwrap();
+        assert_eq!("1", creds[0]["cred_revoc_id"].as_str().unwrap());
+        assert_eq!("2", creds[1]["cred_revoc_id"].as_str().unwrap());
+
+        let revoc_reg_deltas: serde_json::Value = serde_json::from_str(&revoc_reg_deltas_json.unwrap()).unwrap();
+        let revoc_reg_delta_json = revoc_reg_deltas[&rev_reg_id].to_string();
+
+        //7. Provers store Credentials
+        anoncreds::prover_store_credential(prover_1_wallet_handle,
+                                           CREDENTIAL1_ID,
+                                           &cred_req_1_metadata_json,
+                                           &creds[0]["cred"].to_string(),
+                                           &cred_def_json,
+                                           Some(&revoc_reg_def_json)).unwrap();
+
+        anoncreds::prover_store_credential(prover_2_wallet_handle,
+                                           CREDENTIAL2_ID,
+                                           &cred_req_2_metadata_json,
+                                           &creds[1]["cred"].to_string(),
+                                           &cred_def_json,
+                                           Some(&revoc_reg_def_json)).unwrap();
+
+        //8. Issuance of the next Credential continues after the batch
+        let cred_offer_3_json = anoncreds::issuer_create_credential_offer(issuer_wallet_handle, &cred_def_id).unwrap();
+        let (cred_req_3_json, _) = anoncreds::prover_create_credential_req(prover_1_wallet_handle,
+                                                                           DID_MY1,
+                                                                           &cred_offer_3_json,
+                                                                           &cred_def_json,
+                                                                           prover_1_master_secret).unwrap();
+        let (_, cred_rev_id, _) = anoncreds::issuer_create_credential(issuer_wallet_handle,
+                                                                      &cred_offer_3_json,
+                                                                      &cred_req_3_json,
+                                                                      &anoncreds::gvt3_credential_values_json(),
+                                                                      Some(&rev_reg_id),
+                                                                      Some(blob_storage_reader_handle)).unwrap();
+        assert_eq!("3", cred_rev_id.unwrap());
+
+        //9. Each Prover creates Proof and Verifier verifies it against the batch delta
+        let proof_request = json!({
+           "nonce":"123432421212",
+           "name":"proof_req_1",
+           "version":"0.1",
+           "requested_attributes": json!({
+               "attr1_referent": json!({
+                   "name":"name"
+               })
+           }),
+           "requested_predicates": json!({
+               "predicate1_referent": json!({ "name":"age", "p_type":">=", "p_value":18 })
+           }),
+           "non_revoked": json!({ "from":80, "to":100 })
+        }).to_string();
+
+        let schemas_json = json!({
+            schema_id.clone(): serde_json::from_str::<Schema>(&schema_json).unwrap()
+        }).to_string();
+
+        let credential_defs_json = json!({
+            cred_def_id.clone(): serde_json::from_str::<CredentialDefinition>(&cred_def_json).unwrap()
+        }).to_string();
+
+        let rev_reg_defs_json = json!({
+            rev_reg_id.clone(): serde_json::from_str::<RevocationRegistryDefinition>(&revoc_reg_def_json).unwrap()
+        }).to_string();
+
+        let timestamp = 100;
+
+        let rev_regs_json = json!({
+            rev_reg_id.clone(): json!({
+                timestamp.to_string(): serde_json::from_str::<RevocationRegistry>(&revoc_reg_delta_json).unwrap()
+            })
+        }).to_string();
+
+        for (prover_wallet_handle, prover_master_secret, cred_rev_id, expected_name) in vec![
+            (prover_1_wallet_handle, prover_1_master_secret, "1", "Alex"),
+            (prover_2_wallet_handle, prover_2_master_secret, "2", "Alexander")] {
+            let credentials_json = anoncreds::prover_get_credentials_for_proof_req(prover_wallet_handle, &proof_request).unwrap();
+            let credential = anoncreds::get_credential_for_attr_referent(&credentials_json, "attr1_referent");
+
+            let rev_state_json = anoncreds::create_revocation_state(blob_storage_reader_handle,
+                                                                    &revoc_reg_def_json,
+                                                                    &revoc_reg_delta_json,
+                                                                    timestamp,
+                                                                    cred_rev_id).unwrap();
+
+            let requested_credentials_json = json!({
+                 "self_attested_attributes": json!({}),
+                 "requested_attributes": json!({
+                    "attr1_referent": json!({ "cred_id": credential.referent, "timestamp": timestamp,  "revealed":true })
+                 }),
+                 "requested_predicates": json!({
+                    "predicate1_referent": json!({ "cred_id": credential.referent, "timestamp": timestamp })
+                 })
+            }).to_string();
+
+            let rev_states_json = json!({
+                rev_reg_id.clone(): json!({
+                    timestamp.to_string(): serde_json::from_str::<RevocationState>(&rev_state_json).unwrap()
+                })
+            }).to_string();
+
+            let proof_json = anoncreds::prover_create_proof(prover_wallet_handle,
+                                                            &proof_request,
+                                                            &requested_credentials_json,
+                                                            prover_master_secret,
+                                                            &schemas_json,
+                                                            &credential_defs_json,
+                                                            &rev_states_json).unwrap();
+            let proof: Proof = serde_json::from_str(&proof_json).unwrap();
+            assert_eq!(expected_name, proof.requested_proof.revealed_attrs.get("attr1_referent").unwrap().raw);
+
+            let valid = anoncreds::verifier_verify_proof(&proof_request,
+                                                         &proof_json,
+                                                         &schemas_json,
+                                                         &credential_defs_json,
+                                                         &rev_reg_defs_json,
+                                                         &rev_regs_json).unwrap();
+            assert!(valid);
+        }
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_1_wallet_handle, &prover_1_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_2_wallet_handle, &prover_2_wallet_config).unwrap();
+    }
+
+    #[cfg(feature = "revocation_tests")]
+    #[test]
+    fn anoncreds_works_for_credentials_batch_for_full_revocation_registry() {
+        Setup::empty();
+
+        //1. Issuer creates wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_credentials_batch_for_full_revocation_registry").unwrap();
+
+        //2. Prover creates wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_credentials_batch_for_full_revocation_registry").unwrap();
+
+        //3 Issuer creates Schema, Credential Definition and Revocation Registry
+        let (_, _,
+            cred_def_id, cred_def_json,
+            rev_reg_id, _, _,
+            blob_storage_reader_handle) = anoncreds::multi_steps_issuer_revocation_preparation(issuer_wallet_handle,
+                                                                                               ISSUER_DID,
+                                                                                               GVT_SCHEMA_NAME,
+                                                                                               GVT_SCHEMA_ATTRIBUTES,
+                                                                                               r#"{"max_cred_num":1, "issuance_type":"ISSUANCE_ON_DEMAND"}"#);
+
+        //4. Prover creates Master Secret and Credential Request
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        let cred_offer_json = anoncreds::issuer_create_credential_offer(issuer_wallet_handle, &cred_def_id).unwrap();
+        let (cred_req_json, _) = anoncreds::prover_create_credential_req(prover_wallet_handle,
+                                                                         DID_MY1,
+                                                                         &cred_offer_json,
+                                                                         &cred_def_json,
+                                                                         COMMON_MASTER_SECRET).unwrap();
+
+        //5. Batch exceeding the registry capacity fails
+        let cred_request = json!({
+            "cred_offer": serde_json::from_str::<serde_json::Value>(&cred_offer_json).unwrap(),
+            "cred_req": serde_json::from_str::<serde_json::Value>(&cred_req_json).unwrap(),
+            "cred_values": serde_json::from_str::<serde_json::Value>(&anoncreds::gvt_credential_values_json()).unwrap(),
+            "rev_reg_id": rev_reg_id,
+        });
+
+        let res = anoncreds::issuer_create_credentials_batch(issuer_wallet_handle,
+                                                             &json!([cred_request.clone(), cred_request]).to_string(),
+                                                             Some(blob_storage_reader_handle));
+        assert_code!(ErrorCode::AnoncredsRevocationRegistryFullError, res);
+
+        //6. Failed batch doesn't consume revocation ids
+        let (_, cred_rev_id, _) = anoncreds::issuer_create_credential(issuer_wallet_handle,
+                                                                      &cred_offer_json,
+                                                                      &cred_req_json,
+                                                                      &anoncreds::gvt_credential_values_json(),
+                                                                      Some(&rev_reg_id),
+                                                                      Some(blob_storage_reader_handle)).unwrap();
+        assert_eq!("1", cred_rev_id.unwrap());
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
+    #[cfg(feature = "revocation_tests")]
+    #[test]
+    fn anoncreds_works_for_revocation_registry_rollover() {
+        Setup::empty();
+
+        //1. Issuer creates wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_revocation_registry_rollover").unwrap();
+
+        //2. Prover creates wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_revocation_registry_rollover").unwrap();
+
+        //3. Issuer creates Schema and Credential Definition with Revocation Registry rollover
+        let (_, schema_json) = anoncreds::issuer_create_schema(ISSUER_DID,
+                                                               GVT_SCHEMA_NAME,
+                                                               SCHEMA_VERSION,
+                                                               GVT_SCHEMA_ATTRIBUTES).unwrap();
+
+        let (cred_def_id, cred_def_json) = anoncreds::issuer_create_credential_definition(issuer_wallet_handle,
+                                                                                          ISSUER_DID,
+                                                                                          &schema_json,
+                                                                                          TAG_1,
+                                                                                          None,
+                                                                                          Some(&anoncreds::rollover_cred_def_config())).unwrap();
+
+        //4. Issuer creates the first Revocation Registry
+        let tails_writer_config = anoncreds::tails_writer_config();
+        let tails_writer_handle = utils::blob_storage::open_writer("default", &tails_writer_config).unwrap();
+
+        let (rev_reg_id, _, _) = anoncreds::issuer_create_and_store_revoc_reg(issuer_wallet_handle,
+                                                                              ISSUER_DID,
+                                                                              None,
+                                                                              TAG_1,
+                                                                              &cred_def_id,
+                                                                              r#"{"max_cred_num":1, "issuance_type":"ISSUANCE_ON_DEMAND"}"#,
+                                                                              tails_writer_handle).unwrap();
+
+        let blob_storage_reader_handle = utils::blob_storage::open_reader(TYPE, &tails_writer_config).unwrap();
+
+        //5. Prover creates Master Secret and Credential Request
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        let cred_offer_json = anoncreds::issuer_create_credential_offer(issuer_wallet_handle, &cred_def_id).unwrap();
+        let (cred_req_json, cred_req_metadata_json) = anoncreds::prover_create_credential_req(prover_wallet_handle,
+                                                                                              DID_MY1,
+                                                                                              &cred_offer_json,
+                                                                                              &cred_def_json,
+                                                                                              COMMON_MASTER_SECRET).unwrap();
+
+        //6. The first Revocation Registry has no next one before issuance from it starts
+        let res = anoncreds::issuer_get_next_revoc_reg(issuer_wallet_handle, &rev_reg_id);
+        assert_code!(ErrorCode::WalletItemNotFound, res);
+
+        let cred_request = json!({
+            "cred_offer": serde_json::from_str::<serde_json::Value>(&cred_offer_json).unwrap(),
+            "cred_req": serde_json::from_str::<serde_json::Value>(&cred_req_json).unwrap(),
+            "cred_values": serde_json::from_str::<serde_json::Value>(&anoncreds::gvt_credential_values_json()).unwrap(),
+            "rev_reg_id": rev_reg_id,
+        });
+        let cred_requests_json = json!([cred_request]).to_string();
+
+        //7. Issuer fills the first Revocation Registry and gets the next one to publish
+        let (creds_json, _, new_revoc_regs_json) = anoncreds::issuer_create_credentials_batch(issuer_wallet_handle,
+                                                                                             &cred_requests_json,
+                                                                                             Some(blob_storage_reader_handle)).unwrap();
+        let creds: Vec<serde_json::Value> = serde_json::from_str(&creds_json).unwrap();
+        assert_eq!(rev_reg_id, creds[0]["cred"]["rev_reg_id"].as_str().unwrap());
+        assert_eq!("1", creds[0]["cred_revoc_id"].as_str().unwrap());
+
+        let new_revoc_regs: HashMap<String, serde_json::Value> = serde_json::from_str(&new_revoc_regs_json.unwrap()).unwrap();
+        assert_eq!(1, new_revoc_regs.len());
+
+        let (next_rev_reg_id, next_revoc_reg) = new_revoc_regs.into_iter().next().unwrap();
+        assert_ne!(rev_reg_id, next_rev_reg_id);
+        assert_eq!("TAG_2", next_revoc_reg["revoc_reg_def"]["tag"].as_str().unwrap());
+        assert_eq!(cred_def_id, next_revoc_reg["revoc_reg_def"]["credDefId"].as_str().unwrap());
+        assert_eq!(1, next_revoc_reg["revoc_reg_def"]["value"]["maxCredNum"].as_u64().unwrap());
+        assert!(next_revoc_reg["revoc_reg_entry"].is_object());
+
+        let stored_next_revoc_reg: serde_json::Value = serde_json::from_str(&anoncreds::issuer_get_next_revoc_reg(issuer_wallet_handle, &rev_reg_id).unwrap()).unwrap();
+        assert_eq!(next_rev_reg_id, stored_next_revoc_reg["rev_reg_id"].as_str().unwrap());
+        assert_eq!(next_revoc_reg["revoc_reg_def"], stored_next_revoc_reg["revoc_reg_def"]);
+        assert_eq!(next_revoc_reg["revoc_reg_entry"], stored_next_revoc_reg["revoc_reg_entry"]);
+
+        let next_revoc_reg_def_json = next_revoc_reg["revoc_reg_def"].to_string();
+
+        //8. Issuer issues the next Credential from the next Revocation Registry and gets its successor
+        let (creds_json, revoc_reg_deltas_json, new_revoc_regs_json) = anoncreds::issuer_create_credentials_batch(issuer_wallet_handle,
+                                                                                                                  &cred_requests_json,
+                                                                                                                  Some(blob_storage_reader_handle)).unwrap();
+        let creds: Vec<serde_json::Value> = serde_json::from_str(&creds_json).unwrap();
+        assert_eq!(next_rev_reg_id, creds[0]["cred"]["rev_reg_id"].as_str().unwrap());
+        assert_eq!("1", creds[0]["cred_revoc_id"].as_str().unwrap());
+
+        let revoc_reg_deltas: serde_json::Value = serde_json::from_str(&revoc_reg_deltas_json.unwrap()).unwrap();
+        assert!(revoc_reg_deltas[&next_rev_reg_id].is_object());
+
+        let new_revoc_regs: HashMap<String, serde_json::Value> = serde_json::from_str(&new_revoc_regs_json.unwrap()).unwrap();
+        assert_eq!(1, new_revoc_regs.len());
+        assert_eq!("TAG_3", new_revoc_regs.values().next().unwrap()["revoc_reg_def"]["tag"].as_str().unwrap());
+
+        //9. Prover stores Credential issued from the next Revocation Registry
+        anoncreds::prover_store_credential(prover_wallet_handle,
+                                           CREDENTIAL1_ID,
+                                           &cred_req_metadata_json,
+                                           &creds[0]["cred"].to_string(),
+                                           &cred_def_json,
+                                           Some(&next_revoc_reg_def_json)).unwrap();
+
+        //10. Issued Credential record refers to the next Revocation Registry
+        let issued_credentials = anoncreds::issuer_get_issued_credentials(issuer_wallet_handle,
+                                                                          &json!({"rev_reg_id": next_rev_reg_id}).to_string());
+        assert_eq!(1, issued_credentials.len());
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
+    #[cfg(feature = "revocation_tests")]
+    #[test]
+    fn anoncreds_works_for_single_credential_for_revocation_registry_rollover() {
+        Setup::empty();
+
+        //1. Issuer creates wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_single_credential_for_revocation_registry_rollover").unwrap();
+
+        //2. Prover creates wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_single_credential_for_revocation_registry_rollover").unwrap();
+
+        //3. Issuer creates Schema and Credential Definition with Revocation Registry rollover
+        let (_, schema_json) = anoncreds::issuer_create_schema(ISSUER_DID,
+                                                               GVT_SCHEMA_NAME,
+                                                               SCHEMA_VERSION,
+                                                               GVT_SCHEMA_ATTRIBUTES).unwrap();
+
+        let (cred_def_id, cred_def_json) = anoncreds::issuer_create_credential_definition(issuer_wallet_handle,
+                                                                                          ISSUER_DID,
+                                                                                          &schema_json,
+                                                                                          TAG_1,
+                                                                                          None,
+                                                                                          Some(&anoncreds::rollover_cred_def_config())).unwrap();
+
+        //4. Issuer creates the first Revocation Registry
+        let tails_writer_config = anoncreds::tails_writer_config();
+        let tails_writer_handle = utils::blob_storage::open_writer("default", &tails_writer_config).unwrap();
+
+        let (rev_reg_id, _, _) = anoncreds::issuer_create_and_store_revoc_reg(issuer_wallet_handle,
+                                                                              ISSUER_DID,
+                                                                              None,
+                                                                              TAG_1,
+                                                                              &cred_def_id,
+                                                                              r#"{"max_cred_num":1, "issuance_type":"ISSUANCE_ON_DEMAND"}"#,
+                                                                              tails_writer_handle).unwrap();
+
+        let blob_storage_reader_handle = utils::blob_storage::open_reader(TYPE, &tails_writer_config).unwrap();
+
+        //5. Prover creates Master Secret and Credential Request
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        let cred_offer_json = anoncreds::issuer_create_credential_offer(issuer_wallet_handle, &cred_def_id).unwrap();
+        let (cred_req_json, cred_req_metadata_json) = anoncreds::prover_create_credential_req(prover_wallet_handle,
+                                                                                              DID_MY1,
+                                                                                              &cred_offer_json,
+                                                                                              &cred_def_json,
+                                                                                              COMMON_MASTER_SECRET).unwrap();
+
+        //6. Issuer fills the first Revocation Registry, plain issuance does not create the next one
+        let (cred_json, cred_rev_id, _) = anoncreds::issuer_create_credential(issuer_wallet_handle,
+                                                                              &cred_offer_json,
+                                                                              &cred_req_json,
+                                                                              &anoncreds::gvt_credential_values_json(),
+                                                                              Some(&rev_reg_id),
+                                                                              Some(blob_storage_reader_handle)).unwrap();
+        let cred: serde_json::Value = serde_json::from_str(&cred_json).unwrap();
+        assert_eq!(rev_reg_id, cred["rev_reg_id"].as_str().unwrap());
+        assert_eq!("1", cred_rev_id.unwrap());
+
+        let res = anoncreds::issuer_get_next_revoc_reg(issuer_wallet_handle, &rev_reg_id);
+        assert_code!(ErrorCode::WalletItemNotFound, res);
+
+        //7. Plain issuance from the full Revocation Registry fails as the next one is not created yet
+        let res = anoncreds::issuer_create_credential(issuer_wallet_handle,
+                                                      &cred_offer_json,
+                                                      &cred_req_json,
+                                                      &anoncreds::gvt_credential_values_json(),
+                                                      Some(&rev_reg_id),
+                                                      Some(blob_storage_reader_handle));
+        assert_code!(ErrorCode::AnoncredsRevocationRegistryFullError, res);
+
+        //8. Issuer issues the next Credential with rollover and gets created Revocation Registries to publish
+        let (cred_json, cred_rev_id, revoc_reg_delta_json, new_revoc_regs_json) =
+            anoncreds::issuer_create_credential_with_rollover(issuer_wallet_handle,
+                                                              &cred_offer_json,
+                                                              &cred_req_json,
+                                                              &anoncreds::gvt_credential_values_json(),
+                                                              &rev_reg_id,
+                                                              Some(blob_storage_reader_handle)).unwrap();
+
+        let new_revoc_regs: HashMap<String, serde_json::Value> = serde_json::from_str(&new_revoc_regs_json.unwrap()).unwrap();
+        assert_eq!(2, new_revoc_regs.len());
+
+        let next_revoc_reg: serde_json::Value = serde_json::from_str(&anoncreds::issuer_get_next_revoc_reg(issuer_wallet_handle, &rev_reg_id).unwrap()).unwrap();
+        let next_rev_reg_id = next_revoc_reg["rev_reg_id"].as_str().unwrap().to_string();
+        assert_eq!("TAG_2", next_revoc_reg["revoc_reg_def"]["tag"].as_str().unwrap());
+        assert_eq!(next_revoc_reg["revoc_reg_def"], new_revoc_regs[&next_rev_reg_id]["revoc_reg_def"]);
+        assert_eq!(next_revoc_reg["revoc_reg_entry"], new_revoc_regs[&next_rev_reg_id]["revoc_reg_entry"]);
+
+        let cred: serde_json::Value = serde_json::from_str(&cred_json).unwrap();
+        assert_eq!(next_rev_reg_id, cred["rev_reg_id"].as_str().unwrap());
+        assert_eq!("1", cred_rev_id.unwrap());
+
+        assert_eq!(vec![1].into_iter().collect::<HashSet<u32>>(), anoncreds::get_revoc_reg_delta_indices(&revoc_reg_delta_json.unwrap(), "issued"));
+
+        //9. The next Revocation Registry got its own successor which is returned too
+        let next_next_revoc_reg: serde_json::Value = serde_json::from_str(&anoncreds::issuer_get_next_revoc_reg(issuer_wallet_handle, &next_rev_reg_id).unwrap()).unwrap();
+        let next_next_rev_reg_id = next_next_revoc_reg["rev_reg_id"].as_str().unwrap().to_string();
+        assert_eq!("TAG_3", next_next_revoc_reg["revoc_reg_def"]["tag"].as_str().unwrap());
+        assert_eq!(next_next_revoc_reg["revoc_reg_def"], new_revoc_regs[&next_next_rev_reg_id]["revoc_reg_def"]);
+
+        //10. Plain issuance follows the already created Revocation Registries across the full ones
+        let (next_cred_json, _, _) = anoncreds::issuer_create_credential(issuer_wallet_handle,
+                                                                         &cred_offer_json,
+                                                                         &cred_req_json,
+                                                                         &anoncreds::gvt_credential_values_json(),
+                                                                         Some(&rev_reg_id),
+                                                                         Some(blob_storage_reader_handle)).unwrap();
+        let next_cred: serde_json::Value = serde_json::from_str(&next_cred_json).unwrap();
+        assert_eq!(next_next_rev_reg_id, next_cred["rev_reg_id"].as_str().unwrap());
+
+        //11. Prover stores Credential issued from the next Revocation Registry
+        anoncreds::prover_store_credential(prover_wallet_handle,
+                                           CREDENTIAL1_ID,
+                                           &cred_req_metadata_json,
+                                           &cred_json,
+                                           &cred_def_json,
+                                           Some(&next_revoc_reg["revoc_reg_def"].to_string())).unwrap();
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
+    #[cfg(feature = "revocation_tests")]
+    #[test]
+    fn anoncreds_works_for_credentials_batch_for_revocation_registry_rollover() {
+        Setup::empty();
+
+        //1. Issuer creates wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_credentials_batch_for_revocation_registry_rollover").unwrap();
+
+        //2. Prover creates wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_credentials_batch_for_revocation_registry_rollover").unwrap();
+
+        //3. Issuer creates Schema and Credential Definition with Revocation Registry rollover
+        let (_, schema_json) = anoncreds::issuer_create_schema(ISSUER_DID,
+                                                               GVT_SCHEMA_NAME,
+                                                               SCHEMA_VERSION,
+                                                               GVT_SCHEMA_ATTRIBUTES).unwrap();
+
+        let (cred_def_id, cred_def_json) = anoncreds::issuer_create_credential_definition(issuer_wallet_handle,
+                                                                                          ISSUER_DID,
+                                                                                          &schema_json,
+                                                                                          TAG_1,
+                                                                                          None,
+                                                                                          Some(&anoncreds::rollover_cred_def_config())).unwrap();
+
+        //4. Issuer creates the first Revocation Registry
+        let tails_writer_config = anoncreds::tails_writer_config();
+        let tails_writer_handle = utils::blob_storage::open_writer("default", &tails_writer_config).unwrap();
+
+        let (rev_reg_id, _, _) = anoncreds::issuer_create_and_store_revoc_reg(issuer_wallet_handle,
+                                                                              ISSUER_DID,
+                                                                              None,
+                                                                              TAG_1,
+                                                                              &cred_def_id,
+                                                                              r#"{"max_cred_num":1, "issuance_type":"ISSUANCE_ON_DEMAND"}"#,
+                                                                              tails_writer_handle).unwrap();
+
+        let blob_storage_reader_handle = utils::blob_storage::open_reader(TYPE, &tails_writer_config).unwrap();
+
+        //5. Prover creates Master Secret and Credential Request
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        let cred_offer_json = anoncreds::issuer_create_credential_offer(issuer_wallet_handle, &cred_def_id).unwrap();
+        let (cred_req_json, _) = anoncreds::prover_create_credential_req(prover_wallet_handle,
+                                                                         DID_MY1,
+                                                                         &cred_offer_json,
+                                                                         &cred_def_json,
+                                                                         COMMON_MASTER_SECRET).unwrap();
+
+        //6. Batch exceeding several Revocation Registries capacity continues in the pre-created ones
+        let cred_request = json!({
+            "cred_offer": serde_json::from_str::<serde_json::Value>(&cred_offer_json).unwrap(),
+            "cred_req": serde_json::from_str::<serde_json::Value>(&cred_req_json).unwrap(),
+            "cred_values": serde_json::from_str::<serde_json::Value>(&anoncreds::gvt_credential_values_json()).unwrap(),
+            "rev_reg_id": rev_reg_id,
+        });
+
+        let (creds_json, revoc_reg_deltas_json, new_revoc_regs_json) =
+            anoncreds::issuer_create_credentials_batch(issuer_wallet_handle,
+                                                       &json!([cred_request.clone(), cred_request.clone(), cred_request]).to_string(),
+                                                       Some(blob_storage_reader_handle)).unwrap();
+
+        let new_revoc_regs: HashMap<String, serde_json::Value> = serde_json::from_str(&new_revoc_regs_json.unwrap()).unwrap();
+        assert_eq!(3, new_revoc_regs.len());
+
+        let rev_reg_id_by_tag = |tag: &str| -> String {
+            new_revoc_regs.iter()
+                .find(|(_, revoc_reg)| revoc_reg["revoc_reg_def"]["tag"].as_str().unwrap() == tag)
+                .map(|(id, _)| id.clone())
+                .unwrap()
+        };
+        let rev_reg_id_2 = rev_reg_id_by_tag("TAG_2");
+        let rev_reg_id_3 = rev_reg_id_by_tag("TAG_3");
+        let rev_reg_id_4 = rev_reg_id_by_tag("TAG_4");
+
+        let creds: Vec<serde_json::Value> = serde_json::from_str(&creds_json).unwrap();
+        assert_eq!(rev_reg_id, creds[0]["cred"]["rev_reg_id"].as_str().unwrap());
+        assert_eq!(rev_reg_id_2, creds[1]["cred"]["rev_reg_id"].as_str().unwrap());
+        assert_eq!(rev_reg_id_3, creds[2]["cred"]["rev_reg_id"].as_str().unwrap());
+        assert!(creds.iter().all(|cred| cred["cred_revoc_id"].as_str().unwrap() == "1"));
+
+        let revoc_reg_deltas: serde_json::Value = serde_json::from_str(&revoc_reg_deltas_json.unwrap()).unwrap();
+        assert!(revoc_reg_deltas[&rev_reg_id].is_object());
+        assert!(revoc_reg_deltas[&rev_reg_id_2].is_object());
+        assert!(revoc_reg_deltas[&rev_reg_id_3].is_object());
+        assert!(revoc_reg_deltas[&rev_reg_id_4].is_null());
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
     #[cfg(feature = "revocation_tests")]
     #[test]
     fn anoncreds_works_for_revocation_proof_issuance_by_default() {
@@ -2085,6 +2661,394 @@ mod demos {
         wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
     }
 
+    #[cfg(feature = "revocation_tests")]
+    #[test]
+    fn anoncreds_works_for_issuance_on_demand_revocation_strategy_revoke_credentials() {
+        Setup::empty();
+
+        //1. Issuer creates wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_on_demand_revocation_strategy_revoke_credentials").unwrap();
+
+        //2. Prover creates wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_on_demand_revocation_strategy_revoke_credentials").unwrap();
+
+        //3 Issuer creates Schema, Credential Definition and Revocation Registry
+        let (schema_id, schema_json,
+            cred_def_id, cred_def_json,
+            rev_reg_id, revoc_reg_def_json, _,
+            blob_storage_reader_handle) = anoncreds::multi_steps_issuer_revocation_preparation(issuer_wallet_handle,
+                                                                                               ISSUER_DID,
+                                                                                               GVT_SCHEMA_NAME,
+                                                                                               GVT_SCHEMA_ATTRIBUTES,
+                                                                                               r#"{"max_cred_num":5, "issuance_type":"ISSUANCE_ON_DEMAND"}"#);
+
+        //4. Issuance of two Credentials for Prover
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        let (cred_rev_id_1, revoc_reg_delta_1_json) = anoncreds::multi_steps_create_revocation_credential(
+            COMMON_MASTER_SECRET,
+            prover_wallet_handle,
+            issuer_wallet_handle,
+            CREDENTIAL1_ID,
+            &anoncreds::gvt_credential_values_json(),
+            &cred_def_id,
+            &cred_def_json,
+            &rev_reg_id,
+            &revoc_reg_def_json,
+            blob_storage_reader_handle,
+        );
+
+        let (cred_rev_id_2, revoc_reg_delta_2_json) = anoncreds::multi_steps_create_revocation_credential(
+            COMMON_MASTER_SECRET,
+            prover_wallet_handle,
+            issuer_wallet_handle,
+            CREDENTIAL2_ID,
+            &anoncreds::gvt2_credential_values_json(),
+            &cred_def_id,
+            &cred_def_json,
+            &rev_reg_id,
+            &revoc_reg_def_json,
+            blob_storage_reader_handle,
+        );
+
+        let revoc_reg_delta_json = anoncreds::issuer_merge_revocation_registry_deltas(&revoc_reg_delta_1_json.unwrap(),
+                                                                                      &revoc_reg_delta_2_json.unwrap()).unwrap();
+
+        //5. Prover creates RevocationState and Proof for the first Credential
+        let proof_request = json!({
+           "nonce":"123432421212",
+           "name":"proof_req_1",
+           "version":"0.1",
+           "requested_attributes": json!({
+               "attr1_referent": json!({
+                   "name":"name"
+               })
+           }),
+           "requested_predicates": json!({}),
+           "non_revoked": json!({ "from":80, "to":100 })
+        }).to_string();
+
+        let timestamp = 100;
+        let rev_state_json = anoncreds::create_revocation_state(blob_storage_reader_handle,
+                                                                &revoc_reg_def_json,
+                                                                &revoc_reg_delta_json,
+                                                                timestamp,
+                                                                &cred_rev_id_1).unwrap();
+
+        let requested_credentials_json = json!({
+             "self_attested_attributes": json!({}),
+             "requested_attributes": json!({
+                "attr1_referent": json!({ "cred_id": CREDENTIAL1_ID, "timestamp":timestamp, "revealed":true })
+             }),
+             "requested_predicates": json!({})
+        }).to_string();
+
+        let schemas_json = json!({
+            schema_id: serde_json::from_str::<Schema>(&schema_json).unwrap()
+        }).to_string();
+
+        let credential_defs_json = json!({
+            cred_def_id: serde_json::from_str::<CredentialDefinition>(&cred_def_json).unwrap()
+        }).to_string();
+
+        let rev_states_json = json!({
+            rev_reg_id.clone(): json!({
+                timestamp.to_string(): serde_json::from_str::<RevocationState>(&rev_state_json).unwrap()
+            })
+        }).to_string();
+
+        let proof_json = anoncreds::prover_create_proof(prover_wallet_handle,
+                                                        &proof_request,
+                                                        &requested_credentials_json,
+                                                        COMMON_MASTER_SECRET,
+                                                        &schemas_json,
+                                                        &credential_defs_json,
+                                                        &rev_states_json).unwrap();
+
+        //6. Verifier verifies proof before it will be revoked
+        let rev_reg_defs_json = json!({
+            rev_reg_id.clone(): serde_json::from_str::<RevocationRegistryDefinition>(&revoc_reg_def_json).unwrap()
+        }).to_string();
+
+        let rev_regs_json = json!({
+            rev_reg_id.clone(): json!({
+                timestamp.to_string(): serde_json::from_str::<RevocationRegistry>(&revoc_reg_delta_json).unwrap()
+            })
+        }).to_string();
+
+        let valid = anoncreds::verifier_verify_proof(&proof_request,
+                                                     &proof_json,
+                                                     &schemas_json,
+                                                     &credential_defs_json,
+                                                     &rev_reg_defs_json,
+                                                     &rev_regs_json).unwrap();
+        assert!(valid);
+
+        //7. Issuer revokes both Credentials with a single delta
+        let revoked_cred_revoc_ids_json = json!([cred_rev_id_1, cred_rev_id_2]).to_string();
+        let revoc_reg_delta_json = anoncreds::issuer_revoke_credentials(issuer_wallet_handle,
+                                                                        blob_storage_reader_handle,
+                                                                        &rev_reg_id,
+                                                                        &revoked_cred_revoc_ids_json,
+                                                                        None).unwrap();
+
+        assert_eq!(vec![1, 2].into_iter().collect::<HashSet<u32>>(), anoncreds::get_revoc_reg_delta_indices(&revoc_reg_delta_json, "revoked"));
+
+        //8. Verifier verifies proof after that was revoked
+        let rev_regs_json = json!({
+            rev_reg_id.clone(): json!({
+                timestamp.to_string(): serde_json::from_str::<RevocationRegistry>(&revoc_reg_delta_json).unwrap()
+            })
+        }).to_string();
+
+        let valid = anoncreds::verifier_verify_proof(&proof_request,
+                                                     &proof_json,
+                                                     &schemas_json,
+                                                     &credential_defs_json,
+                                                     &rev_reg_defs_json,
+                                                     &rev_regs_json).unwrap();
+        assert!(!valid);
+
+        //9. Issuer recovers the first Credential
+        let recovered_cred_revoc_ids_json = json!([cred_rev_id_1]).to_string();
+        let revoc_reg_delta_json = anoncreds::issuer_revoke_credentials(issuer_wallet_handle,
+                                                                        blob_storage_reader_handle,
+                                                                        &rev_reg_id,
+                                                                        "[]",
+                                                                        Some(&recovered_cred_revoc_ids_json)).unwrap();
+
+        assert_eq!(vec![1].into_iter().collect::<HashSet<u32>>(), anoncreds::get_revoc_reg_delta_indices(&revoc_reg_delta_json, "issued"));
+
+        //10. Revocation of already revoked Credential fails and doesn't change the registry
+        let res = anoncreds::issuer_revoke_credentials(issuer_wallet_handle,
+                                                       blob_storage_reader_handle,
+                                                       &rev_reg_id,
+                                                       &revoked_cred_revoc_ids_json,
+                                                       None);
+        assert_code!(ErrorCode::AnoncredsInvalidUserRevocId, res);
+
+        anoncreds::issuer_revoke_credentials(issuer_wallet_handle,
+                                             blob_storage_reader_handle,
+                                             &rev_reg_id,
+                                             &recovered_cred_revoc_ids_json,
+                                             None).unwrap();
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
+    #[cfg(feature = "revocation_tests")]
+    #[test]
+    fn anoncreds_works_for_issuance_by_default_revocation_strategy_revoke_credentials() {
+        Setup::empty();
+
+        //1. Issuer creates wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_by_default_revocation_strategy_revoke_credentials").unwrap();
+
+        //2 Issuer creates Schema, Credential Definition and Revocation Registry
+        let (_, _,
+            _, _,
+            rev_reg_id, _, _,
+            blob_storage_reader_handle) = anoncreds::multi_steps_issuer_revocation_preparation(issuer_wallet_handle,
+                                                                                               ISSUER_DID,
+                                                                                               GVT_SCHEMA_NAME,
+                                                                                               GVT_SCHEMA_ATTRIBUTES,
+                                                                                               r#"{"max_cred_num":5, "issuance_type":"ISSUANCE_BY_DEFAULT"}"#);
+
+        //3. Issuer revokes several Credentials at once
+        let revoc_reg_delta_json = anoncreds::issuer_revoke_credentials(issuer_wallet_handle,
+                                                                        blob_storage_reader_handle,
+                                                                        &rev_reg_id,
+                                                                        r#"["1", "3", "5"]"#,
+                                                                        None).unwrap();
+
+        assert_eq!(vec![1, 3, 5].into_iter().collect::<HashSet<u32>>(), anoncreds::get_revoc_reg_delta_indices(&revoc_reg_delta_json, "revoked"));
+
+        //4. Issuer revokes and recovers Credentials in one call
+        let revoc_reg_delta_json = anoncreds::issuer_revoke_credentials(issuer_wallet_handle,
+                                                                        blob_storage_reader_handle,
+                                                                        &rev_reg_id,
+                                                                        r#"["2"]"#,
+                                                                        Some(r#"["3"]"#)).unwrap();
+
+        assert_eq!(vec![2].into_iter().collect::<HashSet<u32>>(), anoncreds::get_revoc_reg_delta_indices(&revoc_reg_delta_json, "revoked"));
+        assert_eq!(vec![3].into_iter().collect::<HashSet<u32>>(), anoncreds::get_revoc_reg_delta_indices(&revoc_reg_delta_json, "issued"));
+
+        //5. Invalid requests are rejected
+        let res = anoncreds::issuer_revoke_credentials(issuer_wallet_handle, blob_storage_reader_handle, &rev_reg_id, r#"["4"]"#, Some(r#"["4"]"#));
+        assert_code!(ErrorCode::AnoncredsInvalidUserRevocId, res);
+
+        let res = anoncreds::issuer_revoke_credentials(issuer_wallet_handle, blob_storage_reader_handle, &rev_reg_id, r#"["4", "4"]"#, None);
+        assert_code!(ErrorCode::AnoncredsInvalidUserRevocId, res);
+
+        let res = anoncreds::issuer_revoke_credentials(issuer_wallet_handle, blob_storage_reader_handle, &rev_reg_id, r#"["10"]"#, None);
+        assert_code!(ErrorCode::AnoncredsInvalidUserRevocId, res);
+
+        let res = anoncreds::issuer_revoke_credentials(issuer_wallet_handle, blob_storage_reader_handle, &rev_reg_id, r#"["4"]"#, Some(r#"["2", "3"]"#));
+        assert_code!(ErrorCode::AnoncredsInvalidUserRevocId, res);
+
+        let res = anoncreds::issuer_revoke_credentials(issuer_wallet_handle, blob_storage_reader_handle, &rev_reg_id, "[]", None);
+        assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+        let res = anoncreds::issuer_revoke_credentials(issuer_wallet_handle, blob_storage_reader_handle, &rev_reg_id, r#"{"ids": ["4"]}"#, None);
+        assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+        //6. Failed requests don't change the registry
+        let revoc_reg_delta_json = anoncreds::issuer_revoke_credentials(issuer_wallet_handle,
+                                                                        blob_storage_reader_handle,
+                                                                        &rev_reg_id,
+                                                                        r#"["4"]"#,
+                                                                        Some(r#"["2"]"#)).unwrap();
+
+        assert_eq!(vec![4].into_iter().collect::<HashSet<u32>>(), anoncreds::get_revoc_reg_delta_indices(&revoc_reg_delta_json, "revoked"));
+        assert_eq!(vec![2].into_iter().collect::<HashSet<u32>>(), anoncreds::get_revoc_reg_delta_indices(&revoc_reg_delta_json, "issued"));
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+    }
+
+    #[cfg(feature = "revocation_tests")]
+    #[test]
+    fn anoncreds_works_for_issued_credentials_revocation_status_tracking() {
+        Setup::empty();
+
+        //1. Issuer creates wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issued_credentials_revocation_status_tracking").unwrap();
+
+        //2. Prover creates wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issued_credentials_revocation_status_tracking").unwrap();
+
+        //3 Issuer creates Schema, Credential Definition and Revocation Registry
+        let (_, _,
+            cred_def_id, cred_def_json,
+            rev_reg_id, revoc_reg_def_json, _,
+            blob_storage_reader_handle) = anoncreds::multi_steps_issuer_revocation_preparation(issuer_wallet_handle,
+                                                                                               ISSUER_DID,
+                                                                                               GVT_SCHEMA_NAME,
+                                                                                               GVT_SCHEMA_ATTRIBUTES,
+                                                                                               r#"{"max_cred_num":5, "issuance_type":"ISSUANCE_ON_DEMAND"}"#);
+
+        //4. Issuance of two Credentials for Prover
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        let mut cred_rev_ids = Vec::new();
+        for (cred_id, cred_values) in vec![(CREDENTIAL1_ID, anoncreds::gvt_credential_values_json()),
+                                           (CREDENTIAL2_ID, anoncreds::gvt2_credential_values_json())] {
+            let (cred_rev_id, _) = anoncreds::multi_steps_create_revocation_credential(COMMON_MASTER_SECRET,
+                                                                                      prover_wallet_handle,
+                                                                                      issuer_wallet_handle,
+                                                                                      cred_id,
+                                                                                      &cred_values,
+                                                                                      &cred_def_id,
+                                                                                      &cred_def_json,
+                                                                                      &rev_reg_id,
+                                                                                      &revoc_reg_def_json,
+                                                                                      blob_storage_reader_handle);
+            cred_rev_ids.push(cred_rev_id);
+        }
+
+        //5. Issuer links issued Credentials with connections
+        for (cred_rev_id, connection_id) in cred_rev_ids.iter().zip(vec!["connection_1", "connection_2"]) {
+            let issued_credentials = anoncreds::issuer_get_issued_credentials(issuer_wallet_handle,
+                                                                              &json!({"rev_reg_id": rev_reg_id, "cred_rev_id": cred_rev_id}).to_string());
+            assert_eq!(1, issued_credentials.len());
+            assert_eq!(DID_MY1, issued_credentials[0]["prover_did"].as_str().unwrap());
+
+            anoncreds::issuer_set_issued_credential_metadata(issuer_wallet_handle,
+                                                             issued_credentials[0]["referent"].as_str().unwrap(),
+                                                             &json!({"connection_id": connection_id}).to_string()).unwrap();
+        }
+
+        //6. Issuer revokes the Credential issued to the first connection
+        let issued_credentials = anoncreds::issuer_get_issued_credentials(issuer_wallet_handle, r#"{"connection_id": "connection_1"}"#);
+        assert_eq!(1, issued_credentials.len());
+        assert!(!issued_credentials[0]["revoked"].as_bool().unwrap());
+
+        let cred_rev_id = issued_credentials[0]["cred_rev_id"].as_str().unwrap();
+        assert_eq!(cred_rev_ids[0], cred_rev_id);
+
+        anoncreds::issuer_revoke_credential(issuer_wallet_handle, blob_storage_reader_handle, &rev_reg_id, cred_rev_id).unwrap();
+
+        //7. Revocation status is updated
+        let issued_credentials = anoncreds::issuer_get_issued_credentials(issuer_wallet_handle, r#"{"revoked": "true"}"#);
+        assert_eq!(1, issued_credentials.len());
+        assert_eq!("connection_1", issued_credentials[0]["metadata"]["connection_id"].as_str().unwrap());
+
+        let issued_credentials = anoncreds::issuer_get_issued_credentials(issuer_wallet_handle, r#"{"revoked": "false"}"#);
+        assert_eq!(1, issued_credentials.len());
+        assert_eq!("connection_2", issued_credentials[0]["metadata"]["connection_id"].as_str().unwrap());
+
+        //8. Issuer recovers the Credential and revokes the other one
+        anoncreds::issuer_revoke_credentials(issuer_wallet_handle,
+                                             blob_storage_reader_handle,
+                                             &rev_reg_id,
+                                             &json!([cred_rev_ids[1]]).to_string(),
+                                             Some(&json!([cred_rev_ids[0]]).to_string())).unwrap();
+
+        let issued_credentials = anoncreds::issuer_get_issued_credentials(issuer_wallet_handle, r#"{"revoked": "true"}"#);
+        assert_eq!(1, issued_credentials.len());
+        assert_eq!("connection_2", issued_credentials[0]["metadata"]["connection_id"].as_str().unwrap());
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
+    #[cfg(feature = "revocation_tests")]
+    #[test]
+    fn anoncreds_works_for_issued_credentials_revocation_status_tracking_for_fully_qualified_ids() {
+        Setup::empty();
+
+        //1. Issuer creates wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issued_credentials_revocation_status_tracking_for_fully_qualified_ids").unwrap();
+
+        //2. Prover creates wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issued_credentials_revocation_status_tracking_for_fully_qualified_ids").unwrap();
+
+        //3 Issuer creates Schema, Credential Definition and Revocation Registry with fully qualified ids
+        let (_, _,
+            cred_def_id, cred_def_json,
+            rev_reg_id, revoc_reg_def_json, _,
+            blob_storage_reader_handle) = anoncreds::multi_steps_issuer_revocation_preparation(issuer_wallet_handle,
+                                                                                               ISSUER_DID_V1,
+                                                                                               GVT_SCHEMA_NAME,
+                                                                                               GVT_SCHEMA_ATTRIBUTES,
+                                                                                               r#"{"max_cred_num":5, "issuance_type":"ISSUANCE_ON_DEMAND"}"#);
+
+        let unqualified_rev_reg_id = anoncreds::to_unqualified(&rev_reg_id).unwrap();
+        assert_ne!(rev_reg_id, unqualified_rev_reg_id);
+
+        //4. Issuance of Credential for Prover
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        let (cred_rev_id, _) = anoncreds::multi_steps_create_revocation_credential(COMMON_MASTER_SECRET,
+                                                                                  prover_wallet_handle,
+                                                                                  issuer_wallet_handle,
+                                                                                  CREDENTIAL1_ID,
+                                                                                  &anoncreds::gvt_credential_values_json(),
+                                                                                  &cred_def_id,
+                                                                                  &cred_def_json,
+                                                                                  &rev_reg_id,
+                                                                                  &revoc_reg_def_json,
+                                                                                  blob_storage_reader_handle);
+
+        //5. Issued Credential record is tagged with unqualified Revocation Registry id
+        let query_json = json!({"rev_reg_id": unqualified_rev_reg_id, "cred_rev_id": cred_rev_id}).to_string();
+
+        let issued_credentials = anoncreds::issuer_get_issued_credentials(issuer_wallet_handle, &query_json);
+        assert_eq!(1, issued_credentials.len());
+        assert!(!issued_credentials[0]["revoked"].as_bool().unwrap());
+
+        //6. Issuer revokes the Credential using fully qualified Revocation Registry id
+        anoncreds::issuer_revoke_credential(issuer_wallet_handle, blob_storage_reader_handle, &rev_reg_id, &cred_rev_id).unwrap();
+
+        //7. Revocation status is updated
+        let issued_credentials = anoncreds::issuer_get_issued_credentials(issuer_wallet_handle, &query_json);
+        assert_eq!(1, issued_credentials.len());
+        assert!(issued_credentials[0]["revoked"].as_bool().unwrap());
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
     #[cfg(feature = "revocation_tests")]
     #[test]
     fn anoncreds_works_for_issuance_by_demand_revocation_strategy_for_full_revocation_registry() {
@@ -15,6 +15,25 @@ use self::indy::ErrorCode;
 pub const ENCRYPTED_MESSAGE: &'static [u8; 45] = &[187, 227, 10, 29, 46, 178, 12, 179, 197, 69, 171, 70, 228, 204, 52, 22, 199, 54, 62, 13, 115, 5, 216, 66, 20, 131, 121, 29, 251, 224, 253, 201, 75, 73, 225, 237, 219, 133, 35, 217, 131, 135, 232, 129, 32];
 pub const SIGNATURE: &'static [u8; 64] = &[169, 215, 8, 225, 7, 107, 110, 9, 193, 162, 202, 214, 162, 66, 238, 211, 63, 209, 12, 196, 8, 211, 55, 27, 120, 94, 204, 147, 53, 104, 103, 61, 60, 249, 237, 127, 103, 46, 220, 223, 10, 95, 75, 53, 245, 210, 241, 151, 191, 41, 48, 30, 9, 16, 78, 252, 157, 206, 210, 145, 125, 133, 109, 11];
 
+// Establishes session between DIDs of the setups. Initiator is the first one.
+fn establish_session(initiator: &Setup, responder: &Setup) -> String {
+    crate::utils::did::store_their_did_from_parts(initiator.wallet_handle, &responder.did, &responder.verkey).unwrap();
+    crate::utils::did::store_their_did_from_parts(responder.wallet_handle, &initiator.did, &initiator.verkey).unwrap();
+
+    let (session_id, init_handshake) = crypto::session_start(initiator.wallet_handle, &initiator.verkey, &responder.did, None).unwrap();
+
+    let (responder_session_id, response_handshake) =
+        crypto::session_start(responder.wallet_handle, &responder.verkey, &initiator.did, Some(&init_handshake.unwrap())).unwrap();
+    assert_eq!(session_id, responder_session_id);
+
+    let (completed_session_id, handshake) =
+        crypto::session_start(initiator.wallet_handle, &initiator.verkey, &responder.did, Some(&response_handshake.unwrap())).unwrap();
+    assert_eq!(session_id, completed_session_id);
+    assert_eq!(None, handshake);
+
+    session_id
+}
+
 mod high_cases {
     use super::*;
 
@@ -127,6 +146,254 @@ mod high_cases {
         }
     }
 
+    mod crypto_verify_multi {
+        use super::*;
+        use crate::utils::{did, ledger};
+        use crate::api::WalletHandle;
+        use rust_base58::ToBase58;
+
+        fn _keys(wallet_handle: WalletHandle) -> (String, String, String) {
+            (crypto::create_key(wallet_handle, Some(MY1_SEED)).unwrap(),
+             crypto::create_key(wallet_handle, Some(MY2_SEED)).unwrap(),
+             crypto::create_key(wallet_handle, Some(TRUSTEE_SEED)).unwrap())
+        }
+
+        fn _sign(wallet_handle: WalletHandle, vk: &str) -> String {
+            crypto::sign(wallet_handle, vk, MESSAGE.as_bytes()).unwrap().to_base58()
+        }
+
+        #[test]
+        fn indy_crypto_verify_multi_works() {
+            let setup = Setup::wallet();
+            let (vk1, vk2, vk3) = _keys(setup.wallet_handle);
+
+            let signatures = json!({
+                vk1.clone(): _sign(setup.wallet_handle, &vk1),
+                vk3.clone(): _sign(setup.wallet_handle, &vk3),
+            }).to_string();
+            let policy = json!({"threshold": 2, "signers": [vk1, vk2, vk3]}).to_string();
+
+            let valid = crypto::verify_multi(MESSAGE.as_bytes(), &signatures, &policy).unwrap();
+            assert!(valid);
+        }
+
+        #[test]
+        fn indy_crypto_verify_multi_works_for_not_enough_signatures() {
+            let setup = Setup::wallet();
+            let (vk1, vk2, vk3) = _keys(setup.wallet_handle);
+
+            let signatures = json!({vk1.clone(): _sign(setup.wallet_handle, &vk1)}).to_string();
+            let policy = json!({"threshold": 2, "signers": [vk1, vk2, vk3]}).to_string();
+
+            let valid = crypto::verify_multi(MESSAGE.as_bytes(), &signatures, &policy).unwrap();
+            assert!(!valid);
+        }
+
+        #[test]
+        fn indy_crypto_verify_multi_works_for_signer_not_in_policy() {
+            let setup = Setup::wallet();
+            let (vk1, vk2, vk3) = _keys(setup.wallet_handle);
+
+            let signatures = json!({
+                vk1.clone(): _sign(setup.wallet_handle, &vk1),
+                vk3.clone(): _sign(setup.wallet_handle, &vk3),
+            }).to_string();
+            let policy = json!({"threshold": 2, "signers": [vk1, vk2]}).to_string();
+
+            let valid = crypto::verify_multi(MESSAGE.as_bytes(), &signatures, &policy).unwrap();
+            assert!(!valid);
+        }
+
+        #[test]
+        fn indy_crypto_verify_multi_works_for_signature_of_other_key() {
+            let setup = Setup::wallet();
+            let (vk1, vk2, _) = _keys(setup.wallet_handle);
+
+            let signatures = json!({
+                vk1.clone(): _sign(setup.wallet_handle, &vk1),
+                vk2.clone(): _sign(setup.wallet_handle, &vk1),
+            }).to_string();
+            let policy = json!({"threshold": 2, "signers": [vk1, vk2]}).to_string();
+
+            let valid = crypto::verify_multi(MESSAGE.as_bytes(), &signatures, &policy).unwrap();
+            assert!(!valid);
+        }
+
+        #[test]
+        fn indy_crypto_verify_multi_works_for_same_key_with_crypto_type() {
+            let setup = Setup::wallet();
+            let (vk1, _, _) = _keys(setup.wallet_handle);
+            let vk1_with_crypto_type = format!("{}:ed25519", vk1);
+
+            let signatures = json!({
+                vk1.clone(): _sign(setup.wallet_handle, &vk1),
+                vk1_with_crypto_type.clone(): _sign(setup.wallet_handle, &vk1),
+            }).to_string();
+            let policy = json!({"threshold": 2, "signers": [vk1, vk1_with_crypto_type]}).to_string();
+
+            let res = crypto::verify_multi(MESSAGE.as_bytes(), &signatures, &policy);
+            assert_code!(ErrorCode::CommonInvalidParam5, res);
+        }
+
+        #[test]
+        fn indy_crypto_verify_multi_works_for_abbreviated_signer() {
+            let setup = Setup::did();
+
+            let signatures = json!({}).to_string();
+            let policy = json!({"threshold": 1, "signers": [format!("~{}", &setup.verkey[..22])]}).to_string();
+
+            let res = crypto::verify_multi(MESSAGE.as_bytes(), &signatures, &policy);
+            assert_code!(ErrorCode::CommonInvalidParam5, res);
+        }
+
+        #[test]
+        fn indy_crypto_verify_multi_works_for_multi_signed_request() {
+            let setup = Setup::wallet();
+
+            let (did1, vk1) = did::create_and_store_my_did(setup.wallet_handle, Some(MY1_SEED)).unwrap();
+            let (did2, vk2) = did::create_and_store_my_did(setup.wallet_handle, Some(MY2_SEED)).unwrap();
+
+            let request = ledger::build_nym_request(&did1, &did2, Some(&vk2), None, None).unwrap();
+            let request = ledger::multi_sign_request(setup.wallet_handle, &did1, &request).unwrap();
+            let request = ledger::multi_sign_request(setup.wallet_handle, &did2, &request).unwrap();
+
+            let request_json: serde_json::Value = serde_json::from_str(&request).unwrap();
+            let signatures = json!({
+                vk1.clone(): request_json["signatures"][&did1],
+                vk2.clone(): request_json["signatures"][&did2],
+            }).to_string();
+            let policy = json!({"threshold": 2, "signers": [vk1, vk2], "serialization": "request"}).to_string();
+
+            let valid = crypto::verify_multi(request.as_bytes(), &signatures, &policy).unwrap();
+            assert!(valid);
+        }
+    }
+
+    mod crypto_jws {
+        use super::*;
+        use indy_utils::crypto::base64;
+
+        const JWS_MY1: &str = "eyJhbGciOiJFZERTQSIsImtpZCI6IkdqWldzQkxnWkNSMThhTDQ2OEpBVDd3OUNaUmlCbnB4VVBQZ3lReGg0dm9hIn0.\
+                               eyJyZXFJZCI6MTQ5NjgyMjIxMTM2MjAxNzc2NH0.\
+                               tDtB4FpcugczWuBmI82kADvSRmHMZTjdXFZ_qYWbmiCnM_dI8WqOwChGMnn-bB74ldfhZEJLlyX3lpxrHKOHBQ";
+        const DID_KEY_MY1: &str = "did:key:z6MkvBpZTRb7tjuUF5AkmhG1JDV928hZbg5KAQJcogvhz9ax";
+
+        fn _protected_header(protected: &str) -> serde_json::Value {
+            serde_json::from_slice(&base64::decode_urlsafe(protected).unwrap()).unwrap()
+        }
+
+        #[test]
+        fn indy_crypto_sign_jws_works_for_compact() {
+            let setup = Setup::wallet();
+
+            let my_vk = crypto::create_key(setup.wallet_handle, Some(MY1_SEED)).unwrap();
+
+            let jws = crypto::sign_jws(setup.wallet_handle, &my_vk, MESSAGE.as_bytes(), None).unwrap();
+
+            let parts: Vec<&str> = jws.split('.').collect();
+            assert_eq!(3, parts.len());
+
+            let protected = _protected_header(parts[0]);
+            assert_eq!("EdDSA", protected["alg"].as_str().unwrap());
+            assert_eq!(VERKEY_MY1, protected["kid"].as_str().unwrap());
+            assert_eq!(MESSAGE.as_bytes().to_vec(), base64::decode_urlsafe(parts[1]).unwrap());
+
+            assert!(crypto::verify_jws(&jws, None).unwrap());
+        }
+
+        #[test]
+        fn indy_crypto_sign_jws_works_for_did_key_kid() {
+            let setup = Setup::wallet();
+
+            let my_vk = crypto::create_key(setup.wallet_handle, Some(MY1_SEED)).unwrap();
+
+            let jws = crypto::sign_jws(setup.wallet_handle, &my_vk, MESSAGE.as_bytes(), Some(r#"{"kid": "did:key"}"#)).unwrap();
+
+            let protected = _protected_header(jws.split('.').next().unwrap());
+            assert_eq!(format!("{}#{}", DID_KEY_MY1, &DID_KEY_MY1["did:key:".len()..]), protected["kid"].as_str().unwrap());
+
+            assert!(crypto::verify_jws(&jws, None).unwrap());
+        }
+
+        #[test]
+        fn indy_crypto_sign_jws_works_for_detached_json() {
+            let setup = Setup::wallet();
+
+            let my_vk = crypto::create_key(setup.wallet_handle, Some(MY1_SEED)).unwrap();
+
+            let options = r#"{"serialization": "json", "detached": true, "protected": {"typ": "JWT"}}"#;
+            let jws = crypto::sign_jws(setup.wallet_handle, &my_vk, MESSAGE.as_bytes(), Some(options)).unwrap();
+
+            let jws_json: serde_json::Value = serde_json::from_str(&jws).unwrap();
+            assert!(jws_json.get("payload").is_none());
+
+            let protected = _protected_header(jws_json["signatures"][0]["protected"].as_str().unwrap());
+            assert_eq!("JWT", protected["typ"].as_str().unwrap());
+
+            assert!(crypto::verify_jws(&jws, Some(MESSAGE.as_bytes())).unwrap());
+            assert!(!crypto::verify_jws(&jws, Some(b"other message")).unwrap());
+
+            let res = crypto::verify_jws(&jws, None);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_crypto_sign_jws_works_for_detached_compact() {
+            let setup = Setup::wallet();
+
+            let my_vk = crypto::create_key(setup.wallet_handle, Some(MY1_SEED)).unwrap();
+
+            let jws = crypto::sign_jws(setup.wallet_handle, &my_vk, MESSAGE.as_bytes(), Some(r#"{"detached": true}"#)).unwrap();
+            assert!(jws.contains(".."));
+
+            assert!(crypto::verify_jws(&jws, Some(MESSAGE.as_bytes())).unwrap());
+        }
+
+        #[test]
+        fn indy_crypto_sign_jws_works_for_reserved_protected_header() {
+            let setup = Setup::wallet();
+
+            let my_vk = crypto::create_key(setup.wallet_handle, Some(MY1_SEED)).unwrap();
+
+            let res = crypto::sign_jws(setup.wallet_handle, &my_vk, MESSAGE.as_bytes(), Some(r#"{"protected": {"alg": "none"}}"#));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_crypto_sign_jws_works_for_unknown_signer() {
+            let setup = Setup::wallet();
+            let res = crypto::sign_jws(setup.wallet_handle, VERKEY, MESSAGE.as_bytes(), None);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+
+        #[test]
+        fn indy_crypto_verify_jws_works() {
+            assert!(crypto::verify_jws(JWS_MY1, None).unwrap());
+        }
+
+        #[test]
+        fn indy_crypto_verify_jws_works_for_flattened_json() {
+            let parts: Vec<&str> = JWS_MY1.split('.').collect();
+
+            let jws = json!({"protected": parts[0], "payload": parts[1], "signature": parts[2]}).to_string();
+            assert!(crypto::verify_jws(&jws, None).unwrap());
+        }
+
+        #[test]
+        fn indy_crypto_verify_jws_works_for_other_payload() {
+            let parts: Vec<&str> = JWS_MY1.split('.').collect();
+
+            let jws = format!("{}.{}.{}", parts[0], base64::encode_urlsafe_no_pad(b"other message"), parts[2]);
+            assert!(!crypto::verify_jws(&jws, None).unwrap());
+        }
+
+        #[test]
+        fn indy_crypto_verify_jws_works_for_invalid_jws() {
+            let res = crypto::verify_jws("not a jws", None);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
     mod auth_crypt {
         use super::*;
 
@@ -211,6 +478,134 @@ mod high_cases {
         }
     }
 
+    mod crypto_session {
+        use super::*;
+        use crate::utils::did;
+
+        #[test]
+        fn indy_crypto_session_works() {
+            let alice = Setup::did();
+            let bob = Setup::did();
+
+            let session_id = establish_session(&alice, &bob);
+
+            let encrypted_msg = crypto::session_encrypt(alice.wallet_handle, &session_id, MESSAGE.as_bytes()).unwrap();
+            let msg = crypto::session_decrypt(bob.wallet_handle, &encrypted_msg).unwrap();
+            assert_eq!(MESSAGE.as_bytes().to_vec(), msg);
+
+            let encrypted_msg = crypto::session_encrypt(bob.wallet_handle, &session_id, b"reply").unwrap();
+            let msg = crypto::session_decrypt(alice.wallet_handle, &encrypted_msg).unwrap();
+            assert_eq!(b"reply".to_vec(), msg);
+        }
+
+        #[test]
+        fn indy_crypto_session_works_for_ratcheted_keys() {
+            let alice = Setup::did();
+            let bob = Setup::did();
+
+            let session_id = establish_session(&alice, &bob);
+
+            let encrypted_msg1 = crypto::session_encrypt(alice.wallet_handle, &session_id, MESSAGE.as_bytes()).unwrap();
+            let encrypted_msg2 = crypto::session_encrypt(alice.wallet_handle, &session_id, MESSAGE.as_bytes()).unwrap();
+
+            let msg1: serde_json::Value = serde_json::from_slice(&encrypted_msg1).unwrap();
+            let msg2: serde_json::Value = serde_json::from_slice(&encrypted_msg2).unwrap();
+            assert_eq!(json!(0), msg1["counter"]);
+            assert_eq!(json!(1), msg2["counter"]);
+            assert_ne!(msg1["ciphertext"], msg2["ciphertext"]);
+
+            assert_eq!(MESSAGE.as_bytes().to_vec(), crypto::session_decrypt(bob.wallet_handle, &encrypted_msg1).unwrap());
+            assert_eq!(MESSAGE.as_bytes().to_vec(), crypto::session_decrypt(bob.wallet_handle, &encrypted_msg2).unwrap());
+        }
+
+        #[test]
+        fn indy_crypto_session_works_for_out_of_order_messages() {
+            let alice = Setup::did();
+            let bob = Setup::did();
+
+            let session_id = establish_session(&alice, &bob);
+
+            let encrypted_msg1 = crypto::session_encrypt(alice.wallet_handle, &session_id, b"first").unwrap();
+            let encrypted_msg2 = crypto::session_encrypt(alice.wallet_handle, &session_id, b"second").unwrap();
+
+            assert_eq!(b"second".to_vec(), crypto::session_decrypt(bob.wallet_handle, &encrypted_msg2).unwrap());
+            assert_eq!(b"first".to_vec(), crypto::session_decrypt(bob.wallet_handle, &encrypted_msg1).unwrap());
+        }
+
+        #[test]
+        fn indy_crypto_session_decrypt_fails_for_replayed_message() {
+            let alice = Setup::did();
+            let bob = Setup::did();
+
+            let session_id = establish_session(&alice, &bob);
+
+            let encrypted_msg = crypto::session_encrypt(alice.wallet_handle, &session_id, MESSAGE.as_bytes()).unwrap();
+            crypto::session_decrypt(bob.wallet_handle, &encrypted_msg).unwrap();
+
+            let res = crypto::session_decrypt(bob.wallet_handle, &encrypted_msg);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_crypto_session_decrypt_fails_for_own_message() {
+            let alice = Setup::did();
+            let bob = Setup::did();
+
+            let session_id = establish_session(&alice, &bob);
+
+            let encrypted_msg = crypto::session_encrypt(alice.wallet_handle, &session_id, MESSAGE.as_bytes()).unwrap();
+
+            let res = crypto::session_decrypt(alice.wallet_handle, &encrypted_msg);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_crypto_session_encrypt_fails_for_not_completed_session() {
+            let alice = Setup::did();
+            let bob = Setup::did();
+
+            did::store_their_did_from_parts(alice.wallet_handle, &bob.did, &bob.verkey).unwrap();
+
+            let (session_id, _) = crypto::session_start(alice.wallet_handle, &alice.verkey, &bob.did, None).unwrap();
+
+            let res = crypto::session_encrypt(alice.wallet_handle, &session_id, MESSAGE.as_bytes());
+            assert_code!(ErrorCode::CommonInvalidState, res);
+        }
+
+        #[test]
+        fn indy_crypto_session_start_fails_for_handshake_to_other_party() {
+            let alice = Setup::did();
+            let bob = Setup::did();
+            let mallory = Setup::did();
+
+            did::store_their_did_from_parts(alice.wallet_handle, &bob.did, &bob.verkey).unwrap();
+            did::store_their_did_from_parts(mallory.wallet_handle, &alice.did, &alice.verkey).unwrap();
+
+            let (_, init_handshake) = crypto::session_start(alice.wallet_handle, &alice.verkey, &bob.did, None).unwrap();
+
+            let res = crypto::session_start(mallory.wallet_handle, &mallory.verkey, &alice.did, Some(&init_handshake.unwrap()));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_crypto_session_start_fails_for_completed_session() {
+            let alice = Setup::did();
+            let bob = Setup::did();
+
+            let session_id = establish_session(&alice, &bob);
+
+            let handshake = json!({
+                "session_id": session_id,
+                "sender_vk": bob.verkey,
+                "ephemeral_key": VERKEY_MY1,
+                "signature": "4Be93xmGCYqFwzz5GGUYnz4hpwBBQDqHf6pB43ZtYyiJ8aJTLB2vJFUqRNUUXW5kUMNnkjWRiUZ7CQmgwGwLGUsP",
+            }).to_string();
+
+            let res = crypto::session_start(alice.wallet_handle, &alice.verkey, &bob.did, Some(&handshake));
+            assert_code!(ErrorCode::CommonInvalidState, res);
+        }
+    }
+
     mod pack_message_authcrypt {
         use super::*;
 
@@ -326,6 +721,195 @@ mod high_cases {
             assert_code!(ErrorCode::WalletItemNotFound, res);
         }
     }
+
+    mod pack_message_ecdh {
+        use super::*;
+        use indy_utils::crypto::base64;
+
+        const ECDH_OPTIONS: &str = r#"{"profile": "ecdh"}"#;
+        const ECDH_XC20P_OPTIONS: &str = r#"{"profile": "ecdh", "enc": "XC20P"}"#;
+
+        fn _protected_header(jwe: &[u8]) -> serde_json::Value {
+            let jwe: serde_json::Value = serde_json::from_slice(jwe).unwrap();
+            let protected = base64::decode_urlsafe(jwe["protected"].as_str().unwrap()).unwrap();
+            serde_json::from_slice(&protected).unwrap()
+        }
+
+        #[test]
+        fn indy_pack_message_with_options_works_for_ecdh_1pu() {
+            let sender_setup = Setup::key();
+            let receiver_setup = Setup::key();
+
+            let receiver_keys = serde_json::to_string(&vec![VERKEY_TRUSTEE, &receiver_setup.verkey]).unwrap();
+
+            for options in &[ECDH_OPTIONS, ECDH_XC20P_OPTIONS] {
+                let jwe = crypto::pack_message_with_options(sender_setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_keys,
+                                                            Some(&sender_setup.verkey), Some(options)).unwrap();
+
+                let protected = _protected_header(&jwe);
+                assert_eq!("ECDH-1PU+A256KW", protected["alg"].as_str().unwrap());
+                assert_eq!(sender_setup.verkey, protected["skid"].as_str().unwrap());
+
+                let res = crypto::unpack_message(receiver_setup.wallet_handle, &jwe).unwrap();
+                let res: serde_json::Value = serde_json::from_slice(&res).unwrap();
+
+                assert_eq!(AGENT_MESSAGE, res["message"].as_str().unwrap());
+                assert_eq!(sender_setup.verkey, res["sender_verkey"].as_str().unwrap());
+                assert_eq!(receiver_setup.verkey, res["recipient_verkey"].as_str().unwrap());
+            }
+        }
+
+        #[test]
+        fn indy_pack_message_with_options_works_for_ecdh_es() {
+            let sender_setup = Setup::wallet();
+            let receiver_setup = Setup::key();
+
+            let receiver_keys = serde_json::to_string(&vec![&receiver_setup.verkey]).unwrap();
+
+            for options in &[ECDH_OPTIONS, ECDH_XC20P_OPTIONS] {
+                let jwe = crypto::pack_message_with_options(sender_setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_keys,
+                                                            None, Some(options)).unwrap();
+
+                let protected = _protected_header(&jwe);
+                assert_eq!("ECDH-ES+A256KW", protected["alg"].as_str().unwrap());
+                assert!(protected["skid"].is_null());
+
+                let res = crypto::unpack_message(receiver_setup.wallet_handle, &jwe).unwrap();
+                let res: serde_json::Value = serde_json::from_slice(&res).unwrap();
+
+                assert_eq!(AGENT_MESSAGE, res["message"].as_str().unwrap());
+                assert!(res["sender_verkey"].is_null());
+                assert_eq!(receiver_setup.verkey, res["recipient_verkey"].as_str().unwrap());
+            }
+        }
+
+        #[test]
+        fn indy_pack_message_with_options_works_for_legacy_profile() {
+            let sender_setup = Setup::key();
+            let receiver_setup = Setup::key();
+
+            let receiver_keys = serde_json::to_string(&vec![&receiver_setup.verkey]).unwrap();
+
+            let jwe = crypto::pack_message_with_options(sender_setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_keys,
+                                                        Some(&sender_setup.verkey), None).unwrap();
+            assert_eq!("Authcrypt", _protected_header(&jwe)["alg"].as_str().unwrap());
+
+            let res = crypto::unpack_message(receiver_setup.wallet_handle, &jwe).unwrap();
+            let res: serde_json::Value = serde_json::from_slice(&res).unwrap();
+            assert_eq!(AGENT_MESSAGE, res["message"].as_str().unwrap());
+        }
+
+        #[test]
+        fn indy_unpack_message_fails_for_ecdh_1pu_with_replaced_sender() {
+            let sender_setup = Setup::key();
+            let receiver_setup = Setup::key();
+
+            let receiver_keys = serde_json::to_string(&vec![&receiver_setup.verkey]).unwrap();
+
+            let jwe = crypto::pack_message_with_options(sender_setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_keys,
+                                                        Some(&sender_setup.verkey), Some(ECDH_OPTIONS)).unwrap();
+
+            let mut protected = _protected_header(&jwe);
+            protected["skid"] = json!(VERKEY_TRUSTEE);
+            protected["apu"] = json!(base64::encode_urlsafe_no_pad(VERKEY_TRUSTEE.as_bytes()));
+
+            let mut jwe: serde_json::Value = serde_json::from_slice(&jwe).unwrap();
+            jwe["protected"] = json!(base64::encode_urlsafe_no_pad(protected.to_string().as_bytes()));
+
+            let res = crypto::unpack_message(receiver_setup.wallet_handle, jwe.to_string().as_bytes());
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_pack_message_with_options_fails_for_unknown_profile() {
+            let setup = Setup::key();
+
+            let receiver_keys = serde_json::to_string(&vec![VERKEY_TRUSTEE]).unwrap();
+
+            let res = crypto::pack_message_with_options(setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_keys,
+                                                        None, Some(r#"{"profile": "unknown"}"#));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
+    mod key_manager {
+        use super::*;
+
+        #[test]
+        fn indy_create_key_works_for_key_manager() {
+            let setup = Setup::wallet();
+            crypto::register_key_manager(INMEM_KEY_MANAGER).unwrap();
+
+            let verkey = crypto::create_key_in_key_manager(setup.wallet_handle, Some(MY1_SEED), INMEM_KEY_MANAGER).unwrap();
+            assert_eq!(VERKEY_MY1, verkey);
+        }
+
+        #[test]
+        fn indy_crypto_sign_works_for_key_manager() {
+            let setup = Setup::wallet();
+            crypto::register_key_manager(INMEM_KEY_MANAGER).unwrap();
+
+            let verkey = crypto::create_key_in_key_manager(setup.wallet_handle, Some(MY1_SEED), INMEM_KEY_MANAGER).unwrap();
+
+            let signature = crypto::sign(setup.wallet_handle, &verkey, MESSAGE.as_bytes()).unwrap();
+            assert_eq!(SIGNATURE.to_vec(), signature);
+
+            assert!(crypto::verify(&verkey, MESSAGE.as_bytes(), &signature).unwrap());
+        }
+
+        #[test]
+        fn indy_create_key_works_for_unknown_key_manager() {
+            let setup = Setup::wallet();
+
+            let res = crypto::create_key_in_key_manager(setup.wallet_handle, None, "unknown_key_manager");
+            assert_code!(ErrorCode::UnknownCryptoTypeError, res);
+        }
+
+        #[test]
+        fn indy_pack_unpack_message_works_for_key_manager() {
+            let sender_setup = Setup::wallet();
+            let receiver_setup = Setup::wallet();
+            crypto::register_key_manager(INMEM_KEY_MANAGER).unwrap();
+
+            let sender_vk = crypto::create_key_in_key_manager(sender_setup.wallet_handle, None, INMEM_KEY_MANAGER).unwrap();
+            let receiver_vk = crypto::create_key_in_key_manager(receiver_setup.wallet_handle, None, INMEM_KEY_MANAGER).unwrap();
+
+            let receiver_keys = serde_json::to_string(&vec![&receiver_vk]).unwrap();
+
+            for sender in &[Some(sender_vk.as_str()), None] {
+                let jwe = crypto::pack_message(sender_setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_keys, *sender).unwrap();
+
+                let res = crypto::unpack_message(receiver_setup.wallet_handle, &jwe).unwrap();
+                let res: serde_json::Value = serde_json::from_slice(&res).unwrap();
+
+                assert_eq!(AGENT_MESSAGE, res["message"].as_str().unwrap());
+                assert_eq!(receiver_vk, res["recipient_verkey"].as_str().unwrap());
+                assert_eq!(sender.map(String::from), res["sender_verkey"].as_str().map(String::from));
+            }
+        }
+
+        #[test]
+        fn indy_pack_unpack_message_works_for_key_manager_and_ecdh_profile() {
+            let sender_setup = Setup::wallet();
+            let receiver_setup = Setup::wallet();
+            crypto::register_key_manager(INMEM_KEY_MANAGER).unwrap();
+
+            let sender_vk = crypto::create_key_in_key_manager(sender_setup.wallet_handle, None, INMEM_KEY_MANAGER).unwrap();
+            let receiver_vk = crypto::create_key_in_key_manager(receiver_setup.wallet_handle, None, INMEM_KEY_MANAGER).unwrap();
+
+            let receiver_keys = serde_json::to_string(&vec![&receiver_vk]).unwrap();
+            let options = r#"{"profile": "ecdh"}"#;
+
+            // Key manager doesn't provide X25519 key agreement
+            let res = crypto::pack_message_with_options(sender_setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_keys, Some(&sender_vk), Some(options));
+            assert_code!(ErrorCode::UnknownCryptoTypeError, res);
+
+            let jwe = crypto::pack_message_with_options(sender_setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_keys, None, Some(options)).unwrap();
+
+            let res = crypto::unpack_message(receiver_setup.wallet_handle, &jwe);
+            assert_code!(ErrorCode::UnknownCryptoTypeError, res);
+        }
+    }
 }
 
 #[cfg(not(feature = "only_high_cases"))]
@@ -423,6 +1007,37 @@ mod medium_cases {
         }
     }
 
+    mod crypto_verify_multi {
+        use super::*;
+
+        #[test]
+        fn indy_crypto_verify_multi_works_for_invalid_threshold() {
+            let signatures = json!({VERKEY_MY1: ""}).to_string();
+            let policy = json!({"threshold": 2, "signers": [VERKEY_MY1]}).to_string();
+
+            let res = crypto::verify_multi(MESSAGE.as_bytes(), &signatures, &policy);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_crypto_verify_multi_works_for_invalid_signature() {
+            let signatures = json!({VERKEY_MY1: "invalid_base58_signature"}).to_string();
+            let policy = json!({"threshold": 1, "signers": [VERKEY_MY1]}).to_string();
+
+            let res = crypto::verify_multi(MESSAGE.as_bytes(), &signatures, &policy);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_crypto_verify_multi_works_for_invalid_signer_verkey() {
+            let signatures = json!({}).to_string();
+            let policy = json!({"threshold": 1, "signers": [INVALID_BASE58_VERKEY]}).to_string();
+
+            let res = crypto::verify_multi(MESSAGE.as_bytes(), &signatures, &policy);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
     mod auth_crypt {
         use super::*;
 
@@ -513,6 +1128,71 @@ mod medium_cases {
         }
     }
 
+    mod crypto_session {
+        use super::*;
+
+        #[test]
+        fn indy_crypto_session_start_works_for_unknown_their_did() {
+            let setup = Setup::did();
+
+            let res = crypto::session_start(setup.wallet_handle, &setup.verkey, DID_MY1, None);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+
+        #[test]
+        fn indy_crypto_session_start_works_for_invalid_handshake() {
+            let setup = Setup::did();
+
+            did::store_their_did_from_parts(setup.wallet_handle, DID_MY1, VERKEY_MY1).unwrap();
+
+            let res = crypto::session_start(setup.wallet_handle, &setup.verkey, DID_MY1, Some(r#"{"session_id": "1"}"#));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_crypto_session_start_works_for_invalid_wallet_handle() {
+            Setup::empty();
+
+            let res = crypto::session_start(INVALID_WALLET_HANDLE, VERKEY_MY1, DID_MY1, None);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+
+        #[test]
+        fn indy_crypto_session_encrypt_works_for_unknown_session() {
+            let setup = Setup::wallet();
+
+            let res = crypto::session_encrypt(setup.wallet_handle, "unknown", MESSAGE.as_bytes());
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+
+        #[test]
+        fn indy_crypto_session_decrypt_works_for_invalid_msg() {
+            let setup = Setup::wallet();
+
+            let res = crypto::session_decrypt(setup.wallet_handle, "unencrypted message".as_bytes());
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_crypto_session_decrypt_works_for_modified_msg() {
+            let alice = Setup::did();
+            let bob = Setup::did();
+
+            let session_id = establish_session(&alice, &bob);
+
+            let encrypted_msg = crypto::session_encrypt(alice.wallet_handle, &session_id, MESSAGE.as_bytes()).unwrap();
+
+            let mut msg: serde_json::Value = serde_json::from_slice(&encrypted_msg).unwrap();
+            msg["counter"] = json!(1);
+
+            let res = crypto::session_decrypt(bob.wallet_handle, msg.to_string().as_bytes());
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            // Session state isn't changed by the modified message
+            assert_eq!(MESSAGE.as_bytes().to_vec(), crypto::session_decrypt(bob.wallet_handle, &encrypted_msg).unwrap());
+        }
+    }
+
     mod pack_message_authcrypt {
         use super::*;
 
@@ -6,7 +6,7 @@ inject_indy_dependencies!();
 extern crate indyrs as indy;
 extern crate indyrs as api;
 
-use crate::utils::{did, pool, ledger};
+use crate::utils::{did, pool, ledger, crypto};
 use crate::utils::constants::*;
 use crate::utils::types::ResponseType;
 use crate::utils::Setup;
@@ -121,6 +121,22 @@ mod high_cases {
             let received_verkey = did::key_for_local_did(setup.wallet_handle, &setup.did).unwrap();
             assert_eq!(setup.verkey, received_verkey);
         }
+
+        #[test]
+        fn indy_key_for_local_did_works_for_not_stored_did_key() {
+            let setup = Setup::wallet();
+
+            let received_verkey = did::key_for_local_did(setup.wallet_handle, DID_KEY_MY1).unwrap();
+            assert_eq!(VERKEY_MY1, received_verkey);
+        }
+
+        #[test]
+        fn indy_key_for_local_did_works_for_not_stored_did_peer() {
+            let setup = Setup::wallet();
+
+            let received_verkey = did::key_for_local_did(setup.wallet_handle, DID_PEER_0_MY1).unwrap();
+            assert_eq!(VERKEY_MY1, received_verkey);
+        }
     }
 
     mod set_endpoint_for_did {
@@ -217,6 +233,116 @@ mod high_cases {
         }
     }
 
+    mod build_did_doc {
+        use super::*;
+
+        #[test]
+        fn indy_build_did_doc_works_for_my_did() {
+            let setup = Setup::did();
+
+            let did_doc = did::build_did_doc(setup.wallet_handle, &setup.did).unwrap();
+            let did_doc: serde_json::Value = serde_json::from_str(&did_doc).unwrap();
+
+            let id = format!("did:sov:{}", setup.did);
+            let key_id = format!("{}#key-1", id);
+
+            assert_eq!(json!("https://www.w3.org/ns/did/v1"), did_doc["@context"][0]);
+            assert_eq!(json!(id), did_doc["id"]);
+            assert_eq!(json!([{
+                "id": key_id,
+                "type": "Ed25519VerificationKey2018",
+                "controller": id,
+                "publicKeyBase58": setup.verkey
+            }]), did_doc["verificationMethod"]);
+            assert_eq!(json!([key_id]), did_doc["authentication"]);
+            assert_eq!(json!("X25519KeyAgreementKey2019"), did_doc["keyAgreement"][0]["type"]);
+            assert!(did_doc.get("service").is_none());
+        }
+
+        #[test]
+        fn indy_build_did_doc_works_for_endpoint() {
+            let setup = Setup::did();
+
+            did::set_endpoint_for_did(setup.wallet_handle, &setup.did, ENDPOINT, VERKEY).unwrap();
+
+            let did_doc = did::build_did_doc(setup.wallet_handle, &setup.did).unwrap();
+            let did_doc: serde_json::Value = serde_json::from_str(&did_doc).unwrap();
+
+            let id = format!("did:sov:{}", setup.did);
+            assert_eq!(json!([{
+                "id": format!("{}#did-communication", id),
+                "type": "did-communication",
+                "serviceEndpoint": ENDPOINT,
+                "priority": 0,
+                "recipientKeys": ["did:key:z6MkrEVGLPYHhLGBQ25Bh5DXSakRiDpdoj7fdsNZSqYPcUGt"],
+                "routingKeys": []
+            }]), did_doc["service"]);
+        }
+
+        #[test]
+        fn indy_build_did_doc_works_for_fully_qualified_did() {
+            let setup = Setup::did_fully_qualified();
+
+            let did_doc = did::build_did_doc(setup.wallet_handle, &setup.did).unwrap();
+            let did_doc: serde_json::Value = serde_json::from_str(&did_doc).unwrap();
+
+            assert_eq!(json!(setup.did), did_doc["id"]);
+        }
+
+        #[test]
+        fn indy_build_did_doc_works_for_their_did() {
+            let setup = Setup::wallet();
+
+            did::store_their_did_from_parts(setup.wallet_handle, DID, VERKEY).unwrap();
+
+            let did_doc = did::build_did_doc(setup.wallet_handle, DID).unwrap();
+            let did_doc: serde_json::Value = serde_json::from_str(&did_doc).unwrap();
+
+            assert_eq!(json!(format!("did:sov:{}", DID)), did_doc["id"]);
+            assert_eq!(json!(VERKEY), did_doc["verificationMethod"][0]["publicKeyBase58"]);
+        }
+
+        #[test]
+        fn indy_build_did_doc_works_for_not_stored_did_key() {
+            let setup = Setup::wallet();
+
+            let did_doc = did::build_did_doc(setup.wallet_handle, DID_KEY_MY1).unwrap();
+            let did_doc: serde_json::Value = serde_json::from_str(&did_doc).unwrap();
+
+            assert_eq!(json!(DID_KEY_MY1), did_doc["id"]);
+            assert_eq!(json!(VERKEY_MY1), did_doc["verificationMethod"][0]["publicKeyBase58"]);
+        }
+
+        #[test]
+        fn indy_build_did_doc_works_for_endpoint_from_ledger() {
+            let setup = Setup::new_identity();
+
+            let attrib_data = json!({"endpoint": {"endpoint": "https://agent.example.com", "routingKeys": [VERKEY_TRUSTEE], "types": ["endpoint", "did-communication"]}}).to_string();
+            let attrib_request = ledger::build_attrib_request(&setup.did, &setup.did, None, Some(&attrib_data), None).unwrap();
+            ledger::sign_and_submit_request(setup.pool_handle, setup.wallet_handle, &setup.did, &attrib_request).unwrap();
+
+            thread::sleep(std::time::Duration::from_secs(1));
+
+            did::get_endpoint_for_did(setup.wallet_handle, setup.pool_handle, &setup.did).unwrap();
+
+            let did_doc = did::build_did_doc(setup.wallet_handle, &setup.did).unwrap();
+            let did_doc: serde_json::Value = serde_json::from_str(&did_doc).unwrap();
+
+            assert_eq!(json!("endpoint"), did_doc["service"][0]["type"]);
+            assert_eq!(json!("https://agent.example.com"), did_doc["service"][0]["serviceEndpoint"]);
+            assert_eq!(json!("did-communication"), did_doc["service"][1]["type"]);
+            assert_eq!(json!([VERKEY_TRUSTEE]), did_doc["service"][1]["routingKeys"]);
+        }
+
+        #[test]
+        fn indy_build_did_doc_works_for_unknown_did() {
+            let setup = Setup::wallet();
+
+            let res = did::build_did_doc(setup.wallet_handle, DID);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+    }
+
     mod set_did_metadata {
         use super::*;
 
@@ -457,6 +583,42 @@ mod high_cases {
             let res = did::create_my_did(setup.wallet_handle, &json!({"did": did}).to_string());
             assert_code!(ErrorCode::DidAlreadyExistsError, res);
         }
+
+        #[test]
+        fn indy_create_my_did_works_for_did_key() {
+            let setup = Setup::wallet();
+
+            let my_did_json = json!({"method_name": "key", "seed": MY1_SEED}).to_string();
+            let (my_did, my_verkey) = did::create_my_did(setup.wallet_handle, &my_did_json).unwrap();
+
+            assert_eq!(DID_KEY_MY1, my_did);
+            assert_eq!(VERKEY_MY1, my_verkey);
+            assert_eq!(VERKEY_MY1, did::key_for_local_did(setup.wallet_handle, &my_did).unwrap());
+        }
+
+        #[test]
+        fn indy_create_my_did_works_for_did_peer_numalgo_0() {
+            let setup = Setup::wallet();
+
+            let my_did_json = json!({"method_name": "peer", "seed": MY1_SEED}).to_string();
+            let (my_did, my_verkey) = did::create_my_did(setup.wallet_handle, &my_did_json).unwrap();
+
+            assert_eq!(DID_PEER_0_MY1, my_did);
+            assert_eq!(VERKEY_MY1, my_verkey);
+        }
+
+        #[test]
+        fn indy_create_my_did_works_for_did_peer_numalgo_2() {
+            let setup = Setup::wallet();
+
+            let my_did_json = json!({"method_name": "peer", "peer_numalgo": 2, "seed": MY1_SEED}).to_string();
+            let (my_did, my_verkey) = did::create_my_did(setup.wallet_handle, &my_did_json).unwrap();
+
+            assert!(my_did.starts_with("did:peer:2.Ez6LS"));
+            assert!(my_did.ends_with(&format!(".V{}", &DID_KEY_MY1["did:key:".len()..])));
+            assert_eq!(VERKEY_MY1, my_verkey);
+            assert_eq!(VERKEY_MY1, did::key_for_local_did(setup.wallet_handle, &my_did).unwrap());
+        }
     }
 
     mod replace_keys_start {
@@ -545,6 +707,207 @@ mod high_cases {
         }
     }
 
+    mod replace_keys_rollback {
+        use super::*;
+
+        #[test]
+        fn indy_replace_keys_rollback_works() {
+            let setup = Setup::did();
+
+            let new_verkey = did::replace_keys_start(setup.wallet_handle, &setup.did, "{}").unwrap();
+            did::replace_keys_apply(setup.wallet_handle, &setup.did).unwrap();
+
+            let verkey = did::replace_keys_rollback(setup.wallet_handle, &setup.did).unwrap();
+            assert_eq!(setup.verkey, verkey);
+            assert_eq!(setup.verkey, did::key_for_local_did(setup.wallet_handle, &setup.did).unwrap());
+
+            let key_history: Vec<serde_json::Value> = serde_json::from_str(&did::get_did_key_history(setup.wallet_handle, &setup.did).unwrap()).unwrap();
+            assert_eq!(2, key_history.len());
+            assert_eq!(json!(setup.verkey), key_history[0]["verkey"]);
+            assert_eq!(json!(new_verkey), key_history[1]["verkey"]);
+            assert_eq!(key_history[0]["replaced_at"], key_history[1]["activated_at"]);
+        }
+
+        #[test]
+        fn indy_replace_keys_rollback_works_for_pending_replacement() {
+            let setup = Setup::did();
+
+            did::replace_keys_start(setup.wallet_handle, &setup.did, "{}").unwrap();
+            did::replace_keys_apply(setup.wallet_handle, &setup.did).unwrap();
+
+            did::replace_keys_start(setup.wallet_handle, &setup.did, "{}").unwrap();
+
+            let verkey = did::replace_keys_rollback(setup.wallet_handle, &setup.did).unwrap();
+            assert_eq!(setup.verkey, verkey);
+
+            let res = did::replace_keys_apply(setup.wallet_handle, &setup.did);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+            assert_eq!(setup.verkey, did::key_for_local_did(setup.wallet_handle, &setup.did).unwrap());
+        }
+
+        #[test]
+        fn indy_replace_keys_rollback_works_for_second_rollback() {
+            let setup = Setup::did();
+
+            let new_verkey = did::replace_keys_start(setup.wallet_handle, &setup.did, "{}").unwrap();
+            did::replace_keys_apply(setup.wallet_handle, &setup.did).unwrap();
+            did::replace_keys_rollback(setup.wallet_handle, &setup.did).unwrap();
+
+            let res = did::replace_keys_rollback(setup.wallet_handle, &setup.did);
+            assert_code!(ErrorCode::CommonInvalidState, res);
+            assert_eq!(setup.verkey, did::key_for_local_did(setup.wallet_handle, &setup.did).unwrap());
+
+            let key_history: Vec<serde_json::Value> = serde_json::from_str(&did::get_did_key_history(setup.wallet_handle, &setup.did).unwrap()).unwrap();
+            assert_eq!(2, key_history.len());
+            assert_eq!(json!(setup.verkey), key_history[0]["verkey"]);
+            assert_eq!(json!(new_verkey), key_history[1]["verkey"]);
+            assert_eq!(key_history[0]["replaced_at"], key_history[1]["activated_at"]);
+
+            // A new key change can be rolled back again
+            let next_verkey = did::replace_keys_start(setup.wallet_handle, &setup.did, "{}").unwrap();
+            did::replace_keys_apply(setup.wallet_handle, &setup.did).unwrap();
+
+            let verkey = did::replace_keys_rollback(setup.wallet_handle, &setup.did).unwrap();
+            assert_eq!(setup.verkey, verkey);
+
+            let key_history: Vec<serde_json::Value> = serde_json::from_str(&did::get_did_key_history(setup.wallet_handle, &setup.did).unwrap()).unwrap();
+            assert_eq!(4, key_history.len());
+            assert_eq!(json!(setup.verkey), key_history[2]["verkey"]);
+            assert_eq!(json!(next_verkey), key_history[3]["verkey"]);
+            assert_eq!(key_history[2]["replaced_at"], key_history[3]["activated_at"]);
+        }
+
+        #[test]
+        fn indy_replace_keys_rollback_works_for_no_previous_keys() {
+            let setup = Setup::did();
+
+            let res = did::replace_keys_rollback(setup.wallet_handle, &setup.did);
+            assert_code!(ErrorCode::CommonInvalidState, res);
+        }
+
+        #[test]
+        fn indy_replace_keys_rollback_works_for_unknown_did() {
+            let setup = Setup::wallet();
+
+            let res = did::replace_keys_rollback(setup.wallet_handle, DID);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+    }
+
+    mod get_did_key_history {
+        use super::*;
+
+        #[test]
+        fn indy_get_did_key_history_works() {
+            let setup = Setup::did();
+
+            let new_verkey = did::replace_keys_start(setup.wallet_handle, &setup.did, "{}").unwrap();
+            did::replace_keys_apply(setup.wallet_handle, &setup.did).unwrap();
+
+            did::replace_keys_start(setup.wallet_handle, &setup.did, "{}").unwrap();
+            did::replace_keys_apply(setup.wallet_handle, &setup.did).unwrap();
+
+            let key_history: Vec<serde_json::Value> = serde_json::from_str(&did::get_did_key_history(setup.wallet_handle, &setup.did).unwrap()).unwrap();
+            assert_eq!(2, key_history.len());
+            assert_eq!(json!(setup.verkey), key_history[0]["verkey"]);
+            assert_eq!(json!(new_verkey), key_history[1]["verkey"]);
+            assert!(key_history[0]["replaced_at"].as_u64().unwrap() <= key_history[1]["replaced_at"].as_u64().unwrap());
+        }
+
+        #[test]
+        fn indy_get_did_key_history_works_for_no_replaced_keys() {
+            let setup = Setup::did();
+
+            let key_history = did::get_did_key_history(setup.wallet_handle, &setup.did).unwrap();
+            assert_eq!("[]", key_history);
+        }
+
+        #[test]
+        fn indy_get_did_key_history_works_after_qualify_did() {
+            let setup = Setup::did();
+
+            did::replace_keys_start(setup.wallet_handle, &setup.did, "{}").unwrap();
+            did::replace_keys_apply(setup.wallet_handle, &setup.did).unwrap();
+
+            let full_qualified_did = did::qualify_did(setup.wallet_handle, &setup.did, DEFAULT_METHOD_NAME).unwrap();
+
+            let key_history: Vec<serde_json::Value> = serde_json::from_str(&did::get_did_key_history(setup.wallet_handle, &full_qualified_did).unwrap()).unwrap();
+            assert_eq!(1, key_history.len());
+            assert_eq!(json!(setup.verkey), key_history[0]["verkey"]);
+        }
+
+        #[test]
+        fn indy_get_did_key_history_works_for_unknown_did() {
+            let setup = Setup::wallet();
+
+            let res = did::get_did_key_history(setup.wallet_handle, DID);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+    }
+
+    mod verify_with_did_key_history {
+        use super::*;
+
+        const FUTURE_TIMESTAMP: u64 = 4102444800;
+
+        #[test]
+        fn indy_verify_with_did_key_history_works_for_replaced_key() {
+            let setup = Setup::did();
+
+            let signature = crypto::sign(setup.wallet_handle, &setup.verkey, MESSAGE.as_bytes()).unwrap();
+
+            did::replace_keys_start(setup.wallet_handle, &setup.did, "{}").unwrap();
+            did::replace_keys_apply(setup.wallet_handle, &setup.did).unwrap();
+
+            assert!(did::verify_with_did_key_history(setup.wallet_handle, &setup.did, MESSAGE.as_bytes(), &signature, None).unwrap());
+            assert!(did::verify_with_did_key_history(setup.wallet_handle, &setup.did, MESSAGE.as_bytes(), &signature, Some(0)).unwrap());
+            assert!(!did::verify_with_did_key_history(setup.wallet_handle, &setup.did, MESSAGE.as_bytes(), &signature, Some(FUTURE_TIMESTAMP)).unwrap());
+        }
+
+        #[test]
+        fn indy_verify_with_did_key_history_works_for_current_key() {
+            let setup = Setup::did();
+
+            let new_verkey = did::replace_keys_start(setup.wallet_handle, &setup.did, "{}").unwrap();
+            did::replace_keys_apply(setup.wallet_handle, &setup.did).unwrap();
+
+            let signature = crypto::sign(setup.wallet_handle, &new_verkey, MESSAGE.as_bytes()).unwrap();
+
+            assert!(did::verify_with_did_key_history(setup.wallet_handle, &setup.did, MESSAGE.as_bytes(), &signature, None).unwrap());
+            assert!(did::verify_with_did_key_history(setup.wallet_handle, &setup.did, MESSAGE.as_bytes(), &signature, Some(FUTURE_TIMESTAMP)).unwrap());
+            assert!(!did::verify_with_did_key_history(setup.wallet_handle, &setup.did, MESSAGE.as_bytes(), &signature, Some(0)).unwrap());
+        }
+
+        #[test]
+        fn indy_verify_with_did_key_history_works_for_old_signature_after_rollback() {
+            let setup = Setup::did();
+
+            let signature = crypto::sign(setup.wallet_handle, &setup.verkey, MESSAGE.as_bytes()).unwrap();
+
+            let new_verkey = did::replace_keys_start(setup.wallet_handle, &setup.did, "{}").unwrap();
+            did::replace_keys_apply(setup.wallet_handle, &setup.did).unwrap();
+            did::replace_keys_rollback(setup.wallet_handle, &setup.did).unwrap();
+
+            assert!(did::verify_with_did_key_history(setup.wallet_handle, &setup.did, MESSAGE.as_bytes(), &signature, Some(0)).unwrap());
+            assert!(did::verify_with_did_key_history(setup.wallet_handle, &setup.did, MESSAGE.as_bytes(), &signature, Some(FUTURE_TIMESTAMP)).unwrap());
+
+            let new_signature = crypto::sign(setup.wallet_handle, &new_verkey, MESSAGE.as_bytes()).unwrap();
+
+            assert!(did::verify_with_did_key_history(setup.wallet_handle, &setup.did, MESSAGE.as_bytes(), &new_signature, None).unwrap());
+            assert!(!did::verify_with_did_key_history(setup.wallet_handle, &setup.did, MESSAGE.as_bytes(), &new_signature, Some(0)).unwrap());
+        }
+
+        #[test]
+        fn indy_verify_with_did_key_history_works_for_other_signer() {
+            let setup = Setup::did();
+
+            let (_, other_verkey) = did::create_and_store_my_did(setup.wallet_handle, Some(MY2_SEED)).unwrap();
+            let signature = crypto::sign(setup.wallet_handle, &other_verkey, MESSAGE.as_bytes()).unwrap();
+
+            assert!(!did::verify_with_did_key_history(setup.wallet_handle, &setup.did, MESSAGE.as_bytes(), &signature, None).unwrap());
+        }
+    }
+
     mod store_their_did {
         use super::*;
 
@@ -585,6 +948,17 @@ mod high_cases {
             let verkey = did::key_for_local_did(setup.wallet_handle, DID).unwrap();
             assert_eq!(VERKEY_TRUSTEE, verkey);
         }
+
+        #[test]
+        fn indy_store_their_did_works_for_did_key() {
+            let setup = Setup::wallet();
+
+            let identity_json = json!({"did": DID_KEY_MY1}).to_string();
+            did::store_their_did(setup.wallet_handle, &identity_json).unwrap();
+
+            let verkey = did::key_for_local_did(setup.wallet_handle, DID_KEY_MY1).unwrap();
+            assert_eq!(VERKEY_MY1, verkey);
+        }
     }
 
     mod replace_keys {
@@ -655,6 +1029,17 @@ mod high_cases {
 
         const CUSTOM_METHOD: &str = "peer";
 
+        #[test]
+        fn qualify_did_for_did_key() {
+            let setup = Setup::wallet();
+
+            let my_did_json = json!({"method_name": "key"}).to_string();
+            let (my_did, _) = did::create_my_did(setup.wallet_handle, &my_did_json).unwrap();
+
+            let res = did::qualify_did(setup.wallet_handle, &my_did, DEFAULT_METHOD_NAME);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
         #[test]
         fn qualify_did_for_appending_prefix() {
             let setup = Setup::new_identity();
@@ -772,6 +1157,14 @@ mod medium_cases {
             let res = did::key_for_did(-1, INVALID_WALLET_HANDLE, DID);
             assert_code!(ErrorCode::WalletInvalidHandle, res);
         }
+
+        #[test]
+        fn indy_key_for_did_works_for_did_key_without_ledger() {
+            let setup = Setup::wallet();
+
+            let received_verkey = did::key_for_did(INVALID_POOL_HANDLE, setup.wallet_handle, DID_KEY_MY1).unwrap();
+            assert_eq!(VERKEY_MY1, received_verkey);
+        }
     }
 
     mod key_for_local_did {
@@ -806,6 +1199,26 @@ mod medium_cases {
         }
     }
 
+    mod build_did_doc {
+        use super::*;
+
+        #[test]
+        fn indy_build_did_doc_works_for_invalid_did() {
+            let setup = Setup::wallet();
+
+            let res = did::build_did_doc(setup.wallet_handle, INVALID_BASE58_DID);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_build_did_doc_works_for_invalid_wallet_handle() {
+            Setup::empty();
+
+            let res = did::build_did_doc(INVALID_WALLET_HANDLE, DID);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+    }
+
     mod get_did_metadata {
         use super::*;
 
@@ -862,6 +1275,22 @@ mod medium_cases {
             let res = did::create_my_did(INVALID_WALLET_HANDLE, "{}");
             assert_code!(ErrorCode::WalletInvalidHandle, res);
         }
+
+        #[test]
+        fn indy_create_my_did_works_for_unsupported_peer_numalgo() {
+            let setup = Setup::wallet();
+
+            let res = did::create_my_did(setup.wallet_handle, r#"{"method_name": "peer", "peer_numalgo": 1}"#);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_create_my_did_works_for_did_key_and_passed_did() {
+            let setup = Setup::wallet();
+
+            let res = did::create_my_did(setup.wallet_handle, &json!({"method_name": "key", "did": DID}).to_string());
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
     }
 
     mod replace_keys_start {
@@ -875,6 +1304,17 @@ mod medium_cases {
             assert_code!(ErrorCode::WalletInvalidHandle, res);
         }
 
+        #[test]
+        fn indy_replace_keys_start_works_for_self_certifying_did() {
+            let setup = Setup::wallet();
+
+            let my_did_json = json!({"method_name": "key", "seed": MY1_SEED}).to_string();
+            let (my_did, _) = did::create_my_did(setup.wallet_handle, &my_did_json).unwrap();
+
+            let res = did::replace_keys_start(setup.wallet_handle, &my_did, "{}");
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
         #[test]
         fn indy_replace_keys_start_works_for_seed() {
             let setup = Setup::did();
@@ -895,6 +1335,29 @@ mod medium_cases {
             let res = did::replace_keys_apply(INVALID_WALLET_HANDLE, DID);
             assert_code!(ErrorCode::WalletInvalidHandle, res);
         }
+
+        #[test]
+        fn indy_replace_keys_apply_works_for_self_certifying_did() {
+            let setup = Setup::wallet();
+
+            let my_did_json = json!({"method_name": "peer", "seed": MY1_SEED}).to_string();
+            let (my_did, _) = did::create_my_did(setup.wallet_handle, &my_did_json).unwrap();
+
+            let res = did::replace_keys_apply(setup.wallet_handle, &my_did);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
+    mod verify_with_did_key_history {
+        use super::*;
+
+        #[test]
+        fn indy_verify_with_did_key_history_works_for_invalid_wallet_handle() {
+            Setup::empty();
+
+            let res = did::verify_with_did_key_history(INVALID_WALLET_HANDLE, DID, MESSAGE.as_bytes(), SIGNATURE, None);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
     }
 
     mod store_their_did {
@@ -908,6 +1371,15 @@ mod medium_cases {
             did::store_their_did(setup.wallet_handle, &identity_json).unwrap();
         }
 
+        #[test]
+        fn indy_store_their_did_works_for_did_key_and_other_verkey() {
+            let setup = Setup::wallet();
+
+            let identity_json = json!({"did": DID_KEY_MY1, "verkey": VERKEY}).to_string();
+            let res = did::store_their_did(setup.wallet_handle, &identity_json);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
         #[test]
         fn indy_create_my_did_works_for_invalid_seed() {
             let setup = Setup::wallet();
@@ -5,7 +5,7 @@ use std::collections::HashMap;
 use std::thread;
 
 #[cfg(feature = "local_nodes_pool")]
-use crate::utils::{anoncreds, did, ledger, pool};
+use crate::utils::{anoncreds, crypto, did, ledger, pool};
 use crate::utils::constants::*;
 use crate::utils::domain::anoncreds::credential_definition::CredentialDefinitionV1;
 use crate::utils::domain::anoncreds::revocation_registry::RevocationRegistryV1;
@@ -183,6 +183,19 @@ mod high_cases {
             assert_eq!(request["signature"].as_str().unwrap(), "65hzs4nsdQsTUqLCLy2qisbKLfwYKZSWoyh1C6CU59p5pfG3EHQXGAsjW4Qw4QdwkrvjSgQuyv8qyABcXRBznFKW");
         }
 
+        #[test]
+        fn indy_sign_request_works_for_key_manager() {
+            let setup = Setup::wallet();
+            crypto::register_key_manager(INMEM_KEY_MANAGER).unwrap();
+
+            let my_did_json = json!({"seed": TRUSTEE_SEED, "key_manager": INMEM_KEY_MANAGER}).to_string();
+            let (did, _) = did::create_my_did(setup.wallet_handle, &my_did_json).unwrap();
+
+            let request = ledger::sign_request(setup.wallet_handle, &did, REQUEST).unwrap();
+            let request: serde_json::Value = serde_json::from_str(&request).unwrap();
+            assert_eq!(request["signature"].as_str().unwrap(), "65hzs4nsdQsTUqLCLy2qisbKLfwYKZSWoyh1C6CU59p5pfG3EHQXGAsjW4Qw4QdwkrvjSgQuyv8qyABcXRBznFKW");
+        }
+
         #[test]
         fn indy_sign_request_works_for_fully_qualified() {
             let setup = Setup::trustee_fully_qualified();
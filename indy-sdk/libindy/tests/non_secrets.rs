@@ -345,6 +345,65 @@ mod high_cases {
         }
     }
 
+    mod batch_records {
+        use super::*;
+
+        fn get_record_value(wallet_handle: WalletHandle, id: &str) -> String {
+            let record = get_wallet_record(wallet_handle, TYPE, id, OPTIONS_EMPTY).unwrap();
+            let record: serde_json::Value = serde_json::from_str(&record).unwrap();
+            record["value"].as_str().unwrap().to_string()
+        }
+
+        fn check_batch_rollback(wallet_handle: WalletHandle) {
+            add_wallet_record(wallet_handle, TYPE, ID, VALUE, None).unwrap();
+
+            let operations = json!([
+                {"op": "add", "type": TYPE, "id": ID_2, "value": VALUE_2},
+                {"op": "update", "type": TYPE, "id": ID, "value": VALUE_3},
+                {"op": "add", "type": TYPE, "id": ID, "value": VALUE_4},
+            ]).to_string();
+
+            let res = wallet_batch_records(wallet_handle, &operations);
+            assert_code!(ErrorCode::WalletItemAlreadyExists, res);
+
+            assert_eq!(VALUE, get_record_value(wallet_handle, ID));
+
+            let res = get_wallet_record(wallet_handle, TYPE, ID_2, OPTIONS_EMPTY);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+
+        #[test]
+        fn indy_wallet_batch_records_works() {
+            let setup = Setup::wallet();
+
+            add_wallet_record(setup.wallet_handle, TYPE, ID, VALUE, None).unwrap();
+
+            let operations = json!([
+                {"op": "add", "type": TYPE, "id": ID_2, "value": VALUE_2, "tags": {"tagName1": "str1"}},
+                {"op": "update", "type": TYPE, "id": ID, "value": VALUE_3},
+            ]).to_string();
+
+            wallet_batch_records(setup.wallet_handle, &operations).unwrap();
+
+            assert_eq!(VALUE_3, get_record_value(setup.wallet_handle, ID));
+            assert_eq!(VALUE_2, get_record_value(setup.wallet_handle, ID_2));
+        }
+
+        #[test]
+        fn indy_wallet_batch_records_works_for_rollback() {
+            let setup = Setup::wallet();
+
+            check_batch_rollback(setup.wallet_handle);
+        }
+
+        #[test]
+        fn indy_wallet_batch_records_works_for_rollback_for_plugged_wallet() {
+            let setup = Setup::plugged_wallet();
+
+            check_batch_rollback(setup.wallet_handle);
+        }
+    }
+
     mod search {
         use super::*;
         use indy::{WalletHandle, SearchHandle};
@@ -855,6 +914,104 @@ mod high_cases {
             }
         }
     }
+
+    mod count_records {
+        use super::*;
+
+        #[test]
+        fn indy_count_wallet_records_works() {
+            const SEARCH_WALLET_CONFIG: &str = r#"{"id":"indy_count_wallet_records_works"}"#;
+            init_non_secret_test_wallet("indy_count_wallet_records_works", SEARCH_WALLET_CONFIG);
+            let wallet_handle = wallet::open_wallet(SEARCH_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let count = count_wallet_records(wallet_handle, TYPE, QUERY_EMPTY, None).unwrap();
+            let count: serde_json::Value = serde_json::from_str(&count).unwrap();
+            assert_eq!(json!({"count": 5}), count);
+
+            let count = count_wallet_records(wallet_handle, TYPE, r#"{"tagName1": "str1"}"#, None).unwrap();
+            let count: serde_json::Value = serde_json::from_str(&count).unwrap();
+            assert_eq!(json!({"count": 2}), count);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+            cleanup_wallet("indy_count_wallet_records_works");
+        }
+
+        #[test]
+        fn indy_count_wallet_records_works_for_group_by_tag() {
+            let setup = Setup::wallet();
+
+            add_wallet_record(setup.wallet_handle, TYPE, ID, VALUE, Some(r#"{"~status": "active", "tagName1": "str1"}"#)).unwrap();
+            add_wallet_record(setup.wallet_handle, TYPE, ID_2, VALUE_2, Some(r#"{"~status": "active", "tagName1": "str2"}"#)).unwrap();
+            add_wallet_record(setup.wallet_handle, TYPE, ID_3, VALUE_3, Some(r#"{"~status": "revoked", "tagName1": "str1"}"#)).unwrap();
+            add_wallet_record(setup.wallet_handle, TYPE, ID_4, VALUE_4, None).unwrap();
+
+            let count = count_wallet_records(setup.wallet_handle, TYPE, QUERY_EMPTY, Some("~status")).unwrap();
+            let count: serde_json::Value = serde_json::from_str(&count).unwrap();
+            assert_eq!(json!({"count": 4, "groups": {"active": 2, "revoked": 1}}), count);
+
+            let count = count_wallet_records(setup.wallet_handle, TYPE, r#"{"tagName1": "str1"}"#, Some("~status")).unwrap();
+            let count: serde_json::Value = serde_json::from_str(&count).unwrap();
+            assert_eq!(json!({"count": 2, "groups": {"active": 1, "revoked": 1}}), count);
+        }
+    }
+
+    mod search_page {
+        use super::*;
+
+        #[test]
+        fn indy_search_wallet_records_page_works() {
+            const SEARCH_WALLET_CONFIG: &str = r#"{"id":"indy_search_wallet_records_page_works"}"#;
+            init_non_secret_test_wallet("indy_search_wallet_records_page_works", SEARCH_WALLET_CONFIG);
+            let wallet_handle = wallet::open_wallet(SEARCH_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let mut records: Vec<WalletRecord> = Vec::new();
+            let mut cursor: Option<String> = None;
+            let mut pages = 0;
+
+            loop {
+                let page = search_wallet_records_page(wallet_handle, TYPE, QUERY_EMPTY, OPTIONS_FULL, cursor.as_ref().map(String::as_str), 2).unwrap();
+                let page: serde_json::Value = serde_json::from_str(&page).unwrap();
+                pages += 1;
+
+                assert_eq!(5, page["totalCount"].as_i64().unwrap());
+
+                let page_records: Vec<WalletRecord> = serde_json::from_value(page["records"].clone()).unwrap();
+                assert!(page_records.len() <= 2);
+                records.extend(page_records);
+
+                match page["cursor"].as_str() {
+                    Some(next) => cursor = Some(next.to_string()),
+                    None => break
+                }
+            }
+
+            assert_eq!(3, pages);
+
+            records.sort_by_key(|record| record.id.to_string());
+            assert_eq!(records, vec![record_1(), record_2(), record_3(), record_4(), record_5()]);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+            cleanup_wallet("indy_search_wallet_records_page_works");
+        }
+
+        #[test]
+        fn indy_search_wallet_records_page_works_for_query() {
+            const SEARCH_WALLET_CONFIG: &str = r#"{"id":"indy_search_wallet_records_page_works_for_query"}"#;
+            init_non_secret_test_wallet("indy_search_wallet_records_page_works_for_query", SEARCH_WALLET_CONFIG);
+            let wallet_handle = wallet::open_wallet(SEARCH_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let query_json = r#"{"tagName1": "str1"}"#;
+            let page = search_wallet_records_page(wallet_handle, TYPE, query_json, OPTIONS_FULL, None, 5).unwrap();
+
+            check_search_records(&page, vec![record_1(), record_3()]);
+
+            let page: serde_json::Value = serde_json::from_str(&page).unwrap();
+            assert!(page["cursor"].is_null());
+
+            wallet::close_wallet(wallet_handle).unwrap();
+            cleanup_wallet("indy_search_wallet_records_page_works_for_query");
+        }
+    }
 }
 
 #[cfg(not(feature = "only_high_cases"))]
@@ -1250,6 +1407,102 @@ mod medium_cases {
         }
     }
 
+    mod count_records {
+        use super::*;
+
+        #[test]
+        fn indy_count_wallet_records_works_for_no_records() {
+            let setup = Setup::wallet();
+
+            let count = count_wallet_records(setup.wallet_handle, TYPE, QUERY_EMPTY, Some("~status")).unwrap();
+            let count: serde_json::Value = serde_json::from_str(&count).unwrap();
+            assert_eq!(json!({"count": 0, "groups": {}}), count);
+        }
+
+        #[test]
+        fn indy_count_wallet_records_works_for_encrypted_group_by_tag() {
+            let setup = Setup::wallet();
+
+            add_wallet_record(setup.wallet_handle, TYPE, ID, VALUE, Some(TAGS)).unwrap();
+
+            let res = count_wallet_records(setup.wallet_handle, TYPE, QUERY_EMPTY, Some("tagName1"));
+            assert_code!(ErrorCode::WalletQueryError, res);
+        }
+
+        #[test]
+        fn indy_count_wallet_records_works_for_invalid_query() {
+            let setup = Setup::wallet();
+
+            let res = count_wallet_records(setup.wallet_handle, TYPE, "not a query", None);
+            assert_code!(ErrorCode::WalletQueryError, res);
+        }
+
+        #[test]
+        fn indy_count_wallet_records_works_for_invalid_type() {
+            let setup = Setup::wallet();
+
+            let res = count_wallet_records(setup.wallet_handle, FORBIDDEN_TYPE, QUERY_EMPTY, None);
+            assert_code!(ErrorCode::WalletAccessFailed, res);
+        }
+
+        #[test]
+        fn indy_count_wallet_records_works_for_invalid_wallet_handle() {
+            let _setup = Setup::empty();
+
+            let res = count_wallet_records(INVALID_WALLET_HANDLE, TYPE, QUERY_EMPTY, None);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+    }
+
+    mod search_page {
+        use super::*;
+
+        #[test]
+        fn indy_search_wallet_records_page_works_for_zero_limit() {
+            let setup = Setup::wallet();
+
+            let res = search_wallet_records_page(setup.wallet_handle, TYPE, QUERY_EMPTY, OPTIONS_EMPTY, None, 0);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_search_wallet_records_page_works_for_invalid_cursor() {
+            let setup = Setup::wallet();
+
+            add_wallet_record(setup.wallet_handle, TYPE, ID, VALUE, None).unwrap();
+
+            let res = search_wallet_records_page(setup.wallet_handle, TYPE, QUERY_EMPTY, OPTIONS_EMPTY, Some("invalid cursor"), 5);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_search_wallet_records_page_works_for_no_records() {
+            let setup = Setup::wallet();
+
+            let page = search_wallet_records_page(setup.wallet_handle, TYPE, QUERY_EMPTY, OPTIONS_FULL, None, 5).unwrap();
+            let page: serde_json::Value = serde_json::from_str(&page).unwrap();
+
+            assert_eq!(0, page["totalCount"].as_i64().unwrap());
+            assert!(page["cursor"].is_null());
+        }
+
+        #[test]
+        fn indy_search_wallet_records_page_works_for_invalid_type() {
+            let setup = Setup::wallet();
+
+            let res = search_wallet_records_page(setup.wallet_handle, FORBIDDEN_TYPE, QUERY_EMPTY, OPTIONS_EMPTY, None, 5);
+            assert_code!(ErrorCode::WalletAccessFailed, res);
+        }
+
+        #[test]
+        fn indy_search_wallet_records_page_works_for_invalid_wallet_handle() {
+            let _setup = Setup::empty();
+
+            let res = search_wallet_records_page(INVALID_WALLET_HANDLE, TYPE, QUERY_EMPTY, OPTIONS_EMPTY, None, 5);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+    }
+
     mod rusqlite_transaction_fix {
         use super::*;
 
@@ -10,7 +10,18 @@ use crate::utils::{did, pairwise};
 use crate::utils::constants::*;
 use crate::utils::Setup;
 
-use self::indy::ErrorCode;
+use self::indy::{ErrorCode, WalletHandle};
+
+fn _search_pairwise(wallet_handle: WalletHandle, query_json: Option<&str>) -> Vec<serde_json::Value> {
+    let search_handle = pairwise::open_pairwise_search(wallet_handle, query_json).unwrap();
+
+    let records = pairwise::fetch_pairwise_search_next_records(wallet_handle, search_handle, 100).unwrap();
+    let records: serde_json::Value = serde_json::from_str(&records).unwrap();
+
+    pairwise::close_pairwise_search(search_handle).unwrap();
+
+    records["records"].as_array().cloned().unwrap_or_default()
+}
 
 mod high_cases {
     use super::*;
@@ -56,6 +67,15 @@ mod high_cases {
             pairwise::create_pairwise(setup.wallet_handle, DID, &setup.did, Some(METADATA)).unwrap();
 
         }
+
+        #[test]
+        fn indy_create_pairwise_works_for_not_stored_did_key() {
+            let setup = Setup::did();
+
+            pairwise::create_pairwise(setup.wallet_handle, DID_KEY_MY1, &setup.did, Some(METADATA)).unwrap();
+
+            assert_eq!(VERKEY_MY1, did::key_for_local_did(setup.wallet_handle, DID_KEY_MY1).unwrap());
+        }
     }
 
     mod list_pairwise {
@@ -162,6 +182,134 @@ mod high_cases {
             assert_code!(ErrorCode::WalletItemNotFound, res);
         }
     }
+
+    mod set_pairwise_tags {
+        use super::*;
+
+        #[test]
+        fn indy_set_pairwise_tags_works() {
+            let setup = Setup::did();
+
+            did::store_their_did_from_parts(setup.wallet_handle, DID_TRUSTEE, VERKEY_TRUSTEE).unwrap();
+            pairwise::create_pairwise(setup.wallet_handle, DID_TRUSTEE, &setup.did, Some(METADATA)).unwrap();
+
+            pairwise::set_pairwise_tags(setup.wallet_handle, DID_TRUSTEE, r#"{"label": "Trustee", "~state": "active"}"#).unwrap();
+
+            let records = _search_pairwise(setup.wallet_handle, None);
+            assert_eq!(1, records.len());
+            assert_eq!(json!({"label": "Trustee", "~state": "active"}), records[0]["tags"]);
+        }
+
+        #[test]
+        fn indy_set_pairwise_tags_works_for_replace() {
+            let setup = Setup::did();
+
+            did::store_their_did_from_parts(setup.wallet_handle, DID_TRUSTEE, VERKEY_TRUSTEE).unwrap();
+            pairwise::create_pairwise(setup.wallet_handle, DID_TRUSTEE, &setup.did, None).unwrap();
+
+            pairwise::set_pairwise_tags(setup.wallet_handle, DID_TRUSTEE, r#"{"label": "Trustee", "~state": "invited"}"#).unwrap();
+            pairwise::set_pairwise_tags(setup.wallet_handle, DID_TRUSTEE, r#"{"~state": "active"}"#).unwrap();
+
+            let records = _search_pairwise(setup.wallet_handle, None);
+            assert_eq!(json!({"~state": "active"}), records[0]["tags"]);
+        }
+
+        #[test]
+        fn indy_set_pairwise_tags_works_for_not_created_pairwise() {
+            let setup = Setup::wallet();
+
+            let res = pairwise::set_pairwise_tags(setup.wallet_handle, DID_TRUSTEE, r#"{"label": "Trustee"}"#);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+    }
+
+    mod pairwise_search {
+        use super::*;
+
+        fn _create_pairwise_list(wallet_handle: WalletHandle, my_did: &str) {
+            did::store_their_did_from_parts(wallet_handle, DID_TRUSTEE, VERKEY_TRUSTEE).unwrap();
+            pairwise::create_pairwise(wallet_handle, DID_TRUSTEE, my_did, Some(METADATA)).unwrap();
+            pairwise::set_pairwise_tags(wallet_handle, DID_TRUSTEE, r#"{"label": "Trustee", "~state": "active", "~last_contact": "0001577836800"}"#).unwrap();
+
+            did::store_their_did_from_parts(wallet_handle, DID_MY2, VERKEY_MY2).unwrap();
+            pairwise::create_pairwise(wallet_handle, DID_MY2, my_did, None).unwrap();
+            pairwise::set_pairwise_tags(wallet_handle, DID_MY2, r#"{"label": "My2", "~state": "invited", "~last_contact": "0001500000000"}"#).unwrap();
+        }
+
+        #[test]
+        fn indy_pairwise_search_works_for_all() {
+            let setup = Setup::did();
+            _create_pairwise_list(setup.wallet_handle, &setup.did);
+
+            let records = _search_pairwise(setup.wallet_handle, None);
+            assert_eq!(2, records.len());
+        }
+
+        #[test]
+        fn indy_pairwise_search_works_for_tag_query() {
+            let setup = Setup::did();
+            _create_pairwise_list(setup.wallet_handle, &setup.did);
+
+            let records = _search_pairwise(setup.wallet_handle, Some(r#"{"label": "Trustee"}"#));
+            assert_eq!(1, records.len());
+            assert_eq!(json!(DID_TRUSTEE), records[0]["their_did"]);
+            assert_eq!(json!(setup.did), records[0]["my_did"]);
+            assert_eq!(json!(METADATA), records[0]["metadata"]);
+        }
+
+        #[test]
+        fn indy_pairwise_search_works_for_range_query() {
+            let setup = Setup::did();
+            _create_pairwise_list(setup.wallet_handle, &setup.did);
+
+            let records = _search_pairwise(setup.wallet_handle, Some(r#"{"~last_contact": {"$lt": "0001550000000"}}"#));
+            assert_eq!(1, records.len());
+            assert_eq!(json!(DID_MY2), records[0]["their_did"]);
+        }
+
+        #[test]
+        fn indy_pairwise_search_works_for_no_matches() {
+            let setup = Setup::did();
+            _create_pairwise_list(setup.wallet_handle, &setup.did);
+
+            let records = _search_pairwise(setup.wallet_handle, Some(r#"{"~state": "completed"}"#));
+            assert!(records.is_empty());
+        }
+
+        #[test]
+        fn indy_pairwise_search_works_for_batches() {
+            let setup = Setup::did();
+            _create_pairwise_list(setup.wallet_handle, &setup.did);
+
+            let search_handle = pairwise::open_pairwise_search(setup.wallet_handle, None).unwrap();
+
+            let records = pairwise::fetch_pairwise_search_next_records(setup.wallet_handle, search_handle, 1).unwrap();
+            let records: serde_json::Value = serde_json::from_str(&records).unwrap();
+            assert_eq!(json!(2), records["totalCount"]);
+            assert_eq!(1, records["records"].as_array().unwrap().len());
+
+            let records = pairwise::fetch_pairwise_search_next_records(setup.wallet_handle, search_handle, 1).unwrap();
+            let records: serde_json::Value = serde_json::from_str(&records).unwrap();
+            assert_eq!(1, records["records"].as_array().unwrap().len());
+
+            let records = pairwise::fetch_pairwise_search_next_records(setup.wallet_handle, search_handle, 1).unwrap();
+            let records: serde_json::Value = serde_json::from_str(&records).unwrap();
+            assert!(records["records"].is_null());
+
+            pairwise::close_pairwise_search(search_handle).unwrap();
+        }
+
+        #[test]
+        fn indy_close_pairwise_search_works() {
+            let setup = Setup::wallet();
+
+            let search_handle = pairwise::open_pairwise_search(setup.wallet_handle, None).unwrap();
+            pairwise::close_pairwise_search(search_handle).unwrap();
+
+            let res = pairwise::fetch_pairwise_search_next_records(setup.wallet_handle, search_handle, 1);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+    }
 }
 
 #[cfg(not(feature = "only_high_cases"))]
@@ -268,4 +416,70 @@ mod medium_cases {
             assert_code!(ErrorCode::WalletInvalidHandle, res);
         }
     }
-}
\ No newline at end of file
+
+    mod set_pairwise_tags {
+        use super::*;
+
+        #[test]
+        fn indy_set_pairwise_tags_works_for_invalid_tags() {
+            let setup = Setup::did();
+
+            did::store_their_did_from_parts(setup.wallet_handle, DID_TRUSTEE, VERKEY_TRUSTEE).unwrap();
+            pairwise::create_pairwise(setup.wallet_handle, DID_TRUSTEE, &setup.did, None).unwrap();
+
+            let res = pairwise::set_pairwise_tags(setup.wallet_handle, DID_TRUSTEE, r#"{"label": 1}"#);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_set_pairwise_tags_works_for_invalid_wallet_handle() {
+            Setup::empty();
+
+            let res = pairwise::set_pairwise_tags(INVALID_WALLET_HANDLE, DID_TRUSTEE, r#"{"label": "Trustee"}"#);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+    }
+
+    mod pairwise_search {
+        use super::*;
+
+        #[test]
+        fn indy_open_pairwise_search_works_for_invalid_query() {
+            let setup = Setup::wallet();
+
+            let res = pairwise::open_pairwise_search(setup.wallet_handle, Some(r#"{"label": {"$unknown": "Trustee"}}"#));
+            assert_code!(ErrorCode::WalletQueryError, res);
+        }
+
+        #[test]
+        fn indy_open_pairwise_search_works_for_invalid_wallet_handle() {
+            Setup::empty();
+
+            let res = pairwise::open_pairwise_search(INVALID_WALLET_HANDLE, None);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+
+        #[test]
+        fn indy_fetch_pairwise_search_next_records_works_for_invalid_search_handle() {
+            let setup = Setup::wallet();
+
+            let search_handle = pairwise::open_pairwise_search(setup.wallet_handle, None).unwrap();
+
+            let res = pairwise::fetch_pairwise_search_next_records(setup.wallet_handle, search_handle + 1, 1);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+
+            pairwise::close_pairwise_search(search_handle).unwrap();
+        }
+
+        #[test]
+        fn indy_close_pairwise_search_works_for_twice() {
+            let setup = Setup::wallet();
+
+            let search_handle = pairwise::open_pairwise_search(setup.wallet_handle, None).unwrap();
+            pairwise::close_pairwise_search(search_handle).unwrap();
+
+            let res = pairwise::close_pairwise_search(search_handle);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+    }
+}
@@ -80,14 +80,55 @@ pub fn issuer_create_credential(wallet_handle: WalletHandle, cred_offer_json: &s
     anoncreds::issuer_create_credential(wallet_handle, cred_offer_json, cred_req_json, cred_values_json, rev_reg_id, blob_storage_reader_handle.unwrap_or(-1)).wait() // TODO OPTIONAL blob_storage_reader_handle
 }
 
+pub fn issuer_create_credential_with_rollover(wallet_handle: WalletHandle, cred_offer_json: &str, cred_req_json: &str, cred_values_json: &str,
+                                              rev_reg_id: &str, blob_storage_reader_handle: Option<i32>) -> Result<(String, Option<String>, Option<String>, Option<String>), IndyError> {
+    anoncreds::issuer_create_credential_with_rollover(wallet_handle, cred_offer_json, cred_req_json, cred_values_json, rev_reg_id, blob_storage_reader_handle.unwrap_or(-1)).wait()
+}
+
+pub fn issuer_create_credentials_batch(wallet_handle: WalletHandle, cred_requests_json: &str,
+                                       blob_storage_reader_handle: Option<i32>) -> Result<(String, Option<String>, Option<String>), IndyError> {
+    anoncreds::issuer_create_credentials_batch(wallet_handle, cred_requests_json, blob_storage_reader_handle.unwrap_or(-1)).wait()
+}
+
 pub fn issuer_revoke_credential(wallet_handle: WalletHandle, blob_storage_reader_handle: i32, rev_reg_id: &str, cred_revoc_id: &str) -> Result<String, IndyError> {
     anoncreds::issuer_revoke_credential(wallet_handle, blob_storage_reader_handle, rev_reg_id, cred_revoc_id).wait()
 }
 
+pub fn issuer_revoke_credentials(wallet_handle: WalletHandle, blob_storage_reader_handle: i32, rev_reg_id: &str, revoked_cred_revoc_ids_json: &str, recovered_cred_revoc_ids_json: Option<&str>) -> Result<String, IndyError> {
+    anoncreds::issuer_revoke_credentials(wallet_handle, blob_storage_reader_handle, rev_reg_id, revoked_cred_revoc_ids_json, recovered_cred_revoc_ids_json).wait()
+}
+
 pub fn issuer_merge_revocation_registry_deltas(rev_reg_delta: &str, other_rev_reg_delta: &str) -> Result<String, IndyError> {
     anoncreds::issuer_merge_revocation_registry_deltas(rev_reg_delta, other_rev_reg_delta).wait()
 }
 
+pub fn issuer_get_next_revoc_reg(wallet_handle: WalletHandle, rev_reg_id: &str) -> Result<String, IndyError> {
+    anoncreds::issuer_get_next_revoc_reg(wallet_handle, rev_reg_id).wait()
+}
+
+pub fn issuer_set_issued_credential_metadata(wallet_handle: WalletHandle, issued_cred_id: &str, metadata_json: &str) -> Result<(), IndyError> {
+    anoncreds::issuer_set_issued_credential_metadata(wallet_handle, issued_cred_id, metadata_json).wait()
+}
+
+pub fn issuer_search_issued_credentials(wallet_handle: WalletHandle, query_json: &str) -> Result<(i32, usize), IndyError> {
+    anoncreds::issuer_search_issued_credentials(wallet_handle, Some(query_json)).wait()
+}
+
+pub fn issuer_fetch_issued_credentials(search_handle: i32, count: usize) -> Result<String, IndyError> {
+    anoncreds::issuer_fetch_issued_credentials(search_handle, count).wait()
+}
+
+pub fn issuer_close_issued_credentials_search(search_handle: i32) -> Result<(), IndyError> {
+    anoncreds::issuer_close_issued_credentials_search(search_handle).wait()
+}
+
+pub fn issuer_get_issued_credentials(wallet_handle: WalletHandle, query_json: &str) -> Vec<serde_json::Value> {
+    let (search_handle, total_count) = issuer_search_issued_credentials(wallet_handle, query_json).unwrap();
+    let issued_credentials_json = issuer_fetch_issued_credentials(search_handle, total_count).unwrap();
+    issuer_close_issued_credentials_search(search_handle).unwrap();
+    serde_json::from_str(&issued_credentials_json).unwrap()
+}
+
 pub fn prover_create_master_secret(wallet_handle: WalletHandle, master_secret_id: &str) -> Result<String, IndyError> {
     anoncreds::prover_create_master_secret(wallet_handle, Some(master_secret_id)).wait()
 }
@@ -165,6 +206,11 @@ pub fn verifier_verify_proof(proof_request_json: &str, proof_json: &str, schemas
     anoncreds::verifier_verify_proof(proof_request_json, proof_json, schemas_json, cred_defs_json, rev_reg_defs_json, rev_regs_json).wait()
 }
 
+pub fn verifier_verify_proof_with_report(proof_request_json: &str, proof_json: &str, schemas_json: &str,
+                                         cred_defs_json: &str, rev_reg_defs_json: &str, rev_regs_json: &str) -> Result<String, IndyError> {
+    anoncreds::verifier_verify_proof_with_report(proof_request_json, proof_json, schemas_json, cred_defs_json, rev_reg_defs_json, rev_regs_json).wait()
+}
+
 pub fn create_revocation_state(blob_storage_reader_handle: i32, rev_reg_def_json: &str,
                                rev_reg_delta_json: &str, timestamp: u64, cred_rev_id: &str) -> Result<String, IndyError> {
     anoncreds::create_revocation_state(blob_storage_reader_handle, rev_reg_def_json, rev_reg_delta_json, timestamp, cred_rev_id).wait()
@@ -184,11 +230,21 @@ pub fn to_unqualified(entity: &str) -> Result<String, IndyError> {
 }
 
 pub fn default_cred_def_config() -> String {
-    serde_json::to_string(&CredentialDefinitionConfig { support_revocation: false }).unwrap()
+    serde_json::to_string(&CredentialDefinitionConfig { support_revocation: false, revocation_registry_rollover: None }).unwrap()
 }
 
 pub fn revocation_cred_def_config() -> String {
-    serde_json::to_string(&CredentialDefinitionConfig { support_revocation: true }).unwrap()
+    serde_json::to_string(&CredentialDefinitionConfig { support_revocation: true, revocation_registry_rollover: None }).unwrap()
+}
+
+pub fn rollover_cred_def_config() -> String {
+    json!({
+        "support_revocation": true,
+        "revocation_registry_rollover": {
+            "tails_writer_type": "default",
+            "tails_writer_config": serde_json::from_str::<serde_json::Value>(&tails_writer_config()).unwrap()
+        }
+    }).to_string()
 }
 
 pub fn issuance_on_demand_rev_reg_config() -> String {
@@ -941,6 +997,11 @@ pub fn get_credential_for_attr_referent(credentials_json: &str, referent: &str)
     credentials_for_referent[0].cred_info.clone()
 }
 
+pub fn get_revoc_reg_delta_indices(revoc_reg_delta_json: &str, field: &str) -> HashSet<u32> {
+    let revoc_reg_delta: serde_json::Value = serde_json::from_str(revoc_reg_delta_json).unwrap();
+    serde_json::from_value(revoc_reg_delta["value"][field].clone()).unwrap()
+}
+
 pub fn get_credential_for_predicate_referent(credentials_json: &str, referent: &str) -> CredentialInfo {
     let credentials: CredentialsForProofRequest = serde_json::from_str(&credentials_json).unwrap();
     let credentials_for_referent = credentials.predicates.get(referent).unwrap();
@@ -2,6 +2,7 @@ pub const SEQ_NO: i32 = 1;
 pub const PROTOCOL_VERSION: usize = 2;
 pub const TYPE: &'static str = "default";
 pub const INMEM_TYPE: &'static str = "inmem";
+pub const INMEM_KEY_MANAGER: &'static str = "inmem_key_manager";
 pub const SIGNATURE_TYPE: &'static str = "CL";
 pub const TRUSTEE_SEED: &'static str = "000000000000000000000000Trustee1";
 pub const STEWARD_SEED: &'static str = "000000000000000000000000Steward1";
@@ -15,6 +16,8 @@ pub const DID: &'static str = "CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW";
 pub const DID_V1: &'static str = "did:sov:CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW";
 pub const DID_MY1: &'static str = "VsKV7grR1BUE29mG2Fm2kX";
 pub const DID_MY1_V1: &'static str = "did:sov:VsKV7grR1BUE29mG2Fm2kX";
+pub const DID_KEY_MY1: &'static str = "did:key:z6MkvBpZTRb7tjuUF5AkmhG1JDV928hZbg5KAQJcogvhz9ax";
+pub const DID_PEER_0_MY1: &'static str = "did:peer:0z6MkvBpZTRb7tjuUF5AkmhG1JDV928hZbg5KAQJcogvhz9ax";
 pub const DID_MY2: &'static str = "2PRyVHmkXQnQzJQKxHxnXC";
 pub const DID_TRUSTEE: &'static str = "V4SGRU86Z58d6TV7PBUe6f";
 pub const INVALID_BASE58_DID: &'static str = "invalid_base58string";
@@ -1,10 +1,57 @@
 extern crate futures;
 
-use indy::IndyError;
+use indy::{ErrorCode, IndyError};
 use indy::crypto;
 use self::futures::Future;
 
-use indy::WalletHandle;
+use indy::{WalletHandle, CommandHandle};
+
+use crate::utils::callback;
+use crate::utils::inmem_key_manager::InmemKeyManager;
+
+use std::collections::HashSet;
+use std::ffi::CString;
+use std::sync::Mutex;
+use super::libc::c_char;
+
+pub fn register_key_manager(name: &str) -> Result<(), ErrorCode> {
+    lazy_static! {
+        static ref REGISTERED_KEY_MANAGERS: Mutex<HashSet<String>> = Default::default();
+    }
+
+    let mut key_managers = REGISTERED_KEY_MANAGERS.lock().unwrap();
+
+    if key_managers.contains(name) {
+        // key manager can be registered only once per process
+        return Ok(());
+    }
+
+    let (receiver, command_handle, cb) = callback::_closure_to_cb_ec();
+
+    let name_ = CString::new(name).unwrap();
+
+    let err = unsafe {
+        indy_register_key_manager(
+            command_handle,
+            name_.as_ptr(),
+            Some(InmemKeyManager::create_key),
+            Some(InmemKeyManager::sign),
+            Some(InmemKeyManager::encrypt),
+            Some(InmemKeyManager::decrypt),
+            Some(InmemKeyManager::free_buffer),
+            cb
+        )
+    };
+
+    key_managers.insert(name.to_string());
+
+    super::results::result_to_empty(err as i32, receiver)
+}
+
+pub fn create_key_in_key_manager(wallet_handle: WalletHandle, seed: Option<&str>, key_manager: &str) -> Result<String, IndyError> {
+    let key_json = json!({"seed": seed, "key_manager": key_manager}).to_string();
+    crypto::create_key(wallet_handle, Some(&key_json)).wait()
+}
 
 pub fn create_key(wallet_handle: WalletHandle, seed: Option<&str>) -> Result<String, IndyError> {
     let key_json = json!({"seed": seed}).to_string();
@@ -27,6 +74,18 @@ pub fn verify(their_vk: &str, msg: &[u8], signature: &[u8]) -> Result<bool, Indy
     crypto::verify(their_vk, msg, signature).wait()
 }
 
+pub fn sign_jws(wallet_handle: WalletHandle, my_vk: &str, payload: &[u8], options_json: Option<&str>) -> Result<String, IndyError> {
+    crypto::sign_jws(wallet_handle, my_vk, payload, options_json).wait()
+}
+
+pub fn verify_multi(msg: &[u8], signatures_json: &str, policy_json: &str) -> Result<bool, IndyError> {
+    crypto::verify_multi(msg, signatures_json, policy_json).wait()
+}
+
+pub fn verify_jws(jws: &str, payload: Option<&[u8]>) -> Result<bool, IndyError> {
+    crypto::verify_jws(jws, payload).wait()
+}
+
 pub fn auth_crypt(wallet_handle: WalletHandle, my_vk: &str, their_vk: &str, msg: &[u8]) -> Result<Vec<u8>, IndyError> {
     crypto::auth_crypt(wallet_handle, my_vk, their_vk, msg).wait()
 }
@@ -43,10 +102,68 @@ pub fn anon_decrypt(wallet_handle: WalletHandle, my_vk: &str, encrypted_msg: &[u
     crypto::anon_decrypt(wallet_handle, my_vk, encrypted_msg).wait()
 }
 
+pub fn session_start(wallet_handle: WalletHandle, my_vk: &str, their_did: &str, their_handshake_json: Option<&str>) -> Result<(String, Option<String>), IndyError> {
+    crypto::session_start(wallet_handle, my_vk, their_did, their_handshake_json).wait()
+}
+
+pub fn session_encrypt(wallet_handle: WalletHandle, session_id: &str, msg: &[u8]) -> Result<Vec<u8>, IndyError> {
+    crypto::session_encrypt(wallet_handle, session_id, msg).wait()
+}
+
+pub fn session_decrypt(wallet_handle: WalletHandle, encrypted_msg: &[u8]) -> Result<Vec<u8>, IndyError> {
+    crypto::session_decrypt(wallet_handle, encrypted_msg).wait()
+}
+
 pub fn pack_message(wallet_handle: WalletHandle, message: &[u8], receiver_keys: &str, sender: Option<&str>) -> Result<Vec<u8>, IndyError> {
     crypto::pack_message(wallet_handle, message, receiver_keys, sender).wait()
 }
 
+pub fn pack_message_with_options(wallet_handle: WalletHandle, message: &[u8], receiver_keys: &str, sender: Option<&str>, options_json: Option<&str>) -> Result<Vec<u8>, IndyError> {
+    crypto::pack_message_with_options(wallet_handle, message, receiver_keys, sender, options_json).wait()
+}
+
 pub fn unpack_message(wallet_handle: WalletHandle, jwe: &[u8]) -> Result<Vec<u8>, IndyError> {
     crypto::unpack_message(wallet_handle, jwe).wait()
-}
\ No newline at end of file
+}
+
+extern {
+    #[no_mangle]
+    pub fn indy_register_key_manager(command_handle: CommandHandle,
+                                     name: *const c_char,
+                                     create_key: Option<KeyManagerCreateKey>,
+                                     sign: Option<KeyManagerSign>,
+                                     encrypt: Option<KeyManagerEncrypt>,
+                                     decrypt: Option<KeyManagerDecrypt>,
+                                     free_buffer: Option<KeyManagerFreeBuffer>,
+                                     cb: Option<extern fn(command_handle_: CommandHandle,
+                                                          err: ErrorCode)>) -> ErrorCode;
+}
+
+pub type KeyManagerCreateKey = extern fn(key_config: *const c_char,
+                                         verkey_p: *mut *const c_char,
+                                         buffer_handle_p: *mut i32) -> ErrorCode;
+pub type KeyManagerSign = extern fn(verkey: *const c_char,
+                                    message_raw: *const u8,
+                                    message_len: usize,
+                                    signature_raw_p: *mut *const u8,
+                                    signature_len_p: *mut usize,
+                                    buffer_handle_p: *mut i32) -> ErrorCode;
+pub type KeyManagerEncrypt = extern fn(verkey: *const c_char,
+                                       their_vk: *const c_char,
+                                       message_raw: *const u8,
+                                       message_len: usize,
+                                       nonce_raw: *const u8,
+                                       nonce_len: usize,
+                                       encrypted_raw_p: *mut *const u8,
+                                       encrypted_len_p: *mut usize,
+                                       buffer_handle_p: *mut i32) -> ErrorCode;
+pub type KeyManagerDecrypt = extern fn(verkey: *const c_char,
+                                       their_vk: *const c_char,
+                                       message_raw: *const u8,
+                                       message_len: usize,
+                                       nonce_raw: *const u8,
+                                       nonce_len: usize,
+                                       decrypted_raw_p: *mut *const u8,
+                                       decrypted_len_p: *mut usize,
+                                       buffer_handle_p: *mut i32) -> ErrorCode;
+pub type KeyManagerFreeBuffer = extern fn(buffer_handle: i32) -> ErrorCode;
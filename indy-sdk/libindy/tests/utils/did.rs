@@ -65,6 +65,18 @@ pub fn replace_keys_apply(wallet_handle: WalletHandle, did: &str) -> Result<(),
     did::replace_keys_apply(wallet_handle, did).wait()
 }
 
+pub fn replace_keys_rollback(wallet_handle: WalletHandle, did: &str) -> Result<String, IndyError> {
+    did::replace_keys_rollback(wallet_handle, did).wait()
+}
+
+pub fn get_did_key_history(wallet_handle: WalletHandle, did: &str) -> Result<String, IndyError> {
+    did::get_did_key_history(wallet_handle, did).wait()
+}
+
+pub fn verify_with_did_key_history(wallet_handle: WalletHandle, did: &str, msg: &[u8], signature: &[u8], signed_at: Option<u64>) -> Result<bool, IndyError> {
+    did::verify_with_did_key_history(wallet_handle, did, msg, signature, signed_at).wait()
+}
+
 pub fn replace_keys(pool_handle: PoolHandle, wallet_handle: WalletHandle, did: &str) -> Result<String, IndyError> {
     let verkey = did::replace_keys_start(wallet_handle, did, "{}").wait().unwrap();
 
@@ -84,6 +96,10 @@ pub fn key_for_local_did(wallet_handle: WalletHandle, did: &str) -> Result<Strin
     did::key_for_local_did(wallet_handle, did).wait()
 }
 
+pub fn build_did_doc(wallet_handle: WalletHandle, did: &str) -> Result<String, IndyError> {
+    did::build_did_doc(wallet_handle, did).wait()
+}
+
 pub fn set_endpoint_for_did(wallet_handle: WalletHandle, did: &str, address: &str, transport_key: &str) -> Result<(), IndyError> {
     did::set_endpoint_for_did(wallet_handle, did, address, transport_key).wait()
 }
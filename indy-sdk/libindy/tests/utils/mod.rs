@@ -63,6 +63,10 @@ pub(crate) use indy::ErrorCode;
 #[path = "../../indy-utils/src/inmem_wallet.rs"]
 pub mod inmem_wallet;
 
+/* inmem_key_manager.rs */
+#[path = "../../indy-utils/src/inmem_key_manager.rs"]
+pub mod inmem_key_manager;
+
 /* wql.rs */
 // WMI에 대 한 SQL로 WQL의 키워드를 정의하고 그를 이용해 세부 구현하였다.
 // WMI이란? : https://docs.microsoft.com/ko-kr/windows/win32/wmisdk/wql-sql-for-wmi
@@ -62,6 +62,10 @@ pub fn delete_wallet_record(wallet_handle: WalletHandle, type_: &str, id: &str)
     wallet::delete_wallet_record(wallet_handle, type_, id).wait()
 }
 
+pub fn wallet_batch_records(wallet_handle: WalletHandle, operations_json: &str) -> Result<(), IndyError> {
+    wallet::wallet_batch_records(wallet_handle, operations_json).wait()
+}
+
 pub fn get_wallet_record(wallet_handle: WalletHandle, type_: &str, id: &str, options_json: &str) -> Result<String, IndyError> {
     wallet::get_wallet_record(wallet_handle, type_, id, options_json).wait()
 }
@@ -70,6 +74,10 @@ pub fn open_wallet_search(wallet_handle: WalletHandle, type_: &str, query_json:
     wallet::open_wallet_search(wallet_handle, type_, query_json, options_json).wait()
 }
 
+pub fn count_wallet_records(wallet_handle: WalletHandle, type_: &str, query_json: &str, group_by_tag: Option<&str>) -> Result<String, IndyError> {
+    wallet::count_wallet_records(wallet_handle, type_, query_json, group_by_tag).wait()
+}
+
 pub fn fetch_wallet_search_next_records(wallet_handle: WalletHandle, wallet_search_handle: i32, count: usize) -> Result<String, IndyError> {
     wallet::fetch_wallet_search_next_records(wallet_handle, wallet_search_handle, count).wait()
 }
@@ -78,6 +86,10 @@ pub fn close_wallet_search(wallet_search_handle: i32) -> Result<(), IndyError> {
     wallet::close_wallet_search(wallet_search_handle).wait()
 }
 
+pub fn search_wallet_records_page(wallet_handle: WalletHandle, type_: &str, query_json: &str, options_json: &str, cursor: Option<&str>, limit: u32) -> Result<String, IndyError> {
+    wallet::search_wallet_records_page(wallet_handle, type_, query_json, options_json, cursor, limit).wait()
+}
+
 pub fn tags_1() -> HashMap<String, String> {
     serde_json::from_str(TAGS).unwrap()
 }
@@ -4,7 +4,7 @@ use indy::IndyError;
 use indy::pairwise;
 use self::futures::Future;
 
-use indy::WalletHandle;
+use indy::{WalletHandle, SearchHandle};
 
 pub fn pairwise_exists(wallet_handle: WalletHandle, their_did: &str) -> Result<bool, IndyError> {
     pairwise::is_pairwise_exists(wallet_handle, their_did).wait()
@@ -24,4 +24,20 @@ pub fn get_pairwise(wallet_handle: WalletHandle, their_did: &str) -> Result<Stri
 
 pub fn set_pairwise_metadata(wallet_handle: WalletHandle, their_did: &str, metadata: Option<&str>) -> Result<(), IndyError> {
     pairwise::set_pairwise_metadata(wallet_handle, their_did, metadata).wait()
-}
\ No newline at end of file
+}
+
+pub fn set_pairwise_tags(wallet_handle: WalletHandle, their_did: &str, tags_json: &str) -> Result<(), IndyError> {
+    pairwise::set_pairwise_tags(wallet_handle, their_did, tags_json).wait()
+}
+
+pub fn open_pairwise_search(wallet_handle: WalletHandle, query_json: Option<&str>) -> Result<SearchHandle, IndyError> {
+    pairwise::open_pairwise_search(wallet_handle, query_json).wait()
+}
+
+pub fn fetch_pairwise_search_next_records(wallet_handle: WalletHandle, search_handle: SearchHandle, count: usize) -> Result<String, IndyError> {
+    pairwise::fetch_pairwise_search_next_records(wallet_handle, search_handle, count).wait()
+}
+
+pub fn close_pairwise_search(search_handle: SearchHandle) -> Result<(), IndyError> {
+    pairwise::close_pairwise_search(search_handle).wait()
+}
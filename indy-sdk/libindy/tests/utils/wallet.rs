@@ -111,6 +111,10 @@ pub fn delete_wallet(config: &str, credentials: &str) -> Result<(), IndyError> {
     wallet::delete_wallet(config, credentials).wait()
 }
 
+pub fn check_wallet_migrations(config: &str, credentials: &str) -> Result<String, IndyError> {
+    wallet::check_wallet_migrations(config, credentials).wait()
+}
+
 pub fn close_wallet(wallet_handle: WalletHandle) -> Result<(), IndyError> {
     wallet::close_wallet(wallet_handle).wait()
 }
@@ -128,6 +132,18 @@ pub fn import_wallet(config: &str, credentials: &str, import_config: &str) -> Re
     wallet::import_wallet(config, credentials, import_config).wait()
 }
 
+pub fn export_wallet_with_progress(wallet_handle: WalletHandle, export_config_json: &str, progress_cb: Option<indy_sys::wallet::WalletProgressCB>) -> Result<(), IndyError> {
+    wallet::export_wallet_with_progress(wallet_handle, export_config_json, progress_cb).wait()
+}
+
+pub fn import_wallet_with_progress(config: &str, credentials: &str, import_config: &str, progress_cb: Option<indy_sys::wallet::WalletProgressCB>) -> Result<(), IndyError> {
+    wallet::import_wallet_with_progress(config, credentials, import_config, progress_cb).wait()
+}
+
+pub fn rotate_wallet_keys(wallet_handle: WalletHandle, credentials: &str) -> Result<(), IndyError> {
+    wallet::rotate_wallet_keys(wallet_handle, credentials).wait()
+}
+
 pub fn export_wallet_path(name: &str) -> PathBuf {
     environment::tmp_file_path(name)
 }
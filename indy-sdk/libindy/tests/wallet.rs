@@ -283,6 +283,139 @@ mod high_cases {
         }
     }
 
+    mod import_wallet_with_progress {
+        use super::*;
+        use crate::utils::non_secrets;
+
+        // Cancels import on the first progress report made after 100 imported records
+        extern fn cancel_cb(_command_handle: i32, _records_count: u32) -> i32 {
+            ErrorCode::CommonInvalidState as i32
+        }
+
+        #[test]
+        fn indy_import_wallet_with_progress_works_for_resume() {
+            let setup = Setup::empty();
+            let config = config(&setup.name);
+
+            let path = wallet::export_wallet_path(&setup.name);
+            let config_json = wallet::prepare_export_wallet_config(&path);
+
+            let (wallet_handle, wallet_config) = wallet::create_and_open_default_wallet(&setup.name).unwrap();
+
+            for i in 0..101 {
+                non_secrets::add_wallet_record(wallet_handle, non_secrets::TYPE, &format!("id{}", i), non_secrets::VALUE, None).unwrap();
+            }
+
+            cleanup_file(&path);
+            wallet::export_wallet(wallet_handle, &config_json).unwrap();
+            wallet::close_and_delete_wallet(wallet_handle, &wallet_config).unwrap();
+
+            let res = wallet::import_wallet_with_progress(&config, WALLET_CREDENTIALS, &config_json, Some(cancel_cb));
+            assert_code!(ErrorCode::CommonInvalidState, res);
+
+            // Cancelled import keeps the wallet with already imported records
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            non_secrets::get_wallet_record(wallet_handle, non_secrets::TYPE, "id99", "{}").unwrap();
+            let res = non_secrets::get_wallet_record(wallet_handle, non_secrets::TYPE, "id100", "{}");
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+            wallet::close_wallet(wallet_handle).unwrap();
+
+            let res = wallet::import_wallet(&config, WALLET_CREDENTIALS, &config_json);
+            assert_code!(ErrorCode::WalletAlreadyExistsError, res);
+
+            let resume_config_json = json!({
+                "path": path.to_str().unwrap(),
+                "key": "export_key",
+                "resume": true,
+            }).to_string();
+
+            wallet::import_wallet_with_progress(&config, WALLET_CREDENTIALS, &resume_config_json, None).unwrap();
+
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            for i in 0..101 {
+                non_secrets::get_wallet_record(wallet_handle, non_secrets::TYPE, &format!("id{}", i), "{}").unwrap();
+            }
+
+            wallet::close_and_delete_wallet(wallet_handle, &config).unwrap();
+            cleanup_file(&path);
+        }
+    }
+
+    mod rotate_wallet_keys {
+        use super::*;
+
+        #[test]
+        fn indy_rotate_wallet_keys_works() {
+            let setup = Setup::wallet();
+
+            let (did, verkey) = did::create_my_did(setup.wallet_handle, "{}").unwrap();
+            did::set_did_metadata(setup.wallet_handle, &did, METADATA).unwrap();
+
+            wallet::rotate_wallet_keys(setup.wallet_handle, WALLET_CREDENTIALS).unwrap();
+
+            assert_eq!(verkey, did::key_for_local_did(setup.wallet_handle, &did).unwrap());
+            assert_eq!(METADATA, did::get_did_metadata(setup.wallet_handle, &did).unwrap());
+        }
+
+        #[test]
+        fn indy_rotate_wallet_keys_works_for_reopened_wallet() {
+            let setup = Setup::empty();
+            let config = config(&setup.name);
+
+            wallet::create_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+
+            let (did, verkey) = did::create_my_did(wallet_handle, "{}").unwrap();
+
+            wallet::rotate_wallet_keys(wallet_handle, WALLET_CREDENTIALS).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+
+            assert_eq!(verkey, did::key_for_local_did(wallet_handle, &did).unwrap());
+
+            wallet::close_and_delete_wallet(wallet_handle, &config).unwrap();
+        }
+    }
+
+    mod check_wallet_migrations {
+        use super::*;
+
+        #[test]
+        fn indy_check_wallet_migrations_works() {
+            let setup = Setup::empty();
+            let config = config(&setup.name);
+
+            wallet::create_wallet(&config, WALLET_CREDENTIALS).unwrap();
+
+            let migrations = wallet::check_wallet_migrations(&config, WALLET_CREDENTIALS).unwrap();
+            let migrations: serde_json::Value = serde_json::from_str(&migrations).unwrap();
+            assert_eq!(json!([]), migrations);
+
+            wallet::delete_wallet(&config, WALLET_CREDENTIALS).unwrap();
+        }
+
+        #[test]
+        fn indy_check_wallet_migrations_works_for_opened() {
+            let setup = Setup::wallet();
+
+            did::create_my_did(setup.wallet_handle, "{}").unwrap();
+
+            let migrations = wallet::check_wallet_migrations(&setup.wallet_config, WALLET_CREDENTIALS).unwrap();
+            let migrations: serde_json::Value = serde_json::from_str(&migrations).unwrap();
+            assert_eq!(json!([]), migrations);
+        }
+
+        #[test]
+        fn indy_check_wallet_migrations_works_for_plugged() {
+            let setup = Setup::plugged_wallet();
+
+            let migrations = wallet::check_wallet_migrations(&setup.wallet_config, WALLET_CREDENTIALS).unwrap();
+            let migrations: serde_json::Value = serde_json::from_str(&migrations).unwrap();
+            assert_eq!(json!([]), migrations);
+        }
+    }
+
     mod generate_wallet_key {
         use super::*;
         use rust_base58::FromBase58;
@@ -635,6 +768,62 @@ mod medium_cases {
         }
     }
 
+    mod check_wallet_migrations {
+        use super::*;
+
+        #[test]
+        fn indy_check_wallet_migrations_works_for_not_created_wallet() {
+            let setup = Setup::empty();
+            let config = config(&setup.name);
+
+            let res = wallet::check_wallet_migrations(&config, WALLET_CREDENTIALS);
+            assert_code!(ErrorCode::WalletNotFoundError, res);
+        }
+
+        #[test]
+        fn indy_check_wallet_migrations_works_for_invalid_config() {
+            Setup::empty();
+
+            let res = wallet::check_wallet_migrations(r#"{"field":"value"}"#, WALLET_CREDENTIALS);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
+    mod rotate_wallet_keys {
+        use super::*;
+
+        #[test]
+        fn indy_rotate_wallet_keys_works_for_other_key() {
+            let setup = Setup::wallet();
+
+            let (did, verkey) = did::create_my_did(setup.wallet_handle, "{}").unwrap();
+
+            let other_key = wallet::generate_wallet_key(None).unwrap();
+            let credentials = json!({"key": other_key, "key_derivation_method": "RAW"}).to_string();
+
+            let res = wallet::rotate_wallet_keys(setup.wallet_handle, &credentials);
+            assert_code!(ErrorCode::WalletAccessFailed, res);
+
+            assert_eq!(verkey, did::key_for_local_did(setup.wallet_handle, &did).unwrap());
+        }
+
+        #[test]
+        fn indy_rotate_wallet_keys_works_for_invalid_handle() {
+            Setup::empty();
+
+            let res = wallet::rotate_wallet_keys(INVALID_WALLET_HANDLE, WALLET_CREDENTIALS);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+
+        #[test]
+        fn indy_rotate_wallet_keys_works_for_plugged() {
+            let setup = Setup::plugged_wallet();
+
+            let res = wallet::rotate_wallet_keys(setup.wallet_handle, WALLET_CREDENTIALS);
+            assert_code!(ErrorCode::CommonInvalidState, res);
+        }
+    }
+
     mod import_wallet {
         use super::*;
 
@@ -691,6 +880,41 @@ mod medium_cases {
             let res = wallet::import_wallet(&config, WALLET_CREDENTIALS, &config_json);
             assert_code!(ErrorCode::CommonInvalidStructure, res);
 
+            let res = wallet::open_wallet(&config, WALLET_CREDENTIALS);
+            assert_code!(ErrorCode::WalletNotFoundError, res);
+
+            cleanup_file(&path);
+        }
+
+        #[test]
+        fn indy_import_wallet_works_for_retry_after_other_key() {
+            let setup = Setup::empty();
+            let config = config(&setup.name);
+
+            let path = wallet::export_wallet_path(&setup.name);
+            let config_json = wallet::prepare_export_wallet_config(&path);
+
+            let (wallet_handle, wallet_config) = wallet::create_and_open_default_wallet(&setup.name).unwrap();
+            let (did, _) = did::create_my_did(wallet_handle, "{}").unwrap();
+
+            cleanup_file(&path);
+            wallet::export_wallet(wallet_handle, &config_json).unwrap();
+            wallet::close_and_delete_wallet(wallet_handle, &wallet_config).unwrap();
+
+            let other_config_json = json!({
+                "path": path.to_str().unwrap(),
+                "key": "other_key",
+            }).to_string();
+
+            let res = wallet::import_wallet(&config, WALLET_CREDENTIALS, &other_config_json);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            wallet::import_wallet(&config, WALLET_CREDENTIALS, &config_json).unwrap();
+
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            did::key_for_local_did(wallet_handle, &did).unwrap();
+
+            wallet::close_and_delete_wallet(wallet_handle, &config).unwrap();
             cleanup_file(&path);
         }
 
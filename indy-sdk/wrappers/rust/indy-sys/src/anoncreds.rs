@@ -63,6 +63,29 @@ extern {
                                          blob_storage_reader_handle: BlobStorageReaderHandle,
                                          cb: Option<ResponseStringStringStringCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_issuer_create_credential_with_rollover(command_handle: CommandHandle,
+                                                       wallet_handle: WalletHandle,
+                                                       cred_offer_json: CString,
+                                                       cred_req_json: CString,
+                                                       cred_values_json: CString,
+                                                       rev_reg_id: CString,
+                                                       blob_storage_reader_handle: BlobStorageReaderHandle,
+                                                       cb: Option<ResponseStringStringStringStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_issuer_create_credentials_batch(command_handle: CommandHandle,
+                                                wallet_handle: WalletHandle,
+                                                cred_requests_json: CString,
+                                                blob_storage_reader_handle: BlobStorageReaderHandle,
+                                                cb: Option<ResponseStringStringStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_issuer_get_next_revoc_reg(command_handle: CommandHandle,
+                                          wallet_handle: WalletHandle,
+                                          rev_reg_id: CString,
+                                          cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_issuer_revoke_credential(command_handle: CommandHandle,
                                          wallet_handle: WalletHandle,
@@ -71,12 +94,45 @@ extern {
                                          cred_revoc_id: CString,
                                          cb: Option<ResponseStringCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_issuer_revoke_credentials(command_handle: CommandHandle,
+                                          wallet_handle: WalletHandle,
+                                          blob_storage_reader_cfg_handle: BlobStorageReaderCfgHandle,
+                                          rev_reg_id: CString,
+                                          revoked_cred_revoc_ids_json: CString,
+                                          recovered_cred_revoc_ids_json: CString,
+                                          cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_issuer_merge_revocation_registry_deltas(command_handle: CommandHandle,
                                                         rev_reg_delta_json: CString,
                                                         other_rev_reg_delta_json: CString,
                                                         cb: Option<ResponseStringCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_issuer_set_issued_credential_metadata(command_handle: CommandHandle,
+                                                      wallet_handle: WalletHandle,
+                                                      issued_cred_id: CString,
+                                                      metadata_json: CString,
+                                                      cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_issuer_search_issued_credentials(command_handle: CommandHandle,
+                                                 wallet_handle: WalletHandle,
+                                                 query_json: CString,
+                                                 cb: Option<ResponseI32UsizeCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_issuer_fetch_issued_credentials(command_handle: CommandHandle,
+                                                search_handle: SearchHandle,
+                                                count: usize,
+                                                cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_issuer_close_issued_credentials_search(command_handle: CommandHandle,
+                                                       search_handle: SearchHandle,
+                                                       cb: Option<ResponseEmptyCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_prover_create_master_secret(command_handle: CommandHandle,
                                             wallet_handle: WalletHandle,
@@ -197,6 +253,16 @@ extern {
                                       rev_regs_json: CString,
                                       cb: Option<ResponseBoolCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_verifier_verify_proof_with_report(command_handle: CommandHandle,
+                                                  proof_request_json: CString,
+                                                  proof_json: CString,
+                                                  schemas_json: CString,
+                                                  credential_defs_json: CString,
+                                                  rev_reg_defs_json: CString,
+                                                  rev_regs_json: CString,
+                                                  cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_create_revocation_state(command_handle: CommandHandle,
                                         blob_storage_reader_handle: BlobStorageReaderHandle,
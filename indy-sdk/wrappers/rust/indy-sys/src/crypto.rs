@@ -1,9 +1,19 @@
 use super::*;
 
-use {BString, CString, Error, CommandHandle, WalletHandle};
+use {BString, CString, Error, CommandHandle, WalletHandle, IndyHandle};
 
 extern {
 
+    #[no_mangle]
+    pub fn indy_register_key_manager(command_handle: CommandHandle,
+                                     name: CString,
+                                     create_key: Option<KeyManagerCreateKey>,
+                                     sign: Option<KeyManagerSign>,
+                                     encrypt: Option<KeyManagerEncrypt>,
+                                     decrypt: Option<KeyManagerDecrypt>,
+                                     free_buffer: Option<KeyManagerFreeBuffer>,
+                                     cb: Option<ResponseEmptyCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_create_key(command_handle: CommandHandle,
                            wallet_handle: WalletHandle,
@@ -40,6 +50,30 @@ extern {
                               signature_len: u32,
                               cb: Option<ResponseBoolCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_crypto_verify_multi(command_handle: CommandHandle,
+                                    message_raw: BString,
+                                    message_len: u32,
+                                    signatures_json: CString,
+                                    policy_json: CString,
+                                    cb: Option<ResponseBoolCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_crypto_sign_jws(command_handle: CommandHandle,
+                                wallet_handle: WalletHandle,
+                                signer_vk: CString,
+                                payload_raw: BString,
+                                payload_len: u32,
+                                options_json: CString,
+                                cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_crypto_verify_jws(command_handle: CommandHandle,
+                                  jws: CString,
+                                  payload_raw: BString,
+                                  payload_len: u32,
+                                  cb: Option<ResponseBoolCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_crypto_auth_crypt(command_handle: CommandHandle,
                                   wallet_handle: WalletHandle,
@@ -72,6 +106,29 @@ extern {
                                     encrypted_len: u32,
                                     cb: Option<ResponseSliceCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_crypto_session_start(command_handle: CommandHandle,
+                                     wallet_handle: WalletHandle,
+                                     my_vk: CString,
+                                     their_did: CString,
+                                     their_handshake_json: CString,
+                                     cb: Option<ResponseStringStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_crypto_session_encrypt(command_handle: CommandHandle,
+                                       wallet_handle: WalletHandle,
+                                       session_id: CString,
+                                       msg_data: BString,
+                                       msg_len: u32,
+                                       cb: Option<ResponseSliceCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_crypto_session_decrypt(command_handle: CommandHandle,
+                                       wallet_handle: WalletHandle,
+                                       encrypted_msg: BString,
+                                       encrypted_len: u32,
+                                       cb: Option<ResponseSliceCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_pack_message(command_handle: CommandHandle,
                              wallet_handle: WalletHandle,
@@ -81,6 +138,16 @@ extern {
                              sender: CString,
                              cb: Option<ResponseSliceCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_pack_message_with_options(command_handle: CommandHandle,
+                                          wallet_handle: WalletHandle,
+                                          message: BString,
+                                          message_len: u32,
+                                          receiver_keys: CString,
+                                          sender: CString,
+                                          options_json: CString,
+                                          cb: Option<ResponseSliceCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_unpack_message(command_handle: CommandHandle,
                                wallet_handle: WalletHandle,
@@ -89,3 +156,31 @@ extern {
                                cb: Option<ResponseSliceCB>) -> Error;
 }
 
+pub type KeyManagerCreateKey = extern fn(key_config: CString,
+                                         verkey_p: *mut CString,
+                                         buffer_handle_p: *mut IndyHandle) -> Error;
+pub type KeyManagerSign = extern fn(verkey: CString,
+                                    message_raw: BString,
+                                    message_len: usize,
+                                    signature_raw_p: *mut BString,
+                                    signature_len_p: *mut usize,
+                                    buffer_handle_p: *mut IndyHandle) -> Error;
+pub type KeyManagerEncrypt = extern fn(verkey: CString,
+                                       their_vk: CString,
+                                       message_raw: BString,
+                                       message_len: usize,
+                                       nonce_raw: BString,
+                                       nonce_len: usize,
+                                       encrypted_raw_p: *mut BString,
+                                       encrypted_len_p: *mut usize,
+                                       buffer_handle_p: *mut IndyHandle) -> Error;
+pub type KeyManagerDecrypt = extern fn(verkey: CString,
+                                       their_vk: CString,
+                                       message_raw: BString,
+                                       message_len: usize,
+                                       nonce_raw: BString,
+                                       nonce_len: usize,
+                                       decrypted_raw_p: *mut BString,
+                                       decrypted_len_p: *mut usize,
+                                       buffer_handle_p: *mut IndyHandle) -> Error;
+pub type KeyManagerFreeBuffer = extern fn(buffer_handle: IndyHandle) -> Error;
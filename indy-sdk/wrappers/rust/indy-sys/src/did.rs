@@ -1,6 +1,6 @@
 use super::*;
 
-use {CString, Error, CommandHandle, WalletHandle, PoolHandle};
+use {BString, CString, Error, CommandHandle, WalletHandle, PoolHandle};
 
 extern {
     #[no_mangle]
@@ -22,6 +22,29 @@ extern {
                                    did: CString,
                                    cb: Option<ResponseEmptyCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_replace_keys_rollback(command_handle: CommandHandle,
+                                      wallet_handle: WalletHandle,
+                                      did: CString,
+                                      cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_get_did_key_history(command_handle: CommandHandle,
+                                    wallet_handle: WalletHandle,
+                                    did: CString,
+                                    cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_verify_with_did_key_history(command_handle: CommandHandle,
+                                            wallet_handle: WalletHandle,
+                                            did: CString,
+                                            message_raw: BString,
+                                            message_len: u32,
+                                            signature_raw: BString,
+                                            signature_len: u32,
+                                            signed_at: i64,
+                                            cb: Option<ResponseBoolCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_store_their_did(command_handle: CommandHandle,
                                 wallet_handle: WalletHandle,
@@ -56,6 +79,12 @@ extern {
                                      did: CString,
                                      cb: Option<ResponseStringStringCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_build_did_doc(command_handle: CommandHandle,
+                              wallet_handle: WalletHandle,
+                              did: CString,
+                              cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_set_did_metadata(command_handle: CommandHandle,
                                  wallet_handle: WalletHandle,
@@ -52,6 +52,12 @@ extern {
                                      id: CString,
                                      cb: Option<ResponseEmptyCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_wallet_batch_records(command_handle: CommandHandle,
+                                     wallet_handle: WalletHandle,
+                                     operations_json: CString,
+                                     cb: Option<ResponseEmptyCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_get_wallet_record(command_handle: CommandHandle,
                                   wallet_handle: WalletHandle,
@@ -68,6 +74,14 @@ extern {
                                    options_json: CString,
                                    cb: Option<ResponseI32CB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_count_wallet_records(command_handle: CommandHandle,
+                                     wallet_handle: WalletHandle,
+                                     type_: CString,
+                                     query_json: CString,
+                                     group_by_tag: CString,
+                                     cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_fetch_wallet_search_next_records(command_handle: CommandHandle,
                                                  wallet_handle: WalletHandle,
@@ -79,4 +93,14 @@ extern {
     pub fn indy_close_wallet_search(command_handle: CommandHandle,
                                     wallet_search_handle: SearchHandle,
                                     cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_search_wallet_records_page(command_handle: CommandHandle,
+                                           wallet_handle: WalletHandle,
+                                           type_: CString,
+                                           query_json: CString,
+                                           options_json: CString,
+                                           cursor: CString,
+                                           limit: u32,
+                                           cb: Option<ResponseStringCB>) -> Error;
 }
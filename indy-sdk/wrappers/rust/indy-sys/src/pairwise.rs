@@ -1,6 +1,6 @@
 use super::*;
 
-use {CString, Error, CommandHandle, WalletHandle};
+use {CString, Error, CommandHandle, WalletHandle, SearchHandle};
 
 extern {
 
@@ -35,5 +35,29 @@ extern {
                                       their_did: CString,
                                       metadata: CString,
                                       cb: Option<ResponseEmptyCB>) -> Error;
-}
 
+    #[no_mangle]
+    pub fn indy_set_pairwise_tags(command_handle: CommandHandle,
+                                  wallet_handle: WalletHandle,
+                                  their_did: CString,
+                                  tags_json: CString,
+                                  cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_open_pairwise_search(command_handle: CommandHandle,
+                                     wallet_handle: WalletHandle,
+                                     query_json: CString,
+                                     cb: Option<ResponseI32CB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_fetch_pairwise_search_next_records(command_handle: CommandHandle,
+                                                   wallet_handle: WalletHandle,
+                                                   search_handle: SearchHandle,
+                                                   count: usize,
+                                                   cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_close_pairwise_search(command_handle: CommandHandle,
+                                      search_handle: SearchHandle,
+                                      cb: Option<ResponseEmptyCB>) -> Error;
+}
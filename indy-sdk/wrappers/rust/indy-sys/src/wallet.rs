@@ -51,6 +51,25 @@ extern {
                               export_config: CString,
                               cb: Option<ResponseEmptyCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_export_wallet_with_progress(command_handle: CommandHandle,
+                                            wallet_handle: WalletHandle,
+                                            export_config: CString,
+                                            progress_cb: Option<WalletProgressCB>,
+                                            cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_rotate_wallet_keys(command_handle: CommandHandle,
+                                   wallet_handle: WalletHandle,
+                                   credentials: CString,
+                                   cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_verify_wallet(command_handle: CommandHandle,
+                              wallet_handle: WalletHandle,
+                              repair: bool,
+                              cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_import_wallet(command_handle: CommandHandle,
                               config: CString,
@@ -58,6 +77,14 @@ extern {
                               import_config: CString,
                               cb: Option<ResponseEmptyCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_import_wallet_with_progress(command_handle: CommandHandle,
+                                            config: CString,
+                                            credentials: CString,
+                                            import_config: CString,
+                                            progress_cb: Option<WalletProgressCB>,
+                                            cb: Option<ResponseEmptyCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_close_wallet(command_handle: CommandHandle,
                              wallet_handle: WalletHandle,
@@ -69,12 +96,20 @@ extern {
                               credentials: CString,
                               cb: Option<ResponseEmptyCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_check_wallet_migrations(command_handle: CommandHandle,
+                                        config: CString,
+                                        credentials: CString,
+                                        cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_generate_wallet_key(command_handle: CommandHandle,
                                     config: CString,
                                     cb: Option<ResponseStringCB>) -> Error;
 }
 
+pub type WalletProgressCB = extern fn(xcommand_handle: CommandHandle,
+                                      records_count: u32) -> Error;
 pub type WalletCreate = extern fn(name: CString,
                                   config: CString,
                                   credentials_json: CString,
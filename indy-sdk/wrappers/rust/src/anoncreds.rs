@@ -11,6 +11,7 @@ use ffi::anoncreds;
 use ffi::{ResponseStringStringCB,
           ResponseI32UsizeCB,
           ResponseStringStringStringCB,
+          ResponseStringStringStringStringCB,
           ResponseStringCB,
           ResponseI32CB,
           ResponseEmptyCB,
@@ -91,6 +92,8 @@ fn _issuer_create_schema(command_handle: CommandHandle, issuer_did: &str, name:
 /// * `config_json`: (optional) type-specific configuration of credential definition as json:
 ///     - 'CL':
 ///         - support_revocation: whether to request non-revocation credential (optional, default false)
+///         - revocation_registry_rollover: (optional) continue issuance from the next revocation registry when one is full
+///             {"tails_writer_type": string, "tails_writer_config": object} - blob storage to store tails of created registries
 ///
 /// # Returns
 /// * `cred_def_id`: identifier of created credential definition
@@ -354,6 +357,9 @@ fn _issuer_create_credential_offer(command_handle: CommandHandle, wallet_handle:
 ///     }
 ///    If you want to use empty value for some credential field, you should set "raw" to "" and "encoded" should not be empty
 /// * `rev_reg_id`: id of revocation registry stored in the wallet
+///     With revocation registry rollover the credential is issued from the next registry when this one is full
+///     and the next registry is already created, otherwise the call fails with `AnoncredsRevocationRegistryFullError`.
+///     Use `issuer_create_credential_with_rollover` to issue with creation of the next registry.
 /// * `blob_storage_reader_handle`: configuration of blob storage reader handle that will allow to read revocation tails
 ///
 /// # Returns
@@ -406,6 +412,129 @@ fn _issuer_create_credential(
     })
 }
 
+/// Issue Credential the same way as `issuer_create_credential` and create the next revocation registry by rollover if needed.
+///
+/// Requires revocation registry rollover enabled for the credential definition.
+/// The registry the credential is issued from always has the next registry created in advance.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by open_wallet).
+/// * `cred_offer_json`: a cred offer created by create_credential_offer
+/// * `cred_req_json`: a credential request created by store_credential
+/// * `cred_values_json`: attribute values (see `issuer_create_credential`)
+/// * `rev_reg_id`: id of revocation registry stored in the wallet
+/// * `blob_storage_reader_handle`: configuration of blob storage reader handle that will allow to read revocation tails
+///
+/// # Returns
+/// * `cred_json`: Credential json containing signed credential values (see `issuer_create_credential`)
+/// * `cred_revoc_id`: local id for revocation info (Can be used for revocation of this credential)
+/// * `revoc_reg_delta_json`: Revocation registry delta json with a newly issued credential
+/// * `new_revoc_regs_json`: (Optional) revocation registries created by rollover which must be published to the ledger
+///     {"<rev_reg_id>": {"revoc_reg_def": <revocation registry definition>, "revoc_reg_entry": <revocation registry entry>}, ...}
+pub fn issuer_create_credential_with_rollover(wallet_handle: WalletHandle,
+                                              cred_offer_json: &str,
+                                              cred_req_json: &str,
+                                              cred_values_json: &str,
+                                              rev_reg_id: &str,
+                                              blob_storage_reader_handle: BlobStorageReaderHandle) -> Box<dyn Future<Item=(String, Option<String>, Option<String>, Option<String>), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_opt_string_opt_string_opt_string();
+
+    let err = _issuer_create_credential_with_rollover(command_handle, wallet_handle, cred_offer_json, cred_req_json, cred_values_json, rev_reg_id, blob_storage_reader_handle, cb);
+
+    ResultHandler::str_optstr_optstr_optstr(command_handle, err, receiver)
+}
+
+fn _issuer_create_credential_with_rollover(
+    command_handle: CommandHandle,
+    wallet_handle: WalletHandle,
+    cred_offer_json: &str,
+    cred_req_json: &str,
+    cred_values_json: &str,
+    rev_reg_id: &str,
+    blob_storage_reader_handle: BlobStorageReaderHandle,
+    cb: Option<ResponseStringStringStringStringCB>
+) -> ErrorCode {
+    let cred_offer_json = c_str!(cred_offer_json);
+    let cred_req_json = c_str!(cred_req_json);
+    let cred_values_json = c_str!(cred_values_json);
+    let rev_reg_id = c_str!(rev_reg_id);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_create_credential_with_rollover(command_handle, wallet_handle, cred_offer_json.as_ptr(), cred_req_json.as_ptr(), cred_values_json.as_ptr(), rev_reg_id.as_ptr(), blob_storage_reader_handle, cb)
+    })
+}
+
+/// Issue a batch of Credentials for the same credential definition.
+///
+/// Credential definition private key and revocation registries are loaded from the wallet only once
+/// and a single revocation registry delta per used registry is returned.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by open_wallet).
+/// * `cred_requests_json`: list of credentials to issue
+///     [
+///         {
+///             "cred_offer": <cred offer>, - a cred offer created by issuer_create_credential_offer
+///             "cred_req": <cred request>, - a credential request created by prover_create_credential_req
+///             "cred_values": <cred values>, - attribute values (see `issuer_create_credential`)
+///             "rev_reg_id": Optional<string>, - id of revocation registry stored in the wallet
+///             "metadata": Optional<object>, - metadata of the issued credential record (see `issuer_set_issued_credential_metadata`)
+///         },
+///         ...
+///     ]
+/// * `blob_storage_reader_handle`: configuration of blob storage reader handle that will allow to read revocation tails
+///
+/// # Returns
+/// * `creds_json`: issued credentials in the order of `cred_requests_json`
+///     [{"cred": <credential>, "cred_revoc_id": Optional<string>, "issued_cred_id": <id of issued credential record>}, ...]
+/// * `revoc_reg_deltas_json`: (Optional) revocation registry delta for each used revocation registry
+///     {"<rev_reg_id>": <revocation registry delta>, ...}
+/// * `new_revoc_regs_json`: (Optional) revocation registries created by rollover which must be published to the ledger
+///     {"<rev_reg_id>": {"revoc_reg_def": <revocation registry definition>, "revoc_reg_entry": <revocation registry entry>}, ...}
+pub fn issuer_create_credentials_batch(wallet_handle: WalletHandle,
+                                       cred_requests_json: &str,
+                                       blob_storage_reader_handle: BlobStorageReaderHandle) -> Box<dyn Future<Item=(String, Option<String>, Option<String>), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_opt_string_opt_string();
+
+    let err = _issuer_create_credentials_batch(command_handle, wallet_handle, cred_requests_json, blob_storage_reader_handle, cb);
+
+    ResultHandler::str_optstr_optstr(command_handle, err, receiver)
+}
+
+fn _issuer_create_credentials_batch(command_handle: CommandHandle, wallet_handle: WalletHandle, cred_requests_json: &str, blob_storage_reader_handle: BlobStorageReaderHandle, cb: Option<ResponseStringStringStringCB>) -> ErrorCode {
+    let cred_requests_json = c_str!(cred_requests_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_create_credentials_batch(command_handle, wallet_handle, cred_requests_json.as_ptr(), blob_storage_reader_handle, cb)
+    })
+}
+
+/// Get the next revocation registry created by rollover for the given one.
+/// It must be published to the ledger before it is used for issuance.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by open_wallet).
+/// * `rev_reg_id`: id of revocation registry stored in the wallet
+///
+/// # Returns
+/// * `next_revoc_reg_json`: the next revocation registry
+///     {"rev_reg_id": string, "revoc_reg_def": <revocation registry definition>, "revoc_reg_entry": <revocation registry entry>}
+pub fn issuer_get_next_revoc_reg(wallet_handle: WalletHandle, rev_reg_id: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _issuer_get_next_revoc_reg(command_handle, wallet_handle, rev_reg_id, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _issuer_get_next_revoc_reg(command_handle: CommandHandle, wallet_handle: WalletHandle, rev_reg_id: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let rev_reg_id = c_str!(rev_reg_id);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_get_next_revoc_reg(command_handle, wallet_handle, rev_reg_id.as_ptr(), cb)
+    })
+}
+
 /// Revoke a credential identified by a cred_revoc_id (returned by indy_issuer_create_credential).
 ///
 /// The corresponding credential definition and revocation registry must be already
@@ -444,6 +573,52 @@ fn _issuer_revoke_credential(command_handle: CommandHandle,
     })
 }
 
+/// Revoke and/or recover a list of credentials identified by cred_revoc_ids (returned by indy_issuer_create_credential)
+/// in one update of the revocation registry.
+///
+/// The call either applies all requested changes or none of them.
+///
+/// This call returns a single revoc registry delta as json file intended to be shared as REVOC_REG_ENTRY transaction.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+/// * `blob_storage_reader_cfg_handle`: configuration of blob storage reader handle that will allow to read revocation tails
+/// * `rev_reg_id`: id of revocation registry stored in wallet
+/// * `revoked_cred_revoc_ids_json`: json array of local ids of credentials to revoke
+/// * `recovered_cred_revoc_ids_json`: (Optional) json array of local ids of credentials to recover
+///
+/// # Returns
+/// * `revoc_reg_delta_json`: Revocation registry delta json with all revoked and recovered credentials
+pub fn issuer_revoke_credentials(wallet_handle: WalletHandle, blob_storage_reader_cfg_handle: BlobStorageReaderCfgHandle, rev_reg_id: &str, revoked_cred_revoc_ids_json: &str, recovered_cred_revoc_ids_json: Option<&str>) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _issuer_revoke_credentials(command_handle, wallet_handle, blob_storage_reader_cfg_handle, rev_reg_id, revoked_cred_revoc_ids_json, recovered_cred_revoc_ids_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _issuer_revoke_credentials(command_handle: CommandHandle,
+                              wallet_handle: WalletHandle,
+                              blob_storage_reader_cfg_handle: BlobStorageReaderCfgHandle,
+                              rev_reg_id: &str,
+                              revoked_cred_revoc_ids_json: &str,
+                              recovered_cred_revoc_ids_json: Option<&str>,
+                              cb: Option<ResponseStringCB>) -> ErrorCode {
+    let rev_reg_id = c_str!(rev_reg_id);
+    let revoked_cred_revoc_ids_json = c_str!(revoked_cred_revoc_ids_json);
+    let recovered_cred_revoc_ids_json_str = opt_c_str!(recovered_cred_revoc_ids_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_revoke_credentials(command_handle,
+                                                  wallet_handle,
+                                                  blob_storage_reader_cfg_handle,
+                                                  rev_reg_id.as_ptr(),
+                                                  revoked_cred_revoc_ids_json.as_ptr(),
+                                                  opt_c_ptr!(recovered_cred_revoc_ids_json, recovered_cred_revoc_ids_json_str),
+                                                  cb)
+    })
+}
+
 /// Merge two revocation registry deltas (returned by create_credential or revoke_credential) to accumulate common delta.
 /// Send common delta to ledger to reduce the load.
 ///
@@ -470,6 +645,114 @@ fn _issuer_merge_revocation_registry_deltas(command_handle: CommandHandle, rev_r
     })
 }
 
+/// Set holder-facing metadata for a credential issued from this wallet.
+/// Metadata replaces the previously set one.
+/// Metadata can also be set on issuance with `issuer_create_credentials_batch`.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+/// * `issued_cred_id`: id of issued credential record (referent returned by issuer_fetch_issued_credentials
+///     or "issued_cred_id" returned by issuer_create_credentials_batch)
+/// * `metadata_json`: metadata of issued credential as a flat json object
+///     {"name": "value", ...}
+pub fn issuer_set_issued_credential_metadata(wallet_handle: WalletHandle, issued_cred_id: &str, metadata_json: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _issuer_set_issued_credential_metadata(command_handle, wallet_handle, issued_cred_id, metadata_json, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _issuer_set_issued_credential_metadata(command_handle: CommandHandle, wallet_handle: WalletHandle, issued_cred_id: &str, metadata_json: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let issued_cred_id = c_str!(issued_cred_id);
+    let metadata_json = c_str!(metadata_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_set_issued_credential_metadata(command_handle, wallet_handle, issued_cred_id.as_ptr(), metadata_json.as_ptr(), cb)
+    })
+}
+
+/// Search for records of credentials issued from this wallet.
+///
+/// Instead of immediately returning of fetched records
+/// this call returns search_handle that can be used later
+/// to fetch records by small batches (with issuer_fetch_issued_credentials).
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+/// * `query_json`: Wql query filter for issued credentials searching based on tags.
+///     where query: indy-sdk/doc/design/011-wallet-query-language/README.md
+///
+/// # Returns
+/// * `search_handle`: Search handle that can be used later to fetch records by small batches (with issuer_fetch_issued_credentials)
+/// * `total_count`: Total count of records
+pub fn issuer_search_issued_credentials(wallet_handle: WalletHandle, query_json: Option<&str>) -> Box<dyn Future<Item=(SearchHandle, usize), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_handle_usize();
+
+    let err = _issuer_search_issued_credentials(command_handle, wallet_handle, query_json, cb);
+
+    ResultHandler::handle_usize(command_handle, err, receiver)
+}
+
+fn _issuer_search_issued_credentials(command_handle: CommandHandle, wallet_handle: WalletHandle, query_json: Option<&str>, cb: Option<ResponseI32UsizeCB>) -> ErrorCode {
+    let query_json_str = opt_c_str!(query_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_search_issued_credentials(command_handle, wallet_handle, opt_c_ptr!(query_json, query_json_str), cb)
+    })
+}
+
+/// Fetch next records of issued credentials for search.
+///
+/// # Arguments
+/// * `search_handle`: Search handle (created by issuer_search_issued_credentials)
+/// * `count`: Count of records to fetch
+///
+/// # Returns
+/// * `issued_credentials_json`: List of issued credential records:
+///  [{
+///     "referent": string, // id of issued credential record in the wallet
+///     "cred_def_id": string,
+///     "schema_id": string,
+///     "rev_reg_id": Optional<string>,
+///     "cred_rev_id": Optional<string>,
+///     "cred_offer_nonce": string,
+///     "prover_did": string,
+///     "revoked": bool,
+///     "metadata": {"name": "value", ...}
+///  }]
+pub fn issuer_fetch_issued_credentials(search_handle: SearchHandle, count: usize) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _issuer_fetch_issued_credentials(command_handle, search_handle, count, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _issuer_fetch_issued_credentials(command_handle: CommandHandle, search_handle: SearchHandle, count: usize, cb: Option<ResponseStringCB>) -> ErrorCode {
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_fetch_issued_credentials(command_handle, search_handle, count, cb)
+    })
+}
+
+/// Close issued credentials search (make search handle invalid)
+///
+/// # Arguments
+/// * `search_handle`: Search handle (created by issuer_search_issued_credentials)
+pub fn issuer_close_issued_credentials_search(search_handle: SearchHandle) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _issuer_close_issued_credentials_search(command_handle, search_handle, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _issuer_close_issued_credentials_search(command_handle: CommandHandle, search_handle: SearchHandle, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_close_issued_credentials_search(command_handle, search_handle, cb)
+    })
+}
+
 
 /// Creates a master secret with a given id and stores it in the wallet.
 /// The id must be unique.
@@ -1345,6 +1628,49 @@ fn _verifier_verify_proof(command_handle: CommandHandle, proof_request_json: &st
     })
 }
 
+/// Verifies a proof (of multiple credential) and reports the result of every check
+/// for each requested attribute and predicate.
+///
+/// Unlike `verifier_verify_proof` it doesn't stop at the first failed check and
+/// lists all found problems in the report instead of returning an error.
+///
+/// # Arguments
+/// * `proof_request_json`: proof request json (see `verifier_verify_proof`)
+/// * `proof_json`: created for request proof json (see `verifier_verify_proof`)
+/// * `schemas_json`: all schema jsons participating in the proof
+/// * `credential_defs_json`: all credential definitions json participating in the proof
+/// * `rev_reg_defs_json`: all revocation registry definitions json participating in the proof
+/// * `rev_regs_json`: all revocation registries json participating in the proof
+///
+/// # Returns
+/// * `report_json`: proof verification report
+///     {
+///         "valid": bool,
+///         "requested_attributes": {"<attr_referent>": {"valid": bool, "errors": [{"check": string, "reason": string}]}},
+///         "requested_predicates": {"<predicate_referent>": {"valid": bool, "errors": [{"check": string, "reason": string}]}},
+///         "errors": [{"check": string, "reason": string}] // failures not related to a single referent
+///     }
+pub fn verifier_verify_proof_with_report(proof_request_json: &str, proof_json: &str, schemas_json: &str, credential_defs_json: &str, rev_reg_defs_json: &str, rev_regs_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _verifier_verify_proof_with_report(command_handle, proof_request_json, proof_json, schemas_json, credential_defs_json, rev_reg_defs_json, rev_regs_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _verifier_verify_proof_with_report(command_handle: CommandHandle, proof_request_json: &str, proof_json: &str, schemas_json: &str, credential_defs_json: &str, rev_reg_defs_json: &str, rev_regs_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let proof_request_json = c_str!(proof_request_json);
+    let proof_json = c_str!(proof_json);
+    let schemas_json = c_str!(schemas_json);
+    let credential_defs_json = c_str!(credential_defs_json);
+    let rev_reg_defs_json = c_str!(rev_reg_defs_json);
+    let rev_regs_json = c_str!(rev_regs_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_verifier_verify_proof_with_report(command_handle, proof_request_json.as_ptr(), proof_json.as_ptr(), schemas_json.as_ptr(), credential_defs_json.as_ptr(), rev_reg_defs_json.as_ptr(), rev_regs_json.as_ptr(), cb)
+    })
+}
+
 
 /// Create revocation state for a credential that corresponds to a particular time.
 ///
@@ -3,7 +3,8 @@ use ffi::{ResponseEmptyCB,
           ResponseStringCB,
           ResponseSliceCB,
           ResponseBoolCB,
-          ResponseStringSliceCB};
+          ResponseStringSliceCB,
+          ResponseStringStringCB};
 
 use futures::Future;
 
@@ -14,6 +15,51 @@ use {ErrorCode, IndyError};
 use utils::callbacks::{ClosureHandler, ResultHandler};
 use {WalletHandle, CommandHandle};
 
+/// Registers custom key manager implementation.
+///
+/// Key manager creates and holds private keys (HSM, KMS, etc.). Wallet keeps only verkeys of such keys
+/// and delegates signing and encryption to the key manager.
+///
+/// Key manager interface doesn't provide X25519 key agreement, so key manager keys can't be used
+/// as the sender key of "ecdh" profile of pack_message_with_options (ECDH-1PU) and
+/// can't unpack messages packed with "ecdh" profile. Such calls fail with UnknownCryptoTypeError.
+/// Use "legacy" profile for these keys. Sessions only need signatures, so they work with key manager keys.
+///
+/// # Arguments
+/// * `name` - name of the key manager used in `key_manager` field of key json passed to create_key
+/// * `create_key` - "create_key" operation handler
+/// * `sign` - "sign" operation handler
+/// * `encrypt` - "encrypt" operation handler
+/// * `decrypt` - "decrypt" operation handler
+/// * `free_buffer` - "free_buffer" operation handler
+pub fn register_key_manager(name: &str,
+                            create_key: Option<crypto::KeyManagerCreateKey>,
+                            sign: Option<crypto::KeyManagerSign>,
+                            encrypt: Option<crypto::KeyManagerEncrypt>,
+                            decrypt: Option<crypto::KeyManagerDecrypt>,
+                            free_buffer: Option<crypto::KeyManagerFreeBuffer>) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _register_key_manager(command_handle, name, create_key, sign, encrypt, decrypt, free_buffer, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _register_key_manager(command_handle: CommandHandle,
+                         name: &str,
+                         create_key: Option<crypto::KeyManagerCreateKey>,
+                         sign: Option<crypto::KeyManagerSign>,
+                         encrypt: Option<crypto::KeyManagerEncrypt>,
+                         decrypt: Option<crypto::KeyManagerDecrypt>,
+                         free_buffer: Option<crypto::KeyManagerFreeBuffer>,
+                         cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let name = c_str!(name);
+
+    ErrorCode::from(unsafe {
+        crypto::indy_register_key_manager(command_handle, name.as_ptr(), create_key, sign, encrypt, decrypt, free_buffer, cb)
+    })
+}
+
 /// Creates key pair in wallet
 /// # Arguments
 /// * `wallet_handle` - wallet handle (created by Wallet::open)
@@ -24,7 +70,10 @@ use {WalletHandle, CommandHandle};
 /// {
 ///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
 ///                                Can be UTF-8, base64 or hex string.
-///     "crypto_type": string, // Optional (if not set then ed25519 curve is used); Currently only 'ed25519' value is supported for this field.
+///     "crypto_type": string, // Optional (if not set then ed25519 curve is used); Supported values: 'ed25519', 'secp256k1', 'secp256r1'.
+///                            // secp256k1 and secp256r1 keys can be used only for signing.
+///     "key_manager": string, // Optional name of key manager registered with indy_register_key_manager that
+///                            // creates and holds the private key (if not set then the key is kept in the wallet).
 /// }
 /// # Returns
 /// verkey of generated key pair, also used as key identifier
@@ -133,6 +182,109 @@ fn _verify(command_handle: CommandHandle, signer_vk: &str, message: &[u8], signa
     })
 }
 
+/// Verify a set of signatures of a message against M-of-N threshold policy.
+///
+/// The message is valid if at least `threshold` of the policy `signers` have valid signatures.
+/// Signatures made by keys that aren't in the policy aren't counted.
+///
+/// # Arguments
+/// * `message` - the data that was signed
+/// * `signatures_json` - base58 encoded signatures by verkeys of the signers:
+///   {
+///       "<verkey>": string, // signature
+///   }
+/// * `policy_json` - threshold policy:
+///   {
+///       "threshold": int, // minimal number of valid signatures (M)
+///       "signers": [string], // verkeys of the allowed signers (N)
+///       "serialization": (optional) string, // "raw" (default) or "request" for ledger requests signed as in `ledger::multi_sign_request`
+///   }
+///
+/// # Returns
+/// * `valid` - true if the policy is satisfied, false otherwise
+pub fn verify_multi(message: &[u8], signatures_json: &str, policy_json: &str) -> Box<dyn Future<Item=bool, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_bool();
+
+    let err = _verify_multi(command_handle, message, signatures_json, policy_json, cb);
+
+    ResultHandler::bool(command_handle, err, receiver)
+}
+
+fn _verify_multi(command_handle: CommandHandle, message: &[u8], signatures_json: &str, policy_json: &str, cb: Option<ResponseBoolCB>) -> ErrorCode {
+    let signatures_json = c_str!(signatures_json);
+    let policy_json = c_str!(policy_json);
+
+    ErrorCode::from(unsafe {
+        crypto::indy_crypto_verify_multi(command_handle,
+                                         message.as_ptr() as *const u8, message.len() as u32,
+                                         signatures_json.as_ptr(), policy_json.as_ptr(), cb)
+    })
+}
+
+/// Signs a payload with a key and returns JSON Web Signature (RFC 7515)
+///
+/// Signature algorithm is chosen by the key crypto type: EdDSA for ed25519, ES256K for secp256k1
+/// and ES256 for secp256r1.
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by Wallet::open)
+/// * `signer_vk` - key id or verkey of my key. The key must be created by calling create_key or Did::new
+/// * `payload` - the data to be signed
+/// * `options_json` - (optional) signing options:
+///     {
+///         "serialization": (optional) "compact" (default) or "json" (General JWS JSON Serialization),
+///         "detached": (optional) if true payload is omitted from the result. false by default,
+///         "kid": (optional) "verkey" (default) or "did:key",
+///         "protected": (optional) additional protected header parameters. "alg", "kid", "crit" and "b64" can't be set.
+///     }
+/// # Returns
+/// JWS in requested serialization
+pub fn sign_jws(wallet_handle: WalletHandle, signer_vk: &str, payload: &[u8], options_json: Option<&str>) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _sign_jws(command_handle, wallet_handle, signer_vk, payload, options_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _sign_jws(command_handle: CommandHandle, wallet_handle: WalletHandle, signer_vk: &str, payload: &[u8], options_json: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let signer_vk = c_str!(signer_vk);
+    let options_json_str = opt_c_str!(options_json);
+
+    ErrorCode::from(unsafe {
+        crypto::indy_crypto_sign_jws(command_handle, wallet_handle, signer_vk.as_ptr(),
+                                     payload.as_ptr() as *const u8, payload.len() as u32,
+                                     opt_c_ptr!(options_json, options_json_str),
+                                     cb)
+    })
+}
+
+/// Verifies JSON Web Signature (RFC 7515) in compact or JSON serialization
+///
+/// Signer key is resolved from "kid" header parameter that must contain verkey or did:key.
+/// # Arguments
+/// * `jws` - JWS to be verified
+/// * `payload` - (optional) detached payload. Must be passed only if JWS doesn't contain payload.
+/// # Returns
+/// true if all signatures are valid, false otherwise
+pub fn verify_jws(jws: &str, payload: Option<&[u8]>) -> Box<dyn Future<Item=bool, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_bool();
+
+    let err = _verify_jws(command_handle, jws, payload, cb);
+
+    ResultHandler::bool(command_handle, err, receiver)
+}
+
+fn _verify_jws(command_handle: CommandHandle, jws: &str, payload: Option<&[u8]>, cb: Option<ResponseBoolCB>) -> ErrorCode {
+    let jws = c_str!(jws);
+    let (payload_raw, payload_len) = payload
+        .map(|payload| (payload.as_ptr() as *const u8, payload.len() as u32))
+        .unwrap_or((::std::ptr::null(), 0));
+
+    ErrorCode::from(unsafe {
+        crypto::indy_crypto_verify_jws(command_handle, jws.as_ptr(), payload_raw, payload_len, cb)
+    })
+}
+
 /// **** THIS FUNCTION WILL BE DEPRECATED USE pack_message INSTEAD ****
 /// Encrypt a message by authenticated-encryption scheme.
 ///
@@ -282,6 +434,101 @@ fn _anon_decrypt(command_handle: CommandHandle, wallet_handle: WalletHandle, rec
     })
 }
 
+/// Starts an authenticated session with forward secrecy between my key and their DID (Experimental).
+///
+/// Session is established by the exchange of two handshake messages:
+///  1. initiator calls this function without `their_handshake_json` and sends the returned handshake to the responder
+///  2. responder calls this function with the handshake of the initiator and sends the returned handshake back
+///  3. initiator calls this function with the handshake of the responder to complete the session
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by Wallet::open).
+/// * `my_vk` - id (verkey) of my key.
+/// * `their_did` - DID of the other party. Its verkey must be known unless it is did:key or did:peer
+/// * `their_handshake_json` - (optional) handshake received from the other party
+///
+/// # Returns
+/// * `session_id` - id of the session to use with `session_encrypt`
+/// * `handshake_json` - handshake to send to the other party. None when the session is completed by the initiator.
+pub fn session_start(wallet_handle: WalletHandle, my_vk: &str, their_did: &str, their_handshake_json: Option<&str>) -> Box<dyn Future<Item=(String, Option<String>), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_opt_string();
+
+    let err = _session_start(command_handle, wallet_handle, my_vk, their_did, their_handshake_json, cb);
+
+    ResultHandler::str_optstr(command_handle, err, receiver)
+}
+
+fn _session_start(command_handle: CommandHandle, wallet_handle: WalletHandle, my_vk: &str, their_did: &str, their_handshake_json: Option<&str>, cb: Option<ResponseStringStringCB>) -> ErrorCode {
+    let my_vk = c_str!(my_vk);
+    let their_did = c_str!(their_did);
+    let their_handshake_json_str = opt_c_str!(their_handshake_json);
+
+    ErrorCode::from(unsafe {
+        crypto::indy_crypto_session_start(command_handle,
+                                          wallet_handle,
+                                          my_vk.as_ptr(),
+                                          their_did.as_ptr(),
+                                          opt_c_ptr!(their_handshake_json, their_handshake_json_str),
+                                          cb)
+    })
+}
+
+/// Encrypts a message by the next key of the session (Experimental).
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by Wallet::open).
+/// * `session_id` - id of the session established by `session_start`
+/// * `message` - the data to be encrypted
+///
+/// # Returns
+/// * `encrypted_message` - json of the session message as bytes
+pub fn session_encrypt(wallet_handle: WalletHandle, session_id: &str, message: &[u8]) -> Box<dyn Future<Item=Vec<u8>, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_slice();
+
+    let err = _session_encrypt(command_handle, wallet_handle, session_id, message, cb);
+
+    ResultHandler::slice(command_handle, err, receiver)
+}
+
+fn _session_encrypt(command_handle: CommandHandle, wallet_handle: WalletHandle, session_id: &str, message: &[u8], cb: Option<ResponseSliceCB>) -> ErrorCode {
+    let session_id = c_str!(session_id);
+
+    ErrorCode::from(unsafe {
+        crypto::indy_crypto_session_encrypt(command_handle,
+                                            wallet_handle,
+                                            session_id.as_ptr(),
+                                            message.as_ptr() as *const u8,
+                                            message.len() as u32,
+                                            cb)
+    })
+}
+
+/// Decrypts a message encrypted by `session_encrypt` of the other party (Experimental).
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by Wallet::open).
+/// * `encrypted_message` - the message to be decrypted
+///
+/// # Returns
+/// * `message` - decrypted message
+pub fn session_decrypt(wallet_handle: WalletHandle, encrypted_message: &[u8]) -> Box<dyn Future<Item=Vec<u8>, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_slice();
+
+    let err = _session_decrypt(command_handle, wallet_handle, encrypted_message, cb);
+
+    ResultHandler::slice(command_handle, err, receiver)
+}
+
+fn _session_decrypt(command_handle: CommandHandle, wallet_handle: WalletHandle, encrypted_message: &[u8], cb: Option<ResponseSliceCB>) -> ErrorCode {
+    ErrorCode::from(unsafe {
+        crypto::indy_crypto_session_decrypt(command_handle,
+                                            wallet_handle,
+                                            encrypted_message.as_ptr() as *const u8,
+                                            encrypted_message.len() as u32,
+                                            cb)
+    })
+}
+
 /// Unpacks a message packed using indy_pack_message which follows the wire message format HIPE
 /// (Experimental)
 ///
@@ -317,6 +564,49 @@ fn _pack_message(command_handle: CommandHandle, wallet_handle: WalletHandle, mes
 
 }
 
+/// Packs a message the same way as pack_message but allows to choose envelope profile
+/// (Experimental)
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open).
+/// * `message`: a pointer to the first byte of the message to be encrypted
+/// * `receiver_keys`: a JSON array as a string containing a list of the receivers verkey's
+/// * `sender` : a string of the sender's verkey When None is used in this parameter, anoncrypt is used
+/// * `options_json`: (optional) packing options:
+///     {
+///         "profile": string, (optional) envelope profile:
+///             "legacy" (default) - Authcrypt/Anoncrypt envelope produced by pack_message,
+///             "ecdh" - JWE with X25519 key agreement (DIDComm v2 style):
+///                 ECDH-1PU+A256KW is used if sender is set and ECDH-ES+A256KW otherwise.
+///         "enc": string, (optional) content encryption for "ecdh" profile: "A256GCM" (default) or "XC20P"
+///     }
+/// # Returns
+/// a json structure in the form of a JWE that contains the encrypted message and associated metadata
+pub fn pack_message_with_options(wallet_handle: WalletHandle, message: &[u8], receiver_keys: &str, sender: Option<&str>, options_json: Option<&str>) -> Box<dyn Future<Item=Vec<u8>, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_slice();
+
+    let err = _pack_message_with_options(command_handle, wallet_handle, message, receiver_keys, sender, options_json, cb);
+
+    ResultHandler::slice(command_handle, err, receiver)
+}
+
+fn _pack_message_with_options(command_handle: CommandHandle, wallet_handle: WalletHandle, message: &[u8], receiver_keys: &str, sender: Option<&str>, options_json: Option<&str>, cb: Option<ResponseSliceCB>) -> ErrorCode {
+    let receiver_keys = c_str!(receiver_keys);
+    let sender_str = opt_c_str!(sender);
+    let options_json_str = opt_c_str!(options_json);
+
+    ErrorCode::from(unsafe {
+        crypto::indy_pack_message_with_options(command_handle,
+                                               wallet_handle,
+                                               message.as_ptr() as *const u8,
+                                               message.len() as u32,
+                                               receiver_keys.as_ptr(),
+                                               opt_c_ptr!(sender, sender_str),
+                                               opt_c_ptr!(options_json, options_json_str),
+                                               cb)
+    })
+}
+
 /// Unpacks a message packed using indy_pack_message which follows the wire message format HIPE
 /// (Experimental)
 ///
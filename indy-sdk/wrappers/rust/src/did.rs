@@ -5,7 +5,8 @@ use std::ffi::CString;
 use futures::Future;
 
 use ffi::did;
-use ffi::{ResponseEmptyCB,
+use ffi::{ResponseBoolCB,
+          ResponseEmptyCB,
           ResponseStringCB,
           ResponseStringStringCB};
 
@@ -32,9 +33,14 @@ use {CommandHandle, WalletHandle, PoolHandle};
 ///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
 ///                                Can be UTF-8, base64 or hex string.
 ///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
-///               currently only 'ed25519' value is supported for this field)
+///               supported values: 'ed25519', 'secp256k1', 'secp256r1';
+///               secp256k1 and secp256r1 keys can be used only for signing)
 ///     "cid": bool, (optional; if not set then false is used;)
 ///     "method_name": string, (optional) method name to create fully qualified did.
+///               "key" and "peer" methods create did:key and did:peer DIDs derived from the verkey.
+///     "key_manager": string, (optional) name of key manager registered with indy_register_key_manager
+///               that creates and holds the private key (if not set then the key is kept in the wallet).
+///     "peer_numalgo": int, (optional) did:peer numalgo (0 or 2) used if "method_name" is "peer". 0 by default.
 /// }
 ///
 /// # Returns
@@ -67,7 +73,8 @@ fn _create_and_store_my_did(command_handle: CommandHandle, wallet_handle: Wallet
 ///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
 ///                                Can be UTF-8, base64 or hex string.
 ///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
-///               currently only 'ed25519' value is supported for this field)
+///               supported values: 'ed25519', 'secp256k1', 'secp256r1';
+///               secp256k1 and secp256r1 keys can be used only for signing)
 /// }
 ///
 /// # Returns
@@ -106,6 +113,93 @@ fn _replace_keys_apply(command_handle: CommandHandle, wallet_handle: WalletHandl
     ErrorCode::from(unsafe { did::indy_replace_keys_apply(command_handle, wallet_handle, tgt_did.as_ptr(), cb) })
 }
 
+/// Restores the previous key of an existing DID (owned by the caller of the library).
+///
+/// Rolls back the last key change made by `replace_keys_apply`.
+/// The rollback itself can't be rolled back: a second rollback in a row fails with `CommonInvalidState`.
+/// Replacement started by `replace_keys_start` and not applied yet is discarded.
+/// Only the wallet is changed, NYM transaction with the restored key must be sent to the ledger separately.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handler (created by Wallet::open).
+/// * `tgt_did` - DID stored in the wallet
+///
+/// # Returns
+/// * `verkey` - The restored verkey of the DID.
+pub fn replace_keys_rollback(wallet_handle: WalletHandle, tgt_did: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _replace_keys_rollback(command_handle, wallet_handle, tgt_did, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _replace_keys_rollback(command_handle: CommandHandle, wallet_handle: WalletHandle, tgt_did: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let tgt_did = c_str!(tgt_did);
+
+    ErrorCode::from(unsafe { did::indy_replace_keys_rollback(command_handle, wallet_handle, tgt_did.as_ptr(), cb) })
+}
+
+/// Returns keys of an existing DID (owned by the caller of the library) replaced by
+/// `replace_keys_apply` or `replace_keys_rollback`.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handler (created by Wallet::open).
+/// * `tgt_did` - DID stored in the wallet
+///
+/// # Returns
+/// * `key_history_json` - list of replaced keys, oldest first:
+///   [{
+///       "verkey": string, // the replaced verkey
+///       "activated_at": int, // unix timestamp (in seconds) since which the key was active, 0 if unknown
+///       "replaced_at": int, // unix timestamp (in seconds) until which the key was active
+///   }]
+pub fn get_did_key_history(wallet_handle: WalletHandle, tgt_did: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _get_did_key_history(command_handle, wallet_handle, tgt_did, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _get_did_key_history(command_handle: CommandHandle, wallet_handle: WalletHandle, tgt_did: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let tgt_did = c_str!(tgt_did);
+
+    ErrorCode::from(unsafe { did::indy_get_did_key_history(command_handle, wallet_handle, tgt_did.as_ptr(), cb) })
+}
+
+/// Verify a signature made by the current key or one of historical keys of an existing DID
+/// (owned by the caller of the library).
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handler (created by Wallet::open).
+/// * `tgt_did` - DID stored in the wallet
+/// * `message` - the data that was signed
+/// * `signature` - the signature to verify
+/// * `signed_at` - (optional) unix timestamp (in seconds) of the signing. Only the key that was active at this time is used.
+///
+/// # Returns
+/// * `valid` - true if signature is valid, false otherwise
+pub fn verify_with_did_key_history(wallet_handle: WalletHandle, tgt_did: &str, message: &[u8], signature: &[u8], signed_at: Option<u64>) -> Box<dyn Future<Item=bool, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_bool();
+
+    let err = _verify_with_did_key_history(command_handle, wallet_handle, tgt_did, message, signature, signed_at, cb);
+
+    ResultHandler::bool(command_handle, err, receiver)
+}
+
+fn _verify_with_did_key_history(command_handle: CommandHandle, wallet_handle: WalletHandle, tgt_did: &str, message: &[u8], signature: &[u8], signed_at: Option<u64>, cb: Option<ResponseBoolCB>) -> ErrorCode {
+    let tgt_did = c_str!(tgt_did);
+    let signed_at = opt_u64!(signed_at);
+
+    ErrorCode::from(unsafe {
+        did::indy_verify_with_did_key_history(command_handle, wallet_handle, tgt_did.as_ptr(),
+                                              message.as_ptr() as *const u8, message.len() as u32,
+                                              signature.as_ptr() as *const u8, signature.len() as u32,
+                                              signed_at, cb)
+    })
+}
+
 /// Saves their DID for a pairwise connection in a secured Wallet,
 /// so that it can be used to verify transaction.
 /// Updates DID associated verkey in case DID already exists in the Wallet.
@@ -120,6 +214,7 @@ fn _replace_keys_apply(command_handle: CommandHandle, wallet_handle: WalletHandl
 ///        "verkey": string
 ///             - optional is case of adding a new DID, and DID is cryptonym: did == verkey,
 ///             - mandatory in case of updating an existing DID
+///             - optional for did:key and did:peer, must match the key derived from DID if set
 ///     }
 pub fn store_their_did(wallet_handle: WalletHandle, identity_json: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
     let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
@@ -174,6 +269,9 @@ fn _key_for_did(command_handle: CommandHandle, pool_handle: PoolHandle, wallet_h
 /// "get_ver_key_did" call looks data stored in the local wallet only and skips freshness
 /// checking.
 ///
+/// Keys of did:key and did:peer (numalgo 0 and 2) DIDs are resolved from the DID itself
+/// if they aren't stored in the wallet.
+///
 /// Note if you want to get fresh data from the ledger you can use "get_ver_key" call
 /// instead.
 ///
@@ -246,6 +344,32 @@ fn _get_endpoint_for_did(command_handle: CommandHandle, wallet_handle: WalletHan
     ErrorCode::from(unsafe { did::indy_get_endpoint_for_did(command_handle, wallet_handle, pool_handle, did.as_ptr(), cb) })
 }
 
+/// Builds W3C DID Document (JSON-LD) for the given DID from the local wallet state only.
+///
+/// The document contains the DID key, X25519 key agreement key derived from ed25519 DID key
+/// and the endpoint stored by `set_endpoint_for_did` or cached from the ledger by `get_endpoint_for_did`.
+/// Unqualified DIDs are represented as "did:sov:<did>" in the document.
+///
+/// # Arguments
+/// * `wallet_handle` - Wallet handle (created by Wallet::open).
+/// * `did` - The DID to build DID Document for.
+///
+/// # Returns
+/// * `did_doc_json` - DID Document json.
+pub fn build_did_doc(wallet_handle: WalletHandle, did: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _build_did_doc(command_handle, wallet_handle, did, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _build_did_doc(command_handle: CommandHandle, wallet_handle: WalletHandle, did: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let did = c_str!(did);
+
+    ErrorCode::from(unsafe { did::indy_build_did_doc(command_handle, wallet_handle, did.as_ptr(), cb) })
+}
+
 /// Saves/replaces the meta information for the giving DID in the wallet.
 ///
 /// # Arguments
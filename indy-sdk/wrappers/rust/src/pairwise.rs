@@ -10,8 +10,9 @@ use utils::callbacks::{ClosureHandler, ResultHandler};
 use ffi::pairwise;
 use ffi::{ResponseEmptyCB,
           ResponseStringCB,
-          ResponseBoolCB};
-use {WalletHandle, CommandHandle};
+          ResponseBoolCB,
+          ResponseI32CB};
+use {WalletHandle, CommandHandle, SearchHandle};
 
 pub fn is_pairwise_exists(wallet_handle: WalletHandle, their_did: &str) -> Box<dyn Future<Item=bool, Error=IndyError>> {
     let (receiver, command_handle, cb) = ClosureHandler::cb_ec_bool();
@@ -93,3 +94,106 @@ fn _set_pairwise_metadata(command_handle: CommandHandle, wallet_handle: WalletHa
         pairwise::indy_set_pairwise_metadata(command_handle, wallet_handle, their_did.as_ptr(), opt_c_ptr!(metadata, metadata_str), cb)
     })
 }
+
+/// Replace tags of the pairwise record.
+/// Tags make pairwise searchable by open_pairwise_search (e.g. by label, relationship state or last contact time).
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handler (created by open_wallet).
+/// * `their_did` - encoded Did
+/// * `tags_json` - the new tags of the pairwise as json:
+///   {
+///     "tagName1": <str>, // string tag (will be stored encrypted)
+///     "~tagName2": <str>, // string tag (will be stored un-encrypted)
+///   }
+///   Note that only un-encrypted tags support $gt, $gte, $lt and $lte queries.
+pub fn set_pairwise_tags(wallet_handle: WalletHandle, their_did: &str, tags_json: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _set_pairwise_tags(command_handle, wallet_handle, their_did, tags_json, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _set_pairwise_tags(command_handle: CommandHandle, wallet_handle: WalletHandle, their_did: &str, tags_json: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let their_did = c_str!(their_did);
+    let tags_json = c_str!(tags_json);
+
+    ErrorCode::from(unsafe {
+        pairwise::indy_set_pairwise_tags(command_handle, wallet_handle, their_did.as_ptr(), tags_json.as_ptr(), cb)
+    })
+}
+
+/// Search for pairwise stored in the wallet.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handler (created by open_wallet).
+/// * `query_json` - (optional) Wql query filter for pairwise tags (see set_pairwise_tags)
+///
+/// # Returns
+/// * `search_handle` - Search handle that can be used later to fetch records by small batches (with fetch_pairwise_search_next_records)
+pub fn open_pairwise_search(wallet_handle: WalletHandle, query_json: Option<&str>) -> Box<dyn Future<Item=SearchHandle, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_handle();
+
+    let err = _open_pairwise_search(command_handle, wallet_handle, query_json, cb);
+
+    ResultHandler::handle(command_handle, err, receiver)
+}
+
+fn _open_pairwise_search(command_handle: CommandHandle, wallet_handle: WalletHandle, query_json: Option<&str>, cb: Option<ResponseI32CB>) -> ErrorCode {
+    let query_json_str = opt_c_str!(query_json);
+
+    ErrorCode::from(unsafe {
+        pairwise::indy_open_pairwise_search(command_handle, wallet_handle, opt_c_ptr!(query_json, query_json_str), cb)
+    })
+}
+
+/// Fetch next pairwise for pairwise search.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handler (created by open_wallet).
+/// * `search_handle` - pairwise search handle (created by open_pairwise_search)
+/// * `count` - Count of pairwise to fetch
+///
+/// # Returns
+/// * `pairwise records json` -
+/// {
+///   totalCount: <int>, // count of all pairwise matching the query
+///   records: [{ // absent if no more pairwise left
+///       my_did: "my did",
+///       their_did: "their did",
+///       metadata: "Some metadata", // present only if set
+///       tags: <tags json>,
+///   }],
+/// }
+pub fn fetch_pairwise_search_next_records(wallet_handle: WalletHandle, search_handle: SearchHandle, count: usize) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _fetch_pairwise_search_next_records(command_handle, wallet_handle, search_handle, count, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _fetch_pairwise_search_next_records(command_handle: CommandHandle, wallet_handle: WalletHandle, search_handle: SearchHandle, count: usize, cb: Option<ResponseStringCB>) -> ErrorCode {
+    ErrorCode::from(unsafe {
+        pairwise::indy_fetch_pairwise_search_next_records(command_handle, wallet_handle, search_handle, count, cb)
+    })
+}
+
+/// Close pairwise search (make search handle invalid)
+///
+/// # Arguments
+/// * `search_handle` - pairwise search handle (created by open_pairwise_search)
+pub fn close_pairwise_search(search_handle: SearchHandle) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _close_pairwise_search(command_handle, search_handle, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _close_pairwise_search(command_handle: CommandHandle, search_handle: SearchHandle, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    ErrorCode::from(unsafe {
+        pairwise::indy_close_pairwise_search(command_handle, search_handle, cb)
+    })
+}
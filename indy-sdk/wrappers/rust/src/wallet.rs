@@ -233,6 +233,99 @@ fn _export_wallet(command_handle: CommandHandle, wallet_handle: WalletHandle, ex
     })
 }
 
+/// Exports opened wallet reporting progress of the operation.
+///
+/// Export is written to temporary file "<path>.tmp" that is moved to "path" only
+/// after successful completion, so failed or cancelled export doesn't leave partial file.
+/// Existing "path" is never replaced, except on file systems without hard links (FAT, exFAT, some network shares)
+/// where a file created at "path" by someone else while the export is finalized can be overwritten.
+///
+/// # Arguments:
+/// * `wallet_handle` - wallet handle returned by indy_open_wallet
+/// * `export_config` - JSON containing settings for input operation (see export_wallet).
+/// * `progress_cb` - callback called after every 100 exported records and on completion
+///   with the number of already exported records.
+///   Returning of any error code except Success cancels the export.
+pub fn export_wallet_with_progress(wallet_handle: WalletHandle, export_config: &str, progress_cb: Option<wallet::WalletProgressCB>) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _export_wallet_with_progress(command_handle, wallet_handle, export_config, progress_cb, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _export_wallet_with_progress(command_handle: CommandHandle, wallet_handle: WalletHandle, export_config: &str, progress_cb: Option<wallet::WalletProgressCB>, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let export_config = c_str!(export_config);
+
+    ErrorCode::from(unsafe {
+      wallet::indy_export_wallet_with_progress(command_handle, wallet_handle, export_config.as_ptr(), progress_cb, cb)
+    })
+}
+
+/// Generates new encryption keys for opened wallet and re-encrypts all wallet records with them.
+///
+/// Records are re-encrypted in place in a single storage transaction.
+/// Wallet master key isn't changed (use "rekey" field of open_wallet credentials for it).
+///
+/// Note: wallet searches opened before this call become invalid and have to be reopened.
+/// Note: only 'default' storage type supports keys rotation.
+///
+/// # Arguments:
+/// * `wallet_handle` - wallet handle returned by indy_open_wallet
+/// * `credentials` - Wallet credentials json used to open the wallet
+///   {
+///       "key": string,
+///       "storage_credentials": Optional<object>  List of supported keys are defined by wallet type.
+///       "key_derivation_method": Optional<string>
+///   }
+pub fn rotate_wallet_keys(wallet_handle: WalletHandle, credentials: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _rotate_wallet_keys(command_handle, wallet_handle, credentials, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _rotate_wallet_keys(command_handle: CommandHandle, wallet_handle: WalletHandle, credentials: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let credentials = c_str!(credentials);
+
+    ErrorCode::from(unsafe {
+      wallet::indy_rotate_wallet_keys(command_handle, wallet_handle, credentials.as_ptr(), cb)
+    })
+}
+
+/// Checks integrity of opened wallet
+///
+/// Every record value and tags are decrypted with the current wallet keys
+/// and storage is checked for tags that don't belong to any record.
+///
+/// # Arguments:
+/// * `wallet_handle` - wallet handle returned by indy_open_wallet
+/// * `repair` - remove unreadable records and orphaned tags
+///
+/// # Returns
+/// Verification report json
+///   {
+///       "recordsCount": int, total number of records in the wallet
+///       "unreadableRecords": int, number of records that can't be decrypted with the current wallet keys
+///       "orphanedTags": int, number of tags that don't belong to any record,
+///       null if the storage can't check for orphaned tags (plugged storage)
+///       "repaired": bool, true if unreadable records or orphaned tags were removed
+///   }
+pub fn verify_wallet(wallet_handle: WalletHandle, repair: bool) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _verify_wallet(command_handle, wallet_handle, repair, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _verify_wallet(command_handle: CommandHandle, wallet_handle: WalletHandle, repair: bool, cb: Option<ResponseStringCB>) -> ErrorCode {
+    ErrorCode::from(unsafe {
+      wallet::indy_verify_wallet(command_handle, wallet_handle, repair, cb)
+    })
+}
+
 /// Creates a new secure wallet with the given unique name and then imports its content
 /// according to fields provided in import_config
 /// This can be seen as an create call with additional content import
@@ -274,6 +367,41 @@ fn _import_wallet(command_handle: CommandHandle, config: &str, credentials: &str
     })
 }
 
+/// Creates a new secure wallet and then imports its content reporting progress of the operation.
+///
+/// If "resume" field of import_config is true and the wallet already exists
+/// import continues into it skipping already imported records.
+///
+/// # Arguments
+/// * `config` - Wallet configuration json (see import_wallet).
+/// * `credentials` - Wallet credentials json (see import_wallet).
+/// * `import_config` - JSON containing settings for input operation.
+///   {
+///     "path": path of the file that contains exported wallet content
+///     "key": passphrase used to derive export key
+///     "resume": Optional<bool> continue interrupted import into the existing wallet
+///   }
+/// * `progress_cb` - callback called after every 100 imported records and on completion
+///   with the number of already imported records.
+///   Returning of any error code except Success cancels the import.
+pub fn import_wallet_with_progress(config: &str, credentials: &str, import_config: &str, progress_cb: Option<wallet::WalletProgressCB>) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _import_wallet_with_progress(command_handle, config, credentials, import_config, progress_cb, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _import_wallet_with_progress(command_handle: CommandHandle, config: &str, credentials: &str, import_config: &str, progress_cb: Option<wallet::WalletProgressCB>, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let config = c_str!(config);
+    let credentials = c_str!(credentials);
+    let import_config = c_str!(import_config);
+
+    ErrorCode::from(unsafe {
+      wallet::indy_import_wallet_with_progress(command_handle, config.as_ptr(), credentials.as_ptr(), import_config.as_ptr(), progress_cb, cb)
+    })
+}
+
 /// Deletes created wallet.
 pub fn delete_wallet(config: &str, credentials: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
     let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
@@ -292,6 +420,38 @@ fn _delete_wallet(command_handle: CommandHandle, config: &str, credentials: &str
     })
 }
 
+/// Lists schema migrations that will be applied to the wallet storage on the next opening.
+///
+/// Wallet storage isn't modified and the wallet isn't opened by this call.
+/// Only 'default' storage type has versioned schema. Other storage types return empty list.
+///
+/// # Arguments
+/// * `config` - Wallet configuration json (see open_wallet).
+/// * `credentials` - Wallet credentials json (see open_wallet).
+///
+/// # Returns
+/// Pending migrations json (empty if wallet schema is up to date)
+///   [{
+///       "version": int, schema version the migration brings storage to
+///       "description": string, migration description
+///   }]
+pub fn check_wallet_migrations(config: &str, credentials: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _check_wallet_migrations(command_handle, config, credentials, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _check_wallet_migrations(command_handle: CommandHandle, config: &str, credentials: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let config = c_str!(config);
+    let credentials = c_str!(credentials);
+
+    ErrorCode::from(unsafe {
+      wallet::indy_check_wallet_migrations(command_handle, config.as_ptr(), credentials.as_ptr(), cb)
+    })
+}
+
 /// Closes opened wallet and frees allocated resources.
 ///
 /// # Arguments
@@ -499,6 +659,39 @@ fn _delete_wallet_record(command_handle: CommandHandle, wallet_handle: WalletHan
     })
 }
 
+/// Apply a list of record operations to the wallet in the order they are provided.
+///
+/// The list is applied atomically (all operations or none of them) for the default SQLite storage.
+/// Storages registered with `register_wallet_storage` get a best-effort rollback only, as the plugged storage API
+/// has no transactions: the wallet can be left partially changed unless the plugin makes such changes transactional on its own.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by open_wallet)
+/// * `operations_json` - the list of operations as json:
+///   [
+///     {"op": "add", "type": <record type>, "id": <record id>, "value": <record value>, "tags": (optional) <tags json>},
+///     {"op": "update", "type": <record type>, "id": <record id>, "value": <new record value>},
+///     {"op": "add_tags", "type": <record type>, "id": <record id>, "tags": <tags json>},
+///     {"op": "update_tags", "type": <record type>, "id": <record id>, "tags": <tags json>},
+///     {"op": "delete_tags", "type": <record type>, "id": <record id>, "tag_names": [<tag name>, ...]},
+///     {"op": "delete", "type": <record type>, "id": <record id>},
+///   ]
+pub fn wallet_batch_records(wallet_handle: WalletHandle, operations_json: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _wallet_batch_records(command_handle, wallet_handle, operations_json, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _wallet_batch_records(command_handle: CommandHandle, wallet_handle: WalletHandle, operations_json: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let operations_json = c_str!(operations_json);
+
+    ErrorCode::from(unsafe {
+      non_secrets::indy_wallet_batch_records(command_handle, wallet_handle, operations_json.as_ptr(), cb)
+    })
+}
+
 /// Get an wallet record by id
 ///
 /// # Arguments
@@ -583,6 +776,47 @@ fn _open_wallet_search(command_handle: CommandHandle, wallet_handle: WalletHandl
     })
 }
 
+/// Count wallet records matching the query.
+///
+/// Optionally records can be grouped by the value of an unencrypted tag.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by open_wallet)
+/// * `xtype` - allows to separate different record types collections
+/// * `query_json` - MongoDB style query to wallet record tags (see open_wallet_search)
+/// * `group_by_tag` - (optional) name of unencrypted ("~" prefixed) tag to group records by
+///
+/// # Returns
+/// count json:
+/// {
+///   count: <int>, // count of all records matching the query
+///   groups: { // present only if group_by_tag is set
+///     "<tag value>": <int>, // count of matching records with the tag value
+///   },
+/// }
+pub fn count_wallet_records(wallet_handle: WalletHandle, xtype: &str, query_json: &str, group_by_tag: Option<&str>) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _count_wallet_records(command_handle, wallet_handle, xtype, query_json, group_by_tag, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _count_wallet_records(command_handle: CommandHandle, wallet_handle: WalletHandle, xtype: &str, query_json: &str, group_by_tag: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let xtype = c_str!(xtype);
+    let query_json = c_str!(query_json);
+    let group_by_tag_str = opt_c_str!(group_by_tag);
+
+    ErrorCode::from(unsafe {
+      non_secrets::indy_count_wallet_records(command_handle,
+                                             wallet_handle,
+                                             xtype.as_ptr(),
+                                             query_json.as_ptr(),
+                                             opt_c_ptr!(group_by_tag, group_by_tag_str),
+                                             cb)
+    })
+}
+
 /// Fetch next records for wallet search.
 ///
 /// Not if there are no records this call returns WalletNoRecords error.
@@ -635,6 +869,57 @@ fn _close_wallet_search(command_handle: CommandHandle, wallet_search_handle: Sea
     })
 }
 
+/// Fetch a single page of wallet records.
+///
+/// Unlike open_wallet_search this call doesn't keep any state between calls:
+/// it returns an opaque cursor that can be passed to the next call to continue the search.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by open_wallet)
+/// * `xtype` - allows to separate different record types collections
+/// * `query_json` - MongoDB style query to wallet record tags (see open_wallet_search)
+/// * `options_json` - search options (see open_wallet_search)
+/// * `cursor` - cursor returned by the previous call. The first page is returned if not set
+/// * `limit` - maximum count of records in the page. Must be greater than 0
+///
+/// # Returns
+/// wallet records page json:
+/// {
+///   totalCount: <int>, // present only if retrieveTotalCount set to true
+///   records: [{ // present only if retrieveRecords set to true and the page isn't empty
+///       id: "Some id",
+///       type: "Some type", // present only if retrieveType set to true
+///       value: "Some value", // present only if retrieveValue set to true
+///       tags: <tags json>, // present only if retrieveTags set to true
+///   }],
+///   cursor: <str>, // cursor to fetch the next page, null if there are no more records
+/// }
+pub fn search_wallet_records_page(wallet_handle: WalletHandle, xtype: &str, query_json: &str, options_json: &str, cursor: Option<&str>, limit: u32) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _search_wallet_records_page(command_handle, wallet_handle, xtype, query_json, options_json, cursor, limit, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _search_wallet_records_page(command_handle: CommandHandle, wallet_handle: WalletHandle, xtype: &str, query_json: &str, options_json: &str, cursor: Option<&str>, limit: u32, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let xtype = c_str!(xtype);
+    let query_json = c_str!(query_json);
+    let options_json = c_str!(options_json);
+    let cursor_str = opt_c_str!(cursor);
+
+    ErrorCode::from(unsafe {
+      non_secrets::indy_search_wallet_records_page(command_handle,
+                                                   wallet_handle,
+                                                   xtype.as_ptr(),
+                                                   query_json.as_ptr(),
+                                                   options_json.as_ptr(),
+                                                   opt_c_ptr!(cursor, cursor_str),
+                                                   limit,
+                                                   cb)
+    })
+}
+
 fn _default_credentials(credentials: Option<&str>) -> CString {
     match credentials {
         Some(s) => c_str!(s),
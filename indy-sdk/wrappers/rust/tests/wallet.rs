@@ -12,6 +12,7 @@ use indy::wallet;
 use indy::ErrorCode;
 
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 mod utils;
 
@@ -182,6 +183,56 @@ mod test_wallet_export {
         wallet::close_wallet(handle).wait().unwrap();
         wallet::delete_wallet(&config_wallet, DEFAULT_CREDENTIALS).wait().unwrap();
     }
+
+    #[test]
+    fn export_wallet_with_progress() {
+        static EXPORTED_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+        extern fn progress_cb(_command_handle: i32, records_count: u32) -> i32 {
+            EXPORTED_COUNT.store(records_count as usize, Ordering::SeqCst);
+            ErrorCode::Success as i32
+        }
+
+        let config_wallet = wallet_config::new();
+        let (config_export, path, _dir) = wallet_config::export::with_defaults();
+
+        wallet::create_wallet(&config_wallet, DEFAULT_CREDENTIALS).wait().unwrap();
+        let handle = wallet::open_wallet(&config_wallet, DEFAULT_CREDENTIALS).wait().unwrap();
+
+        did::create_and_store_my_did(handle, "{}").wait().unwrap();
+
+        let result = wallet::export_wallet_with_progress(handle, &config_export, Some(progress_cb)).wait();
+
+        assert_eq!((), result.unwrap());
+        assert!(path.exists());
+        assert!(EXPORTED_COUNT.load(Ordering::SeqCst) > 0);
+
+        wallet::close_wallet(handle).wait().unwrap();
+        wallet::delete_wallet(&config_wallet, DEFAULT_CREDENTIALS).wait().unwrap();
+    }
+
+    #[test]
+    fn export_wallet_with_progress_cancelled() {
+        extern fn progress_cb(_command_handle: i32, _records_count: u32) -> i32 {
+            ErrorCode::CommonInvalidState as i32
+        }
+
+        let config_wallet = wallet_config::new();
+        let (config_export, path, _dir) = wallet_config::export::with_defaults();
+
+        wallet::create_wallet(&config_wallet, DEFAULT_CREDENTIALS).wait().unwrap();
+        let handle = wallet::open_wallet(&config_wallet, DEFAULT_CREDENTIALS).wait().unwrap();
+
+        did::create_and_store_my_did(handle, "{}").wait().unwrap();
+
+        let result = wallet::export_wallet_with_progress(handle, &config_export, Some(progress_cb)).wait();
+
+        assert_eq!(ErrorCode::CommonInvalidState, result.unwrap_err().error_code);
+        assert!(!path.exists());
+
+        wallet::close_wallet(handle).wait().unwrap();
+        wallet::delete_wallet(&config_wallet, DEFAULT_CREDENTIALS).wait().unwrap();
+    }
 }
 
 #[cfg(test)]
@@ -235,4 +286,90 @@ mod test_wallet_import {
         wallet::close_wallet(handle).wait().unwrap();
         wallet::delete_wallet(&config_wallet, DEFAULT_CREDENTIALS).wait().unwrap();
     }
+
+    #[test]
+    fn import_wallet_with_progress() {
+        static IMPORTED_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+        extern fn progress_cb(_command_handle: i32, records_count: u32) -> i32 {
+            IMPORTED_COUNT.store(records_count as usize, Ordering::SeqCst);
+            ErrorCode::Success as i32
+        }
+
+        let config_wallet = wallet_config::new();
+        let (config_export, _path, _dir) = wallet_config::export::with_defaults();
+        let (did, did_with_metadata) = setup_exported_wallet(
+            &config_wallet,
+            DEFAULT_CREDENTIALS,
+            &config_export
+        );
+
+        let result = wallet::import_wallet_with_progress(
+            &config_wallet,
+            DEFAULT_CREDENTIALS,
+            &config_export,
+            Some(progress_cb)
+        ).wait();
+
+        assert_eq!((), result.unwrap());
+        assert!(IMPORTED_COUNT.load(Ordering::SeqCst) > 0);
+
+        let handle = wallet::open_wallet(&config_wallet, DEFAULT_CREDENTIALS).wait().unwrap();
+
+        let imported_did_with_metadata = did::get_did_metadata(handle, &did).wait().unwrap();
+
+        assert_eq!(did_with_metadata, imported_did_with_metadata);
+
+        wallet::close_wallet(handle).wait().unwrap();
+        wallet::delete_wallet(&config_wallet, DEFAULT_CREDENTIALS).wait().unwrap();
+    }
+
+    #[test]
+    fn import_wallet_with_progress_resume() {
+        extern fn cancel_cb(_command_handle: i32, _records_count: u32) -> i32 {
+            ErrorCode::CommonInvalidState as i32
+        }
+
+        let config_wallet = wallet_config::new();
+        let (config_export, path, _dir) = wallet_config::export::with_defaults();
+        let (did, did_with_metadata) = setup_exported_wallet(
+            &config_wallet,
+            DEFAULT_CREDENTIALS,
+            &config_export
+        );
+
+        // Cancelled import keeps the wallet with already imported records
+        let result = wallet::import_wallet_with_progress(
+            &config_wallet,
+            DEFAULT_CREDENTIALS,
+            &config_export,
+            Some(cancel_cb)
+        ).wait();
+
+        assert_eq!(ErrorCode::CommonInvalidState, result.unwrap_err().error_code);
+
+        let config_resume = json!({
+            "path": path,
+            "key": EXPORT_KEY,
+            "resume": true
+        }).to_string();
+
+        let result = wallet::import_wallet_with_progress(
+            &config_wallet,
+            DEFAULT_CREDENTIALS,
+            &config_resume,
+            None
+        ).wait();
+
+        assert_eq!((), result.unwrap());
+
+        let handle = wallet::open_wallet(&config_wallet, DEFAULT_CREDENTIALS).wait().unwrap();
+
+        let imported_did_with_metadata = did::get_did_metadata(handle, &did).wait().unwrap();
+
+        assert_eq!(did_with_metadata, imported_did_with_metadata);
+
+        wallet::close_wallet(handle).wait().unwrap();
+        wallet::delete_wallet(&config_wallet, DEFAULT_CREDENTIALS).wait().unwrap();
+    }
 }